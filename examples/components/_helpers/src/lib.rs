@@ -1,2 +1,5 @@
 pub mod bindings;
+pub mod service;
+pub mod store;
 pub mod trigger;
+pub mod typed;
@@ -0,0 +1,11 @@
+// Helpers for reading the service definition returned by `host::get_service`
+use crate::bindings::world::wavs::types::service::ComponentSource;
+
+/// The digest of the component bytes, whichever way the component is sourced
+pub fn component_digest(source: &ComponentSource) -> String {
+    match source {
+        ComponentSource::Download(download) => download.digest.clone(),
+        ComponentSource::Registry(registry) => registry.digest.clone(),
+        ComponentSource::Digest(digest) => digest.clone(),
+    }
+}
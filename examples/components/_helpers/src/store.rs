@@ -0,0 +1,48 @@
+//! Helpers over `wasi:keyvalue/store`
+
+use crate::bindings::world::wasi::keyvalue::store::{Bucket, Error, KeyResponse};
+
+/// Every key in the bucket, following `list-keys` cursors until the host has no more pages.
+/// Pages are only fetched as the iterator reaches them, and it ends after the first error
+pub fn list_all_keys(bucket: &Bucket) -> impl Iterator<Item = Result<String, Error>> + '_ {
+    ListAllKeys {
+        bucket,
+        page: Vec::new().into_iter(),
+        cursor: None,
+        done: false,
+    }
+}
+
+struct ListAllKeys<'a> {
+    bucket: &'a Bucket,
+    page: std::vec::IntoIter<String>,
+    cursor: Option<String>,
+    done: bool,
+}
+
+impl Iterator for ListAllKeys<'_> {
+    type Item = Result<String, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(key) = self.page.next() {
+                return Some(Ok(key));
+            }
+            if self.done {
+                return None;
+            }
+
+            match self.bucket.list_keys(self.cursor.as_deref()) {
+                Ok(KeyResponse { keys, cursor }) => {
+                    self.done = cursor.is_none();
+                    self.cursor = cursor;
+                    self.page = keys.into_iter();
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
@@ -4,14 +4,14 @@ use crate::bindings::world::wavs::{
     types::{
         events::{
             TriggerDataAtprotoEvent, TriggerDataCosmosContractEvent, TriggerDataEvmContractEvent,
-            TriggerDataHypercoreAppend,
+            TriggerDataHypercoreAppend, TriggerDataWebhook,
         },
         service::ServiceManager,
     },
 };
 use alloy_provider::RootProvider;
 use alloy_sol_types::SolValue;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use cosmwasm_std::HexBinary;
 use cw_wavs_mock_api::message_with_id::MessageWithId;
 use example_submit::DataWithId;
@@ -43,61 +43,99 @@ pub fn decode_trigger_event(trigger_data: component_input::TriggerData) -> Resul
             sequence,
             ..
         }) => Ok((
-            sequence.try_into().expect("Expected sequence to be u64"),
-            record_data
-                .expect("Record data was not provided")
-                .as_bytes()
-                .to_vec(),
+            sequence
+                .try_into()
+                .context("atproto sequence must not be negative")?,
+            // deletes don't carry a record
+            record_data.map(String::into_bytes).unwrap_or_default(),
         )),
         component_input::TriggerData::HypercoreAppend(TriggerDataHypercoreAppend {
             index,
             data,
             ..
         }) => Ok((index, data)),
+        component_input::TriggerData::Webhook(TriggerDataWebhook { body, .. }) => Ok((0, body)),
         _ => Err(anyhow::anyhow!("Unsupported trigger data type")),
     }
 }
 
+/// Shorthand for a [`TriggerResponseBuilder`] with just a payload
 pub fn encode_trigger_output(
     trigger_id: u64,
     output: impl AsRef<[u8]>,
     service_manager: ServiceManager,
 ) -> component_output::WasmResponse {
-    match service_manager {
-        ServiceManager::Evm(_) => evm_encode_trigger_output(trigger_id, output),
-        ServiceManager::Cosmos(_) => cosmos_encode_trigger_output(trigger_id, output),
-    }
+    TriggerResponseBuilder::new(trigger_id)
+        .payload(output)
+        .build(service_manager)
 }
-// For EVM ServiceHandler contracts, encode output using DataWithId struct
-fn evm_encode_trigger_output(
+
+/// Builds the response to a trigger, with the payload encoded the way the service manager's
+/// chain expects it: `DataWithId` for EVM service handlers and `MessageWithId` for Cosmos ones.
+///
+/// ```ignore
+/// TriggerResponseBuilder::new(trigger_id)
+///     .payload(output)
+///     .ordering(1)
+///     .salt(b"second response")
+///     .build(host::get_service().service.manager)
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TriggerResponseBuilder {
     trigger_id: u64,
-    output: impl AsRef<[u8]>,
-) -> component_output::WasmResponse {
-    component_output::WasmResponse {
-        payload: DataWithId {
-            triggerId: trigger_id,
-            data: output.as_ref().to_vec().into(),
+    payload: Vec<u8>,
+    ordering: Option<u64>,
+    salt: Option<Vec<u8>>,
+}
+
+impl TriggerResponseBuilder {
+    pub fn new(trigger_id: u64) -> Self {
+        Self {
+            trigger_id,
+            ..Default::default()
         }
-        .abi_encode(),
-        ordering: None,
-        event_id_salt: None,
     }
-}
 
-// For Cosmos ServiceHandler contracts, encode output using MessageWithId struct
-fn cosmos_encode_trigger_output(
-    trigger_id: u64,
-    output: impl AsRef<[u8]>,
-) -> component_output::WasmResponse {
-    component_output::WasmResponse {
-        payload: MessageWithId {
-            trigger_id: cosmwasm_std::Uint64::from(trigger_id),
-            message: cosmwasm_std::HexBinary::from(output.as_ref().to_vec()),
+    /// The data submitted along with the trigger id, empty if not set
+    pub fn payload(mut self, payload: impl AsRef<[u8]>) -> Self {
+        self.payload = payload.as_ref().to_vec();
+        self
+    }
+
+    /// Orders this response among the others for the same trigger
+    pub fn ordering(mut self, ordering: u64) -> Self {
+        self.ordering = Some(ordering);
+        self
+    }
+
+    /// Mixed into the event id, so several responses to the same trigger get distinct ids.
+    /// If not set, the event id is derived from the trigger data alone
+    pub fn salt(mut self, salt: impl AsRef<[u8]>) -> Self {
+        self.salt = Some(salt.as_ref().to_vec());
+        self
+    }
+
+    pub fn build(self, service_manager: ServiceManager) -> component_output::WasmResponse {
+        let payload = match service_manager {
+            ServiceManager::Evm(_) => DataWithId {
+                triggerId: self.trigger_id,
+                data: self.payload.into(),
+            }
+            .abi_encode(),
+            ServiceManager::Cosmos(_) => MessageWithId {
+                trigger_id: cosmwasm_std::Uint64::from(self.trigger_id),
+                message: cosmwasm_std::HexBinary::from(self.payload),
+            }
+            .to_bytes()
+            // plain data, encoding can't fail
+            .unwrap(),
+        };
+
+        component_output::WasmResponse {
+            payload,
+            ordering: self.ordering,
+            event_id_salt: self.salt,
         }
-        .to_bytes()
-        .unwrap(),
-        ordering: None,
-        event_id_salt: None,
     }
 }
 
@@ -145,6 +183,90 @@ impl ChainQuerierExt for RootProvider {
     }
 }
 
+#[cfg(test)]
+mod test {
+    use crate::bindings::world::wavs::types::{
+        chain::{CosmosAddress, EvmAddress},
+        service::{CosmosManager, EvmManager},
+    };
+
+    use super::*;
+
+    fn evm_manager() -> ServiceManager {
+        ServiceManager::Evm(EvmManager {
+            chain: "evm:31337".to_string(),
+            address: EvmAddress {
+                raw_bytes: vec![1; 20],
+            },
+        })
+    }
+
+    fn cosmos_manager() -> ServiceManager {
+        ServiceManager::Cosmos(CosmosManager {
+            chain: "cosmos:wasmd".to_string(),
+            address: CosmosAddress {
+                bech32_addr: "wasm1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq".to_string(),
+                prefix_len: 4,
+            },
+        })
+    }
+
+    #[test]
+    fn payload_only() {
+        let resp = TriggerResponseBuilder::new(7)
+            .payload(b"hello")
+            .build(evm_manager());
+
+        let data = DataWithId::abi_decode(&resp.payload).unwrap();
+        assert_eq!(data.triggerId, 7);
+        assert_eq!(data.data.as_ref(), b"hello");
+        assert_eq!(resp.ordering, None);
+        assert_eq!(resp.event_id_salt, None);
+
+        // same as the shorthand
+        let shorthand = encode_trigger_output(7, b"hello", evm_manager());
+        assert_eq!(shorthand.payload, resp.payload);
+        assert_eq!(shorthand.ordering, None);
+        assert_eq!(shorthand.event_id_salt, None);
+
+        let resp = TriggerResponseBuilder::new(7)
+            .payload(b"hello")
+            .build(cosmos_manager());
+
+        let message = MessageWithId::from_bytes(&resp.payload).unwrap();
+        assert_eq!(message.trigger_id.u64(), 7);
+        assert_eq!(message.message.as_slice(), b"hello");
+    }
+
+    #[test]
+    fn ordering_and_salt() {
+        let resp = TriggerResponseBuilder::new(7)
+            .payload(b"hello")
+            .ordering(2)
+            .build(evm_manager());
+        assert_eq!(resp.ordering, Some(2));
+        assert_eq!(resp.event_id_salt, None);
+
+        let resp = TriggerResponseBuilder::new(7)
+            .payload(b"hello")
+            .salt(b"second")
+            .build(cosmos_manager());
+        assert_eq!(resp.ordering, None);
+        assert_eq!(resp.event_id_salt, Some(b"second".to_vec()));
+
+        let resp = TriggerResponseBuilder::new(7)
+            .ordering(1)
+            .salt(b"first")
+            .build(evm_manager());
+        assert_eq!(resp.ordering, Some(1));
+        assert_eq!(resp.event_id_salt, Some(b"first".to_vec()));
+        // the payload still carries the trigger id, even without data
+        let data = DataWithId::abi_decode(&resp.payload).unwrap();
+        assert_eq!(data.triggerId, 7);
+        assert!(data.data.is_empty());
+    }
+}
+
 mod example_trigger {
     use alloy_sol_macro::sol;
     pub use ISimpleTrigger::TriggerInfo;
@@ -0,0 +1,58 @@
+//! Components that take a json request and return a json response.
+//!
+//! Implement [`TypedComponent`] and call [`export_typed_component!`](crate::export_typed_component)
+//! instead of implementing `Guest` by hand, the trigger decoding, (de)serialization,
+//! output encoding and error mapping are all handled here.
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    bindings::world::{host, TriggerAction, WasmResponse},
+    trigger::{decode_trigger_event, encode_trigger_output},
+};
+
+pub trait TypedComponent {
+    type Req: DeserializeOwned;
+    type Resp: Serialize;
+    type Error: std::fmt::Display;
+
+    fn handle(req: Self::Req) -> Result<Self::Resp, Self::Error>;
+}
+
+/// Decodes the trigger into `C::Req`, runs `C::handle` and encodes `C::Resp` for submission
+pub fn run_typed<C: TypedComponent>(
+    trigger_action: TriggerAction,
+) -> Result<Vec<WasmResponse>, String> {
+    let (trigger_id, req) = decode_trigger_event(trigger_action.data).map_err(|e| e.to_string())?;
+
+    let req: C::Req =
+        serde_json::from_slice(&req).map_err(|e| format!("Failed to parse request: {e}"))?;
+
+    let resp = C::handle(req).map_err(|e| e.to_string())?;
+
+    let resp =
+        serde_json::to_vec(&resp).map_err(|e| format!("Failed to serialize response: {e}"))?;
+
+    Ok(vec![encode_trigger_output(
+        trigger_id,
+        resp,
+        host::get_service().service.manager,
+    )])
+}
+
+/// Implements `Guest` for a [`TypedComponent`] and exports it.
+/// Like `export_layer_trigger_world!`, the type must be named `Component`
+#[macro_export]
+macro_rules! export_typed_component {
+    ($Component:ty) => {
+        impl $crate::bindings::world::Guest for $Component {
+            fn run(
+                trigger_action: $crate::bindings::world::TriggerAction,
+            ) -> std::result::Result<Vec<$crate::bindings::world::WasmResponse>, String> {
+                $crate::typed::run_typed::<$Component>(trigger_action)
+            }
+        }
+
+        $crate::export_layer_trigger_world!($Component);
+    };
+}
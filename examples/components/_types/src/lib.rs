@@ -13,14 +13,33 @@ pub struct ChainTriggerLookupResponse {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum CosmosQueryRequest {
-    BlockHeight { chain: String },
-    Balance { chain: String, address: Address },
+    BlockHeight {
+        chain: String,
+    },
+    Balance {
+        chain: String,
+        address: Address,
+    },
+    /// CosmWasm smart query, `msg` is sent to the contract as-is
+    SmartQuery {
+        chain: String,
+        contract: Address,
+        msg: serde_json::Value,
+    },
 }
 
 impl CosmosQueryRequest {
     pub fn to_vec(&self) -> Vec<u8> {
         serde_json::to_vec(self).unwrap()
     }
+
+    pub fn chain(&self) -> &str {
+        match self {
+            Self::BlockHeight { chain }
+            | Self::Balance { chain, .. }
+            | Self::SmartQuery { chain, .. } => chain,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -28,6 +47,8 @@ impl CosmosQueryRequest {
 pub enum CosmosQueryResponse {
     BlockHeight(u64),
     Balance(String),
+    /// The contract's JSON response
+    SmartQuery(Vec<u8>),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -37,6 +58,9 @@ pub enum KvStoreRequest {
         bucket: String,
         key: String,
         value: Vec<u8>,
+        /// Expire the key after this many seconds, reads after that fail with `MissingKey`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ttl_secs: Option<u64>,
     },
     Read {
         bucket: String,
@@ -68,10 +92,20 @@ pub enum KvStoreRequest {
         bucket: String,
         keys: Vec<String>,
     },
+    /// Swaps every `(key, expected, value)` entry as a unit, only if each key currently
+    /// holds `expected` (`None` meaning the key must not exist)
+    BatchCompareSwap {
+        bucket: String,
+        entries: Vec<(String, Option<Vec<u8>>, Vec<u8>)>,
+    },
     ListKeys {
         bucket: String,
         cursor: Option<String>,
     },
+    /// Every key in the bucket, however many pages the host splits them into
+    ListAllKeys {
+        bucket: String,
+    },
 }
 
 impl KvStoreRequest {
@@ -100,10 +134,14 @@ pub enum KvStoreResponse {
     },
     BatchWrite,
     BatchDelete,
+    BatchCompareSwap,
     ListKeys {
         keys: Vec<String>,
         cursor: Option<String>,
     },
+    ListAllKeys {
+        keys: Vec<String>,
+    },
 }
 
 #[derive(Error, Debug)]
@@ -159,6 +197,10 @@ pub enum KvStoreError {
     BatchWrite { bucket: String, reason: String },
     #[error("Failed to perform batch delete for bucket {bucket}, {reason}")]
     BatchDelete { bucket: String, reason: String },
+    #[error("Batch compare-and-swap for bucket {bucket} failed, key {key} did not hold the expected value")]
+    BatchCompareSwapMismatch { bucket: String, key: String },
+    #[error("Failed to perform batch compare-and-swap for bucket {bucket}, {reason}")]
+    BatchCompareSwap { bucket: String, reason: String },
     #[error("Failed to list keys for bucket {bucket}, cursor: {cursor:?}: {reason}")]
     ListKeys {
         bucket: String,
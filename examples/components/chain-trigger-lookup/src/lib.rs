@@ -1,6 +1,4 @@
 use alloy_network::Ethereum;
-use alloy_primitives::BlockHash;
-use alloy_provider::Provider;
 
 use anyhow::Context;
 use example_helpers::bindings::world::{
@@ -10,7 +8,10 @@ use example_helpers::bindings::world::{
             input::{TriggerAction, TriggerData},
             output::WasmResponse,
         },
-        types::events::{TriggerDataCosmosContractEvent, TriggerDataEvmContractEvent},
+        types::{
+            chain::EvmBlockId,
+            events::{TriggerDataCosmosContractEvent, TriggerDataEvmContractEvent},
+        },
     },
     Guest,
 };
@@ -47,26 +48,28 @@ impl Guest for Component {
                     let chain_config = host::get_evm_chain_config(&chain)
                         .ok_or(anyhow::anyhow!("EVM chain config for {chain} not found"))?;
 
-                    let provider = new_evm_provider::<Ethereum>(
-                        chain_config
-                            .http_endpoint
-                            .context("http_endpoint is missing")?,
-                    );
-
-                    let block_timestamp = provider
-                        .get_block(BlockHash::from_slice(&log.block_hash).into())
-                        .await?
-                        .ok_or(anyhow::anyhow!("Unable to get block"))?
-                        .header
-                        .timestamp;
+                    let block_timestamp =
+                        host::get_evm_block(&chain, &EvmBlockId::Hash(log.block_hash.clone()))
+                            .ok_or(anyhow::anyhow!("Unable to get block"))?
+                            .timestamp;
 
                     if block_timestamp == 0 {
                         return Err(anyhow::anyhow!("Block timestamp is zero, invalid block"));
                     }
 
-                    host::log(
+                    host::log_structured(
                         LogLevel::Info,
-                        &format!("Block timestamp: {block_timestamp}"),
+                        "Found trigger block",
+                        &[
+                            ("chain".to_string(), chain.clone()),
+                            ("block_timestamp".to_string(), block_timestamp.to_string()),
+                        ],
+                    );
+
+                    let provider = new_evm_provider::<Ethereum>(
+                        chain_config
+                            .http_endpoint
+                            .context("http_endpoint is missing")?,
                     );
 
                     provider
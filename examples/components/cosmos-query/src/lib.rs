@@ -1,63 +1,199 @@
-use anyhow::anyhow;
-use example_helpers::bindings::world::{host, Guest, TriggerAction, WasmResponse};
-use example_helpers::{
-    export_layer_trigger_world,
-    trigger::{decode_trigger_event, encode_trigger_output},
-};
+use anyhow::{anyhow, Result};
+use example_helpers::bindings::world::host;
+use example_helpers::export_typed_component;
+use example_helpers::typed::TypedComponent;
+use layer_climb::prelude::Address;
+use layer_climb::querier::QueryClient;
 use wstd::runtime::block_on;
 
 use example_types::{CosmosQueryRequest, CosmosQueryResponse};
 
 struct Component;
 
-impl Guest for Component {
-    fn run(trigger_action: TriggerAction) -> std::result::Result<Vec<WasmResponse>, String> {
+impl TypedComponent for Component {
+    type Req = CosmosQueryRequest;
+    type Resp = CosmosQueryResponse;
+    type Error = anyhow::Error;
+
+    fn handle(req: CosmosQueryRequest) -> Result<CosmosQueryResponse> {
         block_on(async move {
-            let (trigger_id, req) = decode_trigger_event(trigger_action.data)?;
+            let querier = querier(req.chain()).await?;
+            query(&querier, req).await
+        })
+    }
+}
+
+/// The chain queries the component makes, so they can be answered without a node in tests
+trait Querier {
+    async fn block_height(&self) -> Result<u64>;
 
-            let req: CosmosQueryRequest =
-                serde_json::from_slice(&req).map_err(|e| anyhow!("{:?}", e))?;
+    async fn balance(&self, address: Address) -> Result<Option<u128>>;
 
-            let resp = match req {
-                CosmosQueryRequest::BlockHeight { chain } => {
-                    let chain_config = host::get_cosmos_chain_config(&chain)
-                        .ok_or(anyhow!("chain config for {chain} not found"))?;
+    async fn smart_query(&self, contract: &Address, msg: &serde_json::Value) -> Result<Vec<u8>>;
+}
 
-                    let querier =
-                        layer_climb::querier::QueryClient::new(chain_config.into(), None).await?;
+impl Querier for QueryClient {
+    async fn block_height(&self) -> Result<u64> {
+        QueryClient::block_height(self).await
+    }
 
-                    querier
-                        .block_height()
-                        .await
-                        .map(CosmosQueryResponse::BlockHeight)
-                }
+    async fn balance(&self, address: Address) -> Result<Option<u128>> {
+        QueryClient::balance(self, address, None).await
+    }
 
-                CosmosQueryRequest::Balance { chain, address } => {
-                    let chain_config = host::get_cosmos_chain_config(&chain)
-                        .ok_or(anyhow!("chain config for {chain} not found"))?;
+    async fn smart_query(&self, contract: &Address, msg: &serde_json::Value) -> Result<Vec<u8>> {
+        let response: serde_json::Value = self.contract_smart(contract, msg).await?;
+        Ok(serde_json::to_vec(&response)?)
+    }
+}
 
-                    let querier =
-                        layer_climb::querier::QueryClient::new(chain_config.into(), None).await?;
+async fn query(querier: &impl Querier, req: CosmosQueryRequest) -> Result<CosmosQueryResponse> {
+    match req {
+        CosmosQueryRequest::BlockHeight { .. } => querier
+            .block_height()
+            .await
+            .map(CosmosQueryResponse::BlockHeight),
 
-                    querier
-                        .balance(address, None)
-                        .await
-                        .map(|amount| match amount {
-                            Some(amount) => CosmosQueryResponse::Balance(amount.to_string()),
-                            None => CosmosQueryResponse::Balance("0".to_string()),
-                        })
-                }
-            }?;
+        CosmosQueryRequest::Balance { address, .. } => {
+            querier.balance(address).await.map(|amount| match amount {
+                Some(amount) => CosmosQueryResponse::Balance(amount.to_string()),
+                None => CosmosQueryResponse::Balance("0".to_string()),
+            })
+        }
 
-            serde_json::to_vec(&resp)
-                .map_err(|e| anyhow!("{:?}", e))
-                .map(|output| {
-                    encode_trigger_output(trigger_id, output, host::get_service().service.manager)
-                })
-        })
-        .map_err(|e| e.to_string())
-        .map(|x| vec![x])
+        CosmosQueryRequest::SmartQuery { contract, msg, .. } => querier
+            .smart_query(&contract, &msg)
+            .await
+            .map(CosmosQueryResponse::SmartQuery),
     }
 }
 
-export_layer_trigger_world!(Component);
+async fn querier(chain: &str) -> Result<QueryClient> {
+    let chain_config = host::get_cosmos_chain_config(chain)
+        .ok_or(anyhow!("chain config for {chain} not found"))?;
+
+    QueryClient::new(chain_config.into(), None).await
+}
+
+export_typed_component!(Component);
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use futures::executor::block_on;
+    use layer_climb::prelude::CosmosAddr;
+    use serde_json::json;
+
+    use super::*;
+
+    /// Answers smart queries for a single contract by message name
+    struct MockQuerier {
+        contract: Address,
+        responses: HashMap<String, serde_json::Value>,
+    }
+
+    impl Querier for MockQuerier {
+        async fn block_height(&self) -> Result<u64> {
+            Ok(42)
+        }
+
+        async fn balance(&self, _address: Address) -> Result<Option<u128>> {
+            Ok(None)
+        }
+
+        async fn smart_query(
+            &self,
+            contract: &Address,
+            msg: &serde_json::Value,
+        ) -> Result<Vec<u8>> {
+            if *contract != self.contract {
+                return Err(anyhow!("no contract at {contract}"));
+            }
+
+            let name = msg
+                .as_object()
+                .and_then(|msg| msg.keys().next())
+                .ok_or_else(|| anyhow!("malformed query {msg}"))?;
+            let response = self
+                .responses
+                .get(name)
+                .ok_or_else(|| anyhow!("unknown query {name}"))?;
+
+            Ok(serde_json::to_vec(response)?)
+        }
+    }
+
+    fn address(name: &str) -> Address {
+        Address::Cosmos(CosmosAddr::new_unchecked(format!("wasm1{name}"), 4))
+    }
+
+    #[test]
+    fn smart_query_returns_contract_json() {
+        let contract = address("contract");
+        let querier = MockQuerier {
+            contract: contract.clone(),
+            responses: HashMap::from([(
+                "config".to_string(),
+                json!({ "owner": "wasm1owner", "threshold": 3 }),
+            )]),
+        };
+
+        let response = block_on(query(
+            &querier,
+            CosmosQueryRequest::SmartQuery {
+                chain: "cosmos:wasmd".to_string(),
+                contract: contract.clone(),
+                msg: json!({ "config": {} }),
+            },
+        ))
+        .unwrap();
+
+        let CosmosQueryResponse::SmartQuery(bytes) = response else {
+            panic!("unexpected response {response:?}");
+        };
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json, json!({ "owner": "wasm1owner", "threshold": 3 }));
+
+        // errors from the contract are passed through
+        block_on(query(
+            &querier,
+            CosmosQueryRequest::SmartQuery {
+                chain: "cosmos:wasmd".to_string(),
+                contract,
+                msg: json!({ "unknown": {} }),
+            },
+        ))
+        .unwrap_err();
+    }
+
+    #[test]
+    fn existing_queries_still_routed() {
+        let querier = MockQuerier {
+            contract: address("contract"),
+            responses: HashMap::new(),
+        };
+
+        assert_eq!(
+            block_on(query(
+                &querier,
+                CosmosQueryRequest::BlockHeight {
+                    chain: "cosmos:wasmd".to_string()
+                }
+            ))
+            .unwrap(),
+            CosmosQueryResponse::BlockHeight(42)
+        );
+        assert_eq!(
+            block_on(query(
+                &querier,
+                CosmosQueryRequest::Balance {
+                    chain: "cosmos:wasmd".to_string(),
+                    address: address("holder"),
+                }
+            ))
+            .unwrap(),
+            CosmosQueryResponse::Balance("0".to_string())
+        );
+    }
+}
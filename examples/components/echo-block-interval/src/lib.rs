@@ -48,6 +48,11 @@ fn inner_run_task(
     let bucket = store::open("foo")?;
     let count = atomics::increment(&bucket, "bar", 1)?.try_into()?;
 
+    // the trigger is being removed after this, so clean up the counter
+    if data.is_final {
+        bucket.delete("bar")?;
+    }
+
     // TIP: temporarily comment out the `config.start_block.is_none()` condition
     // but leave the `count == 1`
     // and recompile to confirm that the trigger is deleted after the first trigger
@@ -1,5 +1,6 @@
 use example_helpers::bindings::world::{
     host,
+    wasi::keyvalue::{atomics, store},
     wavs::operator::{
         input::{TriggerAction, TriggerData},
         output::WasmResponse,
@@ -44,6 +45,10 @@ impl Guest for Component {
             }
         }
 
+        if let Some(fail_mode) = host::config_var("fail-mode") {
+            inject_failure(&fail_mode)?;
+        }
+
         // Sanity check that we can get the default event id
         if host::get_event_id(None).iter().all(|x| *x == 0) {
             return Err("event id is all zeros".to_string());
@@ -59,6 +64,7 @@ impl Guest for Component {
 
                 Ok((Some(trigger_id), data))
             }
+            TriggerData::Webhook(webhook) => Ok((None, webhook.body)),
             TriggerData::Raw(data) => Ok((None, data)),
             _ => Err("expected trigger data".to_string()),
         }?;
@@ -100,6 +106,17 @@ impl Guest for Component {
                 } else {
                     return Err(format!("config var {config_var} not found"));
                 }
+            } else if input_str.contains("secret:") {
+                let name = input_str.split("secret:").nth(1).unwrap();
+                if let Some(value) = host::get_secret(name) {
+                    return Ok(vec![WasmResponse {
+                        payload: value.into_bytes(),
+                        ordering: None,
+                        event_id_salt: None,
+                    }]);
+                } else {
+                    return Err(format!("secret {name} not found"));
+                }
             } else if input_str == "custom-event-id" {
                 return Ok(vec![WasmResponse {
                     payload: Vec::new(),
@@ -170,4 +187,38 @@ impl Guest for Component {
     }
 }
 
+/// Fails the execution as configured by `fail-mode`, for exercising retry and error paths:
+/// `panic` traps, `err` returns an error, and `nth:N` returns an error on the Nth invocation only.
+/// Invocations are counted in the service's keyvalue store so the count survives across executions
+fn inject_failure(fail_mode: &str) -> Result<(), String> {
+    const BUCKET: &str = "echo-data";
+    const COUNTER_KEY: &str = "fail-mode-invocations";
+
+    match fail_mode {
+        "panic" => panic!("fail-mode is 'panic'"),
+        "err" => Err("fail-mode is 'err'".to_string()),
+        _ => {
+            let n = fail_mode
+                .strip_prefix("nth:")
+                .ok_or_else(|| {
+                    format!("invalid fail-mode {fail_mode:?}, must be 'panic', 'err', or 'nth:N'")
+                })?
+                .parse::<i64>()
+                .map_err(|e| format!("invalid fail-mode {fail_mode:?}: {e}"))?;
+
+            let bucket = store::open(BUCKET).map_err(|e| e.to_string())?;
+            let invocation =
+                atomics::increment(&bucket, COUNTER_KEY, 1).map_err(|e| e.to_string())?;
+
+            if invocation == n {
+                Err(format!(
+                    "fail-mode is 'nth:{n}', failing invocation {invocation}"
+                ))
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
 export_layer_trigger_world!(Component);
@@ -1,76 +1,74 @@
 use std::collections::HashMap;
 
+use example_helpers::bindings::world::host;
 use example_helpers::bindings::world::wasi::keyvalue::store::KeyResponse;
 use example_helpers::bindings::world::wasi::keyvalue::{atomics, batch, store};
-use example_helpers::bindings::world::WasmResponse;
-use example_helpers::trigger::{decode_trigger_event, encode_trigger_output};
-use example_helpers::{
-    bindings::world::{host, Guest, TriggerAction},
-    export_layer_trigger_world,
-};
+use example_helpers::export_typed_component;
+use example_helpers::store::list_all_keys;
+use example_helpers::typed::TypedComponent;
 use example_types::{KvStoreError, KvStoreRequest, KvStoreResponse, KvStoreResult};
 
 struct Component;
 
-impl Guest for Component {
-    fn run(trigger_action: TriggerAction) -> Result<Vec<WasmResponse>, String> {
-        host::log(host::LogLevel::Info, "KV Store component triggered");
+impl TypedComponent for Component {
+    type Req = KvStoreRequest;
+    type Resp = KvStoreResponse;
+    type Error = KvStoreError;
 
-        let (trigger_id, req) =
-            decode_trigger_event(trigger_action.data).map_err(|e| e.to_string())?;
+    fn handle(req: KvStoreRequest) -> KvStoreResult<KvStoreResponse> {
+        host::log(host::LogLevel::Info, "KV Store component triggered");
 
-        let resp = match serde_json::from_slice::<KvStoreRequest>(&req) {
-            Ok(KvStoreRequest::Write { bucket, key, value }) => {
-                write_value(&bucket, &key, &value).map_err(|e| e.to_string())?;
+        Ok(match req {
+            KvStoreRequest::Write {
+                bucket,
+                key,
+                value,
+                ttl_secs,
+            } => {
+                match ttl_secs {
+                    Some(ttl_secs) => write_value_with_ttl(&bucket, &key, &value, ttl_secs)?,
+                    None => write_value(&bucket, &key, &value)?,
+                }
                 KvStoreResponse::Write
             }
-            Ok(KvStoreRequest::Read { bucket, key }) => {
-                let value = read_value(&bucket, &key).map_err(|e| e.to_string())?;
-                KvStoreResponse::Read { value }
+            KvStoreRequest::Read { bucket, key } => KvStoreResponse::Read {
+                value: read_value(&bucket, &key)?,
+            },
+            KvStoreRequest::AtomicIncrement { bucket, key, delta } => {
+                KvStoreResponse::AtomicIncrement {
+                    value: atomic_increment(&bucket, &key, delta)?,
+                }
             }
-            Ok(KvStoreRequest::AtomicIncrement { bucket, key, delta }) => {
-                let value = atomic_increment(&bucket, &key, delta).map_err(|e| e.to_string())?;
-                KvStoreResponse::AtomicIncrement { value }
-            }
-            Ok(KvStoreRequest::AtomicSwap { bucket, key, value }) => {
-                atomic_swap(&bucket, &key, &value).map_err(|e| e.to_string())?;
+            KvStoreRequest::AtomicSwap { bucket, key, value } => {
+                atomic_swap(&bucket, &key, &value)?;
                 KvStoreResponse::AtomicSwap
             }
-            Ok(KvStoreRequest::AtomicRead { bucket, key }) => {
-                let value = atomic_read(&bucket, &key).map_err(|e| e.to_string())?;
-                KvStoreResponse::AtomicRead { value }
-            }
-            Ok(KvStoreRequest::BatchRead { bucket, keys }) => {
-                let values = batch_read(&bucket, &keys).map_err(|e| e.to_string())?;
-                KvStoreResponse::BatchRead { values }
-            }
-            Ok(KvStoreRequest::BatchWrite { bucket, values }) => {
-                batch_write(&bucket, values).map_err(|e| e.to_string())?;
+            KvStoreRequest::AtomicRead { bucket, key } => KvStoreResponse::AtomicRead {
+                value: atomic_read(&bucket, &key)?,
+            },
+            KvStoreRequest::BatchRead { bucket, keys } => KvStoreResponse::BatchRead {
+                values: batch_read(&bucket, &keys)?,
+            },
+            KvStoreRequest::BatchWrite { bucket, values } => {
+                batch_write(&bucket, values)?;
                 KvStoreResponse::BatchWrite
             }
-            Ok(KvStoreRequest::BatchDelete { bucket, keys }) => {
-                batch_delete(&bucket, &keys).map_err(|e| e.to_string())?;
+            KvStoreRequest::BatchDelete { bucket, keys } => {
+                batch_delete(&bucket, &keys)?;
                 KvStoreResponse::BatchDelete
             }
-            Ok(KvStoreRequest::ListKeys { bucket, cursor }) => {
-                let KeyResponse { keys, cursor } =
-                    list_keys(&bucket, cursor.as_deref()).map_err(|e| e.to_string())?;
-                KvStoreResponse::ListKeys { keys, cursor }
+            KvStoreRequest::BatchCompareSwap { bucket, entries } => {
+                batch_compare_swap(&bucket, entries)?;
+                KvStoreResponse::BatchCompareSwap
             }
-
-            Err(e) => {
-                return Err(format!("Failed to parse request: {e}"));
+            KvStoreRequest::ListKeys { bucket, cursor } => {
+                let KeyResponse { keys, cursor } = list_keys(&bucket, cursor.as_deref())?;
+                KvStoreResponse::ListKeys { keys, cursor }
             }
-        };
-
-        let resp_bytes =
-            serde_json::to_vec(&resp).map_err(|e| format!("Failed to serialize response: {e}"))?;
-
-        Ok(vec![encode_trigger_output(
-            trigger_id,
-            resp_bytes,
-            host::get_service().service.manager,
-        )])
+            KvStoreRequest::ListAllKeys { bucket } => KvStoreResponse::ListAllKeys {
+                keys: list_every_key(&bucket)?,
+            },
+        })
     }
 }
 
@@ -114,6 +112,19 @@ fn write_value(bucket_id: &str, key: &str, value: &[u8]) -> KvStoreResult<()> {
     })
 }
 
+fn write_value_with_ttl(
+    bucket_id: &str,
+    key: &str,
+    value: &[u8],
+    ttl_secs: u64,
+) -> KvStoreResult<()> {
+    host::set_with_ttl(bucket_id, key, value, ttl_secs).map_err(|reason| KvStoreError::WriteKey {
+        bucket: bucket_id.to_string(),
+        key: key.to_string(),
+        reason,
+    })
+}
+
 fn atomic_increment(bucket_id: &str, key: &str, delta: i64) -> KvStoreResult<i64> {
     let bucket = open_bucket(bucket_id)?;
     atomics::increment(&bucket, key, delta).map_err(|e| KvStoreError::AtomicIncrement {
@@ -177,6 +188,31 @@ fn batch_delete(bucket_id: &str, keys: &[String]) -> KvStoreResult<()> {
     })
 }
 
+fn batch_compare_swap(
+    bucket_id: &str,
+    entries: Vec<(String, Option<Vec<u8>>, Vec<u8>)>,
+) -> KvStoreResult<()> {
+    let entries = entries
+        .into_iter()
+        .map(|(key, expected, value)| host::CasEntry {
+            key,
+            expected,
+            value,
+        })
+        .collect::<Vec<_>>();
+
+    host::batch_compare_swap(bucket_id, &entries).map_err(|e| match e {
+        host::BatchCasError::Mismatch(key) => KvStoreError::BatchCompareSwapMismatch {
+            bucket: bucket_id.to_string(),
+            key,
+        },
+        host::BatchCasError::Other(reason) => KvStoreError::BatchCompareSwap {
+            bucket: bucket_id.to_string(),
+            reason,
+        },
+    })
+}
+
 fn list_keys(bucket_id: &str, cursor: Option<&str>) -> KvStoreResult<store::KeyResponse> {
     let bucket = open_bucket(bucket_id)?;
 
@@ -189,4 +225,16 @@ fn list_keys(bucket_id: &str, cursor: Option<&str>) -> KvStoreResult<store::KeyR
         })
 }
 
-export_layer_trigger_world!(Component);
+fn list_every_key(bucket_id: &str) -> KvStoreResult<Vec<String>> {
+    let bucket = open_bucket(bucket_id)?;
+
+    list_all_keys(&bucket)
+        .collect::<Result<_, _>>()
+        .map_err(|e| KvStoreError::ListKeys {
+            bucket: bucket_id.to_string(),
+            reason: e.to_string(),
+            cursor: None,
+        })
+}
+
+export_typed_component!(Component);
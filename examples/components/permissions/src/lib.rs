@@ -2,15 +2,13 @@ use example_helpers::bindings::world::{
     host,
     wavs::{
         operator::{input::TriggerAction, output::WasmResponse},
-        types::{
-            core::LogLevel,
-            service::{ComponentSource, ServiceAndWorkflowId},
-        },
+        types::{core::LogLevel, service::ServiceAndWorkflowId},
     },
     Guest,
 };
 
 use example_helpers::export_layer_trigger_world;
+use example_helpers::service::component_digest;
 use example_helpers::trigger::{decode_trigger_event, encode_trigger_output};
 use std::{fs, io::Write, path::Path};
 use wavs_wasi_utils::http::{fetch_json, fetch_string, http_request_get, http_request_post_json};
@@ -99,17 +97,11 @@ async fn inner_run_task(input: PermissionsRequest) -> Result<PermissionsResponse
         })
         .ok_or(anyhow::anyhow!("Failed to find workflow"))?;
 
-    let digest = match workflow.component.source {
-        ComponentSource::Download(component_source_download) => component_source_download.digest,
-        ComponentSource::Registry(registry) => registry.digest,
-        ComponentSource::Digest(digest) => digest,
-    };
-
     Ok(PermissionsResponse {
         filename: response_path.to_path_buf(),
         contents,
         filecount: responses_count,
-        digest,
+        digest: component_digest(&workflow.component.source),
     })
 }
 
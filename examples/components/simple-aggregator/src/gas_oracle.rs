@@ -18,9 +18,23 @@ struct GasOracleResult {
     propose_gas_price: String,
     #[serde(rename = "FastGasPrice")]
     fast_gas_price: String,
+    #[serde(rename = "suggestBaseFee")]
+    suggest_base_fee: Option<String>,
 }
 
-pub fn get_gas_price() -> Result<Option<u128>, String> {
+pub enum GasFees {
+    Legacy {
+        gas_price: u128,
+    },
+    Eip1559 {
+        max_fee_per_gas: u128,
+        max_priority_fee_per_gas: u128,
+    },
+}
+
+/// Set the `gas_fee_type` config var to `eip1559` for EIP-1559 fees,
+/// otherwise a legacy gas price is returned as before
+pub fn get_gas_fees() -> Result<Option<GasFees>, String> {
     let api_key = match std::env::var(ETHERSCAN_API_KEY_ENV) {
         Ok(key) if !key.is_empty() => key,
         _ => return Ok(None),
@@ -58,12 +72,47 @@ pub fn get_gas_price() -> Result<Option<u128>, String> {
         ));
     }
 
-    let gas_price_wei = (gas_price_gwei * 1_000_000_000.0) as u128;
+    let gas_price_wei = gwei_to_wei(gas_price_gwei);
 
     host::log(
         LogLevel::Info,
         &format!("Successfully fetched gas price: {gas_price_gwei} Gwei ({gas_price_wei} Wei)"),
     );
 
-    Ok(Some(gas_price_wei))
+    let fee_type = host::config_var("gas_fee_type").unwrap_or_else(|| "legacy".to_string());
+    if fee_type != "eip1559" {
+        return Ok(Some(GasFees::Legacy {
+            gas_price: gas_price_wei,
+        }));
+    }
+
+    let base_fee_gwei: f64 = response
+        .result
+        .suggest_base_fee
+        .as_deref()
+        .ok_or("Etherscan did not return a base fee")?
+        .parse()
+        .map_err(|e| format!("Invalid base fee from Etherscan: {e}"))?;
+
+    // the oracle's price is base fee plus tip, so the tip is whatever is left over
+    let base_fee_wei = gwei_to_wei(base_fee_gwei);
+    let max_priority_fee_per_gas = gas_price_wei.saturating_sub(base_fee_wei);
+    // leaves room for the base fee to double before the transaction is priced out
+    let max_fee_per_gas = base_fee_wei * 2 + max_priority_fee_per_gas;
+
+    host::log(
+        LogLevel::Info,
+        &format!(
+            "Using EIP-1559 fees: max fee {max_fee_per_gas} Wei, max priority fee {max_priority_fee_per_gas} Wei"
+        ),
+    );
+
+    Ok(Some(GasFees::Eip1559 {
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+    }))
+}
+
+fn gwei_to_wei(gwei: f64) -> u128 {
+    (gwei * 1_000_000_000.0) as u128
 }
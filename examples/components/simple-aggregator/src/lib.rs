@@ -2,15 +2,17 @@ mod gas_oracle;
 mod world;
 
 use example_types::{KvStoreError, KvStoreResult};
+use gas_oracle::GasFees;
 use wavs_types::ChainKey;
 use wavs_wasi_utils::impl_u128_conversions;
 use world::{
     host,
     wavs::aggregator::input::AggregatorInput,
     wavs::aggregator::output::{
-        AggregatorAction, CosmosAddress, CosmosSubmitAction, EvmSubmitAction, SubmitAction, U128,
+        AggregatorAction, CosmosAddress, CosmosSubmitAction, Eip1559Fees, EvmSubmitAction,
+        SubmitAction, SubmitReceipt, U128,
     },
-    wavs::types::chain::{AnyTxHash, EvmAddress},
+    wavs::types::chain::EvmAddress,
     Guest,
 };
 
@@ -35,16 +37,30 @@ impl Guest for Component {
                     .parse()
                     .map_err(|e| format!("Failed to parse service handler address: {e}"))?;
 
-                // Get gas price from Etherscan if configured
+                // Get gas fees from Etherscan if configured
                 // will fail the entire operation if API key is configured but fetching fails
-                let gas_price = gas_oracle::get_gas_price()?;
+                let (gas_price, eip1559) = match gas_oracle::get_gas_fees()? {
+                    Some(GasFees::Legacy { gas_price }) => (Some(gas_price.into()), None),
+                    Some(GasFees::Eip1559 {
+                        max_fee_per_gas,
+                        max_priority_fee_per_gas,
+                    }) => (
+                        None,
+                        Some(Eip1559Fees {
+                            max_fee_per_gas: max_fee_per_gas.into(),
+                            max_priority_fee_per_gas: max_priority_fee_per_gas.into(),
+                        }),
+                    ),
+                    None => (None, None),
+                };
 
                 SubmitAction::Evm(EvmSubmitAction {
                     chain: chain.to_string(),
                     address: EvmAddress {
                         raw_bytes: address.to_vec(),
                     },
-                    gas_price: gas_price.map(|x| x.into()),
+                    gas_price,
+                    eip1559,
                 })
             }
             AnyChainKey::Cosmos(chain) => {
@@ -76,7 +92,7 @@ impl Guest for Component {
 
     fn handle_submit_callback(
         _input: AggregatorInput,
-        tx_result: Result<AnyTxHash, String>,
+        tx_result: Result<SubmitReceipt, String>,
     ) -> Result<(), String> {
         write_kv_value("submit-result", "completed", "true".as_bytes())
             .map_err(|e| e.to_string())?;
@@ -91,6 +107,16 @@ impl Guest for Component {
         )
         .map_err(|e| e.to_string())?;
 
+        // what the last submission actually cost, for tuning the gas oracle
+        if let Ok(SubmitReceipt {
+            gas_used: Some(gas_used),
+            ..
+        }) = &tx_result
+        {
+            write_kv_value("submit-result", "gas-used", gas_used.to_string().as_bytes())
+                .map_err(|e| e.to_string())?;
+        }
+
         Ok(())
     }
 }
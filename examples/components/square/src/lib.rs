@@ -1,29 +1,18 @@
-use example_helpers::bindings::world::{
-    host,
-    wavs::operator::{input::TriggerAction, output::WasmResponse},
-    Guest,
-};
-
-use example_helpers::export_layer_trigger_world;
-use example_helpers::trigger::{decode_trigger_event, encode_trigger_output};
+use example_helpers::export_typed_component;
+use example_helpers::typed::TypedComponent;
 
 use example_types::{SquareRequest, SquareResponse};
 
 struct Component;
 
-impl Guest for Component {
-    fn run(trigger_action: TriggerAction) -> std::result::Result<Vec<WasmResponse>, String> {
-        let (trigger_id, req) =
-            decode_trigger_event(trigger_action.data).map_err(|e| e.to_string())?;
-        let req: SquareRequest = serde_json::from_slice(&req).map_err(|e| e.to_string())?;
-        let y = req.x * req.x;
-        let resp = serde_json::to_vec(&SquareResponse { y }).map_err(|e| e.to_string())?;
-        Ok(vec![encode_trigger_output(
-            trigger_id,
-            resp,
-            host::get_service().service.manager,
-        )])
+impl TypedComponent for Component {
+    type Req = SquareRequest;
+    type Resp = SquareResponse;
+    type Error = String;
+
+    fn handle(req: SquareRequest) -> Result<SquareResponse, String> {
+        Ok(SquareResponse { y: req.x * req.x })
     }
 }
 
-export_layer_trigger_world!(Component);
+export_typed_component!(Component);
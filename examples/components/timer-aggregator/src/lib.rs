@@ -5,8 +5,10 @@ use example_types::{KvStoreError, KvStoreResult};
 use world::{
     host,
     wavs::aggregator::input::AggregatorInput,
-    wavs::aggregator::output::{AggregatorAction, EvmSubmitAction, SubmitAction, TimerAction},
-    wavs::types::chain::{AnyTxHash, EvmAddress},
+    wavs::aggregator::output::{
+        AggregatorAction, EvmSubmitAction, SubmitAction, SubmitReceipt, TimerAction,
+    },
+    wavs::types::chain::EvmAddress,
     wavs::types::core::Duration,
     Guest,
 };
@@ -46,6 +48,7 @@ impl Guest for Component {
                 raw_bytes: address.to_vec(),
             },
             gas_price: None,
+            eip1559: None,
         });
 
         if !utils::is_valid_tx(input.trigger_action.data)? {
@@ -57,7 +60,7 @@ impl Guest for Component {
 
     fn handle_submit_callback(
         _input: AggregatorInput,
-        tx_result: Result<AnyTxHash, String>,
+        tx_result: Result<SubmitReceipt, String>,
     ) -> Result<(), String> {
         write_kv_value("submit-result", "completed", "true".as_bytes())
             .map_err(|e| e.to_string())?;
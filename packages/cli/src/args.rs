@@ -12,7 +12,8 @@ use utils::{
 };
 use wasm_pkg_client::{PackageRef, Version};
 use wavs_types::{
-    AtProtoAction, ChainKey, ComponentDigest, Credential, ServiceStatus, Timestamp, WorkflowId,
+    AtProtoAction, ChainKey, ComponentDigest, Credential, EvmLogField, ServiceId, ServiceStatus,
+    Timestamp, WorkflowId,
 };
 
 use crate::config::Config;
@@ -36,7 +37,7 @@ pub enum Command {
     ///
     /// # Prerequisites
     /// - Core contracts must have been previously deployed via the CLI
-    /// - Service components must already be uploaded
+    /// - Service components must already be uploaded, unless given with `--local-component`
     /// - Operators must already be registered on the contracts
     ///
     /// # Parameters
@@ -50,6 +51,19 @@ pub enum Command {
         #[clap(long)]
         set_uri: bool,
 
+        /// Client-generated id that makes the deploy safe to retry.
+        /// If the node already applied a deploy with this id, nothing is re-executed
+        /// (including setting the service uri on-chain) and the earlier result is returned
+        #[clap(long)]
+        deployment_id: Option<String>,
+
+        /// Local component file for a workflow, in format 'workflow_id=path/to/component.wasm'.
+        /// The component is uploaded to the node (if it isn't there already) and the workflow
+        /// is rewritten to use its digest. The rewritten service is saved to the node and its
+        /// uri set on the service manager, so this requires `--set-uri`
+        #[clap(long)]
+        local_component: Vec<LocalComponent>,
+
         #[clap(flatten)]
         args: CliArgs,
     },
@@ -154,6 +168,128 @@ pub enum Command {
         #[clap(long = "output", short = 'o')]
         output_file: Option<PathBuf>,
     },
+
+    /// Show the execution status of a workflow as tracked by the WAVS node
+    /// Prints success/failure totals and the most recent outcomes
+    Status {
+        /// The ID of the service
+        #[clap(long)]
+        service: ServiceId,
+
+        /// The ID of the workflow
+        #[clap(long)]
+        workflow: WorkflowId,
+
+        /// Maximum number of recent outcomes to show
+        #[clap(long)]
+        limit: Option<usize>,
+
+        #[clap(flatten)]
+        args: CliArgs,
+    },
+
+    /// Follow a service's triggers, executions and submissions on the WAVS node as they happen,
+    /// printed as JSON lines. Reconnects if the connection drops
+    Tail {
+        /// The ID of the service
+        service_id: ServiceId,
+
+        /// Also show the events of the last this many seconds that the node still remembers
+        #[clap(long)]
+        since: Option<u64>,
+
+        #[clap(flatten)]
+        args: CliArgs,
+    },
+
+    /// Pretty-print a saved trigger, e.g. one captured from a node
+    /// The file may hold a JSON or bincode encoded TriggerAction
+    DecodeTrigger {
+        /// Path to the serialized trigger
+        file: PathBuf,
+
+        /// Optional ABI (or compiler artifact) used to decode EVM event data into named fields
+        #[clap(long)]
+        abi: Option<PathBuf>,
+
+        #[clap(flatten)]
+        args: CliArgs,
+    },
+
+    /// Replay a trigger action recorded from a node against a component, offline.
+    /// Components that only depend on their input give the same responses they gave live,
+    /// anything read over http, the time, and keyvalue or secret state can differ
+    Replay {
+        /// Path to the WASI component
+        #[clap(long)]
+        component: String,
+
+        /// Path to the JSON TriggerAction
+        #[clap(long)]
+        trigger: PathBuf,
+
+        /// Path to the JSON service the trigger was run for
+        #[clap(long)]
+        service: PathBuf,
+
+        /// Optional path to save the responses as JSON
+        #[clap(long = "output", short = 'o')]
+        output_file: Option<PathBuf>,
+
+        #[clap(flatten)]
+        args: CliArgs,
+    },
+
+    /// Commands that work on a local component file
+    Component {
+        #[clap(subcommand)]
+        command: ComponentFileCommand,
+
+        #[clap(flatten)]
+        args: CliArgs,
+    },
+
+    /// Print the JSON Schema of a service definition (or one of its parts),
+    /// for editors to validate and autocomplete service files
+    Schema {
+        #[clap(value_enum)]
+        kind: SchemaKind,
+
+        #[clap(flatten)]
+        args: CliArgs,
+    },
+}
+
+/// The types `wavs-cli schema` can print a JSON Schema for
+#[derive(Debug, Clone, Copy, clap::ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaKind {
+    Service,
+    Workflow,
+    Trigger,
+    Submit,
+}
+
+impl SchemaKind {
+    pub fn json_schema(&self) -> serde_json::Value {
+        match self {
+            SchemaKind::Service => wavs_types::json_schema::<wavs_types::Service>(),
+            SchemaKind::Workflow => wavs_types::json_schema::<wavs_types::Workflow>(),
+            SchemaKind::Trigger => wavs_types::json_schema::<wavs_types::Trigger>(),
+            SchemaKind::Submit => wavs_types::json_schema::<wavs_types::Submit>(),
+        }
+    }
+}
+
+/// Commands that work on a local component file
+#[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
+pub enum ComponentFileCommand {
+    /// List the interfaces a component imports, the functions it exports,
+    /// and the config var keys it declares, to check it against a node before deploying
+    Inspect {
+        /// Path to the WASI component
+        component_path: String,
+    },
 }
 
 /// Commands for managing services
@@ -181,7 +317,11 @@ pub enum ServiceCommand {
         status: ServiceStatus,
     },
     /// Validates the service JSON
-    Validate {},
+    /// Exits with a non-zero status if any problems are found
+    Validate {
+        /// Path to the service JSON, defaults to the `--file` path
+        path: Option<PathBuf>,
+    },
 }
 
 /// Commands for managing components
@@ -337,6 +477,11 @@ pub enum TriggerCommand {
         /// The event type to listen for
         #[clap(long)]
         event_type: String,
+
+        /// Only fire for events with this attribute, as `key=value`.
+        /// May be repeated, events must then carry all of them
+        #[clap(long = "attribute")]
+        attributes: Vec<String>,
     },
 
     /// Set an EVM contract event trigger for a workflow
@@ -352,6 +497,16 @@ pub enum TriggerCommand {
         /// The event hash as a hex string (32 bytes)
         #[clap(long)]
         event_hash: String,
+
+        /// Another event from the same contract that also fires the trigger,
+        /// as a hash or signature. May be repeated
+        #[clap(long = "additional-event-hash")]
+        additional_event_hashes: Vec<String>,
+
+        /// A log field to pass the component instead of the whole log data, e.g.
+        /// "topics[1].address" or "data[0]". May be repeated, the fields are concatenated in order
+        #[clap(long)]
+        transform: Vec<EvmLogField>,
     },
 
     /// Set a block interval trigger for a workflow
@@ -420,6 +575,12 @@ impl Command {
             Self::Exec { args, .. } => args,
             Self::Service { args, .. } => args,
             Self::ExecAggregator { args, .. } => args,
+            Self::Status { args, .. } => args,
+            Self::Tail { args, .. } => args,
+            Self::DecodeTrigger { args, .. } => args,
+            Self::Replay { args, .. } => args,
+            Self::Component { args, .. } => args,
+            Self::Schema { args, .. } => args,
         };
 
         args.clone()
@@ -494,6 +655,12 @@ pub struct CliArgs {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub json: Option<bool>,
 
+    /// Log every EVM RPC call with its result and timing, to see exactly which call failed
+    /// Endpoint urls are logged without their path or query, which may hold API keys
+    #[arg(long, visible_alias = "trace", num_args = 0..=1, default_missing_value = "true")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbose: Option<bool>,
+
     /// The IPFS gateway URL used to access IPFS content over HTTP.
     #[arg(long)]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -540,5 +707,27 @@ impl std::str::FromStr for TriggerKind {
     }
 }
 
+/// A workflow's component, read from a local file rather than its configured source
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LocalComponent {
+    pub workflow_id: WorkflowId,
+    pub path: String,
+}
+
+impl std::str::FromStr for LocalComponent {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (workflow_id, path) = s.split_once('=').ok_or_else(|| {
+            anyhow::anyhow!("Expected 'workflow_id=path/to/component.wasm', got '{}'", s)
+        })?;
+
+        Ok(Self {
+            workflow_id: WorkflowId::new(workflow_id)?,
+            path: path.to_string(),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests;
@@ -449,3 +449,132 @@ fn test_config_formats() {
         }
     }
 }
+
+#[test]
+fn test_status_command_parsing() {
+    let service_id = ServiceId::hash(b"service");
+
+    let parsed = Command::try_parse_from([
+        "test",
+        "status",
+        "--service",
+        &service_id.to_string(),
+        "--workflow",
+        "default",
+        "--limit",
+        "5",
+    ])
+    .unwrap();
+
+    match parsed {
+        Command::Status {
+            service,
+            workflow,
+            limit,
+            ..
+        } => {
+            assert_eq!(service, service_id);
+            assert_eq!(workflow, WorkflowId::new("default").unwrap());
+            assert_eq!(limit, Some(5));
+        }
+        _ => panic!("Expected Status command"),
+    }
+
+    // workflow is required
+    assert!(
+        Command::try_parse_from(["test", "status", "--service", &service_id.to_string()]).is_err()
+    );
+}
+
+#[test]
+fn test_decode_trigger_command_parsing() {
+    let parsed = Command::try_parse_from([
+        "test",
+        "decode-trigger",
+        "trigger.json",
+        "--abi",
+        "Token.json",
+    ])
+    .unwrap();
+
+    match parsed {
+        Command::DecodeTrigger { file, abi, .. } => {
+            assert_eq!(file, PathBuf::from("trigger.json"));
+            assert_eq!(abi, Some(PathBuf::from("Token.json")));
+        }
+        _ => panic!("Expected DecodeTrigger command"),
+    }
+
+    // the file is required
+    assert!(Command::try_parse_from(["test", "decode-trigger"]).is_err());
+}
+
+#[test]
+fn test_verbose_flag_parsing() {
+    let verbose = |args: &[&str]| {
+        Command::try_parse_from(args.iter().copied())
+            .unwrap()
+            .args()
+            .verbose
+    };
+
+    assert_eq!(verbose(&["test", "decode-trigger", "trigger.json"]), None);
+    assert_eq!(
+        verbose(&["test", "decode-trigger", "trigger.json", "--verbose"]),
+        Some(true)
+    );
+    assert_eq!(
+        verbose(&["test", "decode-trigger", "trigger.json", "--trace"]),
+        Some(true)
+    );
+    assert_eq!(
+        verbose(&[
+            "test",
+            "decode-trigger",
+            "trigger.json",
+            "--verbose",
+            "false"
+        ]),
+        Some(false)
+    );
+}
+
+#[test]
+fn test_deploy_service_local_components_parsing() {
+    let parsed = Command::try_parse_from(vec![
+        "wavs-cli",
+        "deploy-service",
+        "--service-uri",
+        "http://localhost:8000/dev/services/abc",
+        "--set-uri",
+        "--local-component",
+        "default=./components/echo.wasm",
+        "--local-component",
+        "second=/tmp/other=name.wasm",
+    ])
+    .unwrap();
+
+    match parsed {
+        Command::DeployService {
+            local_component, ..
+        } => {
+            assert_eq!(local_component.len(), 2);
+            assert_eq!(local_component[0].workflow_id.as_ref(), "default");
+            assert_eq!(local_component[0].path, "./components/echo.wasm");
+            // only the first '=' separates the workflow id from the path
+            assert_eq!(local_component[1].workflow_id.as_ref(), "second");
+            assert_eq!(local_component[1].path, "/tmp/other=name.wasm");
+        }
+        _ => panic!("Expected DeployService command"),
+    }
+
+    assert!(Command::try_parse_from(vec![
+        "wavs-cli",
+        "deploy-service",
+        "--service-uri",
+        "http://localhost:8000/dev/services/abc",
+        "--local-component",
+        "./components/echo.wasm",
+    ])
+    .is_err());
+}
@@ -5,9 +5,10 @@ use anyhow::{Context, Result};
 use layer_climb::{prelude::CosmosAddr, signing::SigningClient};
 use wavs_types::{
     contracts::cosmwasm::service_manager::ServiceManagerExecuteMessages, AddServiceRequest,
-    ChainKey, ComponentDigest, DeleteServicesRequest, DevTriggerStreamsInfo, GetSignerRequest,
-    IWavsServiceManager::IWavsServiceManagerInstance, P2pStatus, SaveServiceResponse, Service,
-    ServiceManager, SignerResponse, UploadComponentResponse,
+    ChainKey, ComponentDigest, DeleteServicesRequest, DeploymentRecord, DevTriggerStreamsInfo,
+    GetSignerRequest, IWavsServiceManager::IWavsServiceManagerInstance, ListServicesResponse,
+    P2pStatus, SaveServiceResponse, Service, ServiceId, ServiceManager, SignerResponse, Timestamp,
+    UploadComponentResponse, WorkflowId, WorkflowStatusResponse,
 };
 
 use crate::command::deploy_service::SetServiceUriArgs;
@@ -49,6 +50,22 @@ impl HttpClient {
         Ok(response.digest)
     }
 
+    /// Digests of all the components stored on the node
+    pub async fn get_component_digests(&self) -> Result<Vec<ComponentDigest>> {
+        let url = format!("{}/services", self.endpoint);
+        let response: ListServicesResponse = self
+            .inner
+            .get(&url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to send request to {}", url))?
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse response from {}", url))?;
+
+        Ok(response.component_digests)
+    }
+
     pub async fn simulate_trigger(&self, req: wavs_types::SimulatedTriggerRequest) -> Result<()> {
         let url = format!("{}/dev/triggers", self.endpoint);
 
@@ -76,6 +93,7 @@ impl HttpClient {
         &self,
         service_manager: ServiceManager,
         save_service_args: Option<SetServiceUriArgs>,
+        deployment_id: Option<String>,
     ) -> Result<Service> {
         if let Some(save_service) = save_service_args {
             match save_service {
@@ -100,6 +118,7 @@ impl HttpClient {
 
         let body: String = serde_json::to_string(&AddServiceRequest {
             service_manager: service_manager.clone(),
+            deployment_id,
         })?;
 
         let url = format!("{}/services", self.endpoint);
@@ -267,6 +286,25 @@ impl HttpClient {
         }
     }
 
+    /// The node's record of an earlier deploy with this id, `None` if it never landed
+    pub async fn get_deployment(&self, deployment_id: &str) -> Result<Option<DeploymentRecord>> {
+        let url = format!("{}/deployments/{deployment_id}", self.endpoint);
+
+        let response = self.inner.get(&url).send().await?;
+
+        match response.status() {
+            reqwest::StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => Ok(Some(response.json().await?)),
+            status => {
+                let body = response
+                    .text()
+                    .await
+                    .unwrap_or_else(|_| "<failed to read body>".to_string());
+                anyhow::bail!("{} from {}: {}", status, url, body);
+            }
+        }
+    }
+
     pub async fn get_trigger_streams_info(&self) -> Result<DevTriggerStreamsInfo> {
         let url = format!("{}/dev/trigger-streams", self.endpoint);
 
@@ -380,6 +418,68 @@ impl HttpClient {
         }
     }
 
+    /// Get the execution status of a workflow
+    pub async fn get_workflow_status(
+        &self,
+        service_id: &ServiceId,
+        workflow_id: &WorkflowId,
+        limit: Option<usize>,
+    ) -> Result<WorkflowStatusResponse> {
+        let mut url = format!(
+            "{}/services/{}/workflows/{}/status",
+            self.endpoint, service_id, workflow_id
+        );
+        if let Some(limit) = limit {
+            url.push_str(&format!("?limit={}", limit));
+        }
+
+        let response = self.inner.get(&url).send().await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "<Failed to read response body>".to_string());
+
+            anyhow::bail!("{} from {}: {}", status, url, error_text);
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Open the node's event stream for a service. Events after `last_event_id` are replayed
+    /// first, or if there isn't one, those at or after `since`
+    pub async fn service_events(
+        &self,
+        service_id: &ServiceId,
+        since: Option<Timestamp>,
+        last_event_id: Option<u64>,
+    ) -> Result<reqwest::Response> {
+        let mut url = format!("{}/services/{}/events", self.endpoint, service_id);
+        if let Some(since) = since {
+            url.push_str(&format!("?since={}", since.as_nanos()));
+        }
+
+        let mut request = self.inner.get(&url);
+        if let Some(id) = last_event_id {
+            request = request.header("Last-Event-ID", id.to_string());
+        }
+        let response = request.send().await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "<Failed to read response body>".to_string());
+
+            anyhow::bail!("{} from {}: {}", status, url, error_text);
+        }
+
+        Ok(response)
+    }
+
     /// Get a value from the KV store
     pub async fn get_kv(
         &self,
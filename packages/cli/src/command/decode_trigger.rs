@@ -0,0 +1,370 @@
+use std::path::PathBuf;
+
+use alloy_dyn_abi::{DynSolValue, EventExt};
+use alloy_json_abi::{Event, JsonAbi};
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use wavs_types::{TriggerAction, TriggerData};
+
+/// A saved `TriggerAction`, with its EVM event fields decoded if an ABI was given
+#[derive(Serialize)]
+pub struct DecodeTrigger {
+    pub action: TriggerAction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<DecodedEvent>,
+}
+
+#[derive(Serialize)]
+pub struct DecodedEvent {
+    pub signature: String,
+    pub fields: Vec<DecodedField>,
+}
+
+#[derive(Serialize)]
+pub struct DecodedField {
+    pub name: String,
+    pub ty: String,
+    pub indexed: bool,
+    pub value: String,
+}
+
+impl std::fmt::Display for DecodeTrigger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let TriggerAction { config, data } = &self.action;
+
+        writeln!(f, "Trigger")?;
+        writeln!(f, "  Service ID:  {}", config.service_id)?;
+        writeln!(f, "  Workflow ID: {}", config.workflow_id)?;
+        write!(f, "  Type:        {}", data.trigger_type())?;
+        if let Some(chain) = data.chain() {
+            write!(f, "\n  Chain:       {}", chain)?;
+        }
+
+        match data {
+            TriggerData::EvmContractEvent {
+                contract_address,
+                log_data,
+                tx_hash,
+                block_number,
+                log_index,
+                block_hash,
+                block_timestamp,
+                tx_index,
+                ..
+            } => {
+                write!(f, "\n  Block:       {} ({})", block_number, block_hash)?;
+                if let Some(timestamp) = block_timestamp {
+                    write!(f, "\n  Timestamp:   {}", timestamp)?;
+                }
+                write!(f, "\n  Tx:          {} (index {})", tx_hash, tx_index)?;
+                write!(f, "\n  Log index:   {}", log_index)?;
+                write!(f, "\n  Contract:    {}", contract_address)?;
+                write!(f, "\n  Topics:")?;
+                for topic in log_data.topics() {
+                    write!(f, "\n    {}", topic)?;
+                }
+
+                if let Some(event) = &self.event {
+                    write!(f, "\n  Event:       {}", event.signature)?;
+                    for field in &event.fields {
+                        let indexed = if field.indexed { " indexed" } else { "" };
+                        write!(
+                            f,
+                            "\n    {} ({}{}): {}",
+                            field.name, field.ty, indexed, field.value
+                        )?;
+                    }
+                }
+
+                write!(f, "\n  Data:        {}", log_data.data)
+            }
+            TriggerData::CosmosContractEvent {
+                contract_address,
+                event,
+                block_height,
+                event_index,
+                ..
+            } => {
+                write!(f, "\n  Block:       {}", block_height)?;
+                write!(f, "\n  Event index: {}", event_index)?;
+                write!(f, "\n  Contract:    {}", contract_address)?;
+                write!(f, "\n  Event:       {}", event.ty)?;
+                for attr in &event.attributes {
+                    write!(f, "\n    {}: {}", attr.key, attr.value)?;
+                }
+                Ok(())
+            }
+            TriggerData::BlockInterval {
+                block_height,
+                is_final,
+                ..
+            } => {
+                write!(f, "\n  Block:       {}", block_height)?;
+                if *is_final {
+                    write!(f, "\n  Final:       true")?;
+                }
+                Ok(())
+            }
+            TriggerData::Cron {
+                trigger_time,
+                missed_ticks,
+            } => {
+                write!(f, "\n  Time (ns):   {}", trigger_time.as_nanos())?;
+                if let Some(missed_ticks) = missed_ticks {
+                    write!(f, "\n  Missed:      {}", missed_ticks)?;
+                }
+                Ok(())
+            }
+            TriggerData::AtProtoEvent {
+                sequence,
+                timestamp,
+                repo,
+                collection,
+                rkey,
+                action,
+                cid,
+                record,
+                ..
+            } => {
+                write!(f, "\n  Sequence:    {}", sequence)?;
+                write!(f, "\n  Time (us):   {}", timestamp)?;
+                write!(f, "\n  Repo:        {}", repo)?;
+                write!(f, "\n  Record:      {}/{} ({:?})", collection, rkey, action)?;
+                if let Some(cid) = cid {
+                    write!(f, "\n  CID:         {}", cid)?;
+                }
+                if let Some(record) = record {
+                    write!(f, "\n  Data:        {}", record)?;
+                }
+                Ok(())
+            }
+            TriggerData::HypercoreAppend {
+                feed_key,
+                index,
+                data,
+            } => {
+                write!(f, "\n  Feed:        {}", feed_key)?;
+                write!(f, "\n  Index:       {}", index)?;
+                write!(f, "\n  Data:        {}", const_hex::encode_prefixed(data))
+            }
+            TriggerData::Webhook { headers, body } => {
+                for (name, value) in headers {
+                    write!(f, "\n  Header:      {}: {}", name, value)?;
+                }
+                write!(f, "\n  Body:        {}", String::from_utf8_lossy(body))
+            }
+            TriggerData::Raw(data) => {
+                write!(f, "\n  Data:        {}", const_hex::encode_prefixed(data))
+            }
+        }
+    }
+}
+
+pub struct DecodeTriggerArgs {
+    pub file: PathBuf,
+    pub abi: Option<PathBuf>,
+}
+
+impl DecodeTrigger {
+    pub fn run(DecodeTriggerArgs { file, abi }: DecodeTriggerArgs) -> Result<Self> {
+        let bytes = std::fs::read(&file)
+            .context(format!("Failed to read trigger file '{}'", file.display()))?;
+        let action = parse_trigger_action(&bytes)?;
+
+        let event = match abi {
+            Some(abi) => {
+                let abi_bytes = std::fs::read(&abi)
+                    .context(format!("Failed to read ABI file '{}'", abi.display()))?;
+                decode_evm_event(&action.data, &parse_abi(&abi_bytes)?)?
+            }
+            None => None,
+        };
+
+        Ok(Self { action, event })
+    }
+}
+
+/// Accepts the JSON form (as used by the simulate endpoint) or the bincode form used internally
+pub fn parse_trigger_action(bytes: &[u8]) -> Result<TriggerAction> {
+    if let Ok(action) = serde_json::from_slice(bytes) {
+        return Ok(action);
+    }
+
+    bincode::decode_from_slice(bytes, bincode::config::standard())
+        .map(|(action, _)| action)
+        .map_err(|_| anyhow!("File is neither a JSON nor a bincode encoded TriggerAction"))
+}
+
+/// Accepts a plain ABI array or a compiler artifact with an `abi` field
+pub fn parse_abi(bytes: &[u8]) -> Result<JsonAbi> {
+    #[derive(serde::Deserialize)]
+    struct Artifact {
+        abi: JsonAbi,
+    }
+
+    serde_json::from_slice::<JsonAbi>(bytes)
+        .or_else(|_| serde_json::from_slice::<Artifact>(bytes).map(|artifact| artifact.abi))
+        .context("Failed to parse ABI, expected a JSON ABI or an artifact with an `abi` field")
+}
+
+/// Decode the event fields of an EVM trigger, `None` for other trigger types
+pub fn decode_evm_event(data: &TriggerData, abi: &JsonAbi) -> Result<Option<DecodedEvent>> {
+    let log_data = match data {
+        TriggerData::EvmContractEvent { log_data, .. } => log_data,
+        _ => return Ok(None),
+    };
+
+    let selector = log_data
+        .topics()
+        .first()
+        .ok_or_else(|| anyhow!("Log has no topics, anonymous events can't be matched"))?;
+
+    let event: &Event = abi
+        .events()
+        .find(|event| !event.anonymous && event.selector() == *selector)
+        .ok_or_else(|| anyhow!("No event in the ABI matches topic {selector}"))?;
+
+    let decoded = event
+        .decode_log(log_data)
+        .context(format!("Failed to decode log as {}", event.signature()))?;
+
+    let mut indexed = decoded.indexed.iter();
+    let mut body = decoded.body.iter();
+
+    let fields = event
+        .inputs
+        .iter()
+        .map(|input| {
+            let value = if input.indexed {
+                indexed.next()
+            } else {
+                body.next()
+            };
+
+            DecodedField {
+                name: input.name.clone(),
+                ty: input.ty.clone(),
+                indexed: input.indexed,
+                value: value.map(format_value).unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    Ok(Some(DecodedEvent {
+        signature: event.signature(),
+        fields,
+    }))
+}
+
+fn format_value(value: &DynSolValue) -> String {
+    if let Some(address) = value.as_address() {
+        address.to_string()
+    } else if let Some((uint, _)) = value.as_uint() {
+        uint.to_string()
+    } else if let Some((int, _)) = value.as_int() {
+        int.to_string()
+    } else if let Some(b) = value.as_bool() {
+        b.to_string()
+    } else if let Some(s) = value.as_str() {
+        format!("{s:?}")
+    } else if let Some(bytes) = value.as_bytes() {
+        const_hex::encode_prefixed(bytes)
+    } else if let Some((bytes, size)) = value.as_fixed_bytes() {
+        const_hex::encode_prefixed(&bytes[..size])
+    } else if let Some(items) = value.as_tuple() {
+        format!("({})", format_values(items))
+    } else if let Some(items) = value.as_fixed_seq() {
+        format!("[{}]", format_values(items))
+    } else {
+        format!("{value:?}")
+    }
+}
+
+fn format_values(values: &[DynSolValue]) -> String {
+    values
+        .iter()
+        .map(format_value)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod test {
+    use alloy_primitives::{address, Address, LogData, B256, U256};
+    use wavs_types::{ChainKey, ServiceId, Trigger, TriggerConfig, WorkflowId};
+
+    use super::*;
+
+    const TRANSFER_ABI: &str = r#"[{
+        "type": "event",
+        "name": "Transfer",
+        "anonymous": false,
+        "inputs": [
+            { "name": "from", "type": "address", "indexed": true },
+            { "name": "to", "type": "address", "indexed": true },
+            { "name": "value", "type": "uint256", "indexed": false }
+        ]
+    }]"#;
+
+    fn transfer_action(from: Address, to: Address, value: U256) -> TriggerAction {
+        let abi = parse_abi(TRANSFER_ABI.as_bytes()).unwrap();
+        let event = abi.events().next().unwrap();
+
+        TriggerAction {
+            config: TriggerConfig {
+                service_id: ServiceId::hash(b"service"),
+                workflow_id: WorkflowId::default(),
+                trigger: Trigger::Manual,
+            },
+            data: TriggerData::EvmContractEvent {
+                chain: ChainKey::new("evm:31337").unwrap(),
+                contract_address: Address::ZERO,
+                log_data: LogData::new_unchecked(
+                    vec![event.selector(), from.into_word(), to.into_word()],
+                    value.to_be_bytes::<32>().to_vec().into(),
+                ),
+                tx_hash: B256::ZERO,
+                block_number: 1,
+                log_index: 0,
+                block_hash: B256::ZERO,
+                block_timestamp: None,
+                tx_index: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn decodes_evm_event_fields() {
+        let from = address!("0x1111111111111111111111111111111111111111");
+        let to = address!("0x2222222222222222222222222222222222222222");
+        let action = transfer_action(from, to, U256::from(42));
+
+        // round-trips through both supported encodings
+        let json = serde_json::to_vec(&action).unwrap();
+        assert_eq!(parse_trigger_action(&json).unwrap(), action);
+        let bincoded = bincode::encode_to_vec(&action, bincode::config::standard()).unwrap();
+        assert_eq!(parse_trigger_action(&bincoded).unwrap(), action);
+
+        let abi = parse_abi(TRANSFER_ABI.as_bytes()).unwrap();
+        let event = decode_evm_event(&action.data, &abi).unwrap().unwrap();
+
+        assert_eq!(event.signature, "Transfer(address,address,uint256)");
+        let values: Vec<_> = event
+            .fields
+            .iter()
+            .map(|field| (field.name.as_str(), field.value.clone()))
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                ("from", from.to_string()),
+                ("to", to.to_string()),
+                ("value", "42".to_string()),
+            ]
+        );
+
+        // artifacts wrap the abi
+        let artifact = format!(r#"{{ "abi": {TRANSFER_ABI} }}"#);
+        assert!(parse_abi(artifact.as_bytes()).is_ok());
+    }
+}
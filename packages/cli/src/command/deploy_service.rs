@@ -1,21 +1,39 @@
-use crate::{clients::HttpClient, context::CliContext, deploy::CommandDeployResult};
+use crate::{
+    args::LocalComponent, clients::HttpClient, context::CliContext, deploy::CommandDeployResult,
+    util::read_component,
+};
 use alloy_provider::DynProvider;
 use anyhow::{Context, Result};
 use iri_string::types::UriString;
 use layer_climb::signing::SigningClient;
-use wavs_types::{Service, ServiceManager};
+use wavs_types::{ComponentDigest, ComponentSource, Service, ServiceManager, WorkflowId};
 
 pub struct DeployService {
     pub args: DeployServiceArgs,
     pub service: Service,
+    /// The deployment id had already been applied, nothing was deployed this time
+    pub already_deployed: bool,
 }
 
 impl std::fmt::Display for DeployService {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "New Service deployed to wavs")?;
-        if let Some(save_service_args) = &self.args.set_service_url_args {
+        if self.already_deployed {
+            write!(f, "Service already deployed to wavs")?;
+        } else {
+            write!(f, "New Service deployed to wavs")?;
+        }
+        if let Some(deployment_id) = &self.args.deployment_id {
+            write!(f, " (deployment id {deployment_id})")?;
+        }
+        // the uri was only set on-chain if this run actually deployed
+        if let (Some(save_service_args), false) =
+            (&self.args.set_service_url_args, self.already_deployed)
+        {
             write!(f, "\n\n{:#?}", save_service_args.service_uri())?;
         }
+        for upload in &self.args.component_uploads {
+            write!(f, "\n\n{}", upload)?;
+        }
         write!(f, "\n\n{:#?}", self.args.service_manager)
     }
 }
@@ -32,6 +50,34 @@ impl CommandDeployResult for DeployService {
 pub struct DeployServiceArgs {
     pub service_manager: ServiceManager,
     pub set_service_url_args: Option<SetServiceUriArgs>,
+    /// Makes the deploy safe to retry, if the node has already applied a deploy with
+    /// this id the on-chain steps are skipped and the earlier result is returned
+    pub deployment_id: Option<String>,
+    /// Local components that were uploaded before deploying, see [`DeployService::upload_local_components`]
+    pub component_uploads: Vec<ComponentUpload>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ComponentUpload {
+    pub workflow_id: WorkflowId,
+    pub digest: ComponentDigest,
+    /// The node already had this component, so it wasn't uploaded again
+    pub already_uploaded: bool,
+}
+
+impl std::fmt::Display for ComponentUpload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = if self.already_uploaded {
+            "already on node"
+        } else {
+            "uploaded"
+        };
+        write!(
+            f,
+            "Workflow {} component {} ({})",
+            self.workflow_id, self.digest, status
+        )
+    }
 }
 
 #[derive(Clone)]
@@ -75,21 +121,107 @@ impl DeployService {
 
         let http_client = HttpClient::new(ctx.config.wavs_endpoint.clone());
 
+        if let Some(deployment_id) = &args.deployment_id {
+            if let Some(record) = http_client.get_deployment(deployment_id).await? {
+                if record.service_manager != service_manager {
+                    anyhow::bail!(
+                        "Deployment id {deployment_id} was already used for {:?}",
+                        record.service_manager
+                    );
+                }
+
+                let (chain, address) = match &service_manager {
+                    ServiceManager::Evm { chain, address } => (chain, address.to_string()),
+                    ServiceManager::Cosmos { chain, address } => (chain, address.to_string()),
+                };
+                let service = http_client.get_service_from_node(chain, &address).await?;
+
+                let _self = Self {
+                    args,
+                    service,
+                    already_deployed: true,
+                };
+                _self.update_deployment(&mut ctx.deployment.lock().unwrap());
+                return Ok(_self);
+            }
+        }
+
         let service = http_client
-            .create_service(service_manager.clone(), args.set_service_url_args.clone())
+            .create_service(
+                service_manager.clone(),
+                args.set_service_url_args.clone(),
+                args.deployment_id.clone(),
+            )
             .await
             .context(format!(
                 "Failed to deploy service with '{:?}'",
                 service_manager
             ))?;
 
-        let _self = Self { args, service };
+        let _self = Self {
+            args,
+            service,
+            already_deployed: false,
+        };
 
         _self.update_deployment(&mut ctx.deployment.lock().unwrap());
 
         Ok(_self)
     }
 
+    /// Uploads each local component the node doesn't have yet and points its workflow at the digest.
+    /// The service is modified in place, so it has to be saved again for the node to see the change
+    pub async fn upload_local_components(
+        ctx: &CliContext,
+        service: &mut Service,
+        local_components: &[LocalComponent],
+    ) -> Result<Vec<ComponentUpload>> {
+        let http_client = HttpClient::new(ctx.config.wavs_endpoint.clone());
+        let existing = http_client.get_component_digests().await?;
+
+        let mut uploads = Vec::with_capacity(local_components.len());
+
+        for LocalComponent { workflow_id, path } in local_components {
+            let workflow = service.workflows.get_mut(workflow_id).ok_or_else(|| {
+                anyhow::anyhow!("Workflow '{}' not found in service", workflow_id)
+            })?;
+
+            let wasm_bytes = read_component(path)
+                .context(format!("Failed to read WASM component from path: {}", path))?;
+            let digest = ComponentDigest::hash(&wasm_bytes);
+
+            let already_uploaded = existing.contains(&digest);
+            if !already_uploaded {
+                let uploaded = http_client
+                    .upload_component(wasm_bytes)
+                    .await
+                    .context(format!(
+                        "Failed to upload component '{}' to WAVS endpoint '{}'",
+                        path, ctx.config.wavs_endpoint
+                    ))?;
+
+                if uploaded != digest {
+                    anyhow::bail!(
+                        "Node stored component '{}' as {}, expected {}",
+                        path,
+                        uploaded,
+                        digest
+                    );
+                }
+            }
+
+            workflow.component.source = ComponentSource::Digest(digest.clone());
+
+            uploads.push(ComponentUpload {
+                workflow_id: workflow_id.clone(),
+                digest,
+                already_uploaded,
+            });
+        }
+
+        Ok(uploads)
+    }
+
     pub async fn save_service(ctx: &CliContext, service: &Service) -> Result<String> {
         let http_client = HttpClient::new(ctx.config.wavs_endpoint.clone());
 
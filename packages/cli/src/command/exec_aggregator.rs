@@ -28,8 +28,10 @@ fn create_dummy_service(
         },
         fuel_limit,
         time_limit_seconds,
+        max_memory_bytes: None,
         config,
         env_keys,
+        secrets: BTreeSet::new(),
     };
     Service {
         name: "dummy-service".to_string(),
@@ -41,7 +43,14 @@ fn create_dummy_service(
                 submit: Submit::Aggregator {
                     component: Box::new(component),
                     signature_kind: SignatureKind::evm_default(),
+                    targets: Vec::new(),
                 },
+                submit_targets: Vec::new(),
+                response_mapping: Default::default(),
+                submit_condition: None,
+                priority: Default::default(),
+                observe_only: false,
+                min_interval_ms: None,
             },
         )]
         .into(),
@@ -50,6 +59,8 @@ fn create_dummy_service(
             chain: "evm:dummy".parse().unwrap(),
             address: alloy_primitives::Address::ZERO,
         },
+        max_concurrent_executions: None,
+        execution_timeout_secs: None,
     }
 }
 fn create_dummy_input(service: &Service) -> AggregatorInput {
@@ -131,7 +142,12 @@ impl ExecAggregator {
             data_dir: tempfile::tempdir()?.keep(),
             chain_configs: &cli_config.chains.read().unwrap(),
             log: HostComponentLogger::AggregatorHostComponentLogger(
-                |_service_id, _workflow_id, _digest, level, message| {
+                |_service_id, _workflow_id, _digest, level, message, fields| {
+                    let message = if fields.is_empty() {
+                        message
+                    } else {
+                        format!("{} {}", message, wavs_engine::utils::log::LogFields(fields))
+                    };
                     match level {
                 wavs_engine::bindings::aggregator::world::wavs::types::core::LogLevel::Error => {
                     tracing::error!("{}", message)
@@ -155,6 +171,10 @@ impl ExecAggregator {
                 utils::storage::db::WavsDb::new()?,
                 service.id().to_string(),
             ),
+            evm_blocks: Default::default(),
+            secrets: Default::default(),
+            determinism: Default::default(),
+            capture_output: false,
             service,
         }
         .build()?;
@@ -236,6 +256,7 @@ mod test {
             },
             fuel_limit: None,
             time_limit_seconds: None,
+            max_memory_bytes: None,
             config: [
                 ("chain".to_string(), "evm:31337".to_string()),
                 (
@@ -246,6 +267,7 @@ mod test {
             .into_iter()
             .collect(),
             env_keys: Default::default(),
+            secrets: Default::default(),
         };
         Service {
             name: "test-service".to_string(),
@@ -257,7 +279,14 @@ mod test {
                     submit: Submit::Aggregator {
                         component: Box::new(component),
                         signature_kind: SignatureKind::evm_default(),
+                        targets: Vec::new(),
                     },
+                    submit_targets: Vec::new(),
+                    response_mapping: Default::default(),
+                    submit_condition: None,
+                    priority: Default::default(),
+                    observe_only: false,
+                    min_interval_ms: None,
                 },
             )]
             .into(),
@@ -266,6 +295,8 @@ mod test {
                 chain: "evm:anvil".parse().unwrap(),
                 address: alloy_primitives::Address::ZERO,
             },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
         }
     }
 
@@ -332,6 +363,7 @@ mod test {
                 EvmChainConfig {
                     chain_id: "31337".to_string().parse().unwrap(),
                     http_endpoint: Some("http://localhost:8545".to_string()),
+                    http_endpoints: Vec::new(),
                     faucet_endpoint: None,
                     ws_endpoints: vec![],
                     ws_priority_endpoint_index: None,
@@ -395,6 +427,7 @@ mod test {
                 EvmChainConfig {
                     chain_id: "31337".to_string().parse().unwrap(),
                     http_endpoint: Some("http://localhost:8545".to_string()),
+                    http_endpoints: Vec::new(),
                     faucet_endpoint: None,
                     ws_endpoints: vec![],
                     ws_priority_endpoint_index: None,
@@ -9,6 +9,7 @@ use utils::{config::WAVS_ENV_PREFIX, storage::db::WavsDb};
 use wasmtime::{component::Component as WasmtimeComponent, Config as WTConfig, Engine as WTEngine};
 use wavs_engine::{
     bindings::operator::world::host::LogLevel,
+    utils::log::LogFields,
     worlds::instance::{HostComponentLogger, InstanceData, InstanceDepsBuilder},
 };
 use wavs_types::{
@@ -133,10 +134,18 @@ impl ExecComponent {
                 },
                 fuel_limit,
                 time_limit_seconds: time_limit,
+                max_memory_bytes: None,
                 config,
                 env_keys,
+                secrets: Default::default(),
             },
             submit: Submit::None,
+            submit_targets: Vec::new(),
+            response_mapping: Default::default(),
+            submit_condition: None,
+            priority: Default::default(),
+            observe_only: false,
+            min_interval_ms: None,
         };
 
         let chain: ChainKey = "evm:exec".parse().unwrap();
@@ -148,12 +157,15 @@ impl ExecComponent {
                 chain: chain.clone(),
                 address: Default::default(),
             },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
         };
 
         let data = match simulates_trigger {
             Some(trigger_kind) => match trigger_kind {
                 TriggerKind::Cron { trigger_time } => TriggerData::Cron {
                     trigger_time: Timestamp::from_nanos(trigger_time),
+                    missed_ticks: None,
                 },
                 TriggerKind::EvmContractEvent {
                     chain,
@@ -182,6 +194,7 @@ impl ExecComponent {
                 } => TriggerData::BlockInterval {
                     chain,
                     block_height,
+                    is_final: false,
                 },
             },
             None => TriggerData::Raw(
@@ -213,46 +226,49 @@ impl ExecComponent {
                 WavsDb::new().unwrap(),
                 "exec_component".to_string(),
             ),
+            evm_blocks: Default::default(),
+            secrets: Default::default(),
+            determinism: Default::default(),
+            capture_output: false,
         }
         .build()
         .context("Failed to build instance dependencies for component execution")?;
 
-        let initial_fuel = instance_deps
-            .store
-            .get_fuel()
-            .context("Failed to get initial fuel value from the instance store")?;
         let start_time = Instant::now();
-        let wasm_responses = match wavs_engine::worlds::operator::execute::execute(
+        let output = match wavs_engine::worlds::operator::execute::execute(
             &mut instance_deps,
             trigger_action,
         )
         .await
         {
-            Ok(responses) => responses,
+            Ok(output) => output,
             Err(e) => {
                 tracing::error!("Error executing component: {}", e);
                 return Err(anyhow::anyhow!("Component execution failed: {}", e));
             }
         };
 
-        let fuel_used = initial_fuel - instance_deps.store.get_fuel()?;
-
         Ok(ExecComponent {
-            wasm_responses,
-            fuel_used,
+            wasm_responses: output.responses,
+            // the CLI engine always meters fuel
+            fuel_used: output.fuel_consumed.unwrap_or_default(),
             time_elapsed: start_time.elapsed().as_millis(),
         })
     }
 }
 
-fn log_wasi(
+pub(crate) fn log_wasi(
     service_id: &ServiceId,
     workflow_id: &WorkflowId,
     digest: &ComponentDigest,
     level: LogLevel,
     message: String,
+    fields: &[(String, String)],
 ) {
-    let message = format!("[{}:{}:{}] {}", service_id, workflow_id, digest, message);
+    let mut message = format!("[{}:{}:{}] {}", service_id, workflow_id, digest, message);
+    if !fields.is_empty() {
+        message = format!("{} {}", message, LogFields(fields));
+    }
 
     match level {
         LogLevel::Error => tracing::error!("{}", message),
@@ -0,0 +1,248 @@
+use std::collections::BTreeSet;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use wasmtime::{
+    component::{types::ComponentItem, Component},
+    Config as WTConfig, Engine as WTEngine,
+};
+use wavs_engine::worlds::interfaces::{host_interface, HostInterfaceKind, WORLD_LOCAL_IMPORTS};
+use wavs_types::ComponentDigest;
+
+use crate::util::read_component;
+
+/// Custom section a component can list the keys it reads through `host::config-var` in,
+/// one per line. The keys are otherwise just bytes somewhere in the data segment,
+/// so this is the only way to know them without running the component
+pub const CONFIG_VARS_SECTION: &str = "wavs:config-vars";
+
+/// What a component needs from the node, and what it provides
+#[derive(Debug, Serialize)]
+pub struct InspectComponent {
+    pub digest: ComponentDigest,
+    pub imports: Vec<ComponentImport>,
+    /// Exported functions, as `interface#function` for functions of an exported interface
+    pub exports: Vec<String>,
+    /// Config var keys declared in the [`CONFIG_VARS_SECTION`]
+    pub config_vars: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComponentImport {
+    pub name: String,
+    pub support: ImportSupport,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportSupport {
+    /// Always linked by the node
+    Provided,
+    /// Only linked if the workflow allows some http hosts
+    RequiresHttpPermission,
+    /// No node provides it, the component won't instantiate
+    Unsupported,
+}
+
+impl ImportSupport {
+    fn of(import_name: &str) -> Self {
+        if WORLD_LOCAL_IMPORTS.contains(&import_name) {
+            return Self::Provided;
+        }
+
+        match host_interface(import_name) {
+            Some(interface) if interface.kind == HostInterfaceKind::WasiHttp => {
+                Self::RequiresHttpPermission
+            }
+            Some(_) => Self::Provided,
+            None => Self::Unsupported,
+        }
+    }
+}
+
+impl std::fmt::Display for InspectComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Component: {}", self.digest)?;
+
+        write!(f, "\n\nImports:")?;
+        for import in &self.imports {
+            write!(f, "\n  {}", import.name)?;
+            match import.support {
+                ImportSupport::Provided => {}
+                ImportSupport::RequiresHttpPermission => write!(f, " (requires http permission)")?,
+                ImportSupport::Unsupported => write!(f, " (not provided by WAVS)")?,
+            }
+        }
+
+        write!(f, "\n\nExports:")?;
+        for export in &self.exports {
+            write!(f, "\n  {}", export)?;
+        }
+
+        write!(f, "\n\nConfig vars:")?;
+        if self.config_vars.is_empty() {
+            write!(f, "\n  none declared")?;
+        }
+        for key in &self.config_vars {
+            write!(f, "\n  {}", key)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct InspectComponentArgs {
+    pub component_path: String,
+}
+
+impl InspectComponent {
+    pub fn run(InspectComponentArgs { component_path }: InspectComponentArgs) -> Result<Self> {
+        let wasm_bytes = read_component(&component_path).context(format!(
+            "Failed to read WASM component from path: {}",
+            component_path
+        ))?;
+
+        let mut wt_config = WTConfig::new();
+        wt_config.wasm_component_model(true);
+        wt_config.async_support(true);
+
+        let engine = WTEngine::new(&wt_config)
+            .context("Failed to create Wasmtime engine with the specified configuration")?;
+        let component =
+            Component::new(&engine, &wasm_bytes).context("Failed to parse WASM component")?;
+        let component_type = component.component_type();
+
+        let imports = component_type
+            .imports(&engine)
+            .map(|(name, _)| ComponentImport {
+                name: name.to_string(),
+                support: ImportSupport::of(name),
+            })
+            .collect();
+
+        let mut exports = Vec::new();
+        for (name, item) in component_type.exports(&engine) {
+            match item {
+                ComponentItem::ComponentFunc(_) => exports.push(name.to_string()),
+                ComponentItem::ComponentInstance(instance) => {
+                    for (func, item) in instance.exports(&engine) {
+                        if matches!(item, ComponentItem::ComponentFunc(_)) {
+                            exports.push(format!("{name}#{func}"));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            digest: ComponentDigest::hash(&wasm_bytes),
+            imports,
+            exports,
+            config_vars: declared_config_vars(&wasm_bytes)?,
+        })
+    }
+}
+
+/// Keys from every [`CONFIG_VARS_SECTION`], in the component itself or any module inside it
+fn declared_config_vars(wasm_bytes: &[u8]) -> Result<Vec<String>> {
+    let mut keys = BTreeSet::new();
+
+    for payload in wasmparser::Parser::new(0).parse_all(wasm_bytes) {
+        if let wasmparser::Payload::CustomSection(section) = payload? {
+            if section.name() == CONFIG_VARS_SECTION {
+                let data = std::str::from_utf8(section.data())
+                    .context(format!("{CONFIG_VARS_SECTION} section is not utf8"))?;
+                keys.extend(
+                    data.lines()
+                        .map(str::trim)
+                        .filter(|key| !key.is_empty())
+                        .map(str::to_string),
+                );
+            }
+        }
+    }
+
+    Ok(keys.into_iter().collect())
+}
+
+#[cfg(test)]
+mod test {
+    use utils::filesystem::workspace_path;
+
+    use super::*;
+
+    /// Appends a custom section, which is valid anywhere in a component
+    fn with_custom_section(mut wasm_bytes: Vec<u8>, name: &str, data: &[u8]) -> Vec<u8> {
+        let mut contents = Vec::new();
+        leb128_u32(&mut contents, name.len() as u32);
+        contents.extend_from_slice(name.as_bytes());
+        contents.extend_from_slice(data);
+
+        wasm_bytes.push(0);
+        leb128_u32(&mut wasm_bytes, contents.len() as u32);
+        wasm_bytes.extend(contents);
+        wasm_bytes
+    }
+
+    fn leb128_u32(out: &mut Vec<u8>, mut value: u32) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                out.push(byte);
+                return;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    #[test]
+    fn inspect_echo_data() {
+        let component_path = workspace_path()
+            .join("examples")
+            .join("build")
+            .join("components")
+            .join("echo_data.wasm");
+
+        let res = InspectComponent::run(InspectComponentArgs {
+            component_path: component_path.to_string_lossy().to_string(),
+        })
+        .unwrap();
+
+        assert!(!res.imports.is_empty());
+        assert!(res
+            .imports
+            .iter()
+            .all(|import| import.support != ImportSupport::Unsupported));
+        assert!(res.exports.iter().any(|export| export == "run"));
+        assert!(res.config_vars.is_empty());
+
+        let wasm_bytes = with_custom_section(
+            std::fs::read(&component_path).unwrap(),
+            CONFIG_VARS_SECTION,
+            b"chain\n  service_handler\n\nchain\n",
+        );
+        assert_eq!(
+            declared_config_vars(&wasm_bytes).unwrap(),
+            vec!["chain".to_string(), "service_handler".to_string()]
+        );
+    }
+
+    #[test]
+    fn import_support() {
+        assert_eq!(
+            ImportSupport::of("wasi:keyvalue/store@0.2.0-draft2"),
+            ImportSupport::Provided
+        );
+        assert_eq!(
+            ImportSupport::of("wasi:http/outgoing-handler@0.2.0"),
+            ImportSupport::RequiresHttpPermission
+        );
+        assert_eq!(ImportSupport::of("host"), ImportSupport::Provided);
+        assert_eq!(
+            ImportSupport::of("wasi:nn/graph@0.2.0"),
+            ImportSupport::Unsupported
+        );
+    }
+}
@@ -1,5 +1,10 @@
+pub mod decode_trigger;
 pub mod deploy_service;
 pub mod exec_aggregator;
 pub mod exec_component;
+pub mod inspect_component;
+pub mod replay;
 pub mod service;
+pub mod status;
+pub mod tail;
 pub mod upload_component;
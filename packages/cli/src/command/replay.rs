@@ -0,0 +1,198 @@
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use utils::storage::{db::WavsDb, memory::MemoryStorage};
+use wavs_engine::{
+    common::base_engine::{BaseEngine, BaseEngineConfig},
+    worlds::operator::replay::execute_recorded,
+};
+use wavs_types::{ComponentDigest, Service, TriggerAction, WasmResponse};
+
+use crate::{command::exec_component::log_wasi, config::Config, util::read_component};
+
+/// The responses from replaying a recorded trigger action
+#[derive(Serialize)]
+pub struct Replay {
+    pub digest: ComponentDigest,
+    pub responses: Vec<WasmResponse>,
+}
+
+impl std::fmt::Display for Replay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Component: {}", self.digest)?;
+
+        if self.responses.is_empty() {
+            return write!(f, "\n\nNo responses from component execution.");
+        }
+
+        for (index, response) in self.responses.iter().enumerate() {
+            write!(
+                f,
+                "\n\nResponse #{}: \n{}",
+                index + 1,
+                const_hex::encode(&response.payload)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct ReplayArgs {
+    pub component_path: String,
+    pub trigger_path: PathBuf,
+    pub service_path: PathBuf,
+}
+
+impl Replay {
+    pub async fn run(
+        cli_config: &Config,
+        ReplayArgs {
+            component_path,
+            trigger_path,
+            service_path,
+        }: ReplayArgs,
+    ) -> Result<Self> {
+        let wasm_bytes = read_component(&component_path).context(format!(
+            "Failed to read WASM component from path: {}",
+            component_path
+        ))?;
+        let trigger_json = std::fs::read(&trigger_path)
+            .context(format!("Failed to read {}", trigger_path.display()))?;
+        let service_json = std::fs::read(&service_path)
+            .context(format!("Failed to read {}", service_path.display()))?;
+
+        // a fresh engine, set up the way the node sets up its own
+        let data_dir = tempfile::tempdir()?;
+        let engine = BaseEngine::new(
+            BaseEngineConfig {
+                app_data_dir: data_dir.path().join("app"),
+                chain_configs: cli_config.chains.clone(),
+                lru_size: 1,
+                max_wasm_fuel: None,
+                max_execution_seconds: None,
+                ipfs_gateway: cli_config.ipfs_gateway.clone(),
+            },
+            WavsDb::new()?,
+            Arc::new(MemoryStorage::new()),
+        )?;
+        let digest = engine.store_component_bytes(&wasm_bytes)?;
+
+        // only a warning, replaying a fixed build of the component is a valid use
+        let trigger_action: TriggerAction = serde_json::from_slice(&trigger_json)?;
+        let service: Service = serde_json::from_slice(&service_json)?;
+        if let Some(workflow) = service.workflows.get(&trigger_action.config.workflow_id) {
+            if *workflow.component.source.digest() != digest {
+                tracing::warn!(
+                    "Replaying {} but the recorded workflow runs {}",
+                    digest,
+                    workflow.component.source.digest()
+                );
+            }
+        }
+
+        let responses =
+            execute_recorded(&engine, &digest, &trigger_json, &service_json, log_wasi).await?;
+
+        Ok(Replay { digest, responses })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use utils::filesystem::workspace_path;
+    use wavs_types::{
+        ComponentSource, Submit, Trigger, TriggerConfig, TriggerData, Workflow, WorkflowId,
+    };
+
+    use super::*;
+    use crate::{
+        command::exec_component::{ExecComponent, ExecComponentArgs},
+        util::ComponentInput,
+    };
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn replay_matches_exec() {
+        let component_path = workspace_path()
+            .join("examples")
+            .join("build")
+            .join("components")
+            .join("echo_data.wasm")
+            .to_string_lossy()
+            .to_string();
+        let wasm_bytes = read_component(&component_path).unwrap();
+
+        let live = ExecComponent::run(
+            &Config::default(),
+            ExecComponentArgs {
+                component_path: component_path.clone(),
+                input: ComponentInput::new("hello world"),
+                fuel_limit: None,
+                time_limit: None,
+                config: BTreeMap::default(),
+                simulates_trigger: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let workflow_id = WorkflowId::default();
+        let service = Service {
+            name: "Recorded Service".to_string(),
+            workflows: BTreeMap::from([(
+                workflow_id.clone(),
+                Workflow {
+                    trigger: Trigger::Manual,
+                    component: wavs_types::Component::new(ComponentSource::Digest(
+                        ComponentDigest::hash(&wasm_bytes),
+                    )),
+                    submit: Submit::None,
+                    submit_targets: Vec::new(),
+                    response_mapping: Default::default(),
+                    submit_condition: None,
+                    priority: Default::default(),
+                    observe_only: false,
+                    min_interval_ms: None,
+                },
+            )]),
+            status: wavs_types::ServiceStatus::Active,
+            manager: wavs_types::ServiceManager::Evm {
+                chain: "evm:exec".parse().unwrap(),
+                address: Default::default(),
+            },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
+        };
+        let trigger_action = TriggerAction {
+            config: TriggerConfig {
+                service_id: service.id(),
+                workflow_id,
+                trigger: Trigger::Manual,
+            },
+            data: TriggerData::Raw(b"hello world".to_vec()),
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let trigger_path = dir.path().join("trigger.json");
+        let service_path = dir.path().join("service.json");
+        std::fs::write(&trigger_path, serde_json::to_vec(&trigger_action).unwrap()).unwrap();
+        std::fs::write(&service_path, serde_json::to_vec(&service).unwrap()).unwrap();
+
+        let replay = Replay::run(
+            &Config::default(),
+            ReplayArgs {
+                component_path,
+                trigger_path,
+                service_path,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(replay.digest, ComponentDigest::hash(&wasm_bytes));
+        assert_eq!(replay.responses, live.wasm_responses);
+    }
+}
@@ -30,9 +30,9 @@ use utils::{config::WAVS_ENV_PREFIX, service::fetch_bytes, wkg::WkgClient};
 use uuid::Uuid;
 use wavs_types::{
     AggregatorBuilder, AllowedHostPermission, AnyChainConfig, AtProtoAction, ByteArray, ChainKey,
-    Component, ComponentBuilder, ComponentDigest, ComponentSource, Registry, ServiceBuilder,
-    ServiceManager, ServiceManagerBuilder, ServiceStatus, SignatureKind, Submit, SubmitBuilder,
-    Timestamp, Trigger, TriggerBuilder, WorkflowBuilder, WorkflowId,
+    Component, ComponentBuilder, ComponentDigest, ComponentSource, EvmLogField, Registry,
+    ServiceBuilder, ServiceManager, ServiceManagerBuilder, ServiceStatus, SignatureKind, Submit,
+    SubmitBuilder, Timestamp, Trigger, TriggerBuilder, WorkflowBuilder, WorkflowId,
 };
 
 use crate::{
@@ -79,18 +79,36 @@ pub async fn handle_service_command(
                     address,
                     chain,
                     event_type,
+                    attributes,
                 } => {
                     let query_client = ctx.new_cosmos_client(chain.id.clone()).await?.querier;
-                    let result =
-                        set_cosmos_trigger(query_client, &file, id, address, chain, event_type)?;
+                    let result = set_cosmos_trigger(
+                        query_client,
+                        &file,
+                        id,
+                        address,
+                        chain,
+                        event_type,
+                        attributes,
+                    )?;
                     display_result(ctx, result, json)?;
                 }
                 TriggerCommand::SetEvm {
                     address,
                     chain,
                     event_hash,
+                    additional_event_hashes,
+                    transform,
                 } => {
-                    let result = set_evm_trigger(&file, id, address, chain, event_hash)?;
+                    let result = set_evm_trigger(
+                        &file,
+                        id,
+                        address,
+                        chain,
+                        event_hash,
+                        additional_event_hashes,
+                        transform,
+                    )?;
                     display_result(ctx, result, json)?;
                 }
                 TriggerCommand::SetBlockInterval {
@@ -157,9 +175,13 @@ pub async fn handle_service_command(
             let result = update_status(&file, status)?;
             display_result(ctx, result, json)?;
         }
-        ServiceCommand::Validate {} => {
-            let result = validate_service(&file, Some(ctx)).await?;
+        ServiceCommand::Validate { path } => {
+            let result = validate_service(&path.unwrap_or(file), Some(ctx)).await?;
+            let failed = !result.errors.is_empty();
             display_result(ctx, result, json)?;
+            if failed {
+                std::process::exit(1);
+            }
         }
     }
 
@@ -632,6 +654,12 @@ pub fn add_workflow(file_path: &Path, id: Option<WorkflowId>) -> Result<Workflow
             trigger,
             component,
             submit,
+            submit_targets: Vec::new(),
+            response_mapping: Default::default(),
+            submit_condition: None,
+            priority: Default::default(),
+            observe_only: false,
+            min_interval_ms: None,
         };
 
         // Add the workflow to the service
@@ -679,7 +707,13 @@ pub fn set_cosmos_trigger(
     address_str: String,
     chain: ChainKey,
     event_type: String,
+    attribute_strs: Vec<String>,
 ) -> Result<WorkflowTriggerResult> {
+    let attributes = attribute_strs
+        .into_iter()
+        .map(parse_event_attribute)
+        .collect::<Result<BTreeMap<_, _>>>()?;
+
     // Parse the Cosmos address
     let address = query_client
         .chain_config
@@ -697,6 +731,7 @@ pub fn set_cosmos_trigger(
             address,
             chain,
             event_type,
+            attributes,
         };
         workflow.trigger = TriggerBuilder::Trigger(trigger.clone());
 
@@ -718,22 +753,14 @@ pub fn set_evm_trigger(
     address: alloy_primitives::Address,
     chain: ChainKey,
     event_hash_str: String,
+    additional_event_hash_strs: Vec<String>,
+    transform: Vec<EvmLogField>,
 ) -> Result<WorkflowTriggerResult> {
-    // Order the match cases from most explicit to event parsing:
-    // 1. 0x-prefixed hex string
-    // 2. raw hex string (no 0x)
-    // 3. event name to be parsed into signature
-    let trigger_event_name = match event_hash_str {
-        name if name.starts_with("0x") => name,
-        name if const_hex::const_check(name.as_bytes()).is_ok() => name,
-        name => Event::parse(&name)
-            .context("Invalid event signature format")?
-            .selector()
-            .to_string(),
-    };
-
-    let mut event_hash: [u8; 32] = [0; 32];
-    event_hash.copy_from_slice(&const_hex::decode(trigger_event_name)?);
+    let event_hash = parse_event_hash(event_hash_str)?;
+    let additional_event_hashes = additional_event_hash_strs
+        .into_iter()
+        .map(parse_event_hash)
+        .collect::<Result<Vec<_>>>()?;
 
     modify_service_file(file_path, |mut service| {
         // Check if the workflow exists
@@ -745,7 +772,9 @@ pub fn set_evm_trigger(
         let trigger = Trigger::EvmContractEvent {
             address,
             chain,
-            event_hash: ByteArray::new(event_hash),
+            event_hash,
+            additional_event_hashes,
+            transform,
         };
         workflow.trigger = TriggerBuilder::Trigger(trigger.clone());
 
@@ -760,6 +789,36 @@ pub fn set_evm_trigger(
     })
 }
 
+fn parse_event_attribute(attribute_str: String) -> Result<(String, String)> {
+    match attribute_str.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(anyhow!(
+            "Invalid event attribute '{}', expected key=value",
+            attribute_str
+        )),
+    }
+}
+
+fn parse_event_hash(event_hash_str: String) -> Result<ByteArray<32>> {
+    // Order the match cases from most explicit to event parsing:
+    // 1. 0x-prefixed hex string
+    // 2. raw hex string (no 0x)
+    // 3. event name to be parsed into signature
+    let trigger_event_name = match event_hash_str {
+        name if name.starts_with("0x") => name,
+        name if const_hex::const_check(name.as_bytes()).is_ok() => name,
+        name => Event::parse(&name)
+            .context("Invalid event signature format")?
+            .selector()
+            .to_string(),
+    };
+
+    let mut event_hash: [u8; 32] = [0; 32];
+    event_hash.copy_from_slice(&const_hex::decode(trigger_event_name)?);
+
+    Ok(ByteArray::new(event_hash))
+}
+
 pub fn set_block_interval_trigger(
     file_path: &Path,
     workflow_id: WorkflowId,
@@ -1052,6 +1111,12 @@ pub async fn validate_service(
                                 ));
                             }
                         }
+                        Some(AnyChainConfig::Solana(_)) => {
+                            errors.push(format!(
+                                "Workflow '{}' uses Solana chain '{}' in BlockInterval trigger, which is not supported yet",
+                                workflow_id, chain
+                            ));
+                        }
                     },
                     _ => {}
                 }
@@ -1136,6 +1201,7 @@ pub fn set_aggregator_submit(
         workflow.submit = SubmitBuilder::AggregatorBuilder(AggregatorBuilder::Aggregator {
             component: ComponentBuilder::new_unset(),
             signature_kind: SignatureKind::evm_default(),
+            targets: Vec::new(),
         });
 
         Ok((
@@ -797,6 +797,7 @@ async fn test_workflow_trigger_operations() {
         cosmos_address.clone(),
         cosmos_chain.clone(),
         cosmos_event.clone(),
+        Vec::new(),
     )
     .unwrap();
 
@@ -806,6 +807,7 @@ async fn test_workflow_trigger_operations() {
         address,
         chain,
         event_type,
+        ..
     } = &cosmos_result.trigger
     {
         assert_eq!(address.to_string(), cosmos_address);
@@ -847,6 +849,7 @@ async fn test_workflow_trigger_operations() {
         neutron_address,
         cosmos_chain.clone(),
         cosmos_event.clone(),
+        Vec::new(),
     );
 
     // This should fail with a prefix validation error
@@ -868,6 +871,8 @@ async fn test_workflow_trigger_operations() {
         evm_address,
         evm_chain.clone(),
         evm_event_hash.clone(),
+        vec!["Approval(address,address,uint256)".to_string()],
+        Vec::new(),
     )
     .unwrap();
 
@@ -877,6 +882,8 @@ async fn test_workflow_trigger_operations() {
         address,
         chain,
         event_hash,
+        additional_event_hashes,
+        ..
     } = &evm_result.trigger
     {
         assert_eq!(*address, evm_address);
@@ -884,6 +891,14 @@ async fn test_workflow_trigger_operations() {
         // For event_hash we'll need to check the bytes match what we expect
         let expected_hash_bytes = hex::decode(evm_event_hash.trim_start_matches("0x")).unwrap();
         assert_eq!(event_hash.as_slice(), &expected_hash_bytes[..]);
+        // signatures are hashed, keccak256("Approval(address,address,uint256)")
+        assert_eq!(
+            additional_event_hashes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925".to_string()]
+        );
     } else {
         panic!("Expected EvmContractEvent trigger");
     }
@@ -899,6 +914,7 @@ async fn test_workflow_trigger_operations() {
             address,
             chain,
             event_hash,
+            ..
         } = trigger
         {
             assert_eq!(*address, evm_address);
@@ -920,6 +936,8 @@ async fn test_workflow_trigger_operations() {
         evm_address,
         evm_chain.clone(),
         evm_event_hash.clone(),
+        Vec::new(),
+        Vec::new(),
     );
 
     // Verify it returns an error with appropriate message
@@ -937,6 +955,7 @@ async fn test_workflow_trigger_operations() {
         invalid_cosmos_address,
         cosmos_chain.clone(),
         cosmos_event.clone(),
+        Vec::new(),
     );
     assert!(invalid_cosmos_result.is_err());
     assert!(invalid_cosmos_result
@@ -1029,11 +1048,14 @@ async fn test_service_validation() {
         address: evm_address,
         chain: evm_chain.clone(),
         event_hash: wavs_types::ByteArray::new([1u8; 32]),
+        additional_event_hashes: Vec::new(),
+        transform: Vec::new(),
     };
 
     let submit = Submit::Aggregator {
         component: Box::new(component.clone()),
         signature_kind: SignatureKind::evm_default(),
+        targets: Vec::new(),
     };
 
     // Create service manager
@@ -1051,6 +1073,12 @@ async fn test_service_validation() {
                 trigger: TriggerBuilder::Trigger(trigger.clone()),
                 component: ComponentBuilder::Component(component.clone()),
                 submit: SubmitBuilder::Submit(submit.clone()),
+                submit_targets: Vec::new(),
+                response_mapping: Default::default(),
+                submit_condition: None,
+                priority: Default::default(),
+                observe_only: false,
+                min_interval_ms: None,
             },
         );
 
@@ -1083,6 +1111,12 @@ async fn test_service_validation() {
                 trigger: TriggerBuilder::Trigger(trigger.clone()),
                 component: ComponentBuilder::Component(component.clone()),
                 submit: SubmitBuilder::Submit(submit.clone()),
+                submit_targets: Vec::new(),
+                response_mapping: Default::default(),
+                submit_condition: None,
+                priority: Default::default(),
+                observe_only: false,
+                min_interval_ms: None,
             },
         );
 
@@ -1094,6 +1128,12 @@ async fn test_service_validation() {
                 trigger: TriggerBuilder::Trigger(trigger.clone()),
                 component: ComponentBuilder::new_unset(),
                 submit: SubmitBuilder::Submit(submit.clone()),
+                submit_targets: Vec::new(),
+                response_mapping: Default::default(),
+                submit_condition: None,
+                priority: Default::default(),
+                observe_only: false,
+                min_interval_ms: None,
             },
         );
 
@@ -1135,6 +1175,12 @@ async fn test_service_validation() {
                 trigger: TriggerBuilder::Trigger(trigger.clone()),
                 component: ComponentBuilder::Component(zero_fuel_component),
                 submit: SubmitBuilder::Submit(submit.clone()),
+                submit_targets: Vec::new(),
+                response_mapping: Default::default(),
+                submit_condition: None,
+                priority: Default::default(),
+                observe_only: false,
+                min_interval_ms: None,
             },
         );
 
@@ -1173,6 +1219,12 @@ async fn test_service_validation() {
                 trigger: TriggerBuilder::Trigger(trigger.clone()),
                 component: ComponentBuilder::Component(component.clone()),
                 submit: SubmitBuilder::Submit(submit.clone()),
+                submit_targets: Vec::new(),
+                response_mapping: Default::default(),
+                submit_condition: None,
+                priority: Default::default(),
+                observe_only: false,
+                min_interval_ms: None,
             },
         );
 
@@ -1213,6 +1265,12 @@ async fn test_service_validation() {
                 trigger: TriggerBuilder::Trigger(trigger.clone()),
                 component: ComponentBuilder::Component(env_component),
                 submit: SubmitBuilder::Submit(submit.clone()),
+                submit_targets: Vec::new(),
+                response_mapping: Default::default(),
+                submit_condition: None,
+                priority: Default::default(),
+                observe_only: false,
+                min_interval_ms: None,
             },
         );
 
@@ -1250,6 +1308,12 @@ async fn test_service_validation() {
                 trigger: TriggerBuilder::Builder(Builder::Unset),
                 component: ComponentBuilder::Component(component.clone()),
                 submit: SubmitBuilder::Submit(submit.clone()),
+                submit_targets: Vec::new(),
+                response_mapping: Default::default(),
+                submit_condition: None,
+                priority: Default::default(),
+                observe_only: false,
+                min_interval_ms: None,
             },
         );
 
@@ -1287,6 +1351,12 @@ async fn test_service_validation() {
                 trigger: TriggerBuilder::Trigger(trigger.clone()),
                 component: ComponentBuilder::Component(component.clone()),
                 submit: SubmitBuilder::Builder(Builder::Unset),
+                submit_targets: Vec::new(),
+                response_mapping: Default::default(),
+                submit_condition: None,
+                priority: Default::default(),
+                observe_only: false,
+                min_interval_ms: None,
             },
         );
 
@@ -1324,6 +1394,12 @@ async fn test_service_validation() {
                 trigger: TriggerBuilder::Trigger(trigger.clone()),
                 component: ComponentBuilder::Component(component.clone()),
                 submit: SubmitBuilder::Submit(submit.clone()),
+                submit_targets: Vec::new(),
+                response_mapping: Default::default(),
+                submit_condition: None,
+                priority: Default::default(),
+                observe_only: false,
+                min_interval_ms: None,
             },
         );
 
@@ -1474,6 +1550,7 @@ fn test_set_aggregator_submit() {
         SubmitBuilder::AggregatorBuilder(AggregatorBuilder::Aggregator {
             component,
             signature_kind,
+            targets: _,
         }) => {
             assert!(component.is_unset());
             assert_eq!(*signature_kind, SignatureKind::evm_default());
@@ -1555,6 +1632,7 @@ async fn test_modify_aggregator_component() {
         SubmitBuilder::AggregatorBuilder(AggregatorBuilder::Aggregator {
             component,
             signature_kind: _,
+            targets: _,
         }) => {
             assert!(component.is_set());
             if let ComponentBuilder::Component(comp) = component {
@@ -1569,6 +1647,7 @@ async fn test_modify_aggregator_component() {
         SubmitBuilder::Submit(Submit::Aggregator {
             component,
             signature_kind: _,
+            targets: _,
         }) => {
             // This might be matched first due to enum ordering
             match &component.source {
@@ -1644,7 +1723,14 @@ fn test_aggregator_validation() {
         submit: SubmitBuilder::Submit(Submit::Aggregator {
             component: Box::new(component.clone()),
             signature_kind: SignatureKind::evm_default(),
+            targets: Vec::new(),
         }),
+        submit_targets: Vec::new(),
+        response_mapping: Default::default(),
+        submit_condition: None,
+        priority: Default::default(),
+        observe_only: false,
+        min_interval_ms: None,
     };
 
     service.workflows.insert(workflow_id.clone(), workflow);
@@ -1669,7 +1755,14 @@ fn test_aggregator_validation() {
         submit: SubmitBuilder::Submit(Submit::Aggregator {
             component: Box::new(invalid_component),
             signature_kind: SignatureKind::evm_default(),
+            targets: Vec::new(),
         }),
+        submit_targets: Vec::new(),
+        response_mapping: Default::default(),
+        submit_condition: None,
+        priority: Default::default(),
+        observe_only: false,
+        min_interval_ms: None,
     };
 
     service
@@ -1701,7 +1794,14 @@ fn test_aggregator_validation() {
         submit: SubmitBuilder::Submit(Submit::Aggregator {
             component: Box::new(invalid_env_component),
             signature_kind: SignatureKind::evm_default(),
+            targets: Vec::new(),
         }),
+        submit_targets: Vec::new(),
+        response_mapping: Default::default(),
+        submit_condition: None,
+        priority: Default::default(),
+        observe_only: false,
+        min_interval_ms: None,
     };
 
     service.workflows.insert(workflow_id, invalid_env_workflow);
@@ -1714,6 +1814,71 @@ fn test_aggregator_validation() {
     assert_eq!(env_errors.len(), 1, "Should have env key validation error");
 }
 
+#[test]
+fn test_component_source_and_chain_validation() {
+    init_tracing_tests();
+
+    let evm_address = alloy_primitives::Address::parse_checksummed(
+        "0x00000000219ab540356cBB839Cbe05303d7705Fa",
+        None,
+    )
+    .unwrap();
+
+    let component = Component::new(ComponentSource::Download {
+        uri: iri_string::types::UriString::try_from("ftp://example.com/component.wasm".to_string())
+            .unwrap(),
+        digest: ComponentDigest::hash(b"component"),
+    });
+
+    let mut workflows = BTreeMap::new();
+    workflows.insert(
+        WorkflowId::default(),
+        WorkflowBuilder {
+            // an EVM event trigger pointed at a Cosmos chain
+            trigger: TriggerBuilder::Trigger(Trigger::EvmContractEvent {
+                address: evm_address,
+                chain: ChainKey::from_str("cosmos:wasmd").unwrap(),
+                event_hash: wavs_types::ByteArray::new([1u8; 32]),
+                additional_event_hashes: Vec::new(),
+                transform: Vec::new(),
+            }),
+            component: ComponentBuilder::Component(component),
+            submit: SubmitBuilder::Submit(Submit::None),
+            submit_targets: Vec::new(),
+            response_mapping: Default::default(),
+            submit_condition: None,
+            priority: Default::default(),
+            observe_only: false,
+            min_interval_ms: None,
+        },
+    );
+
+    let service = ServiceBuilder {
+        name: "Test Service".to_string(),
+        workflows,
+        status: ServiceStatus::Active,
+        manager: ServiceManagerBuilder::Manager(ServiceManager::Cosmos {
+            chain: ChainKey::from_str("evm:1").unwrap(),
+            address: layer_climb::prelude::CosmosAddr::new_unchecked(
+                "layer1hd63rr4lnmnwlvp3lhs4qlhxyw2ukftfr6jyvg".to_string(),
+                "layer".len(),
+            ),
+        }),
+    };
+
+    let errors = service.validate();
+    assert_eq!(errors.len(), 3, "unexpected errors: {errors:?}");
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("unsupported uri scheme 'ftp'")));
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("trigger on chain 'cosmos:wasmd'")));
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("chain 'evm:1' is not a Cosmos chain")));
+}
+
 #[tokio::test]
 async fn test_config_file_functionality() {
     // Test comprehensive config file functionality including parsing and end-to-end integration
@@ -86,25 +86,43 @@ impl std::fmt::Display for WorkflowTriggerResult {
                 address,
                 chain,
                 event_type,
+                attributes,
             } => {
                 writeln!(f, "  Trigger Type: Cosmos Contract Event")?;
                 writeln!(f, "    Address:    {}", address)?;
                 writeln!(f, "    Chain:      {}", chain)?;
                 writeln!(f, "    Event Type: {}", event_type)?;
+                for (key, value) in attributes {
+                    writeln!(f, "    Attribute:  {}={}", key, value)?;
+                }
             }
             Trigger::EvmContractEvent {
                 address,
                 chain,
                 event_hash,
+                additional_event_hashes,
+                transform,
             } => {
                 writeln!(f, "  Trigger Type: EVM Contract Event")?;
                 writeln!(f, "    Address:    {}", address)?;
                 writeln!(f, "    Chain:      {}", chain)?;
                 writeln!(f, "    Event Hash: {}", event_hash)?;
+                for additional in additional_event_hashes {
+                    writeln!(f, "    Also Event: {}", additional)?;
+                }
+                for field in transform {
+                    writeln!(f, "    Payload:    {}", field)?;
+                }
             }
             Trigger::Manual => {
                 writeln!(f, "  Trigger Type: Manual")?;
             }
+            Trigger::Webhook { signature_secret } => {
+                writeln!(f, "  Trigger Type: Webhook")?;
+                if let Some(secret) = signature_secret {
+                    writeln!(f, "    Signed With: secret {}", secret)?;
+                }
+            }
             Trigger::BlockInterval {
                 chain,
                 n_blocks,
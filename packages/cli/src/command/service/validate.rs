@@ -17,6 +17,7 @@ pub async fn validate_workflow_trigger(
             address,
             chain,
             event_type,
+            ..
         } => {
             // Use same validation as in set_cosmos_trigger
             if let Err(err) = query_client
@@ -169,6 +170,7 @@ pub async fn validate_contracts_exist(
             // Other trigger types don't need contract validation
             Trigger::Cron { .. }
             | Trigger::Manual
+            | Trigger::Webhook { .. }
             | Trigger::BlockInterval { .. }
             | Trigger::AtProtoEvent { .. }
             | Trigger::HypercoreAppend { .. } => {}
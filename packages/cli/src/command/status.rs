@@ -0,0 +1,65 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use wavs_types::{ServiceId, WorkflowId, WorkflowStatusResponse};
+
+use crate::{clients::HttpClient, config::Config};
+
+#[derive(Serialize)]
+#[serde(transparent)]
+pub struct WorkflowStatus {
+    pub status: WorkflowStatusResponse,
+}
+
+impl std::fmt::Display for WorkflowStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = &self.status;
+        writeln!(f, "Workflow status")?;
+        writeln!(f, "  Service ID:  {}", status.service_id)?;
+        writeln!(f, "  Workflow ID: {}", status.workflow_id)?;
+        writeln!(f, "  Successes:   {}", status.total_successes)?;
+        writeln!(f, "  Failures:    {}", status.total_failures)?;
+
+        if status.recent_outcomes.is_empty() {
+            write!(f, "  No recorded executions")
+        } else {
+            write!(f, "  Recent outcomes (newest first):")?;
+            for outcome in &status.recent_outcomes {
+                write!(f, "\n    [{}] ", outcome.timestamp.as_nanos())?;
+                match &outcome.error {
+                    None => write!(f, "ok, {} response(s)", outcome.responses)?,
+                    Some(error) => write!(f, "failed: {}", error)?,
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+pub struct WorkflowStatusArgs {
+    pub service_id: ServiceId,
+    pub workflow_id: WorkflowId,
+    pub limit: Option<usize>,
+}
+
+impl WorkflowStatus {
+    pub async fn run(
+        config: &Config,
+        WorkflowStatusArgs {
+            service_id,
+            workflow_id,
+            limit,
+        }: WorkflowStatusArgs,
+    ) -> Result<Self> {
+        let http_client = HttpClient::new(config.wavs_endpoint.clone());
+
+        let status = http_client
+            .get_workflow_status(&service_id, &workflow_id, limit)
+            .await
+            .context(format!(
+                "Failed to get status of workflow '{}' in service '{}' from WAVS endpoint '{}'",
+                workflow_id, service_id, config.wavs_endpoint
+            ))?;
+
+        Ok(Self { status })
+    }
+}
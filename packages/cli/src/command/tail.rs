@@ -0,0 +1,163 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use wavs_types::{ServiceId, Timestamp};
+
+use crate::{clients::HttpClient, config::Config};
+
+pub struct Tail;
+
+pub struct TailArgs {
+    pub service_id: ServiceId,
+    /// Seconds of already recorded events to show first
+    pub since: Option<u64>,
+}
+
+impl Tail {
+    const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+    /// Prints each event as a line of JSON until interrupted
+    pub async fn run(config: &Config, TailArgs { service_id, since }: TailArgs) -> Result<()> {
+        let http_client = HttpClient::new(config.wavs_endpoint.clone());
+
+        let since = since.map(|secs| {
+            Timestamp::from_nanos(
+                Timestamp::now()
+                    .as_nanos()
+                    .saturating_sub(secs.saturating_mul(1_000_000_000)),
+            )
+        });
+
+        let mut last_event_id = None;
+        let mut connected_once = false;
+        let mut backoff = Duration::from_secs(1);
+
+        loop {
+            match http_client
+                .service_events(&service_id, since, last_event_id)
+                .await
+            {
+                Ok(mut response) => {
+                    connected_once = true;
+                    backoff = Duration::from_secs(1);
+
+                    let mut parser = SseParser::default();
+                    loop {
+                        match response.chunk().await {
+                            Ok(Some(chunk)) => {
+                                for message in parser.push(&chunk) {
+                                    if let Some(id) = message.id.and_then(|id| id.parse().ok()) {
+                                        last_event_id = Some(id);
+                                    }
+                                    println!("{}", message.data);
+                                }
+                            }
+                            Ok(None) => {
+                                eprintln!("Event stream closed by the node, reconnecting");
+                                break;
+                            }
+                            Err(e) => {
+                                eprintln!("Event stream interrupted ({e}), reconnecting");
+                                break;
+                            }
+                        }
+                    }
+                }
+                // nothing to reconnect to if the first attempt fails, e.g. for an unknown service
+                Err(e) if !connected_once => {
+                    return Err(e).context(format!(
+                        "Failed to stream events of service '{}' from WAVS endpoint '{}'",
+                        service_id, config.wavs_endpoint
+                    ));
+                }
+                Err(e) => {
+                    eprintln!("Failed to reconnect ({e}), retrying in {backoff:?}");
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(Self::MAX_BACKOFF);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct SseMessage {
+    id: Option<String>,
+    data: String,
+}
+
+/// Splits a `text/event-stream` body into messages, however it's chunked
+#[derive(Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    id: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    fn push(&mut self, chunk: &[u8]) -> Vec<SseMessage> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut messages = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    messages.push(SseMessage {
+                        id: self.id.take(),
+                        data: self.data.join("\n"),
+                    });
+                    self.data.clear();
+                }
+                continue;
+            }
+
+            // lines starting with a colon are comments, like keep-alives
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "data" => self.data.push(value.to_string()),
+                "id" => self.id = Some(value.to_string()),
+                _ => {}
+            }
+        }
+
+        messages
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_chunked_events() {
+        let body = b": keep-alive\n\nid: 1\nevent: trigger_received\ndata: {\"id\":1}\n\nid: 2\r\ndata: {\"id\":2}\r\n\r\n";
+
+        // however the body is split up, the same messages come out
+        for chunk_size in [1, 3, 7, body.len()] {
+            let mut parser = SseParser::default();
+            let messages: Vec<SseMessage> = body
+                .chunks(chunk_size)
+                .flat_map(|chunk| parser.push(chunk))
+                .collect();
+
+            assert_eq!(
+                messages,
+                vec![
+                    SseMessage {
+                        id: Some("1".to_string()),
+                        data: "{\"id\":1}".to_string(),
+                    },
+                    SseMessage {
+                        id: Some("2".to_string()),
+                        data: "{\"id\":2}".to_string(),
+                    },
+                ]
+            );
+        }
+    }
+}
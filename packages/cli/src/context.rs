@@ -4,7 +4,10 @@ use crate::{args::CliArgs, config::Config, deploy::CommandDeployResult};
 use alloy_provider::Provider;
 use anyhow::{anyhow, Context, Result};
 use layer_climb::prelude::*;
-use utils::{config::EvmChainConfigExt, evm_client::EvmSigningClient};
+use utils::{
+    config::EvmChainConfigExt,
+    evm_client::{EvmSigningClient, EvmSigningClientConfig},
+};
 use wavs_types::{AnyChainConfig, ChainKey, ChainKeyId, Credential};
 
 use crate::{args::Command, deploy::Deployment};
@@ -15,6 +18,7 @@ pub struct CliContext {
     pub save_deployment: bool,
     pub quiet_results: bool,
     pub json: bool,
+    pub verbose: bool,
 }
 
 impl CliContext {
@@ -48,6 +52,7 @@ impl CliContext {
             save_deployment: args.save_deployment.unwrap_or(true),
             quiet_results: args.quiet_results.unwrap_or_default(),
             json,
+            verbose: args.verbose.unwrap_or_default(),
         })
     }
 
@@ -63,14 +68,17 @@ impl CliContext {
             .clone()
             .build(chain_id);
 
-        let client_config = chain_config.signing_client_config(
-            self.config
-                .evm_credential
-                .clone()
-                .context("missing evm_credential")?,
-        )?;
+        let client_config = self.with_rpc_logging(
+            chain_config.signing_client_config(
+                self.config
+                    .evm_credential
+                    .clone()
+                    .context("missing evm_credential")?,
+            )?,
+        );
 
         let evm_client = EvmSigningClient::new(client_config).await?;
+        evm_client.verify_chain_id(&chain_config.chain_id).await?;
 
         Ok(evm_client)
     }
@@ -99,12 +107,25 @@ impl CliContext {
             )
         });
 
-        let client_config = chain_config.signing_client_config(credential)?;
+        let client_config = self.with_rpc_logging(chain_config.signing_client_config(credential)?);
         let evm_client = EvmSigningClient::new(client_config).await?;
+        evm_client.verify_chain_id(&chain_config.chain_id).await?;
 
         Ok(evm_client)
     }
 
+    /// Applies `--verbose`
+    pub fn with_rpc_logging(
+        &self,
+        client_config: EvmSigningClientConfig,
+    ) -> EvmSigningClientConfig {
+        if self.verbose {
+            client_config.with_rpc_logging()
+        } else {
+            client_config
+        }
+    }
+
     pub async fn new_cosmos_client(&self, chain_id: ChainKeyId) -> Result<SigningClient> {
         let chain_config = self
             .config
@@ -167,6 +188,11 @@ impl CliContext {
                     .contract_info(&address)
                     .await
                     .is_ok(),
+                AnyChainConfig::Solana(_) => {
+                    return Err(anyhow!(
+                        "checking contracts on solana chain {chain} is not supported yet"
+                    ));
+                }
             },
         )
     }
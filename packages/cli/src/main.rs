@@ -4,6 +4,7 @@ use anyhow::anyhow;
 use anyhow::Context;
 use anyhow::Result;
 use clap::Parser;
+use iri_string::types::UriString;
 use layer_climb::prelude::cosmos_hub_derivation;
 use layer_climb::prelude::KeySigner;
 use layer_climb::signing::SigningClient;
@@ -14,12 +15,17 @@ use utils::{
     service::fetch_service,
 };
 use wavs_cli::{
-    args::Command,
+    args::{Command, ComponentFileCommand},
     command::{
+        decode_trigger::{DecodeTrigger, DecodeTriggerArgs},
         deploy_service::{DeployService, DeployServiceArgs, SetServiceUriArgs},
         exec_aggregator::{ExecAggregator, ExecAggregatorArgs},
         exec_component::{ExecComponent, ExecComponentArgs},
+        inspect_component::{InspectComponent, InspectComponentArgs},
+        replay::{Replay, ReplayArgs},
         service::handle_service_command,
+        status::{WorkflowStatus, WorkflowStatusArgs},
+        tail::{Tail, TailArgs},
         upload_component::{UploadComponent, UploadComponentArgs},
     },
     context::CliContext,
@@ -49,7 +55,8 @@ async fn new_evm_client_with_credential(
         .clone()
         .build(chain_id);
 
-    let mut client_config = chain_config.signing_client_config(credential.clone())?;
+    let mut client_config =
+        ctx.with_rpc_logging(chain_config.signing_client_config(credential.clone())?);
     if let Some(hd_index) = hd_index {
         client_config.hd_index = Some(hd_index);
     }
@@ -143,9 +150,11 @@ async fn main() {
         Command::DeployService {
             service_uri,
             set_uri,
+            deployment_id,
+            local_component,
             args: _,
         } => {
-            let service = fetch_service(&service_uri, &ctx.config.ipfs_gateway)
+            let mut service = fetch_service(&service_uri, &ctx.config.ipfs_gateway)
                 .await
                 .context(format!(
                     "Failed to fetch service from URL '{}' using gateway '{}'",
@@ -153,6 +162,25 @@ async fn main() {
                 ))
                 .unwrap();
 
+            // the node reads the service from the uri on the service manager,
+            // so a rewritten service has to be saved and its new uri set
+            let (service_uri, component_uploads) = if local_component.is_empty() {
+                (service_uri, Vec::new())
+            } else {
+                if !set_uri {
+                    eprintln!("--local-component requires --set-uri");
+                    std::process::exit(1);
+                }
+
+                let uploads =
+                    DeployService::upload_local_components(&ctx, &mut service, &local_component)
+                        .await
+                        .unwrap();
+                let saved_uri = DeployService::save_service(&ctx, &service).await.unwrap();
+
+                (UriString::try_from(saved_uri).unwrap(), uploads)
+            };
+
             let set_service_url_args = if set_uri {
                 match service.manager {
                     wavs_types::ServiceManager::Evm { ref chain, .. } => {
@@ -176,6 +204,8 @@ async fn main() {
                 DeployServiceArgs {
                     service_manager: service.manager.clone(),
                     set_service_url_args,
+                    deployment_id,
+                    component_uploads,
                 },
             )
             .await
@@ -419,5 +449,120 @@ async fn main() {
 
             ctx.handle_display_result(res);
         }
+        Command::Status {
+            service,
+            workflow,
+            limit,
+            args: _,
+        } => {
+            let res = match WorkflowStatus::run(
+                &ctx.config,
+                WorkflowStatusArgs {
+                    service_id: service,
+                    workflow_id: workflow,
+                    limit,
+                },
+            )
+            .await
+            {
+                Ok(result) => result,
+                Err(e) => {
+                    eprintln!("Failed to get workflow status: {e:?}");
+                    std::process::exit(1);
+                }
+            };
+
+            if ctx.json {
+                println!("{}", serde_json::to_string_pretty(&res).unwrap());
+            } else {
+                ctx.handle_display_result(res);
+            }
+        }
+        Command::Tail {
+            service_id,
+            since,
+            args: _,
+        } => {
+            if let Err(e) = Tail::run(&ctx.config, TailArgs { service_id, since }).await {
+                eprintln!("Failed to tail service events: {e:?}");
+                std::process::exit(1);
+            }
+        }
+        Command::DecodeTrigger { file, abi, args: _ } => {
+            let res = match DecodeTrigger::run(DecodeTriggerArgs { file, abi }) {
+                Ok(result) => result,
+                Err(e) => {
+                    eprintln!("Failed to decode trigger: {e:?}");
+                    std::process::exit(1);
+                }
+            };
+
+            if ctx.json {
+                println!("{}", serde_json::to_string_pretty(&res).unwrap());
+            } else {
+                ctx.handle_display_result(res);
+            }
+        }
+        Command::Replay {
+            component,
+            trigger,
+            service,
+            output_file,
+            args: _,
+        } => {
+            let res = match Replay::run(
+                &ctx.config,
+                ReplayArgs {
+                    component_path: component,
+                    trigger_path: trigger,
+                    service_path: service,
+                },
+            )
+            .await
+            {
+                Ok(result) => result,
+                Err(e) => {
+                    eprintln!("Failed to replay trigger: {e:?}");
+                    std::process::exit(1);
+                }
+            };
+
+            if let Some(path) = output_file {
+                if let Err(e) = write_output_file(&res.responses, &path) {
+                    eprintln!("Failed to write replay output: {e}");
+                    std::process::exit(1);
+                }
+            }
+
+            if ctx.json {
+                println!("{}", serde_json::to_string_pretty(&res).unwrap());
+            } else {
+                ctx.handle_display_result(res);
+            }
+        }
+        Command::Component {
+            command: ComponentFileCommand::Inspect { component_path },
+            args: _,
+        } => {
+            let res = match InspectComponent::run(InspectComponentArgs { component_path }) {
+                Ok(result) => result,
+                Err(e) => {
+                    eprintln!("Failed to inspect component: {e:?}");
+                    std::process::exit(1);
+                }
+            };
+
+            if ctx.json {
+                println!("{}", serde_json::to_string_pretty(&res).unwrap());
+            } else {
+                ctx.handle_display_result(res);
+            }
+        }
+        Command::Schema { kind, args: _ } => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&kind.json_schema()).unwrap()
+            );
+        }
     }
 }
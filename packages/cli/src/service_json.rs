@@ -1,10 +1,15 @@
-use std::{num::NonZeroU64, str::FromStr};
+use std::{collections::HashSet, num::NonZeroU64, str::FromStr};
 
 use alloy_primitives::Address;
 use cron::Schedule;
+use utils::{
+    service::{ipfs_to_gateway_url, DEFAULT_IPFS_GATEWAY},
+    wkg::oci::OciReference,
+};
 use wavs_types::{
-    AggregatorBuilder, ServiceBuilder, ServiceManagerBuilder, Submit, SubmitBuilder, Timestamp,
-    Trigger, TriggerBuilder, WAVS_ENV_PREFIX,
+    AggregatorBuilder, ChainKey, ChainKeyNamespace, ComponentSource, ServiceBuilder,
+    ServiceManager, ServiceManagerBuilder, Submit, SubmitBuilder, SubmitTarget, SubmitTargetAuth,
+    Timestamp, Trigger, TriggerBuilder, WorkflowId, WAVS_ENV_PREFIX,
 };
 
 pub trait ServiceJsonExt {
@@ -23,6 +28,8 @@ impl ServiceJsonExt for ServiceBuilder {
             if workflow.component.is_unset() {
                 errors.push(format!("Workflow '{}' has an unset component", workflow_id));
             } else if let Some(component) = workflow.component.as_component() {
+                validate_component_source(workflow_id, "component", &component.source, &mut errors);
+
                 if let Some(limit) = component.fuel_limit {
                     if limit == 0 {
                         errors.push(format!(
@@ -47,7 +54,16 @@ impl ServiceJsonExt for ServiceBuilder {
                     errors.push(format!("Workflow '{}' has an unset trigger", workflow_id));
                 }
                 TriggerBuilder::Trigger(trigger) => match trigger {
-                    Trigger::CosmosContractEvent { event_type, .. } => {
+                    Trigger::CosmosContractEvent {
+                        chain, event_type, ..
+                    } => {
+                        validate_chain_namespace(
+                            workflow_id,
+                            chain,
+                            &[ChainKeyNamespace::COSMOS, ChainKeyNamespace::DEV],
+                            &mut errors,
+                        );
+
                         if event_type.is_empty() {
                             errors.push(format!(
                                 "Workflow '{}' has an empty event type in Cosmos trigger",
@@ -57,9 +73,18 @@ impl ServiceJsonExt for ServiceBuilder {
                     }
                     Trigger::EvmContractEvent {
                         address,
-                        chain: _,
+                        chain,
                         event_hash,
+                        additional_event_hashes,
+                        ..
                     } => {
+                        validate_chain_namespace(
+                            workflow_id,
+                            chain,
+                            &[ChainKeyNamespace::EVM, ChainKeyNamespace::DEV],
+                            &mut errors,
+                        );
+
                         if let Err(err) = Address::parse_checksummed(address.to_string(), None) {
                             errors.push(format!(
                                 "Workflow '{}' has an invalid EVM address format: {}",
@@ -74,6 +99,16 @@ impl ServiceJsonExt for ServiceBuilder {
                                 event_hash.as_slice().len()
                             ));
                         }
+
+                        let mut seen = HashSet::from([*event_hash]);
+                        for additional in additional_event_hashes {
+                            if !seen.insert(*additional) {
+                                errors.push(format!(
+                                    "Workflow '{}' lists event hash {} more than once",
+                                    workflow_id, additional
+                                ));
+                            }
+                        }
                     }
                     Trigger::Cron {
                         schedule,
@@ -95,11 +130,22 @@ impl ServiceJsonExt for ServiceBuilder {
                         }
                     }
                     Trigger::BlockInterval {
-                        chain: _,
+                        chain,
                         n_blocks: _,
                         start_block,
                         end_block,
                     } => {
+                        validate_chain_namespace(
+                            workflow_id,
+                            chain,
+                            &[
+                                ChainKeyNamespace::EVM,
+                                ChainKeyNamespace::COSMOS,
+                                ChainKeyNamespace::DEV,
+                            ],
+                            &mut errors,
+                        );
+
                         if let Err(err) = validate_block_interval_config(*start_block, *end_block) {
                             errors.push(format!(
                                 "Workflow '{}' has an invalid block-interval trigger: {}",
@@ -115,7 +161,15 @@ impl ServiceJsonExt for ServiceBuilder {
                             ));
                         }
                     }
-                    Trigger::Manual | Trigger::AtProtoEvent { .. } => {}
+                    Trigger::Webhook {
+                        signature_secret: Some(secret),
+                    } if secret.trim().is_empty() => {
+                        errors.push(format!(
+                            "Workflow '{}' has an empty signature_secret in Webhook trigger",
+                            workflow_id
+                        ));
+                    }
+                    Trigger::Manual | Trigger::AtProtoEvent { .. } | Trigger::Webhook { .. } => {}
                 },
             }
 
@@ -127,17 +181,32 @@ impl ServiceJsonExt for ServiceBuilder {
                     AggregatorBuilder::Aggregator {
                         component,
                         signature_kind: _,
+                        targets: _,
                     } => {
                         if component.is_unset() {
                             errors.push(format!(
                                 "Workflow '{}' has an unset aggregator component",
                                 workflow_id
                             ));
+                        } else if let Some(component) = component.as_component() {
+                            validate_component_source(
+                                workflow_id,
+                                "aggregator component",
+                                &component.source,
+                                &mut errors,
+                            );
                         }
                     }
                 },
                 SubmitBuilder::Submit(Submit::None) => {}
                 SubmitBuilder::Submit(Submit::Aggregator { component, .. }) => {
+                    validate_component_source(
+                        workflow_id,
+                        "aggregator component",
+                        &component.source,
+                        &mut errors,
+                    );
+
                     if let Some(limit) = component.fuel_limit {
                         if limit == 0 {
                             errors.push(format!(
@@ -157,16 +226,136 @@ impl ServiceJsonExt for ServiceBuilder {
                     }
                 }
             }
+
+            for target in &workflow.submit_targets {
+                match target {
+                    SubmitTarget::Http { url, auth } => {
+                        if let Err(err) = reqwest::Url::parse(url) {
+                            errors.push(format!(
+                                "Workflow '{}' has an invalid http submit target url '{}': {}",
+                                workflow_id, url, err
+                            ));
+                        }
+
+                        let env_key = match auth {
+                            Some(SubmitTargetAuth::Bearer { env_key })
+                            | Some(SubmitTargetAuth::Header { env_key, .. }) => Some(env_key),
+                            None => None,
+                        };
+                        if let Some(env_key) = env_key {
+                            if !env_key.starts_with(WAVS_ENV_PREFIX) {
+                                errors.push(format!(
+                                    "Workflow '{}' has submit target auth environment variable '{}' that doesn't start with '{}'",
+                                    workflow_id, env_key, WAVS_ENV_PREFIX
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+
+            if !workflow.response_mapping.is_default() && workflow.submit_targets.is_empty() {
+                errors.push(format!(
+                    "Workflow '{}' sets a response mapping but has no submit targets",
+                    workflow_id
+                ));
+            }
         }
 
-        if matches!(&self.manager, ServiceManagerBuilder::Builder(_)) {
-            errors.push("Service has an unset service manager".to_owned());
+        match &self.manager {
+            ServiceManagerBuilder::Builder(_) => {
+                errors.push("Service has an unset service manager".to_owned());
+            }
+            ServiceManagerBuilder::Manager(ServiceManager::Evm { chain, .. }) => {
+                if !has_namespace(chain, &[ChainKeyNamespace::EVM, ChainKeyNamespace::DEV]) {
+                    errors.push(format!(
+                        "Service manager is an EVM contract but chain '{}' is not an EVM chain",
+                        chain
+                    ));
+                }
+            }
+            ServiceManagerBuilder::Manager(ServiceManager::Cosmos { chain, .. }) => {
+                if !has_namespace(chain, &[ChainKeyNamespace::COSMOS, ChainKeyNamespace::DEV]) {
+                    errors.push(format!(
+                        "Service manager is a Cosmos contract but chain '{}' is not a Cosmos chain",
+                        chain
+                    ));
+                }
+            }
         }
 
         errors
     }
 }
 
+/// Checks that the node will be able to fetch the component.
+/// A bare digest can't be checked here, it must already be uploaded to the node
+fn validate_component_source(
+    workflow_id: &WorkflowId,
+    label: &str,
+    source: &ComponentSource,
+    errors: &mut Vec<String>,
+) {
+    match source {
+        ComponentSource::Download { uri, .. } => match uri.scheme_str() {
+            "http" | "https" => {}
+            "ipfs" => {
+                if let Err(err) = ipfs_to_gateway_url(uri, DEFAULT_IPFS_GATEWAY) {
+                    errors.push(format!(
+                        "Workflow '{}' has a {} with an invalid ipfs uri '{}': {}",
+                        workflow_id, label, uri, err
+                    ));
+                }
+            }
+            scheme => {
+                errors.push(format!(
+                    "Workflow '{}' has a {} with unsupported uri scheme '{}', expected http, https or ipfs",
+                    workflow_id, label, scheme
+                ));
+            }
+        },
+        ComponentSource::Registry { registry } => {
+            if let Some(domain) = &registry.domain {
+                if domain.trim().is_empty() || domain.contains("://") || domain.contains('/') {
+                    errors.push(format!(
+                        "Workflow '{}' has a {} with invalid registry domain '{}', expected a host name like wa.dev",
+                        workflow_id, label, domain
+                    ));
+                }
+            }
+        }
+        ComponentSource::Oci { reference, .. } => {
+            if let Err(err) = reference.parse::<OciReference>() {
+                errors.push(format!(
+                    "Workflow '{}' has a {} with an invalid OCI reference: {}",
+                    workflow_id, label, err
+                ));
+            }
+        }
+        ComponentSource::Digest(_) => {}
+    }
+}
+
+fn validate_chain_namespace(
+    workflow_id: &WorkflowId,
+    chain: &ChainKey,
+    expected: &[&str],
+    errors: &mut Vec<String>,
+) {
+    if !has_namespace(chain, expected) {
+        errors.push(format!(
+            "Workflow '{}' has a trigger on chain '{}', but this trigger needs a chain in one of these namespaces: {}",
+            workflow_id,
+            chain,
+            expected.join(", ")
+        ));
+    }
+}
+
+fn has_namespace(chain: &ChainKey, expected: &[&str]) -> bool {
+    expected.contains(&chain.namespace.as_str())
+}
+
 pub fn validate_cron_config(
     start_time: Option<Timestamp>,
     end_time: Option<Timestamp>,
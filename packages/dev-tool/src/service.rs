@@ -54,6 +54,7 @@ pub fn create_service(sleep_ms: Option<u64>) -> Service {
                     },
                     fuel_limit: Some(u64::MAX),
                     time_limit_seconds: Some(100),
+                    max_memory_bytes: None,
                     config: match sleep_ms {
                         Some(sleep) => BTreeMap::from([
                             ("sleep-kind".to_string(), "hotloop".to_string()),
@@ -62,6 +63,7 @@ pub fn create_service(sleep_ms: Option<u64>) -> Service {
                         None => BTreeMap::new(),
                     },
                     env_keys: std::collections::BTreeSet::new(),
+                    secrets: std::collections::BTreeSet::new(),
                 },
                 // Use aggregator submit so the submission manager produces packets
                 submit: Submit::Aggregator {
@@ -75,14 +77,25 @@ pub fn create_service(sleep_ms: Option<u64>) -> Service {
                         },
                         fuel_limit: None,
                         time_limit_seconds: None,
+                        max_memory_bytes: None,
                         config: BTreeMap::new(),
                         env_keys: std::collections::BTreeSet::new(),
+                        secrets: std::collections::BTreeSet::new(),
                     }),
                     signature_kind: SignatureKind::evm_default(),
+                    targets: Vec::new(),
                 },
+                submit_targets: Vec::new(),
+                response_mapping: Default::default(),
+                submit_condition: None,
+                priority: Default::default(),
+                observe_only: false,
+                min_interval_ms: None,
             },
         )]),
         status: wavs_types::ServiceStatus::Active,
         manager: SERVICE_MANAGER.clone(),
+        max_concurrent_executions: None,
+        execution_timeout_secs: None,
     }
 }
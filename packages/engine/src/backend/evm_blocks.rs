@@ -0,0 +1,291 @@
+//! Block lookups for `host::get-evm-block`, so components don't need to set up their own
+//! provider just to read a block header.
+//! Like the trigger stream's timestamp backfill, results are kept in a small LRU, shared
+//! by every instance since a burst of triggers usually asks about the same few blocks.
+//! Blocks are only cached by hash, after a reorg the same number can point to another block.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use alloy_primitives::B256;
+use alloy_provider::Provider;
+use alloy_rpc_types_eth::BlockNumberOrTag;
+use async_trait::async_trait;
+use lru::LruCache;
+use tokio::runtime::{Handle, RuntimeFlavor};
+use utils::{
+    config::EvmChainConfigExt,
+    evm_client::{EvmEndpoint, EvmQueryClient},
+    tls::ChainTls,
+};
+use wavs_types::{ChainKey, EvmChainConfig};
+
+const CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(64).unwrap();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EvmBlockId {
+    Hash(B256),
+    Number(u64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvmBlockHeader {
+    pub number: u64,
+    pub hash: B256,
+    pub parent_hash: B256,
+    pub timestamp: u64,
+}
+
+#[async_trait]
+pub trait EvmBlockQuery: Send + Sync {
+    /// `None` if the node doesn't know about the block
+    async fn block(
+        &self,
+        chain: &ChainKey,
+        config: &EvmChainConfig,
+        block: EvmBlockId,
+    ) -> anyhow::Result<Option<EvmBlockHeader>>;
+}
+
+/// Queries through the chain's first query endpoint, with one client per chain
+#[derive(Default)]
+pub struct EndpointBlockQuery {
+    chain_tls: ChainTls,
+    clients: RwLock<HashMap<ChainKey, EvmQueryClient>>,
+}
+
+impl EndpointBlockQuery {
+    pub fn new(chain_tls: ChainTls) -> Self {
+        Self {
+            chain_tls,
+            clients: RwLock::new(HashMap::new()),
+        }
+    }
+
+    async fn client(
+        &self,
+        chain: &ChainKey,
+        config: &EvmChainConfig,
+    ) -> anyhow::Result<EvmQueryClient> {
+        if let Some(client) = self.clients.read().unwrap().get(chain) {
+            return Ok(client.clone());
+        }
+
+        let endpoint: EvmEndpoint = config
+            .query_client_endpoints()?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("no query endpoint for chain {chain}"))?;
+        let client = EvmQueryClient {
+            provider: endpoint
+                .to_provider_with_tls(self.chain_tls.get(chain))
+                .await?,
+            endpoint,
+        };
+
+        self.clients
+            .write()
+            .unwrap()
+            .insert(chain.clone(), client.clone());
+        Ok(client)
+    }
+}
+
+#[async_trait]
+impl EvmBlockQuery for EndpointBlockQuery {
+    async fn block(
+        &self,
+        chain: &ChainKey,
+        config: &EvmChainConfig,
+        block: EvmBlockId,
+    ) -> anyhow::Result<Option<EvmBlockHeader>> {
+        let client = self.client(chain, config).await?;
+
+        let block = match block {
+            EvmBlockId::Hash(hash) => client.provider.get_block_by_hash(hash).await?,
+            EvmBlockId::Number(number) => {
+                client
+                    .provider
+                    .get_block_by_number(BlockNumberOrTag::Number(number))
+                    .await?
+            }
+        };
+
+        Ok(block.map(|block| EvmBlockHeader {
+            number: block.header.number,
+            hash: block.header.hash,
+            parent_hash: block.header.parent_hash,
+            timestamp: block.header.timestamp,
+        }))
+    }
+}
+
+#[derive(Clone)]
+pub struct EvmBlocks {
+    query: Arc<dyn EvmBlockQuery>,
+    cache: Arc<Mutex<LruCache<(ChainKey, B256), EvmBlockHeader>>>,
+}
+
+impl Default for EvmBlocks {
+    fn default() -> Self {
+        Self::new(EndpointBlockQuery::default())
+    }
+}
+
+impl EvmBlocks {
+    pub fn new(query: impl EvmBlockQuery + 'static) -> Self {
+        Self {
+            query: Arc::new(query),
+            cache: Arc::new(Mutex::new(LruCache::new(CACHE_SIZE))),
+        }
+    }
+
+    /// Host functions are sync, so this blocks the calling component until the node answers.
+    /// Failures are logged and reported to the component as a missing block
+    pub fn get(
+        &self,
+        chain: &ChainKey,
+        config: &EvmChainConfig,
+        block: EvmBlockId,
+    ) -> Option<EvmBlockHeader> {
+        if let EvmBlockId::Hash(hash) = block {
+            if let Some(header) = self.cache.lock().unwrap().get(&(chain.clone(), hash)) {
+                return Some(header.clone());
+            }
+        }
+
+        let header = match block_on(self.query.block(chain, config, block)) {
+            Ok(Some(header)) => header,
+            Ok(None) => return None,
+            Err(err) => {
+                tracing::warn!("Failed to query block {:?} on {}: {:?}", block, chain, err);
+                return None;
+            }
+        };
+
+        self.cache
+            .lock()
+            .unwrap()
+            .put((chain.clone(), header.hash), header.clone());
+
+        Some(header)
+    }
+}
+
+/// Runs a future to completion from inside a sync host function.
+/// Components run on the node's multi-threaded runtime, where the worker can just step aside,
+/// anywhere else the future gets a runtime of its own on another thread
+fn block_on<F>(future: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(|| handle.block_on(future))
+        }
+        _ => std::thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                        .expect("failed to build runtime for host call")
+                        .block_on(future)
+                })
+                .join()
+                .expect("host call panicked")
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// Knows a single block, and counts how often it's asked
+    struct MockBlocks {
+        header: EvmBlockHeader,
+        queries: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl EvmBlockQuery for MockBlocks {
+        async fn block(
+            &self,
+            _chain: &ChainKey,
+            _config: &EvmChainConfig,
+            block: EvmBlockId,
+        ) -> anyhow::Result<Option<EvmBlockHeader>> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            let known = match block {
+                EvmBlockId::Hash(hash) => hash == self.header.hash,
+                EvmBlockId::Number(number) => number == self.header.number,
+            };
+            Ok(known.then(|| self.header.clone()))
+        }
+    }
+
+    fn chain_config() -> EvmChainConfig {
+        EvmChainConfig {
+            chain_id: "1".parse().unwrap(),
+            ws_endpoints: Vec::new(),
+            http_endpoint: Some("http://localhost:8545".to_string()),
+            http_endpoints: Vec::new(),
+            faucet_endpoint: None,
+            ws_priority_endpoint_index: None,
+        }
+    }
+
+    #[test]
+    fn caches_blocks_by_hash() {
+        let header = EvmBlockHeader {
+            number: 42,
+            hash: B256::repeat_byte(1),
+            parent_hash: B256::repeat_byte(2),
+            timestamp: 1_000,
+        };
+        let queries = Arc::new(AtomicUsize::new(0));
+        let blocks = EvmBlocks::new(MockBlocks {
+            header: header.clone(),
+            queries: queries.clone(),
+        });
+        let chain = ChainKey::new("evm:1").unwrap();
+        let config = chain_config();
+
+        // found by number, the number could point to another block after a reorg so it's asked again
+        assert_eq!(
+            blocks.get(&chain, &config, EvmBlockId::Number(42)),
+            Some(header.clone())
+        );
+        assert_eq!(
+            blocks.get(&chain, &config, EvmBlockId::Number(42)),
+            Some(header.clone())
+        );
+        assert_eq!(queries.load(Ordering::SeqCst), 2);
+
+        // looking the same block up by hash is served from the cache
+        assert_eq!(
+            blocks.get(&chain, &config, EvmBlockId::Hash(header.hash)),
+            Some(header.clone())
+        );
+        assert_eq!(queries.load(Ordering::SeqCst), 2);
+
+        // unknown blocks aren't cached
+        let unknown = EvmBlockId::Hash(B256::repeat_byte(3));
+        assert_eq!(blocks.get(&chain, &config, unknown), None);
+        assert_eq!(blocks.get(&chain, &config, unknown), None);
+        assert_eq!(queries.load(Ordering::SeqCst), 4);
+
+        // and neither is the same block on some other chain
+        let other_chain = ChainKey::new("evm:2").unwrap();
+        blocks.get(&other_chain, &config, EvmBlockId::Hash(header.hash));
+        assert_eq!(queries.load(Ordering::SeqCst), 5);
+    }
+}
@@ -1 +1,3 @@
+pub mod evm_blocks;
+pub mod outbound_http;
 pub mod wasi_keyvalue;
@@ -0,0 +1,27 @@
+//! Outgoing `wasi:http` requests, checked against the component's `allowed_http_hosts`.
+//! The check is on the request's authority, so a component can't get around it by
+//! building the request itself rather than going through a helper library.
+
+use wasmtime_wasi_http::{
+    bindings::http::types::ErrorCode,
+    body::HyperOutgoingBody,
+    types::{default_send_request, HostFutureIncomingResponse, OutgoingRequestConfig},
+    HttpResult,
+};
+use wavs_types::AllowedHostPermission;
+
+pub fn send_request(
+    allowed: &AllowedHostPermission,
+    request: http::Request<HyperOutgoingBody>,
+    config: OutgoingRequestConfig,
+) -> HttpResult<HostFutureIncomingResponse> {
+    let uri = request.uri();
+    let host = uri.host().unwrap_or_default();
+
+    if !allowed.allows(host, uri.port_u16()) {
+        tracing::warn!("Denied outgoing http request to {}", host);
+        return Err(ErrorCode::HttpRequestDenied.into());
+    }
+
+    Ok(default_send_request(request, config))
+}
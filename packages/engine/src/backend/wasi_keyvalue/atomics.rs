@@ -1,10 +1,14 @@
+use std::sync::Mutex;
+
+use utils::storage::db::WavsDb;
 use wasmtime::component::Resource;
 
 use super::{
     bucket_keys::{Key, KeyValueBucket},
     context::KeyValueState,
+    expiry,
 };
-use crate::bindings::operator::world::wasi::keyvalue::atomics;
+use crate::bindings::operator::world::wasi::keyvalue::{atomics, store};
 
 pub type AtomicsResult<T> = std::result::Result<T, atomics::Error>;
 pub type CasResult<T> = std::result::Result<T, atomics::CasError>;
@@ -57,8 +61,11 @@ impl atomics::Host for KeyValueState<'_> {
         let cas = self
             .get_cas_atomics(&cas)
             .map_err(atomics::CasError::StoreError)?;
-        self.set_store_value(&cas.key, value)
-            .map_err(atomics::CasError::StoreError)
+        swap_value(&self.db, cas.key.to_string(), value).map_err(|e| {
+            atomics::CasError::StoreError(store::Error::Other(format!(
+                "Failed to set key in keyvalue store: {e}"
+            )))
+        })
     }
 }
 
@@ -90,3 +97,108 @@ impl atomics::HostCas for KeyValueState<'_> {
 pub struct KeyValueCas {
     pub key: Key,
 }
+
+/// Held for the whole check-then-write of a batch compare-and-swap, and by a single-key swap,
+/// so neither a batch nor a swap can land between another batch's check and its writes
+static CAS_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BatchCasError {
+    /// The full store key whose current value didn't match
+    Mismatch(String),
+    Other(String),
+}
+
+/// Swaps every `(key, expected, value)` entry if, and only if, each key currently holds
+/// `expected` (`None` meaning the key must not exist). Otherwise nothing is written
+pub fn batch_compare_swap(
+    db: &WavsDb,
+    entries: Vec<(String, Option<Vec<u8>>, Vec<u8>)>,
+    now_secs: u64,
+) -> Result<(), BatchCasError> {
+    let _guard = CAS_LOCK.lock().unwrap();
+
+    for (key, expected, _) in &entries {
+        if expiry::get(db, key, now_secs) != *expected {
+            return Err(BatchCasError::Mismatch(key.clone()));
+        }
+    }
+
+    for (key, _, value) in entries {
+        expiry::set(db, key, value).map_err(|e| BatchCasError::Other(e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+/// Writes a single key while holding the same lock as [`batch_compare_swap`]
+pub fn swap_value(db: &WavsDb, key: String, value: Vec<u8>) -> Result<(), String> {
+    let _guard = CAS_LOCK.lock().unwrap();
+    expiry::set(db, key, value).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn batch_compare_swap_is_all_or_nothing() {
+        let db = WavsDb::new().unwrap();
+        let (a, b) = ("ns/bucket/a".to_string(), "ns/bucket/b".to_string());
+
+        batch_compare_swap(
+            &db,
+            vec![
+                (a.clone(), None, b"1".to_vec()),
+                (b.clone(), None, b"1".to_vec()),
+            ],
+            0,
+        )
+        .unwrap();
+
+        // `b` doesn't match, so `a` must not be written either
+        let err = batch_compare_swap(
+            &db,
+            vec![
+                (a.clone(), Some(b"1".to_vec()), b"2".to_vec()),
+                (b.clone(), Some(b"0".to_vec()), b"2".to_vec()),
+            ],
+            0,
+        )
+        .unwrap_err();
+        assert_eq!(err, BatchCasError::Mismatch(b.clone()));
+        assert_eq!(expiry::get(&db, &a, 0), Some(b"1".to_vec()));
+
+        batch_compare_swap(
+            &db,
+            vec![
+                (a.clone(), Some(b"1".to_vec()), b"2".to_vec()),
+                (b.clone(), Some(b"1".to_vec()), b"2".to_vec()),
+            ],
+            0,
+        )
+        .unwrap();
+        assert_eq!(expiry::get(&db, &a, 0), Some(b"2".to_vec()));
+        assert_eq!(expiry::get(&db, &b, 0), Some(b"2".to_vec()));
+    }
+
+    #[test]
+    fn swap_waits_for_an_in_flight_batch() {
+        let db = WavsDb::new().unwrap();
+        let key = "ns/bucket/swap".to_string();
+
+        // stand in for a batch that has checked its keys but not yet written them
+        let guard = CAS_LOCK.lock().unwrap();
+        let swap = std::thread::spawn({
+            let db = db.clone();
+            let key = key.clone();
+            move || swap_value(&db, key, b"swapped".to_vec()).unwrap()
+        });
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        assert_eq!(expiry::get(&db, &key, 0), None);
+
+        drop(guard);
+        swap.join().unwrap();
+        assert_eq!(expiry::get(&db, &key, 0), Some(b"swapped".to_vec()));
+    }
+}
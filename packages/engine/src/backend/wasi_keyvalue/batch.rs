@@ -1,8 +1,10 @@
+use utils::time::unix_now;
 use wasmtime::component::Resource;
 
 use super::{
     bucket_keys::{Key, KeyValueBucket},
     context::KeyValueState,
+    expiry,
 };
 use crate::bindings::operator::world::wasi::keyvalue::batch;
 
@@ -26,10 +28,11 @@ impl batch::Host for KeyValueState<'_> {
     ) -> BatchResult<Vec<Option<(String, Vec<u8>)>>> {
         let keys = self.get_keys_batch(&bucket, original_keys.clone())?;
         let mut results = Vec::with_capacity(keys.len());
+        let now = unix_now();
 
         for (i, original_key) in original_keys.into_iter().enumerate() {
             let key = keys[i].to_string();
-            if let Some(value) = self.db.kv_store.get_cloned(&key) {
+            if let Some(value) = expiry::get(&self.db, &key, now) {
                 results.push(Some((original_key, value)));
             } else {
                 results.push(None);
@@ -48,9 +51,7 @@ impl batch::Host for KeyValueState<'_> {
 
         for (key, value) in key_values {
             let key = Key::new(prefix.clone(), key).to_string();
-            self.db
-                .kv_store
-                .insert(key, value)
+            expiry::set(&self.db, key, value)
                 .map_err(|e| batch::Error::Other(format!("Failed to set key: {}", e)))?;
         }
 
@@ -65,8 +66,7 @@ impl batch::Host for KeyValueState<'_> {
         let keys = self.get_keys_batch(&bucket, keys)?;
 
         for key in keys {
-            let key = key.to_string();
-            self.db.kv_store.remove(&key);
+            expiry::remove(&self.db, &key.to_string());
         }
 
         Ok(())
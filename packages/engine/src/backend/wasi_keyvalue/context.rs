@@ -29,6 +29,25 @@ impl KeyValueCtx {
             page_size: None,
         }
     }
+
+    pub fn db(&self) -> &WavsDb {
+        &self.db
+    }
+
+    /// The full store key of `key` in `bucket`, same as the wasi keyvalue bindings use
+    pub fn bucket_key(&self, bucket: &str, key: &str) -> String {
+        format!("{}/{}/{}", self.namespace, bucket, key)
+    }
+
+    /// Where the host keeps the running stat `name`, see `utils::stats`
+    pub fn stat_key(&self, name: &str) -> String {
+        format!(
+            "{}/{}/{}",
+            self.namespace,
+            crate::utils::stats::STATS_BUCKET,
+            name
+        )
+    }
     pub fn add_to_linker<T>(linker: &mut wasmtime::component::Linker<T>) -> Result<(), EngineError>
     where
         T: KeyValueCtxProvider + Send,
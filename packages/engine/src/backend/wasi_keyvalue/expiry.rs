@@ -0,0 +1,73 @@
+//! Optional per-key expiry on top of the keyvalue store.
+//!
+//! wasi:keyvalue has no notion of a ttl, so components set one through the `set-with-ttl`
+//! host function. Expiry is lazy: an expired key stays in the table until it's next looked up,
+//! at which point it's removed and reported as missing.
+
+use utils::storage::db::{DBError, WavsDb};
+
+/// Stores `value` under `key`, to be treated as missing once `ttl_secs` have passed
+pub fn set_with_ttl(
+    db: &WavsDb,
+    key: String,
+    value: Vec<u8>,
+    ttl_secs: u64,
+    now_secs: u64,
+) -> Result<(), DBError> {
+    db.kv_expiry
+        .insert(key.clone(), now_secs.saturating_add(ttl_secs))?;
+    db.kv_store.insert(key, value)
+}
+
+/// Stores `value` under `key` with no expiry, replacing any ttl set by an earlier write
+pub fn set(db: &WavsDb, key: String, value: Vec<u8>) -> Result<(), DBError> {
+    db.kv_expiry.remove(&key);
+    db.kv_store.insert(key, value)
+}
+
+/// The value under `key`, unless it has expired
+pub fn get(db: &WavsDb, key: &String, now_secs: u64) -> Option<Vec<u8>> {
+    if remove_if_expired(db, key, now_secs) {
+        return None;
+    }
+    db.kv_store.get_cloned(key)
+}
+
+pub fn remove(db: &WavsDb, key: &String) {
+    db.kv_expiry.remove(key);
+    db.kv_store.remove(key);
+}
+
+/// Removes the key if its ttl has passed, returning whether it did
+pub fn remove_if_expired(db: &WavsDb, key: &String, now_secs: u64) -> bool {
+    match db.kv_expiry.get_cloned(key) {
+        Some(expires_at) if expires_at <= now_secs => {
+            remove(db, key);
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn expires_after_ttl() {
+        let db = WavsDb::new().unwrap();
+        let key = "service/bucket/key".to_string();
+
+        set_with_ttl(&db, key.clone(), b"value".to_vec(), 10, 100).unwrap();
+        assert_eq!(get(&db, &key, 109), Some(b"value".to_vec()));
+        assert_eq!(get(&db, &key, 110), None);
+        // cleaned up on the lookup that found it expired
+        assert!(!db.kv_store.contains_key(&key));
+        assert!(!db.kv_expiry.contains_key(&key));
+
+        // a plain write clears the ttl of an earlier one
+        set_with_ttl(&db, key.clone(), b"value".to_vec(), 10, 100).unwrap();
+        set(&db, key.clone(), b"forever".to_vec()).unwrap();
+        assert_eq!(get(&db, &key, u64::MAX), Some(b"forever".to_vec()));
+    }
+}
@@ -2,4 +2,5 @@ pub mod atomics;
 pub mod batch;
 pub mod bucket_keys;
 pub mod context;
+pub mod expiry;
 pub mod store;
@@ -1,7 +1,9 @@
+use utils::time::unix_now;
 use wasmtime::component::Resource;
 
 use super::bucket_keys::{Key, KeyPrefix, KeyValueBucket};
 use super::context::KeyValueState;
+use super::expiry;
 use crate::bindings::operator::world::wasi::keyvalue::store::{self, KeyResponse};
 
 pub type StoreResult<T> = std::result::Result<T, store::Error>;
@@ -16,14 +18,12 @@ impl<'a> KeyValueState<'a> {
     }
 
     pub fn set_store_value(&self, key: &Key, value: Vec<u8>) -> StoreResult<()> {
-        self.db
-            .kv_store
-            .insert(key.to_string(), value)
+        expiry::set(&self.db, key.to_string(), value)
             .map_err(|e| store::Error::Other(format!("Failed to set key in keyvalue store: {}", e)))
     }
 
     pub fn get_store_value(&self, key: &Key) -> StoreResult<Option<Vec<u8>>> {
-        Ok(self.db.kv_store.get_cloned(&key.to_string()))
+        Ok(expiry::get(&self.db, &key.to_string(), unix_now()))
     }
 }
 
@@ -57,7 +57,7 @@ impl store::HostBucket for KeyValueState<'_> {
 
     fn delete(&mut self, bucket: Resource<KeyValueBucket>, key: String) -> StoreResult<()> {
         let key = self.get_key_store(&bucket, key)?;
-        self.db.kv_store.remove(&key.to_string());
+        expiry::remove(&self.db, &key.to_string());
         Ok(())
     }
 
@@ -85,6 +85,10 @@ impl store::HostBucket for KeyValueState<'_> {
             }
         }
 
+        // Drop expired keys, outside of the iteration so removing them can't deadlock
+        let now = unix_now();
+        all_keys.retain(|key| !expiry::remove_if_expired(&self.db, key, now));
+
         // Sort keys for consistent iteration
         all_keys.sort();
 
@@ -110,7 +114,8 @@ impl store::HostBucket for KeyValueState<'_> {
                 count += 1;
                 if let Some(page_size) = self.page_size {
                     if count > page_size {
-                        next_cursor = Some(key.clone());
+                        // unprefixed, like the cursor the component passes back in
+                        next_cursor = Some(key[prefix_str.len()..].to_string());
                         break;
                     }
                 }
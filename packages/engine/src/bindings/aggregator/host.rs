@@ -2,7 +2,8 @@ use wavs_types::ChainKey;
 
 use crate::worlds::aggregator::component::AggregatorHostComponent;
 
-use super::world::host::Host;
+use super::world::host::{HashAlgorithm, Host};
+use super::world::wavs::aggregator::output::SubmitAction;
 use super::world::wavs::types::core::LogLevel;
 use super::world::wavs::types::service::{ServiceAndWorkflowId, WorkflowAndWorkflowId};
 
@@ -33,6 +34,31 @@ impl Host for AggregatorHostComponent {
             .map(Into::into)
     }
 
+    fn get_solana_chain_config(
+        &mut self,
+        chain: String,
+    ) -> Option<super::world::wavs::types::chain::SolanaChainConfig> {
+        let chain = ChainKey::new(chain).ok()?;
+
+        self.chain_configs
+            .get_chain(&chain)?
+            .to_solana_config()
+            .ok()
+            .map(Into::into)
+    }
+
+    fn get_evm_block(
+        &mut self,
+        chain: String,
+        block: super::world::wavs::types::chain::EvmBlockId,
+    ) -> Option<super::world::wavs::types::chain::EvmBlockHeader> {
+        let chain = ChainKey::new(chain).ok()?;
+        let config = self.chain_configs.get_chain(&chain)?.to_evm_config().ok()?;
+        let block = block.try_into().ok()?;
+
+        self.evm_blocks.get(&chain, &config, block).map(Into::into)
+    }
+
     fn config_var(&mut self, key: String) -> Option<String> {
         self.service
             .workflows
@@ -44,6 +70,10 @@ impl Host for AggregatorHostComponent {
             .cloned()
     }
 
+    fn get_secret(&mut self, name: String) -> Option<String> {
+        self.secrets.get(&name).cloned()
+    }
+
     fn get_service(&mut self) -> ServiceAndWorkflowId {
         ServiceAndWorkflowId {
             service: self.service.clone().try_into().unwrap(),
@@ -74,7 +104,15 @@ impl Host for AggregatorHostComponent {
         self.event_id.as_ref().to_vec()
     }
 
+    fn get_submit_action(&mut self) -> Option<SubmitAction> {
+        self.submit_action.clone().map(Into::into)
+    }
+
     fn log(&mut self, level: LogLevel, message: String) {
+        self.log_structured(level, message, Vec::new());
+    }
+
+    fn log_structured(&mut self, level: LogLevel, message: String, fields: Vec<(String, String)>) {
         let digest = self
             .service
             .workflows
@@ -97,6 +135,17 @@ impl Host for AggregatorHostComponent {
             digest,
             level,
             message,
+            &fields,
         );
     }
+
+    fn hash(&mut self, algorithm: HashAlgorithm, data: Vec<u8>) -> Vec<u8> {
+        let algorithm = match algorithm {
+            HashAlgorithm::Keccak256 => crate::utils::hash::HashAlgorithm::Keccak256,
+            HashAlgorithm::Sha256 => crate::utils::hash::HashAlgorithm::Sha256,
+            HashAlgorithm::Blake3 => crate::utils::hash::HashAlgorithm::Blake3,
+        };
+
+        crate::utils::hash::hash(algorithm, &data)
+    }
 }
@@ -1,8 +1,12 @@
+use utils::time::unix_now;
 use wavs_types::{ChainKey, EventIdSalt};
 
 use crate::worlds::operator::component::OperatorHostComponent;
 
-use super::world::host::{LogLevel, ServiceAndWorkflowId, WorkflowAndWorkflowId};
+use super::world::host::{
+    BatchCasError, CasEntry, HashAlgorithm, LogLevel, ServiceAndWorkflowId, StatSummary,
+    WorkflowAndWorkflowId,
+};
 
 impl super::world::host::Host for OperatorHostComponent {
     fn get_cosmos_chain_config(
@@ -31,6 +35,31 @@ impl super::world::host::Host for OperatorHostComponent {
             .map(Into::into)
     }
 
+    fn get_solana_chain_config(
+        &mut self,
+        chain: String,
+    ) -> Option<super::world::wavs::types::chain::SolanaChainConfig> {
+        let chain = ChainKey::new(chain).ok()?;
+
+        self.chain_configs
+            .get_chain(&chain)?
+            .to_solana_config()
+            .ok()
+            .map(Into::into)
+    }
+
+    fn get_evm_block(
+        &mut self,
+        chain: String,
+        block: super::world::wavs::types::chain::EvmBlockId,
+    ) -> Option<super::world::wavs::types::chain::EvmBlockHeader> {
+        let chain = ChainKey::new(chain).ok()?;
+        let config = self.chain_configs.get_chain(&chain)?.to_evm_config().ok()?;
+        let block = block.try_into().ok()?;
+
+        self.evm_blocks.get(&chain, &config, block).map(Into::into)
+    }
+
     fn get_service(&mut self) -> ServiceAndWorkflowId {
         ServiceAndWorkflowId {
             service: self.service.clone().try_into().unwrap(),
@@ -77,7 +106,15 @@ impl super::world::host::Host for OperatorHostComponent {
             .cloned()
     }
 
+    fn get_secret(&mut self, name: String) -> Option<String> {
+        self.secrets.get(&name).cloned()
+    }
+
     fn log(&mut self, level: LogLevel, message: String) {
+        self.log_structured(level, message, Vec::new());
+    }
+
+    fn log_structured(&mut self, level: LogLevel, message: String, fields: Vec<(String, String)>) {
         let digest = self
             .service
             .workflows
@@ -97,6 +134,103 @@ impl super::world::host::Host for OperatorHostComponent {
             digest,
             level,
             message,
+            &fields,
         );
     }
+
+    fn hash(&mut self, algorithm: HashAlgorithm, data: Vec<u8>) -> Vec<u8> {
+        let algorithm = match algorithm {
+            HashAlgorithm::Keccak256 => crate::utils::hash::HashAlgorithm::Keccak256,
+            HashAlgorithm::Sha256 => crate::utils::hash::HashAlgorithm::Sha256,
+            HashAlgorithm::Blake3 => crate::utils::hash::HashAlgorithm::Blake3,
+        };
+
+        crate::utils::hash::hash(algorithm, &data)
+    }
+
+    fn update_stat(&mut self, name: String, value: f64) -> Result<StatSummary, String> {
+        crate::utils::stats::update_stat(
+            self.keyvalue_ctx.db(),
+            self.keyvalue_ctx.stat_key(&name),
+            value,
+            unix_now(),
+        )
+        .map(Into::into)
+    }
+
+    fn get_stat(
+        &mut self,
+        name: String,
+        window_secs: Option<u64>,
+    ) -> Result<Option<StatSummary>, String> {
+        crate::utils::stats::get_stat(
+            self.keyvalue_ctx.db(),
+            self.keyvalue_ctx.stat_key(&name),
+            window_secs,
+            unix_now(),
+        )
+        .map(|summary| summary.map(Into::into))
+    }
+
+    fn set_with_ttl(
+        &mut self,
+        bucket: String,
+        key: String,
+        value: Vec<u8>,
+        ttl_secs: u64,
+    ) -> Result<(), String> {
+        crate::backend::wasi_keyvalue::expiry::set_with_ttl(
+            self.keyvalue_ctx.db(),
+            self.keyvalue_ctx.bucket_key(&bucket, &key),
+            value,
+            ttl_secs,
+            unix_now(),
+        )
+        .map_err(|e| format!("Failed to set key in keyvalue store: {e}"))
+    }
+
+    fn batch_compare_swap(
+        &mut self,
+        bucket: String,
+        entries: Vec<CasEntry>,
+    ) -> Result<(), BatchCasError> {
+        let prefix_len = self.keyvalue_ctx.bucket_key(&bucket, "").len();
+        let entries = entries
+            .into_iter()
+            .map(|entry| {
+                (
+                    self.keyvalue_ctx.bucket_key(&bucket, &entry.key),
+                    entry.expected,
+                    entry.value,
+                )
+            })
+            .collect();
+
+        crate::backend::wasi_keyvalue::atomics::batch_compare_swap(
+            self.keyvalue_ctx.db(),
+            entries,
+            unix_now(),
+        )
+        .map_err(|err| match err {
+            // report the key as the component named it
+            crate::backend::wasi_keyvalue::atomics::BatchCasError::Mismatch(key) => {
+                BatchCasError::Mismatch(key[prefix_len..].to_string())
+            }
+            crate::backend::wasi_keyvalue::atomics::BatchCasError::Other(reason) => {
+                BatchCasError::Other(reason)
+            }
+        })
+    }
+}
+
+impl From<crate::utils::stats::StatSummary> for StatSummary {
+    fn from(summary: crate::utils::stats::StatSummary) -> Self {
+        Self {
+            count: summary.count,
+            mean: summary.mean,
+            ema: summary.ema,
+            min: summary.min,
+            max: summary.max,
+        }
+    }
 }
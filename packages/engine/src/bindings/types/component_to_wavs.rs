@@ -11,6 +11,33 @@ use crate::{
 };
 
 use crate::bindings::aggregator::world::wavs::aggregator::output as aggregator_output;
+use crate::bindings::aggregator::world::wavs::types::chain as aggregator_chain;
+
+impl TryFrom<component_chain::EvmBlockId> for crate::backend::evm_blocks::EvmBlockId {
+    type Error = anyhow::Error;
+
+    fn try_from(src: component_chain::EvmBlockId) -> Result<Self, Self::Error> {
+        Ok(match src {
+            component_chain::EvmBlockId::Hash(hash) => {
+                Self::Hash(alloy_primitives::B256::try_from(hash.as_slice())?)
+            }
+            component_chain::EvmBlockId::Number(number) => Self::Number(number),
+        })
+    }
+}
+
+impl TryFrom<aggregator_chain::EvmBlockId> for crate::backend::evm_blocks::EvmBlockId {
+    type Error = anyhow::Error;
+
+    fn try_from(src: aggregator_chain::EvmBlockId) -> Result<Self, Self::Error> {
+        Ok(match src {
+            aggregator_chain::EvmBlockId::Hash(hash) => {
+                Self::Hash(alloy_primitives::B256::try_from(hash.as_slice())?)
+            }
+            aggregator_chain::EvmBlockId::Number(number) => Self::Number(number),
+        })
+    }
+}
 
 impl TryFrom<component_service::Trigger> for wavs_types::Trigger {
     type Error = anyhow::Error;
@@ -22,6 +49,8 @@ impl TryFrom<component_service::Trigger> for wavs_types::Trigger {
                     address: source.address.into(),
                     chain: source.chain.parse()?,
                     event_type: source.event_type,
+                    // the wit trigger doesn't carry an attribute filter
+                    attributes: Default::default(),
                 }
             }
             component_service::Trigger::EvmContractEvent(source) => {
@@ -29,6 +58,9 @@ impl TryFrom<component_service::Trigger> for wavs_types::Trigger {
                     address: source.address.into(),
                     chain: source.chain.parse()?,
                     event_hash: source.event_hash.try_into()?,
+                    // the wit trigger only carries the primary event
+                    additional_event_hashes: Vec::new(),
+                    transform: Vec::new(),
                 }
             }
             component_service::Trigger::BlockInterval(source) => {
@@ -39,6 +71,9 @@ impl TryFrom<component_service::Trigger> for wavs_types::Trigger {
                     end_block: source.end_block.map(TryInto::try_into).transpose()?,
                 }
             }
+            component_service::Trigger::Webhook(source) => wavs_types::Trigger::Webhook {
+                signature_secret: source.signature_secret,
+            },
             component_service::Trigger::Manual => wavs_types::Trigger::Manual,
             component_service::Trigger::Cron(source) => wavs_types::Trigger::Cron {
                 schedule: source.schedule,
@@ -88,6 +123,14 @@ impl From<component_chain::EvmAddress> for alloy_primitives::Address {
     }
 }
 
+impl TryFrom<component_chain::SolanaAddress> for wavs_types::SolanaAddress {
+    type Error = wavs_types::SolanaAddressError;
+
+    fn try_from(address: component_chain::SolanaAddress) -> Result<Self, Self::Error> {
+        address.raw_bytes.as_slice().try_into()
+    }
+}
+
 impl From<component_core::Timestamp> for wavs_types::Timestamp {
     fn from(src: component_core::Timestamp) -> Self {
         wavs_types::Timestamp::from_nanos(src.nanos)
@@ -111,6 +154,8 @@ impl TryFrom<component_service::Service> for wavs_types::Service {
                 .collect::<anyhow::Result<BTreeMap<WorkflowId, wavs_types::Workflow>>>()?,
             status: src.status.into(),
             manager: src.manager.try_into()?,
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
         })
     }
 }
@@ -123,6 +168,12 @@ impl TryFrom<component_service::Workflow> for wavs_types::Workflow {
             trigger: src.trigger.try_into()?,
             component: src.component.try_into()?,
             submit: src.submit.into(),
+            submit_targets: Vec::new(),
+            response_mapping: Default::default(),
+            submit_condition: None,
+            priority: Default::default(),
+            observe_only: false,
+            min_interval_ms: None,
         })
     }
 }
@@ -136,8 +187,10 @@ impl TryFrom<component_service::Component> for wavs_types::Component {
             permissions: src.permissions.into(),
             fuel_limit: src.fuel_limit,
             time_limit_seconds: src.time_limit_seconds,
+            max_memory_bytes: None,
             config: src.config.into_iter().collect(),
             env_keys: src.env_keys.into_iter().collect(),
+            secrets: Default::default(),
         })
     }
 }
@@ -151,9 +204,16 @@ impl TryFrom<component_service::ComponentSource> for wavs_types::ComponentSource
                 wavs_types::ComponentSource::Digest(wavs_types::ComponentDigest::from_str(&digest)?)
             }
             component_service::ComponentSource::Download(download) => {
-                wavs_types::ComponentSource::Download {
-                    uri: UriString::try_from(download.uri)?,
-                    digest: wavs_types::ComponentDigest::from_str(&download.digest)?,
+                let digest = wavs_types::ComponentDigest::from_str(&download.digest)?;
+                match download.uri.strip_prefix("oci://") {
+                    Some(reference) => wavs_types::ComponentSource::Oci {
+                        reference: reference.to_string(),
+                        digest,
+                    },
+                    None => wavs_types::ComponentSource::Download {
+                        uri: UriString::try_from(download.uri)?,
+                        digest,
+                    },
                 }
             }
             component_service::ComponentSource::Registry(registry) => {
@@ -241,6 +301,7 @@ impl From<component_service::Submit> for wavs_types::Submit {
             }) => wavs_types::Submit::Aggregator {
                 component: Box::new(component.try_into().unwrap()),
                 signature_kind: signature_kind.into(),
+                targets: Vec::new(),
             },
         }
     }
@@ -310,6 +371,10 @@ impl TryFrom<aggregator_output::SubmitAction> for wavs_types::SubmitAction {
                     chain: action.chain.parse()?,
                     address: action.address.try_into()?,
                     gas_price: action.gas_price.map(|x| x.into()),
+                    eip1559: action.eip1559.map(|fees| wavs_types::Eip1559Fees {
+                        max_fee_per_gas: fees.max_fee_per_gas.into(),
+                        max_priority_fee_per_gas: fees.max_priority_fee_per_gas.into(),
+                    }),
                 })
             }
             aggregator_output::SubmitAction::Cosmos(action) => {
@@ -26,10 +26,12 @@ impl TryFrom<wavs_types::Trigger> for component_service::Trigger {
 
     fn try_from(src: wavs_types::Trigger) -> Result<Self, Self::Error> {
         Ok(match src {
+            // the attribute filter is applied by the node, components only see matching events
             wavs_types::Trigger::CosmosContractEvent {
                 address,
                 chain,
                 event_type,
+                ..
             } => component_service::Trigger::CosmosContractEvent(
                 component_service::TriggerCosmosContractEvent {
                     address: address.into(),
@@ -41,6 +43,7 @@ impl TryFrom<wavs_types::Trigger> for component_service::Trigger {
                 address,
                 chain,
                 event_hash,
+                ..
             } => component_service::Trigger::EvmContractEvent(
                 component_service::TriggerEvmContractEvent {
                     address: address.into(),
@@ -61,6 +64,11 @@ impl TryFrom<wavs_types::Trigger> for component_service::Trigger {
                     end_block: end_block.map(Into::into),
                 })
             }
+            wavs_types::Trigger::Webhook { signature_secret } => {
+                component_service::Trigger::Webhook(component_service::TriggerWebhook {
+                    signature_secret,
+                })
+            }
             wavs_types::Trigger::Manual => component_service::Trigger::Manual,
             wavs_types::Trigger::Cron {
                 schedule,
@@ -159,6 +167,46 @@ impl From<wavs_types::EvmChainConfig> for crate::bindings::operator::world::host
     }
 }
 
+impl From<wavs_types::SolanaChainConfig> for component_chain::SolanaChainConfig {
+    fn from(config: wavs_types::SolanaChainConfig) -> Self {
+        Self {
+            chain_id: config.chain_id.to_string(),
+            rpc_endpoint: config.rpc_endpoint,
+            ws_endpoint: config.ws_endpoint,
+            commitment: config.commitment.into(),
+        }
+    }
+}
+
+impl From<wavs_types::SolanaCommitment> for component_chain::SolanaCommitment {
+    fn from(commitment: wavs_types::SolanaCommitment) -> Self {
+        match commitment {
+            wavs_types::SolanaCommitment::Processed => Self::Processed,
+            wavs_types::SolanaCommitment::Confirmed => Self::Confirmed,
+            wavs_types::SolanaCommitment::Finalized => Self::Finalized,
+        }
+    }
+}
+
+impl From<crate::backend::evm_blocks::EvmBlockHeader> for component_chain::EvmBlockHeader {
+    fn from(header: crate::backend::evm_blocks::EvmBlockHeader) -> Self {
+        Self {
+            number: header.number,
+            hash: header.hash.to_vec(),
+            parent_hash: header.parent_hash.to_vec(),
+            timestamp: header.timestamp,
+        }
+    }
+}
+
+impl From<wavs_types::SolanaAddress> for component_chain::SolanaAddress {
+    fn from(address: wavs_types::SolanaAddress) -> Self {
+        Self {
+            raw_bytes: address.as_bytes().to_vec(),
+        }
+    }
+}
+
 impl From<wavs_types::Timestamp> for component_core::Timestamp {
     fn from(src: wavs_types::Timestamp) -> Self {
         component_core::Timestamp {
@@ -230,6 +278,15 @@ impl From<wavs_types::ComponentSource> for component_service::ComponentSource {
             wavs_types::ComponentSource::Registry { registry } => {
                 component_service::ComponentSource::Registry(registry.into())
             }
+            // the WIT types have no OCI variant, so it's passed as a download with an oci:// uri
+            wavs_types::ComponentSource::Oci { reference, digest } => {
+                component_service::ComponentSource::Download(
+                    component_service::ComponentSourceDownload {
+                        uri: format!("oci://{reference}"),
+                        digest: digest.to_string(),
+                    },
+                )
+            }
         }
     }
 }
@@ -260,9 +317,11 @@ impl From<wavs_types::Submit> for component_service::Submit {
     fn from(src: wavs_types::Submit) -> Self {
         match src {
             wavs_types::Submit::None => component_service::Submit::None,
+            // the WIT service types don't carry the fixed submit targets
             wavs_types::Submit::Aggregator {
                 component,
                 signature_kind,
+                targets: _,
             } => component_service::Submit::Aggregator(component_service::AggregatorSubmit {
                 component: (*component).into(),
                 signature_kind: signature_kind.into(),
@@ -435,17 +494,23 @@ impl TryFrom<wavs_types::TriggerData> for component_input::TriggerData {
             wavs_types::TriggerData::BlockInterval {
                 chain,
                 block_height,
+                is_final,
             } => Ok(component_input::TriggerData::BlockInterval(
                 component_events::TriggerDataBlockInterval {
                     chain: chain.to_string(),
                     block_height,
+                    is_final,
                 },
             )),
-            wavs_types::TriggerData::Cron { trigger_time } => Ok(
-                component_input::TriggerData::Cron(component_events::TriggerDataCron {
+            wavs_types::TriggerData::Cron {
+                trigger_time,
+                missed_ticks,
+            } => Ok(component_input::TriggerData::Cron(
+                component_events::TriggerDataCron {
                     trigger_time: trigger_time.into(),
-                }),
-            ),
+                    missed_ticks,
+                },
+            )),
             wavs_types::TriggerData::AtProtoEvent {
                 sequence,
                 timestamp,
@@ -488,6 +553,12 @@ impl TryFrom<wavs_types::TriggerData> for component_input::TriggerData {
                     data,
                 },
             )),
+            wavs_types::TriggerData::Webhook { headers, body } => Ok(
+                component_input::TriggerData::Webhook(component_events::TriggerDataWebhook {
+                    headers: headers.into_iter().collect(),
+                    body,
+                }),
+            ),
             wavs_types::TriggerData::Raw(data) => Ok(component_input::TriggerData::Raw(data)),
         }
     }
@@ -601,17 +672,23 @@ impl TryFrom<wavs_types::TriggerData> for aggregator_operator_input::TriggerData
             wavs_types::TriggerData::BlockInterval {
                 chain,
                 block_height,
+                is_final,
             } => Ok(aggregator_operator_input::TriggerData::BlockInterval(
                 aggregator_events::TriggerDataBlockInterval {
                     chain: chain.to_string(),
                     block_height,
+                    is_final,
                 },
             )),
-            wavs_types::TriggerData::Cron { trigger_time } => Ok(
-                aggregator_operator_input::TriggerData::Cron(aggregator_events::TriggerDataCron {
+            wavs_types::TriggerData::Cron {
+                trigger_time,
+                missed_ticks,
+            } => Ok(aggregator_operator_input::TriggerData::Cron(
+                aggregator_events::TriggerDataCron {
                     trigger_time: trigger_time.into(),
-                }),
-            ),
+                    missed_ticks,
+                },
+            )),
             wavs_types::TriggerData::AtProtoEvent {
                 sequence,
                 timestamp,
@@ -654,6 +731,14 @@ impl TryFrom<wavs_types::TriggerData> for aggregator_operator_input::TriggerData
                     data,
                 },
             )),
+            wavs_types::TriggerData::Webhook { headers, body } => {
+                Ok(aggregator_operator_input::TriggerData::Webhook(
+                    aggregator_events::TriggerDataWebhook {
+                        headers: headers.into_iter().collect(),
+                        body,
+                    },
+                ))
+            }
             wavs_types::TriggerData::Raw(data) => {
                 Ok(aggregator_operator_input::TriggerData::Raw(data))
             }
@@ -778,6 +863,15 @@ impl From<wavs_types::ComponentSource> for aggregator_service::ComponentSource {
             wavs_types::ComponentSource::Registry { registry } => {
                 aggregator_service::ComponentSource::Registry(registry.into())
             }
+            // the WIT types have no OCI variant, so it's passed as a download with an oci:// uri
+            wavs_types::ComponentSource::Oci { reference, digest } => {
+                aggregator_service::ComponentSource::Download(
+                    aggregator_service::ComponentSourceDownload {
+                        uri: format!("oci://{reference}"),
+                        digest: digest.to_string(),
+                    },
+                )
+            }
         }
     }
 }
@@ -824,9 +918,11 @@ impl From<wavs_types::Submit> for aggregator_service::Submit {
     fn from(submit: wavs_types::Submit) -> Self {
         match submit {
             wavs_types::Submit::None => aggregator_service::Submit::None,
+            // the WIT service types don't carry the fixed submit targets
             wavs_types::Submit::Aggregator {
                 component,
                 signature_kind,
+                targets: _,
             } => aggregator_service::Submit::Aggregator(aggregator_service::AggregatorSubmit {
                 component: (*component).into(),
                 signature_kind: signature_kind.into(),
@@ -868,10 +964,16 @@ impl TryFrom<wavs_types::Trigger> for aggregator_service::Trigger {
     fn try_from(trigger: wavs_types::Trigger) -> Result<Self, Self::Error> {
         Ok(match trigger {
             wavs_types::Trigger::Manual => aggregator_service::Trigger::Manual,
+            wavs_types::Trigger::Webhook { signature_secret } => {
+                aggregator_service::Trigger::Webhook(aggregator_service::TriggerWebhook {
+                    signature_secret,
+                })
+            }
             wavs_types::Trigger::EvmContractEvent {
                 address,
                 chain,
                 event_hash,
+                ..
             } => aggregator_service::Trigger::EvmContractEvent(
                 aggregator_service::TriggerEvmContractEvent {
                     address: address.into(),
@@ -879,10 +981,12 @@ impl TryFrom<wavs_types::Trigger> for aggregator_service::Trigger {
                     event_hash: event_hash.as_slice().to_vec(),
                 },
             ),
+            // the attribute filter is applied by the node, components only see matching events
             wavs_types::Trigger::CosmosContractEvent {
                 address,
                 chain,
                 event_type,
+                ..
             } => aggregator_service::Trigger::CosmosContractEvent(
                 aggregator_service::TriggerCosmosContractEvent {
                     address: address.into(),
@@ -993,6 +1097,10 @@ impl From<wavs_types::SubmitAction> for aggregator_output::SubmitAction {
                     chain: action.chain.to_string(),
                     address: action.address.into(),
                     gas_price: action.gas_price.map(|x| x.into()),
+                    eip1559: action.eip1559.map(|fees| aggregator_output::Eip1559Fees {
+                        max_fee_per_gas: fees.max_fee_per_gas.into(),
+                        max_priority_fee_per_gas: fees.max_priority_fee_per_gas.into(),
+                    }),
                 })
             }
             wavs_types::SubmitAction::Cosmos(action) => {
@@ -1029,3 +1137,43 @@ impl From<wavs_types::EvmChainConfig> for aggregator_chain::EvmChainConfig {
         }
     }
 }
+
+impl From<wavs_types::SolanaChainConfig> for aggregator_chain::SolanaChainConfig {
+    fn from(config: wavs_types::SolanaChainConfig) -> Self {
+        Self {
+            chain_id: config.chain_id.to_string(),
+            rpc_endpoint: config.rpc_endpoint,
+            ws_endpoint: config.ws_endpoint,
+            commitment: config.commitment.into(),
+        }
+    }
+}
+
+impl From<wavs_types::SolanaCommitment> for aggregator_chain::SolanaCommitment {
+    fn from(commitment: wavs_types::SolanaCommitment) -> Self {
+        match commitment {
+            wavs_types::SolanaCommitment::Processed => Self::Processed,
+            wavs_types::SolanaCommitment::Confirmed => Self::Confirmed,
+            wavs_types::SolanaCommitment::Finalized => Self::Finalized,
+        }
+    }
+}
+
+impl From<crate::backend::evm_blocks::EvmBlockHeader> for aggregator_chain::EvmBlockHeader {
+    fn from(header: crate::backend::evm_blocks::EvmBlockHeader) -> Self {
+        Self {
+            number: header.number,
+            hash: header.hash.to_vec(),
+            parent_hash: header.parent_hash.to_vec(),
+            timestamp: header.timestamp,
+        }
+    }
+}
+
+impl From<wavs_types::SolanaAddress> for aggregator_chain::SolanaAddress {
+    fn from(address: wavs_types::SolanaAddress) -> Self {
+        Self {
+            raw_bytes: address.as_bytes().to_vec(),
+        }
+    }
+}
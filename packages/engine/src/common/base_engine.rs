@@ -1,20 +1,31 @@
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::num::NonZeroUsize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use lru::LruCache;
+use sha2::{Digest, Sha256};
 use wasmtime::{component::Component as WasmComponent, Config as WTConfig, Engine as WTEngine};
 
+use utils::filesystem::write_atomic;
 use utils::service::fetch_bytes;
 use utils::storage::db::WavsDb;
 use utils::storage::CAStorage;
-use utils::wkg::WkgClient;
-use wavs_types::{ChainConfigs, ComponentDigest, ComponentSource};
+use utils::wkg::{
+    oci::{OciClient, OciCredentials, OciReference},
+    RegistryError, WkgClient,
+};
+use wavs_types::{ChainConfigs, ComponentDigest, ComponentSource, ServiceId};
 
+use crate::backend::evm_blocks::EvmBlocks;
 use crate::utils::error::EngineError;
+use crate::worlds::determinism::Determinism;
 
 const DEFAULT_LRU_SIZE: usize = 10;
+const PRECOMPILED_DIR: &str = "precompiled";
 
 pub struct BaseEngineConfig {
     pub app_data_dir: PathBuf,
@@ -28,13 +39,30 @@ pub struct BaseEngineConfig {
 pub struct BaseEngine<S: CAStorage> {
     pub wasm_engine: WTEngine,
     pub chain_configs: Arc<RwLock<ChainConfigs>>,
+    /// Compiled components are keyed by digest, so services sharing the same bytes share one entry
     pub memory_cache: Mutex<LruCache<ComponentDigest, WasmComponent>>,
+    /// Held while a digest is being compiled, so concurrent loads of it compile once
+    compiling: Mutex<HashMap<ComponentDigest, Arc<tokio::sync::Mutex<()>>>>,
+    /// Serialized compiled components, so a restart deserializes them instead of compiling.
+    /// Scoped to the engine's compatibility hash, so a wasmtime upgrade starts from an empty dir
+    precompiled_dir: PathBuf,
+    /// How many times a component was actually compiled, rather than loaded from either cache
+    compilations: AtomicU64,
     pub app_data_dir: PathBuf,
+    /// Components granted `file_system` see their service's directory under here as `.`,
+    /// the app data dir unless set otherwise
+    pub component_fs_root: PathBuf,
     pub max_wasm_fuel: Option<u64>,
     pub max_execution_seconds: Option<u64>,
     pub db: WavsDb,
     pub storage: Arc<S>,
     pub ipfs_gateway: String,
+    /// Logins for pulling `Oci` components from private registries
+    pub oci_credentials: OciCredentials,
+    /// Shared by every instance, so blocks looked up by one component are cached for the rest
+    pub evm_blocks: EvmBlocks,
+    /// Which WASI clocks and randomness are pinned to the event, nothing by default
+    pub determinism: Determinism,
 }
 
 impl<S: CAStorage + Send + Sync + 'static> BaseEngine<S> {
@@ -54,6 +82,17 @@ impl<S: CAStorage + Send + Sync + 'static> BaseEngine<S> {
                 .map_err(|e| EngineError::IO(format!("Failed to create app data dir: {}", e)))?;
         }
 
+        let mut hasher = StableHasher::default();
+        wasm_engine
+            .precompile_compatibility_hash()
+            .hash(&mut hasher);
+        let precompiled_root = config.app_data_dir.join(PRECOMPILED_DIR);
+        let precompiled_dir = precompiled_root.join(format!("{:016x}", hasher.finish()));
+        std::fs::create_dir_all(&precompiled_dir).map_err(|e| {
+            EngineError::IO(format!("Failed to create precompiled component dir: {}", e))
+        })?;
+        remove_stale_precompiled(&precompiled_root, &precompiled_dir);
+
         // just run forever, ticking forward till the end of time (or however long this node is up)
         let engine_ticker = wasm_engine.weak();
         std::thread::spawn(move || loop {
@@ -69,12 +108,19 @@ impl<S: CAStorage + Send + Sync + 'static> BaseEngine<S> {
             wasm_engine,
             chain_configs: config.chain_configs,
             memory_cache: Mutex::new(LruCache::new(lru_size)),
+            compiling: Mutex::new(HashMap::new()),
+            precompiled_dir,
+            compilations: AtomicU64::new(0),
+            component_fs_root: config.app_data_dir.clone(),
             app_data_dir: config.app_data_dir,
             max_wasm_fuel: config.max_wasm_fuel,
             max_execution_seconds: config.max_execution_seconds,
             db,
             storage,
             ipfs_gateway: config.ipfs_gateway,
+            oci_credentials: OciCredentials::new(),
+            evm_blocks: EvmBlocks::default(),
+            determinism: Determinism::default(),
         })
     }
 
@@ -82,20 +128,43 @@ impl<S: CAStorage + Send + Sync + 'static> BaseEngine<S> {
         &self,
         digest: &ComponentDigest,
     ) -> Result<WasmComponent, EngineError> {
-        {
-            let mut cache = self.memory_cache.lock().unwrap();
-            if let Some(component) = cache.get(digest) {
-                return Ok(component.clone());
-            }
+        if let Some(component) = self.cached_component(digest) {
+            return Ok(component);
+        }
+
+        let compile_lock = self
+            .compiling
+            .lock()
+            .unwrap()
+            .entry(digest.clone())
+            .or_default()
+            .clone();
+        let _guard = compile_lock.lock().await;
+
+        // another caller may have compiled it while we waited
+        if let Some(component) = self.cached_component(digest) {
+            return Ok(component);
         }
 
+        let result = self.compile_stored_component(digest);
+        self.compiling.lock().unwrap().remove(digest);
+        result
+    }
+
+    fn cached_component(&self, digest: &ComponentDigest) -> Option<WasmComponent> {
+        self.memory_cache.lock().unwrap().get(digest).cloned()
+    }
+
+    fn compile_stored_component(
+        &self,
+        digest: &ComponentDigest,
+    ) -> Result<WasmComponent, EngineError> {
         let bytes = self
             .storage
             .get_data(&digest.clone().into())
             .map_err(|e| EngineError::StorageError(format!("Failed to get component: {}", e)))?;
 
-        let component =
-            WasmComponent::new(&self.wasm_engine, &bytes).map_err(EngineError::Compile)?;
+        let component = self.compile_component(digest, &bytes)?;
 
         self.memory_cache
             .lock()
@@ -105,6 +174,63 @@ impl<S: CAStorage + Send + Sync + 'static> BaseEngine<S> {
         Ok(component)
     }
 
+    /// Deserializes the precompiled component if there is one, otherwise compiles the bytes
+    /// and writes it for next time
+    fn compile_component(
+        &self,
+        digest: &ComponentDigest,
+        bytes: &[u8],
+    ) -> Result<WasmComponent, EngineError> {
+        let path = self.precompiled_path(digest);
+
+        if path.is_file() {
+            let start = Instant::now();
+            // SAFETY: the file was written by `Component::serialize` below, in the node's own data dir.
+            // wasmtime rejects artifacts from an incompatible engine
+            match unsafe { WasmComponent::deserialize_file(&self.wasm_engine, &path) } {
+                Ok(component) => {
+                    tracing::debug!(
+                        "Loaded precompiled component {digest} in {:?}",
+                        start.elapsed()
+                    );
+                    return Ok(component);
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        "Discarding precompiled component {digest}, compiling instead: {err:?}"
+                    );
+                }
+            }
+        }
+
+        let start = Instant::now();
+        let component =
+            WasmComponent::new(&self.wasm_engine, bytes).map_err(EngineError::Compile)?;
+        self.compilations.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!("Compiled component {digest} in {:?}", start.elapsed());
+
+        // the cache is an optimization, a failure to write it only costs a compile on restart
+        if let Err(err) = component
+            .serialize()
+            .map_err(|e| e.to_string())
+            .and_then(|serialized| write_atomic(&path, &serialized).map_err(|e| e.to_string()))
+        {
+            tracing::warn!("Failed to write precompiled component {digest}: {err}");
+        }
+
+        Ok(component)
+    }
+
+    fn precompiled_path(&self, digest: &ComponentDigest) -> PathBuf {
+        self.precompiled_dir.join(format!("{digest}.cwasm"))
+    }
+
+    /// Number of components compiled since startup, loads served by the in-memory or
+    /// precompiled cache don't count
+    pub fn compilations(&self) -> u64 {
+        self.compilations.load(Ordering::Relaxed)
+    }
+
     pub async fn load_component_from_source(
         &self,
         source: &ComponentSource,
@@ -121,12 +247,21 @@ impl<S: CAStorage + Send + Sync + 'static> BaseEngine<S> {
                         })?
                     }
                     ComponentSource::Registry { registry } => {
-                        let client = WkgClient::new(
-                            registry.domain.clone().unwrap_or("wa.dev".to_string()),
-                        )?;
+                        let client =
+                            WkgClient::new(registry.domain.clone().unwrap_or("wa.dev".to_string()))
+                                .map_err(RegistryError::from)?;
 
                         client.fetch(registry).await?
                     }
+                    ComponentSource::Oci { reference, digest } => {
+                        let reference: OciReference =
+                            reference.parse().map_err(RegistryError::from)?;
+
+                        OciClient::new(self.oci_credentials.clone())
+                            .fetch(&reference, digest)
+                            .await
+                            .map_err(RegistryError::from)?
+                    }
                     _ => {
                         return Err(EngineError::UnknownDigest(digest.clone()));
                     }
@@ -142,8 +277,7 @@ impl<S: CAStorage + Send + Sync + 'static> BaseEngine<S> {
                     EngineError::StorageError(format!("Failed to store component: {}", e))
                 })?;
 
-                let component =
-                    WasmComponent::new(&self.wasm_engine, &bytes).map_err(EngineError::Compile)?;
+                let component = self.compile_component(digest, &bytes)?;
 
                 self.memory_cache
                     .lock()
@@ -157,8 +291,7 @@ impl<S: CAStorage + Send + Sync + 'static> BaseEngine<S> {
 
     pub fn store_component_bytes(&self, bytes: &[u8]) -> Result<ComponentDigest, EngineError> {
         // compile component (validate it is proper wasm)
-        let component =
-            WasmComponent::new(&self.wasm_engine, bytes).map_err(EngineError::Compile)?;
+        let component = self.compile_component(&ComponentDigest::hash(bytes), bytes)?;
 
         // store original wasm
         let digest = ComponentDigest::from(
@@ -168,9 +301,6 @@ impl<S: CAStorage + Send + Sync + 'static> BaseEngine<S> {
                 .inner(),
         );
 
-        // // TODO: write precompiled wasm (huge optimization on restart)
-        // tokio::fs::write(self.path_for_precompiled_wasm(digest), cm.serialize()?).await?;
-
         self.memory_cache
             .lock()
             .unwrap()
@@ -179,6 +309,28 @@ impl<S: CAStorage + Send + Sync + 'static> BaseEngine<S> {
         Ok(digest)
     }
 
+    /// Drops the component from the in-memory and precompiled caches and from storage.
+    /// Only call this once no service references the digest anymore
+    pub fn remove_component(&self, digest: &ComponentDigest) -> Result<(), EngineError> {
+        self.memory_cache.lock().unwrap().pop(digest);
+
+        match std::fs::remove_file(self.precompiled_path(digest)) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+                tracing::warn!("Failed to remove precompiled component {digest}: {err}");
+            }
+            _ => {}
+        }
+
+        self.storage
+            .remove_data(&digest.clone().into())
+            .map_err(|e| EngineError::StorageError(format!("Failed to remove component: {}", e)))
+    }
+
+    /// The filesystem sandbox shared by a service's components, so services never see each other's files
+    pub fn service_fs_dir(&self, service_id: &ServiceId) -> PathBuf {
+        self.component_fs_root.join(service_id.to_string())
+    }
+
     pub fn get_chain_configs(&self) -> Result<ChainConfigs, EngineError> {
         self.chain_configs
             .read()
@@ -186,3 +338,43 @@ impl<S: CAStorage + Send + Sync + 'static> BaseEngine<S> {
             .map_err(|e| EngineError::StorageError(format!("Chain configs lock poisoned: {}", e)))
     }
 }
+
+/// Feeds a [`Hash`] impl into sha256, so the result doesn't change between Rust releases
+/// the way [`std::hash::DefaultHasher`]'s may
+#[derive(Default)]
+struct StableHasher(Sha256);
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let hash = self.0.clone().finalize();
+        u64::from_be_bytes(hash[..8].try_into().unwrap())
+    }
+}
+
+/// Artifacts compiled by another wasmtime version can never be loaded again
+fn remove_stale_precompiled(root: &Path, current: &Path) {
+    let entries = match std::fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(err) => {
+            tracing::warn!("Failed to list {}: {err}", root.display());
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path != current && path.is_dir() {
+            tracing::info!(
+                "Removing stale precompiled components in {}",
+                path.display()
+            );
+            if let Err(err) = std::fs::remove_dir_all(&path) {
+                tracing::warn!("Failed to remove {}: {err}", path.display());
+            }
+        }
+    }
+}
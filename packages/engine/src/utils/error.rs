@@ -1,4 +1,7 @@
+use std::time::Duration;
+
 use thiserror::Error;
+use wasmtime::Trap;
 use wavs_types::{ComponentDigest, ServiceId, WorkflowId};
 
 #[derive(Error, Debug)]
@@ -12,6 +15,9 @@ pub enum EngineError {
     #[error("Component returned an error: {0:?}")]
     ComponentError(anyhow::Error),
 
+    #[error("Component trapped: {0:?}")]
+    Trapped(anyhow::Error),
+
     #[error("Workflow {workflow_id} not found for service {service_id}")]
     WorkflowNotFound {
         service_id: ServiceId,
@@ -33,6 +39,15 @@ pub enum EngineError {
     #[error("Time limit exceeded by WasmEngine for service: {0}, workflow: {1}")]
     OutOfTime(ServiceId, WorkflowId),
 
+    #[error("Execution timeout hit for service: {service_id} after {elapsed:?}")]
+    Timeout {
+        service_id: ServiceId,
+        elapsed: Duration,
+    },
+
+    #[error("Memory limit of {limit} bytes exceeded by component for service: {service_id}")]
+    MemoryLimitExceeded { service_id: ServiceId, limit: u64 },
+
     #[error("Unable to add to linker: {0}")]
     AddToLinker(wasmtime::Error),
 
@@ -52,10 +67,19 @@ pub enum EngineError {
     UnknownDigest(ComponentDigest),
 
     #[error("Registry: {0}")]
-    Registry(#[from] wasm_pkg_client::Error),
+    Registry(#[from] utils::wkg::RegistryError),
 
-    #[error("When returning multiple responses, they must all have an event id salt")]
-    MissingEventIdSalt,
+    #[error("Component imports interfaces the host does not provide: {}", .0.join(", "))]
+    UnsupportedImports(Vec<String>),
+
+    #[error("Component init failed: {0}")]
+    InitResult(String),
+
+    #[error("When returning multiple responses, they must all have an event id salt ({missing} of {responses} responses have none)")]
+    MissingEventIdSalt { responses: usize, missing: usize },
+
+    #[error("Multiple responses have ordering {0}, ordered responses must each have a distinct ordering")]
+    DuplicateOrdering(u64),
 
     #[error("Mismatched instance data and logger. Data: {data}, Logger: {logger}")]
     MismatchedInstanceDataAndLogger {
@@ -63,3 +87,106 @@ pub enum EngineError {
         logger: &'static str,
     },
 }
+
+/// Raised from the store's resource limiter when a guest tries to grow memory past its limit,
+/// so the failed call can be told apart from an ordinary trap
+#[derive(Error, Debug)]
+#[error("Memory limit of {limit} bytes exceeded")]
+pub struct MemoryLimitHit {
+    pub limit: u64,
+}
+
+/// Raised from the store's epoch callback once the service's execution timeout has passed
+#[derive(Error, Debug)]
+#[error("Execution timeout hit after {elapsed:?}")]
+pub struct ExecutionTimeoutHit {
+    pub elapsed: Duration,
+}
+
+impl EngineError {
+    /// Maps the error from calling a component export
+    pub fn from_call(e: anyhow::Error, service_id: ServiceId, workflow_id: WorkflowId) -> Self {
+        if let Some(hit) = e.downcast_ref::<MemoryLimitHit>() {
+            return EngineError::MemoryLimitExceeded {
+                service_id,
+                limit: hit.limit,
+            };
+        }
+
+        if let Some(hit) = e.downcast_ref::<ExecutionTimeoutHit>() {
+            return EngineError::Timeout {
+                service_id,
+                elapsed: hit.elapsed,
+            };
+        }
+
+        match e.downcast_ref::<Trap>() {
+            Some(t) if *t == Trap::OutOfFuel => EngineError::OutOfFuel(service_id, workflow_id),
+            Some(t) if *t == Trap::Interrupt => EngineError::OutOfTime(service_id, workflow_id),
+            Some(_) => EngineError::Trapped(e),
+            None => EngineError::ComponentError(e),
+        }
+    }
+
+    /// Classifies this error into the coarse outcome used for logging and metrics
+    pub fn outcome(&self) -> ExecutionOutcome {
+        match self {
+            EngineError::ExecResult(_)
+            | EngineError::InitResult(_)
+            | EngineError::MissingEventIdSalt { .. }
+            | EngineError::DuplicateOrdering(_) => ExecutionOutcome::ApplicationError,
+            EngineError::Trapped(_) => ExecutionOutcome::Trapped,
+            EngineError::OutOfFuel(..) => ExecutionOutcome::OutOfFuel,
+            EngineError::OutOfTime(..) | EngineError::Timeout { .. } => ExecutionOutcome::OutOfTime,
+            EngineError::MemoryLimitExceeded { .. } => ExecutionOutcome::OutOfMemory,
+            _ => ExecutionOutcome::HostError,
+        }
+    }
+}
+
+/// How a component execution ended.
+///
+/// An `ApplicationError` is the component cleanly returning `Err(msg)` (e.g. bad input),
+/// whereas `Trapped` means the guest panicked or hit `unreachable`, a memory fault, etc.
+/// The former is expected behavior, the latter is a bug in the component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionOutcome {
+    Success,
+    ApplicationError,
+    Trapped,
+    OutOfFuel,
+    OutOfTime,
+    OutOfMemory,
+    HostError,
+}
+
+impl ExecutionOutcome {
+    pub fn from_result<T>(result: &Result<T, EngineError>) -> Self {
+        match result {
+            Ok(_) => ExecutionOutcome::Success,
+            Err(e) => e.outcome(),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        matches!(self, ExecutionOutcome::Success)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExecutionOutcome::Success => "success",
+            ExecutionOutcome::ApplicationError => "application_error",
+            ExecutionOutcome::Trapped => "trapped",
+            ExecutionOutcome::OutOfFuel => "out_of_fuel",
+            ExecutionOutcome::OutOfTime => "out_of_time",
+            ExecutionOutcome::OutOfMemory => "out_of_memory",
+            ExecutionOutcome::HostError => "host_error",
+        }
+    }
+}
+
+impl std::fmt::Display for ExecutionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,19 @@
+//! Hashing exposed to components through the `host::hash` import, shared by all worlds.
+
+use sha2::Digest;
+
+/// Mirrors the `hash-algorithm` enum of each world's `host` interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Keccak256,
+    Sha256,
+    Blake3,
+}
+
+pub fn hash(algorithm: HashAlgorithm, data: &[u8]) -> Vec<u8> {
+    match algorithm {
+        HashAlgorithm::Keccak256 => alloy_primitives::keccak256(data).to_vec(),
+        HashAlgorithm::Sha256 => sha2::Sha256::digest(data).to_vec(),
+        HashAlgorithm::Blake3 => blake3::hash(data).as_bytes().to_vec(),
+    }
+}
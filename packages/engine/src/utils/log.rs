@@ -0,0 +1,50 @@
+//! Fields for the `host::log-structured` import, shared by all worlds.
+
+use std::fmt;
+
+/// Shows a component's log fields as logfmt, e.g. `chain=evm:1 note="two words"`,
+/// so they can be recorded as a single tracing field and still be parsed back out
+pub struct LogFields<'a>(pub &'a [(String, String)]);
+
+impl fmt::Display for LogFields<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (key, value)) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" ")?;
+            }
+
+            let needs_quotes = value.is_empty()
+                || value
+                    .chars()
+                    .any(|c| c.is_whitespace() || c == '"' || c == '=' || c == '\\');
+
+            if needs_quotes {
+                write!(f, "{key}={value:?}")?;
+            } else {
+                write!(f, "{key}={value}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn logfmt() {
+        let fields = [
+            ("chain".to_string(), "evm:1".to_string()),
+            ("note".to_string(), "two words".to_string()),
+            ("empty".to_string(), String::new()),
+        ];
+
+        assert_eq!(
+            LogFields(&fields).to_string(),
+            r#"chain=evm:1 note="two words" empty="""#
+        );
+        assert_eq!(LogFields(&[]).to_string(), "");
+    }
+}
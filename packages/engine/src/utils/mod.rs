@@ -1 +1,4 @@
 pub mod error;
+pub mod hash;
+pub mod log;
+pub mod stats;
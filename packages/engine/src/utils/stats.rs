@@ -0,0 +1,176 @@
+//! Running statistics exposed to components through `host::update-stat` and `host::get-stat`.
+//!
+//! Each stat is stored as one keyvalue entry holding all-time totals, an EMA and
+//! a bounded ring of per-minute buckets, so windowed queries never need the raw samples.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+use utils::storage::db::WavsDb;
+
+/// Keyvalue bucket the host keeps stats in, components shouldn't write to it directly
+pub const STATS_BUCKET: &str = "wavs-stats";
+/// Weight of the newest sample in the exponential moving average
+pub const EMA_ALPHA: f64 = 0.1;
+/// Granularity of windowed queries
+pub const BUCKET_SECS: u64 = 60;
+/// One day of per-minute buckets
+pub const MAX_BUCKETS: usize = 1440;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatSummary {
+    pub count: u64,
+    pub mean: f64,
+    pub ema: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunningStat {
+    total: Bucket,
+    ema: f64,
+    buckets: VecDeque<Bucket>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Bucket {
+    start: u64,
+    count: u64,
+    sum: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Bucket {
+    fn add(&mut self, value: f64) {
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.count += 1;
+        self.sum += value;
+    }
+
+    fn merge(&mut self, other: &Bucket) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            self.min = other.min;
+            self.max = other.max;
+        } else {
+            self.min = self.min.min(other.min);
+            self.max = self.max.max(other.max);
+        }
+        self.count += other.count;
+        self.sum += other.sum;
+    }
+}
+
+impl RunningStat {
+    pub fn update(&mut self, value: f64, now_secs: u64) {
+        self.ema = if self.total.count == 0 {
+            value
+        } else {
+            EMA_ALPHA * value + (1.0 - EMA_ALPHA) * self.ema
+        };
+        self.total.add(value);
+
+        let start = now_secs - now_secs % BUCKET_SECS;
+        match self.buckets.back_mut() {
+            Some(bucket) if bucket.start == start => bucket.add(value),
+            _ => {
+                let mut bucket = Bucket {
+                    start,
+                    ..Default::default()
+                };
+                bucket.add(value);
+                self.buckets.push_back(bucket);
+            }
+        }
+
+        let cutoff = now_secs.saturating_sub(MAX_BUCKETS as u64 * BUCKET_SECS);
+        while self
+            .buckets
+            .front()
+            .is_some_and(|bucket| bucket.start + BUCKET_SECS <= cutoff)
+        {
+            self.buckets.pop_front();
+        }
+    }
+
+    /// All-time summary, or only the buckets overlapping the last `window_secs` seconds
+    pub fn summary(&self, window_secs: Option<u64>, now_secs: u64) -> Option<StatSummary> {
+        let range = match window_secs {
+            None => self.total.clone(),
+            Some(window_secs) => {
+                let cutoff = now_secs.saturating_sub(window_secs);
+                let mut range = Bucket::default();
+                for bucket in self
+                    .buckets
+                    .iter()
+                    .filter(|bucket| bucket.start + BUCKET_SECS > cutoff)
+                {
+                    range.merge(bucket);
+                }
+                range
+            }
+        };
+
+        if range.count == 0 {
+            return None;
+        }
+
+        Some(StatSummary {
+            count: range.count,
+            mean: range.sum / range.count as f64,
+            ema: self.ema,
+            min: range.min,
+            max: range.max,
+        })
+    }
+}
+
+/// Add a sample to the stat stored under `key`, atomically with respect to other updates
+pub fn update_stat(
+    db: &WavsDb,
+    key: String,
+    value: f64,
+    now_secs: u64,
+) -> Result<StatSummary, String> {
+    if !value.is_finite() {
+        return Err(format!("Stat values must be finite, got {value}"));
+    }
+
+    db.kv_store.update(key, |entry| {
+        let mut stat = match entry {
+            Some(bytes) => decode(bytes)?,
+            None => RunningStat::default(),
+        };
+
+        stat.update(value, now_secs);
+        *entry = Some(serde_json::to_vec(&stat).map_err(|e| e.to_string())?);
+
+        Ok(stat.summary(None, now_secs).expect("just added a sample"))
+    })
+}
+
+pub fn get_stat(
+    db: &WavsDb,
+    key: String,
+    window_secs: Option<u64>,
+    now_secs: u64,
+) -> Result<Option<StatSummary>, String> {
+    match db.kv_store.get_cloned(&key) {
+        Some(bytes) => Ok(decode(&bytes)?.summary(window_secs, now_secs)),
+        None => Ok(None),
+    }
+}
+
+fn decode(bytes: &[u8]) -> Result<RunningStat, String> {
+    serde_json::from_slice(bytes).map_err(|e| format!("Corrupt stat entry: {e}"))
+}
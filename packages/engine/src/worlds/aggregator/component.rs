@@ -1,26 +1,43 @@
+use std::collections::BTreeMap;
+
 use wasmtime_wasi::{WasiCtx, WasiCtxView, WasiView};
-use wasmtime_wasi_http::{WasiHttpCtx, WasiHttpView};
+use wasmtime_wasi_http::{
+    body::HyperOutgoingBody,
+    types::{HostFutureIncomingResponse, OutgoingRequestConfig},
+    HttpResult, WasiHttpCtx, WasiHttpView,
+};
 use wasmtime_wasi_tls::WasiTlsCtx;
-use wavs_types::{ChainConfigs, ComponentDigest, EventId, Service, ServiceId, WorkflowId};
+use wavs_types::{
+    AllowedHostPermission, ChainConfigs, ComponentDigest, EventId, Service, ServiceId,
+    SubmitAction, WorkflowId,
+};
 
 use crate::{
-    backend::wasi_keyvalue::context::KeyValueCtx, bindings::aggregator::world::host::LogLevel,
+    backend::{evm_blocks::EvmBlocks, outbound_http, wasi_keyvalue::context::KeyValueCtx},
+    bindings::aggregator::world::host::LogLevel,
+    worlds::instance::MemoryLimiter,
 };
 
 pub type AggregatorHostComponentLogger =
-    fn(&ServiceId, &WorkflowId, &ComponentDigest, LogLevel, String);
+    fn(&ServiceId, &WorkflowId, &ComponentDigest, LogLevel, String, &[(String, String)]);
 
 pub struct AggregatorHostComponent {
     pub service: Service,
     pub workflow_id: WorkflowId,
     pub chain_configs: ChainConfigs,
     pub event_id: EventId,
+    /// Set for submit callbacks, the destination the result is for
+    pub submit_action: Option<SubmitAction>,
     pub(crate) table: wasmtime::component::ResourceTable,
     pub(crate) ctx: WasiCtx,
     pub(crate) http_ctx: WasiHttpCtx,
     pub(crate) tls_ctx: WasiTlsCtx,
     pub(crate) keyvalue_ctx: KeyValueCtx,
+    pub(crate) evm_blocks: EvmBlocks,
+    pub(crate) secrets: BTreeMap<String, String>,
+    pub(crate) allowed_http_hosts: AllowedHostPermission,
     pub(crate) inner_log: AggregatorHostComponentLogger,
+    pub(crate) limiter: MemoryLimiter,
 }
 
 impl WasiView for AggregatorHostComponent {
@@ -40,4 +57,12 @@ impl WasiHttpView for AggregatorHostComponent {
     fn table(&mut self) -> &mut wasmtime::component::ResourceTable {
         &mut self.table
     }
+
+    fn send_request(
+        &mut self,
+        request: http::Request<HyperOutgoingBody>,
+        config: OutgoingRequestConfig,
+    ) -> HttpResult<HostFutureIncomingResponse> {
+        outbound_http::send_request(&self.allowed_http_hosts, request, config)
+    }
 }
@@ -0,0 +1,103 @@
+//! Optionally makes the WASI clocks and randomness a component sees depend only on the event
+//! it's running for, so replays and every operator running the same trigger get the same
+//! answers from them.
+//!
+//! With `clock` set, `wasi:clocks/wall-clock` reads the trigger's own time, which is the block
+//! timestamp for EVM events, the scheduled time for cron and the event time for ATProto.
+//! Triggers without a time of their own (cosmos events, block intervals, hypercore, raw data)
+//! read the Unix epoch. `wasi:clocks/monotonic-clock` reads zero, timeouts and sleeps still
+//! wait in real time. Aggregator components aren't run for a single trigger and keep the
+//! host clocks.
+//!
+//! With `rng` set, `wasi:random/random` and `wasi:random/insecure` are seeded from the event id,
+//! the one derived from the trigger for operator components (not a response's salt).
+//! `wasi:random/insecure-seed` returns a value derived from the same seed.
+//! Anything using these for secrets gets values every operator, and anyone who knows the event
+//! id, can reproduce.
+
+use std::time::Duration;
+
+use cap_rand::{rngs::StdRng, SeedableRng};
+use sha2::{Digest, Sha256};
+use wasmtime_wasi::{HostMonotonicClock, HostWallClock, WasiCtxBuilder};
+use wavs_types::{EventId, TriggerData};
+
+/// Which WASI sources of non-determinism to pin, both off by default
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Determinism {
+    pub clock: bool,
+    pub rng: bool,
+}
+
+impl Determinism {
+    /// Pins the clocks to `time` (if it's known) and seeds randomness from `event_id`,
+    /// as far as each is enabled
+    pub fn apply(&self, builder: &mut WasiCtxBuilder, time: Option<Duration>, event_id: &EventId) {
+        if self.clock {
+            if let Some(time) = time {
+                builder
+                    .wall_clock(FixedWallClock(time))
+                    .monotonic_clock(FixedMonotonicClock);
+            }
+        }
+
+        if self.rng {
+            let seed = rng_seed(event_id);
+            let mut insecure_seed = [0u8; 16];
+            insecure_seed.copy_from_slice(&seed[..16]);
+
+            builder
+                .secure_random(StdRng::from_seed(seed))
+                .insecure_random(StdRng::from_seed(Sha256::digest(seed).into()))
+                .insecure_random_seed(u128::from_le_bytes(insecure_seed));
+        }
+    }
+}
+
+/// The time a trigger happened at, as reported by its source, the Unix epoch if it has none
+pub fn trigger_time(trigger_data: &TriggerData) -> Duration {
+    match trigger_data {
+        TriggerData::EvmContractEvent {
+            block_timestamp: Some(secs),
+            ..
+        } => Duration::from_secs(*secs),
+        TriggerData::Cron { trigger_time, .. } => Duration::from_nanos(trigger_time.as_nanos()),
+        TriggerData::AtProtoEvent { timestamp, .. } => {
+            Duration::from_micros((*timestamp).max(0) as u64)
+        }
+        _ => Duration::ZERO,
+    }
+}
+
+fn rng_seed(event_id: &EventId) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"wavs-wasi-random");
+    hasher.update(event_id.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Always reads the same time since the Unix epoch
+pub struct FixedWallClock(pub Duration);
+
+impl HostWallClock for FixedWallClock {
+    fn resolution(&self) -> Duration {
+        Duration::from_secs(1)
+    }
+
+    fn now(&self) -> Duration {
+        self.0
+    }
+}
+
+/// Always reads zero
+pub struct FixedMonotonicClock;
+
+impl HostMonotonicClock for FixedMonotonicClock {
+    fn resolution(&self) -> u64 {
+        1
+    }
+
+    fn now(&self) -> u64 {
+        0
+    }
+}
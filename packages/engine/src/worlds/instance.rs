@@ -1,29 +1,73 @@
+use std::collections::BTreeMap;
+use std::future::Future;
 use std::path::Path;
+use std::time::{Duration, Instant};
 
 use utils::config::WAVS_ENV_PREFIX;
 use wasmtime::component::HasSelf;
-use wasmtime::Store;
 use wasmtime::{component::Linker, Engine as WTEngine};
-use wasmtime_wasi::{DirPerms, FilePerms, WasiCtxBuilder, WasiView};
-use wasmtime_wasi_http::{WasiHttpCtx, WasiHttpView};
+use wasmtime::{ResourceLimiter, Store};
+use wasmtime_wasi::{DirPerms, FilePerms, WasiCtxBuilder};
+use wasmtime_wasi_http::WasiHttpCtx;
 use wasmtime_wasi_tls::{WasiTls, WasiTlsCtxBuilder};
 use wavs_types::{
-    AllowedHostPermission, ChainConfigs, EventId, Permissions, Service, TriggerData, Workflow,
+    ChainConfigs, EventId, EventIdSalt, Service, ServiceId, SubmitAction, TriggerData, Workflow,
     WorkflowId,
 };
 
-use crate::backend::wasi_keyvalue::context::KeyValueCtxProvider;
 use crate::worlds::aggregator::component::{
     AggregatorHostComponent, AggregatorHostComponentLogger,
 };
+use crate::worlds::determinism::{trigger_time, Determinism};
+use crate::worlds::interfaces;
 use crate::worlds::operator::component::{OperatorHostComponent, OperatorHostComponentLogger};
-use crate::{backend::wasi_keyvalue::context::KeyValueCtx, utils::error::EngineError};
+use crate::worlds::output::GuestOutput;
+use crate::{
+    backend::{evm_blocks::EvmBlocks, wasi_keyvalue::context::KeyValueCtx},
+    utils::error::{EngineError, ExecutionTimeoutHit, MemoryLimitHit},
+};
 
 // how often to yield to check for epoch interruption
 // this is in milliseconds since that's the unit we use for driving the epoch
 // via increment_epoch()
 pub const EPOCH_YIELD_PERIOD_MS: u64 = 100;
 
+/// Caps how large a component's linear memories may grow.
+/// Going past the cap fails the call with [`MemoryLimitHit`] rather than letting `memory.grow` return -1,
+/// since most guests abort with an opaque trap on allocation failure
+pub struct MemoryLimiter {
+    limit: Option<u64>,
+}
+
+impl MemoryLimiter {
+    pub fn new(limit: Option<u64>) -> Self {
+        Self { limit }
+    }
+}
+
+impl ResourceLimiter for MemoryLimiter {
+    fn memory_growing(
+        &mut self,
+        _current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> anyhow::Result<bool> {
+        match self.limit {
+            Some(limit) if desired as u64 > limit => Err(MemoryLimitHit { limit }.into()),
+            _ => Ok(true),
+        }
+    }
+
+    fn table_growing(
+        &mut self,
+        _current: usize,
+        _desired: usize,
+        _maximum: Option<usize>,
+    ) -> anyhow::Result<bool> {
+        Ok(true)
+    }
+}
+
 pub enum HostComponentLogger {
     OperatorHostComponentLogger(OperatorHostComponentLogger),
     AggregatorHostComponentLogger(AggregatorHostComponentLogger),
@@ -86,11 +130,24 @@ pub struct InstanceDepsBuilder<'a, P> {
     pub chain_configs: &'a ChainConfigs,
     pub log: HostComponentLogger,
     pub keyvalue_ctx: KeyValueCtx,
+    pub evm_blocks: EvmBlocks,
+    /// Every secret the node holds for the service, the component only sees the ones it's granted
+    pub secrets: BTreeMap<String, String>,
+    /// Which WASI clocks and randomness are pinned to the event, see [`Determinism`]
+    pub determinism: Determinism,
+    /// Keep what the component writes to stdout and stderr rather than passing it through
+    /// to the node's own, see [`GuestOutput`]
+    pub capture_output: bool,
 }
 
 pub enum InstanceData {
-    Operator { trigger_data: Box<TriggerData> },
-    Aggregator { event_id: EventId },
+    Operator {
+        trigger_data: Box<TriggerData>,
+    },
+    Aggregator {
+        event_id: EventId,
+        submit_action: Option<SubmitAction>,
+    },
 }
 
 impl InstanceData {
@@ -101,7 +158,21 @@ impl InstanceData {
     }
 
     pub fn new_aggregator(event_id: EventId) -> Self {
-        InstanceData::Aggregator { event_id }
+        InstanceData::Aggregator {
+            event_id,
+            submit_action: None,
+        }
+    }
+
+    /// For submit callbacks, so the component can tell which destination the result is for
+    pub fn with_submit_action(self, action: SubmitAction) -> Self {
+        match self {
+            InstanceData::Aggregator { event_id, .. } => InstanceData::Aggregator {
+                event_id,
+                submit_action: Some(action),
+            },
+            data => data,
+        }
     }
 }
 
@@ -110,6 +181,61 @@ pub struct InstanceDeps {
     pub component: wasmtime::component::Component,
     pub linker: ComponentLinker,
     pub time_limit_seconds: u64,
+    /// Set if the service has an execution timeout, counted from when the instance was built
+    pub execution_deadline: Option<ExecutionDeadline>,
+    /// What the component writes to stdout and stderr
+    pub output: GuestOutput,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ExecutionDeadline {
+    pub started: Instant,
+    pub timeout: Duration,
+}
+
+impl ExecutionDeadline {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            started: Instant::now(),
+            timeout,
+        }
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.timeout.saturating_sub(self.started.elapsed())
+    }
+
+    pub fn has_passed(&self) -> bool {
+        self.started.elapsed() >= self.timeout
+    }
+}
+
+/// Runs a call into a component under the workflow's time limit, and the service's execution
+/// timeout if it ends first.
+/// The epoch callback enforces the execution timeout from inside the component, this catches
+/// components parked in a host call (e.g. an async sleep) where no wasm runs to hit it
+pub async fn time_limited<T>(
+    time_limit_seconds: u64,
+    deadline: Option<ExecutionDeadline>,
+    service_id: ServiceId,
+    workflow_id: WorkflowId,
+    call: impl Future<Output = Result<T, EngineError>>,
+) -> Result<T, EngineError> {
+    let time_limit = Duration::from_secs(time_limit_seconds);
+
+    match deadline {
+        Some(deadline) if deadline.remaining() < time_limit => {
+            tokio::time::timeout(deadline.remaining(), call)
+                .await
+                .map_err(|_| EngineError::Timeout {
+                    service_id,
+                    elapsed: deadline.started.elapsed(),
+                })?
+        }
+        _ => tokio::time::timeout(time_limit, call)
+            .await
+            .map_err(|_| EngineError::OutOfTime(service_id, workflow_id))?,
+    }
 }
 
 impl<P: AsRef<Path>> InstanceDepsBuilder<'_, P> {
@@ -124,6 +250,10 @@ impl<P: AsRef<Path>> InstanceDepsBuilder<'_, P> {
             chain_configs,
             log,
             keyvalue_ctx,
+            evm_blocks,
+            secrets,
+            determinism,
+            capture_output,
         } = self;
 
         match (&data, &log) {
@@ -171,7 +301,7 @@ impl<P: AsRef<Path>> InstanceDepsBuilder<'_, P> {
                     .unwrap();
 
                     let component = workflow.component.clone();
-                    configure_linker(&mut linker, &component.permissions)?;
+                    interfaces::add_to_linker(&mut linker, &component.permissions)?;
 
                     wasmtime_wasi_tls::add_to_linker(
                         &mut linker,
@@ -195,7 +325,7 @@ impl<P: AsRef<Path>> InstanceDepsBuilder<'_, P> {
                         wavs_types::Submit::None => unreachable!(),
                         wavs_types::Submit::Aggregator { component, .. } => (**component).clone(),
                     };
-                    configure_linker(&mut linker, &component.permissions)?;
+                    interfaces::add_to_linker(&mut linker, &component.permissions)?;
 
                     wasmtime_wasi_tls::add_to_linker(
                         &mut linker,
@@ -215,8 +345,6 @@ impl<P: AsRef<Path>> InstanceDepsBuilder<'_, P> {
         // create wasi context
         let mut builder = WasiCtxBuilder::new();
 
-        builder.inherit_stdout().inherit_stderr();
-
         // conditionally allow fs access
         if wavs_component.permissions.file_system {
             // we namespace by service id so that all components within a service have access to the same data
@@ -230,6 +358,26 @@ impl<P: AsRef<Path>> InstanceDepsBuilder<'_, P> {
                 .map_err(EngineError::Filesystem)?;
         }
 
+        let event_id = match &data {
+            InstanceData::Operator { trigger_data } => {
+                let event_id = EventId::new(
+                    &service.id(),
+                    &workflow_id,
+                    EventIdSalt::Trigger(trigger_data),
+                )
+                .map_err(|e| EngineError::Input(e.into()))?;
+                determinism.apply(&mut builder, Some(trigger_time(trigger_data)), &event_id);
+                event_id
+            }
+            InstanceData::Aggregator { event_id, .. } => {
+                determinism.apply(&mut builder, None, event_id);
+                event_id.clone()
+            }
+        };
+
+        let output = GuestOutput::new(service.id(), workflow_id.clone(), event_id, capture_output);
+        output.attach(&mut builder);
+
         // conditionally allow raw network access
         if wavs_component.permissions.raw_sockets {
             builder.inherit_network();
@@ -241,15 +389,25 @@ impl<P: AsRef<Path>> InstanceDepsBuilder<'_, P> {
         }
 
         // read in system env variables that are prefixed with WAVS_ENV and are allowed to access via the component config
-        let env: Vec<_> = std::env::vars()
-            .filter(|(key, _)| {
-                key.starts_with(WAVS_ENV_PREFIX) && wavs_component.env_keys.contains(key)
-            })
+        // components that are granted secrets get them through the host instead, and no env at all
+        if wavs_component.secrets.is_empty() {
+            let env: Vec<_> = std::env::vars()
+                .filter(|(key, _)| {
+                    key.starts_with(WAVS_ENV_PREFIX) && wavs_component.env_keys.contains(key)
+                })
+                .collect();
+
+            if !env.is_empty() {
+                builder.envs(&env);
+            }
+        }
+
+        let secrets: BTreeMap<String, String> = secrets
+            .into_iter()
+            .filter(|(name, _)| wavs_component.secrets.contains(name))
             .collect();
 
-        if !env.is_empty() {
-            builder.envs(&env);
-        }
+        let allowed_http_hosts = wavs_component.permissions.allowed_http_hosts.clone();
 
         let fuel_limit = wavs_component
             .fuel_limit
@@ -259,6 +417,12 @@ impl<P: AsRef<Path>> InstanceDepsBuilder<'_, P> {
             .time_limit_seconds
             .unwrap_or(Workflow::DEFAULT_TIME_LIMIT_SECONDS);
 
+        let max_memory_bytes = wavs_component.max_memory_bytes;
+
+        let execution_deadline = service
+            .execution_timeout_secs
+            .map(|secs| ExecutionDeadline::new(Duration::from_secs(secs)));
+
         let ctx = builder.build();
 
         let tls_ctx = WasiTlsCtxBuilder::new().build();
@@ -274,38 +438,52 @@ impl<P: AsRef<Path>> InstanceDepsBuilder<'_, P> {
                     table: wasmtime::component::ResourceTable::new(),
                     ctx,
                     keyvalue_ctx,
+                    evm_blocks,
+                    secrets,
+                    allowed_http_hosts,
                     http_ctx: WasiHttpCtx::new(),
                     tls_ctx,
                     inner_log: match log {
                         HostComponentLogger::OperatorHostComponentLogger(log) => log,
                         _ => unreachable!(),
                     },
+                    limiter: MemoryLimiter::new(max_memory_bytes),
                 };
                 let mut store = wasmtime::Store::new(engine, host);
+                store.limiter(|host| &mut host.limiter);
 
-                configure_store(&mut store, fuel_limit)?;
+                configure_store(&mut store, fuel_limit, execution_deadline)?;
 
                 ComponentStore::OperatorComponentStore(store)
             }
-            InstanceData::Aggregator { event_id } => {
+            InstanceData::Aggregator {
+                event_id,
+                submit_action,
+            } => {
                 let host = AggregatorHostComponent {
                     service,
                     workflow_id,
                     chain_configs: chain_configs.clone(),
                     table: wasmtime::component::ResourceTable::new(),
                     event_id,
+                    submit_action,
                     ctx,
                     keyvalue_ctx,
+                    evm_blocks,
+                    secrets,
+                    allowed_http_hosts,
                     http_ctx: WasiHttpCtx::new(),
                     tls_ctx,
                     inner_log: match log {
                         HostComponentLogger::AggregatorHostComponentLogger(log) => log,
                         _ => unreachable!(),
                     },
+                    limiter: MemoryLimiter::new(max_memory_bytes),
                 };
                 let mut store = wasmtime::Store::new(engine, host);
+                store.limiter(|host| &mut host.limiter);
 
-                configure_store(&mut store, fuel_limit)?;
+                configure_store(&mut store, fuel_limit, execution_deadline)?;
 
                 ComponentStore::AggregatorComponentStore(store)
             }
@@ -316,19 +494,32 @@ impl<P: AsRef<Path>> InstanceDepsBuilder<'_, P> {
             component,
             linker,
             time_limit_seconds,
+            execution_deadline,
+            output,
         })
     }
 }
 
-fn configure_store<T>(store: &mut Store<T>, fuel_limit: u64) -> Result<(), EngineError> {
+fn configure_store<T>(
+    store: &mut Store<T>,
+    fuel_limit: u64,
+    execution_deadline: Option<ExecutionDeadline>,
+) -> Result<(), EngineError> {
     store.set_fuel(fuel_limit).map_err(EngineError::Store)?;
 
-    // this only configures the component to yield periodically
-    // killing is done from the outside via a tokio timeout
+    // this configures the component to yield periodically, and to stop once the execution deadline passes
+    // otherwise killing is done from the outside via a tokio timeout
     // The reason we use epoch_deadline_callback instead of epoch_deadline_async_yield_and_update
     // is because the latter appears to have a bug where it doesn't always schedule nicely with tokio
     // See https://github.com/dakom/debug-wasmtime-concurrency for more info
     store.epoch_deadline_callback(move |_| {
+        if let Some(deadline) = execution_deadline.filter(ExecutionDeadline::has_passed) {
+            return Err(ExecutionTimeoutHit {
+                elapsed: deadline.started.elapsed(),
+            }
+            .into());
+        }
+
         Ok(wasmtime::UpdateDeadline::YieldCustom(
             EPOCH_YIELD_PERIOD_MS,
             Box::pin(tokio::task::yield_now()),
@@ -337,21 +528,3 @@ fn configure_store<T>(store: &mut Store<T>, fuel_limit: u64) -> Result<(), Engin
 
     Ok(())
 }
-
-fn configure_linker<T: WasiView + WasiHttpView + KeyValueCtxProvider>(
-    linker: &mut Linker<T>,
-    permissions: &Permissions,
-) -> Result<(), EngineError> {
-    // wasmtime_wasi::add_to_linker_sync(&mut linker).unwrap();
-    // wasmtime_wasi_http::add_only_http_to_linker_sync(&mut linker).unwrap();
-    wasmtime_wasi::p2::add_to_linker_async(linker).unwrap();
-    // don't add http support if we don't allow it
-    // FIXME: we need to apply Only(host) checks as well, but that involves some wat magic
-    if permissions.allowed_http_hosts != AllowedHostPermission::None {
-        wasmtime_wasi_http::add_only_http_to_linker_async(linker).unwrap();
-    }
-
-    KeyValueCtx::add_to_linker(linker)?;
-
-    Ok(())
-}
@@ -0,0 +1,196 @@
+//! The single source of truth for which interfaces the host provides to components.
+//!
+//! The same list is used to populate the `Linker` and to validate a component's imports
+//! ahead of time, so the two can't drift apart.
+
+use wasmtime::{
+    component::{Component, Linker},
+    Engine as WTEngine,
+};
+use wasmtime_wasi::WasiView;
+use wasmtime_wasi_http::WasiHttpView;
+use wavs_types::{AllowedHostPermission, Permissions};
+
+use crate::{
+    backend::wasi_keyvalue::context::{KeyValueCtx, KeyValueCtxProvider},
+    utils::error::EngineError,
+};
+
+/// How an interface gets added to the linker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostInterfaceKind {
+    /// wasi:cli, wasi:clocks, wasi:filesystem, etc.
+    WasiP2,
+    /// wasi:http, only linked if the component is allowed any http hosts
+    WasiHttp,
+    /// wasi:keyvalue backed by the WAVS db
+    KeyValue,
+    /// wasi:tls, linked per-world since it needs the concrete host type
+    WasiTls,
+    /// wavs types and the world's `host` interface, linked by the generated bindings
+    World,
+}
+
+impl HostInterfaceKind {
+    pub fn is_enabled(&self, permissions: &Permissions) -> bool {
+        match self {
+            HostInterfaceKind::WasiHttp => {
+                permissions.allowed_http_hosts != AllowedHostPermission::None
+            }
+            _ => true,
+        }
+    }
+}
+
+/// A WIT package provided by the host
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostInterface {
+    pub kind: HostInterfaceKind,
+    /// e.g. `wasi:http`
+    pub package: &'static str,
+    /// Components may import any semver-compatible version
+    pub version: &'static str,
+}
+
+impl HostInterface {
+    const fn new(kind: HostInterfaceKind, package: &'static str, version: &'static str) -> Self {
+        Self {
+            kind,
+            package,
+            version,
+        }
+    }
+
+    /// Whether an import of `package@version` is satisfied by this interface
+    pub fn provides(&self, package: &str, version: Option<&str>) -> bool {
+        if self.package != package {
+            return false;
+        }
+
+        match version {
+            Some(version) => versions_compatible(self.version, version),
+            None => true,
+        }
+    }
+}
+
+pub const HOST_INTERFACES: &[HostInterface] = &[
+    HostInterface::new(HostInterfaceKind::WasiP2, "wasi:cli", "0.2.0"),
+    HostInterface::new(HostInterfaceKind::WasiP2, "wasi:clocks", "0.2.0"),
+    HostInterface::new(HostInterfaceKind::WasiP2, "wasi:filesystem", "0.2.0"),
+    HostInterface::new(HostInterfaceKind::WasiP2, "wasi:io", "0.2.0"),
+    HostInterface::new(HostInterfaceKind::WasiP2, "wasi:random", "0.2.0"),
+    HostInterface::new(HostInterfaceKind::WasiP2, "wasi:sockets", "0.2.0"),
+    HostInterface::new(HostInterfaceKind::WasiHttp, "wasi:http", "0.2.0"),
+    HostInterface::new(HostInterfaceKind::KeyValue, "wasi:keyvalue", "0.2.0-draft2"),
+    HostInterface::new(HostInterfaceKind::WasiTls, "wasi:tls", "0.2.0-draft"),
+    HostInterface::new(HostInterfaceKind::World, "wavs:types", "3.0.0"),
+    HostInterface::new(HostInterfaceKind::World, "wavs:operator", "3.0.0"),
+];
+
+/// Unversioned, world-local imports (e.g. `import host: interface { ... }`)
+pub const WORLD_LOCAL_IMPORTS: &[&str] = &["host"];
+
+/// Add every enabled interface that doesn't depend on the concrete world to the linker
+pub fn add_to_linker<T: WasiView + WasiHttpView + KeyValueCtxProvider>(
+    linker: &mut Linker<T>,
+    permissions: &Permissions,
+) -> Result<(), EngineError> {
+    let mut kinds: Vec<HostInterfaceKind> = Vec::new();
+    for interface in HOST_INTERFACES {
+        if interface.kind.is_enabled(permissions) && !kinds.contains(&interface.kind) {
+            kinds.push(interface.kind);
+        }
+    }
+
+    for kind in kinds {
+        match kind {
+            HostInterfaceKind::WasiP2 => {
+                wasmtime_wasi::p2::add_to_linker_async(linker).map_err(EngineError::AddToLinker)?
+            }
+            HostInterfaceKind::WasiHttp => {
+                // `Only(hosts)` is enforced per request, see `backend::outbound_http::send_request`
+                wasmtime_wasi_http::add_only_http_to_linker_async(linker)
+                    .map_err(EngineError::AddToLinker)?
+            }
+            HostInterfaceKind::KeyValue => KeyValueCtx::add_to_linker(linker)?,
+            HostInterfaceKind::WasiTls | HostInterfaceKind::World => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that every import of the component is provided by the host with the given permissions
+pub fn validate_imports(
+    engine: &WTEngine,
+    component: &Component,
+    permissions: &Permissions,
+) -> Result<(), EngineError> {
+    let mut unsupported = Vec::new();
+
+    for (name, _) in component.component_type().imports(engine) {
+        if WORLD_LOCAL_IMPORTS.contains(&name) {
+            continue;
+        }
+
+        match host_interface(name) {
+            Some(interface) if interface.kind.is_enabled(permissions) => {}
+            Some(_) => unsupported.push(format!("{name} (not permitted)")),
+            None => unsupported.push(name.to_string()),
+        }
+    }
+
+    if unsupported.is_empty() {
+        Ok(())
+    } else {
+        Err(EngineError::UnsupportedImports(unsupported))
+    }
+}
+
+/// The host interface that satisfies an import like `wasi:http/types@0.2.0`, if any.
+/// World-local imports aren't in [`HOST_INTERFACES`], check [`WORLD_LOCAL_IMPORTS`] first
+pub fn host_interface(import_name: &str) -> Option<&'static HostInterface> {
+    let (package, version) = split_import_name(import_name);
+
+    HOST_INTERFACES
+        .iter()
+        .find(|interface| interface.provides(package, version))
+}
+
+/// `wasi:http/types@0.2.0` -> (`wasi:http`, Some(`0.2.0`))
+fn split_import_name(name: &str) -> (&str, Option<&str>) {
+    let (path, version) = match name.split_once('@') {
+        Some((path, version)) => (path, Some(version)),
+        None => (name, None),
+    };
+
+    let package = path.split_once('/').map(|(pkg, _)| pkg).unwrap_or(path);
+
+    (package, version)
+}
+
+/// Semver-compatible in the same sense wasmtime uses when matching imports:
+/// prereleases must match exactly, `0.x` must share the minor version, otherwise the major version
+fn versions_compatible(provided: &str, requested: &str) -> bool {
+    if provided.contains('-') || requested.contains('-') {
+        return provided == requested;
+    }
+
+    let parse = |v: &str| -> Option<(u64, u64)> {
+        let mut parts = v.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        Some((major, minor))
+    };
+
+    match (parse(provided), parse(requested)) {
+        (Some((0, provided_minor)), Some((0, requested_minor))) => {
+            provided_minor == requested_minor
+        }
+        (Some((provided_major, _)), Some((requested_major, _))) => {
+            provided_major == requested_major
+        }
+        _ => false,
+    }
+}
@@ -1,3 +1,6 @@
 pub mod aggregator;
+pub mod determinism;
 pub mod instance;
+pub mod interfaces;
 pub mod operator;
+pub mod output;
@@ -1,14 +1,26 @@
+use std::collections::BTreeMap;
+
 use wasmtime_wasi::{WasiCtx, WasiCtxView, WasiView};
-use wasmtime_wasi_http::{WasiHttpCtx, WasiHttpView};
+use wasmtime_wasi_http::{
+    body::HyperOutgoingBody,
+    types::{HostFutureIncomingResponse, OutgoingRequestConfig},
+    HttpResult, WasiHttpCtx, WasiHttpView,
+};
 use wasmtime_wasi_tls::WasiTlsCtx;
-use wavs_types::{ChainConfigs, ComponentDigest, Service, ServiceId, TriggerData, WorkflowId};
+use wavs_types::{
+    AllowedHostPermission, ChainConfigs, ComponentDigest, Service, ServiceId, TriggerData,
+    WorkflowId,
+};
 
+use crate::backend::evm_blocks::EvmBlocks;
+use crate::backend::outbound_http;
 use crate::backend::wasi_keyvalue::context::KeyValueCtx;
 use crate::bindings::operator::world::host::LogLevel;
+use crate::worlds::instance::MemoryLimiter;
 
 // This is defined separately because LogLevel comes from bindings
 pub type OperatorHostComponentLogger =
-    fn(&ServiceId, &WorkflowId, &ComponentDigest, LogLevel, String);
+    fn(&ServiceId, &WorkflowId, &ComponentDigest, LogLevel, String, &[(String, String)]);
 
 // TODO: revisit this an understand it.
 // Copied blindly from old code
@@ -22,7 +34,11 @@ pub struct OperatorHostComponent {
     pub(crate) http_ctx: WasiHttpCtx,
     pub(crate) tls_ctx: WasiTlsCtx,
     pub(crate) keyvalue_ctx: KeyValueCtx,
+    pub(crate) evm_blocks: EvmBlocks,
+    pub(crate) secrets: BTreeMap<String, String>,
+    pub(crate) allowed_http_hosts: AllowedHostPermission,
     pub(crate) inner_log: OperatorHostComponentLogger,
+    pub(crate) limiter: MemoryLimiter,
 }
 
 impl WasiView for OperatorHostComponent {
@@ -42,4 +58,12 @@ impl WasiHttpView for OperatorHostComponent {
     fn table(&mut self) -> &mut wasmtime::component::ResourceTable {
         &mut self.table
     }
+
+    fn send_request(
+        &mut self,
+        request: http::Request<HyperOutgoingBody>,
+        config: OutgoingRequestConfig,
+    ) -> HttpResult<HostFutureIncomingResponse> {
+        outbound_http::send_request(&self.allowed_http_hosts, request, config)
+    }
 }
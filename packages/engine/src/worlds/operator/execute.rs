@@ -1,26 +1,113 @@
-use std::{collections::HashSet, time::Duration};
+use std::collections::HashSet;
 
-use wasmtime::Trap;
-use wavs_types::{TriggerAction, WasmResponse};
+use wavs_types::{ServiceId, TriggerAction, WasmResponse, WorkflowId};
 
-use crate::{utils::error::EngineError, worlds::instance::InstanceDeps};
+use crate::{
+    utils::error::EngineError,
+    worlds::{
+        instance::{time_limited, InstanceDeps},
+        output::CapturedOutput,
+    },
+};
+
+/// The optional setup export of components built against `wavs-world-with-init`
+pub const INIT_EXPORT: &str = "init";
+
+/// Whether the component exports the optional `init` hook
+pub fn has_init(deps: &InstanceDeps) -> bool {
+    deps.component.get_export_index(None, INIT_EXPORT).is_some()
+}
+
+/// Run the component's `init` export, if it has one.
+/// Returns `false` without instantiating anything if the component doesn't export it.
+pub async fn execute_init(
+    deps: &mut InstanceDeps,
+    service_id: ServiceId,
+    workflow_id: WorkflowId,
+) -> Result<bool, EngineError> {
+    if !has_init(deps) {
+        return Ok(false);
+    }
+
+    let output = deps.output.clone();
+    let result = time_limited(
+        deps.time_limit_seconds,
+        deps.execution_deadline,
+        service_id.clone(),
+        workflow_id.clone(),
+        {
+            let service_id = service_id.clone();
+            let workflow_id = workflow_id.clone();
+            async move {
+                let instance = deps
+                    .linker
+                    .as_operator_ref()
+                    .instantiate_async(deps.store.as_operator_mut(), &deps.component)
+                    .await
+                    .map_err(EngineError::Instantiate)?;
+
+                let init = instance
+                    .get_typed_func::<(), (Result<(), String>,)>(
+                        deps.store.as_operator_mut(),
+                        INIT_EXPORT,
+                    )
+                    .map_err(EngineError::Instantiate)?;
+
+                let (result,) = init
+                    .call_async(deps.store.as_operator_mut(), ())
+                    .await
+                    .map_err(|e| EngineError::from_call(e, service_id, workflow_id))?;
+
+                init.post_return_async(deps.store.as_operator_mut())
+                    .await
+                    .map_err(EngineError::ComponentError)?;
+
+                result.map_err(EngineError::InitResult).map(|_| true)
+            }
+        },
+    )
+    .await;
+    output.emit();
+
+    result
+}
+
+/// What a successful operator run produced
+#[derive(Debug, Clone)]
+pub struct OperatorOutput {
+    pub responses: Vec<WasmResponse>,
+    /// `None` if the engine isn't metering fuel
+    pub fuel_consumed: Option<u64>,
+    /// What the component wrote to stdout and stderr, only kept if the caller asks for it
+    pub captured_output: Option<CapturedOutput>,
+}
 
 pub async fn execute(
     deps: &mut InstanceDeps,
     trigger: TriggerAction,
-) -> Result<Vec<WasmResponse>, EngineError> {
+) -> Result<OperatorOutput, EngineError> {
     let service_id = trigger.config.service_id.clone();
     let workflow_id = trigger.config.workflow_id.clone();
     let input: crate::bindings::operator::world::wavs::operator::input::TriggerAction =
         trigger.try_into().map_err(EngineError::Input)?;
 
+    // errors only if fuel metering is disabled
+    let initial_fuel = deps.store.get_fuel().ok();
+
     // Even though we have epochs forcing timeouts within WASI
     // we still need to set a timeout on the host side since we need to cancel sleeping components too
     // see https://github.com/bytecodealliance/wasmtime-go/issues/233#issuecomment-2356238658
-    let responses: Vec<WasmResponse> =
-        tokio::time::timeout(Duration::from_secs(deps.time_limit_seconds), {
+    let output = deps.output.clone();
+    let responses: Result<Vec<WasmResponse>, EngineError> = time_limited(
+        deps.time_limit_seconds,
+        deps.execution_deadline,
+        service_id.clone(),
+        workflow_id.clone(),
+        {
             let service_id = service_id.clone();
             let workflow_id = workflow_id.clone();
+            // reborrowed so the store can still be read for the fuel used afterwards
+            let deps = &mut *deps;
             async move {
                 crate::bindings::operator::world::WavsWorld::instantiate_async(
                     deps.store.as_operator_mut(),
@@ -31,42 +118,56 @@ pub async fn execute(
                 .map_err(EngineError::Instantiate)?
                 .call_run(deps.store.as_operator_mut(), &input)
                 .await
-                .map_err(|e| match e.downcast_ref::<Trap>() {
-                    Some(t) if *t == Trap::OutOfFuel => {
-                        EngineError::OutOfFuel(service_id, workflow_id)
-                    }
-                    Some(t) if *t == Trap::Interrupt => {
-                        EngineError::OutOfTime(service_id, workflow_id)
-                    }
-                    _ => EngineError::ComponentError(e),
-                })?
+                .map_err(|e| EngineError::from_call(e, service_id, workflow_id))?
                 .map_err(EngineError::ExecResult)
                 .map(|r| r.into_iter().map(|r| r.into()).collect())
             }
-        })
-        .await
-        .map_err(|_| EngineError::OutOfTime(service_id.clone(), workflow_id.clone()))??;
+        },
+    )
+    .await;
+    output.emit();
+    let responses = responses?;
+
+    let fuel_consumed = initial_fuel
+        .map(|initial| initial.saturating_sub(deps.store.get_fuel().unwrap_or_default()));
 
-    // Invariant: If there are multiple responses, they must all have an event id salt
+    // Invariants: If there are multiple responses, they must all have an event id salt,
+    // and no two of them may claim the same place in the submission order
     if responses.len() > 1 {
+        let missing = responses
+            .iter()
+            .filter(|response| response.event_id_salt.is_none())
+            .count();
+        if missing > 0 {
+            return Err(EngineError::MissingEventIdSalt {
+                responses: responses.len(),
+                missing,
+            });
+        }
+
         let mut seen_salt = HashSet::new();
+        let mut seen_ordering = HashSet::new();
         for response in &responses {
-            match &response.event_id_salt {
-                Some(salt) => {
-                    if !seen_salt.insert(salt) {
-                        tracing::warn!(
-                            service.id = %service_id,
-                            workflow.id = %workflow_id,
-                            "Duplicate event-id-salt: {}", const_hex::encode(salt)
-                        );
-                    }
+            if let Some(salt) = &response.event_id_salt {
+                if !seen_salt.insert(salt) {
+                    tracing::warn!(
+                        service.id = %service_id,
+                        workflow.id = %workflow_id,
+                        "Duplicate event-id-salt: {}", const_hex::encode(salt)
+                    );
                 }
-                None => {
-                    return Err(EngineError::MissingEventIdSalt);
+            }
+            if let Some(ordering) = response.ordering {
+                if !seen_ordering.insert(ordering) {
+                    return Err(EngineError::DuplicateOrdering(ordering));
                 }
             }
         }
     }
 
-    Ok(responses)
+    Ok(OperatorOutput {
+        responses,
+        fuel_consumed,
+        captured_output: None,
+    })
 }
@@ -1,2 +1,3 @@
 pub mod component;
 pub mod execute;
+pub mod replay;
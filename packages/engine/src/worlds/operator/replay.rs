@@ -0,0 +1,68 @@
+//! Replays a trigger action recorded from a node, offline, for debugging.
+//!
+//! The instance is set up the way the node sets it up, from the recorded service, so a
+//! component that only depends on its input produces the same bytes it did live.
+//! Anything the component reads from outside its input can differ on replay:
+//! - http responses, and anything else fetched over the network, e.g. `host::get-evm-block`
+//! - wall-clock time and randomness
+//! - the keyvalue store and data dir, which start out empty rather than with the node's state
+//! - secrets, which the node holds and a replay doesn't have
+
+use anyhow::Context;
+use utils::storage::{db::WavsDb, CAStorage};
+use wavs_types::{ComponentDigest, Service, TriggerAction, WasmResponse};
+
+use crate::{
+    backend::wasi_keyvalue::context::KeyValueCtx,
+    common::base_engine::BaseEngine,
+    utils::error::EngineError,
+    worlds::{
+        instance::{HostComponentLogger, InstanceData, InstanceDepsBuilder},
+        operator::{component::OperatorHostComponentLogger, execute::execute},
+    },
+};
+
+/// Runs the recorded `trigger_action_json` against the component with `digest`, which must
+/// already be in the engine's storage. The workflow is looked up in the recorded service
+pub async fn execute_recorded<S: CAStorage + Send + Sync + 'static>(
+    engine: &BaseEngine<S>,
+    digest: &ComponentDigest,
+    trigger_action_json: &[u8],
+    service_json: &[u8],
+    log: OperatorHostComponentLogger,
+) -> Result<Vec<WasmResponse>, EngineError> {
+    let trigger_action: TriggerAction = serde_json::from_slice(trigger_action_json)
+        .context("Invalid recorded trigger action")
+        .map_err(EngineError::Input)?;
+    let service: Service = serde_json::from_slice(service_json)
+        .context("Invalid recorded service")
+        .map_err(EngineError::Input)?;
+
+    let component = engine.load_component(digest).await?;
+    let chain_configs = engine.get_chain_configs()?;
+    let service_id = service.id();
+
+    let mut instance_deps = InstanceDepsBuilder {
+        keyvalue_ctx: KeyValueCtx::new(
+            WavsDb::new().map_err(|e| EngineError::StorageError(e.to_string()))?,
+            service_id.to_string(),
+        ),
+        evm_blocks: engine.evm_blocks.clone(),
+        secrets: Default::default(),
+        determinism: engine.determinism,
+        capture_output: false,
+        service,
+        workflow_id: trigger_action.config.workflow_id.clone(),
+        component,
+        data: InstanceData::new_operator(trigger_action.data.clone()),
+        engine: &engine.wasm_engine,
+        data_dir: engine.service_fs_dir(&service_id),
+        chain_configs: &chain_configs,
+        log: HostComponentLogger::OperatorHostComponentLogger(log),
+    }
+    .build()?;
+
+    let output = execute(&mut instance_deps, trigger_action).await?;
+
+    Ok(output.responses)
+}
@@ -0,0 +1,88 @@
+//! Captures what a component writes to stdout and stderr during one execution.
+//!
+//! Capturing is opt-in, otherwise the component writes straight to the node's own stdout and stderr.
+//! When it's on, each line is logged at `DEBUG`, tagged with the service, workflow and event it ran for,
+//! once the call into the component returns (whether or not it succeeded).
+//! A component that writes more than [`MAX_CAPTURED_BYTES`] to either stream traps.
+
+use wasmtime_wasi::{p2::pipe::MemoryOutputPipe, WasiCtxBuilder};
+use wavs_types::{EventId, ServiceId, WorkflowId};
+
+/// How much a single execution may write to each of stdout and stderr while captured
+pub const MAX_CAPTURED_BYTES: usize = 256 * 1024;
+
+/// What a component wrote to stdout and stderr, lossily decoded as UTF-8
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CapturedOutput {
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// The stdout and stderr handed to one instance
+#[derive(Clone)]
+pub struct GuestOutput {
+    service_id: ServiceId,
+    workflow_id: WorkflowId,
+    event_id: EventId,
+    /// `None` if the instance inherits the node's stdout and stderr
+    pipes: Option<(MemoryOutputPipe, MemoryOutputPipe)>,
+}
+
+impl GuestOutput {
+    pub fn new(
+        service_id: ServiceId,
+        workflow_id: WorkflowId,
+        event_id: EventId,
+        capture: bool,
+    ) -> Self {
+        Self {
+            service_id,
+            workflow_id,
+            event_id,
+            pipes: capture.then(|| {
+                (
+                    MemoryOutputPipe::new(MAX_CAPTURED_BYTES),
+                    MemoryOutputPipe::new(MAX_CAPTURED_BYTES),
+                )
+            }),
+        }
+    }
+
+    pub fn attach(&self, builder: &mut WasiCtxBuilder) {
+        match &self.pipes {
+            Some((stdout, stderr)) => builder.stdout(stdout.clone()).stderr(stderr.clone()),
+            None => builder.inherit_stdout().inherit_stderr(),
+        };
+    }
+
+    /// Logs everything captured so far at `DEBUG`
+    pub fn emit(&self) {
+        if self.pipes.is_none() || !tracing::enabled!(tracing::Level::DEBUG) {
+            return;
+        }
+
+        let CapturedOutput { stdout, stderr } = self.captured();
+        for (stream, output) in [("stdout", stdout), ("stderr", stderr)] {
+            for line in output.lines() {
+                tracing::debug!(
+                    service_id = %self.service_id,
+                    workflow_id = %self.workflow_id,
+                    event_id = %self.event_id,
+                    stream,
+                    "{line}"
+                );
+            }
+        }
+    }
+
+    /// Empty unless capturing is on
+    pub fn captured(&self) -> CapturedOutput {
+        match &self.pipes {
+            Some((stdout, stderr)) => CapturedOutput {
+                stdout: String::from_utf8_lossy(&stdout.contents()).into_owned(),
+                stderr: String::from_utf8_lossy(&stderr.contents()).into_owned(),
+            },
+            None => CapturedOutput::default(),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+mod helpers;
+
+use alloy_sol_types::SolValue;
+use utils::{
+    init_tracing_tests,
+    test_utils::{
+        mock_engine::COMPONENT_ECHO_DATA_BYTES, test_contracts::ISimpleSubmit::DataWithId,
+    },
+};
+use wasmtime::{Config as WTConfig, Engine as WTEngine};
+use wavs_types::{AtProtoAction, TriggerData};
+
+use crate::helpers::exec::try_execute_component_with_trigger_data;
+
+fn engine() -> WTEngine {
+    let mut wt_config = WTConfig::new();
+
+    wt_config.wasm_component_model(true);
+    wt_config.async_support(true);
+    wt_config.consume_fuel(true);
+
+    WTEngine::new(&wt_config).unwrap()
+}
+
+fn atproto_event(
+    sequence: i64,
+    action: AtProtoAction,
+    record: Option<serde_json::Value>,
+) -> TriggerData {
+    TriggerData::AtProtoEvent {
+        sequence,
+        timestamp: 1_700_000_000_000_000,
+        repo: "did:example:alice".to_string(),
+        collection: "app.bsky.feed.post".to_string(),
+        rkey: "rkey-1".to_string(),
+        action,
+        cid: record.as_ref().map(|_| "bafytestcid".to_string()),
+        record,
+        rev: Some("rev-1".to_string()),
+        op_index: Some(0),
+    }
+}
+
+async fn echo(trigger_data: TriggerData) -> Result<DataWithId, String> {
+    let responses = try_execute_component_with_trigger_data(
+        engine(),
+        COMPONENT_ECHO_DATA_BYTES,
+        Default::default(),
+        None,
+        trigger_data,
+    )
+    .await?;
+
+    assert_eq!(responses.len(), 1);
+    Ok(DataWithId::abi_decode(&responses[0]).unwrap())
+}
+
+#[tokio::test]
+async fn atproto_record_round_trips() {
+    init_tracing_tests();
+
+    let record = serde_json::json!({ "text": "hello from atproto" });
+    let output = echo(atproto_event(
+        42,
+        AtProtoAction::Create,
+        Some(record.clone()),
+    ))
+    .await
+    .unwrap();
+
+    // the sequence is the trigger id, and the record comes back as its json
+    assert_eq!(output.triggerId, 42);
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&output.data).unwrap(),
+        record
+    );
+}
+
+#[tokio::test]
+async fn atproto_delete_echoes_empty_record() {
+    init_tracing_tests();
+
+    let output = echo(atproto_event(43, AtProtoAction::Delete, None))
+        .await
+        .unwrap();
+
+    assert_eq!(output.triggerId, 43);
+    assert!(output.data.is_empty());
+}
+
+#[tokio::test]
+async fn atproto_negative_sequence_fails() {
+    init_tracing_tests();
+
+    let err = match echo(atproto_event(-1, AtProtoAction::Create, None)).await {
+        Ok(output) => panic!("expected an error, got trigger {}", output.triggerId),
+        Err(err) => err,
+    };
+
+    assert!(err.contains("sequence must not be negative"), "{err}");
+}
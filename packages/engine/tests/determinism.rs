@@ -0,0 +1,122 @@
+use alloy_primitives::{Address, LogData, B256};
+use wasmtime::{
+    component::{Component, Linker, ResourceTable},
+    Config as WTConfig, Engine as WTEngine, Store,
+};
+use wasmtime_wasi::{WasiCtx, WasiCtxBuilder, WasiCtxView, WasiView};
+use wavs_engine::worlds::determinism::{trigger_time, Determinism};
+use wavs_types::{EventId, EventIdSalt, ServiceId, TriggerData, WorkflowId};
+
+// exports `now-secs`, the seconds read from `wasi:clocks/wall-clock.now`
+const WALL_CLOCK_COMPONENT: &str = r#"(component
+    (import "wasi:clocks/wall-clock@0.2.0" (instance $clock
+        (type $datetime (record (field "seconds" u64) (field "nanoseconds" u32)))
+        (export "datetime" (type $dt (eq $datetime)))
+        (export "now" (func (result $dt)))
+    ))
+    (core module $libc-module (memory (export "memory") 1))
+    (core instance $libc (instantiate $libc-module))
+    (core func $now (canon lower (func $clock "now") (memory $libc "memory")))
+    (core module $m
+        (import "libc" "memory" (memory 1))
+        (import "clock" "now" (func $now (param i32)))
+        (func (export "now-secs") (result i64)
+            (call $now (i32.const 0))
+            (i64.load (i32.const 0))))
+    (core instance $i (instantiate $m
+        (with "libc" (instance $libc))
+        (with "clock" (instance (export "now" (func $now))))))
+    (func (export "now-secs") (result u64) (canon lift (core func $i "now-secs")))
+)"#;
+
+const BLOCK_TIMESTAMP: u64 = 1_700_000_000;
+
+struct Host {
+    ctx: WasiCtx,
+    table: ResourceTable,
+}
+
+impl WasiView for Host {
+    fn ctx(&mut self) -> WasiCtxView<'_> {
+        WasiCtxView {
+            ctx: &mut self.ctx,
+            table: &mut self.table,
+        }
+    }
+}
+
+fn evm_event(block_timestamp: Option<u64>) -> TriggerData {
+    TriggerData::EvmContractEvent {
+        chain: "evm:anvil".parse().unwrap(),
+        contract_address: Address::repeat_byte(1),
+        log_data: LogData::new_unchecked(vec![B256::repeat_byte(2)], Default::default()),
+        tx_hash: B256::repeat_byte(3),
+        block_number: 10,
+        log_index: 0,
+        block_hash: B256::repeat_byte(4),
+        block_timestamp,
+        tx_index: 0,
+    }
+}
+
+async fn read_wall_clock(determinism: Determinism, trigger_data: &TriggerData) -> u64 {
+    let mut config = WTConfig::new();
+    config.wasm_component_model(true);
+    config.async_support(true);
+    let engine = WTEngine::new(&config).unwrap();
+    let component = Component::new(&engine, WALL_CLOCK_COMPONENT).unwrap();
+
+    let event_id = EventId::new(
+        &ServiceId::hash(b"service"),
+        &WorkflowId::default(),
+        EventIdSalt::Trigger(trigger_data),
+    )
+    .unwrap();
+    let mut builder = WasiCtxBuilder::new();
+    determinism.apply(&mut builder, Some(trigger_time(trigger_data)), &event_id);
+
+    let mut linker = Linker::new(&engine);
+    wasmtime_wasi::p2::add_to_linker_async(&mut linker).unwrap();
+    let mut store = Store::new(
+        &engine,
+        Host {
+            ctx: builder.build(),
+            table: ResourceTable::new(),
+        },
+    );
+
+    let instance = linker
+        .instantiate_async(&mut store, &component)
+        .await
+        .unwrap();
+    let now_secs = instance
+        .get_typed_func::<(), (u64,)>(&mut store, "now-secs")
+        .unwrap();
+    let (secs,) = now_secs.call_async(&mut store, ()).await.unwrap();
+    now_secs.post_return_async(&mut store).await.unwrap();
+
+    secs
+}
+
+#[tokio::test]
+async fn deterministic_clock_reads_trigger_time() {
+    let determinism = Determinism {
+        clock: true,
+        rng: true,
+    };
+    let trigger_data = evm_event(Some(BLOCK_TIMESTAMP));
+
+    let first = read_wall_clock(determinism, &trigger_data).await;
+    let second = read_wall_clock(determinism, &trigger_data).await;
+    assert_eq!(first, BLOCK_TIMESTAMP);
+    assert_eq!(first, second);
+
+    // a trigger without a time of its own reads the epoch rather than the host clock
+    assert_eq!(read_wall_clock(determinism, &evm_event(None)).await, 0);
+}
+
+#[tokio::test]
+async fn host_clock_by_default() {
+    let secs = read_wall_clock(Determinism::default(), &evm_event(Some(BLOCK_TIMESTAMP))).await;
+    assert!(secs > BLOCK_TIMESTAMP);
+}
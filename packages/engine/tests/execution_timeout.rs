@@ -0,0 +1,81 @@
+mod helpers;
+
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use crate::helpers::{exec::try_execute_service_output, service::make_service};
+use utils::{init_tracing_tests, test_utils::mock_engine::COMPONENT_ECHO_DATA_BYTES};
+use wasmtime::{Config as WTConfig, Engine as WTEngine};
+use wavs_engine::utils::error::EngineError;
+use wavs_types::{ComponentDigest, TriggerData};
+
+/// An engine driven by an epoch ticker, like the node's
+fn ticking_engine() -> WTEngine {
+    let mut wt_config = WTConfig::new();
+    wt_config.wasm_component_model(true);
+    wt_config.epoch_interruption(true);
+    wt_config.async_support(true);
+    wt_config.consume_fuel(true);
+
+    let engine = WTEngine::new(&wt_config).unwrap();
+
+    let engine_ticker = engine.weak();
+    std::thread::spawn(move || {
+        while let Some(engine_ticker) = engine_ticker.upgrade() {
+            engine_ticker.increment_epoch();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    });
+
+    engine
+}
+
+async fn run_hotloop(sleep_ms: u64, execution_timeout_secs: u64) -> Result<(), EngineError> {
+    let mut service = make_service(
+        ComponentDigest::hash(COMPONENT_ECHO_DATA_BYTES),
+        BTreeMap::from([
+            ("sleep-ms".to_string(), sleep_ms.to_string()),
+            ("sleep-kind".to_string(), "hotloop".to_string()),
+        ]),
+    );
+    service.execution_timeout_secs = Some(execution_timeout_secs);
+
+    try_execute_service_output(
+        ticking_engine(),
+        COMPONENT_ECHO_DATA_BYTES,
+        service,
+        None,
+        TriggerData::Raw(b"hotloop".to_vec()),
+    )
+    .await
+    .map(|_| ())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn hotloop_is_killed_at_execution_timeout() {
+    init_tracing_tests();
+
+    let start = Instant::now();
+    let err = run_hotloop(30_000, 1).await.unwrap_err();
+
+    match err {
+        EngineError::Timeout { elapsed, .. } => {
+            assert!(
+                elapsed >= Duration::from_secs(1),
+                "stopped early: {elapsed:?}"
+            );
+        }
+        err => panic!("expected a timeout, got {err:?}"),
+    }
+    // nowhere near the 30s the component wanted to spin for
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn hotloop_within_execution_timeout() {
+    init_tracing_tests();
+
+    run_hotloop(10, 5).await.unwrap();
+}
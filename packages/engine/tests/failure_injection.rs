@@ -0,0 +1,98 @@
+mod helpers;
+
+use crate::helpers::{
+    exec::{try_execute_component_raw, try_execute_service_output},
+    service::make_service,
+};
+use utils::{
+    init_tracing_tests, storage::db::WavsDb, test_utils::mock_engine::COMPONENT_ECHO_DATA_BYTES,
+};
+use wasmtime::{Config as WTConfig, Engine as WTEngine};
+use wavs_engine::{
+    backend::wasi_keyvalue::context::KeyValueCtx,
+    utils::error::{EngineError, ExecutionOutcome},
+};
+use wavs_types::{ComponentDigest, TriggerData};
+
+fn engine() -> WTEngine {
+    let mut wt_config = WTConfig::new();
+
+    wt_config.wasm_component_model(true);
+    wt_config.async_support(true);
+    wt_config.consume_fuel(true);
+
+    WTEngine::new(&wt_config).unwrap()
+}
+
+async fn run(fail_mode: &str, keyvalue_ctx: KeyValueCtx) -> Result<Vec<Vec<u8>>, String> {
+    try_execute_component_raw(
+        engine(),
+        COMPONENT_ECHO_DATA_BYTES,
+        [("fail-mode".to_string(), fail_mode.to_string())]
+            .into_iter()
+            .collect(),
+        Some(keyvalue_ctx),
+        b"hello".to_vec(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn fail_mode_err() {
+    init_tracing_tests();
+
+    let keyvalue_ctx = KeyValueCtx::new(WavsDb::new().unwrap(), "test".to_string());
+
+    let err = run("err", keyvalue_ctx).await.unwrap_err();
+    assert!(err.contains("fail-mode is 'err'"), "{err}");
+}
+
+#[tokio::test]
+async fn fail_mode_outcomes() {
+    init_tracing_tests();
+
+    let outcome = |fail_mode: &str| {
+        let service = make_service(
+            ComponentDigest::hash(COMPONENT_ECHO_DATA_BYTES),
+            [("fail-mode".to_string(), fail_mode.to_string())]
+                .into_iter()
+                .collect(),
+        );
+        try_execute_service_output(
+            engine(),
+            COMPONENT_ECHO_DATA_BYTES,
+            service,
+            None,
+            TriggerData::Raw(b"hello".to_vec()),
+        )
+    };
+
+    // a returned error is the component's own doing, a panic traps the guest
+    let err = outcome("err").await.unwrap_err();
+    assert!(matches!(err, EngineError::ExecResult(_)), "{err:?}");
+    assert_eq!(err.outcome(), ExecutionOutcome::ApplicationError);
+
+    let err = outcome("panic").await.unwrap_err();
+    assert!(matches!(err, EngineError::Trapped(_)), "{err:?}");
+    assert_eq!(err.outcome(), ExecutionOutcome::Trapped);
+}
+
+#[tokio::test]
+async fn fail_mode_nth() {
+    init_tracing_tests();
+
+    let keyvalue_ctx = KeyValueCtx::new(WavsDb::new().unwrap(), "test".to_string());
+
+    // only the second invocation fails, the count is kept in the keyvalue store
+    let results = [
+        run("nth:2", keyvalue_ctx.clone()).await,
+        run("nth:2", keyvalue_ctx.clone()).await,
+        run("nth:2", keyvalue_ctx).await,
+    ];
+
+    assert_eq!(results[0], Ok(vec![b"hello".to_vec()]));
+    assert!(results[1]
+        .as_ref()
+        .is_err_and(|err| err.contains("failing invocation 2")));
+    assert_eq!(results[2], Ok(vec![b"hello".to_vec()]));
+}
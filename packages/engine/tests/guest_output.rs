@@ -0,0 +1,61 @@
+mod helpers;
+
+use example_types::PermissionsRequest;
+use utils::{init_tracing_tests, test_utils::mock_engine::COMPONENT_PERMISSIONS_BYTES};
+use wasmtime::{Config as WTConfig, Engine as WTEngine};
+use wavs_types::{AllowedHostPermission, ComponentDigest, TriggerData};
+
+use crate::helpers::{exec::try_execute_service_captured, service::make_service};
+
+#[tokio::test]
+async fn stdout_and_stderr_are_captured() {
+    init_tracing_tests();
+
+    let mut wt_config = WTConfig::new();
+    wt_config.wasm_component_model(true);
+    wt_config.async_support(true);
+    wt_config.consume_fuel(true);
+    let engine = WTEngine::new(&wt_config).unwrap();
+
+    // the component prints before making any request, which is denied so the test stays offline
+    let mut service = make_service(
+        ComponentDigest::hash(COMPONENT_PERMISSIONS_BYTES),
+        Default::default(),
+    );
+    for workflow in service.workflows.values_mut() {
+        workflow.component.permissions.allowed_http_hosts =
+            AllowedHostPermission::Only(vec!["allowed.example".to_string()]);
+    }
+
+    let request = PermissionsRequest {
+        get_url: "https://postman-echo.com/get".to_string(),
+        post_url: "https://postman-echo.com/post".to_string(),
+        post_data: ("hello".to_string(), "world".to_string()),
+        timestamp: 1,
+    };
+
+    let (result, captured) = try_execute_service_captured(
+        engine,
+        COMPONENT_PERMISSIONS_BYTES,
+        service,
+        None,
+        TriggerData::Raw(serde_json::to_vec(&request).unwrap()),
+    )
+    .await;
+
+    // output is kept even though the execution failed
+    assert!(result.is_err());
+    assert!(
+        captured
+            .stdout
+            .contains("(permissions println!) trigger id:"),
+        "{captured:?}"
+    );
+    assert!(
+        captured
+            .stderr
+            .contains("(permissions eprintln!) trigger id:"),
+        "{captured:?}"
+    );
+    assert!(!captured.stdout.contains("eprintln!"));
+}
@@ -0,0 +1,21 @@
+use wavs_engine::utils::hash::{hash, HashAlgorithm};
+
+#[test]
+fn hashes_match_reference_implementations() {
+    assert_eq!(
+        const_hex::encode(hash(HashAlgorithm::Keccak256, b"")),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+    assert_eq!(
+        const_hex::encode(hash(HashAlgorithm::Sha256, b"")),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_eq!(
+        const_hex::encode(hash(HashAlgorithm::Blake3, b"")),
+        "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+    );
+    assert_eq!(
+        const_hex::encode(hash(HashAlgorithm::Sha256, b"abc")),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+}
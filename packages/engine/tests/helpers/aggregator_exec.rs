@@ -32,6 +32,7 @@ pub async fn execute_aggregator_component(
             EvmChainConfig {
                 chain_id: "31337".to_string().parse().unwrap(),
                 http_endpoint: Some("http://localhost:8545".to_string()),
+                http_endpoints: Vec::new(),
                 faucet_endpoint: None,
                 ws_endpoints: vec![],
                 ws_priority_endpoint_index: None,
@@ -51,6 +52,10 @@ pub async fn execute_aggregator_component(
         chain_configs: &chain_configs,
         log: HostComponentLogger::AggregatorHostComponentLogger(log_aggregator),
         keyvalue_ctx,
+        evm_blocks: Default::default(),
+        secrets: Default::default(),
+        determinism: Default::default(),
+        capture_output: false,
     }
     .build()
     .unwrap();
@@ -84,6 +89,7 @@ fn log_aggregator(
     digest: &ComponentDigest,
     level: LogLevel,
     message: String,
+    _fields: &[(String, String)],
 ) {
     let message = format!("[{service_id}:{workflow_id}:{digest}] {message}");
 
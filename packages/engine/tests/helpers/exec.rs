@@ -8,9 +8,13 @@ use wavs_engine::{
     backend::wasi_keyvalue::context::KeyValueCtx,
     bindings::operator::world::host::LogLevel,
     utils::error::EngineError,
-    worlds::instance::{HostComponentLogger, InstanceData, InstanceDepsBuilder},
+    worlds::{
+        instance::{HostComponentLogger, InstanceData, InstanceDepsBuilder},
+        operator::execute::OperatorOutput,
+        output::CapturedOutput,
+    },
 };
-use wavs_types::{ComponentDigest, ServiceId, WorkflowId};
+use wavs_types::{ComponentDigest, Service, ServiceId, TriggerData, WorkflowId};
 
 use crate::helpers::service::{make_service, make_trigger_action};
 
@@ -80,9 +84,59 @@ pub async fn try_execute_component_raw(
     config: BTreeMap<String, String>,
     keyvalue_ctx: Option<KeyValueCtx>,
     input: Vec<u8>,
+) -> std::result::Result<Vec<Vec<u8>>, String> {
+    try_execute_component_with_trigger_data(
+        engine,
+        wasm_bytes,
+        config,
+        keyvalue_ctx,
+        TriggerData::Raw(input),
+    )
+    .await
+}
+
+/// Like [`try_execute_component_raw`], for components that should see a real trigger
+/// rather than raw bytes
+#[allow(dead_code)]
+pub async fn try_execute_component_with_trigger_data(
+    engine: WTEngine,
+    wasm_bytes: &[u8],
+    config: BTreeMap<String, String>,
+    keyvalue_ctx: Option<KeyValueCtx>,
+    trigger_data: TriggerData,
 ) -> std::result::Result<Vec<Vec<u8>>, String> {
     let service = make_service(ComponentDigest::hash(wasm_bytes), config);
-    let trigger_action = make_trigger_action(&service, None, input);
+    try_execute_service(engine, wasm_bytes, service, keyvalue_ctx, trigger_data).await
+}
+
+/// Like [`try_execute_service`], keeping the engine's own output and error
+#[allow(dead_code)]
+pub async fn try_execute_service_output(
+    engine: WTEngine,
+    wasm_bytes: &[u8],
+    service: Service,
+    keyvalue_ctx: Option<KeyValueCtx>,
+    trigger_data: TriggerData,
+) -> std::result::Result<OperatorOutput, EngineError> {
+    try_execute_service_captured(engine, wasm_bytes, service, keyvalue_ctx, trigger_data)
+        .await
+        .0
+}
+
+/// Like [`try_execute_service_output`], also returning what the component wrote to stdout and stderr
+#[allow(dead_code)]
+pub async fn try_execute_service_captured(
+    engine: WTEngine,
+    wasm_bytes: &[u8],
+    service: Service,
+    keyvalue_ctx: Option<KeyValueCtx>,
+    trigger_data: TriggerData,
+) -> (
+    std::result::Result<OperatorOutput, EngineError>,
+    CapturedOutput,
+) {
+    let mut trigger_action = make_trigger_action(&service, None, Vec::new());
+    trigger_action.data = trigger_data;
 
     let data_dir = tempfile::tempdir().unwrap();
     let keyvalue_ctx = keyvalue_ctx
@@ -98,15 +152,34 @@ pub async fn try_execute_component_raw(
         chain_configs: &Default::default(),
         log: HostComponentLogger::OperatorHostComponentLogger(log_wasi),
         keyvalue_ctx,
+        evm_blocks: Default::default(),
+        secrets: Default::default(),
+        determinism: Default::default(),
+        capture_output: true,
     }
     .build()
     .unwrap();
 
-    let responses =
+    let result =
         wavs_engine::worlds::operator::execute::execute(&mut instance_deps, trigger_action).await;
 
-    match responses {
-        Ok(responses) => {
+    (result, instance_deps.output.captured())
+}
+
+/// Runs the first workflow of `service`, for tests that need more than the default service
+#[allow(dead_code)]
+pub async fn try_execute_service(
+    engine: WTEngine,
+    wasm_bytes: &[u8],
+    service: Service,
+    keyvalue_ctx: Option<KeyValueCtx>,
+    trigger_data: TriggerData,
+) -> std::result::Result<Vec<Vec<u8>>, String> {
+    let output =
+        try_execute_service_output(engine, wasm_bytes, service, keyvalue_ctx, trigger_data).await;
+
+    match output {
+        Ok(OperatorOutput { responses, .. }) => {
             if responses.is_empty() {
                 Err("No responses from component".to_string())
             } else {
@@ -134,6 +207,7 @@ fn log_wasi(
     digest: &ComponentDigest,
     level: LogLevel,
     message: String,
+    _fields: &[(String, String)],
 ) {
     let message = format!("[{service_id}:{workflow_id}:{digest}] {message}");
 
@@ -33,8 +33,10 @@ pub fn make_service(wasm_digest: ComponentDigest, config: BTreeMap<String, Strin
         },
         fuel_limit: None,
         time_limit_seconds: None,
+        max_memory_bytes: None,
         config,
         env_keys: Default::default(),
+        secrets: Default::default(),
     };
     let workflow = Workflow {
         trigger: Trigger::Manual,
@@ -42,7 +44,14 @@ pub fn make_service(wasm_digest: ComponentDigest, config: BTreeMap<String, Strin
         submit: Submit::Aggregator {
             component: Box::new(component),
             signature_kind: SignatureKind::evm_default(),
+            targets: Vec::new(),
         },
+        submit_targets: Vec::new(),
+        response_mapping: Default::default(),
+        submit_condition: None,
+        priority: Default::default(),
+        observe_only: false,
+        min_interval_ms: None,
     };
 
     Service {
@@ -53,5 +62,7 @@ pub fn make_service(wasm_digest: ComponentDigest, config: BTreeMap<String, Strin
             chain: "evm:noop".parse().unwrap(),
             address: Default::default(),
         },
+        max_concurrent_executions: None,
+        execution_timeout_secs: None,
     }
 }
@@ -0,0 +1,48 @@
+mod helpers;
+
+use example_types::PermissionsRequest;
+use utils::{init_tracing_tests, test_utils::mock_engine::COMPONENT_PERMISSIONS_BYTES};
+use wasmtime::{Config as WTConfig, Engine as WTEngine};
+use wavs_types::{AllowedHostPermission, ComponentDigest, TriggerData};
+
+use crate::helpers::{exec::try_execute_service, service::make_service};
+
+#[tokio::test]
+async fn disallowed_host_is_denied() {
+    init_tracing_tests();
+
+    let mut wt_config = WTConfig::new();
+    wt_config.wasm_component_model(true);
+    wt_config.async_support(true);
+    wt_config.consume_fuel(true);
+    let engine = WTEngine::new(&wt_config).unwrap();
+
+    let mut service = make_service(
+        ComponentDigest::hash(COMPONENT_PERMISSIONS_BYTES),
+        Default::default(),
+    );
+    for workflow in service.workflows.values_mut() {
+        workflow.component.permissions.allowed_http_hosts =
+            AllowedHostPermission::Only(vec!["allowed.example".to_string()]);
+    }
+
+    let request = PermissionsRequest {
+        get_url: "https://postman-echo.com/get".to_string(),
+        post_url: "https://postman-echo.com/post".to_string(),
+        post_data: ("hello".to_string(), "world".to_string()),
+        timestamp: 1,
+    };
+
+    let err = try_execute_service(
+        engine,
+        COMPONENT_PERMISSIONS_BYTES,
+        service,
+        None,
+        TriggerData::Raw(serde_json::to_vec(&request).unwrap()),
+    )
+    .await
+    .unwrap_err();
+
+    // the request never leaves the host, so this doesn't depend on postman-echo being reachable
+    assert!(err.to_lowercase().contains("denied"), "{err}");
+}
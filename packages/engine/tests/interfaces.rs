@@ -0,0 +1,52 @@
+use utils::test_utils::mock_engine::COMPONENT_SQUARE_BYTES;
+use wasmtime::{component::Component, Config as WTConfig, Engine as WTEngine};
+use wavs_engine::{utils::error::EngineError, worlds::interfaces::validate_imports};
+use wavs_types::{AllowedHostPermission, Permissions};
+
+fn engine() -> WTEngine {
+    let mut config = WTConfig::new();
+    config.wasm_component_model(true);
+    config.async_support(true);
+    WTEngine::new(&config).unwrap()
+}
+
+#[test]
+fn validates_example_component() {
+    let engine = engine();
+    let component = Component::new(&engine, COMPONENT_SQUARE_BYTES).unwrap();
+
+    let permissions = Permissions {
+        allowed_http_hosts: AllowedHostPermission::All,
+        file_system: true,
+        raw_sockets: false,
+        dns_resolution: false,
+    };
+
+    validate_imports(&engine, &component, &permissions).unwrap();
+}
+
+#[test]
+fn rejects_unknown_and_unpermitted_imports() {
+    let engine = engine();
+    let component = Component::new(
+        &engine,
+        r#"(component
+            (import "unknown:pkg/thing@1.0.0" (instance))
+            (import "wasi:http/outgoing-handler@0.2.0" (instance))
+            (import "wasi:io/streams@0.2.3" (instance))
+        )"#,
+    )
+    .unwrap();
+
+    let err = validate_imports(&engine, &component, &Permissions::default()).unwrap_err();
+    match err {
+        EngineError::UnsupportedImports(imports) => assert_eq!(
+            imports,
+            vec![
+                "unknown:pkg/thing@1.0.0".to_string(),
+                "wasi:http/outgoing-handler@0.2.0 (not permitted)".to_string(),
+            ]
+        ),
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
@@ -29,6 +29,7 @@ async fn keyvalue_basic() {
             bucket: BUCKET.to_string(),
             key: KEY.to_string(),
             value: VALUE.to_vec(),
+            ttl_secs: None,
         },
     )
     .await;
@@ -76,6 +77,7 @@ async fn keyvalue_wrong_context() {
             bucket: BUCKET.to_string(),
             key: KEY.to_string(),
             value: VALUE.to_vec(),
+            ttl_secs: None,
         },
     )
     .await;
@@ -126,6 +128,7 @@ async fn keyvalue_wrong_key() {
             bucket: BUCKET.to_string(),
             key: KEY.to_string(),
             value: VALUE.to_vec(),
+            ttl_secs: None,
         },
     )
     .await;
@@ -206,6 +209,7 @@ async fn keyvalue_atomic_increment() {
             bucket: BUCKET.to_string(),
             key: KEY_2.to_string(),
             value: 10i64.to_le_bytes().to_vec(),
+            ttl_secs: None,
         },
     )
     .await;
@@ -249,6 +253,7 @@ async fn keyvalue_atomic_swap() {
             bucket: BUCKET.to_string(),
             key: KEY_1.to_string(),
             value: VALUE.to_vec(),
+            ttl_secs: None,
         },
     )
     .await;
@@ -583,3 +588,190 @@ async fn keyvalue_list() {
         _ => panic!("Expected ListKeys response"),
     }
 }
+
+#[tokio::test]
+async fn keyvalue_list_all_pages() {
+    init_tracing_tests();
+
+    const BUCKET: &str = "test_bucket";
+    let db = WavsDb::new().unwrap();
+    let mut keyvalue_ctx = KeyValueCtx::new(db.clone(), "test".to_string());
+    keyvalue_ctx.page_size = Some(3);
+
+    let values: HashMap<String, Vec<u8>> = (1..=10)
+        .map(|i| (format!("key_{i:02}"), format!("foo_{i}").into_bytes()))
+        .collect();
+
+    let _: Vec<KvStoreResponse> = execute_component(
+        COMPONENT_KV_STORE_BYTES,
+        Default::default(),
+        Some(keyvalue_ctx.clone()),
+        KvStoreRequest::BatchWrite {
+            bucket: BUCKET.to_string(),
+            values: values.clone(),
+        },
+    )
+    .await;
+
+    // a single page stops short
+    let resp = execute_component::<KvStoreResponse>(
+        COMPONENT_KV_STORE_BYTES,
+        Default::default(),
+        Some(keyvalue_ctx.clone()),
+        KvStoreRequest::ListKeys {
+            bucket: BUCKET.to_string(),
+            cursor: None,
+        },
+    )
+    .await;
+    match &resp[0] {
+        KvStoreResponse::ListKeys { keys, cursor } => {
+            assert_eq!(keys.len(), 3);
+            assert_eq!(cursor.as_deref(), Some("key_04"));
+        }
+        _ => panic!("Expected ListKeys response"),
+    }
+
+    // following the cursors finds every key, once
+    let resp = execute_component::<KvStoreResponse>(
+        COMPONENT_KV_STORE_BYTES,
+        Default::default(),
+        Some(keyvalue_ctx),
+        KvStoreRequest::ListAllKeys {
+            bucket: BUCKET.to_string(),
+        },
+    )
+    .await;
+    match &resp[0] {
+        KvStoreResponse::ListAllKeys { keys } => {
+            let mut expected: Vec<_> = values.keys().cloned().collect();
+            expected.sort();
+            assert_eq!(keys, &expected);
+        }
+        _ => panic!("Expected ListAllKeys response"),
+    }
+}
+
+#[tokio::test]
+async fn keyvalue_ttl() {
+    init_tracing_tests();
+
+    const BUCKET: &str = "test_bucket";
+    const KEY: &str = "test_key";
+    const VALUE: &[u8] = b"hello";
+
+    let db = WavsDb::new().unwrap();
+    let keyvalue_ctx = KeyValueCtx::new(db.clone(), "test".to_string());
+
+    let resp: Vec<KvStoreResponse> = execute_component(
+        COMPONENT_KV_STORE_BYTES,
+        Default::default(),
+        Some(keyvalue_ctx.clone()),
+        KvStoreRequest::Write {
+            bucket: BUCKET.to_string(),
+            key: KEY.to_string(),
+            value: VALUE.to_vec(),
+            ttl_secs: Some(1),
+        },
+    )
+    .await;
+
+    assert_eq!(resp[0], KvStoreResponse::Write);
+
+    // expiry has second granularity, so wait past the next full second
+    tokio::time::sleep(std::time::Duration::from_millis(2100)).await;
+
+    let err = try_execute_component::<KvStoreResponse>(
+        COMPONENT_KV_STORE_BYTES,
+        Default::default(),
+        Some(keyvalue_ctx),
+        KvStoreRequest::Read {
+            bucket: BUCKET.to_string(),
+            key: KEY.to_string(),
+        },
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        KvStoreError::MissingKey {
+            bucket: BUCKET.to_string(),
+            key: KEY.to_string(),
+        }
+        .to_string()
+    );
+}
+
+#[tokio::test]
+async fn keyvalue_batch_compare_swap() {
+    init_tracing_tests();
+
+    const BUCKET: &str = "test_bucket";
+    let db = WavsDb::new().unwrap();
+    let keyvalue_ctx = KeyValueCtx::new(db.clone(), "test".to_string());
+
+    // key, expected value and new value
+    type Entry<'a> = (&'a str, Option<&'a [u8]>, &'a [u8]);
+    let swap = |entries: Vec<Entry<'_>>| KvStoreRequest::BatchCompareSwap {
+        bucket: BUCKET.to_string(),
+        entries: entries
+            .into_iter()
+            .map(|(key, expected, value)| {
+                (
+                    key.to_string(),
+                    expected.map(<[u8]>::to_vec),
+                    value.to_vec(),
+                )
+            })
+            .collect(),
+    };
+
+    // both keys are new
+    let resp: Vec<KvStoreResponse> = execute_component(
+        COMPONENT_KV_STORE_BYTES,
+        Default::default(),
+        Some(keyvalue_ctx.clone()),
+        swap(vec![("bid", None, b"1"), ("ask", None, b"1")]),
+    )
+    .await;
+
+    assert_eq!(resp[0], KvStoreResponse::BatchCompareSwap);
+
+    // "ask" doesn't hold the expected value, so neither key is written
+    let err = try_execute_component::<KvStoreResponse>(
+        COMPONENT_KV_STORE_BYTES,
+        Default::default(),
+        Some(keyvalue_ctx.clone()),
+        swap(vec![("bid", Some(b"1"), b"2"), ("ask", Some(b"0"), b"2")]),
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        KvStoreError::BatchCompareSwapMismatch {
+            bucket: BUCKET.to_string(),
+            key: "ask".to_string(),
+        }
+        .to_string()
+    );
+
+    let resp = execute_component::<KvStoreResponse>(
+        COMPONENT_KV_STORE_BYTES,
+        Default::default(),
+        Some(keyvalue_ctx),
+        KvStoreRequest::Read {
+            bucket: BUCKET.to_string(),
+            key: "bid".to_string(),
+        },
+    )
+    .await;
+
+    assert_eq!(
+        resp[0],
+        KvStoreResponse::Read {
+            value: b"1".to_vec()
+        }
+    );
+}
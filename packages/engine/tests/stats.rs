@@ -0,0 +1,47 @@
+use utils::storage::db::WavsDb;
+use wavs_engine::utils::stats::{get_stat, update_stat, BUCKET_SECS, MAX_BUCKETS};
+
+#[test]
+fn running_stats_over_windows() {
+    let db = WavsDb::new().unwrap();
+    let key = "service/wavs-stats/price".to_string();
+    let start = 1_700_000_000 - 1_700_000_000 % BUCKET_SECS;
+
+    assert_eq!(get_stat(&db, key.clone(), None, start).unwrap(), None);
+
+    update_stat(&db, key.clone(), 10.0, start).unwrap();
+    update_stat(&db, key.clone(), 20.0, start + 1).unwrap();
+    let later = start + 10 * BUCKET_SECS;
+    let summary = update_stat(&db, key.clone(), 60.0, later).unwrap();
+
+    assert_eq!(summary.count, 3);
+    assert_eq!(summary.mean, 30.0);
+    assert_eq!(summary.min, 10.0);
+    assert_eq!(summary.max, 60.0);
+    // 10 -> 0.1 * 20 + 0.9 * 10 = 11 -> 0.1 * 60 + 0.9 * 11 = 15.9
+    assert!((summary.ema - 15.9).abs() < 1e-9);
+
+    // only the latest bucket is inside a one minute window
+    let recent = get_stat(&db, key.clone(), Some(BUCKET_SECS), later)
+        .unwrap()
+        .unwrap();
+    assert_eq!(recent.count, 1);
+    assert_eq!(recent.mean, 60.0);
+
+    // buckets older than a day are dropped, the all-time totals are kept
+    let next_day = later + MAX_BUCKETS as u64 * BUCKET_SECS + BUCKET_SECS;
+    update_stat(&db, key.clone(), 0.0, next_day).unwrap();
+    let day = get_stat(&db, key.clone(), Some(u64::MAX), next_day)
+        .unwrap()
+        .unwrap();
+    assert_eq!(day.count, 1);
+    assert_eq!(
+        get_stat(&db, key.clone(), None, next_day)
+            .unwrap()
+            .unwrap()
+            .count,
+        4
+    );
+
+    assert!(update_stat(&db, key, f64::NAN, next_day).is_err());
+}
@@ -21,6 +21,7 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use utils::{
     config::{ConfigBuilder, ConfigExt},
     context::AppContext,
+    logging::LogFormat,
     telemetry::{setup_metrics, setup_tracing, Metrics},
     test_utils::middleware::evm::EvmMiddleware,
 };
@@ -47,6 +48,7 @@ pub fn run(args: TestArgs, ctx: AppContext) {
                     &collector,
                     "wavs-tests",
                     config.tracing_env_filter().unwrap(),
+                    LogFormat::Full,
                 )
             }
         }))
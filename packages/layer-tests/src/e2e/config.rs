@@ -174,6 +174,7 @@ impl From<TestConfig> for Configs {
 
             let chain_config = EvmChainConfigBuilder {
                 http_endpoint: Some(http_endpoint),
+                http_endpoints: Vec::new(),
                 ws_endpoints: vec![ws_endpoint],
                 faucet_endpoint: None,
                 ws_priority_endpoint_index: None,
@@ -268,6 +269,7 @@ impl From<TestConfig> for Configs {
                             max_concurrent_catchup_requests_per_service: None,
                             max_pending_publishes: None,
                             max_stored_submissions_per_service: None,
+                            tls: None,
                         };
                     }
                     TestP2pMode::Mdns => {
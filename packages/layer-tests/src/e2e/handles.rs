@@ -236,6 +236,7 @@ impl AppHandles {
                 max_stored_submissions_per_service,
                 catchup_request_timeout_secs,
                 max_concurrent_catchup_requests_per_service,
+                tls,
             } = &config.p2p
             {
                 config.p2p = P2pConfig::Remote {
@@ -252,6 +253,7 @@ impl AppHandles {
                     catchup_request_timeout_secs: *catchup_request_timeout_secs,
                     max_concurrent_catchup_requests_per_service:
                         *max_concurrent_catchup_requests_per_service,
+                    tls: tls.clone(),
                 };
             }
 
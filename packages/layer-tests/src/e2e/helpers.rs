@@ -25,7 +25,7 @@ use crate::example_cosmos_client::SimpleCosmosSubmitClient;
 use crate::{
     e2e::{
         clients::Clients,
-        components::ComponentSources,
+        components::{ComponentName, ComponentSources, OperatorComponent},
         config::BLOCK_INTERVAL,
         test_definition::{
             AggregatorDefinition, ChangeServiceDefinition, ComponentDefinition, SubmitDefinition,
@@ -66,6 +66,8 @@ pub async fn create_service_for_test(
         workflows: BTreeMap::new(),
         status: ServiceStatus::Active,
         manager: service_manager,
+        max_concurrent_executions: None,
+        execution_timeout_secs: None,
     };
 
     let mut submission_handlers = BTreeMap::new();
@@ -106,9 +108,18 @@ fn deploy_component(
         .unwrap()
         .clone();
 
+    // the permissions component only talks to postman-echo, so it runs against an allowlist
+    // to exercise the host-level check
+    let allowed_http_hosts = match component_definition.name {
+        ComponentName::Operator(OperatorComponent::Permissions) => {
+            AllowedHostPermission::Only(vec!["postman-echo.com".to_string()])
+        }
+        _ => AllowedHostPermission::All,
+    };
+
     let mut component = Component::new(component_source);
     component.permissions = Permissions {
-        allowed_http_hosts: AllowedHostPermission::All,
+        allowed_http_hosts,
         file_system: true,
         raw_sockets: true,
         dns_resolution: true,
@@ -174,6 +185,12 @@ async fn deploy_workflow(
             trigger: trigger.clone(), // Clone for possible use in multi-trigger service
             component,
             submit: submit.clone(),
+            submit_targets: Vec::new(),
+            response_mapping: Default::default(),
+            submit_condition: None,
+            priority: Default::default(),
+            observe_only: false,
+            min_interval_ms: None,
         },
         submission_handler: submission_contract,
     }
@@ -206,6 +223,8 @@ pub async fn create_trigger_from_config(
                     chain: chain.clone(),
                     address,
                     event_hash: ByteArray::new(event_hash),
+                    additional_event_hashes: Vec::new(),
+                    transform: Vec::new(),
                 }
             }
         },
@@ -249,6 +268,7 @@ pub async fn create_trigger_from_config(
                         address: contract.contract_address.try_into().unwrap(),
                         event_type: cw_wavs_trigger_api::simple::PushMessageEvent::EVENT_TYPE
                             .to_string(),
+                        attributes: Default::default(),
                     }
                 }
             }
@@ -324,6 +344,7 @@ pub async fn create_submit_from_config(
                 Ok(Submit::Aggregator {
                     component: Box::new(component),
                     signature_kind: SignatureKind::evm_default(),
+                    targets: Vec::new(),
                 })
             }
         },
@@ -334,11 +334,7 @@ async fn run_test(
         // Execute the trigger once
         let mut reorg_snapshot: Option<U256> = None;
         let trigger_ids = match trigger {
-            Trigger::EvmContractEvent {
-                chain,
-                address,
-                event_hash: _,
-            } => {
+            Trigger::EvmContractEvent { chain, address, .. } => {
                 let evm_client = clients.get_evm_client(chain);
                 let client = SimpleEvmTriggerClient::new(evm_client.clone(), *address);
 
@@ -435,11 +431,7 @@ async fn run_test(
                 );
                 trigger_ids
             }
-            Trigger::CosmosContractEvent {
-                chain,
-                address,
-                event_type: _,
-            } => {
+            Trigger::CosmosContractEvent { chain, address, .. } => {
                 let client = SimpleCosmosTriggerClient::new(
                     clients.get_cosmos_client(chain).await,
                     address.clone().into(),
@@ -609,6 +601,7 @@ async fn run_test(
                 }
             }
             Trigger::Manual => unimplemented!("Manual trigger type is not implemented"),
+            Trigger::Webhook { .. } => unimplemented!("Webhook trigger type is not implemented"),
         };
 
         tracing::info!(
@@ -177,6 +177,8 @@ impl ServiceManagers {
                 workflows: Default::default(),
                 status: ServiceStatus::Paused,
                 manager: service_manager,
+                max_concurrent_executions: None,
+                execution_timeout_secs: None,
             };
 
             // Save the service on WAVS endpoint (just a local test thing, real-world would be IPFS or similar)
@@ -225,7 +227,7 @@ impl ServiceManagers {
                 for (idx, http_client) in http_clients.iter().enumerate() {
                     tracing::info!("Deploying service {} on WAVS instance {}", test.name, idx);
                     http_client
-                        .create_service(service_manager.clone(), None)
+                        .create_service(service_manager.clone(), None, None)
                         .await
                         .unwrap();
                 }
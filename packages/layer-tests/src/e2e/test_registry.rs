@@ -750,6 +750,7 @@ impl TestRegistry {
                             bucket: "test_bucket".to_string(),
                             key: "hello".to_string(),
                             value: b"world".to_vec(),
+                            ttl_secs: None,
                         }))
                         .with_submit(SubmitDefinition::Aggregator(Self::simple_aggregator(chain)))
                         .with_expected_output(ExpectedOutput::KvStore(KvStoreResponse::Write))
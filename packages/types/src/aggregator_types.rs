@@ -2,6 +2,9 @@ use layer_climb_address::{CosmosAddr, EvmAddr};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+#[cfg(feature = "ts-bindings")]
+use ts_rs::TS;
+
 use crate::{ChainKey, Duration, EventId, Submission};
 
 #[derive(
@@ -28,7 +31,30 @@ pub enum QuorumQueue {
     /// Queue that has been processed and submitted on-chain
     /// Includes timestamp (seconds since UNIX epoch) when it was burned
     Burned(u64),
-    Active(Vec<Submission>),
+    Active(Vec<QueuedSubmission>),
+}
+
+/// A submission waiting in a quorum queue, along with where it came from
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct QueuedSubmission {
+    pub submission: Submission,
+    pub provenance: SubmissionProvenance,
+}
+
+/// Audit record of who contributed a submission to a quorum queue
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, ToSchema)]
+pub struct SubmissionProvenance {
+    /// Operator address recovered from the envelope signature
+    #[schema(value_type = String)]
+    pub signer: alloy_primitives::Address,
+    /// P2P peer id the submission arrived from, `None` if it was produced by this node
+    pub peer: Option<String>,
+    /// When the submission was received (seconds since UNIX epoch)
+    pub received_at: u64,
+    /// Weight of the signer's operator when the submission was received, as a decimal string.
+    /// `None` unless the aggregator verifies operators
+    #[serde(default)]
+    pub weight: Option<String>,
 }
 
 impl Default for QuorumQueue {
@@ -37,6 +63,8 @@ impl Default for QuorumQueue {
     }
 }
 
+#[cfg_attr(feature = "ts-bindings", derive(TS))]
+#[cfg_attr(feature = "ts-bindings", ts(export))]
 #[derive(
     Serialize,
     Deserialize,
@@ -65,6 +93,8 @@ impl SubmitAction {
     }
 }
 
+#[cfg_attr(feature = "ts-bindings", derive(TS))]
+#[cfg_attr(feature = "ts-bindings", ts(export))]
 #[derive(
     Serialize,
     Deserialize,
@@ -82,10 +112,39 @@ impl SubmitAction {
 pub struct EvmSubmitAction {
     pub chain: ChainKey,
     // using EvmAddr from climb instead of alloy::primitives::Address for bincode support
+    #[cfg_attr(feature = "ts-bindings", ts(type = "string"))]
     pub address: EvmAddr,
+    /// Legacy gas price, ignored if `eip1559` is set
     pub gas_price: Option<u128>,
+    /// Submit as an EIP-1559 transaction with these fees
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eip1559: Option<Eip1559Fees>,
+}
+
+#[cfg_attr(feature = "ts-bindings", derive(TS))]
+#[cfg_attr(feature = "ts-bindings", ts(export))]
+#[derive(
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    bincode::Encode,
+    bincode::Decode,
+    ToSchema,
+)]
+pub struct Eip1559Fees {
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
 }
 
+#[cfg_attr(feature = "ts-bindings", derive(TS))]
+#[cfg_attr(feature = "ts-bindings", ts(export))]
 #[derive(
     Serialize,
     Deserialize,
@@ -102,6 +161,7 @@ pub struct EvmSubmitAction {
 )]
 pub struct CosmosSubmitAction {
     pub chain: ChainKey,
+    #[cfg_attr(feature = "ts-bindings", ts(type = "string"))]
     pub address: CosmosAddr,
     pub gas_price: Option<u128>,
 }
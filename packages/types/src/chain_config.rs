@@ -13,6 +13,8 @@ pub enum ChainConfigError {
     ExpectedEvmChain,
     #[error("Expected Cosmos chain")]
     ExpectedCosmosChain,
+    #[error("Expected Solana chain")]
+    ExpectedSolanaChain,
     #[error("Invalid chain: {0}")]
     InvalidChainKey(#[from] ChainKeyError),
     #[error("Chain already exists: {0}")]
@@ -21,7 +23,9 @@ pub enum ChainConfigError {
     InvalidNamespaceForCosmos(ChainKeyNamespace),
     #[error("Namespace for cosmos chain must be {evm} or {dev}, got {0}", evm=ChainKeyNamespace::EVM, dev=ChainKeyNamespace::DEV)]
     InvalidNamespaceForEvm(ChainKeyNamespace),
-    #[error("Namespace must be one of {cosmos}, {evm}, or {dev}, got {0}", cosmos=ChainKeyNamespace::COSMOS, evm=ChainKeyNamespace::EVM, dev=ChainKeyNamespace::DEV)]
+    #[error("Namespace for solana chain must be {solana} or {dev}, got {0}", solana=ChainKeyNamespace::SOLANA, dev=ChainKeyNamespace::DEV)]
+    InvalidNamespaceForSolana(ChainKeyNamespace),
+    #[error("Namespace must be one of {cosmos}, {evm}, {solana}, or {dev}, got {0}", cosmos=ChainKeyNamespace::COSMOS, evm=ChainKeyNamespace::EVM, solana=ChainKeyNamespace::SOLANA, dev=ChainKeyNamespace::DEV)]
     InvalidNamespace(ChainKeyNamespace),
     #[error("Chain ID mismatch: expected {expected}, got {actual}")]
     IdMismatch {
@@ -61,10 +65,28 @@ pub struct EvmChainConfig {
     pub chain_id: ChainKeyId,
     pub ws_endpoints: Vec<String>,
     pub http_endpoint: Option<String>,
+    /// More http endpoints for the same chain, calls fail over to these in order
+    /// when `http_endpoint` keeps failing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub http_endpoints: Vec<String>,
     pub faucet_endpoint: Option<String>,
     pub ws_priority_endpoint_index: Option<usize>,
 }
 
+impl EvmChainConfig {
+    /// Every configured http endpoint in the order they should be tried,
+    /// a single `http_endpoint` is just a list of one
+    pub fn all_http_endpoints(&self) -> Vec<&str> {
+        let mut endpoints: Vec<&str> = Vec::new();
+        for endpoint in self.http_endpoint.iter().chain(&self.http_endpoints) {
+            if !endpoints.contains(&endpoint.as_str()) {
+                endpoints.push(endpoint);
+            }
+        }
+        endpoints
+    }
+}
+
 impl From<&EvmChainConfig> for ChainKey {
     fn from(config: &EvmChainConfig) -> Self {
         ChainKey {
@@ -80,11 +102,49 @@ impl From<EvmChainConfig> for ChainKey {
     }
 }
 
+/// An SVM chain. Only the config is available for now, e.g. for components to read
+/// through the host, there are no Solana triggers or submissions yet
+#[derive(Debug, Deserialize, Serialize, Clone, ToSchema)]
+pub struct SolanaChainConfig {
+    /// The cluster, e.g. "mainnet-beta" or "devnet"
+    pub chain_id: ChainKeyId,
+    pub rpc_endpoint: Option<String>,
+    pub ws_endpoint: Option<String>,
+    #[serde(default)]
+    pub commitment: SolanaCommitment,
+}
+
+/// How settled a block must be before the node reports on it, from least to most final
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SolanaCommitment {
+    Processed,
+    #[default]
+    Confirmed,
+    Finalized,
+}
+
+impl From<&SolanaChainConfig> for ChainKey {
+    fn from(config: &SolanaChainConfig) -> Self {
+        ChainKey {
+            id: config.chain_id.clone(),
+            namespace: ChainKeyNamespace::SOLANA.parse().unwrap(),
+        }
+    }
+}
+
+impl From<SolanaChainConfig> for ChainKey {
+    fn from(config: SolanaChainConfig) -> Self {
+        (&config).into()
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum AnyChainConfig {
     Cosmos(CosmosChainConfig),
     Evm(EvmChainConfig),
+    Solana(SolanaChainConfig),
 }
 
 impl From<&AnyChainConfig> for ChainKey {
@@ -92,6 +152,7 @@ impl From<&AnyChainConfig> for ChainKey {
         match config {
             AnyChainConfig::Cosmos(config) => config.into(),
             AnyChainConfig::Evm(config) => config.into(),
+            AnyChainConfig::Solana(config) => config.into(),
         }
     }
 }
@@ -107,6 +168,7 @@ impl AnyChainConfig {
         match self {
             AnyChainConfig::Cosmos(config) => &config.chain_id,
             AnyChainConfig::Evm(config) => &config.chain_id,
+            AnyChainConfig::Solana(config) => &config.chain_id,
         }
     }
 }
@@ -123,13 +185,19 @@ impl From<EvmChainConfig> for AnyChainConfig {
     }
 }
 
+impl From<SolanaChainConfig> for AnyChainConfig {
+    fn from(config: SolanaChainConfig) -> Self {
+        AnyChainConfig::Solana(config)
+    }
+}
+
 impl TryFrom<AnyChainConfig> for CosmosChainConfig {
     type Error = ChainConfigError;
 
     fn try_from(config: AnyChainConfig) -> Result<Self, Self::Error> {
         match config {
             AnyChainConfig::Cosmos(config) => Ok(config),
-            AnyChainConfig::Evm(_) => Err(ChainConfigError::ExpectedCosmosChain),
+            _ => Err(ChainConfigError::ExpectedCosmosChain),
         }
     }
 }
@@ -140,7 +208,18 @@ impl TryFrom<AnyChainConfig> for EvmChainConfig {
     fn try_from(config: AnyChainConfig) -> Result<Self, Self::Error> {
         match config {
             AnyChainConfig::Evm(config) => Ok(config),
-            AnyChainConfig::Cosmos(_) => Err(ChainConfigError::ExpectedEvmChain),
+            _ => Err(ChainConfigError::ExpectedEvmChain),
+        }
+    }
+}
+
+impl TryFrom<AnyChainConfig> for SolanaChainConfig {
+    type Error = ChainConfigError;
+
+    fn try_from(config: AnyChainConfig) -> Result<Self, Self::Error> {
+        match config {
+            AnyChainConfig::Solana(config) => Ok(config),
+            _ => Err(ChainConfigError::ExpectedSolanaChain),
         }
     }
 }
@@ -210,14 +289,21 @@ impl AnyChainConfig {
     pub fn to_cosmos_config(&self) -> Result<CosmosChainConfig, ChainConfigError> {
         match self {
             AnyChainConfig::Cosmos(config) => Ok(config.clone()),
-            AnyChainConfig::Evm(_) => Err(ChainConfigError::ExpectedCosmosChain),
+            _ => Err(ChainConfigError::ExpectedCosmosChain),
         }
     }
 
     pub fn to_evm_config(&self) -> Result<EvmChainConfig, ChainConfigError> {
         match self {
             AnyChainConfig::Evm(config) => Ok(config.clone()),
-            AnyChainConfig::Cosmos(_) => Err(ChainConfigError::ExpectedEvmChain),
+            _ => Err(ChainConfigError::ExpectedEvmChain),
+        }
+    }
+
+    pub fn to_solana_config(&self) -> Result<SolanaChainConfig, ChainConfigError> {
+        match self {
+            AnyChainConfig::Solana(config) => Ok(config.clone()),
+            _ => Err(ChainConfigError::ExpectedSolanaChain),
         }
     }
 
@@ -233,7 +319,7 @@ impl AnyChainConfig {
 }
 
 /// Chains are identified by `ChainKey`, which is a combination of a namespace and id
-/// for now - we natively support 3 namespaces: cosmos, evm, and dev
+/// for now - we natively support 4 namespaces: cosmos, evm, solana, and dev
 #[derive(Debug, Default, Deserialize, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct ChainConfigs {
@@ -243,6 +329,9 @@ pub struct ChainConfigs {
     /// EVM-style chains
     #[serde(default)]
     pub evm: BTreeMap<ChainKeyId, EvmChainConfigBuilder>,
+    /// Solana-style (SVM) chains
+    #[serde(default)]
+    pub solana: BTreeMap<ChainKeyId, SolanaChainConfigBuilder>,
     /// DEV-only chains
     /// The key here can be different than the chain_id inside the config
     #[serde(default)]
@@ -260,6 +349,10 @@ impl ChainConfigs {
                 .evm
                 .get(&key.id)
                 .map(|c| AnyChainConfig::Evm(c.clone().build(key.id.clone()))),
+            ChainKeyNamespace::SOLANA => self
+                .solana
+                .get(&key.id)
+                .map(|c| AnyChainConfig::Solana(c.clone().build(key.id.clone()))),
             ChainKeyNamespace::DEV => self.dev.get(&key.id).cloned(),
             _ => None,
         }
@@ -277,6 +370,12 @@ impl ChainConfigs {
             .map(|(id, config)| config.clone().build(id.clone()))
     }
 
+    pub fn solana_iter(&self) -> impl Iterator<Item = SolanaChainConfig> + '_ {
+        self.solana
+            .iter()
+            .map(|(id, config)| config.clone().build(id.clone()))
+    }
+
     pub fn dev_iter(&self) -> impl Iterator<Item = AnyChainConfig> + '_ {
         self.dev.values().cloned()
     }
@@ -296,6 +395,12 @@ impl ChainConfigs {
                 id: id.clone(),
             });
         }
+        for id in self.solana.keys() {
+            keys.push(ChainKey {
+                namespace: ChainKeyNamespace::SOLANA.parse()?,
+                id: id.clone(),
+            });
+        }
         for id in self.dev.keys() {
             keys.push(ChainKey {
                 namespace: ChainKeyNamespace::DEV.parse()?,
@@ -324,6 +429,14 @@ impl ChainConfigs {
                     id: id.clone(),
                 })
                 .collect(),
+            ChainKeyNamespace::SOLANA => self
+                .solana
+                .keys()
+                .map(|id| ChainKey {
+                    namespace: namespace.clone(),
+                    id: id.clone(),
+                })
+                .collect(),
             ChainKeyNamespace::DEV => self
                 .dev
                 .keys()
@@ -360,6 +473,7 @@ impl ChainConfigs {
                     let evm_config = EvmChainConfigBuilder {
                         ws_endpoints: evm_config.ws_endpoints,
                         http_endpoint: evm_config.http_endpoint,
+                        http_endpoints: evm_config.http_endpoints,
                         faucet_endpoint: evm_config.faucet_endpoint,
                         ws_priority_endpoint_index: evm_config.ws_priority_endpoint_index,
                     };
@@ -387,6 +501,23 @@ impl ChainConfigs {
                 }
                 _ => return Err(ChainConfigError::InvalidNamespaceForCosmos(key.namespace)),
             },
+            ChainKeyNamespace::SOLANA => match config {
+                AnyChainConfig::Solana(solana_config) => {
+                    if solana_config.chain_id != key.id {
+                        return Err(ChainConfigError::IdMismatch {
+                            expected: key.id,
+                            actual: solana_config.chain_id,
+                        });
+                    }
+                    let solana_config = SolanaChainConfigBuilder {
+                        rpc_endpoint: solana_config.rpc_endpoint,
+                        ws_endpoint: solana_config.ws_endpoint,
+                        commitment: solana_config.commitment,
+                    };
+                    self.solana.insert(key.id, solana_config);
+                }
+                _ => return Err(ChainConfigError::InvalidNamespaceForSolana(key.namespace)),
+            },
             _ => return Err(ChainConfigError::InvalidNamespace(key.namespace)),
         }
 
@@ -422,6 +553,8 @@ impl CosmosChainConfigBuilder {
 pub struct EvmChainConfigBuilder {
     pub ws_endpoints: Vec<String>,
     pub http_endpoint: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub http_endpoints: Vec<String>,
     pub faucet_endpoint: Option<String>,
     pub ws_priority_endpoint_index: Option<usize>,
 }
@@ -432,8 +565,28 @@ impl EvmChainConfigBuilder {
             chain_id: id,
             ws_endpoints: self.ws_endpoints,
             http_endpoint: self.http_endpoint,
+            http_endpoints: self.http_endpoints,
             faucet_endpoint: self.faucet_endpoint,
             ws_priority_endpoint_index: self.ws_priority_endpoint_index,
         }
     }
 }
+
+#[derive(Debug, Deserialize, Serialize, Clone, ToSchema)]
+pub struct SolanaChainConfigBuilder {
+    pub rpc_endpoint: Option<String>,
+    pub ws_endpoint: Option<String>,
+    #[serde(default)]
+    pub commitment: SolanaCommitment,
+}
+
+impl SolanaChainConfigBuilder {
+    pub fn build(self, id: ChainKeyId) -> SolanaChainConfig {
+        SolanaChainConfig {
+            chain_id: id,
+            rpc_endpoint: self.rpc_endpoint,
+            ws_endpoint: self.ws_endpoint,
+            commitment: self.commitment,
+        }
+    }
+}
@@ -1,9 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 use super::Service;
 use crate::{
-    AnyChainConfig, ByteArray, ChainKey, ComponentDigest, ServiceDigest, ServiceId, ServiceManager,
-    Trigger, TriggerData, WorkflowId,
+    AnyChainConfig, ByteArray, ChainKey, ComponentDigest, EventId, ServiceDigest, ServiceId,
+    ServiceManager, SubmissionProvenance, SubmitAction, Timestamp, Trigger, TriggerData,
+    WorkflowId,
 };
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -22,6 +23,20 @@ pub enum SignerResponse {
 #[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
 pub struct AddServiceRequest {
     pub service_manager: ServiceManager,
+    /// Client-generated id that makes the deploy safe to retry,
+    /// a repeat request with the same id returns the original result
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployment_id: Option<String>,
+}
+
+/// What the node remembers about a deploy made with a `deployment_id`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, ToSchema)]
+pub struct DeploymentRecord {
+    pub deployment_id: String,
+    pub service_manager: ServiceManager,
+    pub service_id: ServiceId,
+    /// Unix timestamp in seconds
+    pub deployed_at: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
@@ -45,6 +60,49 @@ pub struct ListServicesResponse {
     pub services: Vec<Service>,
     pub service_ids: Vec<ServiceId>,
     pub component_digests: Vec<ComponentDigest>,
+    /// A short summary of each service in `services`
+    #[serde(default)]
+    pub summaries: Vec<ServiceSummary>,
+    /// Number of services on the node, regardless of paging
+    #[serde(default)]
+    pub total: usize,
+}
+
+/// What a service is at a glance, without its full definition
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, ToSchema)]
+pub struct ServiceSummary {
+    #[schema(value_type = String)]
+    pub service_id: ServiceId,
+    pub name: String,
+    pub workflows: usize,
+    /// The distinct kinds of trigger its workflows fire on, e.g. `evm_contract_event`
+    pub trigger_kinds: Vec<String>,
+}
+
+impl From<&Service> for ServiceSummary {
+    fn from(service: &Service) -> Self {
+        let trigger_kinds: BTreeSet<&str> = service
+            .workflows
+            .values()
+            .map(|workflow| workflow.trigger.trigger_type())
+            .collect();
+
+        Self {
+            service_id: service.id(),
+            name: service.name.clone(),
+            workflows: service.workflows.len(),
+            trigger_kinds: trigger_kinds.into_iter().map(str::to_string).collect(),
+        }
+    }
+}
+
+/// Body of every error response from the node's HTTP API
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, ToSchema)]
+pub struct ErrorResponse {
+    /// Human readable description of what went wrong
+    pub error: String,
+    /// Stable, machine readable kind of error, e.g. `not_found` or `bad_request`
+    pub code: String,
 }
 
 #[derive(Serialize, Deserialize, ToSchema)]
@@ -144,3 +202,137 @@ pub struct P2pStatus {
     /// Number of peers subscribed to our topics (topic -> peer count)
     pub topic_peer_counts: HashMap<String, usize>,
 }
+
+/// Operational state of a single workflow, as tracked by the node since startup
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct WorkflowStatusResponse {
+    pub service_id: ServiceId,
+    pub workflow_id: WorkflowId,
+    /// Total number of successful executions
+    pub total_successes: u64,
+    /// Total number of failed executions
+    pub total_failures: u64,
+    /// The most recent execution outcomes, newest first
+    pub recent_outcomes: Vec<WorkflowOutcome>,
+}
+
+/// A single recorded execution outcome for a workflow
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct WorkflowOutcome {
+    /// When the execution finished
+    pub timestamp: Timestamp,
+    /// Whether the execution succeeded
+    pub success: bool,
+    /// Number of responses produced by the component
+    pub responses: usize,
+    /// The error message, if the execution failed
+    pub error: Option<String>,
+}
+
+/// Contents of an aggregator quorum queue, showing which operators have contributed so far
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct QuorumQueueStatus {
+    /// Hex encoded queue id, for `GET /aggregator/queues/{id}`
+    pub id: String,
+    #[schema(value_type = String)]
+    pub event_id: EventId,
+    pub action: SubmitAction,
+    /// Service of the queued submissions, `None` once burned
+    #[schema(value_type = Option<String>)]
+    pub service_id: Option<ServiceId>,
+    /// Number of signatures collected so far
+    pub collected: usize,
+    /// Seconds since the first signature was received, `None` once burned
+    pub age_secs: Option<u64>,
+    /// Weights from the last quorum check, on-chain or against `quorum_threshold_percent`,
+    /// `None` until the queue has been checked
+    pub quorum: Option<QuorumProgress>,
+    /// Set once the queue has been submitted on-chain (seconds since UNIX epoch)
+    pub burned_at: Option<u64>,
+    /// Provenance of each queued submission, empty once burned
+    pub entries: Vec<SubmissionProvenance>,
+}
+
+/// Signer weight against the threshold, as the service manager reported it.
+/// Weights are decimal strings since they can exceed what JSON numbers hold
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct QuorumProgress {
+    pub signer_weight: String,
+    pub threshold_weight: String,
+    pub total_weight: String,
+}
+
+/// What an `observe_only` workflow would have submitted, had it not been observe-only
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ObservedSubmission {
+    pub service_id: ServiceId,
+    pub workflow_id: WorkflowId,
+    #[schema(value_type = String)]
+    pub event_id: EventId,
+    pub action: SubmitAction,
+    /// Number of operator signatures that reached quorum
+    pub signatures: usize,
+    /// When the submission was skipped (seconds since UNIX epoch)
+    pub observed_at: u64,
+}
+
+/// Outcome of posting a result to one of a workflow's `submit_targets`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TargetDelivery {
+    pub service_id: ServiceId,
+    pub workflow_id: WorkflowId,
+    #[schema(value_type = String)]
+    pub event_id: EventId,
+    /// The target's url
+    pub target: String,
+    /// Number of operator signatures in the posted result
+    pub signatures: usize,
+    /// Why the delivery failed once retries ran out, `None` if it went through
+    pub error: Option<String>,
+    /// When the delivery finished (seconds since UNIX epoch)
+    pub delivered_at: u64,
+}
+
+/// Something that happened to one of a service's workflows, as streamed by
+/// `GET /services/{service_id}/events`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ServiceEvent {
+    /// Increases with every event the node records, across all services
+    pub id: u64,
+    pub timestamp: Timestamp,
+    #[schema(value_type = String)]
+    pub service_id: ServiceId,
+    #[schema(value_type = String)]
+    pub workflow_id: WorkflowId,
+    pub kind: ServiceEventKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServiceEventKind {
+    /// The dispatcher received a trigger for the workflow
+    TriggerReceived { trigger_type: String },
+    /// The operator component started running
+    ExecutionStarted,
+    /// The operator component finished, with the number of responses or the error
+    ExecutionResult {
+        responses: usize,
+        error: Option<String>,
+    },
+    /// The operator signed a response and handed it to the aggregator
+    Submission {
+        #[schema(value_type = String)]
+        event_id: EventId,
+    },
+}
+
+impl ServiceEventKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ServiceEventKind::TriggerReceived { .. } => "trigger_received",
+            ServiceEventKind::ExecutionStarted => "execution_started",
+            ServiceEventKind::ExecutionResult { .. } => "execution_result",
+            ServiceEventKind::Submission { .. } => "submission",
+        }
+    }
+}
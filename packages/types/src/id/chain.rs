@@ -16,9 +16,7 @@ use utoipa::ToSchema;
 /// and changes the naming of chain_id -> chain_key, reference -> chain_id
 #[cfg_attr(feature = "ts-bindings", derive(TS))]
 #[cfg_attr(feature = "ts-bindings", ts(export, type = "string"))]
-#[derive(
-    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, ToSchema, bincode::Decode, bincode::Encode,
-)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, bincode::Decode, bincode::Encode)]
 pub struct ChainKey {
     pub namespace: ChainKeyNamespace,
     pub id: ChainKeyId,
@@ -78,6 +76,21 @@ impl Serialize for ChainKey {
     }
 }
 
+// described as the string it serializes to, not as the struct
+impl utoipa::PartialSchema for ChainKey {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        utoipa::openapi::ObjectBuilder::new()
+            .schema_type(utoipa::openapi::schema::Type::String)
+            .description(Some(
+                "Blockchain network identifier as `namespace:id`, e.g. `evm:1`",
+            ))
+            .pattern(Some(CHAIN_KEY_REGEX.as_str()))
+            .into()
+    }
+}
+
+impl ToSchema for ChainKey {}
+
 impl std::fmt::Display for ChainKey {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}:{}", self.namespace, self.id)
@@ -118,6 +131,7 @@ impl ChainKeyNamespace {
     pub const EVM: &str = "evm";
     pub const COSMOS: &str = "cosmos";
     pub const DEV: &str = "dev";
+    pub const SOLANA: &str = "solana";
 
     pub fn new(s: impl Into<String>) -> ChainKeyResult<Self> {
         let s = s.into();
@@ -32,6 +32,7 @@ macro_rules! new_hash_id_type {
             bincode::Decode,
             bincode::Encode,
         )]
+        #[schema(value_type = String)]
         pub struct $type_name([u8; 32]);
 
         impl $type_name {
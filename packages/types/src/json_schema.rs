@@ -0,0 +1,239 @@
+//! Standalone JSON Schemas for the types that make up a service definition.
+//!
+//! These are built from the same `ToSchema` derives as the node's OpenAPI spec,
+//! so they can't drift from the Rust types. Referenced types end up under `$defs`.
+
+use serde_json::{json, Map, Value};
+use utoipa::ToSchema;
+
+const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+const OPENAPI_REF_PREFIX: &str = "#/components/schemas/";
+const DEFS_REF_PREFIX: &str = "#/$defs/";
+
+/// A JSON Schema (draft 2020-12) document for `T`
+pub fn json_schema<T: ToSchema>() -> Value {
+    let mut schemas = Vec::new();
+    T::schemas(&mut schemas);
+
+    let defs: Map<String, Value> = schemas
+        .into_iter()
+        .map(|(name, schema)| (name, to_json_schema(&schema)))
+        .collect();
+
+    let mut root = match to_json_schema(&T::schema()) {
+        Value::Object(root) => root,
+        other => Map::from_iter([("allOf".to_string(), json!([other]))]),
+    };
+    root.insert("$schema".to_string(), json!(DIALECT));
+    root.insert("title".to_string(), json!(T::name()));
+    if !defs.is_empty() {
+        root.insert("$defs".to_string(), Value::Object(defs));
+    }
+
+    Value::Object(root)
+}
+
+/// OpenAPI 3.1 schemas are JSON Schema, only their references point into the OpenAPI document
+fn to_json_schema(schema: &impl serde::Serialize) -> Value {
+    let mut value = serde_json::to_value(schema).expect("schemas serialize to JSON");
+    rewrite_refs(&mut value);
+    value
+}
+
+fn rewrite_refs(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                match value {
+                    Value::String(reference) if key == "$ref" => {
+                        if let Some(name) = reference.strip_prefix(OPENAPI_REF_PREFIX) {
+                            *reference = format!("{DEFS_REF_PREFIX}{name}");
+                        }
+                    }
+                    _ => rewrite_refs(value),
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(rewrite_refs),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::num::NonZeroU32;
+
+    use super::*;
+    use crate::{
+        ByteArray, ChainKey, ComponentDigest, ComponentSource, Service, ServiceManager, Submit,
+        Trigger, Workflow,
+    };
+
+    /// Checks the parts of JSON Schema that utoipa emits
+    fn validate(schema: &Value, value: &Value, root: &Value) -> Result<(), String> {
+        let Value::Object(schema) = schema else {
+            return Ok(());
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let name = reference
+                .strip_prefix(DEFS_REF_PREFIX)
+                .ok_or_else(|| format!("unexpected reference {reference}"))?;
+            let target = root["$defs"]
+                .get(name)
+                .ok_or_else(|| format!("unresolved reference {reference}"))?;
+            validate(target, value, root)?;
+        }
+
+        if let Some(types) = schema.get("type") {
+            let types: Vec<&str> = match types {
+                Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+                ty => ty.as_str().into_iter().collect(),
+            };
+            let matches = types.iter().any(|ty| match *ty {
+                "object" => value.is_object(),
+                "array" => value.is_array(),
+                "string" => value.is_string(),
+                "integer" => value.is_i64() || value.is_u64(),
+                "number" => value.is_number(),
+                "boolean" => value.is_boolean(),
+                "null" => value.is_null(),
+                _ => true,
+            });
+            if !matches {
+                return Err(format!("{value} is not of type {types:?}"));
+            }
+        }
+
+        if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+            if !allowed.contains(value) {
+                return Err(format!("{value} is not one of {allowed:?}"));
+            }
+        }
+
+        for all in schema.get("allOf").and_then(Value::as_array).into_iter() {
+            for schema in all {
+                validate(schema, value, root)?;
+            }
+        }
+
+        for key in ["oneOf", "anyOf"] {
+            if let Some(options) = schema.get(key).and_then(Value::as_array) {
+                if !options.iter().any(|s| validate(s, value, root).is_ok()) {
+                    return Err(format!("{value} matches none of {key}"));
+                }
+            }
+        }
+
+        if let Value::Object(object) = value {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for required in schema.get("required").and_then(Value::as_array).into_iter() {
+                for field in required.iter().filter_map(Value::as_str) {
+                    if !object.contains_key(field) {
+                        return Err(format!("missing required field {field}"));
+                    }
+                }
+            }
+            for (field, field_value) in object {
+                match properties.and_then(|properties| properties.get(field)) {
+                    Some(field_schema) => validate(field_schema, field_value, root)
+                        .map_err(|err| format!("{field}: {err}"))?,
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            return Err(format!("unexpected field {field}"))
+                        }
+                        Some(additional) => validate(additional, field_value, root)
+                            .map_err(|err| format!("{field}: {err}"))?,
+                        None => {}
+                    },
+                }
+            }
+        }
+
+        if let (Some(items), Value::Array(values)) = (schema.get("items"), value) {
+            for value in values {
+                validate(items, value, root)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn example_services() -> Vec<Service> {
+        let chain: ChainKey = "evm:anvil".parse().unwrap();
+        let triggers = [
+            Trigger::EvmContractEvent {
+                address: Default::default(),
+                chain: chain.clone(),
+                event_hash: ByteArray::new([1; 32]),
+                additional_event_hashes: Vec::new(),
+                transform: Vec::new(),
+            },
+            Trigger::BlockInterval {
+                chain: chain.clone(),
+                n_blocks: NonZeroU32::new(10).unwrap(),
+                start_block: None,
+                end_block: None,
+            },
+            Trigger::Cron {
+                schedule: "0 * * * * *".to_string(),
+                start_time: None,
+                end_time: None,
+            },
+            Trigger::Webhook {
+                signature_secret: Some("WEBHOOK_SECRET".to_string()),
+            },
+            Trigger::Manual,
+        ];
+
+        triggers
+            .into_iter()
+            .map(|trigger| {
+                let source = ComponentSource::Digest(ComponentDigest::hash(b"component"));
+                let mut service = Service::new_simple(
+                    Some("example".to_string()),
+                    trigger,
+                    source.clone(),
+                    Submit::Aggregator {
+                        component: Box::new(crate::Component::new(source)),
+                        signature_kind: crate::SignatureKind::evm_default(),
+                        targets: Vec::new(),
+                    },
+                    ServiceManager::Evm {
+                        chain: chain.clone(),
+                        address: Default::default(),
+                    },
+                );
+                for workflow in service.workflows.values_mut() {
+                    workflow.min_interval_ms = Some(1000);
+                }
+                service
+            })
+            .collect()
+    }
+
+    #[test]
+    fn service_schema_validates_services() {
+        let schema = json_schema::<Service>();
+        assert_eq!(schema["$schema"], DIALECT);
+        assert_eq!(schema["title"], "Service");
+        assert!(!schema.to_string().contains(OPENAPI_REF_PREFIX));
+
+        for service in example_services() {
+            let value = serde_json::to_value(&service).unwrap();
+            validate(&schema, &value, &schema).unwrap();
+        }
+
+        // and rejects what isn't one
+        let mut value = serde_json::to_value(&example_services()[0]).unwrap();
+        value.as_object_mut().unwrap().remove("workflows");
+        validate(&schema, &value, &schema).unwrap_err();
+    }
+
+    #[test]
+    fn workflow_schema_title() {
+        let schema = json_schema::<Workflow>();
+        assert_eq!(schema["title"], "Workflow");
+        assert!(schema["$defs"].get("Trigger").is_some());
+    }
+}
@@ -5,13 +5,16 @@ pub mod contracts;
 mod credential;
 mod http;
 mod id;
+mod json_schema;
 mod serde_helpers;
 mod service;
 mod service_builder;
 mod signing;
+mod solana;
 mod solidity_types;
 mod submission;
 mod time;
+mod trigger_transform;
 pub const WAVS_ENV_PREFIX: &str = "WAVS_ENV";
 pub use aggregator_types::*;
 pub use bytes::*;
@@ -19,9 +22,12 @@ pub use chain_config::*;
 pub use credential::*;
 pub use http::*;
 pub use id::*;
+pub use json_schema::*;
 pub use service::*;
 pub use service_builder::*;
 pub use signing::*;
+pub use solana::*;
 pub use solidity_types::*;
 pub use submission::*;
 pub use time::*;
+pub use trigger_transform::*;
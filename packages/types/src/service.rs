@@ -13,7 +13,7 @@ use wasm_pkg_common::package::PackageRef;
 #[cfg(feature = "ts-bindings")]
 use ts_rs::TS;
 
-use crate::{ByteArray, ComponentDigest, ServiceDigest, Timestamp};
+use crate::{ByteArray, ComponentDigest, EvmLogField, ServiceDigest, SubmitAction, Timestamp};
 
 use super::{ChainKey, ServiceId, WorkflowId};
 
@@ -81,6 +81,16 @@ pub struct Service {
     pub status: ServiceStatus,
 
     pub manager: ServiceManager,
+
+    /// Cap on how many of this service's executions may run at once, overriding the node-wide default.
+    /// Triggers beyond the cap wait their turn rather than being dropped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_executions: Option<u32>,
+
+    /// Wall-clock limit on each execution of this service's components, on top of the workflow time limit.
+    /// Enforced inside the component too, so busy loops are killed as reliably as sleeping components
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_timeout_secs: Option<u64>,
 }
 
 impl Service {
@@ -93,6 +103,22 @@ impl Service {
     pub fn id(&self) -> ServiceId {
         ServiceId::from(&self.manager)
     }
+
+    /// Every component this service runs, operator and aggregator.
+    /// Workflows sharing the same bytes are listed once
+    pub fn component_digests(&self) -> BTreeSet<ComponentDigest> {
+        self.workflows
+            .values()
+            .flat_map(|workflow| {
+                let aggregator = match &workflow.submit {
+                    Submit::Aggregator { component, .. } => Some(component.source.digest()),
+                    _ => None,
+                };
+                std::iter::once(workflow.component.source.digest()).chain(aggregator)
+            })
+            .cloned()
+            .collect()
+    }
 }
 
 #[cfg_attr(feature = "ts-bindings", derive(TS))]
@@ -135,6 +161,33 @@ impl From<&ServiceManager> for ServiceId {
     }
 }
 
+/// Short form for logs, e.g. `evm:8453:0xd9fc...81f3`
+impl std::fmt::Display for ServiceManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let address = match self {
+            ServiceManager::Evm { address, .. } => address.to_string(),
+            ServiceManager::Cosmos { address, .. } => address.to_string(),
+        };
+        // keep the `0x` or bech32 prefix and a few characters either side
+        let prefix_len = match address.rfind('1') {
+            Some(separator) if matches!(self, ServiceManager::Cosmos { .. }) => separator + 1,
+            _ => 2,
+        } + 4;
+
+        if address.len() > prefix_len + 7 {
+            write!(
+                f,
+                "{}:{}...{}",
+                self.chain(),
+                &address[..prefix_len],
+                &address[address.len() - 4..]
+            )
+        } else {
+            write!(f, "{}:{}", self.chain(), address)
+        }
+    }
+}
+
 impl ServiceManager {
     pub fn chain(&self) -> &ChainKey {
         match self {
@@ -164,6 +217,12 @@ impl Service {
             trigger,
             component: Component::new(source),
             submit,
+            submit_targets: Vec::new(),
+            response_mapping: Default::default(),
+            submit_condition: None,
+            priority: Default::default(),
+            observe_only: false,
+            min_interval_ms: None,
         };
 
         let workflows = BTreeMap::from([(workflow_id, workflow)]);
@@ -173,6 +232,8 @@ impl Service {
             workflows,
             status: ServiceStatus::Active,
             manager,
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
         }
     }
 }
@@ -196,12 +257,23 @@ pub struct Component {
     /// If not supplied, default will be `Workflow::DEFAULT_TIME_LIMIT_SECONDS`
     pub time_limit_seconds: Option<u64>,
 
+    /// The maximum size linear memory may grow to during a single component execution, in bytes
+    /// If not supplied, memory is only bounded by the wasm address space
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_memory_bytes: Option<u64>,
+
     /// Key-value pairs that are accessible in the components via host bindings.
     pub config: BTreeMap<String, String>,
 
     /// External env variable keys to be read from the system host on execute (i.e. API keys).
     /// Must be prefixed with `WAVS_ENV_`.
     pub env_keys: BTreeSet<String>,
+
+    /// Names of the secrets this component may read with `host::get-secret`.
+    /// Values are held by each operator, never in the service definition.
+    /// A component granted any secrets gets no env variables, `env_keys` included
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub secrets: BTreeSet<String>,
 }
 
 #[cfg_attr(feature = "ts-bindings", derive(TS))]
@@ -222,6 +294,13 @@ pub enum ComponentSource {
         #[serde(flatten)]
         registry: Registry,
     },
+    /// The wasm layer of an OCI artifact, e.g. `ghcr.io/org/component:1.0.0`,
+    /// digest provided to ensure no tampering
+    Oci {
+        reference: String,
+        #[cfg_attr(feature = "ts-bindings", ts(type = "string"))]
+        digest: ComponentDigest,
+    },
     /// An already deployed component
     #[cfg_attr(feature = "ts-bindings", ts(type = "string"))]
     Digest(ComponentDigest),
@@ -251,6 +330,7 @@ impl ComponentSource {
         match self {
             ComponentSource::Download { digest, .. } => digest,
             ComponentSource::Registry { registry } => &registry.digest,
+            ComponentSource::Oci { digest, .. } => digest,
             ComponentSource::Digest(digest) => digest,
         }
     }
@@ -271,6 +351,32 @@ pub struct Workflow {
 
     /// How to submit the result of the component.
     pub submit: Submit,
+
+    /// Additional off-chain destinations for the signed result, delivered alongside `submit`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub submit_targets: Vec<SubmitTarget>,
+
+    /// How multiple component responses are split across `submit_targets`
+    #[serde(default, skip_serializing_if = "ResponseMapping::is_default")]
+    pub response_mapping: ResponseMapping,
+
+    /// Optional on-chain check made before broadcasting, to skip results the target contract already reflects
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submit_condition: Option<SubmitCondition>,
+
+    /// Share of execution capacity this workflow gets when the node is busy
+    #[serde(default, skip_serializing_if = "WorkflowPriority::is_default")]
+    pub priority: WorkflowPriority,
+
+    /// Run and aggregate as usual, but only record what would have been submitted instead of broadcasting it.
+    /// Useful for shadow testing a new component against live triggers
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub observe_only: bool,
+
+    /// Minimum time in milliseconds between two triggers of this workflow.
+    /// Triggers arriving sooner than this after the last one that fired are dropped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_interval_ms: Option<u64>,
 }
 
 impl Workflow {
@@ -291,6 +397,11 @@ pub enum Trigger {
         address: layer_climb_address::CosmosAddr,
         chain: ChainKey,
         event_type: String,
+        /// Only fire for events carrying all of these attributes with exactly these values,
+        /// e.g. `action = swap`. Events are matched on type alone if empty
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        #[cfg_attr(feature = "ts-bindings", ts(type = "Record<string, string>", optional))]
+        attributes: BTreeMap<String, String>,
     },
     EvmContractEvent {
         #[schema(value_type = String)]
@@ -299,6 +410,18 @@ pub enum Trigger {
         chain: ChainKey,
         #[cfg_attr(feature = "ts-bindings", ts(type = "string"))]
         event_hash: ByteArray<32>,
+        /// Other events from the same contract that also fire this trigger.
+        /// Components can tell which one matched from the first log topic
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        #[cfg_attr(feature = "ts-bindings", ts(type = "string[]", optional))]
+        additional_event_hashes: Vec<ByteArray<32>>,
+        /// Fields to hand the component instead of the whole log data, concatenated in order,
+        /// e.g. `["topics[1].address", "data[0]"]`. Topics and the rest of the log are passed
+        /// as is. See [`EvmLogField`] for the supported paths
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        #[schema(value_type = Vec<String>)]
+        #[cfg_attr(feature = "ts-bindings", ts(type = "string[]", optional))]
+        transform: Vec<EvmLogField>,
     },
     BlockInterval {
         /// The chain to use for the block interval
@@ -338,10 +461,63 @@ pub enum Trigger {
         /// Feed key to filter on.
         feed_key: String,
     },
+    /// Fired by a `POST /trigger/{service_id}/{workflow_id}` to the node
+    Webhook {
+        /// Name of a secret in the operator's secrets file. If set, requests must carry
+        /// `X-Wavs-Signature: sha256=<hex HMAC-SHA256 of the body>` keyed with it
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        signature_secret: Option<String>,
+    },
     // not a real trigger, just for testing
     Manual,
 }
 
+impl Trigger {
+    /// The same names as [`TriggerData::trigger_type`] uses for the data each kind fires with
+    pub fn trigger_type(&self) -> &str {
+        match self {
+            Trigger::CosmosContractEvent { .. } => "cosmos_contract_event",
+            Trigger::EvmContractEvent { .. } => "evm_contract_event",
+            Trigger::BlockInterval { .. } => "block_interval",
+            Trigger::Cron { .. } => "cron",
+            Trigger::AtProtoEvent { .. } => "atproto_event",
+            Trigger::HypercoreAppend { .. } => "hypercore_append",
+            Trigger::Webhook { .. } => "webhook",
+            Trigger::Manual => "manual",
+        }
+    }
+
+    /// Every event hash an `EvmContractEvent` trigger matches, empty for other triggers
+    pub fn evm_event_hashes(&self) -> Vec<ByteArray<32>> {
+        match self {
+            Trigger::EvmContractEvent {
+                event_hash,
+                additional_event_hashes,
+                ..
+            } => std::iter::once(*event_hash)
+                .chain(additional_event_hashes.iter().copied())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Whether the event carries every attribute a `CosmosContractEvent` trigger filters on,
+    /// always `true` for other triggers
+    pub fn matches_cosmos_attributes(&self, event: &cosmwasm_std::Event) -> bool {
+        match self {
+            Trigger::CosmosContractEvent { attributes, .. } => {
+                attributes.iter().all(|(key, value)| {
+                    event
+                        .attributes
+                        .iter()
+                        .any(|attr| attr.key == *key && attr.value == *value)
+                })
+            }
+            _ => true,
+        }
+    }
+}
+
 /// The data that came from the trigger and is passed to the component after being converted into the WIT-friendly type
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, ToSchema)]
 pub enum TriggerData {
@@ -391,10 +567,17 @@ pub enum TriggerData {
         chain: ChainKey,
         /// The block height where the event was emitted
         block_height: u64,
+        /// The trigger reached its end block and is being removed, so this is the last one
+        #[serde(default)]
+        is_final: bool,
     },
     Cron {
-        /// The trigger time
+        /// The scheduled tick time, which may be a little before it actually fired
         trigger_time: Timestamp,
+        /// How many scheduled ticks before this one were skipped because the node fell behind.
+        /// Only ticks missed while the node was running are counted
+        #[serde(default)]
+        missed_ticks: Option<u32>,
     },
     /// ATProto Jetstream event data
     AtProtoEvent {
@@ -427,6 +610,13 @@ pub enum TriggerData {
         /// Raw entry data
         data: Vec<u8>,
     },
+    /// A webhook posted to the node
+    Webhook {
+        /// Request headers by lowercased name, without credentials
+        headers: BTreeMap<String, String>,
+        /// Request body
+        body: Vec<u8>,
+    },
     Raw(Vec<u8>),
 }
 
@@ -449,6 +639,7 @@ impl TriggerData {
             TriggerData::Cron { .. } => "cron",
             TriggerData::AtProtoEvent { .. } => "atproto_event",
             TriggerData::HypercoreAppend { .. } => "hypercore_append",
+            TriggerData::Webhook { .. } => "webhook",
             TriggerData::Raw(_) => "manual",
         }
     }
@@ -461,6 +652,7 @@ impl TriggerData {
             TriggerData::Cron { .. }
             | TriggerData::AtProtoEvent { .. }
             | TriggerData::HypercoreAppend { .. }
+            | TriggerData::Webhook { .. }
             | TriggerData::Raw(_) => None,
         }
     }
@@ -500,9 +692,160 @@ pub enum Submit {
         /// component dynamically determines the destination
         component: Box<Component>,
         signature_kind: SignatureKind,
+        /// Destinations every result is submitted to as well as any the component returns,
+        /// for fanning the same result out to several chains. Each is tracked on its own,
+        /// so one failing doesn't hold back the others
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        targets: Vec<SubmitAction>,
+    },
+}
+
+/// A pre-submission check, evaluated by the aggregator right before broadcasting
+#[cfg_attr(feature = "ts-bindings", derive(TS))]
+#[cfg_attr(feature = "ts-bindings", ts(export))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmitCondition {
+    /// `eth_call` the function `selector(bytes20 eventId) returns (bool)`.
+    /// If it returns `true` the result is already on-chain and the submission is skipped.
+    EvmCall {
+        /// Contract to call, defaults to the address the aggregator is submitting to
+        #[schema(value_type = Option<String>)]
+        #[cfg_attr(feature = "ts-bindings", ts(type = "string | null"))]
+        address: Option<alloy_primitives::Address>,
+        #[cfg_attr(feature = "ts-bindings", ts(type = "string"))]
+        selector: ByteArray<4>,
     },
 }
 
+/// An off-chain destination for a workflow's signed result
+#[cfg_attr(feature = "ts-bindings", derive(TS))]
+#[cfg_attr(feature = "ts-bindings", ts(export))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmitTarget {
+    /// POST the signed submission as JSON to the given url
+    Http {
+        url: String,
+        auth: Option<SubmitTargetAuth>,
+    },
+}
+
+/// Credentials for a `SubmitTarget`, read from the host environment so they never live in the service definition.
+/// The env keys must be prefixed with `WAVS_ENV_`.
+#[cfg_attr(feature = "ts-bindings", derive(TS))]
+#[cfg_attr(feature = "ts-bindings", ts(export))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmitTargetAuth {
+    /// Sent as `Authorization: Bearer <value>`
+    Bearer { env_key: String },
+    /// Sent as `<name>: <value>`
+    Header { name: String, env_key: String },
+}
+
+/// How a component's responses are delivered to the workflow's `submit_targets`.
+///
+/// A single response always goes to every target. With several responses, response `i`
+/// goes to target `i` unless `BroadcastAll` is set. The `submit` destination is unaffected
+/// and always receives every response.
+#[cfg_attr(feature = "ts-bindings", derive(TS))]
+#[cfg_attr(feature = "ts-bindings", ts(export))]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseMapping {
+    /// Fail the execution if the number of responses doesn't match the number of targets
+    #[default]
+    Error,
+    /// Drop responses without a matching target, targets without a matching response get nothing
+    Truncate,
+    /// Deliver every response to every target
+    BroadcastAll,
+}
+
+/// How executions are ordered when more triggers are waiting than the node has execution slots.
+///
+/// Waiting executions are served by weighted round robin, so higher priorities get proportionally
+/// more of the slots while lower ones still make progress under sustained load
+#[cfg_attr(feature = "ts-bindings", derive(TS))]
+#[cfg_attr(feature = "ts-bindings", ts(export))]
+#[derive(
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkflowPriority {
+    /// Best-effort work such as batch jobs
+    Low,
+    #[default]
+    Normal,
+    /// Latency-critical work such as price feeds
+    High,
+}
+
+impl WorkflowPriority {
+    pub const ALL: [WorkflowPriority; 3] = [Self::High, Self::Normal, Self::Low];
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Slots handed out per round when every priority has executions waiting
+    pub fn weight(&self) -> u32 {
+        match self {
+            Self::Low => 1,
+            Self::Normal => 4,
+            Self::High => 16,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Normal => "normal",
+            Self::High => "high",
+        }
+    }
+}
+
+impl ResponseMapping {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether `responses` responses can be mapped onto `targets` targets
+    pub fn fits(&self, responses: usize, targets: usize) -> bool {
+        match self {
+            ResponseMapping::Error => responses <= 1 || targets == 0 || responses == targets,
+            ResponseMapping::Truncate | ResponseMapping::BroadcastAll => true,
+        }
+    }
+
+    /// The targets that response `index` (out of `responses`) should be delivered to
+    pub fn targets_for(
+        &self,
+        index: usize,
+        responses: usize,
+        targets: &[SubmitTarget],
+    ) -> Vec<SubmitTarget> {
+        if responses <= 1 || *self == ResponseMapping::BroadcastAll {
+            return targets.to_vec();
+        }
+
+        targets.get(index).cloned().into_iter().collect()
+    }
+}
+
 /// Defines the signature configuration for cryptographic operations in WAVS.
 ///
 /// This struct separates the cryptographic algorithm from the message formatting
@@ -619,6 +962,26 @@ fn permission_defaults() {
     assert!(!permissions_default.file_system);
 }
 
+#[test]
+fn allowed_host_matching() {
+    let allowed = AllowedHostPermission::Only(vec![
+        "postman-echo.com".to_string(),
+        "localhost:8080".to_string(),
+        "[::1]".to_string(),
+    ]);
+
+    assert!(allowed.allows("postman-echo.com", None));
+    assert!(allowed.allows("Postman-Echo.com", Some(443)));
+    assert!(allowed.allows("localhost", Some(8080)));
+    assert!(!allowed.allows("localhost", Some(8081)));
+    assert!(!allowed.allows("localhost", None));
+    assert!(allowed.allows("[::1]", Some(3000)));
+    assert!(!allowed.allows("echo.postman-echo.com", None));
+
+    assert!(AllowedHostPermission::All.allows("anywhere.com", None));
+    assert!(!AllowedHostPermission::None.allows("postman-echo.com", None));
+}
+
 // TODO: remove / change defaults?
 
 #[cfg_attr(feature = "ts-bindings", derive(TS))]
@@ -632,6 +995,28 @@ pub enum AllowedHostPermission {
     None,
 }
 
+impl AllowedHostPermission {
+    /// Whether a request to `host`, on `port` if the url names one, is allowed.
+    /// `Only` entries are either a hostname, allowing any port, or `host:port`
+    pub fn allows(&self, host: &str, port: Option<u16>) -> bool {
+        match self {
+            AllowedHostPermission::All => true,
+            AllowedHostPermission::None => false,
+            AllowedHostPermission::Only(hosts) => hosts.iter().any(|allowed| {
+                match allowed
+                    .rsplit_once(':')
+                    .and_then(|(host, port)| Some((host, port.parse::<u16>().ok()?)))
+                {
+                    Some((allowed_host, allowed_port)) => {
+                        port == Some(allowed_port) && allowed_host.eq_ignore_ascii_case(host)
+                    }
+                    None => allowed.eq_ignore_ascii_case(host),
+                }
+            }),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, ToSchema)]
 #[serde(default, rename_all = "snake_case")]
 #[derive(Default)]
@@ -664,8 +1049,10 @@ mod test_ext {
                 permissions: Default::default(),
                 fuel_limit: None,
                 time_limit_seconds: None,
+                max_memory_bytes: None,
                 config: BTreeMap::new(),
                 env_keys: BTreeSet::new(),
+                secrets: BTreeSet::new(),
             }
         }
     }
@@ -680,6 +1067,7 @@ mod test_ext {
                 address,
                 chain: chain.try_into().unwrap(),
                 event_type: event_type.to_string(),
+                attributes: BTreeMap::new(),
             }
         }
         pub fn evm_contract_event(
@@ -691,6 +1079,8 @@ mod test_ext {
                 address,
                 chain: chain.try_into().unwrap(),
                 event_hash,
+                additional_event_hashes: Vec::new(),
+                transform: Vec::new(),
             }
         }
     }
@@ -755,3 +1145,68 @@ mod test_ext {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{ResponseMapping, ServiceManager, SubmitTarget};
+
+    fn targets(n: usize) -> Vec<SubmitTarget> {
+        (0..n)
+            .map(|i| SubmitTarget::Http {
+                url: format!("http://localhost/{i}"),
+                auth: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn response_mapping() {
+        let targets = targets(2);
+
+        // a single response is never ambiguous
+        for mapping in [
+            ResponseMapping::Error,
+            ResponseMapping::Truncate,
+            ResponseMapping::BroadcastAll,
+        ] {
+            assert!(mapping.fits(1, 2));
+            assert_eq!(mapping.targets_for(0, 1, &targets), targets);
+        }
+
+        assert!(ResponseMapping::Error.fits(2, 2));
+        assert!(!ResponseMapping::Error.fits(3, 2));
+        assert!(ResponseMapping::Truncate.fits(3, 2));
+
+        assert_eq!(
+            ResponseMapping::Truncate.targets_for(1, 3, &targets),
+            vec![targets[1].clone()]
+        );
+        assert!(ResponseMapping::Truncate
+            .targets_for(2, 3, &targets)
+            .is_empty());
+        assert_eq!(
+            ResponseMapping::BroadcastAll.targets_for(2, 3, &targets),
+            targets
+        );
+    }
+
+    #[test]
+    fn service_manager_display() {
+        let evm = ServiceManager::Evm {
+            chain: "evm:8453".parse().unwrap(),
+            address: "0xd8da6bf26964af9d7eed9e03e53415d37aa96045"
+                .parse()
+                .unwrap(),
+        };
+        assert_eq!(evm.to_string(), "evm:8453:0xd8dA...6045");
+
+        let cosmos = ServiceManager::Cosmos {
+            chain: "cosmos:layer".parse().unwrap(),
+            address: layer_climb_address::CosmosAddr::new_unchecked(
+                "layer1hd63rr4lnmnwlvp3lhs4qlhxyw2ukftfr6jyvg".to_string(),
+                "layer".len(),
+            ),
+        };
+        assert_eq!(cosmos.to_string(), "cosmos:layer:layer1hd63...jyvg");
+    }
+}
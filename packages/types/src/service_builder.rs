@@ -4,7 +4,10 @@ use std::collections::BTreeMap;
 #[cfg(feature = "ts-bindings")]
 use ts_rs::TS;
 
-use crate::{Component, ServiceManager, ServiceStatus, SignatureKind, Submit, Trigger, WorkflowId};
+use crate::{
+    Component, ResponseMapping, ServiceManager, ServiceStatus, SignatureKind, Submit, SubmitAction,
+    SubmitCondition, SubmitTarget, Trigger, WorkflowId, WorkflowPriority,
+};
 
 /// This struct is only used in the CLI to build up a service for later uploading.
 #[cfg_attr(feature = "ts-bindings", derive(TS))]
@@ -26,6 +29,18 @@ pub struct WorkflowBuilder {
     pub trigger: TriggerBuilder,
     pub component: ComponentBuilder,
     pub submit: SubmitBuilder,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub submit_targets: Vec<SubmitTarget>,
+    #[serde(default, skip_serializing_if = "ResponseMapping::is_default")]
+    pub response_mapping: ResponseMapping,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submit_condition: Option<SubmitCondition>,
+    #[serde(default, skip_serializing_if = "WorkflowPriority::is_default")]
+    pub priority: WorkflowPriority,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub observe_only: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_interval_ms: Option<u64>,
 }
 
 #[cfg_attr(feature = "ts-bindings", derive(TS))]
@@ -62,6 +77,8 @@ pub enum AggregatorBuilder {
     Aggregator {
         component: ComponentBuilder,
         signature_kind: SignatureKind,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        targets: Vec<SubmitAction>,
     },
 }
 
@@ -154,6 +154,14 @@ impl EventId {
                     )?;
                     hasher.update(encoded);
                 }
+                // Missed ticks depend on how busy each node was, the scheduled time alone identifies the tick
+                TriggerData::Cron { trigger_time, .. } => {
+                    hasher.update(bincode::serde::encode_to_vec(trigger_time, bincode_config)?);
+                }
+                // Each node is posted to separately, with proxy and client headers that can differ
+                TriggerData::Webhook { body, .. } => {
+                    hasher.update(bincode::serde::encode_to_vec(body, bincode_config)?);
+                }
                 _ => hasher.update(bincode::serde::encode_to_vec(trigger_data, bincode_config)?),
             },
         }
@@ -303,4 +311,28 @@ mod tests {
 
         assert_eq!(id1, id2);
     }
+
+    #[test]
+    fn webhook_event_id_ignores_headers() {
+        let service_id = ServiceId::hash(b"service");
+        let workflow_id = WorkflowId::new("flow1").unwrap();
+
+        let webhook = |headers: &[(&str, &str)], body: &[u8]| TriggerData::Webhook {
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            body: body.to_vec(),
+        };
+        let event_id = |data: &TriggerData| {
+            EventId::new(&service_id, &workflow_id, EventIdSalt::Trigger(data)).unwrap()
+        };
+
+        let id1 = event_id(&webhook(&[("x-forwarded-for", "10.0.0.1")], b"{}"));
+        let id2 = event_id(&webhook(&[("x-forwarded-for", "10.0.0.2")], b"{}"));
+        let id3 = event_id(&webhook(&[("x-forwarded-for", "10.0.0.1")], b"{\"a\":1}"));
+
+        assert_eq!(id1, id2);
+        assert_ne!(id1, id3);
+    }
 }
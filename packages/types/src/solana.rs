@@ -0,0 +1,105 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use utoipa::ToSchema;
+
+/// A Solana account, program id, or any other ed25519 public key.
+/// Shown and parsed as base58, the way Solana tooling prints them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, ToSchema)]
+#[schema(value_type = String)]
+pub struct SolanaAddress([u8; 32]);
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum SolanaAddressError {
+    #[error("Invalid base58: {0}")]
+    Base58(String),
+    #[error("Solana address must be 32 bytes, got {0}")]
+    Length(usize),
+}
+
+impl SolanaAddress {
+    pub fn new(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl TryFrom<&[u8]> for SolanaAddress {
+    type Error = SolanaAddressError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        bytes
+            .try_into()
+            .map(Self)
+            .map_err(|_| SolanaAddressError::Length(bytes.len()))
+    }
+}
+
+impl FromStr for SolanaAddress {
+    type Err = SolanaAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = bs58::decode(s)
+            .into_vec()
+            .map_err(|err| SolanaAddressError::Base58(err.to_string()))?;
+
+        bytes.as_slice().try_into()
+    }
+}
+
+impl fmt::Display for SolanaAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", bs58::encode(self.0).into_string())
+    }
+}
+
+impl Serialize for SolanaAddress {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for SolanaAddress {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn base58_round_trip() {
+        // the system program is all zeroes
+        let system_program: SolanaAddress = "11111111111111111111111111111111".parse().unwrap();
+        assert_eq!(system_program, SolanaAddress::new([0; 32]));
+        assert_eq!(
+            system_program.to_string(),
+            "11111111111111111111111111111111"
+        );
+
+        let token_program = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+        let address: SolanaAddress = token_program.parse().unwrap();
+        assert_eq!(address.to_string(), token_program);
+        assert_eq!(
+            serde_json::from_value::<SolanaAddress>(serde_json::to_value(address).unwrap())
+                .unwrap(),
+            address
+        );
+
+        assert_eq!(
+            "3yZe7d".parse::<SolanaAddress>(),
+            Err(SolanaAddressError::Length(4))
+        );
+        assert!(matches!(
+            "not-base58!".parse::<SolanaAddress>(),
+            Err(SolanaAddressError::Base58(_))
+        ));
+    }
+}
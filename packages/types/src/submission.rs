@@ -1,15 +1,23 @@
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
-use crate::{Envelope, EventId, ServiceId, TriggerAction, WasmResponse, WavsSignature, WorkflowId};
+use crate::{
+    Envelope, EventId, ServiceId, SubmitTarget, TriggerAction, WasmResponse, WavsSignature,
+    WorkflowId,
+};
 
 // The data we send from submission to dispatcher
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct Submission {
     pub trigger_action: TriggerAction,
     pub operator_response: WasmResponse,
     pub event_id: EventId,
     pub envelope: Envelope,
     pub envelope_signature: WavsSignature,
+    /// Off-chain targets for this response, as mapped from the workflow's `submit_targets`.
+    /// The aggregator posts the combined result to them once it's on-chain
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub submit_targets: Vec<SubmitTarget>,
 }
 
 impl Submission {
@@ -29,3 +37,172 @@ impl Submission {
         &self.trigger_action.config.workflow_id
     }
 }
+
+/// What a workflow's `submit_targets` receive: the envelope, with every operator signature
+/// collected for it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SignedResult {
+    pub service_id: ServiceId,
+    pub workflow_id: WorkflowId,
+    pub event_id: EventId,
+    pub envelope: Envelope,
+    pub signatures: Vec<ResultSignature>,
+    /// The transaction that put the result on-chain, `None` for workflows without an aggregator
+    pub tx_hash: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ResultSignature {
+    pub signer: alloy_primitives::Address,
+    pub signature: WavsSignature,
+}
+
+impl SignedResult {
+    pub fn new(
+        submission: &Submission,
+        signatures: Vec<ResultSignature>,
+        tx_hash: Option<String>,
+    ) -> Self {
+        Self {
+            service_id: submission.service_id().clone(),
+            workflow_id: submission.workflow_id().clone(),
+            event_id: submission.event_id.clone(),
+            envelope: submission.envelope.clone(),
+            signatures,
+            tx_hash,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        format!(
+            "SignedResult(service_id: {}, workflow_id: {}, event_id: {}, signatures: {})",
+            self.service_id,
+            self.workflow_id,
+            self.event_id,
+            self.signatures.len()
+        )
+    }
+}
+
+/// Version byte leading every submission written by [`Submission::to_bytes`].
+/// Bump it whenever the encoding after it changes, and keep decoding the older ones
+pub const SUBMISSION_WIRE_VERSION: u8 = 1;
+
+#[derive(Debug, Error)]
+pub enum SubmissionWireError {
+    #[error("Empty submission bytes")]
+    Empty,
+
+    #[error("Unsupported submission wire version {0}, this node understands up to {SUBMISSION_WIRE_VERSION}")]
+    UnsupportedVersion(u8),
+
+    #[error("Invalid submission (wire version {version}): {source}")]
+    Decode {
+        version: u8,
+        source: serde_json::Error,
+    },
+
+    #[error("Unable to encode submission: {0}")]
+    Encode(serde_json::Error),
+}
+
+impl Submission {
+    /// The versioned wire format nodes exchange submissions in
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SubmissionWireError> {
+        let mut bytes = vec![SUBMISSION_WIRE_VERSION];
+        serde_json::to_writer(&mut bytes, self).map_err(SubmissionWireError::Encode)?;
+        Ok(bytes)
+    }
+
+    /// Reads any wire version up to [`SUBMISSION_WIRE_VERSION`], as well as the unversioned
+    /// JSON nodes sent before there was a version byte.
+    /// That always starts with `{`, which is well past any version we'll get to
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SubmissionWireError> {
+        let (version, body) = match bytes.split_first() {
+            None => return Err(SubmissionWireError::Empty),
+            Some((b'{', _)) => (0, bytes),
+            Some((&version, body)) => (version, body),
+        };
+
+        match version {
+            0 | 1 => serde_json::from_slice(body)
+                .map_err(|source| SubmissionWireError::Decode { version, source }),
+            _ => Err(SubmissionWireError::UnsupportedVersion(version)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloy_primitives::FixedBytes;
+
+    use super::*;
+    use crate::{ServiceId, SignatureKind, Trigger, TriggerConfig, TriggerData, WorkflowId};
+
+    /// [`submission`] as gossiped by nodes before the wire format was versioned, captured from
+    /// `serde_json::to_vec` with the wavs-types of that release (there was no `submit_targets` yet)
+    const UNVERSIONED_SUBMISSION: &str = r#"{"trigger_action":{"config":{"service_id":"0101010101010101010101010101010101010101010101010101010101010101","workflow_id":"default","trigger":"manual"},"data":{"Raw":[104,105]}},"operator_response":{"payload":"0x6869","ordering":null,"event_id_salt":null},"event_id":"0x0202020202020202020202020202020202020202","envelope":{"eventId":"0x0202020202020202020202020202020202020202","ordering":"0x000000000000000000000000","payload":"0x6869"},"envelope_signature":{"data":[3,3,3],"kind":{"algorithm":"secp256k1","prefix":"eip191"}}}"#;
+
+    fn submission() -> Submission {
+        Submission {
+            trigger_action: TriggerAction {
+                config: TriggerConfig {
+                    service_id: ServiceId::from([1; 32]),
+                    workflow_id: WorkflowId::default(),
+                    trigger: Trigger::Manual,
+                },
+                data: TriggerData::Raw(b"hi".to_vec()),
+            },
+            operator_response: WasmResponse {
+                payload: b"hi".to_vec(),
+                ordering: None,
+                event_id_salt: None,
+            },
+            event_id: EventId::from([2; 20]),
+            envelope: Envelope {
+                eventId: FixedBytes::new([2; 20]),
+                ordering: Default::default(),
+                payload: b"hi".to_vec().into(),
+            },
+            envelope_signature: WavsSignature {
+                data: vec![3; 3],
+                kind: SignatureKind::evm_default(),
+            },
+            submit_targets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let bytes = submission().to_bytes().unwrap();
+        assert_eq!(bytes[0], SUBMISSION_WIRE_VERSION);
+        assert_eq!(Submission::from_bytes(&bytes).unwrap(), submission());
+    }
+
+    #[test]
+    fn reads_unversioned_submissions() {
+        assert_eq!(
+            Submission::from_bytes(UNVERSIONED_SUBMISSION.as_bytes()).unwrap(),
+            submission()
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        let mut bytes = submission().to_bytes().unwrap();
+        bytes[0] = SUBMISSION_WIRE_VERSION + 1;
+        assert!(matches!(
+            Submission::from_bytes(&bytes),
+            Err(SubmissionWireError::UnsupportedVersion(v)) if v == SUBMISSION_WIRE_VERSION + 1
+        ));
+
+        assert!(matches!(
+            Submission::from_bytes(&[]),
+            Err(SubmissionWireError::Empty)
+        ));
+        assert!(matches!(
+            Submission::from_bytes(&[SUBMISSION_WIRE_VERSION, b'{']),
+            Err(SubmissionWireError::Decode { version: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,216 @@
+use std::{fmt, str::FromStr};
+
+use alloy_primitives::{Address, LogData};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ts-bindings")]
+use ts_rs::TS;
+
+/// A field picked out of an EVM log, written as a path:
+///
+/// - `address`: the contract that emitted the log, 20 bytes
+/// - `topics[i]`: topic `i`, 32 bytes. `topics[0]` is the event signature, indexed params follow
+/// - `data`: the whole log data
+/// - `data[i]`: the `i`th 32-byte word of the data, i.e. the `i`th static non-indexed param
+/// - `data[start..end]`: bytes `start` up to `end` of the data
+///
+/// Any of them can end in `.address` to keep only the last 20 bytes, for addresses padded
+/// to a word, e.g. `topics[1].address` for the `from` of an ERC-20 `Transfer`.
+#[cfg_attr(feature = "ts-bindings", derive(TS))]
+#[cfg_attr(feature = "ts-bindings", ts(export, type = "string"))]
+#[derive(Hash, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub struct EvmLogField {
+    pub selector: EvmLogSelector,
+    /// Keep only the last 20 bytes of the selected value
+    pub as_address: bool,
+}
+
+#[derive(Hash, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EvmLogSelector {
+    Address,
+    Topic(usize),
+    Data,
+    DataWord(usize),
+    DataRange(usize, usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EvmLogFieldError {
+    #[error("Invalid log field '{0}', expected address, topics[i], data, data[i] or data[start..end], optionally followed by .address")]
+    Parse(String),
+    #[error("Log has no {0}")]
+    Missing(EvmLogField),
+}
+
+impl EvmLogField {
+    /// The field's bytes, failing if the log is too short to have it
+    pub fn extract(
+        &self,
+        address: Address,
+        log_data: &LogData,
+    ) -> Result<Vec<u8>, EvmLogFieldError> {
+        let data = log_data.data.as_ref();
+        let value: Option<&[u8]> = match self.selector {
+            EvmLogSelector::Address => Some(address.as_slice()),
+            EvmLogSelector::Topic(index) => {
+                log_data.topics().get(index).map(|topic| topic.as_slice())
+            }
+            EvmLogSelector::Data => Some(data),
+            EvmLogSelector::DataWord(index) => index
+                .checked_mul(32)
+                .and_then(|start| data.get(start..start.checked_add(32)?)),
+            EvmLogSelector::DataRange(start, end) => data.get(start..end),
+        };
+
+        match value {
+            Some(value) if !self.as_address => Ok(value.to_vec()),
+            Some(value) if value.len() >= 20 => Ok(value[value.len() - 20..].to_vec()),
+            _ => Err(EvmLogFieldError::Missing(self.clone())),
+        }
+    }
+}
+
+/// Concatenates the fields in order, the payload an `EvmContractEvent` trigger with a `transform`
+/// hands to its component in place of the log data
+pub fn extract_evm_log_fields(
+    fields: &[EvmLogField],
+    address: Address,
+    log_data: &LogData,
+) -> Result<Vec<u8>, EvmLogFieldError> {
+    let mut payload = Vec::new();
+    for field in fields {
+        payload.extend(field.extract(address, log_data)?);
+    }
+    Ok(payload)
+}
+
+impl FromStr for EvmLogField {
+    type Err = EvmLogFieldError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || EvmLogFieldError::Parse(s.to_string());
+
+        let (path, as_address) = match s.trim().strip_suffix(".address") {
+            Some(path) => (path, true),
+            None => (s.trim(), false),
+        };
+
+        let (name, index) = match path.split_once('[') {
+            Some((name, rest)) => (name, Some(rest.strip_suffix(']').ok_or_else(invalid)?)),
+            None => (path, None),
+        };
+        let parse_usize = |value: &str| value.trim().parse::<usize>().map_err(|_| invalid());
+
+        let selector = match (name, index) {
+            ("address", None) => EvmLogSelector::Address,
+            ("topics", Some(index)) => EvmLogSelector::Topic(parse_usize(index)?),
+            ("data", None) => EvmLogSelector::Data,
+            ("data", Some(index)) => match index.split_once("..") {
+                Some((start, end)) => {
+                    let (start, end) = (parse_usize(start)?, parse_usize(end)?);
+                    if start > end {
+                        return Err(invalid());
+                    }
+                    EvmLogSelector::DataRange(start, end)
+                }
+                None => EvmLogSelector::DataWord(parse_usize(index)?),
+            },
+            _ => return Err(invalid()),
+        };
+
+        Ok(Self {
+            selector,
+            as_address,
+        })
+    }
+}
+
+impl fmt::Display for EvmLogField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.selector {
+            EvmLogSelector::Address => write!(f, "address")?,
+            EvmLogSelector::Topic(index) => write!(f, "topics[{index}]")?,
+            EvmLogSelector::Data => write!(f, "data")?,
+            EvmLogSelector::DataWord(index) => write!(f, "data[{index}]")?,
+            EvmLogSelector::DataRange(start, end) => write!(f, "data[{start}..{end}]")?,
+        }
+        if self.as_address {
+            write!(f, ".address")?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<String> for EvmLogField {
+    type Error = EvmLogFieldError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<EvmLogField> for String {
+    fn from(value: EvmLogField) -> Self {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloy_primitives::B256;
+
+    use super::*;
+
+    #[test]
+    fn paths_round_trip() {
+        for path in [
+            "address",
+            "topics[0]",
+            "topics[2].address",
+            "data",
+            "data[1]",
+            "data[4..36]",
+        ] {
+            let field: EvmLogField = path.parse().unwrap();
+            assert_eq!(field.to_string(), path);
+        }
+
+        for path in ["topics", "topics[x]", "data[4..2]", "data[1", "block", ""] {
+            assert!(
+                path.parse::<EvmLogField>().is_err(),
+                "{path} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn extracts_indexed_topic() {
+        let contract = Address::repeat_byte(0xaa);
+        let from = Address::repeat_byte(0x11);
+        let amount = B256::with_last_byte(42);
+
+        // Transfer(address indexed from, address indexed to, uint256 amount)
+        let log_data = LogData::new_unchecked(
+            vec![B256::repeat_byte(0xdd), from.into_word(), B256::ZERO],
+            amount.to_vec().into(),
+        );
+
+        let fields: Vec<EvmLogField> = ["topics[1].address", "data[0]"]
+            .into_iter()
+            .map(|path| path.parse().unwrap())
+            .collect();
+        let payload = extract_evm_log_fields(&fields, contract, &log_data).unwrap();
+        assert_eq!(payload, [from.as_slice(), amount.as_slice()].concat());
+
+        let missing: EvmLogField = "topics[3]".parse().unwrap();
+        assert_eq!(
+            missing.extract(contract, &log_data),
+            Err(EvmLogFieldError::Missing(missing.clone()))
+        );
+        assert!("data[1]"
+            .parse::<EvmLogField>()
+            .unwrap()
+            .extract(contract, &log_data)
+            .is_err());
+    }
+}
@@ -18,6 +18,10 @@
 //! * **Optional cleanup** – disabled by default; if enabled, we prune a key when
 //!   its mutex’s last `Arc` drops (requires a write‑lock).
 //!
+//! * **Retries** – [`AsyncTransaction::run_with_retry`] repeats a fallible
+//!   transaction with exponential backoff and jitter, as long as its error is
+//!   [`Retryable`].  The key's lock is released while backing off.
+//!
 //! ## Example
 //! ```ignore
 //! let exec = TransactionExecutor::new(false); // no cleanup
@@ -26,12 +30,66 @@
 //! }).await;
 //! ```
 
+use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
 use std::future::Future;
-use std::hash::Hash;
+use std::hash::{BuildHasher, Hash};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{Mutex, RwLock};
 
+/// Errors that may clear up if the transaction is simply tried again.
+pub trait Retryable {
+    fn is_retryable(&self) -> bool;
+}
+
+/// How often, and how far apart, [`AsyncTransaction::run_with_retry`] tries a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first one.  `0` is treated as `1`.
+    pub max_attempts: u32,
+    /// Backoff before the second attempt, doubled for every attempt after that.
+    pub base_delay: Duration,
+    /// Upper bound on the backoff between two attempts.
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Try once, never retry.
+    pub const NONE: Self = Self {
+        max_attempts: 1,
+        base_delay: Duration::ZERO,
+        max_delay: Duration::ZERO,
+    };
+
+    pub fn new(max_attempts: u32, base_delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            base_delay,
+            max_delay: base_delay.saturating_mul(32),
+        }
+    }
+
+    /// Backoff after the given failed attempt (1-based), somewhere between half and
+    /// all of the exponential delay so concurrent retries don't line up.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(1u32 << attempt.saturating_sub(1).min(16))
+            .min(self.max_delay);
+        let half = exponential / 2;
+        let jitter = RandomState::new().hash_one(attempt) % (half.as_nanos() as u64 + 1);
+
+        half + Duration::from_nanos(jitter)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3, Duration::from_millis(500))
+    }
+}
+
 /// An async executor that guarantees **one transaction at a time per key**.
 #[derive(Clone)]
 pub struct AsyncTransaction<K: Eq + Hash + Clone> {
@@ -89,6 +147,41 @@ impl<K: Eq + Hash + Clone> AsyncTransaction<K> {
         out
     }
 
+    /// Run `f` as an exclusive transaction for `key`, trying again with backoff
+    /// while it fails with a [`Retryable`] error and `policy` has attempts left.
+    pub async fn run_with_retry<F, Fut, T, E>(
+        &self,
+        key: K,
+        policy: &RetryPolicy,
+        mut f: F,
+    ) -> Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Retryable + std::fmt::Display,
+    {
+        let max_attempts = policy.max_attempts.max(1);
+        let mut attempt = 1;
+
+        loop {
+            match self.run(key.clone(), &mut f).await {
+                Err(err) if attempt < max_attempts && err.is_retryable() => {
+                    let delay = policy.delay(attempt);
+                    tracing::warn!(
+                        "Transaction failed (attempt {}/{}), retrying in {:?}: {}",
+                        attempt,
+                        max_attempts,
+                        delay,
+                        err
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
     /// Explicitly remove a key's mutex from the registry.
     /// Returns `true` if a lock was removed. In‑flight transactions are safe.
     pub async fn remove_key(&self, key: &K) -> bool {
@@ -242,6 +335,86 @@ mod tests {
         );
     }
 
+    #[derive(Debug)]
+    enum MockError {
+        Timeout,
+        Revert,
+    }
+
+    impl std::fmt::Display for MockError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{self:?}")
+        }
+    }
+
+    impl Retryable for MockError {
+        fn is_retryable(&self) -> bool {
+            matches!(self, MockError::Timeout)
+        }
+    }
+
+    #[tokio::test]
+    async fn retry_transient_errors() {
+        let exe = AsyncTransaction::new(false);
+        let policy = RetryPolicy::new(5, std::time::Duration::from_millis(1));
+        let attempts = AtomicUsize::new(0);
+
+        // fails twice, then succeeds
+        let result = exe
+            .run_with_retry("chain", &policy, || async {
+                match attempts.fetch_add(1, Ordering::SeqCst) {
+                    0 | 1 => Err(MockError::Timeout),
+                    n => Ok(n),
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn retry_gives_up() {
+        let exe = AsyncTransaction::new(false);
+        let policy = RetryPolicy::new(5, std::time::Duration::from_millis(1));
+
+        // a revert fails immediately
+        let attempts = AtomicUsize::new(0);
+        let result: Result<(), _> = exe
+            .run_with_retry("chain", &policy, || async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(MockError::Revert)
+            })
+            .await;
+        assert!(matches!(result, Err(MockError::Revert)));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        // transient errors stop at max_attempts
+        let attempts = AtomicUsize::new(0);
+        let result: Result<(), _> = exe
+            .run_with_retry("chain", &policy, || async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(MockError::Timeout)
+            })
+            .await;
+        assert!(matches!(result, Err(MockError::Timeout)));
+        assert_eq!(attempts.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn retry_delay_grows_with_jitter() {
+        let policy = RetryPolicy::new(10, std::time::Duration::from_millis(100));
+
+        for attempt in 1..=4 {
+            let full = std::time::Duration::from_millis(100 * (1 << (attempt - 1)));
+            let delay = policy.delay(attempt);
+            assert!(delay >= full / 2 && delay <= full, "{attempt}: {delay:?}");
+        }
+
+        // capped at max_delay
+        assert!(policy.delay(20) <= policy.max_delay);
+    }
+
     #[tokio::test]
     async fn remove_key() {
         let exe = AsyncTransaction::new(false);
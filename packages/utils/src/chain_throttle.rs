@@ -0,0 +1,91 @@
+//! Caps the number of concurrent RPC-heavy operations per chain.
+//!
+//! Bursts of triggers can fan out into many simultaneous lookups, gas estimations
+//! and submissions against the same endpoint, which public providers quickly rate-limit.
+//! Each chain with a configured limit gets its own semaphore; chains without one are
+//! never throttled.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use wavs_types::ChainKey;
+
+use crate::telemetry::ChainMetrics;
+
+/// Shared, cheaply cloneable per-chain concurrency limiter.
+#[derive(Clone)]
+pub struct ChainThrottle {
+    semaphores: Arc<HashMap<ChainKey, Arc<Semaphore>>>,
+    metrics: Option<ChainMetrics>,
+}
+
+impl ChainThrottle {
+    pub fn new(limits: &HashMap<ChainKey, usize>, metrics: Option<ChainMetrics>) -> Self {
+        let semaphores = limits
+            .iter()
+            .filter(|(_, limit)| **limit > 0)
+            .map(|(chain, limit)| (chain.clone(), Arc::new(Semaphore::new(*limit))))
+            .collect();
+
+        Self {
+            semaphores: Arc::new(semaphores),
+            metrics,
+        }
+    }
+
+    /// A throttle with no limits at all
+    pub fn unlimited() -> Self {
+        Self::new(&HashMap::new(), None)
+    }
+
+    /// Wait for a free slot on `chain`. The slot is released when the returned permit is dropped.
+    ///
+    /// Returns `None` immediately if the chain has no configured limit.
+    pub async fn acquire(&self, chain: &ChainKey) -> Option<OwnedSemaphorePermit> {
+        let semaphore = self.semaphores.get(chain)?.clone();
+
+        match semaphore.clone().try_acquire_owned() {
+            Ok(permit) => Some(permit),
+            Err(_) => {
+                if let Some(metrics) = &self.metrics {
+                    metrics.increment_rpc_throttled(chain);
+                }
+                tracing::debug!(
+                    "RPC operations on {} are throttled, waiting for a slot",
+                    chain
+                );
+                // the semaphore is never closed, so this only fails if that changes
+                semaphore.acquire_owned().await.ok()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn limits_in_flight_per_chain() {
+        let limited: ChainKey = "evm:1".parse().unwrap();
+        let unlimited: ChainKey = "evm:2".parse().unwrap();
+
+        let throttle = ChainThrottle::new(&HashMap::from([(limited.clone(), 1)]), None);
+
+        let permit = throttle.acquire(&limited).await;
+        assert!(permit.is_some());
+
+        // unconfigured chains never wait
+        assert!(throttle.acquire(&unlimited).await.is_none());
+
+        // a second slot on the limited chain waits until the first is released
+        let waiting = tokio::time::timeout(Duration::from_millis(50), throttle.acquire(&limited));
+        assert!(waiting.await.is_err());
+
+        drop(permit);
+        let waiting = tokio::time::timeout(Duration::from_millis(50), throttle.acquire(&limited));
+        assert!(waiting.await.unwrap().is_some());
+    }
+}
@@ -1,7 +1,12 @@
+pub mod interpolate;
+
 use anyhow::{bail, Context, Result};
 use figment::{providers::Format, Figment};
 use serde::{de::DeserializeOwned, Serialize};
-use std::{marker::PhantomData, path::PathBuf};
+use std::{
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
 use wavs_types::{Credential, EvmChainConfig};
 
 use crate::{
@@ -123,16 +128,18 @@ impl<CONFIG: ConfigExt, ARG: CliEnvExt> ConfigBuilder<CONFIG, ARG> {
             eprintln!("Loading config from {}", filepath.display());
         }
 
+        let config_toml = read_config_toml(&filepath)?;
+
         let figment = Figment::new()
             // Start with the default values as the base
             .merge(figment::providers::Serialized::defaults(CONFIG::default()))
             // Then add default section from TOML
             .merge(Figment::from(
-                figment::providers::Toml::file(&filepath).nested(),
+                figment::providers::Toml::string(&config_toml).nested(),
             ))
             // Then add specific section, overriding globals where needed
             .merge(
-                Figment::from(figment::providers::Toml::file(&filepath).nested())
+                Figment::from(figment::providers::Toml::string(&config_toml).nested())
                     .select(ARG::TOML_IDENTIFIER),
             )
             // Finally override with cli/env args
@@ -157,6 +164,24 @@ impl<CONFIG: ConfigExt, ARG: CliEnvExt> ConfigBuilder<CONFIG, ARG> {
     }
 }
 
+/// The config file with `${VAR}` references resolved, see [`interpolate`].
+/// A missing file is treated as empty, so defaults and cli/env args still apply
+fn read_config_toml(filepath: &Path) -> Result<String> {
+    if !filepath.exists() {
+        return Ok(String::new());
+    }
+
+    let contents = std::fs::read_to_string(filepath)
+        .context(format!("Error reading config file {}", filepath.display()))?;
+    let mut table: toml::Table = toml::from_str(&contents)
+        .context(format!("Error parsing config file {}", filepath.display()))?;
+
+    interpolate::interpolate_env(&mut table)
+        .context(format!("Error in config file {}", filepath.display()))?;
+
+    Ok(toml::to_string(&table)?)
+}
+
 // a helper to try a series of fallback paths, looking for a config file
 #[derive(Clone, Debug)]
 pub struct ConfigFilePath {
@@ -269,10 +294,21 @@ impl EvmChainConfigExt for EvmChainConfig {
         credential: Credential,
     ) -> std::result::Result<EvmSigningClientConfig, EvmClientError> {
         // TODO: https://github.com/Lay3rLabs/WAVS/issues/1019
-        let endpoint = match (self.ws_endpoints.is_empty(), self.http_endpoint.clone()) {
+        let http_endpoints = self
+            .all_http_endpoints()
+            .into_iter()
+            .map(EvmEndpoint::new_http)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let config = match (self.ws_endpoints.is_empty(), http_endpoints.split_first()) {
             // prefer HTTP for signing clients
-            (_, Some(url)) => EvmEndpoint::new_http(&url)?,
-            (false, _) => EvmEndpoint::new_ws(&self.ws_endpoints[0])?,
+            (_, Some((endpoint, fallbacks))) => {
+                EvmSigningClientConfig::new(endpoint.clone(), credential)
+                    .with_fallback_endpoints(fallbacks.to_vec())
+            }
+            (false, None) => {
+                EvmSigningClientConfig::new(EvmEndpoint::new_ws(&self.ws_endpoints[0])?, credential)
+            }
             _ => {
                 return Err(EvmClientError::ParseEndpoint(
                     "No endpoint provided".to_string(),
@@ -280,8 +316,6 @@ impl EvmChainConfigExt for EvmChainConfig {
             }
         };
 
-        let config = EvmSigningClientConfig::new(endpoint, credential);
-
         Ok(config)
     }
 
@@ -293,7 +327,11 @@ impl EvmChainConfigExt for EvmChainConfig {
                 .iter()
                 .map(|url| EvmEndpoint::new_ws(url))
                 .collect(),
-            (_, Some(url)) => Ok(vec![EvmEndpoint::new_http(&url)?]),
+            (_, Some(_)) => self
+                .all_http_endpoints()
+                .into_iter()
+                .map(EvmEndpoint::new_http)
+                .collect(),
             _ => Err(EvmClientError::ParseEndpoint(
                 "No endpoint provided".to_string(),
             )),
@@ -307,8 +345,9 @@ mod test {
 
     use serde::{Deserialize, Serialize};
     use wavs_types::{
-        AnyChainConfig, ChainConfigError, ChainConfigs, ChainKey, CosmosChainConfig,
-        CosmosChainConfigBuilder, EvmChainConfig, EvmChainConfigBuilder,
+        AnyChainConfig, ChainConfigError, ChainConfigs, ChainKey, ChainKeyNamespace,
+        CosmosChainConfig, CosmosChainConfigBuilder, EvmChainConfig, EvmChainConfigBuilder,
+        SolanaCommitment,
     };
 
     use crate::{
@@ -789,6 +828,60 @@ mod test {
         assert_eq!(service2_config.data, PathBuf::from("/var/service2"));
     }
 
+    #[test]
+    fn solana_chain_configs_toml() {
+        let test_config = r#"
+            [solana.devnet]
+            rpc_endpoint = "https://api.devnet.solana.com"
+            ws_endpoint = "wss://api.devnet.solana.com"
+            commitment = "finalized"
+
+            [solana.mainnet-beta]
+            rpc_endpoint = "https://api.mainnet-beta.solana.com"
+
+            [dev.my-local-solana]
+            type = "solana"
+            chain_id = "localnet"
+            rpc_endpoint = "http://localhost:8899"
+        "#;
+
+        let chain_configs: ChainConfigs = toml::from_str(test_config).unwrap();
+
+        let devnet = chain_configs
+            .get_chain(&ChainKey::try_from("solana:devnet").unwrap())
+            .unwrap()
+            .to_solana_config()
+            .unwrap();
+        assert_eq!(devnet.chain_id.as_str(), "devnet");
+        assert_eq!(devnet.commitment, SolanaCommitment::Finalized);
+
+        // commitment defaults to confirmed
+        let mainnet = chain_configs
+            .get_chain(&ChainKey::try_from("solana:mainnet-beta").unwrap())
+            .unwrap()
+            .to_solana_config()
+            .unwrap();
+        assert_eq!(mainnet.commitment, SolanaCommitment::Confirmed);
+        assert_eq!(mainnet.ws_endpoint, None);
+
+        let local = chain_configs
+            .get_chain(&ChainKey::try_from("dev:my-local-solana").unwrap())
+            .unwrap();
+        assert!(local.to_evm_config().is_err());
+        assert_eq!(
+            local.to_solana_config().unwrap().chain_id.as_str(),
+            "localnet"
+        );
+
+        let mut chain_configs = chain_configs;
+        assert_eq!(
+            chain_configs.add_chain(ChainKey::try_from("evm:localnet").unwrap(), local),
+            Err(ChainConfigError::InvalidNamespaceForEvm(
+                ChainKeyNamespace::new("evm").unwrap()
+            ))
+        );
+    }
+
     #[test]
     fn chain_configs_toml() {
         let test_config = r#"
@@ -1148,6 +1241,7 @@ mod test {
                     EvmChainConfigBuilder {
                         ws_endpoints: vec!["ws://127.0.0.1:8546".to_string()],
                         http_endpoint: Some("http://127.0.0.1:8545".to_string()),
+                        http_endpoints: Vec::new(),
                         faucet_endpoint: Some("http://127.0.0.1:8000".to_string()),
                         ws_priority_endpoint_index: None,
                     },
@@ -1157,6 +1251,7 @@ mod test {
                     EvmChainConfigBuilder {
                         ws_endpoints: vec!["ws://127.0.0.1:8546".to_string()],
                         http_endpoint: Some("http://127.0.0.1:8545".to_string()),
+                        http_endpoints: Vec::new(),
                         faucet_endpoint: Some("http://127.0.0.1:8000".to_string()),
                         ws_priority_endpoint_index: None,
                     },
@@ -1164,6 +1259,7 @@ mod test {
             ]
             .into_iter()
             .collect(),
+            solana: BTreeMap::new(),
             dev: BTreeMap::new(),
         }
     }
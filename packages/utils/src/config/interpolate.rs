@@ -0,0 +1,205 @@
+//! `${VAR}` references in config files, resolved from the process environment at load time,
+//! so secrets like RPC urls with api keys can stay out of the file itself.
+//! `$${...}` is an escape for a literal `${...}`, any other `$` is left alone.
+//! Only string values are interpolated, keys are taken as written.
+
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum InterpolateError {
+    #[error("{path} references environment variable {var}, which is not set")]
+    MissingVar { path: String, var: String },
+
+    #[error("{path} has an unterminated ${{ in \"{value}\"")]
+    Unterminated { path: String, value: String },
+
+    #[error("{path} has an empty ${{}} in \"{value}\"")]
+    EmptyName { path: String, value: String },
+}
+
+/// Resolves every string in the table, recursing into nested tables and arrays
+pub fn interpolate_env(table: &mut toml::Table) -> Result<(), InterpolateError> {
+    interpolate_table(table, "", &|name| std::env::var(name).ok())
+}
+
+fn interpolate_table(
+    table: &mut toml::Table,
+    path: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<(), InterpolateError> {
+    for (key, value) in table.iter_mut() {
+        let path = match path {
+            "" => key.clone(),
+            _ => format!("{path}.{key}"),
+        };
+        interpolate_value(value, &path, lookup)?;
+    }
+    Ok(())
+}
+
+fn interpolate_value(
+    value: &mut toml::Value,
+    path: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<(), InterpolateError> {
+    match value {
+        toml::Value::String(s) => *s = interpolate_str(s, path, lookup)?,
+        toml::Value::Table(table) => interpolate_table(table, path, lookup)?,
+        toml::Value::Array(values) => {
+            for (index, value) in values.iter_mut().enumerate() {
+                interpolate_value(value, &format!("{path}[{index}]"), lookup)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn interpolate_str(
+    value: &str,
+    path: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<String, InterpolateError> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find('$') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if rest.starts_with("$${") {
+            // escaped, drop the first `$` and copy the rest through untouched
+            let end = rest
+                .find('}')
+                .ok_or_else(|| InterpolateError::Unterminated {
+                    path: path.to_string(),
+                    value: value.to_string(),
+                })?;
+            out.push_str(&rest[1..=end]);
+            rest = &rest[end + 1..];
+        } else if let Some(reference) = rest.strip_prefix("${") {
+            let end = reference
+                .find('}')
+                .ok_or_else(|| InterpolateError::Unterminated {
+                    path: path.to_string(),
+                    value: value.to_string(),
+                })?;
+            let var = reference[..end].trim();
+            if var.is_empty() {
+                return Err(InterpolateError::EmptyName {
+                    path: path.to_string(),
+                    value: value.to_string(),
+                });
+            }
+            let resolved = lookup(var).ok_or_else(|| InterpolateError::MissingVar {
+                path: path.to_string(),
+                var: var.to_string(),
+            })?;
+            out.push_str(&resolved);
+            rest = &reference[end + 1..];
+        } else {
+            out.push('$');
+            rest = &rest[1..];
+        }
+    }
+
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "BASE_RPC_URL" => Some("https://base.example.com/key".to_string()),
+            "PORT" => Some("8545".to_string()),
+            _ => None,
+        }
+    }
+
+    fn interpolate(toml_str: &str) -> Result<toml::Table, InterpolateError> {
+        let mut table: toml::Table = toml::from_str(toml_str).unwrap();
+        interpolate_table(&mut table, "", &lookup)?;
+        Ok(table)
+    }
+
+    #[test]
+    fn resolves_nested_values() {
+        let table = interpolate(
+            r#"
+            [wavs.chains.evm.base]
+            http_endpoint = "${BASE_RPC_URL}"
+            ws_endpoints = ["ws://localhost:${PORT}", "ws://other:${ PORT }"]
+            chain_id = 8453
+
+            [[wavs.list]]
+            url = "http://localhost:${PORT}/rpc"
+            "#,
+        )
+        .unwrap();
+
+        let base = &table["wavs"]["chains"]["evm"]["base"];
+        assert_eq!(
+            base["http_endpoint"].as_str(),
+            Some("https://base.example.com/key")
+        );
+        assert_eq!(
+            base["ws_endpoints"].as_array().unwrap(),
+            &vec![
+                toml::Value::String("ws://localhost:8545".to_string()),
+                toml::Value::String("ws://other:8545".to_string()),
+            ]
+        );
+        assert_eq!(base["chain_id"].as_integer(), Some(8453));
+        assert_eq!(
+            table["wavs"]["list"][0]["url"].as_str(),
+            Some("http://localhost:8545/rpc")
+        );
+    }
+
+    #[test]
+    fn missing_var() {
+        assert_eq!(
+            interpolate(
+                r#"
+                [wavs.chains.evm.base]
+                ws_endpoints = ["ws://localhost", "${NOT_SET}"]
+                "#
+            )
+            .unwrap_err(),
+            InterpolateError::MissingVar {
+                path: "wavs.chains.evm.base.ws_endpoints[1]".to_string(),
+                var: "NOT_SET".to_string()
+            }
+        );
+
+        assert!(matches!(
+            interpolate(r#"url = "${BASE_RPC_URL""#).unwrap_err(),
+            InterpolateError::Unterminated { .. }
+        ));
+        assert!(matches!(
+            interpolate(r#"url = "${}""#).unwrap_err(),
+            InterpolateError::EmptyName { .. }
+        ));
+    }
+
+    #[test]
+    fn escaped() {
+        let table = interpolate(
+            r#"
+            [wavs]
+            literal = "$${NOT_SET}"
+            mixed = "$${PORT} is ${PORT}, $5 stays"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(table["wavs"]["literal"].as_str(), Some("${NOT_SET}"));
+        assert_eq!(
+            table["wavs"]["mixed"].as_str(),
+            Some("${PORT} is 8545, $5 stays")
+        );
+    }
+}
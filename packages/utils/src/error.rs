@@ -1,6 +1,9 @@
+use alloy_primitives::TxHash;
 use alloy_rpc_types_eth::TransactionReceipt;
 use thiserror::Error;
 
+use crate::async_transaction::Retryable;
+
 #[derive(Debug, Error)]
 pub enum EvmClientError {
     #[error("HD index must be zero when using a private key (use mnemonic instead)")]
@@ -42,6 +45,109 @@ pub enum EvmClientError {
     #[error("Unable to create http provider: {0:#?}")]
     HttpProvider(anyhow::Error),
 
+    #[error("Custom TLS is only supported for http endpoints, not {0}")]
+    UnsupportedTls(String),
+
+    #[error("Failover is only supported across http endpoints, not {0}")]
+    UnsupportedFailover(String),
+
+    #[error("Network gas price of {current} wei stayed above the cap of {cap} wei")]
+    GasPriceAboveCap { current: u128, cap: u128 },
+
+    #[error("Transaction {tx_hash} only reached {confirmations} of {required} confirmations before timing out")]
+    ConfirmationTimeout {
+        tx_hash: TxHash,
+        confirmations: u64,
+        required: u64,
+    },
+
+    #[error("Unable to get chain id: {0:#?}")]
+    ChainId(anyhow::Error),
+
+    #[error("Endpoint {endpoint} is serving chain id {actual}, but is configured for {expected}")]
+    ChainIdMismatch {
+        endpoint: String,
+        expected: String,
+        actual: u64,
+    },
+
     #[error("Unable to get block height")]
     BlockHeight,
 }
+
+/// Messages of errors that won't go away by sending the same transaction again
+const FATAL_RPC_ERRORS: &[&str] = &["revert", "insufficient funds"];
+
+/// Messages of errors caused by the network, a busy node or a stale nonce
+const TRANSIENT_RPC_ERRORS: &[&str] = &[
+    "timeout",
+    "timed out",
+    "429",
+    "too many requests",
+    "rate limit",
+    "connection",
+    "temporarily",
+    "unavailable",
+    "nonce too low",
+    "replacement transaction underpriced",
+    "txpool is full",
+    "mempool is full",
+];
+
+/// Whether an RPC error message says the transaction itself can't succeed, e.g. it reverts
+pub fn is_fatal_rpc_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+
+    FATAL_RPC_ERRORS.iter().any(|fatal| message.contains(fatal))
+}
+
+/// Whether an RPC error message describes something that may clear up on its own.
+/// Fatal errors never do, unrecognized errors are assumed not to either
+pub fn is_transient_rpc_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+
+    !is_fatal_rpc_error(&message)
+        && TRANSIENT_RPC_ERRORS
+            .iter()
+            .any(|transient| message.contains(transient))
+}
+
+impl Retryable for EvmClientError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            EvmClientError::SendTransaction(err)
+            | EvmClientError::TransactionWithoutReceipt(err)
+            | EvmClientError::GasEstimation(err)
+            | EvmClientError::ChainId(err) => is_transient_rpc_error(&format!("{err:#}")),
+            EvmClientError::GasPriceAboveCap { .. } | EvmClientError::BlockHeight => true,
+            // the transaction landed (reverted or unconfirmed), sending it again could duplicate it
+            EvmClientError::TransactionWithReceipt(_)
+            | EvmClientError::ConfirmationTimeout { .. } => false,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn classify_rpc_errors() {
+        for transient in [
+            "error sending request: operation timed out",
+            "HTTP error 429 with body: Too Many Requests",
+            "server returned an error response: error code -32000: nonce too low",
+        ] {
+            assert!(is_transient_rpc_error(transient), "{transient}");
+        }
+
+        for fatal in [
+            "server returned an error response: error code 3: execution reverted",
+            "insufficient funds for gas * price + value",
+            "something nobody has seen before",
+        ] {
+            assert!(!is_transient_rpc_error(fatal), "{fatal}");
+        }
+    }
+}
@@ -1,4 +1,9 @@
+pub mod confirmations;
 pub mod contracts;
+pub mod failover;
+pub mod gas;
+pub mod pool;
+pub mod rpc_log;
 pub mod signing;
 
 use alloy_network::{EthereumWallet, Network, TransactionBuilder};
@@ -7,19 +12,25 @@ use alloy_provider::{
     fillers::{BlobGasFiller, ChainIdFiller, GasFiller, NonceManager},
     DynProvider, Provider, ProviderBuilder, WsConnect,
 };
+use alloy_rpc_client::{ClientBuilder, RpcClient};
 use alloy_rpc_types_eth::TransactionRequest;
 use alloy_signer_local::PrivateKeySigner;
 use alloy_transport::{TransportErrorKind, TransportResult};
 use anyhow::Result;
 use async_trait::async_trait;
+use confirmations::ConfirmationPolicy;
+use failover::{failover_rpc_client, FailoverConfig};
+use gas::GasPriceBounds;
+use pool::NoncePool;
+use rpc_log::RpcLogLayer;
 use signing::make_signer;
 use std::{
     str::FromStr,
     sync::{atomic::AtomicU64, Arc},
 };
-use wavs_types::Credential;
+use wavs_types::{ChainKeyId, Credential};
 
-use crate::error::EvmClientError;
+use crate::{error::EvmClientError, tls::RpcTls};
 
 #[derive(Clone)]
 pub struct EvmQueryClient {
@@ -98,6 +109,56 @@ impl EvmEndpoint {
             }
         })
     }
+
+    /// Like `to_provider`, but with custom TLS if the chain has any configured
+    pub async fn to_provider_with_tls(
+        &self,
+        tls: Option<&RpcTls>,
+    ) -> std::result::Result<DynProvider, EvmClientError> {
+        match tls {
+            None => self.to_provider().await,
+            Some(_) => Ok(DynProvider::new(
+                ProviderBuilder::new().connect_client(self.rpc_client(tls, false).await?),
+            )),
+        }
+    }
+
+    /// The underlying alloy rpc client, with the chain's TLS settings and optionally
+    /// logging every call (see [`rpc_log`])
+    pub async fn rpc_client(
+        &self,
+        tls: Option<&RpcTls>,
+        log_rpc: bool,
+    ) -> std::result::Result<RpcClient, EvmClientError> {
+        let builder = ClientBuilder::default();
+
+        match self {
+            EvmEndpoint::Http(url) => {
+                let client = tls.map(RpcTls::http_client).unwrap_or_default();
+                let transport = alloy_transport_http::Http::with_client(client, url.clone());
+                let is_local = transport.guess_local();
+                Ok(if log_rpc {
+                    builder
+                        .layer(RpcLogLayer::new(url))
+                        .transport(transport, is_local)
+                } else {
+                    RpcClient::new(transport, is_local)
+                })
+            }
+            EvmEndpoint::WebSocket(_) if tls.is_some() => {
+                Err(EvmClientError::UnsupportedTls(self.to_string()))
+            }
+            EvmEndpoint::WebSocket(url) => {
+                let ws = WsConnect::new(url.clone());
+                if log_rpc {
+                    builder.layer(RpcLogLayer::new(url)).ws(ws).await
+                } else {
+                    builder.ws(ws).await
+                }
+                .map_err(|e| EvmClientError::WebSocketProvider(e.into()))
+            }
+        }
+    }
 }
 
 impl EvmQueryClient {
@@ -107,6 +168,73 @@ impl EvmQueryClient {
             endpoint,
         })
     }
+
+    /// A client over the first of `endpoints` with the chain's TLS settings, failing over
+    /// to the rest if there are any (which then must all be http)
+    pub async fn new_with_failover(
+        endpoints: Vec<EvmEndpoint>,
+        tls: Option<&RpcTls>,
+    ) -> std::result::Result<Self, EvmClientError> {
+        let (endpoint, fallbacks) = endpoints
+            .split_first()
+            .ok_or_else(|| EvmClientError::ParseEndpoint("No endpoint provided".to_string()))?;
+
+        let provider = if fallbacks.is_empty() {
+            endpoint.to_provider_with_tls(tls).await?
+        } else {
+            DynProvider::new(ProviderBuilder::new().connect_client(failover_rpc_client(
+                &endpoints,
+                tls,
+                false,
+                FailoverConfig::default(),
+            )?))
+        };
+
+        Ok(EvmQueryClient {
+            provider,
+            endpoint: endpoint.clone(),
+        })
+    }
+
+    pub async fn verify_chain_id(
+        &self,
+        expected: &ChainKeyId,
+    ) -> std::result::Result<(), EvmClientError> {
+        verify_chain_id(&self.provider, &self.endpoint, expected).await
+    }
+}
+
+/// Fails if the endpoint is serving a different chain than it's configured for,
+/// e.g. `evm:8453` pointed at a Sepolia RPC, which otherwise only shows up later as
+/// confusing failures deep in execution
+pub async fn verify_chain_id(
+    provider: &DynProvider,
+    endpoint: &EvmEndpoint,
+    expected: &ChainKeyId,
+) -> std::result::Result<(), EvmClientError> {
+    let actual = provider
+        .get_chain_id()
+        .await
+        .map_err(|e| EvmClientError::ChainId(e.into()))?;
+
+    check_chain_id(endpoint, expected, actual)
+}
+
+/// Compares a chain id reported by `endpoint` against the configured one
+pub fn check_chain_id(
+    endpoint: impl std::fmt::Display,
+    expected: &ChainKeyId,
+    actual: u64,
+) -> std::result::Result<(), EvmClientError> {
+    if expected.as_str() == actual.to_string() {
+        Ok(())
+    } else {
+        Err(EvmClientError::ChainIdMismatch {
+            endpoint: endpoint.to_string(),
+            expected: expected.to_string(),
+            actual,
+        })
+    }
 }
 
 impl std::fmt::Debug for EvmQueryClient {
@@ -142,11 +270,20 @@ impl EvmSigningClient {
         self.provider.send_transaction(tx).await?.watch().await?;
         Ok(())
     }
+
+    pub async fn verify_chain_id(
+        &self,
+        expected: &ChainKeyId,
+    ) -> std::result::Result<(), EvmClientError> {
+        verify_chain_id(&self.provider, &self.config.endpoint, expected).await
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct EvmSigningClientConfig {
     pub endpoint: EvmEndpoint,
+    /// Http endpoints for the same chain that calls fail over to when `endpoint` fails
+    pub fallback_endpoints: Vec<EvmEndpoint>,
     pub credential: Credential,
     pub hd_index: Option<u32>,
     /// If a transaction does not have `max_gas` set, then it will estimate
@@ -154,6 +291,16 @@ pub struct EvmSigningClientConfig {
     /// if unset, it will be 1.25
     pub gas_estimate_multiplier: Option<f32>,
     pub nonce_manager_kind: NonceManagerKind,
+    /// Custom TLS for the endpoint, only supported over http
+    pub tls: Option<RpcTls>,
+    /// Log every RPC call with its result and timing, for debugging
+    pub log_rpc: bool,
+    /// Floor and cap on the fees submissions pay
+    pub gas_bounds: GasPriceBounds,
+    /// How many confirmations a submission waits for before it's considered final
+    pub confirmations: ConfirmationPolicy,
+    /// If set, the fast nonce manager is shared with other clients for the same key and chain
+    pub nonce_pool: Option<NoncePool>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -190,22 +337,34 @@ impl EvmSigningClientConfig {
     pub fn new(endpoint: EvmEndpoint, credential: Credential) -> Self {
         Self {
             endpoint,
+            fallback_endpoints: Vec::new(),
             credential,
             hd_index: None,
             gas_estimate_multiplier: None,
             nonce_manager_kind: NonceManagerKind::Fast,
+            tls: None,
+            log_rpc: false,
+            gas_bounds: GasPriceBounds::default(),
+            confirmations: ConfirmationPolicy::default(),
+            nonce_pool: None,
         }
     }
 
     pub fn new_anvil(endpoint_url: &str) -> Self {
         Self {
             endpoint: EvmEndpoint::from_str(endpoint_url).expect("Failed to parse anvil endpoint"),
+            fallback_endpoints: Vec::new(),
             credential: Credential::new(
                 "test test test test test test test test test test test junk".to_string(),
             ),
             hd_index: None,
             gas_estimate_multiplier: None,
             nonce_manager_kind: NonceManagerKind::Fast,
+            tls: None,
+            log_rpc: false,
+            gas_bounds: GasPriceBounds::default(),
+            confirmations: ConfirmationPolicy::default(),
+            nonce_pool: None,
         }
     }
 
@@ -217,22 +376,77 @@ impl EvmSigningClientConfig {
         self.gas_estimate_multiplier = Some(gas_estimate_multiplier);
         self
     }
+    pub fn with_tls(mut self, tls: RpcTls) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+    pub fn with_rpc_logging(mut self) -> Self {
+        self.log_rpc = true;
+        self
+    }
+    pub fn with_gas_bounds(mut self, gas_bounds: GasPriceBounds) -> Self {
+        self.gas_bounds = gas_bounds;
+        self
+    }
+    pub fn with_nonce_pool(mut self, nonce_pool: NoncePool) -> Self {
+        self.nonce_pool = Some(nonce_pool);
+        self
+    }
+    pub fn with_confirmations(mut self, confirmations: ConfirmationPolicy) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+    pub fn with_fallback_endpoints(mut self, fallback_endpoints: Vec<EvmEndpoint>) -> Self {
+        self.fallback_endpoints = fallback_endpoints;
+        self
+    }
+
+    /// The rpc client for `endpoint`, failing over to `fallback_endpoints` if there are any
+    pub async fn rpc_client(&self) -> std::result::Result<RpcClient, EvmClientError> {
+        if self.fallback_endpoints.is_empty() {
+            self.endpoint
+                .rpc_client(self.tls.as_ref(), self.log_rpc)
+                .await
+        } else {
+            failover_rpc_client(
+                std::iter::once(&self.endpoint).chain(&self.fallback_endpoints),
+                self.tls.as_ref(),
+                self.log_rpc,
+                FailoverConfig::default(),
+            )
+        }
+    }
 }
 
 impl EvmSigningClient {
     const DEFAULT_GAS_ESTIMATE_MULTIPLIER: f32 = 1.25;
 
     pub async fn new(config: EvmSigningClientConfig) -> Result<Self> {
+        let rpc_client = config.rpc_client().await?;
+        Self::with_rpc_client(config, rpc_client).await
+    }
+
+    /// Like [`Self::new`], but sending every call through `rpc_client` rather than the
+    /// config's endpoints, e.g. to an in-memory chain in tests
+    pub async fn with_rpc_client(
+        config: EvmSigningClientConfig,
+        rpc_client: RpcClient,
+    ) -> Result<Self> {
         let signer = make_signer(&config.credential, config.hd_index)?;
 
         let wallet: EthereumWallet = signer.clone().into();
 
         let nonce_manager = match config.nonce_manager_kind {
             NonceManagerKind::Fast => {
-                let nonce_manager = FastNonceManager::new(Some(signer.address()));
-                nonce_manager
-                    .set_current_nonce(&config.endpoint.to_provider().await?)
-                    .await?;
+                let provider = ProviderBuilder::new().connect_client(rpc_client.clone());
+                let nonce_manager = match &config.nonce_pool {
+                    Some(pool) => pool.manager(&provider, signer.address()).await?,
+                    None => {
+                        let nonce_manager = FastNonceManager::new(Some(signer.address()));
+                        nonce_manager.set_current_nonce(&provider).await?;
+                        nonce_manager
+                    }
+                };
                 AnyNonceManager::Fast(nonce_manager)
             }
             NonceManagerKind::Safe => {
@@ -248,13 +462,7 @@ impl EvmSigningClient {
             .filler(ChainIdFiller::new(None))
             .wallet(wallet.clone());
 
-        let provider = match &config.endpoint {
-            EvmEndpoint::WebSocket(url) => {
-                let ws = WsConnect::new(url.clone());
-                DynProvider::new(builder.connect_ws(ws).await?)
-            }
-            EvmEndpoint::Http(url) => DynProvider::new(builder.connect_http(url.clone())),
-        };
+        let provider = DynProvider::new(builder.connect_client(rpc_client));
 
         Ok(Self {
             config,
@@ -325,7 +533,8 @@ impl FastNonceManager {
     {
         match self.address {
             Some(address) => {
-                let current_nonce = provider.get_transaction_count(address).await?;
+                // pending, so transactions already sent but not yet mined aren't handed out again
+                let current_nonce = provider.get_transaction_count(address).pending().await?;
                 self.counter
                     .store(current_nonce, std::sync::atomic::Ordering::SeqCst);
                 Ok(current_nonce)
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// How often the chain head is re-checked while waiting for confirmations
+pub const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// How long a submission waits for its confirmations, unless configured
+pub const DEFAULT_CONFIRMATION_TIMEOUT_SECS: u64 = 600;
+
+/// How deep a submitted transaction must be before it's considered final.
+/// The default of 1 treats a transaction as final as soon as it's mined
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ConfirmationPolicy {
+    /// Blocks that must include or build on the transaction's block, counting that block itself
+    pub confirmations: u64,
+    /// How long to wait for the confirmations after the transaction is mined
+    pub timeout_secs: Option<u64>,
+}
+
+impl Default for ConfirmationPolicy {
+    fn default() -> Self {
+        Self {
+            confirmations: 1,
+            timeout_secs: None,
+        }
+    }
+}
+
+impl ConfirmationPolicy {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.timeout_secs
+                .unwrap_or(DEFAULT_CONFIRMATION_TIMEOUT_SECS),
+        )
+    }
+
+    /// Whether anything beyond the receipt needs to be waited for
+    pub fn needs_polling(&self) -> bool {
+        self.confirmations > 1
+    }
+}
+
+/// Confirmations of a transaction mined in `tx_block`, given the current chain head
+pub fn confirmations_at(tx_block: u64, head: u64) -> u64 {
+    match head.checked_sub(tx_block) {
+        Some(depth) => depth + 1,
+        None => 0,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn counts_confirmations() {
+        assert_eq!(confirmations_at(10, 10), 1);
+        assert_eq!(confirmations_at(10, 15), 6);
+        // the node we asked for the head is behind the one that gave us the receipt
+        assert_eq!(confirmations_at(10, 9), 0);
+
+        assert!(!ConfirmationPolicy::default().needs_polling());
+        assert!(ConfirmationPolicy {
+            confirmations: 12,
+            timeout_secs: None
+        }
+        .needs_polling());
+    }
+}
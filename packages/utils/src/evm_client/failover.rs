@@ -0,0 +1,278 @@
+//! Sends JSON-RPC calls for one chain over several http endpoints.
+//! Calls go to the first healthy endpoint and fall through to the next one if it fails or
+//! times out. An endpoint that fails too many times in a row is skipped for a cooldown,
+//! unless every endpoint is failing, in which case they're all still tried.
+
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_rpc_client::RpcClient;
+use alloy_transport::{BoxTransport, TransportError, TransportErrorKind, TransportFut};
+use tower::{Layer, Service};
+
+use super::{
+    rpc_log::{redact_url, RpcLogLayer},
+    EvmEndpoint,
+};
+use crate::{error::EvmClientError, tls::RpcTls};
+
+#[derive(Debug, Clone, Copy)]
+pub struct FailoverConfig {
+    /// Consecutive failures before an endpoint is skipped
+    pub failure_threshold: u32,
+    /// How long a failing endpoint is skipped for
+    pub cooldown: Duration,
+    /// A call that takes longer than this counts as a failure
+    pub request_timeout: Duration,
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 3,
+            cooldown: Duration::from_secs(30),
+            request_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct EndpointHealth {
+    consecutive_failures: u32,
+    unhealthy_until: Option<Instant>,
+}
+
+#[derive(Clone)]
+pub struct FailoverTransport {
+    /// Redacted url for logging, and the transport itself
+    endpoints: Arc<[(String, BoxTransport)]>,
+    health: Arc<Mutex<Vec<EndpointHealth>>>,
+    config: FailoverConfig,
+}
+
+impl FailoverTransport {
+    pub fn new(endpoints: Vec<(String, BoxTransport)>, config: FailoverConfig) -> Self {
+        let health = endpoints
+            .iter()
+            .map(|_| EndpointHealth::default())
+            .collect();
+
+        Self {
+            endpoints: endpoints.into(),
+            health: Arc::new(Mutex::new(health)),
+            config,
+        }
+    }
+
+    /// Healthy endpoints in configured order, followed by the ones cooling down
+    fn attempt_order(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let health = self.health.lock().unwrap();
+
+        let (healthy, cooling_down): (Vec<usize>, Vec<usize>) =
+            (0..health.len()).partition(|&index| {
+                health[index]
+                    .unhealthy_until
+                    .is_none_or(|until| until <= now)
+            });
+
+        healthy.into_iter().chain(cooling_down).collect()
+    }
+
+    fn record_success(&self, index: usize) {
+        self.health.lock().unwrap()[index] = EndpointHealth::default();
+    }
+
+    fn record_failure(&self, index: usize) {
+        let mut health = self.health.lock().unwrap();
+        let endpoint = &mut health[index];
+        endpoint.consecutive_failures += 1;
+
+        if endpoint.consecutive_failures >= self.config.failure_threshold {
+            if endpoint.unhealthy_until.is_none() {
+                tracing::warn!(
+                    "RPC endpoint {} failed {} times in a row, skipping it for {:?}",
+                    self.endpoints[index].0,
+                    endpoint.consecutive_failures,
+                    self.config.cooldown
+                );
+            }
+            endpoint.unhealthy_until = Some(Instant::now() + self.config.cooldown);
+        }
+    }
+}
+
+impl Service<RequestPacket> for FailoverTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // each attempt clones its endpoint's transport, which is always ready
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let this = self.clone();
+
+        Box::pin(async move {
+            let mut last_err = None;
+
+            for index in this.attempt_order() {
+                let (name, transport) = &this.endpoints[index];
+                let call = transport.clone().call(req.clone());
+
+                let err = match tokio::time::timeout(this.config.request_timeout, call).await {
+                    Ok(Ok(resp)) => {
+                        this.record_success(index);
+                        return Ok(resp);
+                    }
+                    Ok(Err(err)) => err,
+                    Err(_) => TransportErrorKind::custom_str(&format!(
+                        "request timed out after {:?}",
+                        this.config.request_timeout
+                    )),
+                };
+
+                tracing::warn!("RPC endpoint {} failed: {}", name, err);
+                this.record_failure(index);
+                last_err = Some(err);
+            }
+
+            Err(last_err
+                .unwrap_or_else(|| TransportErrorKind::custom_str("no RPC endpoints configured")))
+        })
+    }
+}
+
+/// An rpc client that fails over across `endpoints`, which must all be http
+pub fn failover_rpc_client<'a>(
+    endpoints: impl IntoIterator<Item = &'a EvmEndpoint>,
+    tls: Option<&RpcTls>,
+    log_rpc: bool,
+    config: FailoverConfig,
+) -> Result<RpcClient, EvmClientError> {
+    let mut transports = Vec::new();
+    let mut is_local = true;
+
+    for endpoint in endpoints {
+        let url = match endpoint {
+            EvmEndpoint::Http(url) => url,
+            EvmEndpoint::WebSocket(_) => {
+                return Err(EvmClientError::UnsupportedFailover(endpoint.to_string()))
+            }
+        };
+
+        let client = tls.map(RpcTls::http_client).unwrap_or_default();
+        let http = alloy_transport_http::Http::with_client(client, url.clone());
+        is_local &= http.guess_local();
+
+        let transport = if log_rpc {
+            BoxTransport::new(RpcLogLayer::new(url).layer(http))
+        } else {
+            BoxTransport::new(http)
+        };
+
+        transports.push((redact_url(url), transport));
+    }
+
+    Ok(RpcClient::new(
+        FailoverTransport::new(transports, config),
+        is_local,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use alloy_json_rpc::{Response, ResponsePayload};
+    use alloy_primitives::U64;
+    use serde_json::value::RawValue;
+
+    use super::*;
+
+    /// Answers every call with chain id 1, or fails every call
+    #[derive(Clone)]
+    struct MockEndpoint {
+        fail: bool,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl Service<RequestPacket> for MockEndpoint {
+        type Response = ResponsePacket;
+        type Error = TransportError;
+        type Future = TransportFut<'static>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: RequestPacket) -> Self::Future {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let fail = self.fail;
+
+            Box::pin(async move {
+                if fail {
+                    return Err(TransportErrorKind::custom_str("connection refused"));
+                }
+
+                match req {
+                    RequestPacket::Single(req) => Ok(ResponsePacket::Single(Response {
+                        id: req.id().clone(),
+                        payload: ResponsePayload::Success(
+                            RawValue::from_string("\"0x1\"".to_string()).unwrap(),
+                        ),
+                    })),
+                    RequestPacket::Batch(_) => {
+                        Err(TransportErrorKind::custom_str("batches not supported"))
+                    }
+                }
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn fails_over_to_healthy_endpoint() {
+        let failing_calls = Arc::new(AtomicUsize::new(0));
+        let healthy_calls = Arc::new(AtomicUsize::new(0));
+
+        let transport = FailoverTransport::new(
+            vec![
+                (
+                    "failing".to_string(),
+                    BoxTransport::new(MockEndpoint {
+                        fail: true,
+                        calls: failing_calls.clone(),
+                    }),
+                ),
+                (
+                    "healthy".to_string(),
+                    BoxTransport::new(MockEndpoint {
+                        fail: false,
+                        calls: healthy_calls.clone(),
+                    }),
+                ),
+            ],
+            FailoverConfig {
+                failure_threshold: 2,
+                cooldown: Duration::from_secs(60),
+                request_timeout: Duration::from_secs(1),
+            },
+        );
+        let client = RpcClient::new(transport, true);
+
+        for _ in 0..5 {
+            let chain_id: U64 = client.request_noparams("eth_chainId").await.unwrap();
+            assert_eq!(chain_id, U64::from(1));
+        }
+
+        // the failing endpoint is tried until it hits the threshold, then skipped
+        assert_eq!(failing_calls.load(Ordering::SeqCst), 2);
+        assert_eq!(healthy_calls.load(Ordering::SeqCst), 5);
+    }
+}
@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// How often the network gas price is re-checked while a submission is held
+pub const GAS_PRICE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// How long a submission is held waiting for fees to drop, unless configured
+pub const DEFAULT_MAX_GAS_WAIT_SECS: u64 = 300;
+
+/// What a submission pays per unit of gas, when the caller has an opinion, in wei
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GasFees {
+    Legacy {
+        gas_price: u128,
+    },
+    Eip1559 {
+        max_fee_per_gas: u128,
+        max_priority_fee_per_gas: u128,
+    },
+}
+
+/// Per-chain bounds on what a submission pays per unit of gas, in wei.
+/// Guards against gas oracles returning zero (stuck transactions) or absurd spikes (drained funds)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct GasPriceBounds {
+    /// Gas prices and max fees below this are raised to it
+    pub min_gas_price: Option<u128>,
+    /// Gas prices and max fees above this are lowered to it. If the network itself
+    /// is above it, the submission is held until fees drop or `max_wait_secs` passes
+    pub max_fee_per_gas: Option<u128>,
+    /// How long to hold a submission waiting for fees to drop below `max_fee_per_gas`
+    pub max_wait_secs: Option<u64>,
+}
+
+impl GasPriceBounds {
+    pub fn is_unbounded(&self) -> bool {
+        self.min_gas_price.is_none() && self.max_fee_per_gas.is_none()
+    }
+
+    pub fn max_wait(&self) -> Duration {
+        Duration::from_secs(self.max_wait_secs.unwrap_or(DEFAULT_MAX_GAS_WAIT_SECS))
+    }
+
+    /// Clamps `fee` into the bounds, logging whenever it had to be changed
+    pub fn clamp(&self, fee: u128, label: &str) -> u128 {
+        if let Some(min) = self.min_gas_price {
+            if fee < min {
+                tracing::warn!("{label} of {fee} wei is below the floor, raising it to {min} wei");
+                return min;
+            }
+        }
+
+        if let Some(max) = self.max_fee_per_gas {
+            if fee > max {
+                tracing::warn!("{label} of {fee} wei is above the cap, lowering it to {max} wei");
+                return max;
+            }
+        }
+
+        fee
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn clamps_into_bounds() {
+        let bounds = GasPriceBounds {
+            min_gas_price: Some(10),
+            max_fee_per_gas: Some(100),
+            max_wait_secs: None,
+        };
+
+        assert_eq!(bounds.clamp(0, "gas price"), 10);
+        assert_eq!(bounds.clamp(50, "gas price"), 50);
+        assert_eq!(bounds.clamp(1_000, "gas price"), 100);
+        assert_eq!(
+            bounds.max_wait(),
+            Duration::from_secs(DEFAULT_MAX_GAS_WAIT_SECS)
+        );
+
+        let unbounded = GasPriceBounds::default();
+        assert!(unbounded.is_unbounded());
+        assert_eq!(unbounded.clamp(u128::MAX, "gas price"), u128::MAX);
+    }
+}
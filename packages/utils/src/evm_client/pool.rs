@@ -0,0 +1,121 @@
+//! Shares one nonce counter between every signing client for the same key on the same chain.
+//!
+//! Each [`FastNonceManager`] reserves nonces from its own counter, so two clients built for
+//! the same key would hand out the same nonces and one of the transactions would fail with
+//! `nonce too low`. Clients built with a pool share the counter instead, which is synced
+//! from the chain when it's first created and again whenever a submission hits a nonce error.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use alloy_network::Network;
+use alloy_primitives::Address;
+use alloy_provider::Provider;
+
+use super::FastNonceManager;
+
+#[derive(Debug, Clone, Default)]
+pub struct NoncePool {
+    managers: Arc<Mutex<HashMap<(u64, Address), FastNonceManager>>>,
+}
+
+impl NoncePool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The shared nonce manager for `address` on the chain `provider` is connected to.
+    /// A new one is synced from the chain before it's handed out
+    pub async fn manager<P, N>(
+        &self,
+        provider: &P,
+        address: Address,
+    ) -> anyhow::Result<FastNonceManager>
+    where
+        P: Provider<N>,
+        N: Network,
+    {
+        let chain_id = provider.get_chain_id().await?;
+
+        if let Some(manager) = self.managers.lock().unwrap().get(&(chain_id, address)) {
+            return Ok(manager.clone());
+        }
+
+        let manager = FastNonceManager::new(Some(address));
+        manager.set_current_nonce(provider).await?;
+
+        // another client may have raced us here, in which case keep theirs so there's one counter
+        Ok(self
+            .managers
+            .lock()
+            .unwrap()
+            .entry((chain_id, address))
+            .or_insert(manager)
+            .clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloy_network::TransactionBuilder;
+    use alloy_primitives::U256;
+    use alloy_rpc_types_eth::{TransactionRequest, TransactionTrait};
+
+    use super::*;
+    use crate::{evm_client::EvmSigningClient, test_utils::test_contracts::TestContractDeps};
+
+    #[tokio::test]
+    async fn concurrent_submissions_get_sequential_nonces() {
+        let test_deps = TestContractDeps::new().await;
+        let config = test_deps
+            .client
+            .config
+            .clone()
+            .with_nonce_pool(NoncePool::new());
+
+        // separate clients for the same key, as separate workflows would have
+        let clients = [
+            EvmSigningClient::new(config.clone()).await.unwrap(),
+            EvmSigningClient::new(config).await.unwrap(),
+        ];
+        let provider = clients[0].provider.clone();
+        let address = clients[0].address();
+        let start_nonce = provider.get_transaction_count(address).await.unwrap();
+
+        let receipts = futures::future::join_all((0..20).map(|i| {
+            let client = clients[i % clients.len()].clone();
+            async move {
+                let tx = TransactionRequest::default()
+                    .with_from(client.address())
+                    .with_to(client.address())
+                    .with_value(U256::from(1));
+
+                client
+                    .provider
+                    .send_transaction(tx)
+                    .await
+                    .unwrap()
+                    .get_receipt()
+                    .await
+                    .unwrap()
+            }
+        }))
+        .await;
+
+        let mut nonces = Vec::new();
+        for receipt in receipts {
+            assert!(receipt.status());
+            let tx = provider
+                .get_transaction_by_hash(receipt.transaction_hash)
+                .await
+                .unwrap()
+                .unwrap();
+            nonces.push(tx.inner.nonce());
+        }
+        nonces.sort();
+
+        assert_eq!(nonces, (start_nonce..start_nonce + 20).collect::<Vec<_>>());
+    }
+}
@@ -1,13 +1,20 @@
 use alloy_primitives::Address;
 use alloy_provider::Provider;
-use alloy_rpc_types_eth::TransactionReceipt;
+use alloy_rpc_types_eth::{TransactionReceipt, TransactionRequest};
 use alloy_signer::k256::SecretKey;
 use alloy_signer_local::{coins_bip39::English, MnemonicBuilder, PrivateKeySigner};
 use std::time::Duration;
-use tokio::time::sleep;
+use tokio::time::{sleep, Instant};
 use wavs_types::{Credential, Envelope, SignatureData};
 
-use crate::{error::EvmClientError, evm_client::AnyNonceManager};
+use crate::{
+    error::{is_fatal_rpc_error, EvmClientError},
+    evm_client::{
+        confirmations::{confirmations_at, ConfirmationPolicy, CONFIRMATION_POLL_INTERVAL},
+        gas::{GasFees, GasPriceBounds, GAS_PRICE_POLL_INTERVAL},
+        AnyNonceManager,
+    },
+};
 
 use super::EvmSigningClient;
 
@@ -46,46 +53,68 @@ impl EvmSigningClient {
         signature_data: SignatureData,
         service_handler: Address,
         max_gas: Option<u64>,
-        gas_price: Option<u128>,
+        fees: Option<GasFees>,
     ) -> Result<TransactionReceipt, EvmClientError> {
-        if self
-            .provider
-            .get_code_at(service_handler)
-            .await
-            .map_err(|e| EvmClientError::FailedGetCode(service_handler, e.into()))?
-            .is_empty()
-        {
-            return Err(EvmClientError::NotContract(service_handler));
-        }
-
-        let gas = match max_gas {
-            None => {
-                let gas_estimate = self
-                    .service_handler(service_handler)
-                    .handleSignedEnvelope(envelope.clone(), signature_data.clone())
-                    .estimate_gas()
-                    .await
-                    .map_err(|e| EvmClientError::TransactionWithoutReceipt(e.into()))?;
-
-                // pad it with a multiplier to account for gas fluctuations
-                ((gas_estimate as f32) * self.gas_estimate_multiplier()) as u64
-            }
-
-            Some(gas) => {
-                // EIP-1559 has a default 30m gas limit per block without override. Else:
-                // 'a intrinsic gas too high -- tx.gas_limit > env.block.gas_limit' is thrown
-                gas.min(30_000_000)
-            }
-        };
+        let gas = self
+            .envelope_gas(&envelope, &signature_data, service_handler, max_gas)
+            .await?;
 
         let service_handler_instance = self.service_handler(service_handler);
         let mut tx_builder = service_handler_instance
             .handleSignedEnvelope(envelope, signature_data)
             .gas(gas);
 
-        // Set gas price if provided
-        if let Some(price) = gas_price {
-            tx_builder = tx_builder.gas_price(price);
+        let bounds = self.config.gas_bounds;
+        if bounds.is_unbounded() {
+            // Set fees if provided
+            match fees {
+                Some(GasFees::Legacy { gas_price }) => {
+                    tx_builder = tx_builder.gas_price(gas_price);
+                }
+                Some(GasFees::Eip1559 {
+                    max_fee_per_gas,
+                    max_priority_fee_per_gas,
+                }) => {
+                    tx_builder = tx_builder
+                        .max_fee_per_gas(max_fee_per_gas)
+                        .max_priority_fee_per_gas(max_priority_fee_per_gas);
+                }
+                None => {}
+            }
+        } else {
+            self.wait_for_gas_under_cap(&bounds).await?;
+
+            match fees {
+                Some(GasFees::Legacy { gas_price }) => {
+                    tx_builder = tx_builder.gas_price(bounds.clamp(gas_price, "Gas price"));
+                }
+                Some(GasFees::Eip1559 {
+                    max_fee_per_gas,
+                    max_priority_fee_per_gas,
+                }) => {
+                    let max_fee = bounds.clamp(max_fee_per_gas, "Max fee per gas");
+                    tx_builder = tx_builder
+                        .max_fee_per_gas(max_fee)
+                        .max_priority_fee_per_gas(max_priority_fee_per_gas.min(max_fee));
+                }
+                None => match self.provider.estimate_eip1559_fees().await {
+                    Ok(fees) => {
+                        let max_fee = bounds.clamp(fees.max_fee_per_gas, "Max fee per gas");
+                        tx_builder = tx_builder
+                            .max_fee_per_gas(max_fee)
+                            .max_priority_fee_per_gas(fees.max_priority_fee_per_gas.min(max_fee));
+                    }
+                    // chains without EIP-1559 only have a legacy gas price
+                    Err(_) => {
+                        let price = self
+                            .provider
+                            .get_gas_price()
+                            .await
+                            .map_err(|e| EvmClientError::GasEstimation(e.into()))?;
+                        tx_builder = tx_builder.gas_price(bounds.clamp(price, "Gas price"));
+                    }
+                },
+            }
         }
 
         let mut retry_count = 0;
@@ -101,7 +130,8 @@ impl EvmSigningClient {
                         .map_err(|e| EvmClientError::TransactionWithoutReceipt(e.into()))?;
                 }
                 Err(e) => {
-                    if retry_count >= MAX_RETRIES {
+                    // reverts and insufficient funds fail the same way every time
+                    if retry_count >= MAX_RETRIES || is_fatal_rpc_error(&e.to_string()) {
                         return Err(EvmClientError::SendTransaction(e.into()));
                     }
 
@@ -138,6 +168,16 @@ impl EvmSigningClient {
                             e
                         );
 
+                        // the failed attempt may have reserved a nonce that never reached the
+                        // chain, which would leave a gap that stalls every later transaction
+                        if let AnyNonceManager::Fast(fast_nonce_manager) = &self.nonce_manager {
+                            if let Err(err) =
+                                fast_nonce_manager.set_current_nonce(&self.provider).await
+                            {
+                                tracing::warn!("Failed to resync nonce after failed send: {err}");
+                            }
+                        }
+
                         // Add exponential backoff delay for network-related retries
                         let delay_ms = BASE_RETRY_DELAY_MS * (1 << (retry_count - 1));
                         sleep(Duration::from_millis(delay_ms)).await;
@@ -147,9 +187,170 @@ impl EvmSigningClient {
             }
         };
 
-        match receipt.status() {
-            true => Ok(receipt),
-            false => Err(EvmClientError::TransactionWithReceipt(Box::new(receipt))),
+        if !receipt.status() {
+            return Err(EvmClientError::TransactionWithReceipt(Box::new(receipt)));
+        }
+
+        let confirmations = self.config.confirmations;
+        if confirmations.needs_polling() {
+            self.wait_for_confirmations(receipt, &confirmations).await
+        } else {
+            Ok(receipt)
+        }
+    }
+
+    /// The transaction [`Self::send_envelope_signatures`] would send, down to the calldata
+    /// and gas limit, without signing or broadcasting it
+    pub async fn preview_envelope_signatures(
+        &self,
+        envelope: Envelope,
+        signature_data: SignatureData,
+        service_handler: Address,
+        max_gas: Option<u64>,
+    ) -> Result<TransactionRequest, EvmClientError> {
+        let gas = self
+            .envelope_gas(&envelope, &signature_data, service_handler, max_gas)
+            .await?;
+
+        Ok(self
+            .service_handler(service_handler)
+            .handleSignedEnvelope(envelope, signature_data)
+            .from(self.address())
+            .gas(gas)
+            .into_transaction_request())
+    }
+
+    async fn envelope_gas(
+        &self,
+        envelope: &Envelope,
+        signature_data: &SignatureData,
+        service_handler: Address,
+        max_gas: Option<u64>,
+    ) -> Result<u64, EvmClientError> {
+        if self
+            .provider
+            .get_code_at(service_handler)
+            .await
+            .map_err(|e| EvmClientError::FailedGetCode(service_handler, e.into()))?
+            .is_empty()
+        {
+            return Err(EvmClientError::NotContract(service_handler));
+        }
+
+        match max_gas {
+            None => {
+                let gas_estimate = self
+                    .service_handler(service_handler)
+                    .handleSignedEnvelope(envelope.clone(), signature_data.clone())
+                    .estimate_gas()
+                    .await
+                    .map_err(|e| EvmClientError::TransactionWithoutReceipt(e.into()))?;
+
+                // pad it with a multiplier to account for gas fluctuations
+                Ok(((gas_estimate as f32) * self.gas_estimate_multiplier()) as u64)
+            }
+
+            Some(gas) => {
+                // EIP-1559 has a default 30m gas limit per block without override. Else:
+                // 'a intrinsic gas too high -- tx.gas_limit > env.block.gas_limit' is thrown
+                Ok(gas.min(30_000_000))
+            }
+        }
+    }
+
+    /// Polls until the transaction is buried deep enough, returning its latest receipt.
+    /// The receipt is re-fetched every time, so a reorg that moves or drops the
+    /// transaction restarts the count rather than being missed
+    async fn wait_for_confirmations(
+        &self,
+        receipt: TransactionReceipt,
+        policy: &ConfirmationPolicy,
+    ) -> Result<TransactionReceipt, EvmClientError> {
+        let tx_hash = receipt.transaction_hash;
+        let deadline = Instant::now() + policy.timeout();
+        let mut latest = receipt;
+
+        loop {
+            let head = self
+                .provider
+                .get_block_number()
+                .await
+                .map_err(|e| EvmClientError::TransactionWithoutReceipt(e.into()))?;
+
+            let current = self
+                .provider
+                .get_transaction_receipt(tx_hash)
+                .await
+                .map_err(|e| EvmClientError::TransactionWithoutReceipt(e.into()))?;
+
+            let confirmations = match &current {
+                Some(receipt) => receipt
+                    .block_number
+                    .map(|block| confirmations_at(block, head))
+                    .unwrap_or_default(),
+                None => {
+                    tracing::warn!(
+                        "Transaction {tx_hash} is no longer on chain, possibly reorged out"
+                    );
+                    0
+                }
+            };
+
+            if let Some(receipt) = current {
+                latest = receipt;
+            }
+
+            if confirmations >= policy.confirmations {
+                return match latest.status() {
+                    true => Ok(latest),
+                    false => Err(EvmClientError::TransactionWithReceipt(Box::new(latest))),
+                };
+            }
+
+            if Instant::now() + CONFIRMATION_POLL_INTERVAL > deadline {
+                return Err(EvmClientError::ConfirmationTimeout {
+                    tx_hash,
+                    confirmations,
+                    required: policy.confirmations,
+                });
+            }
+
+            tracing::debug!(
+                "Transaction {tx_hash} has {confirmations} of {} confirmations",
+                policy.confirmations
+            );
+            sleep(CONFIRMATION_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Holds while the network gas price is above the cap, so a fee spike delays
+    /// submissions instead of overpaying or sending transactions that can't be mined
+    async fn wait_for_gas_under_cap(&self, bounds: &GasPriceBounds) -> Result<(), EvmClientError> {
+        let Some(cap) = bounds.max_fee_per_gas else {
+            return Ok(());
+        };
+
+        let deadline = Instant::now() + bounds.max_wait();
+
+        loop {
+            let current = self
+                .provider
+                .get_gas_price()
+                .await
+                .map_err(|e| EvmClientError::GasEstimation(e.into()))?;
+
+            if current <= cap {
+                return Ok(());
+            }
+
+            if Instant::now() + GAS_PRICE_POLL_INTERVAL > deadline {
+                return Err(EvmClientError::GasPriceAboveCap { current, cap });
+            }
+
+            tracing::warn!(
+                "Network gas price of {current} wei is above the cap of {cap} wei, holding the submission"
+            );
+            sleep(GAS_PRICE_POLL_INTERVAL).await;
         }
     }
 }
@@ -164,35 +365,9 @@ mod test {
 
     use crate::{
         evm_client::{AnyNonceManager, EvmSigningClient, EvmSigningClientConfig},
-        test_utils::test_contracts::TestContractDeps,
+        test_utils::test_contracts::{TestContractDeps, TestServiceHandler, TestServiceManager},
     };
 
-    mod test_service_contracts {
-        pub mod manager {
-            use alloy_sol_types::sol;
-
-            sol!(
-                #[allow(missing_docs)]
-                #[sol(rpc)]
-                TestServiceManager,
-                "../../examples/contracts/solidity/abi/TestServiceContracts.sol/TestServiceManager.json"
-            );
-        }
-
-        pub mod handler {
-            use alloy_sol_types::sol;
-
-            sol!(
-                #[allow(missing_docs)]
-                #[sol(rpc)]
-                TestServiceHandler,
-                "../../examples/contracts/solidity/abi/TestServiceContracts.sol/TestServiceHandler.json"
-            );
-        }
-    }
-
-    use test_service_contracts::{handler::TestServiceHandler, manager::TestServiceManager};
-
     #[tokio::test]
     async fn signature_validation() {
         let signer = mock_signer();
@@ -422,4 +597,73 @@ mod test {
 
         tracing::info!("✓ send_envelope_signatures recovered from nonce desync");
     }
+
+    #[tokio::test]
+    async fn preview_does_not_broadcast() {
+        let test_deps = TestContractDeps::new().await;
+        let client = test_deps.client.clone();
+
+        let manager_address = *TestServiceManager::deploy(client.provider.clone())
+            .await
+            .unwrap()
+            .address();
+        let handler_address = *TestServiceHandler::deploy(client.provider.clone(), manager_address)
+            .await
+            .unwrap()
+            .address();
+
+        let envelope = mock_envelope();
+        let signature = envelope
+            .sign(client.signer.as_ref(), SignatureKind::evm_default())
+            .await
+            .unwrap();
+        let block = client.provider.get_block_number().await.unwrap();
+        let signature_data = envelope
+            .signature_data(vec![signature], block.saturating_sub(1))
+            .unwrap();
+
+        let nonce_before = client
+            .provider
+            .get_transaction_count(client.address())
+            .await
+            .unwrap();
+
+        let preview = client
+            .preview_envelope_signatures(
+                envelope.clone(),
+                signature_data.clone(),
+                handler_address,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(preview.to, Some(handler_address.into()));
+        assert_eq!(preview.from, Some(client.address()));
+        assert!(preview.gas.is_some());
+
+        // nothing was sent: no nonce used up, no block mined
+        assert_eq!(
+            client
+                .provider
+                .get_transaction_count(client.address())
+                .await
+                .unwrap(),
+            nonce_before
+        );
+        assert_eq!(client.provider.get_block_number().await.unwrap(), block);
+
+        // and the preview is what actually goes out
+        let receipt = client
+            .send_envelope_signatures(envelope, signature_data, handler_address, None, None)
+            .await
+            .unwrap();
+        let submitted_tx = client
+            .provider
+            .get_transaction_by_hash(receipt.transaction_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(preview.input.input().unwrap(), submitted_tx.inner.input());
+    }
 }
@@ -1,4 +1,10 @@
-use std::path::PathBuf;
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Extension of the temporary file [`write_atomic`] writes before renaming it into place
+pub const TMP_EXTENSION: &str = "tmp";
 
 pub fn workspace_path() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -8,3 +14,58 @@ pub fn workspace_path() -> PathBuf {
         .unwrap()
         .to_path_buf()
 }
+
+/// Writes to a uniquely named temporary file next to `path` and renames it into place,
+/// so a crash leaves either the old or the new contents, never a truncated file,
+/// and concurrent writers to the same path never share a temporary file
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let mut prefix = path.file_name().unwrap_or_default().to_os_string();
+    prefix.push(".");
+
+    // dropped (and removed) if anything fails before the rename
+    let mut file = tempfile::Builder::new()
+        .prefix(&prefix)
+        .suffix(&format!(".{TMP_EXTENSION}"))
+        .tempfile_in(dir)?;
+    file.write_all(bytes)?;
+    file.as_file().sync_all()?;
+
+    file.persist(path).map(|_| ()).map_err(|e| e.error)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn concurrent_writers_never_share_a_temp_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+
+        let payloads: Vec<Vec<u8>> = (0..8u8).map(|i| vec![i; 64 * 1024]).collect();
+        std::thread::scope(|scope| {
+            for payload in &payloads {
+                let path = &path;
+                scope.spawn(move || {
+                    for _ in 0..16 {
+                        write_atomic(path, payload).unwrap();
+                    }
+                });
+            }
+        });
+
+        // the file holds exactly one writer's contents, and no temp files are left behind
+        let written = std::fs::read(&path).unwrap();
+        assert!(payloads.contains(&written));
+        let leftovers: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == TMP_EXTENSION))
+            .collect();
+        assert!(leftovers.is_empty(), "{leftovers:?}");
+    }
+}
@@ -1,3 +1,5 @@
+pub mod signed;
+
 use alloy_primitives::U256;
 use alloy_provider::Provider;
 use alloy_rpc_types_eth::SyncStatus;
@@ -23,6 +25,11 @@ pub async fn health_check_single_chain(
             check_cosmos_chain_health_query(key.clone(), config.clone()).await?;
             tracing::info!("Cosmos chain [{key}] is healthy");
         }
+        AnyChainConfig::Solana(_) => {
+            tracing::warn!(
+                "Health checks for Solana chain [{key}] are not supported yet, skipping"
+            );
+        }
     }
     Ok(())
 }
@@ -47,10 +54,9 @@ async fn check_evm_chain_health_query(
             .await
             .map_err(|e| HealthCheckError::EvmBlockNumber(key.clone(), e.to_string()))?;
 
-        // Check chain ID
+        // Check chain ID matches the configured chain
         client
-            .provider
-            .get_chain_id()
+            .verify_chain_id(&key.id)
             .await
             .map_err(|e| HealthCheckError::EvmChainId(key.clone(), e.to_string()))?;
 
@@ -92,13 +98,28 @@ async fn check_cosmos_chain_health_query(
         .await
         .map_err(|e| HealthCheckError::CosmosBlockHeight(key.clone(), e))?;
 
-    // Check node info
-    client
+    // Check node info, which includes the chain ID
+    check_cosmos_chain_id(&key, &client).await?;
+
+    Ok(())
+}
+
+/// Fails if the node is serving a different chain than it's configured for
+pub async fn check_cosmos_chain_id(
+    key: &ChainKey,
+    client: &layer_climb::querier::QueryClient,
+) -> Result<(), HealthCheckError> {
+    let node_info = client
         .node_info()
         .await
         .map_err(|e| HealthCheckError::CosmosNodeInfo(key.clone(), e))?;
 
-    Ok(())
+    match node_info.default_node_info {
+        Some(info) if info.network != key.id.as_str() => Err(
+            HealthCheckError::CosmosChainIdMismatch(key.clone(), info.network),
+        ),
+        _ => Ok(()),
+    }
 }
 
 pub async fn check_evm_chain_endpoint_health_query(
@@ -116,10 +137,9 @@ pub async fn check_evm_chain_endpoint_health_query(
         .await
         .map_err(|e| HealthCheckError::EvmBlockNumber(key.clone(), e.to_string()))?;
 
-    // Check chain ID
+    // Check chain ID matches the configured chain
     client
-        .provider
-        .get_chain_id()
+        .verify_chain_id(&key.id)
         .await
         .map_err(|e| HealthCheckError::EvmChainId(key.clone(), e.to_string()))?;
 
@@ -154,7 +174,7 @@ pub enum HealthCheckError {
     #[error("[{0}] Failed to get block number: {1}")]
     EvmBlockNumber(ChainKey, String),
 
-    #[error("[{0}] Failed to get chain ID: {1}")]
+    #[error("[{0}] Chain ID check failed: {1}")]
     EvmChainId(ChainKey, String),
 
     #[error("[{0}] Failed to get gas price: {1}")]
@@ -174,4 +194,7 @@ pub enum HealthCheckError {
 
     #[error("[{0}] node info: {1:?}")]
     CosmosNodeInfo(ChainKey, anyhow::Error),
+
+    #[error("[{0}] Node is serving chain id {1}")]
+    CosmosChainIdMismatch(ChainKey, String),
 }
@@ -0,0 +1,167 @@
+//! Proof that a health response came from the operator node itself.
+//! The node signs the response timestamp together with its own address, so a checker that
+//! knows the operator's address can tell a genuine response from a proxy replaying or faking one.
+
+use alloy_primitives::{Address, Signature};
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+/// Domain separator, so a health signature can't be passed off as any other signed message
+const SIGNED_HEALTH_PREFIX: &[u8] = b"wavs-health:";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct SignedHealth {
+    /// When the response was signed (seconds since UNIX epoch)
+    pub timestamp: u64,
+    /// Address of the node's health signing key
+    #[schema(value_type = String)]
+    pub signer: Address,
+    /// EIP-191 signature over the timestamp and signer, hex encoded
+    pub signature: String,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SignedHealthError {
+    #[error("Failed to sign health response: {0}")]
+    Sign(String),
+
+    #[error("Invalid health signature: {0}")]
+    InvalidSignature(String),
+
+    #[error("Health response signed by {recovered}, expected {expected}")]
+    WrongSigner {
+        expected: Address,
+        recovered: Address,
+    },
+
+    #[error("Health response is {age_secs}s old, older than the allowed {max_age_secs}s")]
+    Stale { age_secs: u64, max_age_secs: u64 },
+}
+
+impl SignedHealth {
+    pub fn sign(signer: &PrivateKeySigner, timestamp: u64) -> Result<Self, SignedHealthError> {
+        let signature = signer
+            .sign_message_sync(&Self::message(timestamp, signer.address()))
+            .map_err(|err| SignedHealthError::Sign(err.to_string()))?;
+
+        Ok(Self {
+            timestamp,
+            signer: signer.address(),
+            signature: const_hex::encode_prefixed(signature.as_bytes()),
+        })
+    }
+
+    /// Checks the signature was made by `expected_signer` and isn't older than `max_age_secs`,
+    /// relative to `now` (seconds since UNIX epoch)
+    pub fn verify(
+        &self,
+        expected_signer: Address,
+        now: u64,
+        max_age_secs: u64,
+    ) -> Result<(), SignedHealthError> {
+        if self.signer != expected_signer {
+            return Err(SignedHealthError::WrongSigner {
+                expected: expected_signer,
+                recovered: self.signer,
+            });
+        }
+
+        let bytes = const_hex::decode(&self.signature)
+            .map_err(|err| SignedHealthError::InvalidSignature(err.to_string()))?;
+        let recovered = Signature::from_raw(&bytes)
+            .and_then(|signature| {
+                signature.recover_address_from_msg(Self::message(self.timestamp, self.signer))
+            })
+            .map_err(|err| SignedHealthError::InvalidSignature(err.to_string()))?;
+
+        if recovered != expected_signer {
+            return Err(SignedHealthError::WrongSigner {
+                expected: expected_signer,
+                recovered,
+            });
+        }
+
+        // a timestamp from the future is as suspicious as an old one
+        let age_secs = now.abs_diff(self.timestamp);
+        if age_secs > max_age_secs {
+            return Err(SignedHealthError::Stale {
+                age_secs,
+                max_age_secs,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn message(timestamp: u64, signer: Address) -> Vec<u8> {
+        let mut message = SIGNED_HEALTH_PREFIX.to_vec();
+        message.extend_from_slice(&timestamp.to_be_bytes());
+        message.extend_from_slice(signer.as_slice());
+        message
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sign_and_verify() {
+        let signer = PrivateKeySigner::random();
+        let signed = SignedHealth::sign(&signer, 1_000).unwrap();
+
+        signed.verify(signer.address(), 1_010, 30).unwrap();
+
+        assert_eq!(
+            signed.verify(signer.address(), 1_100, 30),
+            Err(SignedHealthError::Stale {
+                age_secs: 100,
+                max_age_secs: 30
+            })
+        );
+
+        let other = PrivateKeySigner::random();
+        assert!(matches!(
+            signed.verify(other.address(), 1_010, 30),
+            Err(SignedHealthError::WrongSigner { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_tampered_payload() {
+        let signer = PrivateKeySigner::random();
+        let signed = SignedHealth::sign(&signer, 1_000).unwrap();
+
+        // a proxy bumping the timestamp to look fresh
+        let tampered = SignedHealth {
+            timestamp: 2_000,
+            ..signed.clone()
+        };
+        assert!(matches!(
+            tampered.verify(signer.address(), 2_000, 30),
+            Err(SignedHealthError::WrongSigner { .. })
+        ));
+
+        // or claiming to be the operator, with a signature of its own
+        let proxy = PrivateKeySigner::random();
+        let forged = SignedHealth {
+            signer: signer.address(),
+            ..SignedHealth::sign(&proxy, 1_000).unwrap()
+        };
+        assert!(matches!(
+            forged.verify(signer.address(), 1_000, 30),
+            Err(SignedHealthError::WrongSigner { .. })
+        ));
+
+        let mut bytes = const_hex::decode(&signed.signature).unwrap();
+        bytes[10] ^= 0xff;
+        let corrupted = SignedHealth {
+            signature: const_hex::encode(bytes),
+            ..signed
+        };
+        assert!(corrupted.verify(signer.address(), 1_000, 30).is_err());
+    }
+}
@@ -2,6 +2,7 @@
 
 pub mod alloy_helpers;
 pub mod async_transaction;
+pub mod chain_throttle;
 pub mod config;
 pub mod context;
 pub mod error;
@@ -9,12 +10,15 @@ pub mod evm_client;
 pub mod filesystem;
 pub mod health;
 pub mod http;
+pub mod logging;
 pub mod serde;
 pub mod service;
 pub mod storage;
 pub mod telemetry;
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
+pub mod time;
+pub mod tls;
 pub mod wkg;
 
 // the test version of init_tracing does not take a config
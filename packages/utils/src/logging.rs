@@ -0,0 +1,551 @@
+//! Console log output for the node, in a format picked by config.
+//!
+//! The `json` format writes one object per line for log aggregators. The fields of the spans an
+//! event happened in (like `subsys`, `service_id` and `workflow_id`) are put alongside the event's
+//! own fields at the top level, so they can be filtered on without digging through nested spans.
+//!
+//! [`ServiceLogLevels`] lets individual services log more or less than everything else, going by
+//! the service id or name recorded on events and the spans they happened in.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use tracing::{
+    callsite::rebuild_interest_cache,
+    field::{Field, Visit},
+    level_filters::LevelFilter,
+    span,
+    subscriber::Interest,
+    Event, Metadata, Subscriber,
+};
+use tracing_subscriber::{
+    filter::EnvFilter,
+    fmt::{
+        format::{self, FormatEvent, FormatFields, JsonFields},
+        time::{FormatTime, SystemTime},
+        FmtContext, FormattedFields, MakeWriter,
+    },
+    layer::Context,
+    registry::LookupSpan,
+    Layer,
+};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human readable lines, without timestamps or targets (default)
+    #[default]
+    Full,
+    /// Multi-line, for reading logs during development
+    Pretty,
+    /// Single lines, with span fields appended
+    Compact,
+    /// One JSON object per line, with span fields as top-level keys
+    Json,
+}
+
+impl LogFormat {
+    pub const ALL: [LogFormat; 4] = [
+        LogFormat::Full,
+        LogFormat::Pretty,
+        LogFormat::Compact,
+        LogFormat::Json,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogFormat::Full => "full",
+            LogFormat::Pretty => "pretty",
+            LogFormat::Compact => "compact",
+            LogFormat::Json => "json",
+        }
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!(
+                    "Unknown log format '{s}', expected one of {}",
+                    Self::ALL.map(|format| format.as_str()).join(", ")
+                )
+            })
+    }
+}
+
+/// The console logging layer for `format`, writing to stdout
+pub fn log_layer<S>(format: LogFormat) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a> + 'static,
+{
+    match format {
+        LogFormat::Full => tracing_subscriber::fmt::layer()
+            .without_time()
+            .with_target(false)
+            .boxed(),
+        LogFormat::Pretty => tracing_subscriber::fmt::layer().pretty().boxed(),
+        LogFormat::Compact => tracing_subscriber::fmt::layer().compact().boxed(),
+        LogFormat::Json => json_layer(std::io::stdout).boxed(),
+    }
+}
+
+fn json_layer<S, W>(make_writer: W) -> impl Layer<S> + Send + Sync + 'static
+where
+    S: Subscriber + for<'a> LookupSpan<'a> + 'static,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .fmt_fields(JsonFields::new())
+        .event_format(JsonLines)
+        .with_writer(make_writer)
+}
+
+/// Fields that name the service an event or span is about
+const SERVICE_FIELDS: [&str; 3] = ["service_id", "service.id", "service.name"];
+
+/// Per-service log levels, keyed by service id or name, which can be changed while running
+#[derive(Clone, Debug, Default)]
+pub struct ServiceLogLevels {
+    levels: Arc<RwLock<HashMap<String, LevelFilter>>>,
+}
+
+impl ServiceLogLevels {
+    /// Parses levels like `warn` or `debug`, keyed by service id or name
+    pub fn new<'a>(levels: impl IntoIterator<Item = (&'a String, &'a String)>) -> Result<Self> {
+        let levels = levels
+            .into_iter()
+            .map(|(service, level)| {
+                let level = level
+                    .parse::<LevelFilter>()
+                    .map_err(|err| anyhow!("Invalid log level for service {service}: {err}"))?;
+                Ok((service.clone(), level))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            levels: Arc::new(RwLock::new(levels)),
+        })
+    }
+
+    pub fn set(&self, service: impl Into<String>, level: LevelFilter) {
+        self.levels.write().unwrap().insert(service.into(), level);
+        // callsites that were switched off for good may be needed now, and vice versa
+        rebuild_interest_cache();
+    }
+
+    pub fn remove(&self, service: &str) {
+        self.levels.write().unwrap().remove(service);
+        rebuild_interest_cache();
+    }
+
+    pub fn get(&self, service: &str) -> Option<LevelFilter> {
+        self.levels.read().unwrap().get(service).copied()
+    }
+
+    /// A filtering layer that applies these levels to services, and `env_filter` to the rest
+    pub fn filter(&self, env_filter: EnvFilter) -> ServiceLogFilter {
+        ServiceLogFilter {
+            env_filter,
+            levels: self.clone(),
+        }
+    }
+
+    /// The most verbose level of any service
+    fn max_level(&self) -> Option<LevelFilter> {
+        self.levels.read().unwrap().values().max().copied()
+    }
+}
+
+/// Filters like its [`EnvFilter`], except for events logged about or within a service that has
+/// its own level in [`ServiceLogLevels`]
+pub struct ServiceLogFilter {
+    env_filter: EnvFilter,
+    levels: ServiceLogLevels,
+}
+
+impl ServiceLogFilter {
+    /// The level of the innermost service the event is about, if it has one
+    fn service_level<S>(&self, event: &Event<'_>, ctx: &Context<'_, S>) -> Option<LevelFilter>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        let levels = self.levels.levels.read().unwrap();
+        if levels.is_empty() {
+            return None;
+        }
+        let level_of =
+            |services: &ServiceNames| services.0.iter().find_map(|name| levels.get(name).copied());
+
+        let mut services = ServiceNames::default();
+        event.record(&mut services);
+        if let Some(level) = level_of(&services) {
+            return Some(level);
+        }
+
+        ctx.event_scope(event)?.find_map(|span| {
+            let extensions = span.extensions();
+            level_of(extensions.get::<ServiceNames>()?)
+        })
+    }
+}
+
+impl<S> Layer<S> for ServiceLogFilter
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        let interest = Layer::<S>::register_callsite(&self.env_filter, metadata);
+        match self.levels.max_level() {
+            Some(max_level) if !interest.is_always() && *metadata.level() <= max_level => {
+                Interest::sometimes()
+            }
+            _ => interest,
+        }
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        let hint = Layer::<S>::max_level_hint(&self.env_filter)?;
+        Some(
+            self.levels
+                .max_level()
+                .map_or(hint, |max_level| hint.max(max_level)),
+        )
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, ctx: Context<'_, S>) -> bool {
+        // spans are let through too, so the services they're about can be seen from their events
+        if self
+            .levels
+            .max_level()
+            .is_some_and(|max_level| *metadata.level() <= max_level)
+        {
+            return true;
+        }
+        self.env_filter.enabled(metadata, ctx)
+    }
+
+    fn event_enabled(&self, event: &Event<'_>, ctx: Context<'_, S>) -> bool {
+        match self.service_level(event, &ctx) {
+            Some(level) => *event.metadata().level() <= level,
+            None => self.env_filter.enabled(event.metadata(), ctx),
+        }
+    }
+
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let mut services = ServiceNames::default();
+        attrs.record(&mut services);
+        if !services.0.is_empty() {
+            if let Some(span) = ctx.span(id) {
+                span.extensions_mut().insert(services);
+            }
+        }
+        self.env_filter.on_new_span(attrs, id, ctx);
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        let mut services = ServiceNames::default();
+        values.record(&mut services);
+        if !services.0.is_empty() {
+            if let Some(span) = ctx.span(id) {
+                let mut extensions = span.extensions_mut();
+                match extensions.get_mut::<ServiceNames>() {
+                    Some(existing) => existing.0.extend(services.0),
+                    None => extensions.insert(services),
+                }
+            }
+        }
+        self.env_filter.on_record(id, values, ctx);
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        self.env_filter.on_enter(id, ctx);
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        self.env_filter.on_exit(id, ctx);
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        self.env_filter.on_close(id, ctx);
+    }
+}
+
+/// The values of an event or span's [`SERVICE_FIELDS`]
+#[derive(Default)]
+struct ServiceNames(Vec<String>);
+
+impl Visit for ServiceNames {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if SERVICE_FIELDS.contains(&field.name()) {
+            self.0.push(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if SERVICE_FIELDS.contains(&field.name()) {
+            self.0.push(format!("{value:?}"));
+        }
+    }
+}
+
+/// Formats each event as a single JSON object, flattening the fields of its spans into it
+struct JsonLines;
+
+impl<S, N> FormatEvent<S, N> for JsonLines
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: format::Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let metadata = event.metadata();
+        let mut line = Map::new();
+
+        let mut timestamp = String::new();
+        SystemTime.format_time(&mut format::Writer::new(&mut timestamp))?;
+        line.insert("timestamp".to_string(), json!(timestamp));
+        line.insert("level".to_string(), json!(metadata.level().as_str()));
+        line.insert("target".to_string(), json!(metadata.target()));
+
+        // from the root, so the innermost span wins if they share a field
+        let mut spans = Vec::new();
+        if let Some(scope) = ctx.event_scope() {
+            for span in scope.from_root() {
+                spans.push(json!(span.name()));
+
+                let extensions = span.extensions();
+                let Some(fields) = extensions.get::<FormattedFields<N>>() else {
+                    continue;
+                };
+                // span fields are already recorded as JSON by `JsonFields`
+                if let Ok(Value::Object(fields)) = serde_json::from_str::<Value>(fields) {
+                    line.extend(fields);
+                }
+            }
+        }
+        if !spans.is_empty() {
+            line.insert("spans".to_string(), Value::Array(spans));
+        }
+
+        event.record(&mut JsonVisitor(&mut line));
+
+        writeln!(writer, "{}", Value::Object(line))
+    }
+}
+
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), json!(format!("{value:?}")));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::{self, Write},
+        sync::{Arc, Mutex},
+    };
+
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Buffer {
+        type Writer = Buffer;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn json_lines_have_span_fields_at_top_level() {
+        let buffer = Buffer::default();
+        let subscriber = tracing_subscriber::registry().with(json_layer(buffer.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let dispatcher = tracing::info_span!("run_trigger", subsys = "Dispatcher");
+            let _dispatcher = dispatcher.enter();
+            let workflow =
+                tracing::info_span!("execute", service_id = "abc123", workflow_id = "default");
+            let _workflow = workflow.enter();
+
+            tracing::info!(responses = 2, "Workflow executed");
+            tracing::warn!("Slow submission");
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+
+        let line = &lines[0];
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["message"], "Workflow executed");
+        assert_eq!(line["responses"], 2);
+        assert_eq!(line["subsys"], "Dispatcher");
+        assert_eq!(line["service_id"], "abc123");
+        assert_eq!(line["workflow_id"], "default");
+        assert_eq!(line["spans"], json!(["run_trigger", "execute"]));
+        assert!(line["timestamp"].is_string());
+
+        assert_eq!(lines[1]["level"], "WARN");
+        assert_eq!(lines[1]["service_id"], "abc123");
+    }
+
+    fn messages(buffer: &Buffer) -> Vec<String> {
+        let output = std::mem::take(&mut *buffer.0.lock().unwrap());
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| {
+                let line: Value = serde_json::from_str(line).unwrap();
+                line["message"].as_str().unwrap().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn service_levels_change_at_runtime() {
+        let levels = ServiceLogLevels::new(&HashMap::from([(
+            "debug-svc".to_string(),
+            "debug".to_string(),
+        )]))
+        .unwrap();
+        let buffer = Buffer::default();
+        let subscriber = tracing_subscriber::registry()
+            .with(levels.filter(EnvFilter::new("info")))
+            .with(json_layer(buffer.clone()));
+
+        let log_all = || {
+            tracing::info_span!(
+                "execute",
+                service_id = "farmer-id",
+                service.name = "wavs-farmer"
+            )
+            .in_scope(|| {
+                tracing::info!("farmer info");
+                tracing::debug!("farmer debug");
+            });
+            tracing::info_span!("execute", service_id = "debug-svc").in_scope(|| {
+                tracing::debug!("debug-svc debug");
+                tracing::trace!("debug-svc trace");
+            });
+            tracing::debug!(service_id = "debug-svc", "debug-svc event field");
+            tracing::info!("global info");
+            tracing::debug!("global debug");
+        };
+
+        tracing::subscriber::with_default(subscriber, || {
+            log_all();
+            assert_eq!(
+                messages(&buffer),
+                [
+                    "farmer info",
+                    "debug-svc debug",
+                    "debug-svc event field",
+                    "global info"
+                ]
+            );
+
+            // quiet the farmer by name, leaving everything else alone
+            levels.set("wavs-farmer", LevelFilter::WARN);
+            log_all();
+            assert_eq!(
+                messages(&buffer),
+                ["debug-svc debug", "debug-svc event field", "global info"]
+            );
+
+            levels.remove("wavs-farmer");
+            levels.set("debug-svc", LevelFilter::TRACE);
+            log_all();
+            assert_eq!(
+                messages(&buffer),
+                [
+                    "farmer info",
+                    "debug-svc debug",
+                    "debug-svc trace",
+                    "debug-svc event field",
+                    "global info"
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn rejects_invalid_service_levels() {
+        ServiceLogLevels::new(&HashMap::from([("svc".to_string(), "loud".to_string())]))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn parses_format_names() {
+        for format in LogFormat::ALL {
+            assert_eq!(format.to_string().parse::<LogFormat>().unwrap(), format);
+        }
+        assert_eq!("JSON".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        "yaml".parse::<LogFormat>().unwrap_err();
+    }
+}
@@ -0,0 +1,102 @@
+//! The [`CAStorage`] a node keeps its component blobs in, picked from its config
+
+use wavs_types::AnyDigest;
+
+use super::{fs::FileStorage, prelude::*};
+#[cfg(feature = "s3")]
+use super::{object::ObjectCAStorage, s3::S3Storage};
+
+#[derive(Clone)]
+pub enum AnyStorage {
+    /// Blobs in a local directory
+    File(FileStorage),
+    /// Blobs in an S3-compatible bucket, which several nodes can share
+    #[cfg(feature = "s3")]
+    S3(ObjectCAStorage<S3Storage>),
+}
+
+impl From<FileStorage> for AnyStorage {
+    fn from(storage: FileStorage) -> Self {
+        Self::File(storage)
+    }
+}
+
+#[cfg(feature = "s3")]
+impl From<ObjectCAStorage<S3Storage>> for AnyStorage {
+    fn from(storage: ObjectCAStorage<S3Storage>) -> Self {
+        Self::S3(storage)
+    }
+}
+
+impl CAStorage for AnyStorage {
+    fn reset(&self) -> Result<(), CAStorageError> {
+        match self {
+            Self::File(storage) => storage.reset(),
+            #[cfg(feature = "s3")]
+            Self::S3(storage) => storage.reset(),
+        }
+    }
+
+    fn set_data(&self, data: &[u8]) -> Result<AnyDigest, CAStorageError> {
+        match self {
+            Self::File(storage) => storage.set_data(data),
+            #[cfg(feature = "s3")]
+            Self::S3(storage) => storage.set_data(data),
+        }
+    }
+
+    fn get_data(&self, digest: &AnyDigest) -> Result<Vec<u8>, CAStorageError> {
+        match self {
+            Self::File(storage) => storage.get_data(digest),
+            #[cfg(feature = "s3")]
+            Self::S3(storage) => storage.get_data(digest),
+        }
+    }
+
+    fn data_exists(&self, digest: &AnyDigest) -> Result<bool, CAStorageError> {
+        match self {
+            Self::File(storage) => storage.data_exists(digest),
+            #[cfg(feature = "s3")]
+            Self::S3(storage) => storage.data_exists(digest),
+        }
+    }
+
+    fn remove_data(&self, digest: &AnyDigest) -> Result<(), CAStorageError> {
+        match self {
+            Self::File(storage) => storage.remove_data(digest),
+            #[cfg(feature = "s3")]
+            Self::S3(storage) => storage.remove_data(digest),
+        }
+    }
+
+    fn digests(
+        &self,
+    ) -> Result<impl Iterator<Item = Result<AnyDigest, CAStorageError>>, CAStorageError> {
+        let digests: Box<dyn Iterator<Item = Result<AnyDigest, CAStorageError>> + '_> = match self {
+            Self::File(storage) => Box::new(storage.digests()?),
+            #[cfg(feature = "s3")]
+            Self::S3(storage) => Box::new(storage.digests()?),
+        };
+        Ok(digests)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::storage::tests::castorage;
+
+    #[test]
+    fn test_file_set_and_get() {
+        let dir = tempdir().unwrap();
+        castorage::test_set_and_get(AnyStorage::from(FileStorage::new(dir.path()).unwrap()));
+    }
+
+    #[test]
+    fn test_file_list_digests() {
+        let dir = tempdir().unwrap();
+        castorage::test_list_digests(AnyStorage::from(FileStorage::new(dir.path()).unwrap()));
+    }
+}
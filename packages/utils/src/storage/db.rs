@@ -15,6 +15,8 @@ pub struct WavsDb {
     pub aggregator_services: WavsDbTable<ServiceId, ()>,
     pub quorum_queues: WavsDbTable<QuorumQueueId, QuorumQueue>,
     pub kv_store: WavsDbTable<String, Vec<u8>>,
+    /// When a `kv_store` key expires (seconds since UNIX epoch), for keys written with a ttl
+    pub kv_expiry: WavsDbTable<String, u64>,
     pub kv_atomics_counter: WavsDbTable<String, i64>,
 }
 
@@ -29,6 +31,7 @@ impl WavsDb {
             aggregator_services: WavsDbTable::new()?,
             quorum_queues: WavsDbTable::new()?,
             kv_store: WavsDbTable::new()?,
+            kv_expiry: WavsDbTable::new()?,
             kv_atomics_counter: WavsDbTable::new()?,
         })
     }
@@ -112,6 +115,39 @@ where
             inner: self.inner.iter(),
         }
     }
+
+    /// Read-modify-write a single entry while holding its lock.
+    /// The entry is removed if `update_fn` leaves `None` behind
+    pub fn update<T, F>(&self, key: K, update_fn: F) -> T
+    where
+        F: FnOnce(&mut Option<V>) -> T,
+    {
+        use dashmap::mapref::entry::Entry;
+
+        match self.inner.entry(key) {
+            Entry::Occupied(mut entry) => {
+                let mut value = Some(entry.get().clone());
+                let result = update_fn(&mut value);
+                match value {
+                    Some(value) => {
+                        entry.insert(value);
+                    }
+                    None => {
+                        entry.remove();
+                    }
+                }
+                result
+            }
+            Entry::Vacant(entry) => {
+                let mut value = None;
+                let result = update_fn(&mut value);
+                if let Some(value) = value {
+                    entry.insert(value);
+                }
+                result
+            }
+        }
+    }
 }
 
 impl<K, V> WavsDbTable<K, V>
@@ -276,6 +312,8 @@ mod tests {
                 chain: "evm:anvil".parse().unwrap(),
                 address: alloy_primitives::Address::ZERO,
             },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
         };
 
         assert!(db.services.get_cloned(&service_id).is_none());
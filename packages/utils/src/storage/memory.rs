@@ -58,6 +58,13 @@ impl CAStorage for MemoryStorage {
         Ok(tree.get(digest).is_some())
     }
 
+    #[instrument(skip(self), fields(subsys = "CaStorage"))]
+    fn remove_data(&self, digest: &AnyDigest) -> Result<(), CAStorageError> {
+        let mut tree = self.data.write()?;
+        tree.remove(digest);
+        Ok(())
+    }
+
     #[instrument(skip(self), fields(subsys = "CaStorage"))]
     fn digests(
         &self,
@@ -86,6 +93,12 @@ mod tests {
         castorage::test_reset(store);
     }
 
+    #[test]
+    fn test_remove() {
+        let store = MemoryStorage::new();
+        castorage::test_remove(store);
+    }
+
     #[test]
     fn test_multiple_keys() {
         let store = MemoryStorage::new();
@@ -1,9 +1,13 @@
 mod prelude;
 pub use prelude::*;
 
+pub mod any;
 pub mod db;
 pub mod fs;
 pub mod memory;
+pub mod object;
+#[cfg(feature = "s3")]
+pub mod s3;
 
 #[cfg(test)]
 mod tests;
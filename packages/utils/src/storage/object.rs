@@ -0,0 +1,121 @@
+//! Component blobs in object storage, so several nodes can share one store.
+//!
+//! An [`ObjectStorage`] only needs to get, put and check for keys. [`ObjectCAStorage`] turns
+//! one into a [`CAStorage`], keyed by digest, which is what `Dispatcher<S>` and the engines
+//! are generic over.
+
+use tracing::instrument;
+use wavs_types::AnyDigest;
+
+use super::prelude::*;
+
+/// A flat key-value blob store, e.g. an S3 bucket
+pub trait ObjectStorage: Send + Sync + Clone {
+    /// Stores `data` under `key`, replacing anything already there
+    fn put_object(&self, key: &str, data: &[u8]) -> Result<(), CAStorageError>;
+
+    /// `None` if nothing is stored under `key`
+    fn get_object(&self, key: &str) -> Result<Option<Vec<u8>>, CAStorageError>;
+
+    fn object_exists(&self, key: &str) -> Result<bool, CAStorageError>;
+}
+
+#[derive(Clone)]
+pub struct ObjectCAStorage<O: ObjectStorage> {
+    objects: O,
+}
+
+impl<O: ObjectStorage> ObjectCAStorage<O> {
+    pub fn new(objects: O) -> Self {
+        Self { objects }
+    }
+
+    fn key(digest: &AnyDigest) -> String {
+        digest.to_string()
+    }
+}
+
+impl<O: ObjectStorage> CAStorage for ObjectCAStorage<O> {
+    /// The store is shared with other nodes, so this node doesn't get to wipe it
+    fn reset(&self) -> Result<(), CAStorageError> {
+        Err(CAStorageError::Other(
+            "reset is not supported on object storage".to_string(),
+        ))
+    }
+
+    #[instrument(skip(self, data), fields(subsys = "CaStorage"))]
+    fn set_data(&self, data: &[u8]) -> Result<AnyDigest, CAStorageError> {
+        let digest = AnyDigest::hash(data);
+        let key = Self::key(&digest);
+        if !self.objects.object_exists(&key)? {
+            self.objects.put_object(&key, data)?;
+        }
+        Ok(digest)
+    }
+
+    #[instrument(skip(self), fields(subsys = "CaStorage"))]
+    fn get_data(&self, digest: &AnyDigest) -> Result<Vec<u8>, CAStorageError> {
+        self.objects
+            .get_object(&Self::key(digest))?
+            .ok_or_else(|| CAStorageError::NotFound(digest.clone()))
+    }
+
+    #[instrument(skip(self), fields(subsys = "CaStorage"))]
+    fn data_exists(&self, digest: &AnyDigest) -> Result<bool, CAStorageError> {
+        self.objects.object_exists(&Self::key(digest))
+    }
+
+    /// Other nodes may still be running the component, so blobs are left in place
+    #[instrument(skip(self), fields(subsys = "CaStorage"))]
+    fn remove_data(&self, digest: &AnyDigest) -> Result<(), CAStorageError> {
+        tracing::debug!("Leaving {} in shared object storage", digest);
+        Ok(())
+    }
+
+    fn digests(
+        &self,
+    ) -> Result<impl Iterator<Item = Result<AnyDigest, CAStorageError>>, CAStorageError> {
+        Err::<std::iter::Empty<_>, _>(CAStorageError::Other(
+            "listing digests is not supported on object storage".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    use super::*;
+    use crate::storage::tests::castorage;
+
+    #[derive(Clone, Default)]
+    struct MemoryObjects(Arc<Mutex<HashMap<String, Vec<u8>>>>);
+
+    impl ObjectStorage for MemoryObjects {
+        fn put_object(&self, key: &str, data: &[u8]) -> Result<(), CAStorageError> {
+            self.0.lock()?.insert(key.to_string(), data.to_vec());
+            Ok(())
+        }
+
+        fn get_object(&self, key: &str) -> Result<Option<Vec<u8>>, CAStorageError> {
+            Ok(self.0.lock()?.get(key).cloned())
+        }
+
+        fn object_exists(&self, key: &str) -> Result<bool, CAStorageError> {
+            Ok(self.0.lock()?.contains_key(key))
+        }
+    }
+
+    #[test]
+    fn test_set_and_get() {
+        castorage::test_set_and_get(ObjectCAStorage::new(MemoryObjects::default()));
+    }
+
+    #[test]
+    fn test_multiple_keys() {
+        castorage::test_multiple_keys(ObjectCAStorage::new(MemoryObjects::default()));
+    }
+}
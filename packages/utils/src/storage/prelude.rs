@@ -38,6 +38,10 @@ pub trait CAStorage: Send + Sync + Clone {
     /// Check if the data has been stored
     fn data_exists(&self, digest: &AnyDigest) -> Result<bool, CAStorageError>;
 
+    /// Removes the data for a given digest. Removing data that isn't stored is a no-op.
+    /// The caller is responsible for making sure nothing still references it.
+    fn remove_data(&self, digest: &AnyDigest) -> Result<(), CAStorageError>;
+
     fn digests(
         &self,
     ) -> Result<impl Iterator<Item = Result<AnyDigest, CAStorageError>>, CAStorageError>;
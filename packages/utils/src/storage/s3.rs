@@ -0,0 +1,241 @@
+//! [`ObjectStorage`] on an S3-compatible bucket (AWS, MinIO, localstack, ...)
+
+use std::future::Future;
+
+use aws_sdk_s3::{
+    config::{BehaviorVersion, Credentials, Region},
+    primitives::ByteStream,
+    Client,
+};
+use serde::{Deserialize, Serialize};
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tracing::instrument;
+use wavs_types::Credential;
+
+use super::{object::ObjectStorage, prelude::*};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct S3Config {
+    pub endpoint: String,
+    pub bucket: String,
+    #[serde(default = "S3Config::default_region")]
+    pub region: String,
+    pub access_key_id: Credential,
+    pub secret_access_key: Credential,
+    /// Prepended to every key, so one bucket can hold several deployments
+    #[serde(default)]
+    pub prefix: String,
+}
+
+impl S3Config {
+    fn default_region() -> String {
+        "us-east-1".to_string()
+    }
+}
+
+#[derive(Clone)]
+pub struct S3Storage {
+    client: Client,
+    bucket: String,
+    prefix: String,
+}
+
+impl S3Storage {
+    pub fn new(config: S3Config) -> Self {
+        let credentials = Credentials::new(
+            config.access_key_id.as_str(),
+            config.secret_access_key.as_str(),
+            None,
+            None,
+            "wavs",
+        );
+
+        let sdk_config = aws_sdk_s3::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .endpoint_url(config.endpoint)
+            .region(Region::new(config.region))
+            .credentials_provider(credentials)
+            // S3-compatible servers generally don't do virtual-hosted buckets
+            .force_path_style(true)
+            .build();
+
+        Self {
+            client: Client::from_conf(sdk_config),
+            bucket: config.bucket,
+            prefix: config.prefix,
+        }
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+}
+
+impl ObjectStorage for S3Storage {
+    #[instrument(skip(self, data), fields(subsys = "CaStorage"))]
+    fn put_object(&self, key: &str, data: &[u8]) -> Result<(), CAStorageError> {
+        block_on(
+            self.client
+                .put_object()
+                .bucket(&self.bucket)
+                .key(self.key(key))
+                .body(ByteStream::from(data.to_vec()))
+                .send(),
+        )
+        .map_err(|err| CAStorageError::Other(format!("S3 put {key}: {err}")))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(subsys = "CaStorage"))]
+    fn get_object(&self, key: &str) -> Result<Option<Vec<u8>>, CAStorageError> {
+        block_on(async {
+            let output = match self
+                .client
+                .get_object()
+                .bucket(&self.bucket)
+                .key(self.key(key))
+                .send()
+                .await
+            {
+                Ok(output) => output,
+                Err(err)
+                    if err
+                        .as_service_error()
+                        .is_some_and(|err| err.is_no_such_key()) =>
+                {
+                    return Ok(None)
+                }
+                Err(err) => return Err(CAStorageError::Other(format!("S3 get {key}: {err}"))),
+            };
+
+            let data = output
+                .body
+                .collect()
+                .await
+                .map_err(|err| CAStorageError::Other(format!("S3 get {key}: {err}")))?;
+
+            Ok(Some(data.to_vec()))
+        })
+    }
+
+    #[instrument(skip(self), fields(subsys = "CaStorage"))]
+    fn object_exists(&self, key: &str) -> Result<bool, CAStorageError> {
+        match block_on(
+            self.client
+                .head_object()
+                .bucket(&self.bucket)
+                .key(self.key(key))
+                .send(),
+        ) {
+            Ok(_) => Ok(true),
+            Err(err) if err.as_service_error().is_some_and(|err| err.is_not_found()) => Ok(false),
+            Err(err) => Err(CAStorageError::Other(format!("S3 head {key}: {err}"))),
+        }
+    }
+}
+
+/// `CAStorage` is sync, and the node calls it from its multi-threaded runtime, where the
+/// worker can step aside while the request runs. Anywhere else gets a runtime of its own
+fn block_on<F>(future: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(|| handle.block_on(future))
+        }
+        _ => std::thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                        .expect("failed to build runtime for S3 request")
+                        .block_on(future)
+                })
+                .join()
+                .expect("S3 request panicked")
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use axum::{
+        body::Bytes,
+        extract::{Path, State},
+        http::StatusCode,
+        routing::get,
+        Router,
+    };
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::storage::{object::ObjectCAStorage, tests::castorage};
+
+    type Objects = Arc<Mutex<HashMap<(String, String), Vec<u8>>>>;
+
+    /// Just enough of the S3 api, path-style, for get/put/head
+    async fn mock_s3() -> String {
+        async fn get_object(
+            State(objects): State<Objects>,
+            Path((bucket, key)): Path<(String, String)>,
+        ) -> Result<Vec<u8>, (StatusCode, &'static str)> {
+            objects.lock().await.get(&(bucket, key)).cloned().ok_or((
+                StatusCode::NOT_FOUND,
+                "<Error><Code>NoSuchKey</Code><Message>not found</Message></Error>",
+            ))
+        }
+
+        async fn put_object(
+            State(objects): State<Objects>,
+            Path((bucket, key)): Path<(String, String)>,
+            body: Bytes,
+        ) -> StatusCode {
+            objects.lock().await.insert((bucket, key), body.to_vec());
+            StatusCode::OK
+        }
+
+        let router = Router::new()
+            .route("/{bucket}/{*key}", get(get_object).put(put_object))
+            .with_state(Objects::default());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        format!("http://{addr}")
+    }
+
+    fn storage(endpoint: String) -> ObjectCAStorage<S3Storage> {
+        ObjectCAStorage::new(S3Storage::new(S3Config {
+            endpoint,
+            bucket: "components".to_string(),
+            region: S3Config::default_region(),
+            access_key_id: Credential::new("test".to_string()),
+            secret_access_key: Credential::new("test".to_string()),
+            prefix: "node-1/".to_string(),
+        }))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_set_and_get() {
+        castorage::test_set_and_get(storage(mock_s3().await));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_multiple_keys() {
+        let store = storage(mock_s3().await);
+        let digest = store.set_data(b"hello world").unwrap();
+        assert!(store.data_exists(&digest).unwrap());
+        assert!(!store
+            .data_exists(&wavs_types::AnyDigest::hash(b"missing"))
+            .unwrap());
+
+        castorage::test_multiple_keys(store);
+    }
+}
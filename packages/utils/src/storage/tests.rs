@@ -22,6 +22,22 @@ pub mod castorage {
         assert!(matches!(err, CAStorageError::NotFound(_)));
     }
 
+    pub fn test_remove<S: CAStorage>(store: S) {
+        let data1 = b"hello world";
+        let data2 = b"hello mom";
+        let digest1 = store.set_data(data1).unwrap();
+        let digest2 = store.set_data(data2).unwrap();
+
+        store.remove_data(&digest1).unwrap();
+        assert!(!store.data_exists(&digest1).unwrap());
+        let err = store.get_data(&digest1).unwrap_err();
+        assert!(matches!(err, CAStorageError::NotFound(_)));
+
+        // other data is untouched, and removing again is fine
+        assert_eq!(data2, store.get_data(&digest2).unwrap().as_slice());
+        store.remove_data(&digest1).unwrap();
+    }
+
     pub fn test_multiple_keys<S: CAStorage>(store: S) {
         let data1 = b"hello world";
         let data2 = b"hello mom";
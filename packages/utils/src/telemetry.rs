@@ -11,15 +11,18 @@ use opentelemetry_sdk::{
     resource::Resource,
     trace::{self, Sampler, SdkTracerProvider},
 };
-use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::{layer::SubscriberExt, Layer};
 use wavs_types::{ChainKey, Service, WorkflowId};
 
+use crate::logging::{log_layer, LogFormat};
+
 const DEFAULT_PROMETHEUS_PUSH_INTERVAL: u64 = 30; // seconds
 
 pub fn setup_tracing(
     collector: &str,
     service_name: &str,
-    filters: tracing_subscriber::EnvFilter,
+    filters: impl Layer<tracing_subscriber::Registry> + Send + Sync + 'static,
+    log_format: LogFormat,
 ) -> SdkTracerProvider {
     global::set_text_map_propagator(opentelemetry_jaeger_propagator::Propagator::new());
     let endpoint = format!("{collector}/v1/traces");
@@ -44,9 +47,17 @@ pub fn setup_tracing(
     let tracer = provider.tracer(format!("{service_name}-tracer"));
     let telemetry = tracing_opentelemetry::layer().with_tracer(tracer);
 
+    // console logging layer, with line numbers to help match logs up with traces
+    let console = match log_format {
+        LogFormat::Full => tracing_subscriber::fmt::layer()
+            .with_line_number(true)
+            .boxed(),
+        format => log_layer(format),
+    };
+
     let subscriber = tracing_subscriber::Registry::default()
         .with(filters)
-        .with(tracing_subscriber::fmt::layer().with_line_number(true)) // console logging layer
+        .with(console)
         .with(telemetry);
 
     tracing::subscriber::set_global_default(subscriber)
@@ -181,6 +192,8 @@ pub struct WavsMetrics {
     pub submission: SubmissionMetrics,
     pub trigger: TriggerMetrics,
     pub aggregator: AggregatorMetrics,
+    pub chain: ChainMetrics,
+    pub pipeline: PipelineMetrics,
 }
 
 impl WavsMetrics {
@@ -190,7 +203,9 @@ impl WavsMetrics {
             dispatcher: DispatcherMetrics::new(meter.clone()),
             submission: SubmissionMetrics::new(meter.clone()),
             trigger: TriggerMetrics::new(meter.clone()),
-            aggregator: AggregatorMetrics::new(meter),
+            aggregator: AggregatorMetrics::new(meter.clone()),
+            chain: ChainMetrics::new(meter.clone()),
+            pipeline: PipelineMetrics::new(meter),
         }
     }
 }
@@ -205,6 +220,8 @@ pub struct EngineMetrics {
     pub operator_executions_failed: Counter<u64>,
     pub aggregator_executions_success: Counter<u64>,
     pub aggregator_executions_failed: Counter<u64>,
+    pub execution_outcomes: Counter<u64>,
+    pub queued_executions: UpDownCounter<i64>,
 }
 
 impl EngineMetrics {
@@ -253,21 +270,38 @@ impl EngineMetrics {
                 .u64_counter(format!("{}.aggregator_executions_failed", Self::NAMESPACE))
                 .with_description("Failed WASM operator executions")
                 .build(),
+            execution_outcomes: meter
+                .u64_counter(format!("{}.execution_outcomes", Self::NAMESPACE))
+                .with_description(
+                    "WASM executions by outcome (success, application_error, trapped, etc.)",
+                )
+                .build(),
+            queued_executions: meter
+                .i64_up_down_counter(format!("{}.queued_executions", Self::NAMESPACE))
+                .with_description("Operator executions waiting for a free slot, by priority")
+                .build(),
         }
     }
 
+    pub fn record_queued_executions(&self, priority: &str, delta: i64) {
+        self.queued_executions
+            .add(delta, &[KeyValue::new("priority", priority.to_owned())]);
+    }
+
     pub fn increment_total_errors(&self, error: &str) {
         self.total_errors
             .add(1, &[KeyValue::new("error", error.to_owned())]);
     }
 
+    /// `fuel` is `None` when the engine isn't metering it
     pub fn record_operator_execution(
         &self,
         duration: f64,
-        fuel: u64,
+        fuel: Option<u64>,
         service_id: &str,
         workflow_id: &str,
         success: bool,
+        outcome: &str,
     ) {
         let labels = &[
             KeyValue::new("service_id", service_id.to_owned()),
@@ -275,7 +309,18 @@ impl EngineMetrics {
         ];
 
         self.execution_duration.record(duration, labels);
-        self.fuel_consumption.record(fuel, labels);
+        if let Some(fuel) = fuel {
+            self.fuel_consumption.record(fuel, labels);
+        }
+        self.execution_outcomes.add(
+            1,
+            &[
+                KeyValue::new("service_id", service_id.to_owned()),
+                KeyValue::new("workflow_id", workflow_id.to_owned()),
+                KeyValue::new("kind", "operator"),
+                KeyValue::new("outcome", outcome.to_owned()),
+            ],
+        );
 
         if success {
             self.operator_executions_success.add(1, labels);
@@ -291,6 +336,7 @@ impl EngineMetrics {
         service_id: &str,
         workflow_id: &str,
         success: bool,
+        outcome: &str,
     ) {
         let labels = &[
             KeyValue::new("service_id", service_id.to_owned()),
@@ -299,6 +345,15 @@ impl EngineMetrics {
 
         self.execution_duration.record(duration, labels);
         self.fuel_consumption.record(fuel, labels);
+        self.execution_outcomes.add(
+            1,
+            &[
+                KeyValue::new("service_id", service_id.to_owned()),
+                KeyValue::new("workflow_id", workflow_id.to_owned()),
+                KeyValue::new("kind", "aggregator"),
+                KeyValue::new("outcome", outcome.to_owned()),
+            ],
+        );
 
         if success {
             self.aggregator_executions_success.add(1, labels);
@@ -363,6 +418,12 @@ pub struct SubmissionMetrics {
 
     dispatch_error_count: Counter<u64>,
     dispatch_error_count_raw: Arc<AtomicU64>,
+
+    target_delivery_count: Counter<u64>,
+    target_delivery_count_raw: Arc<AtomicU64>,
+
+    target_delivery_error_count: Counter<u64>,
+    target_delivery_error_count_raw: Arc<AtomicU64>,
 }
 
 impl SubmissionMetrics {
@@ -399,6 +460,20 @@ impl SubmissionMetrics {
                 .with_description("Total number of submissions failed on dispatched to aggregator")
                 .build(),
             dispatch_error_count_raw: Arc::new(AtomicU64::new(0)),
+
+            target_delivery_count: meter
+                .u64_counter(format!("{}.target_delivery_count", Self::NAMESPACE))
+                .with_description("Total number of signed results delivered to off-chain submit targets")
+                .build(),
+            target_delivery_count_raw: Arc::new(AtomicU64::new(0)),
+
+            target_delivery_error_count: meter
+                .u64_counter(format!("{}.target_delivery_error_count", Self::NAMESPACE))
+                .with_description(
+                    "Total number of signed results that failed delivery to off-chain submit targets after all retries",
+                )
+                .build(),
+            target_delivery_error_count_raw: Arc::new(AtomicU64::new(0)),
         }
     }
 
@@ -467,6 +542,44 @@ impl SubmissionMetrics {
             .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
     }
 
+    pub fn increment_target_delivery_count(
+        &self,
+        service: &Service,
+        workflow_id: &WorkflowId,
+        target: &str,
+    ) {
+        self.target_delivery_count.add(
+            1,
+            &[
+                KeyValue::new("service_name", service.name.clone()),
+                KeyValue::new("service_id", service.id().to_string()),
+                KeyValue::new("workflow_id", workflow_id.to_string()),
+                KeyValue::new("target", target.to_string()),
+            ],
+        );
+        self.target_delivery_count_raw
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub fn increment_target_delivery_error_count(
+        &self,
+        service: &Service,
+        workflow_id: &WorkflowId,
+        target: &str,
+    ) {
+        self.target_delivery_error_count.add(
+            1,
+            &[
+                KeyValue::new("service_name", service.name.clone()),
+                KeyValue::new("service_id", service.id().to_string()),
+                KeyValue::new("workflow_id", workflow_id.to_string()),
+                KeyValue::new("target", target.to_string()),
+            ],
+        );
+        self.target_delivery_error_count_raw
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+
     pub fn get_request_count(&self) -> u64 {
         self.request_count_raw
             .load(std::sync::atomic::Ordering::Relaxed)
@@ -491,13 +604,25 @@ impl SubmissionMetrics {
         self.dispatch_error_count_raw
             .load(std::sync::atomic::Ordering::Relaxed)
     }
+
+    pub fn get_target_delivery_count(&self) -> u64 {
+        self.target_delivery_count_raw
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    pub fn get_target_delivery_error_count(&self) -> u64 {
+        self.target_delivery_error_count_raw
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct TriggerMetrics {
     pub total_errors: Counter<u64>,
     pub triggers_fired: Counter<u64>,
+    pub evm_logs_removed: Counter<u64>,
     pub sent_dispatcher_command_latency: Histogram<f64>,
+    pub dispatcher_channel_full: Counter<u64>,
 }
 
 impl TriggerMetrics {
@@ -513,6 +638,10 @@ impl TriggerMetrics {
                 .u64_counter(format!("{}.triggers_fired", Self::NAMESPACE))
                 .with_description("Total triggers fired")
                 .build(),
+            evm_logs_removed: meter
+                .u64_counter(format!("{}.evm_logs_removed", Self::NAMESPACE))
+                .with_description("EVM logs retracted by a reorg")
+                .build(),
             sent_dispatcher_command_latency: meter
                 .f64_histogram(format!(
                     "{}.sent_dispatcher_command_latency_seconds",
@@ -521,6 +650,10 @@ impl TriggerMetrics {
                 .with_description("Time taken to send command to dispatcher")
                 .with_boundaries(vec![0.001, 0.01, 0.05, 0.1, 0.2, 0.5, 1.0])
                 .build(),
+            dispatcher_channel_full: meter
+                .u64_counter(format!("{}.dispatcher_channel_full", Self::NAMESPACE))
+                .with_description("Commands that had to wait for room in the dispatcher channel")
+                .build(),
         }
     }
 
@@ -539,9 +672,18 @@ impl TriggerMetrics {
         );
     }
 
+    pub fn record_evm_log_removed(&self, chain: &ChainKey) {
+        self.evm_logs_removed
+            .add(1, &[KeyValue::new("chain", chain.to_string())]);
+    }
+
     pub fn record_trigger_sent_dispatcher_command(&self, duration: f64) {
         self.sent_dispatcher_command_latency.record(duration, &[]);
     }
+
+    pub fn increment_dispatcher_channel_full(&self) {
+        self.dispatcher_channel_full.add(1, &[]);
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -557,6 +699,12 @@ pub struct AggregatorMetrics {
 
     submit_count: Counter<u64>,
     submit_count_raw: Arc<AtomicU64>,
+
+    skipped_count: Counter<u64>,
+    skipped_count_raw: Arc<AtomicU64>,
+
+    observed_count: Counter<u64>,
+    observed_count_raw: Arc<AtomicU64>,
 }
 
 impl AggregatorMetrics {
@@ -587,6 +735,22 @@ impl AggregatorMetrics {
                 .with_description("Total submissions sent by aggregator")
                 .build(),
             submit_count_raw: Arc::new(AtomicU64::new(0)),
+
+            skipped_count: meter
+                .u64_counter(format!("{}.skipped_count", Self::NAMESPACE))
+                .with_description(
+                    "Total submissions skipped because the submit condition reported them as already on-chain",
+                )
+                .build(),
+            skipped_count_raw: Arc::new(AtomicU64::new(0)),
+
+            observed_count: meter
+                .u64_counter(format!("{}.observed_count", Self::NAMESPACE))
+                .with_description(
+                    "Total submissions recorded instead of sent because the workflow is observe-only",
+                )
+                .build(),
+            observed_count_raw: Arc::new(AtomicU64::new(0)),
         }
     }
 
@@ -642,6 +806,32 @@ impl AggregatorMetrics {
             .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
     }
 
+    pub fn increment_skipped_count(&self, service: &Service, workflow_id: &WorkflowId) {
+        self.skipped_count.add(
+            1,
+            &[
+                KeyValue::new("service_name", service.name.clone()),
+                KeyValue::new("service_id", service.id().to_string()),
+                KeyValue::new("workflow_id", workflow_id.to_string()),
+            ],
+        );
+        self.skipped_count_raw
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub fn increment_observed_count(&self, service: &Service, workflow_id: &WorkflowId) {
+        self.observed_count.add(
+            1,
+            &[
+                KeyValue::new("service_name", service.name.clone()),
+                KeyValue::new("service_id", service.id().to_string()),
+                KeyValue::new("workflow_id", workflow_id.to_string()),
+            ],
+        );
+        self.observed_count_raw
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+
     pub fn get_receive_count(&self) -> u64 {
         self.receive_count_raw
             .load(std::sync::atomic::Ordering::Relaxed)
@@ -661,4 +851,145 @@ impl AggregatorMetrics {
         self.submit_count_raw
             .load(std::sync::atomic::Ordering::Relaxed)
     }
+
+    pub fn get_skipped_count(&self) -> u64 {
+        self.skipped_count_raw
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    pub fn get_observed_count(&self) -> u64 {
+        self.observed_count_raw
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ChainMetrics {
+    rpc_throttled: Counter<u64>,
+    rpc_throttled_raw: Arc<AtomicU64>,
+}
+
+impl ChainMetrics {
+    pub const NAMESPACE: &'static str = "chain";
+
+    pub fn new(meter: Meter) -> Self {
+        Self {
+            rpc_throttled: meter
+                .u64_counter(format!("{}.rpc_throttled", Self::NAMESPACE))
+                .with_description(
+                    "Total chain operations that had to wait for the per-chain in-flight limit",
+                )
+                .build(),
+            rpc_throttled_raw: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn increment_rpc_throttled(&self, chain: &ChainKey) {
+        self.rpc_throttled
+            .add(1, &[KeyValue::new("chain", chain.to_string())]);
+        self.rpc_throttled_raw
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub fn get_rpc_throttled(&self) -> u64 {
+        self.rpc_throttled_raw
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+}
+
+/// Follows a trigger through the node, from the trigger stream to the aggregator.
+/// Every metric carries the service, and the chain the trigger came from (empty for triggers
+/// without one, like cron) so a stage can be compared across services and chains
+#[derive(Clone, Debug)]
+pub struct PipelineMetrics {
+    triggers_received: Counter<u64>,
+    execution_duration: Histogram<f64>,
+    submissions_attempted: Counter<u64>,
+    submissions_succeeded: Counter<u64>,
+    submissions_failed: Counter<u64>,
+    aggregation_queue_depth: Gauge<u64>,
+}
+
+impl PipelineMetrics {
+    pub const NAMESPACE: &'static str = "pipeline";
+
+    pub fn new(meter: Meter) -> Self {
+        Self {
+            triggers_received: meter
+                .u64_counter(format!("{}.triggers_received", Self::NAMESPACE))
+                .with_description("Trigger actions received by the dispatcher")
+                .build(),
+            execution_duration: meter
+                .f64_histogram(format!("{}.execution_seconds", Self::NAMESPACE))
+                .with_description("Operator component execution duration in seconds")
+                .with_boundaries(vec![0.001, 0.01, 0.1, 0.5, 1.0, 5.0, 10.0])
+                .build(),
+            submissions_attempted: meter
+                .u64_counter(format!("{}.submissions_attempted", Self::NAMESPACE))
+                .with_description("Operator responses handed to the submission manager")
+                .build(),
+            submissions_succeeded: meter
+                .u64_counter(format!("{}.submissions_succeeded", Self::NAMESPACE))
+                .with_description("Operator responses signed and sent on to the aggregator")
+                .build(),
+            submissions_failed: meter
+                .u64_counter(format!("{}.submissions_failed", Self::NAMESPACE))
+                .with_description("Operator responses that failed to sign or send")
+                .build(),
+            aggregation_queue_depth: meter
+                .u64_gauge(format!("{}.aggregation_queue_depth", Self::NAMESPACE))
+                .with_description(
+                    "Signatures waiting for quorum in the most recently updated queue",
+                )
+                .build(),
+        }
+    }
+
+    fn labels(service_id: &str, chain: Option<&ChainKey>) -> [KeyValue; 2] {
+        [
+            KeyValue::new("service_id", service_id.to_owned()),
+            KeyValue::new("chain", chain.map(|c| c.to_string()).unwrap_or_default()),
+        ]
+    }
+
+    pub fn record_trigger_received(&self, service_id: &str, chain: Option<&ChainKey>) {
+        self.triggers_received
+            .add(1, &Self::labels(service_id, chain));
+    }
+
+    pub fn record_execution(&self, duration: f64, service_id: &str, chain: Option<&ChainKey>) {
+        self.execution_duration
+            .record(duration, &Self::labels(service_id, chain));
+    }
+
+    pub fn increment_submissions_attempted(&self, service_id: &str, chain: Option<&ChainKey>) {
+        self.submissions_attempted
+            .add(1, &Self::labels(service_id, chain));
+    }
+
+    pub fn increment_submissions_succeeded(&self, service_id: &str, chain: Option<&ChainKey>) {
+        self.submissions_succeeded
+            .add(1, &Self::labels(service_id, chain));
+    }
+
+    pub fn increment_submissions_failed(&self, service_id: &str, chain: Option<&ChainKey>) {
+        self.submissions_failed
+            .add(1, &Self::labels(service_id, chain));
+    }
+
+    pub fn record_aggregation_queue_depth(
+        &self,
+        depth: usize,
+        service_id: &str,
+        chain: Option<&ChainKey>,
+    ) {
+        self.aggregation_queue_depth
+            .record(depth as u64, &Self::labels(service_id, chain));
+    }
+}
+
+impl Default for PipelineMetrics {
+    fn default() -> Self {
+        Self::new(global::meter("wavs_metrics"))
+    }
 }
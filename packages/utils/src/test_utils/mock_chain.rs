@@ -0,0 +1,535 @@
+//! An in-memory EVM chain that answers JSON-RPC calls the way a node would, so anything built
+//! on an alloy provider (trigger backfill, block timestamps, signing clients) can run against it
+//! without Docker or anvil.
+//!
+//! Blocks are only added by [`MockEvmChain::push_block`], or when a transaction is sent, which
+//! is mined into a block of its own right away. Logs from [`MockEvmChain::emit_log`] go into the
+//! next block. Like many nodes, the logs it returns don't carry the block timestamp.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use alloy_consensus::{Transaction, TxEnvelope};
+use alloy_json_rpc::{
+    ErrorPayload, RequestPacket, Response, ResponsePacket, ResponsePayload, SerializedRequest,
+};
+use alloy_network::eip2718::Decodable2718;
+use alloy_primitives::{keccak256, Address, Bytes, LogData, B256, U256, U64};
+use alloy_provider::{DynProvider, ProviderBuilder};
+use alloy_rpc_client::RpcClient;
+use alloy_rpc_types_eth::{Block, BlockNumberOrTag, BlockTransactions, Filter, Header, Log};
+use alloy_transport::{TransportError, TransportFut};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use tower::Service;
+use wavs_types::ChainKey;
+
+/// Gas price, base fee and priority fee reported for every block
+const GAS_PRICE: u128 = 1_000_000_000;
+/// Timestamp of the genesis block, later blocks are 12 seconds apart unless pushed at a time
+const GENESIS_TIMESTAMP: u64 = 1_700_000_000;
+
+#[derive(Clone)]
+pub struct MockEvmChain {
+    chain_id: u64,
+    state: Arc<Mutex<MockChainState>>,
+}
+
+#[derive(Default)]
+struct MockChainState {
+    blocks: Vec<MockBlock>,
+    pending_logs: Vec<alloy_primitives::Log>,
+    transactions: Vec<SentTransaction>,
+    calls: HashMap<(Address, [u8; 4]), Bytes>,
+}
+
+struct MockBlock {
+    number: u64,
+    hash: B256,
+    parent_hash: B256,
+    timestamp: u64,
+    logs: Vec<Log>,
+    transactions: Vec<B256>,
+}
+
+struct SentTransaction {
+    tx: TxEnvelope,
+    from: Address,
+    block_number: u64,
+    block_hash: B256,
+}
+
+impl MockEvmChain {
+    /// A chain with just its genesis block
+    pub fn new(chain_id: u64) -> Self {
+        let chain = Self {
+            chain_id,
+            state: Arc::new(Mutex::new(MockChainState::default())),
+        };
+        chain.push_block_at(GENESIS_TIMESTAMP);
+        chain
+    }
+
+    pub fn chain_key(&self) -> ChainKey {
+        ChainKey::new(format!("evm:{}", self.chain_id)).unwrap()
+    }
+
+    /// Number of the latest block
+    pub fn head(&self) -> u64 {
+        self.state.lock().unwrap().head().number
+    }
+
+    /// Seals a new block 12 seconds after the last one, holding the logs emitted since,
+    /// and returns its number
+    pub fn push_block(&self) -> u64 {
+        let timestamp = self.state.lock().unwrap().head().timestamp + 12;
+        self.push_block_at(timestamp)
+    }
+
+    /// Like [`Self::push_block`], with the given block timestamp
+    pub fn push_block_at(&self, timestamp: u64) -> u64 {
+        self.state.lock().unwrap().seal_block(timestamp, None)
+    }
+
+    /// Queues a log for the next block, as if emitted by its own transaction
+    pub fn emit_log(&self, address: Address, topics: Vec<B256>, data: impl Into<Bytes>) {
+        self.state
+            .lock()
+            .unwrap()
+            .pending_logs
+            .push(alloy_primitives::Log {
+                address,
+                data: LogData::new_unchecked(topics, data.into()),
+            });
+    }
+
+    /// What `eth_call`s to `to` with calldata starting with `selector` return
+    pub fn on_call(&self, to: Address, selector: [u8; 4], output: impl Into<Bytes>) {
+        self.state
+            .lock()
+            .unwrap()
+            .calls
+            .insert((to, selector), output.into());
+    }
+
+    /// Every transaction sent to the chain so far, in order
+    pub fn sent_transactions(&self) -> Vec<TxEnvelope> {
+        self.state
+            .lock()
+            .unwrap()
+            .transactions
+            .iter()
+            .map(|sent| sent.tx.clone())
+            .collect()
+    }
+
+    /// A client whose calls are answered by this chain, for signing clients and the like
+    pub fn rpc_client(&self) -> RpcClient {
+        RpcClient::new(self.clone(), true)
+    }
+
+    pub fn provider(&self) -> DynProvider {
+        DynProvider::new(ProviderBuilder::new().connect_client(self.rpc_client()))
+    }
+
+    fn respond(&self, req: &SerializedRequest) -> Response {
+        let payload = match self.handle(req.method(), req.params().map(|params| params.get())) {
+            Ok(value) => ResponsePayload::Success(serde_json::value::to_raw_value(&value).unwrap()),
+            Err(message) => ResponsePayload::Failure(ErrorPayload {
+                code: -32000,
+                message: message.into(),
+                data: None,
+            }),
+        };
+
+        Response {
+            id: req.id().clone(),
+            payload,
+        }
+    }
+
+    fn handle(&self, method: &str, params: Option<&str>) -> Result<Value, String> {
+        let mut state = self.state.lock().unwrap();
+
+        match method {
+            "eth_chainId" => to_value(U64::from(self.chain_id)),
+            "net_version" => to_value(self.chain_id.to_string()),
+            "eth_blockNumber" => to_value(U64::from(state.head().number)),
+            "eth_gasPrice" | "eth_maxPriorityFeePerGas" => to_value(U256::from(GAS_PRICE)),
+            "eth_getBalance" => to_value(U256::MAX),
+            "eth_estimateGas" => to_value(U64::from(100_000)),
+            "eth_feeHistory" => {
+                let (block_count, _, _): (U64, Value, Value) = parse(params)?;
+                let block_count = block_count.to::<usize>().max(1);
+                let oldest_block = state.head().number.saturating_sub(block_count as u64 - 1);
+                Ok(json!({
+                    "oldestBlock": U64::from(oldest_block),
+                    "baseFeePerGas": vec![U256::from(GAS_PRICE); block_count + 1],
+                    "gasUsedRatio": vec![0.5; block_count],
+                    "reward": vec![vec![U256::from(GAS_PRICE)]; block_count],
+                }))
+            }
+            "eth_getBlockByNumber" => {
+                let (block, _): (BlockNumberOrTag, Value) = parse(params)?;
+                let number = match block {
+                    BlockNumberOrTag::Number(number) => number,
+                    BlockNumberOrTag::Earliest => 0,
+                    _ => state.head().number,
+                };
+                to_value(state.block(number).map(MockBlock::to_rpc))
+            }
+            "eth_getBlockByHash" => {
+                let (hash, _): (B256, Value) = parse(params)?;
+                to_value(
+                    state
+                        .blocks
+                        .iter()
+                        .find(|block| block.hash == hash)
+                        .map(MockBlock::to_rpc),
+                )
+            }
+            "eth_getLogs" => {
+                let (filter,): (Filter,) = parse(params)?;
+                to_value(state.logs(&filter))
+            }
+            "eth_getTransactionCount" => {
+                let (address, _): (Address, Value) = parse(params)?;
+                let count = state
+                    .transactions
+                    .iter()
+                    .filter(|sent| sent.from == address)
+                    .count();
+                to_value(U64::from(count))
+            }
+            "eth_sendRawTransaction" => {
+                let (raw,): (Bytes,) = parse(params)?;
+                let tx = TxEnvelope::decode_2718(&mut raw.as_ref()).map_err(|e| e.to_string())?;
+                let from = tx
+                    .signature()
+                    .recover_address_from_prehash(&tx.signature_hash())
+                    .map_err(|e| e.to_string())?;
+                let hash = *tx.tx_hash();
+
+                // mined right away, in a block of its own
+                let timestamp = state.head().timestamp + 12;
+                let block_number = state.seal_block(timestamp, Some(hash));
+                let block_hash = state.head().hash;
+                state.transactions.push(SentTransaction {
+                    tx,
+                    from,
+                    block_number,
+                    block_hash,
+                });
+
+                to_value(hash)
+            }
+            "eth_getTransactionReceipt" => {
+                let (hash,): (B256,) = parse(params)?;
+                Ok(state
+                    .transactions
+                    .iter()
+                    .find(|sent| *sent.tx.tx_hash() == hash)
+                    .map(SentTransaction::receipt)
+                    .unwrap_or(Value::Null))
+            }
+            "eth_call" => {
+                // the block is optional
+                let params: Vec<Value> = parse(params)?;
+                let call = params.into_iter().next().unwrap_or_default();
+                let to: Address = serde_json::from_value(call["to"].clone())
+                    .map_err(|_| "eth_call without a destination".to_string())?;
+                let input: Bytes = serde_json::from_value(
+                    call.get("input")
+                        .or_else(|| call.get("data"))
+                        .cloned()
+                        .unwrap_or_default(),
+                )
+                .unwrap_or_default();
+                let selector: [u8; 4] = input
+                    .get(..4)
+                    .and_then(|selector| selector.try_into().ok())
+                    .ok_or_else(|| "eth_call without a function selector".to_string())?;
+
+                state
+                    .calls
+                    .get(&(to, selector))
+                    .map(|output| to_value(output.clone()))
+                    .unwrap_or_else(|| {
+                        Err(format!(
+                            "execution reverted: no mocked result for {to} {}",
+                            const_hex::encode(selector)
+                        ))
+                    })
+            }
+            method => Err(format!("method {method} isn't supported by the mock chain")),
+        }
+    }
+}
+
+impl MockChainState {
+    fn head(&self) -> &MockBlock {
+        self.blocks.last().unwrap()
+    }
+
+    fn block(&self, number: u64) -> Option<&MockBlock> {
+        self.blocks.get(usize::try_from(number).ok()?)
+    }
+
+    /// Adds a block with the pending logs, and `transaction` if there is one
+    fn seal_block(&mut self, timestamp: u64, transaction: Option<B256>) -> u64 {
+        let number = self.blocks.len() as u64;
+        let parent_hash = self
+            .blocks
+            .last()
+            .map(|block| block.hash)
+            .unwrap_or_default();
+        let hash = keccak256([parent_hash.as_slice(), &number.to_be_bytes()].concat());
+
+        let logs = std::mem::take(&mut self.pending_logs)
+            .into_iter()
+            .enumerate()
+            .map(|(index, inner)| Log {
+                inner,
+                block_hash: Some(hash),
+                block_number: Some(number),
+                block_timestamp: None,
+                transaction_hash: Some(keccak256(
+                    [hash.as_slice(), &(index as u64).to_be_bytes()].concat(),
+                )),
+                transaction_index: Some(index as u64),
+                log_index: Some(index as u64),
+                removed: false,
+            })
+            .collect::<Vec<_>>();
+
+        let mut transactions: Vec<B256> =
+            logs.iter().filter_map(|log| log.transaction_hash).collect();
+        transactions.extend(transaction);
+
+        self.blocks.push(MockBlock {
+            number,
+            hash,
+            parent_hash,
+            timestamp,
+            logs,
+            transactions,
+        });
+
+        number
+    }
+
+    fn logs(&self, filter: &Filter) -> Vec<Log> {
+        let blocks: Vec<&MockBlock> = match filter.get_block_hash() {
+            Some(hash) => self
+                .blocks
+                .iter()
+                .filter(|block| block.hash == hash)
+                .collect(),
+            None => {
+                let from = filter.get_from_block().unwrap_or_default();
+                let to = filter.get_to_block().unwrap_or(self.head().number);
+                self.blocks
+                    .iter()
+                    .filter(|block| (from..=to).contains(&block.number))
+                    .collect()
+            }
+        };
+
+        blocks
+            .into_iter()
+            .flat_map(|block| &block.logs)
+            .filter(|log| filter.address.matches(&log.address()))
+            .filter(|log| {
+                filter.topics.iter().enumerate().all(|(index, topic)| {
+                    topic.is_empty()
+                        || log
+                            .topics()
+                            .get(index)
+                            .is_some_and(|value| topic.matches(value))
+                })
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+impl MockBlock {
+    fn to_rpc(&self) -> Block {
+        Block {
+            header: Header {
+                hash: self.hash,
+                inner: alloy_consensus::Header {
+                    number: self.number,
+                    parent_hash: self.parent_hash,
+                    timestamp: self.timestamp,
+                    gas_limit: 30_000_000,
+                    base_fee_per_gas: Some(GAS_PRICE as u64),
+                    ..Default::default()
+                },
+                total_difficulty: None,
+                size: None,
+            },
+            uncles: Vec::new(),
+            transactions: BlockTransactions::Hashes(self.transactions.clone()),
+            withdrawals: None,
+        }
+    }
+}
+
+impl SentTransaction {
+    fn receipt(&self) -> Value {
+        json!({
+            "type": U64::from(self.tx.tx_type() as u8),
+            "status": "0x1",
+            "cumulativeGasUsed": U64::from(21_000),
+            "gasUsed": U64::from(21_000),
+            "effectiveGasPrice": U256::from(GAS_PRICE),
+            "logs": [],
+            "logsBloom": alloy_primitives::Bloom::default(),
+            "transactionHash": self.tx.tx_hash(),
+            "transactionIndex": U64::ZERO,
+            "blockHash": self.block_hash,
+            "blockNumber": U64::from(self.block_number),
+            "from": self.from,
+            "to": self.tx.to(),
+            "contractAddress": null,
+        })
+    }
+}
+
+impl Service<RequestPacket> for MockEvmChain {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let resp = match req {
+            RequestPacket::Single(req) => ResponsePacket::Single(self.respond(&req)),
+            RequestPacket::Batch(reqs) => {
+                ResponsePacket::Batch(reqs.iter().map(|req| self.respond(req)).collect())
+            }
+        };
+
+        Box::pin(async move { Ok(resp) })
+    }
+}
+
+fn parse<T: DeserializeOwned>(params: Option<&str>) -> Result<T, String> {
+    serde_json::from_str(params.unwrap_or("[]")).map_err(|e| format!("invalid params: {e}"))
+}
+
+fn to_value(value: impl serde::Serialize) -> Result<Value, String> {
+    serde_json::to_value(value).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use alloy_network::TransactionBuilder;
+    use alloy_provider::Provider;
+    use alloy_rpc_types_eth::TransactionRequest;
+    use wavs_types::Credential;
+
+    use super::*;
+    use crate::evm_client::{EvmSigningClient, EvmSigningClientConfig};
+
+    #[tokio::test]
+    async fn logs_are_filtered_like_a_node() {
+        let chain = MockEvmChain::new(31337);
+        let provider = chain.provider();
+        let contract = Address::repeat_byte(1);
+        let deposit = B256::repeat_byte(2);
+
+        chain.emit_log(contract, vec![deposit], b"first".to_vec());
+        chain.emit_log(
+            Address::repeat_byte(9),
+            vec![deposit],
+            b"elsewhere".to_vec(),
+        );
+        chain.push_block();
+        chain.emit_log(
+            contract,
+            vec![B256::repeat_byte(3)],
+            b"other event".to_vec(),
+        );
+        chain.emit_log(contract, vec![deposit], b"second".to_vec());
+        let head = chain.push_block();
+
+        assert_eq!(provider.get_block_number().await.unwrap(), head);
+        assert_eq!(provider.get_chain_id().await.unwrap(), 31337);
+
+        let logs = provider
+            .get_logs(
+                &Filter::new()
+                    .address(contract)
+                    .event_signature(deposit)
+                    .from_block(0),
+            )
+            .await
+            .unwrap();
+        let data: Vec<&[u8]> = logs.iter().map(|log| log.data().data.as_ref()).collect();
+        assert_eq!(data, vec![b"first".as_slice(), b"second".as_slice()]);
+        assert_eq!(logs[1].block_number, Some(head));
+        assert_eq!(logs[1].log_index, Some(1));
+
+        let block = provider
+            .get_block_by_number(head.into())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(block.header.hash, logs[1].block_hash.unwrap());
+        assert_eq!(block.header.timestamp, GENESIS_TIMESTAMP + 24);
+    }
+
+    #[tokio::test]
+    async fn signing_client_sends_transactions() {
+        let chain = MockEvmChain::new(31337);
+        let client = EvmSigningClient::with_rpc_client(
+            EvmSigningClientConfig::new(
+                "http://localhost:8545".parse().unwrap(),
+                Credential::new(
+                    "test test test test test test test test test test test junk".to_string(),
+                ),
+            ),
+            chain.rpc_client(),
+        )
+        .await
+        .unwrap();
+
+        let recipient = Address::repeat_byte(7);
+        for _ in 0..2 {
+            let hash = *client
+                .provider
+                .send_transaction(
+                    TransactionRequest::default()
+                        .with_to(recipient)
+                        .with_value(U256::from(1)),
+                )
+                .await
+                .unwrap()
+                .tx_hash();
+
+            let receipt = client
+                .provider
+                .get_transaction_receipt(hash)
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(receipt.status());
+            assert_eq!(receipt.from, client.address());
+        }
+
+        let sent = chain.sent_transactions();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0].to(), Some(recipient));
+        assert_eq!(sent[1].nonce(), 1);
+        // each was mined into a block of its own
+        assert_eq!(chain.head(), 2);
+    }
+}
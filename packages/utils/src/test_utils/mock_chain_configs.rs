@@ -12,6 +12,7 @@ pub fn mock_chain_configs() -> Arc<RwLock<ChainConfigs>> {
             EvmChainConfigBuilder {
                 ws_endpoints: vec!["ws://localhost:8546".to_string()],
                 http_endpoint: Some("http://localhost:8545".to_string()),
+                http_endpoints: Vec::new(),
                 faucet_endpoint: None,
                 ws_priority_endpoint_index: None,
             },
@@ -31,6 +32,7 @@ pub fn mock_chain_configs() -> Arc<RwLock<ChainConfigs>> {
         )]
         .into_iter()
         .collect(),
+        solana: BTreeMap::new(),
         dev: BTreeMap::new(),
     }))
 }
@@ -1,6 +1,7 @@
 pub mod address;
 pub mod anvil;
 pub mod middleware;
+pub mod mock_chain;
 pub mod mock_chain_configs;
 pub mod mock_engine;
 pub mod mock_service_manager;
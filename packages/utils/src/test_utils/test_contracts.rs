@@ -27,6 +27,33 @@ pub use service_handler::{
     SimpleSubmitInstance as SimpleServiceHandlerInstance,
 };
 
+/// A service manager that accepts any signatures, and a handler that takes submissions for it
+pub mod test_service_contracts {
+    pub mod manager {
+        use alloy_sol_types::sol;
+
+        sol!(
+            #[allow(missing_docs)]
+            #[sol(rpc)]
+            TestServiceManager,
+            "../../examples/contracts/solidity/abi/TestServiceContracts.sol/TestServiceManager.json"
+        );
+    }
+
+    pub mod handler {
+        use alloy_sol_types::sol;
+
+        sol!(
+            #[allow(missing_docs)]
+            #[sol(rpc)]
+            TestServiceHandler,
+            "../../examples/contracts/solidity/abi/TestServiceContracts.sol/TestServiceHandler.json"
+        );
+    }
+}
+
+pub use test_service_contracts::{handler::TestServiceHandler, manager::TestServiceManager};
+
 /// Test dependencies for EVM contract testing
 /// Provides a reusable setup for testing with simple service manager and handler contracts
 pub struct TestContractDeps {
@@ -80,4 +107,17 @@ impl TestContractDeps {
 
         instance
     }
+
+    /// Deploy a test service manager and a handler for it, returning the handler's address
+    pub async fn deploy_test_service_handler(&self) -> Address {
+        let manager_address = *TestServiceManager::deploy(self.client.provider.clone())
+            .await
+            .unwrap()
+            .address();
+
+        *TestServiceHandler::deploy(self.client.provider.clone(), manager_address)
+            .await
+            .unwrap()
+            .address()
+    }
 }
@@ -0,0 +1,214 @@
+//! Custom TLS for RPC endpoints on private infrastructure.
+//!
+//! A chain can add its own CA bundle on top of the system roots and present a client
+//! certificate for mTLS. The files are read and parsed once at startup, so a bad path or
+//! certificate fails fast instead of on the first RPC call, and the resulting rustls config
+//! is shared by the HTTP providers and the websocket trigger streams.
+
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
+
+use rustls::{
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer},
+    ClientConfig, RootCertStore,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use wavs_types::ChainKey;
+
+/// Paths to the PEM files used when connecting to a chain's RPC endpoints
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct RpcTlsConfig {
+    /// CA bundle trusted in addition to the system roots
+    pub ca_cert: Option<PathBuf>,
+    /// Client certificate chain for mTLS, requires `client_key`
+    pub client_cert: Option<PathBuf>,
+    /// Private key for `client_cert`
+    pub client_key: Option<PathBuf>,
+}
+
+#[derive(Debug, Error)]
+pub enum RpcTlsError {
+    #[error("Unable to read {path}: {source}")]
+    Read {
+        path: PathBuf,
+        source: rustls::pki_types::pem::Error,
+    },
+
+    #[error("No certificates found in {0}")]
+    NoCertificates(PathBuf),
+
+    #[error("Invalid CA certificate in {path}: {source}")]
+    InvalidCa {
+        path: PathBuf,
+        source: rustls::Error,
+    },
+
+    #[error("client_cert and client_key must be set together")]
+    IncompleteClientAuth,
+
+    #[error("TLS config: {0}")]
+    Config(#[from] rustls::Error),
+
+    #[error("HTTP client: {0}")]
+    HttpClient(#[from] reqwest::Error),
+
+    #[error("Chain {0}: {1}")]
+    Chain(ChainKey, Box<RpcTlsError>),
+}
+
+/// A loaded and validated `RpcTlsConfig`
+#[derive(Clone)]
+pub struct RpcTls {
+    config: Arc<ClientConfig>,
+    http_client: reqwest::Client,
+}
+
+impl std::fmt::Debug for RpcTls {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RpcTls").finish_non_exhaustive()
+    }
+}
+
+impl RpcTlsConfig {
+    pub fn load(&self) -> Result<RpcTls, RpcTlsError> {
+        let mut roots = RootCertStore::empty();
+        // a missing or partially unreadable system store shouldn't stop a custom CA from working
+        roots.add_parsable_certificates(rustls_native_certs::load_native_certs().certs);
+
+        if let Some(path) = &self.ca_cert {
+            for cert in read_certs(path)? {
+                roots.add(cert).map_err(|source| RpcTlsError::InvalidCa {
+                    path: path.clone(),
+                    source,
+                })?;
+            }
+        }
+
+        let provider = rustls::crypto::CryptoProvider::get_default()
+            .cloned()
+            .unwrap_or_else(|| Arc::new(rustls::crypto::ring::default_provider()));
+
+        let builder = ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .with_root_certificates(roots);
+
+        let config = match (&self.client_cert, &self.client_key) {
+            (Some(cert_path), Some(key_path)) => {
+                let certs = read_certs(cert_path)?;
+                let key =
+                    PrivateKeyDer::from_pem_file(key_path).map_err(|source| RpcTlsError::Read {
+                        path: key_path.clone(),
+                        source,
+                    })?;
+                builder.with_client_auth_cert(certs, key)?
+            }
+            (None, None) => builder.with_no_client_auth(),
+            _ => return Err(RpcTlsError::IncompleteClientAuth),
+        };
+
+        let http_client = reqwest::Client::builder()
+            .use_preconfigured_tls(config.clone())
+            .build()?;
+
+        Ok(RpcTls {
+            config: Arc::new(config),
+            http_client,
+        })
+    }
+}
+
+impl RpcTls {
+    /// For alloy's HTTP transport and anything else speaking HTTP to the chain
+    pub fn http_client(&self) -> reqwest::Client {
+        self.http_client.clone()
+    }
+
+    /// For websocket connections
+    pub fn client_config(&self) -> Arc<ClientConfig> {
+        self.config.clone()
+    }
+}
+
+fn read_certs(path: &PathBuf) -> Result<Vec<CertificateDer<'static>>, RpcTlsError> {
+    let read_err = |source| RpcTlsError::Read {
+        path: path.clone(),
+        source,
+    };
+
+    let certs = CertificateDer::pem_file_iter(path)
+        .map_err(read_err)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(read_err)?;
+
+    if certs.is_empty() {
+        return Err(RpcTlsError::NoCertificates(path.clone()));
+    }
+
+    Ok(certs)
+}
+
+/// Per-chain TLS settings, loaded once and shared by every subsystem that talks to the chains
+#[derive(Clone, Debug, Default)]
+pub struct ChainTls(Arc<HashMap<ChainKey, RpcTls>>);
+
+impl ChainTls {
+    pub fn load(configs: &HashMap<ChainKey, RpcTlsConfig>) -> Result<Self, RpcTlsError> {
+        let loaded = configs
+            .iter()
+            .map(|(chain, config)| {
+                config
+                    .load()
+                    .map(|tls| (chain.clone(), tls))
+                    .map_err(|err| RpcTlsError::Chain(chain.clone(), Box::new(err)))
+            })
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        Ok(Self(Arc::new(loaded)))
+    }
+
+    /// `None` means the chain uses the default TLS settings
+    pub fn get(&self, chain: &ChainKey) -> Option<&RpcTls> {
+        self.0.get(chain)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rejects_invalid_tls_config() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // no custom settings just uses the system roots
+        RpcTlsConfig::default().load().unwrap();
+
+        let missing = RpcTlsConfig {
+            ca_cert: Some(dir.path().join("missing.pem")),
+            ..Default::default()
+        };
+        assert!(matches!(missing.load(), Err(RpcTlsError::Read { .. })));
+
+        let empty_path = dir.path().join("empty.pem");
+        std::fs::write(&empty_path, "not a certificate").unwrap();
+        let empty = RpcTlsConfig {
+            ca_cert: Some(empty_path.clone()),
+            ..Default::default()
+        };
+        assert!(matches!(empty.load(), Err(RpcTlsError::NoCertificates(_))));
+
+        let half = RpcTlsConfig {
+            client_cert: Some(empty_path),
+            ..Default::default()
+        };
+        assert!(matches!(
+            half.load(),
+            Err(RpcTlsError::IncompleteClientAuth)
+        ));
+
+        let chain = ChainKey::new("evm:1").unwrap();
+        let err = ChainTls::load(&HashMap::from([(chain.clone(), missing)])).unwrap_err();
+        assert!(matches!(err, RpcTlsError::Chain(c, _) if c == chain));
+    }
+}
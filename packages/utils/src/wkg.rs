@@ -1,4 +1,6 @@
-use std::sync::Arc;
+pub mod oci;
+
+use std::{path::PathBuf, sync::Arc};
 
 use anyhow::{anyhow, Result};
 use futures::TryStreamExt;
@@ -8,11 +10,30 @@ use wasm_pkg_client::{
 };
 use wavs_types::{ComponentDigest, Registry};
 
+use crate::filesystem::write_atomic;
+
+#[derive(Debug, thiserror::Error)]
+pub enum RegistryError {
+    #[error(transparent)]
+    Client(#[from] WkgError),
+    #[error(transparent)]
+    Oci(#[from] oci::OciError),
+    #[error("Registry served {package}@{version} with digest {actual}, expected {expected}")]
+    DigestMismatch {
+        package: PackageRef,
+        version: Version,
+        expected: ComponentDigest,
+        actual: ComponentDigest,
+    },
+}
+
 pub struct WkgClient {
     // due to a bug in the client which can deadlock with the filesystem
     // we want to use a mutex, and hold it across the await point, only releasing when we're done
     // https://github.com/bytecodealliance/wasm-pkg-tools/issues/155
     inner: Arc<tokio::sync::Mutex<InnerWkgClient>>,
+    /// Components that passed digest verification, `None` if there's nowhere to keep them
+    verified_cache: Option<VerifiedCache>,
 }
 
 struct InnerWkgClient {
@@ -41,8 +62,19 @@ url = "http://localhost:8090"
             client: None,
             config,
         }));
+        let verified_cache = FileCache::global_cache_path()
+            .map(|path| VerifiedCache::new(path.join("verified-components")));
+
+        Ok(Self {
+            inner,
+            verified_cache,
+        })
+    }
 
-        Ok(Self { inner })
+    /// Keep verified components in `dir` instead of the global wkg cache
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.verified_cache = Some(VerifiedCache::new(dir.into()));
+        self
     }
 
     /// Helper function to initialize a client with the appropriate domain
@@ -148,10 +180,15 @@ url = "http://localhost:8090"
         // without downloading content, implement that optimization here
 
         // Download the content and compute the digest
-        let (_, digest) = self
+        let (content, digest) = self
             .download_and_get_digest(&client, package, &release)
             .await?;
 
+        // the digest is computed from these very bytes, so they're as verified as it gets
+        if let Some(cache) = &self.verified_cache {
+            cache.put(&digest, &content);
+        }
+
         Ok((digest, resolved_version))
     }
 
@@ -161,7 +198,16 @@ url = "http://localhost:8090"
     /// latest value.
     /// Finally, checks if the user provided an alternative registry other than WAVS default (currently wa.dev),
     /// before fetching the component from the registry.
-    pub async fn fetch(&self, registry: &Registry) -> Result<Vec<u8>, WkgError> {
+    /// The bytes are only returned, and cached by digest, if they hash to `registry.digest`.
+    pub async fn fetch(&self, registry: &Registry) -> Result<Vec<u8>, RegistryError> {
+        if let Some(content) = self
+            .verified_cache
+            .as_ref()
+            .and_then(|cache| cache.get(&registry.digest))
+        {
+            return Ok(content);
+        }
+
         // Get the client
         let client = self.get_client(registry.domain.as_ref()).await?;
 
@@ -180,9 +226,127 @@ url = "http://localhost:8090"
             .download_and_get_digest(&client, &registry.package, &release)
             .await?;
 
-        // Verify the digest matches what's expected
-        assert_eq!(fetched_digest, registry.digest);
+        verify_digest(registry, &resolved_version, &fetched_digest)?;
+
+        if let Some(cache) = &self.verified_cache {
+            cache.put(&fetched_digest, &content);
+        }
 
         Ok(content)
     }
 }
+
+/// Rejects a download that doesn't hash to the digest the service asked for, whether the
+/// registry is compromised, misconfigured or the version was republished
+#[allow(clippy::result_large_err)]
+fn verify_digest(
+    registry: &Registry,
+    version: &Version,
+    actual: &ComponentDigest,
+) -> Result<(), RegistryError> {
+    if *actual == registry.digest {
+        return Ok(());
+    }
+
+    tracing::error!(
+        "Rejecting {}@{} from the registry, its digest {} doesn't match the expected {}",
+        registry.package,
+        version,
+        actual,
+        registry.digest
+    );
+    Err(RegistryError::DigestMismatch {
+        package: registry.package.clone(),
+        version: version.clone(),
+        expected: registry.digest.clone(),
+        actual: actual.clone(),
+    })
+}
+
+/// Components that passed verification, stored as `<digest>.wasm`.
+/// A failure to read or write it only costs another download
+struct VerifiedCache {
+    dir: PathBuf,
+}
+
+impl VerifiedCache {
+    fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn path(&self, digest: &ComponentDigest) -> PathBuf {
+        self.dir.join(format!("{digest}.wasm"))
+    }
+
+    /// Files are checked again on the way out, anything that changed on disk is dropped
+    fn get(&self, digest: &ComponentDigest) -> Option<Vec<u8>> {
+        let path = self.path(digest);
+        let content = std::fs::read(&path).ok()?;
+
+        if ComponentDigest::hash(&content) == *digest {
+            return Some(content);
+        }
+
+        tracing::warn!(
+            "Cached component {} no longer matches its digest, removing it",
+            path.display()
+        );
+        let _ = std::fs::remove_file(&path);
+        None
+    }
+
+    fn put(&self, digest: &ComponentDigest, content: &[u8]) {
+        let path = self.path(digest);
+        if let Err(err) =
+            std::fs::create_dir_all(&self.dir).and_then(|_| write_atomic(&path, content))
+        {
+            tracing::warn!("Failed to cache component {}: {}", path.display(), err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn registry(content: &[u8]) -> Registry {
+        Registry {
+            digest: ComponentDigest::hash(content),
+            domain: None,
+            version: None,
+            package: "wavs:example".parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn tampered_download_is_rejected() {
+        let registry = registry(b"component");
+        let version = Version::new(0, 1, 0);
+
+        verify_digest(&registry, &version, &ComponentDigest::hash(b"component")).unwrap();
+
+        let err =
+            verify_digest(&registry, &version, &ComponentDigest::hash(b"tampered")).unwrap_err();
+        assert!(matches!(
+            err,
+            RegistryError::DigestMismatch { expected, actual, .. }
+                if expected == registry.digest && actual == ComponentDigest::hash(b"tampered")
+        ));
+    }
+
+    #[test]
+    fn cache_only_serves_verified_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = VerifiedCache::new(dir.path().join("components"));
+        let digest = ComponentDigest::hash(b"component");
+
+        assert_eq!(cache.get(&digest), None);
+        cache.put(&digest, b"component");
+        assert_eq!(cache.get(&digest), Some(b"component".to_vec()));
+
+        // swapped out on disk
+        std::fs::write(cache.path(&digest), b"tampered").unwrap();
+        assert_eq!(cache.get(&digest), None);
+        assert!(!cache.path(&digest).exists());
+    }
+}
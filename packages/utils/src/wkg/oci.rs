@@ -0,0 +1,562 @@
+//! Pulls components published as OCI artifacts, the layout `wkg oci push` produces,
+//! from any registry speaking the distribution API such as GHCR or Docker Hub.
+//!
+//! Only the wasm layer is downloaded, and only returned if it hashes to the digest the
+//! service asked for, the same as components from a `Registry` or a `Download`.
+
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use reqwest::{header, Client, RequestBuilder, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use wavs_types::{ComponentDigest, Credential};
+
+/// Media type of the layer holding the component in a wasm OCI artifact
+pub const WASM_LAYER_MEDIA_TYPE: &str = "application/wasm";
+
+/// Largest wasm layer downloaded, unless the client is given another limit
+pub const MAX_COMPONENT_BYTES: usize = 64 * 1024 * 1024;
+
+const MANIFEST_MEDIA_TYPES: &str =
+    "application/vnd.oci.image.manifest.v1+json, application/vnd.docker.distribution.manifest.v2+json";
+
+#[derive(Debug, thiserror::Error)]
+pub enum OciError {
+    #[error("Invalid OCI reference '{0}': {1}")]
+    InvalidReference(String, &'static str),
+    #[error("Request to {url} failed: {source}")]
+    Http {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("{url} returned {status}")]
+    Status { url: String, status: StatusCode },
+    #[error("{0} asked for an authentication scheme other than Basic or Bearer")]
+    UnsupportedAuth(String),
+    #[error("Token endpoint {0} didn't return a token")]
+    MissingToken(String),
+    #[error("Manifest of {0} has no {WASM_LAYER_MEDIA_TYPE} layer")]
+    NoWasmLayer(OciReference),
+    #[error("Wasm layer of {reference} is larger than the {max} byte limit")]
+    TooLarge { reference: OciReference, max: usize },
+    #[error("OCI artifact {reference} has digest {actual}, expected {expected}")]
+    DigestMismatch {
+        reference: OciReference,
+        expected: ComponentDigest,
+        actual: ComponentDigest,
+    },
+}
+
+/// Login for a registry, keyed by its host in [`OciCredentials`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OciCredential {
+    pub username: String,
+    pub password: Credential,
+}
+
+/// Logins by registry host as written in references, e.g. `ghcr.io` or `docker.io`.
+/// Registries without one are pulled from anonymously
+pub type OciCredentials = HashMap<String, OciCredential>;
+
+/// `[registry/]repository[:tag][@digest]`, where the registry defaults to Docker Hub
+/// and the tag to `latest`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OciReference {
+    pub registry: String,
+    pub repository: String,
+    /// A tag, or a manifest digest like `sha256:...`
+    pub reference: String,
+}
+
+const DOCKER_HUB: &str = "docker.io";
+
+impl FromStr for OciReference {
+    type Err = OciError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason| OciError::InvalidReference(s.to_string(), reason);
+
+        let (name, reference) = match s.split_once('@') {
+            Some((name, digest)) => (name, digest.to_string()),
+            None => match s.rsplit_once(':') {
+                // a colon before the last slash belongs to the registry's port
+                Some((name, tag)) if !tag.contains('/') => (name, tag.to_string()),
+                _ => (s, "latest".to_string()),
+            },
+        };
+
+        let (registry, repository) = match name.split_once('/') {
+            Some((host, rest))
+                if host.contains('.') || host.contains(':') || host == "localhost" =>
+            {
+                (host.to_string(), rest.to_string())
+            }
+            _ => (DOCKER_HUB.to_string(), name.to_string()),
+        };
+
+        if repository.is_empty() || repository.contains("//") || repository.ends_with('/') {
+            return Err(invalid("missing repository"));
+        }
+        if reference.is_empty() {
+            return Err(invalid("empty tag or digest"));
+        }
+
+        // official images live under library/ on Docker Hub
+        let repository = match (registry.as_str(), repository.contains('/')) {
+            (DOCKER_HUB, false) => format!("library/{repository}"),
+            _ => repository,
+        };
+
+        Ok(Self {
+            registry,
+            repository,
+            reference,
+        })
+    }
+}
+
+impl fmt::Display for OciReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let separator = if self.reference.contains(':') {
+            '@'
+        } else {
+            ':'
+        };
+        write!(
+            f,
+            "{}/{}{}{}",
+            self.registry, self.repository, separator, self.reference
+        )
+    }
+}
+
+impl OciReference {
+    fn base_url(&self) -> String {
+        let host = match self.registry.as_str() {
+            DOCKER_HUB => "registry-1.docker.io",
+            host => host,
+        };
+        // local registries for development rarely have certificates
+        let scheme = if is_local_host(host) { "http" } else { "https" };
+        format!("{scheme}://{host}/v2/{}", self.repository)
+    }
+}
+
+/// `localhost` or `127.0.0.1`, with an optional port
+fn is_local_host(host: &str) -> bool {
+    let name = match host.rsplit_once(':') {
+        Some((name, port)) if !port.is_empty() && port.bytes().all(|b| b.is_ascii_digit()) => name,
+        _ => host,
+    };
+    name == "localhost" || name == "127.0.0.1"
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    layers: Vec<Descriptor>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+    media_type: String,
+    digest: String,
+    #[serde(default)]
+    size: u64,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    token: Option<String>,
+    access_token: Option<String>,
+}
+
+pub struct OciClient {
+    client: Client,
+    credentials: OciCredentials,
+    max_component_bytes: usize,
+}
+
+impl OciClient {
+    pub fn new(credentials: OciCredentials) -> Self {
+        Self {
+            client: Client::new(),
+            credentials,
+            max_component_bytes: MAX_COMPONENT_BYTES,
+        }
+    }
+
+    pub fn with_max_component_bytes(mut self, max_component_bytes: usize) -> Self {
+        self.max_component_bytes = max_component_bytes;
+        self
+    }
+
+    /// Fetches the artifact's wasm layer, returned only if it hashes to `digest`
+    pub async fn fetch(
+        &self,
+        reference: &OciReference,
+        digest: &ComponentDigest,
+    ) -> Result<Vec<u8>, OciError> {
+        let base_url = reference.base_url();
+        let mut token = None;
+
+        let manifest_url = format!("{base_url}/manifests/{}", reference.reference);
+        let manifest: Manifest = self
+            .get(reference, &manifest_url, MANIFEST_MEDIA_TYPES, &mut token)
+            .await?
+            .json()
+            .await
+            .map_err(|source| OciError::Http {
+                url: manifest_url.clone(),
+                source,
+            })?;
+
+        let layer = manifest
+            .layers
+            .into_iter()
+            .find(|layer| layer.media_type == WASM_LAYER_MEDIA_TYPE)
+            .ok_or_else(|| OciError::NoWasmLayer(reference.clone()))?;
+
+        let too_large = || OciError::TooLarge {
+            reference: reference.clone(),
+            max: self.max_component_bytes,
+        };
+        if layer.size > self.max_component_bytes as u64 {
+            return Err(too_large());
+        }
+
+        let blob_url = format!("{base_url}/blobs/{}", layer.digest);
+        let mut response = self
+            .get(reference, &blob_url, WASM_LAYER_MEDIA_TYPE, &mut token)
+            .await?;
+
+        // the manifest's size can't be trusted, so the download is capped as it streams in
+        let mut content = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|source| OciError::Http {
+            url: blob_url.clone(),
+            source,
+        })? {
+            if content.len() + chunk.len() > self.max_component_bytes {
+                return Err(too_large());
+            }
+            content.extend_from_slice(&chunk);
+        }
+
+        let actual = ComponentDigest::hash(&content);
+        if actual != *digest {
+            tracing::error!(
+                "Rejecting OCI artifact {}, its digest {} doesn't match the expected {}",
+                reference,
+                actual,
+                digest
+            );
+            return Err(OciError::DigestMismatch {
+                reference: reference.clone(),
+                expected: digest.clone(),
+                actual,
+            });
+        }
+
+        Ok(content)
+    }
+
+    /// Sends the request, logging in once if the registry asks for it.
+    /// The bearer token is kept in `token` for the requests that follow
+    async fn get(
+        &self,
+        reference: &OciReference,
+        url: &str,
+        accept: &str,
+        token: &mut Option<String>,
+    ) -> Result<Response, OciError> {
+        let request = || self.client.get(url).header(header::ACCEPT, accept);
+
+        let response = self.send(url, self.authorize(request(), token)).await?;
+        let response = match response.status() {
+            StatusCode::UNAUTHORIZED => {
+                let challenge = response
+                    .headers()
+                    .get(header::WWW_AUTHENTICATE)
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or_default()
+                    .to_string();
+                let credential = self.credentials.get(&reference.registry);
+
+                let request = match challenge.split_once(' ') {
+                    Some((scheme, params)) if scheme.eq_ignore_ascii_case("bearer") => {
+                        *token = Some(self.fetch_token(params, credential).await?);
+                        self.authorize(request(), token)
+                    }
+                    Some((scheme, _)) if scheme.eq_ignore_ascii_case("basic") => match credential {
+                        Some(credential) => request()
+                            .basic_auth(&credential.username, Some(credential.password.as_str())),
+                        None => {
+                            return Err(OciError::Status {
+                                url: url.to_string(),
+                                status: StatusCode::UNAUTHORIZED,
+                            })
+                        }
+                    },
+                    _ => return Err(OciError::UnsupportedAuth(url.to_string())),
+                };
+                self.send(url, request).await?
+            }
+            _ => response,
+        };
+
+        match response.status() {
+            status if status.is_success() => Ok(response),
+            status => Err(OciError::Status {
+                url: url.to_string(),
+                status,
+            }),
+        }
+    }
+
+    fn authorize(&self, request: RequestBuilder, token: &Option<String>) -> RequestBuilder {
+        match token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    async fn send(&self, url: &str, request: RequestBuilder) -> Result<Response, OciError> {
+        request.send().await.map_err(|source| OciError::Http {
+            url: url.to_string(),
+            source,
+        })
+    }
+
+    /// Token flow of the distribution spec, anonymous unless there's a login for the registry
+    async fn fetch_token(
+        &self,
+        challenge_params: &str,
+        credential: Option<&OciCredential>,
+    ) -> Result<String, OciError> {
+        let params = parse_challenge_params(challenge_params);
+        let realm = params
+            .get("realm")
+            .ok_or_else(|| OciError::UnsupportedAuth(challenge_params.to_string()))?;
+
+        let query: Vec<(&str, &str)> = ["service", "scope"]
+            .into_iter()
+            .filter_map(|key| Some((key, params.get(key)?.as_str())))
+            .collect();
+        let mut request = self.client.get(realm).query(&query);
+        if let Some(credential) = credential {
+            request = request.basic_auth(&credential.username, Some(credential.password.as_str()));
+        }
+
+        let response = self.send(realm, request).await?;
+        if !response.status().is_success() {
+            return Err(OciError::Status {
+                url: realm.clone(),
+                status: response.status(),
+            });
+        }
+
+        let body: TokenResponse = response.json().await.map_err(|source| OciError::Http {
+            url: realm.clone(),
+            source,
+        })?;
+        body.token
+            .or(body.access_token)
+            .ok_or_else(|| OciError::MissingToken(realm.clone()))
+    }
+}
+
+/// `realm="https://auth.example",service="registry",scope="repository:a/b:pull"`
+fn parse_challenge_params(params: &str) -> HashMap<String, String> {
+    let mut parsed = HashMap::new();
+    let mut rest = params.trim();
+
+    while let Some((key, after)) = rest.split_once('=') {
+        let key = key
+            .trim()
+            .trim_start_matches(',')
+            .trim()
+            .to_ascii_lowercase();
+        let (value, after) = match after.strip_prefix('"') {
+            Some(quoted) => match quoted.split_once('"') {
+                Some((value, after)) => (value, after),
+                None => (quoted, ""),
+            },
+            None => after.split_once(',').unwrap_or((after, "")),
+        };
+        parsed.insert(key, value.to_string());
+        rest = after.trim_start_matches(',').trim();
+    }
+
+    parsed
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axum::{
+        extract::Path,
+        http::{HeaderMap, StatusCode},
+        response::IntoResponse,
+        routing::get,
+        Json, Router,
+    };
+    use serde_json::json;
+
+    const WASM: &[u8] = b"\0asm component";
+    const TOKEN: &str = "pull-token";
+
+    #[test]
+    fn parses_references() {
+        let parsed: OciReference = "ghcr.io/lay3rlabs/echo:0.1.0".parse().unwrap();
+        assert_eq!(parsed.registry, "ghcr.io");
+        assert_eq!(parsed.repository, "lay3rlabs/echo");
+        assert_eq!(parsed.reference, "0.1.0");
+
+        let parsed: OciReference = "localhost:5000/echo".parse().unwrap();
+        assert_eq!(parsed.registry, "localhost:5000");
+        assert_eq!(parsed.reference, "latest");
+        assert_eq!(parsed.base_url(), "http://localhost:5000/v2/echo");
+
+        // only the exact local hosts skip tls
+        let parsed: OciReference = "localhost.evil.com/echo".parse().unwrap();
+        assert_eq!(parsed.base_url(), "https://localhost.evil.com/v2/echo");
+        let parsed: OciReference = "127.0.0.1.nip.io:5000/echo".parse().unwrap();
+        assert_eq!(parsed.base_url(), "https://127.0.0.1.nip.io:5000/v2/echo");
+
+        let parsed: OciReference = "echo@sha256:abcd".parse().unwrap();
+        assert_eq!(parsed.registry, DOCKER_HUB);
+        assert_eq!(parsed.repository, "library/echo");
+        assert_eq!(parsed.to_string(), "docker.io/library/echo@sha256:abcd");
+
+        assert!("ghcr.io/".parse::<OciReference>().is_err());
+    }
+
+    /// A registry that only serves pulls with a token, and only hands out tokens to `user:pass`
+    async fn mock_registry() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let layer_digest = format!("sha256:{}", ComponentDigest::hash(WASM));
+
+        let authorized = |headers: &HeaderMap| {
+            headers
+                .get(header::AUTHORIZATION)
+                .is_some_and(|value| value == format!("Bearer {TOKEN}").as_str())
+        };
+        let challenge = format!(
+            "Bearer realm=\"http://{addr}/token\",service=\"mock\",scope=\"repository:wavs/echo:pull\""
+        );
+
+        let app = Router::new()
+            .route(
+                "/token",
+                get(|headers: HeaderMap| async move {
+                    // base64 of user:pass
+                    match headers.get(header::AUTHORIZATION) {
+                        Some(value) if value == "Basic dXNlcjpwYXNz" => {
+                            Json(json!({ "token": TOKEN })).into_response()
+                        }
+                        _ => StatusCode::UNAUTHORIZED.into_response(),
+                    }
+                }),
+            )
+            .route(
+                "/v2/wavs/echo/manifests/{tag}",
+                get({
+                    let challenge = challenge.clone();
+                    move |Path(tag): Path<String>, headers: HeaderMap| {
+                        let challenge = challenge.clone();
+                        let layer_digest = layer_digest.clone();
+                        async move {
+                            if !authorized(&headers) {
+                                return (
+                                    StatusCode::UNAUTHORIZED,
+                                    [(header::WWW_AUTHENTICATE, challenge)],
+                                )
+                                    .into_response();
+                            }
+                            if tag != "1.0.0" {
+                                return StatusCode::NOT_FOUND.into_response();
+                            }
+                            Json(json!({
+                                "schemaVersion": 2,
+                                "mediaType": "application/vnd.oci.image.manifest.v1+json",
+                                "config": {
+                                    "mediaType": "application/vnd.wasm.config.v0+json",
+                                    "digest": "sha256:00",
+                                    "size": 2
+                                },
+                                "layers": [{
+                                    "mediaType": WASM_LAYER_MEDIA_TYPE,
+                                    "digest": layer_digest,
+                                    "size": WASM.len()
+                                }]
+                            }))
+                            .into_response()
+                        }
+                    }
+                }),
+            )
+            .route(
+                "/v2/wavs/echo/blobs/{digest}",
+                get(move |headers: HeaderMap| async move {
+                    if !authorized(&headers) {
+                        return StatusCode::UNAUTHORIZED.into_response();
+                    }
+                    WASM.into_response()
+                }),
+            );
+
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        format!("127.0.0.1:{}/wavs/echo:1.0.0", addr.port())
+    }
+
+    #[tokio::test]
+    async fn pulls_and_verifies_wasm_layer() {
+        let reference: OciReference = mock_registry().await.parse().unwrap();
+        let credentials = OciCredentials::from([(
+            reference.registry.clone(),
+            OciCredential {
+                username: "user".to_string(),
+                password: Credential::new("pass".to_string()),
+            },
+        )]);
+        let client = OciClient::new(credentials.clone());
+
+        let content = client
+            .fetch(&reference, &ComponentDigest::hash(WASM))
+            .await
+            .unwrap();
+        assert_eq!(content, WASM);
+
+        let err = client
+            .fetch(&reference, &ComponentDigest::hash(b"something else"))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            OciError::DigestMismatch { actual, .. } if actual == ComponentDigest::hash(WASM)
+        ));
+
+        let err = OciClient::new(credentials)
+            .with_max_component_bytes(WASM.len() - 1)
+            .fetch(&reference, &ComponentDigest::hash(WASM))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, OciError::TooLarge { .. }));
+
+        // no login, no token
+        let err = OciClient::new(OciCredentials::new())
+            .fetch(&reference, &ComponentDigest::hash(WASM))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            OciError::Status {
+                status: StatusCode::UNAUTHORIZED,
+                ..
+            }
+        ));
+    }
+}
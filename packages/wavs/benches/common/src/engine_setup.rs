@@ -63,10 +63,18 @@ impl EngineSetup {
                 },
                 fuel_limit: None,
                 time_limit_seconds: None,
+                max_memory_bytes: None,
                 config,
                 env_keys: std::collections::BTreeSet::new(),
+                secrets: std::collections::BTreeSet::new(),
             },
             submit: wavs_types::Submit::None,
+            submit_targets: Vec::new(),
+            response_mapping: Default::default(),
+            submit_condition: None,
+            priority: Default::default(),
+            observe_only: false,
+            min_interval_ms: None,
         };
 
         let service = wavs_types::Service {
@@ -77,6 +85,8 @@ impl EngineSetup {
                 chain: "evm:exec".parse().unwrap(),
                 address: Default::default(),
             },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
         };
 
         let chain_configs = ChainConfigs::default();
@@ -109,7 +119,7 @@ impl EngineSetup {
     /// Create a new InstanceDeps for execution
     pub fn create_instance_deps(&self, trigger_action: &TriggerAction) -> InstanceDeps {
         let log = HostComponentLogger::OperatorHostComponentLogger(
-            |_service_id, _workflow_id, _digest, _level, _message| {
+            |_service_id, _workflow_id, _digest, _level, _message, _fields| {
                 // No-op logger for benchmarks
             },
         );
@@ -124,6 +134,10 @@ impl EngineSetup {
             chain_configs: &self.chain_configs,
             log,
             keyvalue_ctx: self.keyvalue_ctx.clone(),
+            evm_blocks: Default::default(),
+            secrets: Default::default(),
+            determinism: Default::default(),
+            capture_output: false,
         };
 
         builder.build().unwrap()
@@ -16,7 +16,7 @@ pub enum ComponentConfig {
 }
 
 pub struct DevTriggersRuntime {
-    pub dispatcher: Arc<wavs::dispatcher::Dispatcher<utils::storage::fs::FileStorage>>,
+    pub dispatcher: Arc<wavs::dispatcher::Dispatcher<utils::storage::any::AnyStorage>>,
     pub server_addr: SocketAddr,
     pub service: Service,
     pub workflow_id: WorkflowId,
@@ -81,8 +81,10 @@ impl DevTriggersRuntime {
                         },
                         fuel_limit: None,
                         time_limit_seconds: None,
+                        max_memory_bytes: None,
                         config: component_config,
                         env_keys: std::collections::BTreeSet::new(),
+                        secrets: std::collections::BTreeSet::new(),
                     },
                     // Use aggregator submit so the submission manager produces packets
                     submit: Submit::Aggregator {
@@ -96,11 +98,20 @@ impl DevTriggersRuntime {
                             },
                             fuel_limit: None,
                             time_limit_seconds: None,
+                            max_memory_bytes: None,
                             config: std::collections::BTreeMap::new(),
                             env_keys: std::collections::BTreeSet::new(),
+                            secrets: std::collections::BTreeSet::new(),
                         }),
                         signature_kind: SignatureKind::evm_default(),
+                        targets: Vec::new(),
                     },
+                    submit_targets: Vec::new(),
+                    response_mapping: Default::default(),
+                    submit_condition: None,
+                    priority: Default::default(),
+                    observe_only: false,
+                    min_interval_ms: None,
                 },
             )]),
             status: wavs_types::ServiceStatus::Active,
@@ -108,6 +119,8 @@ impl DevTriggersRuntime {
                 chain: "evm:exec".parse().unwrap(),
                 address: Default::default(),
             },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
         };
 
         // Build dispatcher
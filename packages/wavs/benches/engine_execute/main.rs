@@ -22,11 +22,11 @@ pub fn run_simulation(setup: Arc<ExecuteSetup>) {
 
             // Execute the component and measure performance
             match wavs_engine::worlds::operator::execute::execute(&mut deps, trigger_action).await {
-                Ok(responses) => {
-                    if responses.is_empty() {
+                Ok(output) => {
+                    if output.responses.is_empty() {
                         panic!("Execution returned no responses");
                     }
-                    assert_eq!(responses[0].payload, echo_data, "Payload mismatch");
+                    assert_eq!(output.responses[0].payload, echo_data, "Payload mismatch");
                 }
                 Err(err) => {
                     panic!("Execution failed: {err:?}");
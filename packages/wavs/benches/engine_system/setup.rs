@@ -6,10 +6,11 @@ use utils::service::DEFAULT_IPFS_GATEWAY;
 use utils::storage::{db::WavsDb, fs::FileStorage};
 use utils::telemetry::Metrics;
 use wavs::dispatcher::DispatcherCommand;
+use wavs::subsystems::engine::scheduler::ExecutionScheduler;
 use wavs::subsystems::engine::EngineCommand;
 use wavs::{
     services::Services,
-    subsystems::engine::{wasm_engine::WasmEngine, EngineManager},
+    subsystems::engine::{status::WorkflowStatusTracker, wasm_engine::WasmEngine, EngineManager},
 };
 use wavs_benchmark_common::{app_context::APP_CONTEXT, engine_setup::EngineSetup};
 use wavs_types::{Service, TriggerAction};
@@ -80,6 +81,8 @@ impl SystemSetup {
         let engine_manager = EngineManager::new(
             wasm_engine,
             Services::new(db_storage),
+            WorkflowStatusTracker::default(),
+            ExecutionScheduler::new(None, None),
             dispatcher_to_engine_rx,
             subsystem_to_dispatcher_tx,
         );
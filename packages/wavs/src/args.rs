@@ -1,7 +1,7 @@
 use clap::Parser;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use utils::{config::CliEnvExt, serde::deserialize_vec_string};
+use utils::{config::CliEnvExt, logging::LogFormat, serde::deserialize_vec_string};
 use wavs_types::Credential;
 
 /// This struct is used for both args and environment variables
@@ -35,6 +35,11 @@ pub struct CliArgs {
     #[serde(deserialize_with = "deserialize_vec_string")]
     pub log_level: Vec<String>,
 
+    /// Log output format: full, pretty, compact or json
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_format: Option<LogFormat>,
+
     /// The host to bind the server to
     #[arg(long)]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -118,6 +123,11 @@ pub struct CliArgs {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bearer_token: Option<Credential>,
 
+    /// Optional key (mnemonic or 0x-prefixed private key) to sign `/health` responses with
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_signing_key: Option<Credential>,
+
     /// Maximum HTTP request body size in megabytes
     #[arg(long)]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -154,6 +164,26 @@ pub struct CliArgs {
     #[arg(long)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hyperswarm_bootstrap: Option<String>,
+
+    /// Window (seconds) across which cron trigger executions are spread per operator
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cron_jitter_secs: Option<u64>,
+
+    /// Precompile all deployed components at startup
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warmup_components: Option<bool>,
+
+    /// Maximum number of components compiled concurrently during warmup
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warmup_parallelism: Option<usize>,
+
+    /// Replay EVM contract events missed while the node was offline
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evm_backfill: Option<bool>,
 }
 
 impl CliEnvExt for CliArgs {
@@ -1,11 +1,22 @@
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
     path::PathBuf,
     sync::{Arc, RwLock},
+    time::Duration,
+};
+use utils::{
+    async_transaction::RetryPolicy,
+    config::ConfigExt,
+    evm_client::{confirmations::ConfirmationPolicy, gas::GasPriceBounds},
+    logging::LogFormat,
+    service::DEFAULT_IPFS_GATEWAY,
+    storage::s3::S3Config,
+    tls::RpcTlsConfig,
+    wkg::oci::OciCredentials,
 };
-use utils::{config::ConfigExt, service::DEFAULT_IPFS_GATEWAY};
 use utoipa::ToSchema;
-use wavs_types::{ChainConfigs, Credential, Workflow};
+use wavs_types::{ChainConfigs, ChainKey, Credential, ServiceId, Workflow};
 
 use crate::subsystems::aggregator::p2p::P2pConfig;
 
@@ -28,15 +39,123 @@ pub struct AggregatorConfig {
     /// Time-to-live for burned quorum queues in seconds (default: 172800 = 48 hours)
     /// Burned queues older than this will be cleaned up
     pub burned_queue_ttl_secs: Option<u64>,
+    /// How long each service's quorum queues wait for enough signatures.
+    /// Queues for services that aren't listed wait indefinitely
+    #[schema(value_type = HashMap<String, QuorumTimeoutConfig>)]
+    pub quorum_timeouts: HashMap<ServiceId, QuorumTimeoutConfig>,
+    /// Build and validate every submission, and log the transaction it would send,
+    /// but never broadcast it (default: false). Submissions are reported as succeeded
+    pub dry_run: bool,
+    /// Drop received submissions unless their signer is a staked operator of the service,
+    /// as reported by the service manager (default: false)
+    pub verify_operators: bool,
+    /// How long a signer's operator lookup is trusted before it's queried again
+    /// (default: 300 = 5 minutes)
+    pub operator_set_refresh_secs: Option<u64>,
+    /// Hold quorum queues until the weight of their signers reaches this percentage of the
+    /// total weight (e.g. 66), instead of checking on-chain on every new signature.
+    /// Needs `verify_operators` for the signer weights
+    pub quorum_threshold_percent: Option<u8>,
+    /// How many times an on-chain submission is tried when it fails with a transient
+    /// error like a timeout, a rate limit or a stale nonce (default: 3)
+    pub submit_max_attempts: Option<u32>,
+    /// Backoff before the first retry of a submission in milliseconds, doubled for every
+    /// retry after that, with jitter (default: 500)
+    pub submit_retry_base_delay_ms: Option<u64>,
 }
 
 impl AggregatorConfig {
     const DEFAULT_BURNED_QUEUE_TTL_SECS: u64 = 172800; // 48 hours
+    const DEFAULT_OPERATOR_SET_REFRESH_SECS: u64 = 300;
+    const DEFAULT_SUBMIT_MAX_ATTEMPTS: u32 = 3;
+    const DEFAULT_SUBMIT_RETRY_BASE_DELAY_MS: u64 = 500;
 
     pub fn burned_queue_ttl_secs(&self) -> u64 {
         self.burned_queue_ttl_secs
             .unwrap_or(Self::DEFAULT_BURNED_QUEUE_TTL_SECS)
     }
+
+    pub fn operator_set_refresh_secs(&self) -> u64 {
+        self.operator_set_refresh_secs
+            .unwrap_or(Self::DEFAULT_OPERATOR_SET_REFRESH_SECS)
+    }
+
+    pub fn submit_retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::new(
+            self.submit_max_attempts
+                .unwrap_or(Self::DEFAULT_SUBMIT_MAX_ATTEMPTS),
+            Duration::from_millis(
+                self.submit_retry_base_delay_ms
+                    .unwrap_or(Self::DEFAULT_SUBMIT_RETRY_BASE_DELAY_MS),
+            ),
+        )
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(percent) = self.quorum_threshold_percent {
+            if percent == 0 || percent > 100 {
+                return Err(format!(
+                    "quorum_threshold_percent must be between 1 and 100, got {percent}"
+                ));
+            }
+            if !self.verify_operators {
+                return Err("quorum_threshold_percent requires verify_operators".to_string());
+            }
+        }
+        for (service_id, timeout) in &self.quorum_timeouts {
+            if timeout.quorum_timeout_secs == 0 {
+                return Err(format!(
+                    "quorum_timeout_secs for service {service_id} must be greater than 0"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct QuorumTimeoutConfig {
+    /// Seconds since the queue's first signature arrived before it expires
+    pub quorum_timeout_secs: u64,
+    /// What happens to the collected signatures on expiry (default: drop)
+    #[serde(default)]
+    pub on_timeout: OnTimeout,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OnTimeout {
+    /// Burn the queue without submitting, later signatures for the event are ignored
+    #[default]
+    Drop,
+    /// Make a final submission attempt with the signatures collected so far, then burn the queue.
+    /// The service manager still validates the signatures on-chain
+    SubmitPartial,
+}
+
+/// Disk spill for workflow outcomes that no longer fit in the in-memory status buffer
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(default)]
+pub struct StatusSpillConfig {
+    /// Append evicted outcomes to rotating files under the data directory (default: false)
+    pub enabled: bool,
+    /// Maximum total size of the spill files in megabytes (default: 64)
+    pub max_disk_mb: Option<u64>,
+    /// Spilled outcomes older than this are dropped, in seconds (default: 604800 = 7 days)
+    pub retention_secs: Option<u64>,
+}
+
+impl StatusSpillConfig {
+    const DEFAULT_MAX_DISK_MB: u64 = 64;
+    const DEFAULT_RETENTION_SECS: u64 = 604800; // 7 days
+
+    pub fn max_disk_bytes(&self) -> u64 {
+        self.max_disk_mb.unwrap_or(Self::DEFAULT_MAX_DISK_MB) * 1024 * 1024
+    }
+
+    pub fn retention_secs(&self) -> u64 {
+        self.retention_secs.unwrap_or(Self::DEFAULT_RETENTION_SECS)
+    }
 }
 
 /// The fully parsed and validated config struct we use in the application
@@ -53,6 +172,14 @@ pub struct Config {
     /// The log-level to use, in the format of [tracing directives](https://docs.rs/tracing-subscriber/latest/tracing_subscriber/filter/struct.EnvFilter.html#directives).
     /// Default is `["info"]`
     pub log_level: Vec<String>,
+    /// How log lines are written to the console: `full`, `pretty`, `compact` or `json`.
+    /// `json` puts span fields like `service_id` at the top level of each line.
+    /// Default is `full`
+    pub log_format: LogFormat,
+    /// Log levels for individual services, keyed by service id or name, e.g. `{ "my-service" = "warn" }`.
+    /// They replace `log_level` for anything logged about or while running those services.
+    /// Default is empty
+    pub service_log_levels: HashMap<String, String>,
     /// The host to bind the server to
     /// Default is `127.0.0.1`
     pub host: String,
@@ -86,6 +213,11 @@ pub struct Config {
     /// The maximum amount of time (seconds) to allow for 1 component's execution
     pub max_execution_seconds: u64,
 
+    /// The maximum size (bytes) of each response payload a component returns. Larger responses
+    /// fail the execution instead of being signed and submitted.
+    /// Default is `None` (unlimited)
+    pub max_response_bytes: Option<usize>,
+
     /// Jaeger collector to send trace data
     pub jaeger: Option<String>,
 
@@ -98,10 +230,23 @@ pub struct Config {
     /// The IPFS gateway URL used to access IPFS content over HTTP.
     pub ipfs_gateway: String,
 
+    /// Logins for pulling `oci` components from private registries, keyed by registry host
+    /// as written in the component's reference, e.g. `ghcr.io`. Other registries are pulled
+    /// from anonymously.
+    /// Default is empty
+    #[serde(default)]
+    #[schema(value_type = HashMap<String, Object>)]
+    pub oci_credentials: OciCredentials,
+
     /// Optional bearer token to protect mutating HTTP endpoints.
     /// If None, endpoints remain unauthenticated.
     pub bearer_token: Option<Credential>,
 
+    /// Optional key (mnemonic or 0x-prefixed private key) to sign `/health` responses with,
+    /// so load balancers and external checkers can verify they reach the operator node.
+    /// If None, health responses are unsigned
+    pub health_signing_key: Option<Credential>,
+
     /// Enable dev endpoints for testing (default: false)
     pub dev_endpoints_enabled: bool,
 
@@ -115,6 +260,10 @@ pub struct Config {
     #[serde(default)]
     pub aggregator: AggregatorConfig,
 
+    /// Disk spill for workflow status history
+    #[serde(default)]
+    pub status_spill: StatusSpillConfig,
+
     /// P2P networking configuration for signature aggregation
     #[serde(default)]
     #[schema(value_type = String)]
@@ -139,6 +288,128 @@ pub struct Config {
 
     /// Optional hyperswarm bootstrap address (host:port) for Hypercore discovery
     pub hyperswarm_bootstrap: Option<String>,
+
+    /// Window (seconds) across which cron trigger executions are spread per operator.
+    /// Each operator fires at a stable offset derived from its signing mnemonic.
+    /// Default is `0` (fire exactly on the tick)
+    pub cron_jitter_secs: u64,
+
+    /// Precompile all deployed components at startup, before triggers are registered,
+    /// so the first execution of each workflow doesn't pay the compile cost.
+    /// Default is `false`
+    pub warmup_components: bool,
+
+    /// Maximum number of components compiled concurrently during warmup.
+    /// Default is `4`
+    pub warmup_parallelism: usize,
+
+    /// Maximum number of operator components executing at once. Triggers beyond it wait
+    /// and are picked by workflow priority as slots free up.
+    /// Default is `None` (unlimited)
+    pub max_concurrent_executions: Option<usize>,
+
+    /// Maximum number of executions a single service may have in flight at once, so one busy
+    /// service can't take every slot. Services can override it with `max_concurrent_executions`.
+    /// Default is `None` (unlimited)
+    pub max_concurrent_executions_per_service: Option<usize>,
+
+    /// Maximum number of concurrent RPC operations per chain
+    /// (service lookups, submit condition checks, submissions).
+    /// Chains that aren't listed are unlimited
+    #[serde(default)]
+    #[schema(value_type = HashMap<String, usize>)]
+    pub chain_max_in_flight: HashMap<ChainKey, usize>,
+
+    /// Custom CA bundle and client certificate per chain, for RPC endpoints
+    /// behind private infrastructure. Loaded and validated at startup.
+    /// Only http endpoints and the evm trigger websockets use these
+    #[serde(default)]
+    #[schema(value_type = HashMap<String, Object>)]
+    pub chain_tls: HashMap<ChainKey, RpcTlsConfig>,
+
+    /// Floor and cap (in wei) on the gas price of aggregator submissions per EVM chain.
+    /// Prices outside the range are clamped, and submissions are held while the
+    /// network price is above the cap. Chains that aren't listed are unbounded
+    #[serde(default)]
+    #[schema(value_type = HashMap<String, Object>)]
+    pub chain_gas_bounds: HashMap<ChainKey, GasPriceBounds>,
+
+    /// Confirmations an aggregator submission waits for per EVM chain before it's
+    /// considered final, for reorg-prone chains. Chains that aren't listed treat a
+    /// submission as final once it's mined
+    #[serde(default)]
+    #[schema(value_type = HashMap<String, Object>)]
+    pub chain_confirmations: HashMap<ChainKey, ConfirmationPolicy>,
+
+    /// JSON file with the secret values this operator holds per service, which components
+    /// read with `host::get-secret` when their service definition grants them.
+    /// Default is `None` (no secrets)
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub secrets_file: Option<PathBuf>,
+
+    /// Components granted `file_system` access see `<component_fs_root>/<service_id>` as `.`,
+    /// shared by the service's components and isolated from every other service.
+    /// Default is `None` (`<data>/components`)
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub component_fs_root: Option<PathBuf>,
+
+    /// Compress new component blobs in the content-addressed store with zstd at this level
+    /// (1-22). Blobs already stored are read either way.
+    /// Default is `None` (uncompressed)
+    #[serde(default)]
+    pub ca_compression_level: Option<i32>,
+
+    /// Keep component blobs in an S3-compatible bucket instead of `<data>/ca`, so several
+    /// nodes can share them. Blobs are never removed from the bucket, and listing the stored
+    /// digests isn't supported. `ca_compression_level` only applies to `<data>/ca`.
+    /// Default is `None` (local files)
+    #[serde(default)]
+    #[schema(value_type = Option<Object>)]
+    pub ca_s3: Option<S3Config>,
+
+    /// Record the last log each EVM trigger fired for, and on startup replay the logs
+    /// emitted since then before following the live subscription.
+    /// Default is `false`
+    pub evm_backfill: bool,
+
+    /// Number of blocks requested per `eth_getLogs` call while backfilling.
+    /// Default is `1000`
+    pub evm_backfill_chunk_blocks: u64,
+
+    /// Longest wait, in seconds, between attempts to reconnect a dropped EVM websocket.
+    /// The wait starts at one second and doubles after every round of failed endpoints.
+    /// Default is `30`
+    pub evm_ws_max_backoff_secs: u64,
+
+    /// Make `wasi:clocks` read the trigger's own time (e.g. the block timestamp) in operator
+    /// components, so every operator and every replay sees the same time.
+    /// Default is `false`
+    pub deterministic_clock: bool,
+
+    /// Seed `wasi:random` from the event id, so every operator and every replay draws the
+    /// same values. Don't enable it for components that generate secrets.
+    /// Default is `false`
+    pub deterministic_rng: bool,
+
+    /// Capture what components write to stdout and stderr instead of passing it through to the node's own.
+    /// It's logged at `DEBUG`, tagged with the service, workflow and event, and returned along with
+    /// operator responses, for test harnesses. A component writing more than 256 KiB to either stream traps.
+    /// Default is `false`
+    pub capture_guest_output: bool,
+
+    /// On shutdown, how long to wait for executions and submissions already under way to finish
+    /// after triggers stop, before the remaining subsystems are stopped regardless.
+    /// Default is `30`
+    pub shutdown_timeout_secs: u64,
+
+    /// How much new work can queue at each step from the trigger watcher to an execution slot:
+    /// triggers waiting for the dispatcher, commands waiting for the engine and executions
+    /// waiting for a slot. Once they're full the trigger watcher waits for the engine to catch up
+    /// instead of buffering more triggers.
+    /// Default is `1024`
+    pub dispatcher_channel_capacity: usize,
 }
 
 impl ConfigExt for Config {
@@ -158,6 +429,8 @@ impl Default for Config {
         Self {
             port: 8000,
             log_level: vec!["info".to_string()],
+            log_format: LogFormat::default(),
+            service_log_levels: HashMap::new(),
             host: "127.0.0.1".to_string(),
             data: PathBuf::from("/var/wavs"),
             cors_allowed_origins: Vec::new(),
@@ -167,16 +440,20 @@ impl Default for Config {
             aggregator_cosmos_credential: None,
             aggregator_evm_credential: None,
             max_execution_seconds: Workflow::DEFAULT_TIME_LIMIT_SECONDS,
+            max_response_bytes: None,
             max_wasm_fuel: Workflow::DEFAULT_FUEL_LIMIT,
             jaeger: None,
             prometheus: None,
             prometheus_push_interval_secs: None,
             ipfs_gateway: DEFAULT_IPFS_GATEWAY.to_string(),
+            oci_credentials: OciCredentials::new(),
             bearer_token: None,
+            health_signing_key: None,
             dev_endpoints_enabled: false,
             max_body_size_mb: 15,
             health_check_mode: HealthCheckMode::default(),
             aggregator: AggregatorConfig::default(),
+            status_spill: StatusSpillConfig::default(),
             p2p: P2pConfig::default(),
             #[cfg(feature = "dev")]
             disable_trigger_networking: false,
@@ -185,6 +462,27 @@ impl Default for Config {
             jetstream_endpoint: "wss://jetstream1.us-east.bsky.network/subscribe".to_string(),
             jetstream_max_message_size: 1024 * 1024, // 1MB
             hyperswarm_bootstrap: None,
+            cron_jitter_secs: 0,
+            warmup_components: false,
+            warmup_parallelism: 4,
+            max_concurrent_executions: None,
+            max_concurrent_executions_per_service: None,
+            chain_max_in_flight: HashMap::new(),
+            chain_tls: HashMap::new(),
+            chain_gas_bounds: HashMap::new(),
+            chain_confirmations: HashMap::new(),
+            secrets_file: None,
+            component_fs_root: None,
+            ca_compression_level: None,
+            ca_s3: None,
+            evm_backfill: false,
+            evm_backfill_chunk_blocks: 1000,
+            evm_ws_max_backoff_secs: 30,
+            deterministic_clock: false,
+            deterministic_rng: false,
+            capture_guest_output: false,
+            shutdown_timeout_secs: 30,
+            dispatcher_channel_capacity: 1024,
         }
     }
 }
@@ -0,0 +1,172 @@
+//! Deploys made with a client-supplied deployment id, so a retried deploy returns
+//! the original result instead of registering the service again.
+//!
+//! Services themselves are re-read from chain, but a deployment id only exists here,
+//! so the records are kept in a json file in the data directory to survive restarts.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use thiserror::Error;
+use utils::filesystem::write_atomic;
+use wavs_types::{DeploymentRecord, ServiceManager};
+
+type Result<T> = std::result::Result<T, DeploymentsError>;
+
+/// Longest accepted deployment id
+pub const MAX_DEPLOYMENT_ID_LEN: usize = 128;
+
+#[derive(Clone)]
+pub struct Deployments {
+    path: PathBuf,
+    records: Arc<Mutex<HashMap<String, DeploymentRecord>>>,
+    /// Held across check-deploy-record so concurrent retries of the same deploy don't race
+    deploy_lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl Deployments {
+    pub const FILENAME: &str = "deployments.json";
+
+    pub fn load(data_dir: &Path) -> Result<Self> {
+        let path = data_dir.join(Self::FILENAME);
+        let records = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice::<Vec<DeploymentRecord>>(&bytes)?
+                .into_iter()
+                .map(|record| (record.deployment_id.clone(), record))
+                .collect(),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(Self {
+            path,
+            records: Arc::new(Mutex::new(records)),
+            deploy_lock: Arc::new(tokio::sync::Mutex::new(())),
+        })
+    }
+
+    pub async fn lock(&self) -> tokio::sync::MutexGuard<'_, ()> {
+        self.deploy_lock.lock().await
+    }
+
+    pub fn get(&self, deployment_id: &str) -> Option<DeploymentRecord> {
+        self.records.lock().unwrap().get(deployment_id).cloned()
+    }
+
+    /// The existing record for a repeated deploy, or `None` if this is the first attempt.
+    /// Reusing an id for a different service manager is an error
+    pub fn check(
+        &self,
+        deployment_id: &str,
+        service_manager: &ServiceManager,
+    ) -> Result<Option<DeploymentRecord>> {
+        if deployment_id.is_empty() || deployment_id.len() > MAX_DEPLOYMENT_ID_LEN {
+            return Err(DeploymentsError::InvalidId(deployment_id.to_string()));
+        }
+
+        match self.get(deployment_id) {
+            Some(record) if record.service_manager != *service_manager => {
+                Err(DeploymentsError::Conflict {
+                    deployment_id: deployment_id.to_string(),
+                    service_manager: Box::new(record.service_manager),
+                })
+            }
+            record => Ok(record),
+        }
+    }
+
+    /// Persists the record before returning, replacing the file atomically
+    /// so a crash can't leave a truncated file behind
+    pub async fn record(&self, record: DeploymentRecord) -> Result<()> {
+        let _self = self.clone();
+        tokio::task::spawn_blocking(move || _self.record_blocking(record)).await?
+    }
+
+    fn record_blocking(&self, record: DeploymentRecord) -> Result<()> {
+        // held through the write, so the file always ends up with the latest records
+        let mut records = self.records.lock().unwrap();
+        records.insert(record.deployment_id.clone(), record);
+
+        let mut sorted: Vec<_> = records.values().collect();
+        sorted.sort_by_key(|record| (record.deployed_at, &record.deployment_id));
+
+        write_atomic(&self.path, &serde_json::to_vec_pretty(&sorted)?)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum DeploymentsError {
+    #[error("Deployment id must be 1-{MAX_DEPLOYMENT_ID_LEN} characters, got {0:?}")]
+    InvalidId(String),
+
+    #[error("Deployment id {deployment_id} was already used for {service_manager:?}")]
+    Conflict {
+        deployment_id: String,
+        service_manager: Box<ServiceManager>,
+    },
+
+    #[error("Deployments file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Deployments file: {0}")]
+    Serde(#[from] serde_json::Error),
+
+    #[error("Deployments file write task failed: {0}")]
+    WriteTask(#[from] tokio::task::JoinError),
+}
+
+#[cfg(test)]
+mod test {
+    use alloy_primitives::Address;
+    use wavs_types::{ChainKey, ServiceId};
+
+    use super::*;
+
+    fn manager(address: Address) -> ServiceManager {
+        ServiceManager::Evm {
+            chain: ChainKey::new("evm:31337").unwrap(),
+            address,
+        }
+    }
+
+    #[tokio::test]
+    async fn records_survive_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let deployments = Deployments::load(dir.path()).unwrap();
+        let service_manager = manager(Address::ZERO);
+
+        assert_eq!(
+            deployments.check("deploy-1", &service_manager).unwrap(),
+            None
+        );
+
+        let record = DeploymentRecord {
+            deployment_id: "deploy-1".to_string(),
+            service_manager: service_manager.clone(),
+            service_id: ServiceId::hash(b"service"),
+            deployed_at: 1,
+        };
+        deployments.record(record.clone()).await.unwrap();
+
+        let reloaded = Deployments::load(dir.path()).unwrap();
+        assert_eq!(
+            reloaded.check("deploy-1", &service_manager).unwrap(),
+            Some(record)
+        );
+
+        // same id for a different service manager
+        assert!(matches!(
+            reloaded.check("deploy-1", &manager(Address::repeat_byte(1))),
+            Err(DeploymentsError::Conflict { .. })
+        ));
+        assert!(matches!(
+            reloaded.check("", &service_manager),
+            Err(DeploymentsError::InvalidId(_))
+        ));
+    }
+}
@@ -22,37 +22,47 @@
  *
  ***/
 
-use alloy_provider::{DynProvider, ProviderBuilder};
+use alloy_provider::DynProvider;
 use anyhow::Result;
 use futures::{stream, StreamExt};
 use iri_string::types::{CreationError, UriString};
 use layer_climb::querier::QueryClient;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::ops::Bound;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
 use thiserror::Error;
 use tracing::instrument;
+use utils::chain_throttle::ChainThrottle;
 use utils::error::EvmClientError;
+use utils::evm_client::EvmEndpoint;
 use utils::service::fetch_service;
-use utils::storage::fs::FileStorage;
-use utils::telemetry::{DispatcherMetrics, WavsMetrics};
+use utils::storage::{any::AnyStorage, fs::FileStorage, object::ObjectCAStorage, s3::S3Storage};
+use utils::telemetry::{DispatcherMetrics, PipelineMetrics, WavsMetrics};
+use utils::tls::{ChainTls, RpcTlsError};
+use wavs_engine::worlds::determinism::Determinism;
 use wavs_types::contracts::cosmwasm::service_manager::ServiceManagerQueryMessages;
 use wavs_types::IWavsServiceManager::IWavsServiceManagerInstance;
 use wavs_types::{
-    AnyChainConfig, ChainConfigError, ChainConfigs, ChainKey, ComponentDigest, ServiceManager,
-    Submission, WorkflowIdError,
+    AnyChainConfig, ChainConfigError, ChainConfigs, ChainKey, ChainKeyNamespace, ComponentDigest,
+    ServiceEventKind, ServiceManager, Submission, WorkflowIdError,
 };
 use wavs_types::{Service, ServiceError, ServiceId, SignerResponse, TriggerAction};
 
 use crate::config::Config;
+use crate::service_events::ServiceEvents;
 use crate::services::{Services, ServicesError};
 use crate::subsystems::aggregator::error::AggregatorError;
 use crate::subsystems::aggregator::{Aggregator, AggregatorCommand};
 use crate::subsystems::engine::error::EngineError;
+use crate::subsystems::engine::scheduler::ExecutionScheduler;
+use crate::subsystems::engine::secrets::{SecretsError, ServiceSecrets};
+use crate::subsystems::engine::status::{OutcomeSpill, WorkflowStatusTracker};
 use crate::subsystems::engine::wasm_engine::WasmEngine;
 use crate::subsystems::engine::{
     AggregatorExecuteKind, EngineCommand, EngineManager, EngineResponse,
 };
+use crate::subsystems::in_flight::InFlight;
 use crate::subsystems::submission::error::SubmissionError;
 use crate::subsystems::submission::{SubmissionCommand, SubmissionManager};
 use crate::subsystems::trigger::error::TriggerError;
@@ -70,16 +80,33 @@ pub struct Dispatcher<S: CAStorage> {
     pub services: Services,
     pub chain_configs: Arc<RwLock<ChainConfigs>>,
     pub metrics: DispatcherMetrics,
+    pub pipeline_metrics: PipelineMetrics,
     pub ipfs_gateway: String,
     pub subsystem_to_dispatcher_rx: crossbeam::channel::Receiver<DispatcherCommand>,
+    pub trigger_to_dispatcher_rx: crossbeam::channel::Receiver<DispatcherCommand>,
     pub dispatcher_to_engine_tx: crossbeam::channel::Sender<EngineCommand>,
     pub dispatcher_to_submission_tx: crossbeam::channel::Sender<SubmissionCommand>,
     pub dispatcher_to_aggregator_tx: crossbeam::channel::Sender<AggregatorCommand>,
     pub db_storage: WavsDb,
+    /// Parallelism for precompiling components at startup, `None` if warmup is disabled
+    pub warmup_parallelism: Option<usize>,
+    /// Per-chain limit on concurrent RPC operations, shared with the aggregator
+    pub chain_throttle: ChainThrottle,
+    /// Custom TLS for chain RPC endpoints, shared with the trigger manager and aggregator
+    pub chain_tls: ChainTls,
+    /// Work the engine, submission manager and aggregator have under way, drained on shutdown
+    pub in_flight: InFlight,
+    /// How long shutdown waits for `in_flight` to drain
+    pub shutdown_timeout: Duration,
+    /// What's happening to each service's workflows, for `GET /services/{service_id}/events`
+    pub service_events: ServiceEvents,
     /// Cached EVM HTTP providers per chain to avoid creating new connections for each query
     evm_http_providers: Arc<RwLock<HashMap<ChainKey, DynProvider>>>,
     /// Cached Cosmos query clients per chain to avoid creating new connections for each query
     cosmos_query_clients: Arc<RwLock<HashMap<ChainKey, QueryClient>>>,
+    /// Components stored for a service that isn't saved yet, with how many such services use each.
+    /// `release_components` checks them under the same lock, so it can't remove them in between
+    pinned_components: Arc<Mutex<HashMap<ComponentDigest, usize>>>,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -99,15 +126,22 @@ pub enum DispatcherCommand {
     },
 }
 
-impl Dispatcher<FileStorage> {
+impl Dispatcher<AnyStorage> {
     pub fn new(config: &Config, metrics: WavsMetrics) -> Result<Self, DispatcherError> {
         // Create all our channels for communication
         // except dispatcher_to_trigger calls its local stream channel
+        // New work is bounded all the way to the engine, so the trigger watcher waits when the
+        // engine falls behind instead of queueing triggers without limit. Responses stay
+        // unbounded: they're sent from inside executions, which have to finish to free up room
+        let channel_capacity = config.dispatcher_channel_capacity.max(1);
+        let (trigger_to_dispatcher_tx, trigger_to_dispatcher_rx) =
+            crossbeam::channel::bounded::<DispatcherCommand>(channel_capacity);
+
         let (subsystem_to_dispatcher_tx, subsystem_to_dispatcher_rx) =
             crossbeam::channel::unbounded::<DispatcherCommand>();
 
         let (dispatcher_to_engine_tx, dispatcher_to_engine_rx) =
-            crossbeam::channel::unbounded::<EngineCommand>();
+            crossbeam::channel::bounded::<EngineCommand>(channel_capacity);
 
         let (dispatcher_to_submission_tx, dispatcher_to_submission_rx) =
             crossbeam::channel::unbounded::<SubmissionCommand>();
@@ -115,21 +149,49 @@ impl Dispatcher<FileStorage> {
         let (dispatcher_to_aggregator_tx, dispatcher_to_aggregator_rx) =
             crossbeam::channel::unbounded::<AggregatorCommand>();
 
-        let file_storage = FileStorage::new(config.data.join("ca"))?;
+        // fail before anything connects if the certificates can't be used
+        let chain_tls = ChainTls::load(&config.chain_tls)?;
+        for chain in config.chain_tls.keys() {
+            if chain.namespace.as_str() != ChainKeyNamespace::EVM {
+                return Err(DispatcherError::Config(format!(
+                    "chain_tls is only supported for evm chains, not {chain}"
+                )));
+            }
+        }
+
+        let ca_storage: AnyStorage = match &config.ca_s3 {
+            Some(s3) => ObjectCAStorage::new(S3Storage::new(s3.clone())).into(),
+            None => {
+                let mut file_storage = FileStorage::new(config.data.join("ca"))?;
+                if let Some(level) = config.ca_compression_level {
+                    file_storage = file_storage.with_compression(level);
+                }
+                file_storage.into()
+            }
+        };
         let db_storage = WavsDb::new()?;
 
         let services = Services::new(db_storage.clone());
+        let in_flight = InFlight::default();
+        let service_events = ServiceEvents::default();
 
         let trigger_manager = TriggerManager::new(
             config,
             metrics.trigger,
             services.clone(),
-            subsystem_to_dispatcher_tx.clone(),
-        )?;
+            trigger_to_dispatcher_tx,
+        )?
+        .with_chain_tls(chain_tls.clone());
+
+        let scheduler = ExecutionScheduler::new(
+            config.max_concurrent_executions,
+            Some(metrics.engine.clone()),
+        )
+        .with_default_service_limit(config.max_concurrent_executions_per_service);
 
         let app_storage = config.data.join("app");
         let engine = WasmEngine::new(
-            file_storage,
+            ca_storage,
             app_storage,
             config.wasm_lru_size,
             config.chains.clone(),
@@ -138,13 +200,50 @@ impl Dispatcher<FileStorage> {
             metrics.engine,
             db_storage.clone(),
             config.ipfs_gateway.clone(),
+        )
+        .with_secrets(match &config.secrets_file {
+            Some(path) => ServiceSecrets::load(path)?,
+            None => ServiceSecrets::default(),
+        })
+        .with_oci_credentials(config.oci_credentials.clone())
+        .with_chain_tls(chain_tls.clone())
+        .with_determinism(Determinism {
+            clock: config.deterministic_clock,
+            rng: config.deterministic_rng,
+        })
+        .with_capture_guest_output(config.capture_guest_output)
+        .with_max_response_bytes(config.max_response_bytes)
+        .with_component_fs_root(
+            config
+                .component_fs_root
+                .clone()
+                .unwrap_or_else(|| config.data.join("components")),
         );
+        let workflow_status = WorkflowStatusTracker::new(if config.status_spill.enabled {
+            Some(
+                OutcomeSpill::new(
+                    config.data.join("workflow_status"),
+                    config.status_spill.max_disk_bytes(),
+                    Duration::from_secs(config.status_spill.retention_secs()),
+                )
+                .map_err(DispatcherError::StatusSpill)?,
+            )
+        } else {
+            None
+        });
+
         let engine_manager = EngineManager::new(
             engine,
             services.clone(),
+            workflow_status,
+            scheduler,
             dispatcher_to_engine_rx,
             subsystem_to_dispatcher_tx.clone(),
-        );
+        )
+        .with_pipeline_metrics(metrics.pipeline.clone())
+        .with_in_flight(in_flight.clone())
+        .with_service_events(service_events.clone())
+        .with_max_queued(channel_capacity);
 
         let submission_manager = SubmissionManager::new(
             config,
@@ -152,16 +251,25 @@ impl Dispatcher<FileStorage> {
             services.clone(),
             dispatcher_to_submission_rx,
             subsystem_to_dispatcher_tx.clone(),
-        )?;
+        )?
+        .with_pipeline_metrics(metrics.pipeline.clone())
+        .with_in_flight(in_flight.clone());
+
+        let chain_throttle = ChainThrottle::new(&config.chain_max_in_flight, Some(metrics.chain));
 
         let aggregator = Aggregator::new(
             config,
             metrics.aggregator,
+            chain_throttle.clone(),
+            chain_tls.clone(),
             services.clone(),
             dispatcher_to_aggregator_rx,
             dispatcher_to_aggregator_tx.clone(),
             subsystem_to_dispatcher_tx.clone(),
-        )?;
+        )?
+        .with_pipeline_metrics(metrics.pipeline.clone())
+        .with_in_flight(in_flight.clone())
+        .with_target_deliverer(submission_manager.target_deliverer());
 
         Ok(Self {
             trigger_manager,
@@ -170,20 +278,43 @@ impl Dispatcher<FileStorage> {
             aggregator,
             services,
             db_storage,
+            warmup_parallelism: config
+                .warmup_components
+                .then_some(config.warmup_parallelism),
+            chain_throttle,
+            chain_tls,
+            in_flight,
+            shutdown_timeout: Duration::from_secs(config.shutdown_timeout_secs),
+            service_events,
             chain_configs: config.chains.clone(),
             metrics: metrics.dispatcher.clone(),
+            pipeline_metrics: metrics.pipeline,
             ipfs_gateway: config.ipfs_gateway.clone(),
             subsystem_to_dispatcher_rx,
+            trigger_to_dispatcher_rx,
             dispatcher_to_engine_tx,
             dispatcher_to_submission_tx,
             dispatcher_to_aggregator_tx,
             evm_http_providers: Arc::new(RwLock::new(HashMap::new())),
             cosmos_query_clients: Arc::new(RwLock::new(HashMap::new())),
+            pinned_components: Arc::new(Mutex::new(HashMap::new())),
         })
     }
 }
 
 impl<S: CAStorage + 'static> Dispatcher<S> {
+    /// Responses from the subsystems go first, they're what frees up room for new triggers
+    fn next_command(&self) -> Result<DispatcherCommand, crossbeam::channel::RecvError> {
+        if let Ok(command) = self.subsystem_to_dispatcher_rx.try_recv() {
+            return Ok(command);
+        }
+
+        crossbeam::channel::select! {
+            recv(self.subsystem_to_dispatcher_rx) -> command => command,
+            recv(self.trigger_to_dispatcher_rx) -> command => command,
+        }
+    }
+
     /// This will run forever, taking the triggers, processing results, and sending them to submission to write.
     #[instrument(skip(self, ctx), fields(subsys = "Dispatcher"))]
     pub fn start(&self, ctx: AppContext) -> Result<(), DispatcherError> {
@@ -230,7 +361,7 @@ impl<S: CAStorage + 'static> Dispatcher<S> {
                 ctx.rt.clone().block_on(async move {
                     if ctx.get_kill_receiver().recv().await.is_ok() {
                         tracing::info!("Shutdown signal received, shutting down dispatcher");
-                        // shut down trigger manager
+                        // shut down trigger manager first, so no new work comes in
                         if let Err(err) = _self
                             .trigger_manager
                             .command_sender
@@ -238,6 +369,19 @@ impl<S: CAStorage + 'static> Dispatcher<S> {
                         {
                             tracing::error!("Error shutting down trigger manager: {:?}", err);
                         }
+                        // let executions and submissions already under way finish
+                        tracing::info!(
+                            "Waiting up to {:?} for {} in-flight execution(s) and submission(s)",
+                            _self.shutdown_timeout,
+                            _self.in_flight.count()
+                        );
+                        match _self.in_flight.drain(_self.shutdown_timeout).await {
+                            Ok(()) => tracing::info!("In-flight work drained"),
+                            Err(remaining) => tracing::warn!(
+                                "Shutdown timeout elapsed with {} execution(s) and submission(s) still in flight, stopping anyway",
+                                remaining
+                            ),
+                        }
                         // shut down engine manager
                         if let Err(err) = _self.dispatcher_to_engine_tx.send(EngineCommand::Kill) {
                             tracing::error!("Error sending kill to engine manager: {:?}", err);
@@ -264,11 +408,25 @@ impl<S: CAStorage + 'static> Dispatcher<S> {
         // handle incoming commands from subsystems
         std::thread::spawn({
             let _self = self.clone();
+            let ctx = ctx.clone();
             let ctx_rt = ctx.rt.clone();
             move || {
-                while let Ok(command) = _self.subsystem_to_dispatcher_rx.recv() {
+                while let Ok(command) = _self.next_command() {
                     match command {
+                        // triggers that were already queued when shutdown started are dropped
+                        DispatcherCommand::Trigger(action) if ctx.killed() => {
+                            tracing::debug!(
+                                service_id = %action.config.service_id,
+                                workflow_id = %action.config.workflow_id,
+                                "Shutting down, dropping trigger action",
+                            );
+                        }
                         DispatcherCommand::Trigger(action) => {
+                            _self.pipeline_metrics.record_trigger_received(
+                                &action.config.service_id.to_string(),
+                                action.data.chain(),
+                            );
+
                             let service = match _self.services.get(&action.config.service_id) {
                                 Ok(service) => service,
                                 Err(err) => {
@@ -277,7 +435,16 @@ impl<S: CAStorage + 'static> Dispatcher<S> {
                                 }
                             };
 
+                            _self.service_events.publish(
+                                &action.config.service_id,
+                                &action.config.workflow_id,
+                                ServiceEventKind::TriggerReceived {
+                                    trigger_type: action.data.trigger_type().to_string(),
+                                },
+                            );
+
                             tracing::info!(
+                                service.name = %service.name,
                                 service_id = %action.config.service_id,
                                 workflow_id = %action.config.workflow_id,
                                 "Dispatcher received trigger action",
@@ -318,6 +485,18 @@ impl<S: CAStorage + 'static> Dispatcher<S> {
                                 }
                             }
 
+                            EngineResponse::OperatorSequence(msgs) => {
+                                if let Err(e) = _self
+                                    .dispatcher_to_submission_tx
+                                    .send(SubmissionCommand::SubmitSequence(msgs))
+                                {
+                                    tracing::error!(
+                                        "Error sending message to submission manager: {:?}",
+                                        e
+                                    );
+                                }
+                            }
+
                             // This is AFTER aggregator has aggregated, and executed the component
                             EngineResponse::Aggregator {
                                 submission,
@@ -337,6 +516,14 @@ impl<S: CAStorage + 'static> Dispatcher<S> {
                         },
 
                         DispatcherCommand::SubmissionResponse(submission) => {
+                            _self.service_events.publish(
+                                &submission.trigger_action.config.service_id,
+                                &submission.trigger_action.config.workflow_id,
+                                ServiceEventKind::Submission {
+                                    event_id: submission.event_id.clone(),
+                                },
+                            );
+
                             // This is BEFORE aggregator has even broadcast
                             if let Err(e) = _self
                                 .dispatcher_to_aggregator_tx
@@ -379,6 +566,12 @@ impl<S: CAStorage + 'static> Dispatcher<S> {
             self.list_component_digests()?.len()
         );
 
+        // compile components before any triggers are registered, so the first executions are warm
+        if let Some(parallelism) = self.warmup_parallelism {
+            ctx.rt
+                .block_on(self.engine_manager.warmup(&initial_services, parallelism));
+        }
+
         for service in initial_services.iter() {
             add_service_to_managers(
                 service,
@@ -394,11 +587,15 @@ impl<S: CAStorage + 'static> Dispatcher<S> {
         let ipfs_gateway = self.ipfs_gateway.clone();
         let evm_http_providers = self.evm_http_providers.clone();
         let cosmos_query_clients = self.cosmos_query_clients.clone();
+        let chain_throttle = self.chain_throttle.clone();
+        let chain_tls = self.chain_tls.clone();
         ctx.rt.block_on(async {
             let ipfs_gateway = ipfs_gateway.as_ref();
             let chain_configs = &chain_configs;
             let evm_http_providers = &evm_http_providers;
             let cosmos_query_clients = &cosmos_query_clients;
+            let chain_throttle = &chain_throttle;
+            let chain_tls = &chain_tls;
 
             // Limit concurrent ServiceURI checks
             const MAX_CONCURRENT_CHECKS: usize = 10;
@@ -414,6 +611,8 @@ impl<S: CAStorage + 'static> Dispatcher<S> {
                                 ipfs_gateway,
                                 evm_http_providers,
                                 cosmos_query_clients,
+                                chain_throttle,
+                                chain_tls,
                             )
                             .await,
                         )
@@ -486,7 +685,7 @@ impl<S: CAStorage + 'static> Dispatcher<S> {
         Ok(digests)
     }
 
-    #[instrument(skip(self), fields(subsys = "Dispatcher"))]
+    #[instrument(skip(self, service_manager), fields(subsys = "Dispatcher", service.manager = %service_manager))]
     pub async fn add_service(
         &self,
         service_manager: ServiceManager,
@@ -507,6 +706,8 @@ impl<S: CAStorage + 'static> Dispatcher<S> {
             &self.ipfs_gateway,
             &self.evm_http_providers,
             &self.cosmos_query_clients,
+            &self.chain_throttle,
+            &self.chain_tls,
         )
         .await?;
 
@@ -517,26 +718,40 @@ impl<S: CAStorage + 'static> Dispatcher<S> {
         let total_services = current_services.len();
         let total_workflows: usize = current_services.iter().map(|s| s.workflows.len()).sum();
 
-        tracing::info!(service.name = %service.name, service.manager = ?service.manager, workflows = %service.workflows.len(), total_services = %total_services, total_workflows = %total_workflows, "Service registered: {} [{:?}], workflows={}, total_services={}, total_workflows={}", service.name, service.manager, service.workflows.len(), total_services, total_workflows);
+        tracing::info!(service.name = %service.name, service.manager = %service.manager, workflows = %service.workflows.len(), total_services = %total_services, total_workflows = %total_workflows, "Service registered: {} [{}], workflows={}, total_services={}, total_workflows={}", service.name, service.manager, service.workflows.len(), total_services, total_workflows);
 
         Ok(service)
     }
 
     // this is public just so we can call it from tests
-    #[instrument(skip(self), fields(subsys = "Dispatcher", service.name = %service.name, service.manager = ?service.manager))]
+    #[instrument(skip(self, service), fields(subsys = "Dispatcher", service.name = %service.name, service.manager = %service.manager))]
     pub async fn add_service_direct(&self, service: Service) -> Result<(), DispatcherError> {
         let service_id = service.id();
-        tracing::info!("Adding service: {} [{:?}]", service.name, service.manager);
+        tracing::info!(
+            "Adding service: {} [{}] ({})",
+            service.name,
+            service.manager,
+            service_id
+        );
+        tracing::debug!("Adding service: {:?}", service);
         // Check if service is already registered
         if self.services.exists(&service_id)? {
             return Err(DispatcherError::ServiceRegistered(service_id));
         }
 
+        // Held until the service is saved, so removing another service can't release them first
+        let _pins = self.pin_components(&service);
+
         // Store components
         self.engine_manager
             .store_components_for_service(&service)
             .await?;
 
+        // Run component init hooks, a failure here keeps the service from going live
+        self.engine_manager
+            .init_components_for_service(&service)
+            .await?;
+
         // Store the service
         self.services.save(&service)?;
 
@@ -554,8 +769,26 @@ impl<S: CAStorage + 'static> Dispatcher<S> {
 
     #[instrument(skip(self), fields(subsys = "Dispatcher"))]
     pub fn remove_service(&self, id: ServiceId) -> Result<(), DispatcherError> {
+        let digests = self
+            .services
+            .try_get(&id)?
+            .map(|service| service.component_digests());
+
+        self.unregister_service(id)?;
+
+        if let Some(digests) = digests {
+            self.release_components(digests)?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the service everywhere except component storage,
+    /// which may still be needed by a replacement service
+    fn unregister_service(&self, id: ServiceId) -> Result<(), DispatcherError> {
         self.services.remove(&id)?;
         self.engine_manager.engine.remove_storage(&id);
+        self.engine_manager.workflow_status.remove_service(&id);
         self.trigger_manager.remove_service(id.clone())?;
         // no need to remove from submission manager, it has nothing to do
 
@@ -587,6 +820,48 @@ impl<S: CAStorage + 'static> Dispatcher<S> {
         Ok(())
     }
 
+    /// Services with the same component bytes share one stored and compiled copy,
+    /// so a digest is only dropped once no remaining service uses it
+    fn release_components(
+        &self,
+        digests: BTreeSet<ComponentDigest>,
+    ) -> Result<(), DispatcherError> {
+        // a service being added pins its components before storing them and unpins after
+        // it's saved, so holding this covers every service that could use them
+        let pinned = self.pinned_components.lock().unwrap();
+
+        let in_use: BTreeSet<ComponentDigest> = self
+            .services
+            .list(Bound::Unbounded, Bound::Unbounded)?
+            .iter()
+            .flat_map(Service::component_digests)
+            .chain(pinned.keys().cloned())
+            .collect();
+
+        for digest in digests.difference(&in_use) {
+            match self.engine_manager.engine.remove_component(digest) {
+                Ok(()) => tracing::info!("Removed component {digest}, no service uses it anymore"),
+                Err(err) => tracing::warn!("Failed to remove unused component {digest}: {err}"),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn pin_components(&self, service: &Service) -> ComponentPins {
+        let digests = service.component_digests();
+
+        let mut pinned = self.pinned_components.lock().unwrap();
+        for digest in &digests {
+            *pinned.entry(digest.clone()).or_default() += 1;
+        }
+
+        ComponentPins {
+            pinned: self.pinned_components.clone(),
+            digests,
+        }
+    }
+
     #[instrument(skip(self), fields(subsys = "Dispatcher"))]
     pub fn get_service_signer(
         &self,
@@ -608,7 +883,7 @@ impl<S: CAStorage + 'static> Dispatcher<S> {
         self.change_service_inner(service_id, service).await
     }
 
-    #[instrument(skip(self), fields(subsys = "Dispatcher"))]
+    #[instrument(skip(self, service), fields(subsys = "Dispatcher", service.name = %service.name))]
     async fn change_service_inner(
         &self,
         service_id: ServiceId,
@@ -628,8 +903,15 @@ impl<S: CAStorage + 'static> Dispatcher<S> {
         if tracing::enabled!(tracing::Level::INFO) {
             let old_service = self.services.get(&service_id)?;
 
-            tracing::info!("Changing service from {:?} to {:?}", old_service, service);
-            tracing::info!("hash {} to {}", old_service.hash()?, service.hash()?);
+            tracing::info!(
+                "Changing service {} [{}] ({}), hash {} to {}",
+                service.name,
+                service.manager,
+                service_id,
+                old_service.hash()?,
+                service.hash()?
+            );
+            tracing::debug!("Changing service from {:?} to {:?}", old_service, service);
         }
 
         // We can't exactly just remove the service and then call `add_service_direct` here because it's async
@@ -637,13 +919,25 @@ impl<S: CAStorage + 'static> Dispatcher<S> {
         // so we do the same steps manually and call the async part of the flow (adding components)
         // _before_ removing the service.
 
+        let _pins = self.pin_components(&service);
+
         // Store components
         self.engine_manager
             .store_components_for_service(&service)
             .await?;
 
+        // Run component init hooks while the old service is still live, so a failure leaves it in place
+        self.engine_manager
+            .init_components_for_service(&service)
+            .await?;
+
+        let old_digests = self
+            .services
+            .try_get(&service_id)?
+            .map(|old_service| old_service.component_digests());
+
         // Remove the old service - after this, no await points until the new service is added
-        self.remove_service(service_id.clone())?;
+        self.unregister_service(service_id.clone())?;
 
         // Store the service BEFORE setting up triggers/P2P subscription
         // This ensures the service is in the database before any triggers can fire
@@ -659,10 +953,35 @@ impl<S: CAStorage + 'static> Dispatcher<S> {
             Some(hd_index),
         )?;
 
+        // only now that the new service is saved can we tell which old components are unused
+        if let Some(old_digests) = old_digests {
+            self.release_components(old_digests)?;
+        }
+
         Ok(())
     }
 }
 
+/// Unpins a service's components when dropped, once it's saved or failed to be added
+struct ComponentPins {
+    pinned: Arc<Mutex<HashMap<ComponentDigest, usize>>>,
+    digests: BTreeSet<ComponentDigest>,
+}
+
+impl Drop for ComponentPins {
+    fn drop(&mut self) {
+        let mut pinned = self.pinned.lock().unwrap();
+        for digest in &self.digests {
+            if let Some(count) = pinned.get_mut(digest) {
+                *count -= 1;
+                if *count == 0 {
+                    pinned.remove(digest);
+                }
+            }
+        }
+    }
+}
+
 /// Standalone function to verify service URI
 /// Returns Some(Service) with the new Service if the service needs updating, None if it's up to date
 async fn check_service_needs_update(
@@ -671,6 +990,8 @@ async fn check_service_needs_update(
     ipfs_gateway: &str,
     evm_http_providers: &Arc<RwLock<HashMap<ChainKey, DynProvider>>>,
     cosmos_query_clients: &Arc<RwLock<HashMap<ChainKey, QueryClient>>>,
+    chain_throttle: &ChainThrottle,
+    chain_tls: &ChainTls,
 ) -> Result<Option<Service>, DispatcherError> {
     let service_id = service.id();
     let cached_hash = service.hash()?;
@@ -685,6 +1006,8 @@ async fn check_service_needs_update(
                 ipfs_gateway,
                 evm_http_providers,
                 cosmos_query_clients,
+                chain_throttle,
+                chain_tls,
             )
             .await?
         }
@@ -696,6 +1019,8 @@ async fn check_service_needs_update(
                 ipfs_gateway,
                 evm_http_providers,
                 cosmos_query_clients,
+                chain_throttle,
+                chain_tls,
             )
             .await?
         }
@@ -717,6 +1042,7 @@ async fn check_service_needs_update(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn query_service_from_address(
     chain: ChainKey,
     address: layer_climb::prelude::Address,
@@ -724,12 +1050,17 @@ async fn query_service_from_address(
     ipfs_gateway: &str,
     evm_http_providers: &Arc<RwLock<HashMap<ChainKey, DynProvider>>>,
     cosmos_query_clients: &Arc<RwLock<HashMap<ChainKey, QueryClient>>>,
+    chain_throttle: &ChainThrottle,
+    chain_tls: &ChainTls,
 ) -> Result<Service, DispatcherError> {
     // Get the chain config
     let chain_config = chain_configs.get_chain(&chain).ok_or_else(|| {
         DispatcherError::Config(format!("Could not get chain config for chain {chain}"))
     })?;
 
+    // only the contract query counts against the chain's limit, not fetching the service itself
+    let permit = chain_throttle.acquire(&chain).await;
+
     // Handle different chain types
     let service_uri = match chain_config {
         AnyChainConfig::Evm(evm_config) => {
@@ -750,11 +1081,12 @@ async fn query_service_from_address(
                     })?;
 
                     // Create a provider using the HTTP endpoint
-                    let new_provider = DynProvider::new(ProviderBuilder::new().connect_http(
-                        reqwest::Url::parse(&http_endpoint).unwrap_or_else(|_| {
-                            panic!("Could not parse http endpoint {}", http_endpoint)
-                        }),
-                    ));
+                    let new_provider =
+                        EvmEndpoint::Http(reqwest::Url::parse(&http_endpoint).unwrap_or_else(
+                            |_| panic!("Could not parse http endpoint {}", http_endpoint),
+                        ))
+                        .to_provider_with_tls(chain_tls.get(&chain))
+                        .await?;
 
                     // Cache the provider
                     let mut providers = evm_http_providers.write().unwrap();
@@ -802,8 +1134,15 @@ async fn query_service_from_address(
 
             service_uri
         }
+        AnyChainConfig::Solana(_) => {
+            return Err(DispatcherError::Config(format!(
+                "Service managers on Solana chains are not supported yet, chain {chain}"
+            )));
+        }
     };
 
+    drop(permit);
+
     let service_uri = UriString::try_from(service_uri)?;
 
     // Fetch the service JSON from the URI
@@ -923,4 +1262,13 @@ pub enum DispatcherError {
 
     #[error("Cosmos query error: {0}")]
     CosmosQuery(anyhow::Error),
+
+    #[error("Workflow status spill: {0}")]
+    StatusSpill(#[source] std::io::Error),
+
+    #[error("RPC TLS: {0}")]
+    RpcTls(#[from] RpcTlsError),
+
+    #[error("Secrets: {0}")]
+    Secrets(#[from] SecretsError),
 }
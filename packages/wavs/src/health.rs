@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
+use utils::health::signed::SignedHealth;
 use utoipa::ToSchema;
 use wavs_types::{ChainConfigs, ChainKey};
 
@@ -8,6 +9,9 @@ use wavs_types::{ChainConfigs, ChainKey};
 pub struct HealthStatus {
     pub timestamp: u64,
     pub chains: HashMap<ChainKey, ChainHealthResult>,
+    /// Signature over `timestamp` by the node's health signing key, if one is configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signed: Option<SignedHealth>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -22,6 +26,7 @@ impl Default for HealthStatus {
         Self {
             timestamp: chrono::Utc::now().timestamp() as u64,
             chains: HashMap::new(),
+            signed: None,
         }
     }
 }
@@ -3,52 +3,113 @@
 // this allows us to use `?` in handlers with anything anyhow-friendly
 // specific errors can formed by returning `HttpError` can be recovered via downcast
 // and HttpError knows how to become a specific HttpResponse with status code
+//
+// Either way the body is an `ErrorResponse`, so clients can tell errors apart by `code`
+// without parsing the message
 use axum::body::Body;
 use axum::http::{Response, StatusCode};
 use axum::response::IntoResponse;
+use axum::Json;
+use wavs_types::ErrorResponse;
+
+use crate::services::ServicesError;
 
 pub type HttpResult<T> = std::result::Result<T, AnyError>;
 
 #[derive(thiserror::Error, Debug)]
 pub enum HttpError {
-    #[error("not found")]
-    NotFound,
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    BadRequest(String),
+
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error("{0}")]
+    Unauthorized(String),
+
+    /// The node's own configuration doesn't allow serving the request
+    #[error("{0}")]
+    Misconfigured(String),
 }
 
+impl HttpError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            HttpError::NotFound(_) => StatusCode::NOT_FOUND,
+            HttpError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            HttpError::Conflict(_) => StatusCode::CONFLICT,
+            HttpError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            HttpError::Misconfigured(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            HttpError::NotFound(_) => "not_found",
+            HttpError::BadRequest(_) => "bad_request",
+            HttpError::Conflict(_) => "conflict",
+            HttpError::Unauthorized(_) => "unauthorized",
+            HttpError::Misconfigured(_) => "misconfigured",
+        }
+    }
+}
+
+/// `code` for anything that isn't an [`HttpError`]
+pub const INTERNAL_ERROR_CODE: &str = "internal_error";
+
 // Make our own error that wraps `anyhow::Error`.
 pub struct AnyError(anyhow::Error);
 
 impl IntoResponse for AnyError {
     fn into_response(self) -> Response<Body> {
-        match self.0.downcast::<HttpError>() {
-            Ok(app_error) => app_error.into_response(),
-            Err(e) => {
-                let e = e.to_string();
-
-                Response::builder()
-                    .status(StatusCode::INTERNAL_SERVER_ERROR)
-                    .body(e.into())
-                    .unwrap()
-            }
+        let e = match self.0.downcast::<HttpError>() {
+            Ok(app_error) => return app_error.into_response(),
+            Err(e) => e,
+        };
+
+        // lookups of a service or workflow that doesn't exist, however deep they were wrapped
+        let unknown = e
+            .chain()
+            .find_map(|cause| match cause.downcast_ref::<ServicesError>() {
+                Some(
+                    err
+                    @ (ServicesError::UnknownService(_) | ServicesError::UnknownWorkflow { .. }),
+                ) => Some(err.to_string()),
+                _ => None,
+            });
+        if let Some(message) = unknown {
+            return HttpError::NotFound(message).into_response();
         }
+
+        tracing::error!("Internal error handling request: {:?}", e);
+        error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            e.to_string(),
+            INTERNAL_ERROR_CODE,
+        )
     }
 }
 
 impl IntoResponse for HttpError {
     fn into_response(self) -> Response<Body> {
-        let status = match &self {
-            HttpError::NotFound => StatusCode::NOT_FOUND,
-        };
-
-        let body = self.to_string().into();
-
-        Response::builder()
-            .status(status)
-            .body(body)
-            .expect("failed to render response")
+        error_response(self.status(), self.to_string(), self.code())
     }
 }
 
+fn error_response(status: StatusCode, error: String, code: &str) -> Response<Body> {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+        }),
+    )
+        .into_response()
+}
+
 // This enables using `?` on functions that return `Result<_, anyhow::Error>` to turn them into
 // `Result<_, AppError>`. That way you don't need to do that manually.
 impl<E> From<E> for AnyError
@@ -0,0 +1,38 @@
+// JSON responses with an `ETag` derived from the body, so clients polling a read-only endpoint
+// can send it back in `If-None-Match` and get an empty `304 Not Modified` while nothing changed
+use axum::{
+    body::Body,
+    http::{header, HeaderMap, HeaderValue, Response, StatusCode},
+    response::IntoResponse,
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use super::error::HttpResult;
+
+pub fn json_with_etag<T: Serialize>(headers: &HeaderMap, value: &T) -> HttpResult<Response<Body>> {
+    let body = serde_json::to_vec(value)?;
+    let etag = format!("\"{}\"", const_hex::encode(Sha256::digest(&body)));
+    let etag_header = HeaderValue::from_str(&etag)?;
+
+    if if_none_match(headers, &etag) {
+        let mut response = StatusCode::NOT_MODIFIED.into_response();
+        response.headers_mut().insert(header::ETAG, etag_header);
+        return Ok(response);
+    }
+
+    Ok(Response::builder()
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::ETAG, etag_header)
+        .body(Body::from(body))?)
+}
+
+fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
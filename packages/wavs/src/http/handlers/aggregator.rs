@@ -0,0 +1,139 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use wavs_types::{
+    EventId, ObservedSubmission, QuorumQueueId, QuorumQueueStatus, ServiceId, WorkflowId,
+};
+
+use crate::http::{
+    error::{HttpError, HttpResult},
+    state::HttpState,
+};
+
+/// Page size of the queue list when no `limit` is given
+pub const DEFAULT_QUEUE_PAGE_LIMIT: usize = 100;
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct QuorumQueuesQuery {
+    /// Only return the queues for this event (hex encoded)
+    #[param(value_type = Option<String>)]
+    pub event_id: Option<EventId>,
+    /// Only return the active queues of this service
+    #[param(value_type = Option<String>)]
+    pub service_id: Option<ServiceId>,
+    /// Number of queues to skip
+    pub offset: Option<usize>,
+    /// Maximum number of queues to return, defaults to 100
+    pub limit: Option<usize>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/aggregator/queues",
+    params(QuorumQueuesQuery),
+    responses(
+        (status = 200, description = "Quorum queues retrieved successfully", body = Vec<QuorumQueueStatus>),
+        (status = 500, description = "Internal server error")
+    ),
+    description = "Lists the aggregator's quorum queues, ordered by event id, along with which operator contributed each queued submission, from which peer and when"
+)]
+#[axum::debug_handler]
+pub async fn handle_list_quorum_queues(
+    State(state): State<HttpState>,
+    Query(query): Query<QuorumQueuesQuery>,
+) -> impl IntoResponse {
+    match inner(&state, query).await {
+        Ok(resp) => Json(resp).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn inner(state: &HttpState, query: QuorumQueuesQuery) -> HttpResult<Vec<QuorumQueueStatus>> {
+    let queues = state
+        .dispatcher
+        .aggregator
+        .list_quorum_queues(query.event_id, query.service_id)
+        .await?;
+
+    Ok(queues
+        .into_iter()
+        .skip(query.offset.unwrap_or_default())
+        .take(query.limit.unwrap_or(DEFAULT_QUEUE_PAGE_LIMIT))
+        .collect())
+}
+
+#[utoipa::path(
+    get,
+    path = "/aggregator/queues/{id}",
+    params(
+        ("id" = String, Path, description = "Hex encoded queue id, as listed by /aggregator/queues")
+    ),
+    responses(
+        (status = 200, description = "Quorum queue retrieved successfully", body = QuorumQueueStatus),
+        (status = 400, description = "Invalid queue id"),
+        (status = 404, description = "Nothing has been queued under this id"),
+        (status = 500, description = "Internal server error")
+    ),
+    description = "Shows how far a single quorum queue is from quorum: signatures collected, the weights of the last quorum check, its age and the contributing operators"
+)]
+#[axum::debug_handler]
+pub async fn handle_get_quorum_queue(
+    State(state): State<HttpState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match get_inner(&state, &id).await {
+        Ok(resp) => Json(resp).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn get_inner(state: &HttpState, id: &str) -> HttpResult<QuorumQueueStatus> {
+    let queue_id = const_hex::decode(id)
+        .ok()
+        .and_then(|bytes| QuorumQueueId::from_bytes(&bytes).ok())
+        .ok_or_else(|| HttpError::BadRequest(format!("Invalid queue id: {id}")))?;
+
+    state
+        .dispatcher
+        .aggregator
+        .get_quorum_queue_status(&queue_id)
+        .await?
+        .ok_or_else(|| HttpError::NotFound(format!("Unknown queue id: {id}")).into())
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ObservedSubmissionsQuery {
+    /// Only return the records of this service
+    #[param(value_type = Option<String>)]
+    pub service_id: Option<ServiceId>,
+    /// Only return the records of this workflow
+    #[param(value_type = Option<String>)]
+    pub workflow_id: Option<WorkflowId>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/aggregator/observed",
+    params(ObservedSubmissionsQuery),
+    responses(
+        (status = 200, description = "Observed submissions retrieved successfully", body = Vec<ObservedSubmission>),
+    ),
+    description = "Lists what observe-only workflows would have submitted, newest first"
+)]
+#[axum::debug_handler]
+pub async fn handle_list_observed_submissions(
+    State(state): State<HttpState>,
+    Query(query): Query<ObservedSubmissionsQuery>,
+) -> impl IntoResponse {
+    Json(
+        state
+            .dispatcher
+            .aggregator
+            .list_observed(query.service_id.as_ref(), query.workflow_id.as_ref()),
+    )
+}
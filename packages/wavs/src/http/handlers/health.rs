@@ -1,15 +1,20 @@
 use axum::{extract::State, response::IntoResponse, Json};
 use tracing::instrument;
+use utils::health::signed::SignedHealth;
 
-use crate::{health::HealthStatus, http::state::HttpState};
+use crate::{
+    health::HealthStatus,
+    http::{error::AnyError, state::HttpState},
+};
 
 #[utoipa::path(
     get,
     path = "/health",
     responses(
         (status = 200, description = "Health status", body = HealthStatus),
+        (status = 500, description = "Failed to sign the health status"),
     ),
-    description = "Get health status of chain endpoints"
+    description = "Get health status of chain endpoints, signed by the node if a health signing key is configured"
 )]
 #[instrument(level = "debug", skip(state))]
 #[axum::debug_handler]
@@ -24,6 +29,16 @@ pub async fn handle_health(State(state): State<HttpState>) -> impl IntoResponse
 
     state.health_status.update(&chain_configs).await;
 
-    let health_status = state.health_status.read().unwrap().clone();
+    let mut health_status = state.health_status.read().unwrap().clone();
+
+    if let Some(signer) = &state.health_signer {
+        match SignedHealth::sign(signer, health_status.timestamp) {
+            Ok(signed) => health_status.signed = Some(signed),
+            Err(err) => {
+                return AnyError::from(err).into_response();
+            }
+        }
+    }
+
     Json(health_status).into_response()
 }
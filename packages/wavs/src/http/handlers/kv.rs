@@ -3,8 +3,10 @@ use axum::{
     http::StatusCode,
     response::IntoResponse,
 };
+use utils::time::unix_now;
+use wavs_engine::backend::wasi_keyvalue::expiry;
 
-use crate::http::state::HttpState;
+use crate::http::{error::HttpError, state::HttpState};
 
 /// Query a value from the KV store
 ///
@@ -31,8 +33,9 @@ pub async fn handle_get_kv(
     // Construct the full key: {service_id}/{bucket}/{key}
     let full_key = format!("{}/{}/{}", service_id, bucket, key);
 
-    match state.db_storage.kv_store.get_cloned(&full_key) {
+    match expiry::get(&state.db_storage, &full_key, unix_now()) {
         Some(value) => (StatusCode::OK, value).into_response(),
-        None => StatusCode::NOT_FOUND.into_response(),
+        None => HttpError::NotFound(format!("No value for key {key} in bucket {bucket}"))
+            .into_response(),
     }
 }
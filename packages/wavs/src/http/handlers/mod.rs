@@ -1,3 +1,4 @@
+pub mod aggregator;
 pub mod chain;
 mod config;
 pub mod debug;
@@ -8,7 +9,12 @@ mod not_found;
 pub(crate) mod openapi;
 mod p2p;
 pub mod service;
+mod submission;
+mod webhook;
 
+pub use aggregator::{
+    handle_get_quorum_queue, handle_list_observed_submissions, handle_list_quorum_queues,
+};
 pub use chain::add::handle_add_chain;
 pub use config::handle_config;
 pub use health::handle_health;
@@ -19,3 +25,5 @@ pub use service::{
     add::handle_add_service, delete::handle_delete_service, list::handle_list_services,
     upload::handle_upload_component,
 };
+pub use submission::handle_list_target_deliveries;
+pub use webhook::handle_webhook_trigger;
@@ -1,5 +1,7 @@
+use axum::http::Uri;
+
 use crate::http::error::{HttpError, HttpResult};
 
-pub async fn handle_not_found() -> HttpResult<()> {
-    Err(HttpError::NotFound.into())
+pub async fn handle_not_found(uri: Uri) -> HttpResult<()> {
+    Err(HttpError::NotFound(format!("no route for {}", uri.path())).into())
 }
@@ -7,14 +7,24 @@ use utoipa::OpenApi;
     paths(
         config::handle_config,
         get::handle_get_service,
+        get::handle_get_service_by_id,
         key::handle_get_service_signer,
         save::handle_save_service,
+        status::handle_get_workflow_status,
+        events::handle_service_events,
         list::handle_list_services,
         add::handle_add_service,
+        deployment::handle_get_deployment,
         delete::handle_delete_service,
         info::handle_info,
-        upload::handle_upload_component
+        upload::handle_upload_component,
+        aggregator::handle_list_quorum_queues,
+        aggregator::handle_get_quorum_queue,
+        aggregator::handle_list_observed_submissions,
+        submission::handle_list_target_deliveries,
+        webhook::handle_webhook_trigger
     ),
+    components(schemas(wavs_types::ErrorResponse)),
     info(
         title = "WAVS API",
         description = "API documentation for the WAVS service"
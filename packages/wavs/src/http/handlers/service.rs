@@ -1,7 +1,10 @@
 pub mod add;
 pub mod delete;
+pub mod deployment;
+pub mod events;
 pub mod get;
 pub mod key;
 pub mod list;
 pub mod save;
+pub mod status;
 pub mod upload;
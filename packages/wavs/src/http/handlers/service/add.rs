@@ -1,42 +1,93 @@
 use axum::{extract::State, response::IntoResponse, Json};
 use reqwest::StatusCode;
+use utils::time::unix_now;
 
-use crate::http::{
-    error::HttpResult, handlers::service::get::get_service_inner_hash, state::HttpState,
+use crate::{
+    deployments::DeploymentsError,
+    http::{
+        error::{AnyError, HttpError, HttpResult},
+        handlers::service::get::get_service_inner_hash,
+        state::HttpState,
+    },
 };
-use wavs_types::{AddServiceRequest, ServiceManager};
+use wavs_types::{AddServiceRequest, DeploymentRecord};
 
 #[utoipa::path(
     post,
     path = "/services",
     request_body = AddServiceRequest,
     responses(
-        (status = 204, description = "Service successfully added"),
-        (status = 400, description = "Invalid service configuration"),
-        (status = 409, description = "Service already exists"),
+        (status = 204, description = "Service successfully added, or already added by an earlier request with the same deployment id"),
+        (status = 400, description = "Invalid service configuration or deployment id"),
+        (status = 409, description = "Service already exists, or the deployment id was used for a different service manager"),
         (status = 500, description = "Internal server error")
     ),
-    description = "Registers a new service with WAVS"
+    description = "Registers a new service with WAVS. With a deployment id the request is idempotent: repeating it returns success without registering the service again"
 )]
 #[axum::debug_handler]
 pub async fn handle_add_service(
     State(state): State<HttpState>,
     Json(req): Json<AddServiceRequest>,
 ) -> impl IntoResponse {
-    match add_service_inner(state, req.service_manager).await {
+    match add_service_inner(state, req).await {
         Ok(_) => StatusCode::NO_CONTENT.into_response(),
         Err(e) => e.into_response(),
     }
 }
 
-async fn add_service_inner(state: HttpState, service_manager: ServiceManager) -> HttpResult<()> {
-    state.dispatcher.add_service(service_manager).await?;
+async fn add_service_inner(state: HttpState, req: AddServiceRequest) -> HttpResult<()> {
+    let AddServiceRequest {
+        service_manager,
+        deployment_id,
+    } = req;
+
+    let Some(deployment_id) = deployment_id else {
+        state.dispatcher.add_service(service_manager).await?;
+        state.metrics.increment_registered_services();
+        return Ok(());
+    };
+
+    let _guard = state.deployments.lock().await;
 
+    if state
+        .deployments
+        .check(&deployment_id, &service_manager)
+        .map_err(deployment_error)?
+        .is_some()
+    {
+        tracing::info!(
+            "Deployment {deployment_id} was already applied, not adding the service again"
+        );
+        return Ok(());
+    }
+
+    let service = state
+        .dispatcher
+        .add_service(service_manager.clone())
+        .await?;
     state.metrics.increment_registered_services();
 
+    state
+        .deployments
+        .record(DeploymentRecord {
+            deployment_id,
+            service_manager,
+            service_id: service.id(),
+            deployed_at: unix_now(),
+        })
+        .await?;
+
     Ok(())
 }
 
+fn deployment_error(err: DeploymentsError) -> AnyError {
+    match err {
+        DeploymentsError::InvalidId(_) => HttpError::BadRequest(err.to_string()).into(),
+        DeploymentsError::Conflict { .. } => HttpError::Conflict(err.to_string()).into(),
+        _ => err.into(),
+    }
+}
+
 #[utoipa::path(
     post,
     path = "/dev/services/{service_hash}",
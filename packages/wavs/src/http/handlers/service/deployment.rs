@@ -0,0 +1,38 @@
+use axum::{extract::State, response::IntoResponse, Json};
+use wavs_types::DeploymentRecord;
+
+use crate::http::{
+    error::{HttpError, HttpResult},
+    state::HttpState,
+};
+
+#[utoipa::path(
+    get,
+    path = "/deployments/{deployment_id}",
+    params(
+        ("deployment_id" = String, Path, description = "Client-generated id passed when adding the service")
+    ),
+    responses(
+        (status = 200, description = "The deploy with this id has been applied", body = DeploymentRecord),
+        (status = 404, description = "No deploy with this id"),
+        (status = 500, description = "Internal server error")
+    ),
+    description = "Looks up a deploy by its deployment id, so a client can tell whether a deploy that timed out actually landed"
+)]
+#[axum::debug_handler]
+pub async fn handle_get_deployment(
+    State(state): State<HttpState>,
+    axum::extract::Path(deployment_id): axum::extract::Path<String>,
+) -> impl IntoResponse {
+    match inner(&state, &deployment_id) {
+        Ok(record) => Json(record).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+fn inner(state: &HttpState, deployment_id: &str) -> HttpResult<DeploymentRecord> {
+    state
+        .deployments
+        .get(deployment_id)
+        .ok_or_else(|| HttpError::NotFound(format!("Unknown deployment: {deployment_id}")).into())
+}
@@ -0,0 +1,117 @@
+use std::{convert::Infallible, str::FromStr};
+
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
+};
+use futures::Stream;
+use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
+use wavs_types::{ServiceEvent, ServiceId, Timestamp};
+
+use crate::http::{
+    error::{HttpError, HttpResult},
+    state::HttpState,
+};
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ServiceEventsQuery {
+    /// Also send the recent events at or after this time (nanoseconds since UNIX epoch)
+    pub since: Option<u64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/services/{service_id}/events",
+    params(
+        ("service_id" = String, Path, description = "Service ID"),
+        ("Last-Event-ID" = Option<String>, Header, description = "Resume after this event, sent by reconnecting clients"),
+        ServiceEventsQuery
+    ),
+    responses(
+        (status = 200, description = "Server-sent events, each data a JSON ServiceEvent", body = ServiceEvent, content_type = "text/event-stream"),
+        (status = 400, description = "Invalid service id"),
+        (status = 404, description = "Service not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    description = "Streams the service's triggers, executions and submissions as they happen"
+)]
+pub async fn handle_service_events(
+    State(state): State<HttpState>,
+    Path(service_id): Path<String>,
+    Query(query): Query<ServiceEventsQuery>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    match inner(&state, service_id, query, &headers) {
+        Ok(stream) => Sse::new(stream)
+            .keep_alive(KeepAlive::default())
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+fn inner(
+    state: &HttpState,
+    service_id: String,
+    query: ServiceEventsQuery,
+    headers: &HeaderMap,
+) -> HttpResult<impl Stream<Item = Result<Event, Infallible>>> {
+    let service_id = ServiceId::from_str(&service_id)
+        .map_err(|e| HttpError::BadRequest(format!("Invalid service id {service_id}: {e}")))?;
+    state.dispatcher.services.get(&service_id)?;
+
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+
+    let (backlog, mut receiver) = state.dispatcher.service_events.subscribe(
+        &service_id,
+        last_event_id,
+        query.since.map(Timestamp::from_nanos),
+    );
+
+    Ok(async_stream::stream! {
+        let mut last_id = backlog.last().map(|event| event.id).or(last_event_id);
+        for event in backlog {
+            if let Some(event) = sse_event(&event) {
+                yield Ok(event);
+            }
+        }
+
+        loop {
+            match receiver.recv().await {
+                Ok(event) => {
+                    if event.service_id != service_id || last_id.is_some_and(|id| event.id <= id) {
+                        continue;
+                    }
+                    last_id = Some(event.id);
+                    if let Some(event) = sse_event(&event) {
+                        yield Ok(event);
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(
+                        "Event stream for service {} fell behind, skipped {} events",
+                        service_id,
+                        skipped
+                    );
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+fn sse_event(event: &ServiceEvent) -> Option<Event> {
+    Event::default()
+        .id(event.id.to_string())
+        .event(event.kind.name())
+        .json_data(event)
+        .ok()
+}
@@ -1,11 +1,11 @@
 use std::str::FromStr;
 
 use crate::http::{
-    error::{AnyError, HttpResult},
+    error::{HttpError, HttpResult},
+    etag::json_with_etag,
     state::HttpState,
 };
-use anyhow::anyhow;
-use axum::{extract::State, response::IntoResponse, Json};
+use axum::{extract::State, http::HeaderMap, response::IntoResponse, Json};
 use layer_climb::prelude::CosmosAddr;
 use wavs_types::{AnyChainConfig, ChainKey, ServiceDigest, ServiceId, ServiceManager};
 
@@ -30,7 +30,9 @@ pub async fn handle_get_service(
 ) -> impl IntoResponse {
     let chain_key = match ChainKey::from_str(&chain) {
         Ok(key) => key,
-        Err(e) => return AnyError::from(e).into_response(),
+        Err(e) => {
+            return HttpError::BadRequest(format!("Invalid chain {chain}: {e}")).into_response()
+        }
     };
 
     let service_manager = match state.config.chains.read().unwrap().get_chain(&chain_key) {
@@ -39,7 +41,10 @@ pub async fn handle_get_service(
                 let address = match CosmosAddr::new_str(&address, Some(&chain_config.bech32_prefix))
                 {
                     Ok(addr) => addr,
-                    Err(e) => return AnyError::from(e).into_response(),
+                    Err(e) => {
+                        return HttpError::BadRequest(format!("Invalid address {address}: {e}"))
+                            .into_response()
+                    }
                 };
 
                 ServiceManager::Cosmos {
@@ -50,7 +55,10 @@ pub async fn handle_get_service(
             AnyChainConfig::Evm(_) => {
                 let address = match address.parse::<alloy_primitives::Address>() {
                     Ok(addr) => addr,
-                    Err(e) => return AnyError::from(e).into_response(),
+                    Err(e) => {
+                        return HttpError::BadRequest(format!("Invalid address {address}: {e}"))
+                            .into_response()
+                    }
                 };
 
                 ServiceManager::Evm {
@@ -58,9 +66,16 @@ pub async fn handle_get_service(
                     address,
                 }
             }
+            AnyChainConfig::Solana(_) => {
+                return HttpError::BadRequest(format!(
+                    "service managers on solana chains are not supported yet: {chain_key}"
+                ))
+                .into_response();
+            }
         },
         None => {
-            return AnyError::from(anyhow!("missing chain config for {chain_key}")).into_response();
+            return HttpError::NotFound(format!("missing chain config for {chain_key}"))
+                .into_response();
         }
     };
 
@@ -77,6 +92,45 @@ async fn get_service_inner(
     Ok(state.load_service(&ServiceId::from(&service_manager))?)
 }
 
+#[utoipa::path(
+    get,
+    path = "/services/{service_id}",
+    params(
+        ("service_id" = String, Path, description = "Id of the service")
+    ),
+    responses(
+        (status = 200, description = "Service found", body = wavs_types::Service),
+        (status = 304, description = "The service is unchanged since the `If-None-Match` ETag"),
+        (status = 400, description = "Invalid service id"),
+        (status = 404, description = "Service not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    description = "Retrieves the full definition of a service by its id"
+)]
+#[axum::debug_handler]
+pub async fn handle_get_service_by_id(
+    State(state): State<HttpState>,
+    headers: HeaderMap,
+    axum::extract::Path(service_id): axum::extract::Path<String>,
+) -> impl IntoResponse {
+    let service_id = match ServiceId::from_str(&service_id) {
+        Ok(service_id) => service_id,
+        Err(e) => {
+            return HttpError::BadRequest(format!("Invalid service id {service_id}: {e}"))
+                .into_response()
+        }
+    };
+
+    match state
+        .load_service(&service_id)
+        .map_err(Into::into)
+        .and_then(|service| json_with_etag(&headers, &service))
+    {
+        Ok(resp) => resp,
+        Err(e) => e.into_response(),
+    }
+}
+
 #[utoipa::path(
     get,
     path = "/dev/services/{service_hash}",
@@ -105,7 +159,8 @@ pub async fn get_service_inner_hash(
     state: &HttpState,
     service_hash: String,
 ) -> HttpResult<wavs_types::Service> {
-    let service_hash = ServiceDigest::from_str(&service_hash)?;
+    let service_hash = ServiceDigest::from_str(&service_hash)
+        .map_err(|e| HttpError::BadRequest(format!("Invalid service hash {service_hash}: {e}")))?;
 
     Ok(state.load_service_by_hash(&service_hash)?)
 }
@@ -1,30 +1,60 @@
-use crate::http::{error::HttpResult, state::HttpState};
-use axum::{extract::State, response::IntoResponse, Json};
+use crate::http::{error::HttpResult, etag::json_with_etag, state::HttpState};
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::IntoResponse,
+};
+use serde::Deserialize;
 use std::ops::Bound;
-use wavs_types::ListServicesResponse;
+use wavs_types::{ListServicesResponse, ServiceSummary};
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListServicesQuery {
+    /// Number of services to skip
+    pub offset: Option<usize>,
+    /// Maximum number of services to return, all of them if not set
+    pub limit: Option<usize>,
+}
 
 #[utoipa::path(
     get,
     path = "/services",
+    params(ListServicesQuery),
     responses(
         (status = 200, description = "Successfully retrieved service list", body = ListServicesResponse),
+        (status = 304, description = "The list is unchanged since the `If-None-Match` ETag"),
         (status = 500, description = "Internal server error")
     ),
-    description = "Returns a list of all services registered with WAVS"
+    description = "Returns the services registered with WAVS, ordered by service id, along with a summary of each"
 )]
 #[axum::debug_handler]
-pub async fn handle_list_services(State(state): State<HttpState>) -> impl IntoResponse {
-    match list_services_inner(&state).await {
-        Ok(resp) => Json(resp).into_response(),
+pub async fn handle_list_services(
+    State(state): State<HttpState>,
+    Query(query): Query<ListServicesQuery>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    match list_services_inner(&state, query).and_then(|resp| json_with_etag(&headers, &resp)) {
+        Ok(resp) => resp,
         Err(e) => e.into_response(),
     }
 }
 
-async fn list_services_inner(state: &HttpState) -> HttpResult<ListServicesResponse> {
-    let services = state
+fn list_services_inner(
+    state: &HttpState,
+    query: ListServicesQuery,
+) -> HttpResult<ListServicesResponse> {
+    let all_services = state
         .dispatcher
         .services
         .list(Bound::Unbounded, Bound::Unbounded)?;
+    let total = all_services.len();
+
+    let services = all_services
+        .into_iter()
+        .skip(query.offset.unwrap_or_default())
+        .take(query.limit.unwrap_or(usize::MAX))
+        .collect::<Vec<_>>();
 
     let component_digests = state.dispatcher.list_component_digests()?;
 
@@ -33,9 +63,13 @@ async fn list_services_inner(state: &HttpState) -> HttpResult<ListServicesRespon
         .map(|service| service.id())
         .collect::<Vec<_>>();
 
+    let summaries = services.iter().map(ServiceSummary::from).collect();
+
     Ok(ListServicesResponse {
         services,
         service_ids,
         component_digests,
+        summaries,
+        total,
     })
 }
@@ -0,0 +1,77 @@
+use std::str::FromStr;
+
+use crate::http::{
+    error::{HttpError, HttpResult},
+    state::HttpState,
+};
+use axum::{
+    extract::{Path, Query, State},
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use wavs_types::{ServiceId, WorkflowId, WorkflowStatusResponse};
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WorkflowStatusQuery {
+    /// Maximum number of recent outcomes to return
+    pub limit: Option<usize>,
+    /// Fill the rest of `limit` with older outcomes from the disk spill, if enabled
+    #[serde(default)]
+    pub include_spilled: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/services/{service_id}/workflows/{workflow_id}/status",
+    params(
+        ("service_id" = String, Path, description = "Service ID"),
+        ("workflow_id" = String, Path, description = "Workflow ID"),
+        WorkflowStatusQuery
+    ),
+    responses(
+        (status = 200, description = "Workflow status retrieved successfully", body = WorkflowStatusResponse),
+        (status = 400, description = "Invalid service or workflow id"),
+        (status = 404, description = "Service or workflow not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    description = "Returns execution totals and the most recent outcomes for a workflow"
+)]
+#[axum::debug_handler]
+pub async fn handle_get_workflow_status(
+    State(state): State<HttpState>,
+    Path((service_id, workflow_id)): Path<(String, String)>,
+    Query(query): Query<WorkflowStatusQuery>,
+) -> impl IntoResponse {
+    match inner(&state, service_id, workflow_id, query).await {
+        Ok(resp) => Json(resp).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn inner(
+    state: &HttpState,
+    service_id: String,
+    workflow_id: String,
+    query: WorkflowStatusQuery,
+) -> HttpResult<WorkflowStatusResponse> {
+    let service_id = ServiceId::from_str(&service_id)
+        .map_err(|e| HttpError::BadRequest(format!("Invalid service id {service_id}: {e}")))?;
+    let workflow_id = WorkflowId::new(workflow_id.clone())
+        .map_err(|e| HttpError::BadRequest(format!("Invalid workflow id {workflow_id}: {e}")))?;
+
+    // make sure the workflow actually exists before reporting on it
+    state
+        .dispatcher
+        .services
+        .get_workflow(&service_id, &workflow_id)?;
+
+    let workflow_status = &state.dispatcher.engine_manager.workflow_status;
+
+    Ok(if query.include_spilled {
+        workflow_status.get_with_spilled(&service_id, &workflow_id, query.limit)
+    } else {
+        workflow_status.get(&service_id, &workflow_id, query.limit)
+    })
+}
@@ -33,8 +33,7 @@ async fn inner_handle_upload_service(
     let digest = tokio::task::spawn_blocking(|| async move {
         state.dispatcher.store_component_bytes(bytes.to_vec())
     })
-    .await
-    .unwrap()
+    .await?
     .await?;
 
     Ok(UploadComponentResponse { digest })
@@ -0,0 +1,43 @@
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use wavs_types::{ServiceId, TargetDelivery, WorkflowId};
+
+use crate::http::state::HttpState;
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TargetDeliveriesQuery {
+    /// Only return the deliveries of this service
+    #[param(value_type = Option<String>)]
+    pub service_id: Option<ServiceId>,
+    /// Only return the deliveries of this workflow
+    #[param(value_type = Option<String>)]
+    pub workflow_id: Option<WorkflowId>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/submission/deliveries",
+    params(TargetDeliveriesQuery),
+    responses(
+        (status = 200, description = "Target deliveries retrieved successfully", body = Vec<TargetDelivery>),
+    ),
+    description = "Lists how recent deliveries to off-chain submit targets went, newest first"
+)]
+#[axum::debug_handler]
+pub async fn handle_list_target_deliveries(
+    State(state): State<HttpState>,
+    Query(query): Query<TargetDeliveriesQuery>,
+) -> impl IntoResponse {
+    Json(
+        state
+            .dispatcher
+            .submission_manager
+            .target_deliverer()
+            .list(query.service_id.as_ref(), query.workflow_id.as_ref()),
+    )
+}
@@ -0,0 +1,221 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+use wavs_types::{ServiceId, Trigger, TriggerAction, TriggerConfig, TriggerData, WorkflowId};
+
+use crate::{
+    http::{
+        error::{HttpError, HttpResult},
+        state::HttpState,
+    },
+    subsystems::engine::secrets::ServiceSecrets,
+};
+
+/// Header carrying `sha256=<hex HMAC-SHA256 of the body>` for workflows with a `signature_secret`
+pub const SIGNATURE_HEADER: &str = "x-wavs-signature";
+
+#[utoipa::path(
+    post,
+    path = "/trigger/{service_id}/{workflow_id}",
+    params(
+        ("service_id" = String, Path, description = "Service ID"),
+        ("workflow_id" = String, Path, description = "Workflow ID"),
+        ("X-Wavs-Signature" = Option<String>, Header, description = "sha256=<hex HMAC-SHA256 of the body>, required if the workflow's trigger has a signature_secret")
+    ),
+    request_body(content = Vec<u8>, description = "Passed to the component as webhook trigger data, with the request headers", content_type = "application/octet-stream"),
+    responses(
+        (status = 202, description = "Trigger accepted"),
+        (status = 400, description = "Invalid ids, or the workflow isn't triggered by webhooks"),
+        (status = 401, description = "Missing or invalid signature"),
+        (status = 404, description = "Service or workflow not found"),
+        (status = 500, description = "The signature secret isn't in the secrets file, or an internal server error")
+    ),
+    description = "Fires a workflow with a webhook trigger, passing it the request body"
+)]
+pub async fn handle_webhook_trigger(
+    State(state): State<HttpState>,
+    Path((service_id, workflow_id)): Path<(String, String)>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    match inner(&state, service_id, workflow_id, headers, body) {
+        Ok(()) => StatusCode::ACCEPTED.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+fn inner(
+    state: &HttpState,
+    service_id: String,
+    workflow_id: String,
+    headers: HeaderMap,
+    body: Bytes,
+) -> HttpResult<()> {
+    let service_id = ServiceId::from_str(&service_id)
+        .map_err(|e| HttpError::BadRequest(format!("Invalid service id {service_id}: {e}")))?;
+    let workflow_id = WorkflowId::new(workflow_id.clone())
+        .map_err(|e| HttpError::BadRequest(format!("Invalid workflow id {workflow_id}: {e}")))?;
+
+    let workflow = state
+        .dispatcher
+        .services
+        .get_workflow(&service_id, &workflow_id)?;
+
+    let Trigger::Webhook { signature_secret } = &workflow.trigger else {
+        return Err(HttpError::BadRequest(format!(
+            "Workflow {workflow_id} of service {service_id} isn't triggered by webhooks"
+        ))
+        .into());
+    };
+
+    if let Some(secret_name) = signature_secret {
+        let secret = signature_secret_value(&state.secrets, &service_id, secret_name)?;
+
+        let signature = headers
+            .get(SIGNATURE_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| HttpError::Unauthorized(format!("Missing {SIGNATURE_HEADER} header")))?;
+
+        if !verify_signature(secret.as_bytes(), &body, signature) {
+            return Err(HttpError::Unauthorized("Invalid webhook signature".to_string()).into());
+        }
+    }
+
+    state
+        .dispatcher
+        .trigger_manager
+        .add_trigger(TriggerAction {
+            config: TriggerConfig {
+                service_id,
+                workflow_id,
+                trigger: workflow.trigger.clone(),
+            },
+            data: TriggerData::Webhook {
+                headers: forwarded_headers(&headers),
+                body: body.to_vec(),
+            },
+        })?;
+
+    Ok(())
+}
+
+/// The value of the workflow's `signature_secret`. A name that isn't in the secrets file is
+/// a mistake in the operator's setup, not the caller's, so it's reported as such
+fn signature_secret_value(
+    secrets: &ServiceSecrets,
+    service_id: &ServiceId,
+    secret_name: &str,
+) -> Result<String, HttpError> {
+    secrets
+        .for_service(service_id)
+        .remove(secret_name)
+        .ok_or_else(|| {
+            tracing::error!(
+                "Webhook signature secret {secret_name} for service {service_id} isn't in the secrets file"
+            );
+            HttpError::Misconfigured(format!(
+                "Webhook signature secret {secret_name} for service {service_id} isn't configured on this node"
+            ))
+        })
+}
+
+/// Checks a `sha256=<hex>` signature of `body` in constant time
+pub fn verify_signature(secret: &[u8], body: &[u8], signature: &str) -> bool {
+    let Some(expected) = signature
+        .strip_prefix("sha256=")
+        .and_then(|hex| const_hex::decode(hex).ok())
+    else {
+        return false;
+    };
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    let actual = mac.finalize().into_bytes();
+
+    actual.as_slice().ct_eq(&expected).into()
+}
+
+/// Headers worth handing to the trigger, leaving out credentials
+fn forwarded_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .filter(|(name, _)| *name != header::AUTHORIZATION && *name != header::COOKIE)
+        .filter_map(|(name, value)| {
+            Some((name.as_str().to_string(), value.to_str().ok()?.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sign(secret: &[u8], body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret).unwrap();
+        mac.update(body);
+        format!("sha256={}", const_hex::encode(mac.finalize().into_bytes()))
+    }
+
+    #[test]
+    fn signatures_verify() {
+        let body = br#"{"event":"push"}"#;
+        let signature = sign(b"secret", body);
+
+        assert!(verify_signature(b"secret", body, &signature));
+        assert!(!verify_signature(b"other secret", body, &signature));
+        assert!(!verify_signature(b"secret", b"tampered", &signature));
+        assert!(!verify_signature(
+            b"secret",
+            body,
+            signature.trim_start_matches("sha256=")
+        ));
+        assert!(!verify_signature(b"secret", body, "sha256=not-hex"));
+        assert!(!verify_signature(b"secret", body, "sha256="));
+    }
+
+    #[test]
+    fn missing_signature_secret_is_a_misconfiguration() {
+        let service_id = ServiceId::hash(b"service");
+        let secrets = ServiceSecrets::from_iter([(
+            service_id.clone(),
+            BTreeMap::from([("github".to_string(), "secret".to_string())]),
+        )]);
+
+        assert_eq!(
+            signature_secret_value(&secrets, &service_id, "github").unwrap(),
+            "secret"
+        );
+
+        let err = signature_secret_value(&secrets, &service_id, "gitlab").unwrap_err();
+        assert!(matches!(err, HttpError::Misconfigured(_)));
+        assert_eq!(err.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(err.code(), "misconfigured");
+        assert!(err.to_string().contains("gitlab"));
+
+        // other services' secrets don't count
+        let err = signature_secret_value(&secrets, &ServiceId::hash(b"other"), "github");
+        assert!(matches!(err, Err(HttpError::Misconfigured(_))));
+    }
+
+    #[test]
+    fn credentials_are_not_forwarded() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, "Bearer token".parse().unwrap());
+        headers.insert(header::COOKIE, "session=1".parse().unwrap());
+        headers.insert("X-GitHub-Event", "push".parse().unwrap());
+
+        let forwarded = forwarded_headers(&headers);
+        assert_eq!(
+            forwarded,
+            BTreeMap::from([("x-github-event".to_string(), "push".to_string())])
+        );
+    }
+}
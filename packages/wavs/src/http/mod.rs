@@ -1,4 +1,5 @@
 mod error;
+mod etag;
 pub mod handlers;
 pub mod server;
 mod state;
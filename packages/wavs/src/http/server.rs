@@ -16,7 +16,7 @@ use axum::{
 use axum_tracing_opentelemetry::middleware::OtelAxumLayer;
 use std::sync::Arc;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
-use utils::{storage::fs::FileStorage, telemetry::HttpMetrics};
+use utils::{storage::any::AnyStorage, telemetry::HttpMetrics};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 use wildmatch::WildMatch;
@@ -24,13 +24,19 @@ use wildmatch::WildMatch;
 use super::{
     handlers::{
         debug::handle_debug_trigger,
-        handle_add_chain, handle_add_service, handle_config, handle_delete_service, handle_health,
-        handle_info, handle_list_services, handle_not_found, handle_p2p_status,
-        handle_upload_component,
+        handle_add_chain, handle_add_service, handle_config, handle_delete_service,
+        handle_get_quorum_queue, handle_health, handle_info, handle_list_observed_submissions,
+        handle_list_quorum_queues, handle_list_services, handle_list_target_deliveries,
+        handle_not_found, handle_p2p_status, handle_upload_component, handle_webhook_trigger,
         kv::handle_get_kv,
         openapi::ApiDoc,
         service::{
-            get::handle_get_service, key::handle_get_service_signer, save::handle_save_service,
+            deployment::handle_get_deployment,
+            events::handle_service_events,
+            get::{handle_get_service, handle_get_service_by_id},
+            key::handle_get_service_signer,
+            save::handle_save_service,
+            status::handle_get_workflow_status,
         },
     },
     state::HttpState,
@@ -42,7 +48,7 @@ const REALM: &str = "wavs";
 pub fn start(
     ctx: AppContext,
     config: Config,
-    dispatcher: Arc<Dispatcher<FileStorage>>,
+    dispatcher: Arc<Dispatcher<AnyStorage>>,
     metrics: HttpMetrics,
     health_status: SharedHealthStatus,
 ) -> anyhow::Result<()> {
@@ -75,7 +81,7 @@ pub fn start(
 // this is called from main and tests
 pub async fn make_router(
     config: Config,
-    dispatcher: Arc<Dispatcher<FileStorage>>,
+    dispatcher: Arc<Dispatcher<AnyStorage>>,
     is_mock_chain_client: bool,
     metrics: HttpMetrics,
     health_status: SharedHealthStatus,
@@ -97,15 +103,33 @@ pub async fn make_router(
         .route("/config", get(handle_config))
         .route("/services", get(handle_list_services))
         .route("/services/{chain}/{address}", get(handle_get_service))
+        .route("/services/{service_id}", get(handle_get_service_by_id))
+        .route("/deployments/{deployment_id}", get(handle_get_deployment))
+        .route(
+            "/services/{service_id}/workflows/{workflow_id}/status",
+            get(handle_get_workflow_status),
+        )
+        .route("/services/{service_id}/events", get(handle_service_events))
         .route("/info", get(handle_info))
         .route("/health", get(handle_health))
-        .route("/p2p/status", get(handle_p2p_status));
+        .route("/p2p/status", get(handle_p2p_status))
+        .route("/aggregator/queues", get(handle_list_quorum_queues))
+        .route("/aggregator/queues/{id}", get(handle_get_quorum_queue))
+        .route(
+            "/aggregator/observed",
+            get(handle_list_observed_submissions),
+        )
+        .route("/submission/deliveries", get(handle_list_target_deliveries));
 
     // protected routes (POST/DELETE)
     let mut protected = axum::Router::new()
         .route("/services/signer", post(handle_get_service_signer))
         .route("/services", post(handle_add_service))
-        .route("/services", delete(handle_delete_service));
+        .route("/services", delete(handle_delete_service))
+        .route(
+            "/trigger/{service_id}/{workflow_id}",
+            post(handle_webhook_trigger),
+        );
 
     // Only add debug routes if debug endpoints are enabled
     if config.dev_endpoints_enabled {
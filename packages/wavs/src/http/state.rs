@@ -1,29 +1,39 @@
 use std::convert::TryInto;
 use std::sync::Arc;
 
+use alloy_signer_local::PrivateKeySigner;
 use utils::{
-    storage::{db::WavsDb, fs::FileStorage},
+    evm_client::signing::make_signer,
+    storage::{any::AnyStorage, db::WavsDb},
     telemetry::HttpMetrics,
 };
 use wavs_types::{Service, ServiceDigest, ServiceId};
 
-use crate::{config::Config, dispatcher::Dispatcher, health::SharedHealthStatus};
+use crate::{
+    config::Config, deployments::Deployments, dispatcher::Dispatcher, health::SharedHealthStatus,
+    http::error::HttpError, subsystems::engine::secrets::ServiceSecrets,
+};
 
 #[derive(Clone)]
 pub struct HttpState {
     pub config: Config,
-    pub dispatcher: Arc<Dispatcher<FileStorage>>,
+    pub dispatcher: Arc<Dispatcher<AnyStorage>>,
     pub is_mock_chain_client: bool,
     pub http_client: reqwest::Client,
     pub db_storage: WavsDb,
     pub metrics: HttpMetrics,
     pub health_status: SharedHealthStatus,
+    /// Signs `/health` responses, `None` if no `health_signing_key` is configured
+    pub health_signer: Option<Arc<PrivateKeySigner>>,
+    pub deployments: Deployments,
+    /// Operator secrets, for verifying signed webhooks
+    pub secrets: ServiceSecrets,
 }
 
 impl HttpState {
     pub async fn new(
         config: Config,
-        dispatcher: Arc<Dispatcher<FileStorage>>,
+        dispatcher: Arc<Dispatcher<AnyStorage>>,
         is_mock_chain_client: bool,
         metrics: HttpMetrics,
         health_status: SharedHealthStatus,
@@ -38,6 +48,22 @@ impl HttpState {
             })?;
         }
 
+        let deployments = Deployments::load(&config.data)?;
+
+        let health_signer = match &config.health_signing_key {
+            Some(key) => {
+                Some(Arc::new(make_signer(key, None).map_err(|err| {
+                    anyhow::anyhow!("Invalid health signing key: {}", err)
+                })?))
+            }
+            None => None,
+        };
+
+        let secrets = match &config.secrets_file {
+            Some(path) => ServiceSecrets::load(path)?,
+            None => ServiceSecrets::default(),
+        };
+
         Ok(Self {
             config,
             db_storage: dispatcher.db_storage.clone(),
@@ -46,16 +72,14 @@ impl HttpState {
             http_client: reqwest::Client::new(),
             metrics,
             health_status,
+            health_signer,
+            deployments,
+            secrets,
         })
     }
 
     pub fn load_service(&self, service_id: &ServiceId) -> anyhow::Result<wavs_types::Service> {
-        match self.dispatcher.services.get(service_id) {
-            Ok(service) => Ok(service),
-            _ => Err(anyhow::anyhow!(
-                "Service ID {service_id} has not been set on the http server",
-            )),
-        }
+        Ok(self.dispatcher.services.get(service_id)?)
     }
 
     pub fn load_service_by_hash(
@@ -65,14 +89,15 @@ impl HttpState {
         let key: [u8; 32] = service_hash
             .as_ref()
             .try_into()
-            .map_err(|_| anyhow::anyhow!("invalid service hash length"))?;
+            .map_err(|_| HttpError::BadRequest("invalid service hash length".to_string()))?;
         if let Some(service) = self.db_storage.services_by_hash.get_cloned(&key) {
             Ok(service)
         } else {
-            Err(anyhow::anyhow!(
+            Err(HttpError::NotFound(format!(
                 "Service Hash {} has not been set on the http server",
                 service_hash
             ))
+            .into())
         }
     }
     pub fn save_service_by_hash(&self, service: &Service) -> anyhow::Result<ServiceDigest> {
@@ -4,16 +4,18 @@
 
 pub mod args;
 pub mod config;
+pub mod deployments;
 pub mod dispatcher; // where we have the high-level dispatcher
 pub mod health;
 pub mod http;
+pub mod service_events;
 pub mod services;
 pub mod subsystems; // subsystems: engine, submission, and trigger // services lookup
 
 use config::Config;
 use dispatcher::Dispatcher;
 use health::SharedHealthStatus;
-use utils::storage::fs::FileStorage;
+use utils::storage::any::AnyStorage;
 
 // This section is called from both main and end-to-end tests
 use std::sync::Arc;
@@ -25,7 +27,7 @@ use utils::telemetry::HttpMetrics;
 pub fn run_server(
     ctx: AppContext,
     config: Config,
-    dispatcher: Arc<Dispatcher<FileStorage>>,
+    dispatcher: Arc<Dispatcher<AnyStorage>>,
     metrics: HttpMetrics,
     health_status: SharedHealthStatus,
 ) {
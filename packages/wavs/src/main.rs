@@ -6,6 +6,7 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use utils::{
     config::{ConfigBuilder, ConfigExt},
     context::AppContext,
+    logging::{log_layer, ServiceLogLevels},
     telemetry::{setup_metrics, setup_tracing, Metrics},
 };
 use wavs::{
@@ -22,19 +23,17 @@ fn main() {
     let ctx = AppContext::new();
 
     // setup tracing
-    let filters = config.tracing_env_filter().unwrap();
+    let filters = ServiceLogLevels::new(&config.service_log_levels)
+        .unwrap()
+        .filter(config.tracing_env_filter().unwrap());
     let tracer_provider = if let Some(collector) = config.jaeger.as_ref() {
         Some(ctx.rt.block_on({
             let config = config.clone();
-            async move { setup_tracing(collector, "wavs", config.tracing_env_filter().unwrap()) }
+            async move { setup_tracing(collector, "wavs", filters, config.log_format) }
         }))
     } else {
         tracing_subscriber::registry()
-            .with(
-                tracing_subscriber::fmt::layer()
-                    .without_time()
-                    .with_target(false),
-            )
+            .with(log_layer(config.log_format))
             .with(filters)
             .try_init()
             .unwrap();
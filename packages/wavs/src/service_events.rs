@@ -0,0 +1,148 @@
+//! Live feed of what happens to each service's workflows, for `GET /services/{service_id}/events`.
+//!
+//! The dispatcher and engine publish events as triggers arrive, components run and responses are
+//! signed. The most recent ones are kept so clients can catch up on what they missed, whether
+//! they're asking for everything since a point in time or reconnecting after their last event id.
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use tokio::sync::broadcast;
+use wavs_types::{ServiceEvent, ServiceEventKind, ServiceId, Timestamp, WorkflowId};
+
+#[derive(Clone)]
+pub struct ServiceEvents {
+    inner: Arc<Mutex<Inner>>,
+    sender: broadcast::Sender<ServiceEvent>,
+}
+
+struct Inner {
+    next_id: u64,
+    recent: VecDeque<ServiceEvent>,
+    capacity: usize,
+}
+
+impl Default for ServiceEvents {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl ServiceEvents {
+    /// How many events are kept for catching up, and buffered for each live subscriber
+    pub const DEFAULT_CAPACITY: usize = 1024;
+
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        let (sender, _) = broadcast::channel(capacity);
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                next_id: 1,
+                recent: VecDeque::with_capacity(capacity),
+                capacity,
+            })),
+            sender,
+        }
+    }
+
+    pub fn publish(
+        &self,
+        service_id: &ServiceId,
+        workflow_id: &WorkflowId,
+        kind: ServiceEventKind,
+    ) {
+        let mut inner = self.inner.lock().unwrap();
+        let event = ServiceEvent {
+            id: inner.next_id,
+            timestamp: Timestamp::now(),
+            service_id: service_id.clone(),
+            workflow_id: workflow_id.clone(),
+            kind,
+        };
+        inner.next_id += 1;
+
+        if inner.recent.len() == inner.capacity {
+            inner.recent.pop_front();
+        }
+        inner.recent.push_back(event.clone());
+
+        // no subscribers is fine
+        let _ = self.sender.send(event);
+    }
+
+    /// The service's recent events after `after_id` and no older than `since`, along with a
+    /// receiver for everything published from then on (for all services)
+    pub fn subscribe(
+        &self,
+        service_id: &ServiceId,
+        after_id: Option<u64>,
+        since: Option<Timestamp>,
+    ) -> (Vec<ServiceEvent>, broadcast::Receiver<ServiceEvent>) {
+        // holding the lock keeps events from being published between the two
+        let inner = self.inner.lock().unwrap();
+        let receiver = self.sender.subscribe();
+
+        let backlog = match (after_id, since) {
+            (None, None) => Vec::new(),
+            _ => inner
+                .recent
+                .iter()
+                .filter(|event| event.service_id == *service_id)
+                .filter(|event| after_id.is_none_or(|id| event.id > id))
+                .filter(|event| since.is_none_or(|since| event.timestamp >= since))
+                .cloned()
+                .collect(),
+        };
+
+        (backlog, receiver)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn catches_up_from_last_event_id() {
+        let events = ServiceEvents::new(3);
+        let service_id = ServiceId::hash(b"service");
+        let other_id = ServiceId::hash(b"other");
+        let workflow_id = WorkflowId::default();
+
+        for _ in 0..2 {
+            events.publish(
+                &service_id,
+                &workflow_id,
+                ServiceEventKind::ExecutionStarted,
+            );
+            events.publish(&other_id, &workflow_id, ServiceEventKind::ExecutionStarted);
+        }
+
+        // only live events without a starting point
+        let (backlog, _) = events.subscribe(&service_id, None, None);
+        assert!(backlog.is_empty());
+
+        // the oldest event has already been dropped
+        let (backlog, _) = events.subscribe(&service_id, Some(0), None);
+        let ids: Vec<u64> = backlog.iter().map(|event| event.id).collect();
+        assert_eq!(ids, vec![3]);
+
+        let (backlog, mut receiver) =
+            events.subscribe(&service_id, None, Some(Timestamp::from_nanos(0)));
+        assert_eq!(backlog.len(), 1);
+
+        events.publish(
+            &service_id,
+            &workflow_id,
+            ServiceEventKind::ExecutionResult {
+                responses: 1,
+                error: None,
+            },
+        );
+        let live = receiver.try_recv().unwrap();
+        assert_eq!(live.id, 5);
+        assert_eq!(live.kind.name(), "execution_result");
+    }
+}
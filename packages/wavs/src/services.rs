@@ -130,7 +130,7 @@ macro_rules! tracing_service_info {
         if tracing::enabled!(tracing::Level::INFO) {
             match $services.get(&$service_id).ok() {
                 Some(service) => {
-                    tracing::info!(service.name = %service.name, service.manager = ?service.manager, "Service {} [{:?}]: {}", service.name, service.manager, format_args!($($msg)*));
+                    tracing::info!(service.name = %service.name, service.id = %$service_id, service.manager = %service.manager, "Service {} [{}]: {}", service.name, service.manager, format_args!($($msg)*));
                 },
                 None => {
                     tracing::info!(service.id = %$service_id, "Service [id: {}]: {}", $service_id, format_args!($($msg)*));
@@ -146,7 +146,7 @@ macro_rules! tracing_service_debug {
         if tracing::enabled!(tracing::Level::DEBUG) {
             match $services.get(&$service_id).ok() {
                 Some(service) => {
-                    tracing::debug!(service.name = %service.name, service.manager = ?service.manager, "Service {} [{:?}]: {}", service.name, service.manager, format_args!($($msg)*));
+                    tracing::debug!(service.name = %service.name, service.id = %$service_id, service.manager = %service.manager, "Service {} [{}]: {}", service.name, service.manager, format_args!($($msg)*));
                 },
                 None => {
                     tracing::debug!(service.id = %$service_id, "Service [id: {}]: {}", $service_id, format_args!($($msg)*));
@@ -162,7 +162,7 @@ macro_rules! tracing_service_trace {
         if tracing::enabled!(tracing::Level::TRACE) {
             match $services.get(&$service_id).ok() {
                 Some(service) => {
-                    tracing::trace!(service.name = %service.name, service.manager = ?service.manager, "Service {} [{:?}]: {}", service.name, service.manager, format_args!($($msg)*));
+                    tracing::trace!(service.name = %service.name, service.id = %$service_id, service.manager = %service.manager, "Service {} [{}]: {}", service.name, service.manager, format_args!($($msg)*));
                 },
                 None => {
                     tracing::trace!(service.id = %$service_id, "Service [id: {}]: {}", $service_id, format_args!($($msg)*));
@@ -177,7 +177,7 @@ macro_rules! tracing_service_warn {
         if tracing::enabled!(tracing::Level::WARN) {
             match $services.get(&$service_id).ok() {
                 Some(service) => {
-                    tracing::warn!(service.name = %service.name, service.manager = ?service.manager, "Service {} [{:?}]: {}", service.name, service.manager, format_args!($($msg)*));
+                    tracing::warn!(service.name = %service.name, service.id = %$service_id, service.manager = %service.manager, "Service {} [{}]: {}", service.name, service.manager, format_args!($($msg)*));
                 },
                 None => {
                     tracing::warn!(service.id = %$service_id, "Service [id: {}]: {}", $service_id, format_args!($($msg)*));
@@ -193,7 +193,7 @@ macro_rules! tracing_service_error {
         if tracing::enabled!(tracing::Level::ERROR) {
             match $services.get(&$service_id).ok() {
                 Some(service) => {
-                    tracing::error!(service.name = %service.name, service.manager = ?service.manager, "Service {} [{:?}]: {}", service.name, service.manager, format_args!($($msg)*));
+                    tracing::error!(service.name = %service.name, service.id = %$service_id, service.manager = %service.manager, "Service {} [{}]: {}", service.name, service.manager, format_args!($($msg)*));
                 },
                 None => {
                     tracing::error!(service.id = %$service_id, "Service [id: {}]: {}", $service_id, format_args!($($msg)*));
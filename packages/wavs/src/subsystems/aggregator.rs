@@ -1,27 +1,40 @@
 pub mod error;
+pub mod operators;
 pub mod p2p;
 pub mod peer;
 mod queue;
+pub mod quorum;
+pub mod store;
 mod submit;
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc,
+        Arc, Mutex,
     },
 };
 
+use alloy_primitives::{Address, U256};
+use dashmap::DashMap;
 use layer_climb::prelude::*;
 use tracing::instrument;
 use utils::{
-    async_transaction::AsyncTransaction, config::EvmChainConfigExt, context::AppContext,
-    evm_client::EvmSigningClient, storage::db::WavsDb, telemetry::AggregatorMetrics,
+    async_transaction::AsyncTransaction,
+    chain_throttle::ChainThrottle,
+    config::EvmChainConfigExt,
+    context::AppContext,
+    evm_client::{pool::NoncePool, EvmSigningClient},
+    storage::db::WavsDb,
+    telemetry::{AggregatorMetrics, PipelineMetrics},
+    time::unix_now,
+    tls::ChainTls,
 };
-use wavs_engine::bindings::aggregator::world::AnyTxHash;
+use wavs_engine::bindings::aggregator::world::SubmitReceipt;
 use wavs_types::{
-    AggregatorAction, ChainKey, QuorumQueue, QuorumQueueId, Service, Submission, Submit,
-    SubmitAction, TimerAction,
+    AggregatorAction, ChainKey, EventId, ObservedSubmission, QueuedSubmission, QuorumProgress,
+    QuorumQueue, QuorumQueueId, ResultSignature, Service, ServiceId, SignedResult, Submission,
+    SubmissionProvenance, Submit, SubmitAction, TimerAction, WorkflowId,
 };
 
 use crate::{
@@ -30,26 +43,59 @@ use crate::{
     services::Services,
     subsystems::{
         aggregator::{
-            error::AggregatorError, p2p::P2pHandle, peer::Peer, queue::append_submission_to_queue,
+            error::AggregatorError,
+            operators::{ChainOperatorSetQuery, OperatorSet},
+            p2p::P2pHandle,
+            peer::Peer,
+            queue::append_submission_to_queue,
+            quorum::WeightedQuorum,
+            store::FileQueueStore,
             submit::AnyTransactionReceipt,
         },
         engine::AggregatorExecuteKind,
+        in_flight::InFlight,
+        submission::target::{target_label, TargetDeliverer},
     },
 };
 
 pub struct Aggregator {
     pub metrics: AggregatorMetrics,
+    pub pipeline_metrics: PipelineMetrics,
     storage: WavsDb,
+    /// Persists quorum queues under the data directory,
+    /// `storage` is loaded from it on startup and written through on every change
+    queue_store: FileQueueStore,
     config: Arc<Config>,
     services: Services,
     dispatcher_to_aggregator_rx: crossbeam::channel::Receiver<AggregatorCommand>,
     aggregator_to_self_tx: crossbeam::channel::Sender<AggregatorCommand>,
     subsystem_to_dispatcher_tx: crossbeam::channel::Sender<DispatcherCommand>,
     evm_submission_clients: Arc<std::sync::RwLock<HashMap<ChainKey, EvmSigningClient>>>,
+    /// Nonce counters shared by every EVM submission client for the same key and chain
+    nonce_pool: NoncePool,
     cosmos_submission_clients:
         Arc<std::sync::RwLock<HashMap<ChainKey, layer_climb::prelude::SigningClient>>>,
     queue_transaction: AsyncTransaction<QuorumQueueId>,
     chain_transaction: AsyncTransaction<ChainKey>,
+    /// Per-chain limit on concurrent RPC operations, shared with the dispatcher
+    chain_throttle: ChainThrottle,
+    /// Custom TLS for the submission clients, shared with the dispatcher
+    chain_tls: ChainTls,
+    /// Where each received submission came from, keyed by event and signer,
+    /// so it can be attached when the submission is appended to a quorum queue
+    received: Arc<DashMap<(EventId, Address), SubmissionProvenance>>,
+    /// Recent would-be submissions of `observe_only` workflows, newest last
+    observed: Arc<Mutex<VecDeque<ObservedSubmission>>>,
+    /// Weights from the last insufficient quorum check of each active queue, for status reporting.
+    /// Not persisted, the next submission to a queue checks it again
+    quorum_progress: Arc<DashMap<QuorumQueueId, QuorumProgress>>,
+    /// Signers known to the service managers, `None` unless `verify_operators` is enabled
+    operator_set: Option<OperatorSet>,
+    /// Shared with the engine and submission managers, so shutdown can drain them
+    in_flight: InFlight,
+    /// Shared with the submission manager, posts results to off-chain submit targets
+    /// once they're on-chain. `None` skips delivery
+    target_deliverer: Option<TargetDeliverer>,
     /// Optional P2P handle for broadcasting submissions to peers
     p2p_handle: Arc<std::sync::RwLock<Option<P2pHandle>>>,
     /// Tracks whether this is the primary instance (true) or a clone for async tasks (false).
@@ -58,6 +104,7 @@ pub struct Aggregator {
 }
 
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum AggregatorCommand {
     Kill,
     // From Submission Manager
@@ -84,33 +131,86 @@ pub enum AggregatorCommand {
 }
 
 impl Aggregator {
-    #[allow(clippy::new_without_default)]
-    #[instrument(skip(services), fields(subsys = "Aggregator"))]
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    #[instrument(skip(services, chain_throttle), fields(subsys = "Aggregator"))]
     pub fn new(
         config: &Config,
         metrics: AggregatorMetrics,
+        chain_throttle: ChainThrottle,
+        chain_tls: ChainTls,
         services: Services,
         dispatcher_to_aggregator_rx: crossbeam::channel::Receiver<AggregatorCommand>,
         aggregator_to_self_tx: crossbeam::channel::Sender<AggregatorCommand>,
         subsystem_to_dispatcher_tx: crossbeam::channel::Sender<DispatcherCommand>,
     ) -> Result<Self, AggregatorError> {
+        config
+            .aggregator
+            .validate()
+            .map_err(AggregatorError::InvalidConfig)?;
+
+        let storage = WavsDb::new().map_err(AggregatorError::Db)?;
+        let queue_store =
+            FileQueueStore::open(&config.data.join("aggregator")).map_err(AggregatorError::Db)?;
+
+        let queues = queue_store.load_queues().map_err(AggregatorError::Db)?;
+        tracing::info!("Loaded {} quorum queue(s) from disk", queues.len());
+        for (id, queue) in queues {
+            storage
+                .quorum_queues
+                .insert(id, queue)
+                .map_err(AggregatorError::Db)?;
+        }
+
+        let operator_set = config.aggregator.verify_operators.then(|| {
+            OperatorSet::new(
+                ChainOperatorSetQuery::new(config.chains.clone(), chain_tls.clone()),
+                std::time::Duration::from_secs(config.aggregator.operator_set_refresh_secs()),
+            )
+        });
+
         Ok(Self {
-            storage: WavsDb::new().map_err(AggregatorError::Db)?,
+            storage,
+            queue_store,
             dispatcher_to_aggregator_rx,
             aggregator_to_self_tx,
             subsystem_to_dispatcher_tx,
             metrics,
+            pipeline_metrics: PipelineMetrics::default(),
             services,
             evm_submission_clients: Arc::new(std::sync::RwLock::new(HashMap::default())),
+            nonce_pool: NoncePool::new(),
             cosmos_submission_clients: Arc::new(std::sync::RwLock::new(HashMap::default())),
             config: Arc::new(config.clone()),
             queue_transaction: AsyncTransaction::new(false),
             chain_transaction: AsyncTransaction::new(false),
+            chain_throttle,
+            chain_tls,
+            received: Arc::new(DashMap::new()),
+            observed: Arc::new(Mutex::new(VecDeque::new())),
+            quorum_progress: Arc::new(DashMap::new()),
+            operator_set,
+            in_flight: InFlight::default(),
+            target_deliverer: None,
             p2p_handle: Arc::new(std::sync::RwLock::new(None)), // Initialized in start() method
             is_primary: Arc::new(AtomicBool::new(true)),
         })
     }
 
+    pub fn with_pipeline_metrics(mut self, pipeline_metrics: PipelineMetrics) -> Self {
+        self.pipeline_metrics = pipeline_metrics;
+        self
+    }
+
+    pub fn with_in_flight(mut self, in_flight: InFlight) -> Self {
+        self.in_flight = in_flight;
+        self
+    }
+
+    pub fn with_target_deliverer(mut self, target_deliverer: TargetDeliverer) -> Self {
+        self.target_deliverer = Some(target_deliverer);
+        self
+    }
+
     /// Get the current P2P network status
     pub async fn get_p2p_status(&self) -> wavs_types::P2pStatus {
         let handle = self.p2p_handle.read().unwrap().clone();
@@ -181,10 +281,38 @@ impl Aggregator {
             });
         }
 
+        // Spawn the quorum timeout sweeper, only needed if any service has a timeout
+        if !self.config.aggregator.quorum_timeouts.is_empty() {
+            let _self = _self.clone();
+            let mut shutdown_signal = ctx.get_kill_receiver();
+            ctx.rt.spawn(async move {
+                let mut sweep_interval = tokio::time::interval(tokio::time::Duration::from_secs(
+                    Self::QUORUM_TIMEOUT_SWEEP_SECS,
+                ));
+                sweep_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+                loop {
+                    tokio::select! {
+                        _ = shutdown_signal.recv() => {
+                            tracing::info!("Quorum timeout sweeper shutting down");
+                            break;
+                        }
+                        _ = sweep_interval.tick() => {
+                            if let Err(e) = _self.expire_quorum_queues().await {
+                                tracing::error!("Error expiring quorum queues: {:?}", e);
+                            }
+                        }
+                    }
+                }
+            });
+        }
+
+        // keep going after the kill signal, the dispatcher sends Kill once in-flight work has drained
         while let Ok(command) = self.dispatcher_to_aggregator_rx.recv() {
+            let kill = matches!(command, AggregatorCommand::Kill);
             self.handle_dispatcher_command(&ctx, command);
 
-            if ctx.killed() {
+            if kill {
                 break;
             }
         }
@@ -226,6 +354,8 @@ impl Aggregator {
         match command {
             AggregatorCommand::Kill => {
                 tracing::info!("Aggregator received Kill command, shutting down");
+                let persisted = self.persist_quorum_queues();
+                tracing::info!("Persisted {} active quorum queue(s)", persisted);
             }
             AggregatorCommand::Broadcast(submission) => {
                 let service =
@@ -238,7 +368,9 @@ impl Aggregator {
 
                 ctx.rt.spawn({
                     let _self = self.clone();
+                    let in_flight = self.in_flight.start();
                     async move {
+                        let _in_flight = in_flight;
                         match _self.handle_broadcast(&submission).await {
                             Ok(_) => {
                                 _self
@@ -265,9 +397,20 @@ impl Aggregator {
                 };
                 ctx.rt.spawn({
                     let _self = self.clone();
+                    let in_flight = self.in_flight.start();
                     async move {
+                        let _in_flight = in_flight;
                         let workflow_id = submission.workflow_id().clone();
 
+                        let weight = match _self.verify_operator(&submission, &service).await {
+                            Ok(weight) => weight,
+                            Err(e) => {
+                                tracing::warn!("{label}: Rejected submission: {}", e);
+                                return;
+                            }
+                        };
+                        _self.record_provenance(&submission, &peer, weight);
+
                         match _self.handle_receive(submission, service.clone()).await {
                             Ok(_) => {
                                 _self
@@ -284,8 +427,8 @@ impl Aggregator {
 
             AggregatorCommand::Actions {
                 submission,
-                actions,
-                kind: _,
+                mut actions,
+                kind,
             } => {
                 let service =
                     match self.extract_service_from_submission(&submission, &Peer::Me, &label) {
@@ -295,12 +438,20 @@ impl Aggregator {
                         }
                     };
 
+                // callbacks only decide what happens next, the fixed targets were already
+                // added when the result first came in
+                if matches!(kind, AggregatorExecuteKind::Standard) {
+                    add_fixed_targets(&service, submission.workflow_id(), &mut actions);
+                }
+
                 for action in actions {
                     ctx.rt.spawn({
                         let _self = self.clone();
                         let service = service.clone();
                         let submission = submission.clone();
+                        let in_flight = self.in_flight.start();
                         async move {
+                            let _in_flight = in_flight;
                             match action {
                                 AggregatorAction::Submit(action) => {
                                     let workflow_id = submission.workflow_id().clone();
@@ -326,7 +477,7 @@ impl Aggregator {
                                                                 tracing::warn!("Tried to access burned quorum queue: {:?} (burned at {})", queue_id, timestamp);
                                                                 return;
                                                             }
-                                                            QuorumQueue::Active(submissions) => submissions,
+                                                            QuorumQueue::Active(entries) => entries,
                                                         }
                                                     }
                                                     Err(err) => {
@@ -342,7 +493,14 @@ impl Aggregator {
                                                 // CRITICAL: Append current submission to the queue
                                                 // This submission is now IN the queue, so we must save it if submission fails
                                                 // Otherwise this submission will be lost and never retried
-                                                if let Err(err) = append_submission_to_queue(&queue_id, &mut queue,submission.clone()) {
+                                                let provenance = match _self.provenance_for(&submission) {
+                                                    Ok(provenance) => provenance,
+                                                    Err(err) => {
+                                                        tracing::error!("{}", err);
+                                                        return;
+                                                    }
+                                                };
+                                                if let Err(err) = append_submission_to_queue(&queue_id, &mut queue, submission.clone(), provenance) {
                                                     tracing::error!("{}", err);
                                                     return;
                                                 }
@@ -519,6 +677,101 @@ impl Aggregator {
         Ok(())
     }
 
+    /// Checks the submission's signer against the service's operator set.
+    /// Only with `verify_operators` enabled, otherwise every signer is left for the
+    /// service manager to judge when the queue is submitted.
+    /// Returns the weight of the signer's operator, if it was looked up
+    async fn verify_operator(
+        &self,
+        submission: &Submission,
+        service: &Service,
+    ) -> Result<Option<U256>, AggregatorError> {
+        let Some(operator_set) = &self.operator_set else {
+            return Ok(None);
+        };
+
+        let signer = submission
+            .envelope_signature
+            .evm_signer_address(&submission.envelope)?;
+
+        let info = operator_set
+            .check(&service.id(), &service.manager, signer)
+            .await?;
+
+        Ok(Some(info.weight))
+    }
+
+    /// Remember where a submission came from until it's appended to a quorum queue
+    fn record_provenance(&self, submission: &Submission, peer: &Peer, weight: Option<U256>) {
+        // an invalid signature is rejected when the submission is queued, nothing to audit yet
+        let Ok(signer) = submission
+            .envelope_signature
+            .evm_signer_address(&submission.envelope)
+        else {
+            return;
+        };
+
+        let peer = match peer {
+            Peer::Me => None,
+            Peer::Other(id) => Some(id.clone()),
+        };
+
+        self.received.insert(
+            (submission.event_id.clone(), signer),
+            SubmissionProvenance {
+                signer,
+                peer,
+                received_at: unix_now(),
+                weight: weight.map(|weight| weight.to_string()),
+            },
+        );
+    }
+
+    /// Provenance recorded when the submission was received.
+    /// Callbacks re-run actions with the same submission, so the record is kept rather than taken
+    fn provenance_for(
+        &self,
+        submission: &Submission,
+    ) -> Result<SubmissionProvenance, AggregatorError> {
+        let signer = submission
+            .envelope_signature
+            .evm_signer_address(&submission.envelope)?;
+
+        Ok(self
+            .received
+            .get(&(submission.event_id.clone(), signer))
+            .map(|entry| entry.clone())
+            .unwrap_or_else(|| SubmissionProvenance {
+                signer,
+                peer: None,
+                received_at: unix_now(),
+                weight: None,
+            }))
+    }
+
+    /// Local stake-weighted check of a queue, `None` if it can't be made and the queue
+    /// should go to the service manager: no `quorum_threshold_percent`, a signer with
+    /// unknown weight, or no on-chain check of the queue yet to learn the total weight from
+    fn weighted_quorum(
+        &self,
+        queue_id: &QuorumQueueId,
+        queue: &[QueuedSubmission],
+    ) -> Option<WeightedQuorum> {
+        let threshold_percent = self.config.aggregator.quorum_threshold_percent?;
+        let total_weight = self
+            .quorum_progress
+            .get(queue_id)?
+            .total_weight
+            .parse()
+            .ok()?;
+
+        WeightedQuorum::new(
+            queue.iter().map(|queued| &queued.provenance),
+            total_weight,
+            threshold_percent,
+        )
+    }
+
     /// Handle submission to blockchain with automatic retry mechanism
     ///
     /// This function implements the core retry logic for on-chain submissions:
@@ -548,16 +801,103 @@ impl Aggregator {
         submission: &Submission,
         service: &Service,
         queue_id: QuorumQueueId,
-        queue: Vec<Submission>,
+        queue: Vec<QueuedSubmission>,
         action: SubmitAction,
     ) -> Result<(), AggregatorError> {
-        // Running in a transaction keyed by chain to avoid nonce errors
+        let workflow = service.workflows.get(submission.workflow_id());
+
+        if let Some(quorum) = self.weighted_quorum(&queue_id, &queue) {
+            if !quorum.reached() {
+                tracing::info!(
+                    "Aggregator: Waiting for weighted quorum for {}: signer weight: {}, threshold weight: {}, total weight: {}",
+                    submission.label(),
+                    quorum.signer_weight,
+                    quorum.threshold_weight,
+                    quorum.total_weight
+                );
+                self.quorum_progress
+                    .insert(queue_id.clone(), QuorumProgress::from(&quorum));
+                self.record_queue_depth(service, submission, queue.len());
+                self.save_quorum_queue(queue_id, queue).await?;
+                return Ok(());
+            }
+        }
+
+        // Shadow testing: everything up to here ran for real, only the broadcast is skipped
+        if workflow.is_some_and(|workflow| workflow.observe_only) {
+            tracing::info!(
+                "Aggregator: Observe-only workflow, not submitting {} with {} signatures: {:?}",
+                submission.label(),
+                queue.len(),
+                action
+            );
+            self.metrics
+                .increment_observed_count(service, submission.workflow_id());
+            self.record_observed(ObservedSubmission {
+                service_id: service.id(),
+                workflow_id: submission.workflow_id().clone(),
+                event_id: submission.event_id.clone(),
+                action,
+                signatures: queue.len(),
+                observed_at: unix_now(),
+            });
+            // Burn queue: later signatures for this event shouldn't trigger another record
+            self.burn_quorum_queue(queue_id).await?;
+            self.record_queue_depth(service, submission, 0);
+            return Ok(());
+        }
+
+        // Skip the broadcast entirely if the target contract already reflects this result
+        if let SubmitAction::Evm(evm_action) = &action {
+            if let Some(condition) =
+                workflow.and_then(|workflow| workflow.submit_condition.as_ref())
+            {
+                match self
+                    .evm_submit_condition_met(condition, submission, evm_action)
+                    .await
+                {
+                    Ok(true) => {
+                        tracing::info!(
+                            "Aggregator: Skipping submission for {}, submit condition reports it is already on-chain",
+                            submission.label()
+                        );
+                        self.metrics
+                            .increment_skipped_count(service, submission.workflow_id());
+                        // Burn queue: there's nothing left to submit for this event
+                        self.burn_quorum_queue(queue_id).await?;
+                        self.record_queue_depth(service, submission, 0);
+                        return Ok(());
+                    }
+                    Ok(false) => {}
+                    Err(err) => {
+                        // the condition is only an optimization, so fall back to submitting
+                        tracing::warn!(
+                            "Aggregator: Failed to evaluate submit condition for {}, submitting anyway: {:?}",
+                            submission.label(),
+                            err
+                        );
+                    }
+                }
+            }
+        }
+
+        // Running in a transaction keyed by chain to avoid nonce errors,
+        // transient RPC failures are retried with backoff
+        let retry_policy = self.config.aggregator.submit_retry_policy();
+        let submissions: Vec<Submission> = queue
+            .iter()
+            .map(|queued| queued.submission.clone())
+            .collect();
         let result: Result<Option<AnyTransactionReceipt>, AggregatorError> = self
             .chain_transaction
-            .run(action.chain().clone(), {
+            .run_with_retry(action.chain().clone(), &retry_policy, || {
                 let _self = self.clone();
-                let queue = queue.clone();
-                move || async move {
+                let submissions = submissions.clone();
+                let action = action.clone();
+                async move {
+                    // held for gas estimation and submission, released once the receipt is in
+                    let _permit = _self.chain_throttle.acquire(action.chain()).await;
+
                     match action {
                         SubmitAction::Evm(action) => {
                             let client = match _self.get_evm_client(&action.chain).await? {
@@ -568,7 +908,7 @@ impl Aggregator {
                             };
 
                             _self
-                                .handle_action_submit_evm(client, &queue, action)
+                                .handle_action_submit_evm(client, &submissions, action)
                                 .await
                                 .map(Some)
                         }
@@ -581,7 +921,7 @@ impl Aggregator {
                             };
 
                             _self
-                                .handle_action_submit_cosmos(client, &queue, action)
+                                .handle_action_submit_cosmos(client, &submissions, action)
                                 .await
                                 .map(Some)
                         }
@@ -594,7 +934,16 @@ impl Aggregator {
         // and returning early if None
         let result = match result {
             Ok(None) => {
-                return Ok(());
+                // still report it, other destinations of the same result may have gone through
+                return self.send_submit_callback(
+                    submission,
+                    service,
+                    Err(format!(
+                        "No submission client for chain {}, check its config and credential",
+                        action.chain()
+                    )),
+                    action,
+                );
             }
             Ok(Some(tx_resp)) => Ok(tx_resp),
             Err(e) => Err(e),
@@ -620,6 +969,15 @@ impl Aggregator {
                     );
 
                 // Save queue: Next submission from another operator will retry with accumulated signatures
+                self.quorum_progress.insert(
+                    queue_id.clone(),
+                    QuorumProgress {
+                        signer_weight: signer_weight.clone(),
+                        threshold_weight: threshold_weight.clone(),
+                        total_weight: total_weight.clone(),
+                    },
+                );
+                self.record_queue_depth(service, submission, queue.len());
                 self.save_quorum_queue(queue_id, queue).await?;
             }
             Ok(tx_resp) => {
@@ -630,6 +988,22 @@ impl Aggregator {
                 );
                 // Burn queue: Mark as completed to prevent duplicate on-chain submissions
                 self.burn_quorum_queue(queue_id).await?;
+                self.record_queue_depth(service, submission, 0);
+                // a dry run has no side effects, off-chain targets included
+                if tx_resp.is_dry_run() {
+                    tracing::info!(
+                        "Aggregator: Dry run, not delivering {} to submit targets: {:?}",
+                        submission.label(),
+                        submission
+                            .submit_targets
+                            .iter()
+                            .map(target_label)
+                            .collect::<Vec<_>>()
+                    );
+                } else {
+                    self.deliver_to_targets(submission, service, &queue, tx_resp.tx_hash())
+                        .await;
+                }
             }
 
             Err(err) => {
@@ -658,24 +1032,75 @@ impl Aggregator {
                 // 2. Append its own submission
                 // 3. Retry submission with all signatures
                 // This implements automatic retry for transient errors like SignerNotRegistered
+                self.record_queue_depth(service, submission, queue.len());
                 self.save_quorum_queue(queue_id, queue).await?;
             }
         }
 
+        self.send_submit_callback(
+            submission,
+            service,
+            result
+                .map(|tx_resp| tx_resp.submit_receipt())
+                .map_err(|err| err.to_string()),
+            action,
+        )
+    }
+
+    /// Posts the result, with every signature that took it on-chain, to the submission's
+    /// off-chain targets. Only targets the workflow itself lists are used,
+    /// so a peer can't have results posted somewhere else
+    async fn deliver_to_targets(
+        &self,
+        submission: &Submission,
+        service: &Service,
+        queue: &[QueuedSubmission],
+        tx_hash: String,
+    ) {
+        let Some(target_deliverer) = &self.target_deliverer else {
+            return;
+        };
+        let Some(workflow) = service.workflows.get(submission.workflow_id()) else {
+            return;
+        };
+
+        let submit_targets: Vec<_> = submission
+            .submit_targets
+            .iter()
+            .filter(|target| workflow.submit_targets.contains(target))
+            .cloned()
+            .collect();
+        if submit_targets.is_empty() {
+            return;
+        }
+
+        let signatures = queue
+            .iter()
+            .map(|queued| ResultSignature {
+                signer: queued.provenance.signer,
+                signature: queued.submission.envelope_signature.clone(),
+            })
+            .collect();
+        let result = SignedResult::new(submission, signatures, Some(tx_hash));
+
+        target_deliverer
+            .deliver_all(service, &submit_targets, &result)
+            .await;
+    }
+
+    /// Hands the outcome for one destination back to the aggregator component
+    fn send_submit_callback(
+        &self,
+        submission: &Submission,
+        service: &Service,
+        result: Result<SubmitReceipt, String>,
+        action: SubmitAction,
+    ) -> Result<(), AggregatorError> {
         self.subsystem_to_dispatcher_tx
             .send(DispatcherCommand::AggregatorExecute {
                 submission: submission.clone(),
                 service: service.clone(),
-                kind: AggregatorExecuteKind::SubmitCallback {
-                    result: result
-                        .map(|tx_resp| match tx_resp {
-                            AnyTransactionReceipt::Evm(transaction_receipt) => {
-                                AnyTxHash::Evm(transaction_receipt.transaction_hash.to_vec())
-                            }
-                            AnyTransactionReceipt::Cosmos(tx_hash) => AnyTxHash::Cosmos(tx_hash),
-                        })
-                        .map_err(|err| err.to_string()),
-                },
+                kind: AggregatorExecuteKind::SubmitCallback { action, result },
             })
             .map_err(Box::new)?;
 
@@ -715,12 +1140,29 @@ impl Aggregator {
             }
         };
 
-        let client_config = chain_config.signing_client_config(credential.clone())?;
+        let mut client_config = chain_config
+            .signing_client_config(credential.clone())?
+            .with_nonce_pool(self.nonce_pool.clone());
+        if let Some(tls) = self.chain_tls.get(chain) {
+            client_config = client_config.with_tls(tls.clone());
+        }
+        if let Some(gas_bounds) = self.config.chain_gas_bounds.get(chain) {
+            client_config = client_config.with_gas_bounds(*gas_bounds);
+        }
+        if let Some(confirmations) = self.config.chain_confirmations.get(chain) {
+            client_config = client_config.with_confirmations(*confirmations);
+        }
 
         let client = EvmSigningClient::new(client_config)
             .await
             .map_err(AggregatorError::CreateEvmClient)?;
 
+        // refuse to submit through an endpoint serving some other chain
+        client
+            .verify_chain_id(&chain.id)
+            .await
+            .map_err(|e| AggregatorError::CreateEvmClient(e.into()))?;
+
         {
             let clients = &mut self.evm_submission_clients.write().unwrap();
             clients.insert(chain.clone(), client.clone());
@@ -778,20 +1220,70 @@ impl Aggregator {
     }
 }
 
+impl Aggregator {
+    /// How many observe-only records are kept, the oldest are dropped first
+    pub const MAX_OBSERVED_SUBMISSIONS: usize = 1000;
+
+    /// Signatures held for `submission`'s event, 0 once its queue is burned
+    fn record_queue_depth(&self, service: &Service, submission: &Submission, depth: usize) {
+        self.pipeline_metrics.record_aggregation_queue_depth(
+            depth,
+            &service.id().to_string(),
+            submission.trigger_action.data.chain(),
+        );
+    }
+
+    fn record_observed(&self, observed: ObservedSubmission) {
+        let mut records = self.observed.lock().unwrap();
+        if records.len() >= Self::MAX_OBSERVED_SUBMISSIONS {
+            records.pop_front();
+        }
+        records.push_back(observed);
+    }
+
+    /// Recorded would-be submissions of observe-only workflows, newest first
+    pub fn list_observed(
+        &self,
+        service_id: Option<&ServiceId>,
+        workflow_id: Option<&WorkflowId>,
+    ) -> Vec<ObservedSubmission> {
+        self.observed
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|observed| service_id.is_none_or(|id| observed.service_id == *id))
+            .filter(|observed| workflow_id.is_none_or(|id| observed.workflow_id == *id))
+            .cloned()
+            .collect()
+    }
+}
+
 impl Clone for Aggregator {
     fn clone(&self) -> Self {
         Self {
             metrics: self.metrics.clone(),
+            pipeline_metrics: self.pipeline_metrics.clone(),
             storage: self.storage.clone(),
+            queue_store: self.queue_store.clone(),
             config: self.config.clone(),
             services: self.services.clone(),
             dispatcher_to_aggregator_rx: self.dispatcher_to_aggregator_rx.clone(),
             aggregator_to_self_tx: self.aggregator_to_self_tx.clone(),
             subsystem_to_dispatcher_tx: self.subsystem_to_dispatcher_tx.clone(),
             evm_submission_clients: self.evm_submission_clients.clone(),
+            nonce_pool: self.nonce_pool.clone(),
             cosmos_submission_clients: self.cosmos_submission_clients.clone(),
             queue_transaction: self.queue_transaction.clone(),
             chain_transaction: self.chain_transaction.clone(),
+            chain_throttle: self.chain_throttle.clone(),
+            chain_tls: self.chain_tls.clone(),
+            received: self.received.clone(),
+            observed: self.observed.clone(),
+            quorum_progress: self.quorum_progress.clone(),
+            operator_set: self.operator_set.clone(),
+            in_flight: self.in_flight.clone(),
+            target_deliverer: self.target_deliverer.clone(),
             p2p_handle: self.p2p_handle.clone(),
             // Clones are not primary - only the original instance is
             is_primary: Arc::new(AtomicBool::new(false)),
@@ -808,3 +1300,25 @@ impl Drop for Aggregator {
         }
     }
 }
+
+/// Appends the workflow's fixed submit targets the component didn't already return
+fn add_fixed_targets(
+    service: &Service,
+    workflow_id: &WorkflowId,
+    actions: &mut Vec<AggregatorAction>,
+) {
+    let Some(Submit::Aggregator { targets, .. }) = service
+        .workflows
+        .get(workflow_id)
+        .map(|workflow| &workflow.submit)
+    else {
+        return;
+    };
+
+    for target in targets {
+        let action = AggregatorAction::Submit(target.clone());
+        if !actions.contains(&action) {
+            actions.push(action);
+        }
+    }
+}
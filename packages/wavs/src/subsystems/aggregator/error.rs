@@ -1,5 +1,9 @@
+use alloy_primitives::Address;
 use thiserror::Error;
-use utils::error::EvmClientError;
+use utils::{
+    async_transaction::Retryable,
+    error::{is_transient_rpc_error, EvmClientError},
+};
 use wavs_types::{
     contracts::cosmwasm::service_manager::error::WavsValidateError, ChainConfigError,
     QuorumQueueId, ServiceManagerError, SigningError,
@@ -69,4 +73,35 @@ pub enum AggregatorError {
 
     #[error("P2P: {0}")]
     P2p(String),
+
+    #[error("Submit condition: {0}")]
+    SubmitCondition(String),
+
+    #[error("Invalid aggregator config: {0}")]
+    InvalidConfig(String),
+
+    #[error("Submission signed by {0}, which is not the signing key of any operator")]
+    UnknownSigner(Address),
+
+    #[error("Submission signed by {signer} for operator {operator}, which has no weight")]
+    UnstakedOperator { signer: Address, operator: Address },
+
+    #[error("Operator set lookup: {0}")]
+    OperatorSet(String),
+}
+
+impl Retryable for AggregatorError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            AggregatorError::EvmClient(err) => err.is_retryable(),
+            AggregatorError::CosmosClient(err) | AggregatorError::BlockNumber(err) => {
+                is_transient_rpc_error(&format!("{err:#}"))
+            }
+            AggregatorError::EvmServiceManagerValidateUnknown(err)
+            | AggregatorError::EvmServiceManagerLookup(err) => {
+                is_transient_rpc_error(&err.to_string())
+            }
+            _ => false,
+        }
+    }
 }
@@ -0,0 +1,450 @@
+//! Checks that a submission was signed by a registered, staked operator of its service
+//! before it's queued, so a peer can't fill quorum queues with signatures the service manager
+//! will reject anyway.
+//! Lookups go to the service manager and are cached per signer, re-checked once the
+//! refresh interval passes so registrations and stake changes are picked up.
+//! Signers are taken from signatures anyone can make, so the cache is bounded and signers
+//! without an operator are kept apart from registered ones.
+
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
+};
+
+use alloy_primitives::{Address, U256};
+use async_trait::async_trait;
+use layer_climb::prelude::*;
+use lru::LruCache;
+use utils::{
+    config::EvmChainConfigExt,
+    evm_client::{EvmEndpoint, EvmQueryClient},
+    tls::ChainTls,
+};
+use wavs_types::{
+    contracts::cosmwasm::service_manager::ServiceManagerQueryMessages, AnyChainConfig,
+    ChainConfigs, ChainKey, ServiceId, ServiceManager,
+};
+
+use super::error::AggregatorError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperatorInfo {
+    pub operator: Address,
+    pub weight: U256,
+}
+
+#[async_trait]
+pub trait OperatorSetQuery: Send + Sync {
+    /// The operator currently registered for `signing_key`, `None` if there isn't one
+    async fn operator(
+        &self,
+        manager: &ServiceManager,
+        signing_key: Address,
+    ) -> Result<Option<OperatorInfo>, AggregatorError>;
+}
+
+/// Queries the service manager on its own chain, with one client per chain kept for reuse
+pub struct ChainOperatorSetQuery {
+    chains: Arc<RwLock<ChainConfigs>>,
+    chain_tls: ChainTls,
+    evm_clients: RwLock<HashMap<ChainKey, EvmQueryClient>>,
+    cosmos_clients: RwLock<HashMap<ChainKey, QueryClient>>,
+}
+
+impl ChainOperatorSetQuery {
+    pub fn new(chains: Arc<RwLock<ChainConfigs>>, chain_tls: ChainTls) -> Self {
+        Self {
+            chains,
+            chain_tls,
+            evm_clients: RwLock::new(HashMap::new()),
+            cosmos_clients: RwLock::new(HashMap::new()),
+        }
+    }
+
+    async fn evm_client(
+        &self,
+        chain: &ChainKey,
+        chain_config: &AnyChainConfig,
+    ) -> Result<EvmQueryClient, AggregatorError> {
+        if let Some(client) = self.evm_clients.read().unwrap().get(chain) {
+            return Ok(client.clone());
+        }
+
+        // the same endpoints the aggregator submits through: every http endpoint in turn,
+        // or the first websocket one if there are none
+        let chain_config = chain_config.to_evm_config()?;
+        let mut endpoints = chain_config
+            .all_http_endpoints()
+            .into_iter()
+            .map(EvmEndpoint::new_http)
+            .collect::<Result<Vec<_>, _>>()?;
+        if endpoints.is_empty() {
+            endpoints.extend(chain_config.query_client_endpoints()?.into_iter().take(1));
+        }
+
+        let client =
+            EvmQueryClient::new_with_failover(endpoints, self.chain_tls.get(chain)).await?;
+
+        // refuse to trust an operator set read from some other chain
+        client.verify_chain_id(&chain.id).await?;
+
+        self.evm_clients
+            .write()
+            .unwrap()
+            .insert(chain.clone(), client.clone());
+        Ok(client)
+    }
+
+    async fn cosmos_client(
+        &self,
+        chain: &ChainKey,
+        chain_config: &AnyChainConfig,
+    ) -> Result<QueryClient, AggregatorError> {
+        if let Some(client) = self.cosmos_clients.read().unwrap().get(chain) {
+            return Ok(client.clone());
+        }
+
+        let client = QueryClient::new(chain_config.to_cosmos_config()?.into(), None)
+            .await
+            .map_err(AggregatorError::CosmosClient)?;
+
+        self.cosmos_clients
+            .write()
+            .unwrap()
+            .insert(chain.clone(), client.clone());
+        Ok(client)
+    }
+}
+
+#[async_trait]
+impl OperatorSetQuery for ChainOperatorSetQuery {
+    async fn operator(
+        &self,
+        manager: &ServiceManager,
+        signing_key: Address,
+    ) -> Result<Option<OperatorInfo>, AggregatorError> {
+        let chain_config = self
+            .chains
+            .read()
+            .unwrap()
+            .get_chain(manager.chain())
+            .ok_or_else(|| {
+                AggregatorError::OperatorSet(format!(
+                    "chain config not found for {}",
+                    manager.chain()
+                ))
+            })?;
+
+        match manager {
+            ServiceManager::Evm { address, .. } => {
+                let client = self.evm_client(manager.chain(), &chain_config).await?;
+                let service_manager = client.service_manager(*address);
+
+                let operator = service_manager
+                    .getLatestOperatorForSigningKey(signing_key)
+                    .call()
+                    .await
+                    .map_err(|e| AggregatorError::OperatorSet(e.to_string()))?;
+                if operator.is_zero() {
+                    return Ok(None);
+                }
+
+                let weight = service_manager
+                    .getOperatorWeight(operator)
+                    .call()
+                    .await
+                    .map_err(|e| AggregatorError::OperatorSet(e.to_string()))?;
+
+                Ok(Some(OperatorInfo { operator, weight }))
+            }
+            ServiceManager::Cosmos { address, .. } => {
+                let client = self.cosmos_client(manager.chain(), &chain_config).await?;
+
+                let operator: Option<EvmAddr> = client
+                    .contract_smart(
+                        &address.clone().into(),
+                        &ServiceManagerQueryMessages::WavsLatestOperatorForSigningKey {
+                            signing_key_addr: signing_key.into(),
+                        },
+                    )
+                    .await
+                    .map_err(AggregatorError::CosmosClient)?;
+                let Some(operator) = operator else {
+                    return Ok(None);
+                };
+
+                let weight: cosmwasm_std::Uint256 = client
+                    .contract_smart(
+                        &address.clone().into(),
+                        &ServiceManagerQueryMessages::WavsOperatorWeight {
+                            operator_address: operator.clone(),
+                        },
+                    )
+                    .await
+                    .map_err(AggregatorError::CosmosClient)?;
+
+                Ok(Some(OperatorInfo {
+                    operator: operator.into(),
+                    weight: U256::from_be_bytes(weight.to_be_bytes()),
+                }))
+            }
+        }
+    }
+}
+
+/// How many signers are remembered with an operator, and separately without one
+const CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(1024).unwrap();
+
+type CacheKey = (ServiceId, Address);
+
+struct CachedOperator {
+    operator: Option<OperatorInfo>,
+    fetched_at: Instant,
+}
+
+struct OperatorCache {
+    registered: LruCache<CacheKey, CachedOperator>,
+    /// Apart from the registered signers, so made-up ones can't push those out
+    unknown: LruCache<CacheKey, CachedOperator>,
+}
+
+impl OperatorCache {
+    /// The cached lookup for `key`, dropping it if it's older than `refresh`
+    fn get(&mut self, key: &CacheKey, refresh: Duration) -> Option<Option<OperatorInfo>> {
+        for cache in [&mut self.registered, &mut self.unknown] {
+            let Some(entry) = cache.get(key) else {
+                continue;
+            };
+            if entry.fetched_at.elapsed() < refresh {
+                return Some(entry.operator);
+            }
+            cache.pop(key);
+        }
+        None
+    }
+
+    fn insert(&mut self, key: CacheKey, operator: Option<OperatorInfo>) {
+        let (cache, stale) = match operator {
+            Some(_) => (&mut self.registered, &mut self.unknown),
+            None => (&mut self.unknown, &mut self.registered),
+        };
+        stale.pop(&key);
+        cache.put(
+            key,
+            CachedOperator {
+                operator,
+                fetched_at: Instant::now(),
+            },
+        );
+    }
+}
+
+/// Snapshot of each service's operator set, filled in one signer at a time as submissions arrive
+#[derive(Clone)]
+pub struct OperatorSet {
+    query: Arc<dyn OperatorSetQuery>,
+    refresh: Duration,
+    cache: Arc<Mutex<OperatorCache>>,
+}
+
+impl OperatorSet {
+    pub fn new(query: impl OperatorSetQuery + 'static, refresh: Duration) -> Self {
+        Self {
+            query: Arc::new(query),
+            refresh,
+            cache: Arc::new(Mutex::new(OperatorCache {
+                registered: LruCache::new(CACHE_SIZE),
+                unknown: LruCache::new(CACHE_SIZE),
+            })),
+        }
+    }
+
+    /// Fails unless `signer` is the signing key of an operator with some weight.
+    /// Unknown signers are cached too, but only the most recent ones: a peer signing with
+    /// fresh keys still costs a lookup each, it just can't grow the cache or evict operators
+    pub async fn check(
+        &self,
+        service_id: &ServiceId,
+        manager: &ServiceManager,
+        signer: Address,
+    ) -> Result<OperatorInfo, AggregatorError> {
+        let key = (service_id.clone(), signer);
+
+        let cached = self.cache.lock().unwrap().get(&key, self.refresh);
+
+        let operator = match cached {
+            Some(operator) => operator,
+            None => {
+                let operator = self.query.operator(manager, signer).await?;
+                self.cache.lock().unwrap().insert(key, operator);
+                operator
+            }
+        };
+
+        match operator {
+            None => Err(AggregatorError::UnknownSigner(signer)),
+            Some(info) if info.weight.is_zero() => Err(AggregatorError::UnstakedOperator {
+                signer,
+                operator: info.operator,
+            }),
+            Some(info) => Ok(info),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::HashMap,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use wavs_types::ChainKey;
+
+    use super::*;
+
+    /// A fixed operator set, counting how often it's asked
+    struct MockOperatorSet {
+        operators: HashMap<Address, OperatorInfo>,
+        queries: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl OperatorSetQuery for MockOperatorSet {
+        async fn operator(
+            &self,
+            _manager: &ServiceManager,
+            signing_key: Address,
+        ) -> Result<Option<OperatorInfo>, AggregatorError> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            Ok(self.operators.get(&signing_key).copied())
+        }
+    }
+
+    fn setup(refresh: Duration) -> (OperatorSet, Arc<AtomicUsize>, ServiceId, ServiceManager) {
+        let queries = Arc::new(AtomicUsize::new(0));
+        let operators = HashMap::from([
+            (
+                Address::repeat_byte(1),
+                OperatorInfo {
+                    operator: Address::repeat_byte(0x11),
+                    weight: U256::from(100),
+                },
+            ),
+            (
+                Address::repeat_byte(2),
+                OperatorInfo {
+                    operator: Address::repeat_byte(0x22),
+                    weight: U256::ZERO,
+                },
+            ),
+        ]);
+        let manager = ServiceManager::Evm {
+            chain: ChainKey::new("evm:1").unwrap(),
+            address: Address::repeat_byte(0xaa),
+        };
+        let service_id = ServiceId::from(&manager);
+
+        (
+            OperatorSet::new(
+                MockOperatorSet {
+                    operators,
+                    queries: queries.clone(),
+                },
+                refresh,
+            ),
+            queries,
+            service_id,
+            manager,
+        )
+    }
+
+    #[tokio::test]
+    async fn accepts_staked_operator() {
+        let (operators, queries, service_id, manager) = setup(Duration::from_secs(60));
+
+        let info = operators
+            .check(&service_id, &manager, Address::repeat_byte(1))
+            .await
+            .unwrap();
+        assert_eq!(info.operator, Address::repeat_byte(0x11));
+
+        // served from the snapshot
+        operators
+            .check(&service_id, &manager, Address::repeat_byte(1))
+            .await
+            .unwrap();
+        assert_eq!(queries.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_and_unstaked_signers() {
+        let (operators, queries, service_id, manager) = setup(Duration::from_secs(60));
+
+        let unknown = Address::repeat_byte(3);
+        for _ in 0..2 {
+            assert!(matches!(
+                operators.check(&service_id, &manager, unknown).await,
+                Err(AggregatorError::UnknownSigner(signer)) if signer == unknown
+            ));
+        }
+
+        assert!(matches!(
+            operators
+                .check(&service_id, &manager, Address::repeat_byte(2))
+                .await,
+            Err(AggregatorError::UnstakedOperator { operator, .. })
+                if operator == Address::repeat_byte(0x22)
+        ));
+
+        // unknown signers are cached like known ones
+        assert_eq!(queries.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn unknown_signers_do_not_evict_operators() {
+        let (operators, queries, service_id, manager) = setup(Duration::from_secs(60));
+
+        operators
+            .check(&service_id, &manager, Address::repeat_byte(1))
+            .await
+            .unwrap();
+
+        let spam = CACHE_SIZE.get() as u64 * 2;
+        for i in 0..spam {
+            let signer = Address::left_padding_from(&(i + 0x100).to_be_bytes());
+            assert!(operators
+                .check(&service_id, &manager, signer)
+                .await
+                .is_err());
+        }
+
+        // the cache stays bounded
+        let cache = operators.cache.lock().unwrap();
+        assert_eq!(cache.unknown.len(), CACHE_SIZE.get());
+        assert_eq!(cache.registered.len(), 1);
+        drop(cache);
+
+        // and the operator is still served from it
+        operators
+            .check(&service_id, &manager, Address::repeat_byte(1))
+            .await
+            .unwrap();
+        assert_eq!(queries.load(Ordering::SeqCst), 1 + spam as usize);
+    }
+
+    #[tokio::test]
+    async fn refreshes_after_interval() {
+        let (operators, queries, service_id, manager) = setup(Duration::ZERO);
+
+        for _ in 0..3 {
+            operators
+                .check(&service_id, &manager, Address::repeat_byte(1))
+                .await
+                .unwrap();
+        }
+        assert_eq!(queries.load(Ordering::SeqCst), 3);
+    }
+}
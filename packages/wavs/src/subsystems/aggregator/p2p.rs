@@ -24,15 +24,18 @@ use std::{
     collections::{hash_map::DefaultHasher, HashMap, HashSet, VecDeque},
     hash::{Hash, Hasher},
     io, iter,
+    path::{Path, PathBuf},
     time::{Duration, Instant},
 };
 
 use async_trait::async_trait;
 use futures::{AsyncWriteExt, StreamExt};
 use libp2p::{
-    autonat,
+    allow_block_list, autonat,
     gossipsub::{self, IdentTopic, MessageAuthenticity, MessageId, ValidationMode},
-    identify, kad, mdns,
+    identify,
+    identity::Keypair,
+    kad, mdns,
     request_response::{self, Codec, ProtocolSupport},
     swarm::{behaviour::toggle::Toggle, NetworkBehaviour, SwarmEvent},
     Multiaddr, PeerId, StreamProtocol, Swarm, SwarmBuilder,
@@ -97,9 +100,26 @@ pub enum P2pConfig {
         max_stored_submissions_per_service: Option<usize>,
         catchup_request_timeout_secs: Option<u64>,
         max_concurrent_catchup_requests_per_service: Option<usize>,
+        /// Optional mutually-authenticated TLS transport, replacing Noise
+        #[serde(default)]
+        tls: Option<P2pTlsConfig>,
     },
 }
 
+/// Mutual TLS for the quorum-collection transport.
+///
+/// libp2p TLS certificates are self-signed and bound to the node's identity key,
+/// so trust is expressed by pinning the peer ids of the other operators rather than a CA.
+/// Both sides of every connection verify each other's certificate during the handshake,
+/// and connections from peers outside `trusted_peers` are refused.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct P2pTlsConfig {
+    /// Path to the protobuf-encoded identity keypair. Generated on first start if missing.
+    pub identity_key_path: PathBuf,
+    /// Peer ids (base58) of the operators allowed to connect
+    pub trusted_peers: Vec<String>,
+}
+
 impl P2pConfig {
     const DEFAULT_MAX_RETRY_DURATION_SECS: u64 = 10;
     const DEFAULT_RETRY_INTERVAL_MS: u64 = 200;
@@ -338,6 +358,14 @@ impl P2pConfig {
             P2pConfig::Disabled => Self::DEFAULT_MAX_CONCURRENT_CATCHUP_REQUESTS_PER_SERVICE,
         }
     }
+
+    /// Mutual TLS settings, only available in Remote mode
+    pub fn tls(&self) -> Option<&P2pTlsConfig> {
+        match self {
+            P2pConfig::Remote { tls, .. } => tls.as_ref(),
+            P2pConfig::Local { .. } | P2pConfig::Disabled => None,
+        }
+    }
 }
 
 // ============================================================================
@@ -399,7 +427,7 @@ impl Codec for CatchUpCodec {
 
         let mut buf = Vec::new();
         io.take(MAX_RESPONSE_SIZE).read_to_end(&mut buf).await?;
-        serde_json::from_slice(&buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        decode_catch_up_response(&buf)
     }
 
     async fn write_request<T>(
@@ -426,13 +454,59 @@ impl Codec for CatchUpCodec {
     where
         T: futures::AsyncWrite + Unpin + Send,
     {
-        let data =
-            serde_json::to_vec(&res).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let data = encode_catch_up_response(&res)?;
         io.write_all(&data).await?;
         io.close().await
     }
 }
 
+/// Each submission in its versioned wire format (see [`Submission::to_bytes`]),
+/// preceded by its length as a big-endian u32
+fn encode_catch_up_response(res: &CatchUpResponse) -> io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    for submission in &res.submissions {
+        let bytes = submission
+            .to_bytes()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let len = u32::try_from(bytes.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        data.extend_from_slice(&len.to_be_bytes());
+        data.extend_from_slice(&bytes);
+    }
+    Ok(data)
+}
+
+/// Also reads the plain JSON `CatchUpResponse` nodes sent before submissions were versioned.
+/// That starts with `{`, a length prefix can't unless a submission is over 2GB
+fn decode_catch_up_response(data: &[u8]) -> io::Result<CatchUpResponse> {
+    if data.first() == Some(&b'{') {
+        return serde_json::from_slice(data)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+    }
+
+    let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+
+    let mut submissions = Vec::new();
+    let mut rest = data;
+    while !rest.is_empty() {
+        let (len, tail) = rest
+            .split_first_chunk::<4>()
+            .ok_or_else(|| invalid("truncated submission length"))?;
+        let len = u32::from_be_bytes(*len) as usize;
+        if tail.len() < len {
+            return Err(invalid("truncated submission"));
+        }
+        let (bytes, tail) = tail.split_at(len);
+        submissions.push(
+            Submission::from_bytes(bytes)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+        );
+        rest = tail;
+    }
+
+    Ok(CatchUpResponse { submissions })
+}
+
 // ============================================================================
 // Network Behaviour
 // ============================================================================
@@ -452,6 +526,8 @@ struct WavsBehaviour {
     identify: identify::Behaviour,
     /// AutoNAT for external address discovery
     autonat: autonat::Behaviour,
+    /// Only allows connections from trusted peers (mutual TLS mode only)
+    trusted_peers: Toggle<allow_block_list::Behaviour<allow_block_list::AllowedPeers>>,
 }
 
 // ============================================================================
@@ -553,6 +629,7 @@ impl P2pHandle {
 
         let mode_name = match p2p_config {
             P2pConfig::Local { .. } => "Local (mDNS)",
+            P2pConfig::Remote { tls: Some(_), .. } => "Remote (Kademlia, mutual TLS)",
             P2pConfig::Remote { .. } => "Remote (Kademlia)",
             P2pConfig::Disabled => "Disabled",
         };
@@ -604,62 +681,149 @@ fn build_swarm(config: &P2pConfig) -> Result<Swarm<WavsBehaviour>, AggregatorErr
 
     let is_local = matches!(config, P2pConfig::Local { .. });
     let catchup_request_timeout = config.catchup_request_timeout();
+    let trusted_peers = config
+        .tls()
+        .map(|tls| parse_trusted_peers(&tls.trusted_peers))
+        .transpose()?;
+
+    let build_behaviour = move |key: &Keypair| {
+        let peer_id = key.public().to_peer_id();
+
+        let gossipsub =
+            gossipsub::Behaviour::new(MessageAuthenticity::Signed(key.clone()), gossipsub_config)
+                .map_err(|e| format!("Failed to create gossipsub: {}", e))?;
+
+        // Catch-up request/response protocol with explicit timeout
+        let catchup = request_response::Behaviour::new(
+            iter::once((StreamProtocol::new(CATCHUP_PROTOCOL), ProtocolSupport::Full)),
+            request_response::Config::default().with_request_timeout(catchup_request_timeout),
+        );
+
+        // Discovery: mDNS for Local mode, Kademlia for Remote mode
+        let (mdns, kademlia) = if is_local {
+            let mdns = mdns::tokio::Behaviour::new(mdns::Config::default(), peer_id)
+                .map_err(|e| format!("Failed to create mDNS: {}", e))?;
+            (Toggle::from(Some(mdns)), Toggle::from(None))
+        } else {
+            // Remote mode: use Kademlia DHT
+            let store = kad::store::MemoryStore::new(peer_id);
+            let kademlia = kad::Behaviour::new(peer_id, store);
+            (Toggle::from(None), Toggle::from(Some(kademlia)))
+        };
+
+        let identify = identify::Behaviour::new(identify::Config::new(
+            PROTOCOL_VERSION.to_string(),
+            key.public(),
+        ));
+
+        let autonat = autonat::Behaviour::new(peer_id, autonat::Config::default());
 
-    let swarm = SwarmBuilder::with_new_identity()
-        .with_tokio()
-        .with_tcp(
-            libp2p::tcp::Config::default(),
-            libp2p::noise::Config::new,
-            libp2p::yamux::Config::default,
-        )
-        .map_err(|e| AggregatorError::P2p(format!("Failed to configure TCP: {}", e)))?
-        .with_behaviour(|key| {
-            let peer_id = key.public().to_peer_id();
-
-            let gossipsub = gossipsub::Behaviour::new(
-                MessageAuthenticity::Signed(key.clone()),
-                gossipsub_config,
+        let trusted_peers = Toggle::from(trusted_peers.map(|peers| {
+            let mut allowed =
+                allow_block_list::Behaviour::<allow_block_list::AllowedPeers>::default();
+            for peer in peers {
+                allowed.allow_peer(peer);
+            }
+            allowed
+        }));
+
+        Ok::<_, Box<dyn std::error::Error + Send + Sync>>(WavsBehaviour {
+            gossipsub,
+            catchup,
+            mdns,
+            kademlia,
+            identify,
+            autonat,
+            trusted_peers,
+        })
+    };
+
+    let swarm = match config.tls() {
+        Some(tls) => {
+            let keypair = load_or_create_identity(&tls.identity_key_path)?;
+            SwarmBuilder::with_existing_identity(keypair)
+                .with_tokio()
+                .with_tcp(
+                    libp2p::tcp::Config::default(),
+                    libp2p::tls::Config::new,
+                    libp2p::yamux::Config::default,
+                )
+                .map_err(|e| AggregatorError::P2p(format!("Failed to configure TCP/TLS: {}", e)))?
+                .with_behaviour(build_behaviour)
+                .map_err(|e| AggregatorError::P2p(format!("Failed to build behaviour: {}", e)))?
+                .build()
+        }
+        None => SwarmBuilder::with_new_identity()
+            .with_tokio()
+            .with_tcp(
+                libp2p::tcp::Config::default(),
+                libp2p::noise::Config::new,
+                libp2p::yamux::Config::default,
             )
-            .map_err(|e| format!("Failed to create gossipsub: {}", e))?;
+            .map_err(|e| AggregatorError::P2p(format!("Failed to configure TCP: {}", e)))?
+            .with_behaviour(build_behaviour)
+            .map_err(|e| AggregatorError::P2p(format!("Failed to build behaviour: {}", e)))?
+            .build(),
+    };
 
-            // Catch-up request/response protocol with explicit timeout
-            let catchup = request_response::Behaviour::new(
-                iter::once((StreamProtocol::new(CATCHUP_PROTOCOL), ProtocolSupport::Full)),
-                request_response::Config::default().with_request_timeout(catchup_request_timeout),
-            );
+    Ok(swarm)
+}
 
-            // Discovery: mDNS for Local mode, Kademlia for Remote mode
-            let (mdns, kademlia) = if is_local {
-                let mdns = mdns::tokio::Behaviour::new(mdns::Config::default(), peer_id)
-                    .map_err(|e| format!("Failed to create mDNS: {}", e))?;
-                (Toggle::from(Some(mdns)), Toggle::from(None))
-            } else {
-                // Remote mode: use Kademlia DHT
-                let store = kad::store::MemoryStore::new(peer_id);
-                let kademlia = kad::Behaviour::new(peer_id, store);
-                (Toggle::from(None), Toggle::from(Some(kademlia)))
-            };
+/// Load the node identity from disk, generating and persisting a new one if it doesn't exist yet.
+/// A stable identity is required so other operators can pin our peer id.
+fn load_or_create_identity(path: &Path) -> Result<Keypair, AggregatorError> {
+    if path.exists() {
+        let bytes = std::fs::read(path).map_err(|e| {
+            AggregatorError::P2p(format!("Failed to read identity key {:?}: {}", path, e))
+        })?;
+        return Keypair::from_protobuf_encoding(&bytes)
+            .map_err(|e| AggregatorError::P2p(format!("Invalid identity key {:?}: {}", path, e)));
+    }
 
-            let identify = identify::Behaviour::new(identify::Config::new(
-                PROTOCOL_VERSION.to_string(),
-                key.public(),
-            ));
+    let keypair = Keypair::generate_ed25519();
+    let bytes = keypair
+        .to_protobuf_encoding()
+        .map_err(|e| AggregatorError::P2p(format!("Failed to encode identity key: {}", e)))?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| {
+            AggregatorError::P2p(format!("Failed to create directory {:?}: {}", parent, e))
+        })?;
+    }
+    write_private_key(path, &bytes).map_err(|e| {
+        AggregatorError::P2p(format!("Failed to write identity key {:?}: {}", path, e))
+    })?;
+    tracing::info!(
+        "Generated new P2P identity {} at {:?}",
+        keypair.public().to_peer_id(),
+        path
+    );
+
+    Ok(keypair)
+}
 
-            let autonat = autonat::Behaviour::new(peer_id, autonat::Config::default());
+/// Only the owner can read the key, it's created that way rather than narrowed after writing
+fn write_private_key(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    use std::io::Write;
 
-            Ok(WavsBehaviour {
-                gossipsub,
-                catchup,
-                mdns,
-                kademlia,
-                identify,
-                autonat,
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options.open(path)?;
+    file.write_all(bytes)?;
+    file.sync_all()
+}
+
+fn parse_trusted_peers(peers: &[String]) -> Result<Vec<PeerId>, AggregatorError> {
+    peers
+        .iter()
+        .map(|peer| {
+            peer.parse::<PeerId>().map_err(|e| {
+                AggregatorError::P2p(format!("Invalid trusted peer id {}: {}", peer, e))
             })
         })
-        .map_err(|e| AggregatorError::P2p(format!("Failed to build behaviour: {}", e)))?
-        .build();
-
-    Ok(swarm)
+        .collect()
 }
 
 // ============================================================================
@@ -1362,7 +1526,7 @@ fn handle_gossip_message(
     aggregator_tx: &crossbeam::channel::Sender<AggregatorCommand>,
     state: &mut EventLoopState,
 ) {
-    let submission: Submission = match serde_json::from_slice(&message.data) {
+    let submission = match Submission::from_bytes(&message.data) {
         Ok(s) => s,
         Err(e) => {
             tracing::warn!(
@@ -1433,7 +1597,7 @@ fn handle_command(
             state.store_submission(*submission.clone());
 
             // Serialize and publish
-            let data = match serde_json::to_vec(&*submission) {
+            let data = match submission.to_bytes() {
                 Ok(d) => d,
                 Err(e) => {
                     tracing::error!("Failed to serialize submission: {}", e);
@@ -1611,3 +1775,107 @@ fn service_topic_name(service_id: &ServiceId) -> String {
 fn is_dialable_address(addr: &Multiaddr) -> bool {
     !addr.to_string().contains("/ip4/0.0.0.0/")
 }
+
+#[cfg(test)]
+mod test {
+    use alloy_primitives::FixedBytes;
+    use wavs_types::{
+        Envelope, EventId, ServiceId, SignatureKind, Trigger, TriggerAction, TriggerConfig,
+        TriggerData, WasmResponse, WavsSignature, WorkflowId,
+    };
+
+    use super::*;
+
+    fn submission(payload: &[u8]) -> Submission {
+        Submission {
+            trigger_action: TriggerAction {
+                config: TriggerConfig {
+                    service_id: ServiceId::from([1; 32]),
+                    workflow_id: WorkflowId::default(),
+                    trigger: Trigger::Manual,
+                },
+                data: TriggerData::Raw(payload.to_vec()),
+            },
+            operator_response: WasmResponse {
+                payload: payload.to_vec(),
+                ordering: None,
+                event_id_salt: None,
+            },
+            event_id: EventId::from([2; 20]),
+            envelope: Envelope {
+                eventId: FixedBytes::new([2; 20]),
+                ordering: Default::default(),
+                payload: payload.to_vec().into(),
+            },
+            envelope_signature: WavsSignature {
+                data: vec![3; 65],
+                kind: SignatureKind::evm_default(),
+            },
+            submit_targets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn catch_up_response_round_trips() {
+        let response = CatchUpResponse {
+            submissions: vec![submission(b"first"), submission(b"second")],
+        };
+
+        let data = encode_catch_up_response(&response).unwrap();
+        let decoded = decode_catch_up_response(&data).unwrap();
+        assert_eq!(decoded.submissions, response.submissions);
+
+        assert!(decode_catch_up_response(&[])
+            .unwrap()
+            .submissions
+            .is_empty());
+        assert!(decode_catch_up_response(&data[..data.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn reads_unversioned_catch_up_response() {
+        // how nodes answered before submissions were versioned
+        let response = CatchUpResponse {
+            submissions: vec![submission(b"first")],
+        };
+        let legacy = serde_json::to_vec(&response).unwrap();
+
+        let decoded = decode_catch_up_response(&legacy).unwrap();
+        assert_eq!(decoded.submissions, response.submissions);
+    }
+
+    #[test]
+    fn creates_identity_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("p2p").join("identity.key");
+
+        let keypair = load_or_create_identity(&path).unwrap();
+        assert!(path.exists());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let stored = Keypair::from_protobuf_encoding(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(stored.public(), keypair.public());
+    }
+
+    #[test]
+    fn reloads_identity_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("identity.key");
+
+        let created = load_or_create_identity(&path).unwrap();
+        let reloaded = load_or_create_identity(&path).unwrap();
+        assert_eq!(
+            reloaded.public().to_peer_id(),
+            created.public().to_peer_id()
+        );
+
+        std::fs::write(&path, b"not a key").unwrap();
+        assert!(load_or_create_identity(&path).is_err());
+    }
+}
@@ -1,8 +1,18 @@
-use wavs_types::{QuorumQueue, QuorumQueueId, Submission};
+use utils::time::unix_now;
+use wavs_types::{
+    EventId, QueuedSubmission, QuorumProgress, QuorumQueue, QuorumQueueId, QuorumQueueStatus,
+    ServiceId, Submission, SubmissionProvenance,
+};
 
-use crate::subsystems::aggregator::{error::AggregatorError, Aggregator};
+use crate::{
+    config::OnTimeout,
+    subsystems::aggregator::{error::AggregatorError, Aggregator},
+};
 
 impl Aggregator {
+    /// How often queues are checked against their service's quorum timeout
+    pub const QUORUM_TIMEOUT_SWEEP_SECS: u64 = 5;
+
     pub async fn get_quorum_queue(
         &self,
         id: &QuorumQueueId,
@@ -26,14 +36,20 @@ impl Aggregator {
     pub async fn save_quorum_queue(
         &self,
         id: QuorumQueueId,
-        submissions: Vec<Submission>,
+        submissions: Vec<QueuedSubmission>,
     ) -> Result<(), AggregatorError> {
         let storage = self.storage.clone();
+        let queue_store = self.queue_store.clone();
 
         let _ = tokio::task::spawn_blocking(move || {
+            let queue = QuorumQueue::Active(submissions);
+            // the in-memory copy stays authoritative if the disk write fails
+            if let Err(err) = queue_store.write_queue(&id, &queue) {
+                tracing::warn!("Failed to persist quorum queue {:?}: {:?}", id, err);
+            }
             storage
                 .quorum_queues
-                .insert(id, QuorumQueue::Active(submissions))
+                .insert(id, queue)
                 .map_err(AggregatorError::Db)
         })
         .await
@@ -45,15 +61,19 @@ impl Aggregator {
     #[allow(clippy::result_large_err)]
     pub async fn burn_quorum_queue(&self, id: QuorumQueueId) -> Result<(), AggregatorError> {
         let storage = self.storage.clone();
-        let burned_at = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        let burned_at = unix_now();
+        let queue_store = self.queue_store.clone();
+        self.quorum_progress.remove(&id);
 
         let _ = tokio::task::spawn_blocking(move || {
+            let queue = QuorumQueue::Burned(burned_at);
+            // the in-memory copy stays authoritative if the disk write fails
+            if let Err(err) = queue_store.write_queue(&id, &queue) {
+                tracing::warn!("Failed to persist quorum queue {:?}: {:?}", id, err);
+            }
             storage
                 .quorum_queues
-                .insert(id, QuorumQueue::Burned(burned_at))
+                .insert(id, queue)
                 .map_err(AggregatorError::Db)
         })
         .await
@@ -62,18 +82,207 @@ impl Aggregator {
         Ok(())
     }
 
+    /// Writes every active quorum queue to disk, so submissions still waiting for quorum at shutdown
+    /// are picked up again on the next start. Returns how many were written
+    pub fn persist_quorum_queues(&self) -> usize {
+        let mut persisted = 0;
+
+        for entry in self.storage.quorum_queues.iter() {
+            let (id, queue) = entry.pair();
+            if !matches!(queue, QuorumQueue::Active(_)) {
+                continue;
+            }
+            match self.queue_store.write_queue(id, queue) {
+                Ok(()) => persisted += 1,
+                Err(err) => tracing::warn!("Failed to persist quorum queue {:?}: {:?}", id, err),
+            }
+        }
+
+        persisted
+    }
+
+    /// Snapshot of the quorum queues, optionally only those for a single event or service
+    pub async fn list_quorum_queues(
+        &self,
+        event_id: Option<EventId>,
+        service_id: Option<ServiceId>,
+    ) -> Result<Vec<QuorumQueueStatus>, AggregatorError> {
+        let storage = self.storage.clone();
+        let quorum_progress = self.quorum_progress.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let now = unix_now();
+            let mut queues: Vec<QuorumQueueStatus> = storage
+                .quorum_queues
+                .iter()
+                .filter(|entry| {
+                    event_id
+                        .as_ref()
+                        .is_none_or(|event_id| entry.pair().0.event_id == *event_id)
+                })
+                .map(|entry| {
+                    let (id, queue) = entry.pair();
+                    let quorum = quorum_progress.get(id).map(|progress| progress.clone());
+                    queue_status(id, queue, quorum, now)
+                })
+                .filter(|status| {
+                    service_id
+                        .as_ref()
+                        .is_none_or(|service_id| status.service_id.as_ref() == Some(service_id))
+                })
+                .collect();
+
+            queues.sort_by(|a, b| (&a.event_id, &a.action).cmp(&(&b.event_id, &b.action)));
+            queues
+        })
+        .await
+        .map_err(|e| AggregatorError::JoinError(e.to_string()))
+    }
+
+    /// Snapshot of a single quorum queue, `None` if no submission has been queued for it
+    pub async fn get_quorum_queue_status(
+        &self,
+        id: &QuorumQueueId,
+    ) -> Result<Option<QuorumQueueStatus>, AggregatorError> {
+        let storage = self.storage.clone();
+        let quorum = self
+            .quorum_progress
+            .get(id)
+            .map(|progress| progress.clone());
+
+        tokio::task::spawn_blocking({
+            let id = id.clone();
+            move || {
+                storage
+                    .quorum_queues
+                    .get_cloned(&id)
+                    .map(|queue| queue_status(&id, &queue, quorum, unix_now()))
+            }
+        })
+        .await
+        .map_err(|e| AggregatorError::JoinError(e.to_string()))
+    }
+
+    /// Expire active queues that have waited longer than their service's quorum timeout,
+    /// counted from the first signature received. Returns how many were expired
+    pub async fn expire_quorum_queues(&self) -> Result<usize, AggregatorError> {
+        let timeouts = &self.config.aggregator.quorum_timeouts;
+        let now = unix_now();
+
+        let expired: Vec<QuorumQueueId> = self
+            .storage
+            .quorum_queues
+            .iter()
+            .filter_map(|entry| {
+                let (id, queue) = entry.pair();
+                let QuorumQueue::Active(queued) = queue else {
+                    return None;
+                };
+                let timeout = timeouts.get(queued.first()?.submission.service_id())?;
+                let started_at = queued
+                    .iter()
+                    .map(|queued| queued.provenance.received_at)
+                    .min()?;
+
+                (now.saturating_sub(started_at) >= timeout.quorum_timeout_secs).then(|| id.clone())
+            })
+            .collect();
+
+        let mut expired_count = 0;
+        for queue_id in expired {
+            // same lock as appending, so a submission arriving right now can't be lost or resubmitted
+            let result = self
+                .queue_transaction
+                .run(queue_id.clone(), || self.expire_quorum_queue(queue_id))
+                .await;
+
+            match result {
+                Ok(true) => expired_count += 1,
+                Ok(false) => {}
+                Err(err) => tracing::error!("Aggregator: Error expiring quorum queue: {:?}", err),
+            }
+        }
+
+        Ok(expired_count)
+    }
+
+    /// Returns false if the queue was burned in the meantime
+    async fn expire_quorum_queue(&self, queue_id: QuorumQueueId) -> Result<bool, AggregatorError> {
+        let queued = match self.get_quorum_queue(&queue_id).await? {
+            QuorumQueue::Active(queued) if !queued.is_empty() => queued,
+            _ => return Ok(false),
+        };
+
+        // safe - checked above that the queue isn't empty
+        let submission = queued.last().unwrap().submission.clone();
+        let on_timeout = self
+            .config
+            .aggregator
+            .quorum_timeouts
+            .get(submission.service_id())
+            .map(|timeout| timeout.on_timeout)
+            .unwrap_or_default();
+
+        tracing::warn!(
+            queue_id = ?queue_id,
+            collected = queued.len(),
+            on_timeout = ?on_timeout,
+            "Aggregator: Quorum timeout reached for {}",
+            submission.label()
+        );
+
+        if on_timeout == OnTimeout::SubmitPartial {
+            match self.services.get(submission.service_id()) {
+                Ok(service) => {
+                    let action = queue_id.action.clone();
+                    if let Err(err) = self
+                        .handle_submit_action(
+                            &submission,
+                            &service,
+                            queue_id.clone(),
+                            queued,
+                            action,
+                        )
+                        .await
+                    {
+                        tracing::error!(
+                            "Aggregator: Partial submission for {} failed: {:?}",
+                            submission.label(),
+                            err
+                        );
+                    }
+                }
+                Err(err) => {
+                    tracing::error!(
+                        "Aggregator: Cannot submit partial quorum for {}, service not found: {:?}",
+                        submission.label(),
+                        err
+                    );
+                }
+            }
+        }
+
+        // Burn queue: whatever happened above, this event is no longer waiting for quorum
+        self.burn_quorum_queue(queue_id).await?;
+
+        Ok(true)
+    }
+
     /// Clean up burned quorum queues that are older than the configured TTL
     pub async fn cleanup_old_burned_queues(&self) -> Result<usize, AggregatorError> {
         let storage = self.storage.clone();
+        let queue_store = self.queue_store.clone();
         let ttl_secs = self.config.aggregator.burned_queue_ttl_secs();
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        let now = unix_now();
+
+        let cutoff_time = now.saturating_sub(ttl_secs);
+
+        // provenance is copied into the queue entry on append, so old receive records aren't needed
+        self.received
+            .retain(|_, provenance| provenance.received_at >= cutoff_time);
 
         tokio::task::spawn_blocking(move || {
             let mut removed_count = 0;
-            let cutoff_time = now.saturating_sub(ttl_secs);
 
             // Collect keys to remove (can't remove while iterating)
             let keys_to_remove: Vec<QuorumQueueId> = storage
@@ -92,6 +301,9 @@ impl Aggregator {
 
             // Remove the expired entries
             for key in keys_to_remove {
+                if let Err(err) = queue_store.delete_queue(&key) {
+                    tracing::warn!("Failed to delete quorum queue file {:?}: {:?}", key, err);
+                }
                 storage.quorum_queues.remove(&key);
                 removed_count += 1;
             }
@@ -105,12 +317,15 @@ impl Aggregator {
 
 pub fn append_submission_to_queue(
     queue_id: &QuorumQueueId,
-    queue: &mut Vec<Submission>,
+    queue: &mut Vec<QueuedSubmission>,
     submission: Submission,
+    provenance: SubmissionProvenance,
 ) -> Result<(), AggregatorError> {
     match queue.first() {
         None => {}
-        Some(prev) => {
+        Some(QueuedSubmission {
+            submission: prev, ..
+        }) => {
             // check if the submission is the same as the last one
             // TODO - let custom logic here? wasm component?
             if submission.envelope != prev.envelope {
@@ -124,21 +339,66 @@ pub fn append_submission_to_queue(
         .envelope_signature
         .evm_signer_address(&submission.envelope)?;
 
-    for queued_submission in queue.iter_mut() {
-        let queued_submission_signer_address = queued_submission
+    let queued_submission = QueuedSubmission {
+        submission,
+        provenance,
+    };
+
+    for queued in queue.iter_mut() {
+        let queued_signer_address = queued
+            .submission
             .envelope_signature
-            .evm_signer_address(&queued_submission.envelope)?;
+            .evm_signer_address(&queued.submission.envelope)?;
 
         // if the signer is the same as the one in the queue, we can just update it
         // this effectively allows re-trying failed aggregation
-        if submission_signer_address == queued_submission_signer_address {
-            *queued_submission = submission;
+        if submission_signer_address == queued_signer_address {
+            *queued = queued_submission;
 
             return Ok(());
         }
     }
 
-    queue.push(submission);
+    queue.push(queued_submission);
 
     Ok(())
 }
+
+fn queue_status(
+    id: &QuorumQueueId,
+    queue: &QuorumQueue,
+    quorum: Option<QuorumProgress>,
+    now: u64,
+) -> QuorumQueueStatus {
+    let (service_id, age_secs, burned_at, entries) = match queue {
+        QuorumQueue::Burned(timestamp) => (None, None, Some(*timestamp), Vec::new()),
+        QuorumQueue::Active(queued) => (
+            queued
+                .first()
+                .map(|queued| queued.submission.service_id().clone()),
+            queued
+                .iter()
+                .map(|queued| queued.provenance.received_at)
+                .min()
+                .map(|started_at| now.saturating_sub(started_at)),
+            None,
+            queued
+                .iter()
+                .map(|queued| queued.provenance.clone())
+                .collect::<Vec<_>>(),
+        ),
+    };
+
+    QuorumQueueStatus {
+        // encoding a queue id can't fail, it's just plain data
+        id: const_hex::encode(id.to_bytes().unwrap_or_default()),
+        event_id: id.event_id.clone(),
+        action: id.action.clone(),
+        service_id,
+        collected: entries.len(),
+        age_secs,
+        quorum,
+        burned_at,
+        entries,
+    }
+}
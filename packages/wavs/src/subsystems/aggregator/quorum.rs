@@ -0,0 +1,139 @@
+//! Stake-weighted quorum over the signatures collected in a queue, so a queue that can't pass
+//! the service manager's own check yet isn't sent to the chain on every new signature.
+//! Signer weights come from the operator-set snapshot taken when each submission was received,
+//! the total weight from the service manager's last quorum check of the queue.
+
+use std::collections::BTreeMap;
+
+use alloy_primitives::{Address, U256};
+use wavs_types::{QuorumProgress, SubmissionProvenance};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeightedQuorum {
+    pub signer_weight: U256,
+    pub threshold_weight: U256,
+    pub total_weight: U256,
+}
+
+impl WeightedQuorum {
+    /// `None` if the weight of any signer is unknown, the on-chain check has to decide then.
+    /// A signer counts once, however many of its submissions are queued
+    pub fn new<'a>(
+        signers: impl IntoIterator<Item = &'a SubmissionProvenance>,
+        total_weight: U256,
+        threshold_percent: u8,
+    ) -> Option<Self> {
+        let mut weights = BTreeMap::<Address, U256>::new();
+        for provenance in signers {
+            let weight = provenance.weight.as_ref()?.parse().ok()?;
+            weights.insert(provenance.signer, weight);
+        }
+
+        let signer_weight = weights
+            .into_values()
+            .fold(U256::ZERO, |sum, weight| sum.saturating_add(weight));
+
+        // rounded up, so 66% of 10 needs 7 rather than 6
+        let threshold_weight = total_weight
+            .saturating_mul(U256::from(threshold_percent))
+            .div_ceil(U256::from(100));
+
+        Some(Self {
+            signer_weight,
+            threshold_weight,
+            total_weight,
+        })
+    }
+
+    pub fn reached(&self) -> bool {
+        !self.signer_weight.is_zero() && self.signer_weight >= self.threshold_weight
+    }
+}
+
+impl From<&WeightedQuorum> for QuorumProgress {
+    fn from(quorum: &WeightedQuorum) -> Self {
+        Self {
+            signer_weight: quorum.signer_weight.to_string(),
+            threshold_weight: quorum.threshold_weight.to_string(),
+            total_weight: quorum.total_weight.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn signer(n: u8, weight: u64) -> SubmissionProvenance {
+        SubmissionProvenance {
+            signer: Address::repeat_byte(n),
+            peer: None,
+            received_at: 0,
+            weight: Some(weight.to_string()),
+        }
+    }
+
+    fn quorum(signers: &[SubmissionProvenance], total_weight: u64) -> WeightedQuorum {
+        WeightedQuorum::new(signers, U256::from(total_weight), 66).unwrap()
+    }
+
+    #[test]
+    fn uneven_stake() {
+        // one large operator (70) and three small ones (10 each)
+        let large = signer(1, 70);
+        let small = [signer(2, 10), signer(3, 10), signer(4, 10)];
+
+        // the large one alone is enough
+        let alone = quorum(std::slice::from_ref(&large), 100);
+        assert_eq!(alone.threshold_weight, U256::from(66));
+        assert!(alone.reached());
+
+        // every small operator together is not
+        let smalls = quorum(&small, 100);
+        assert_eq!(smalls.signer_weight, U256::from(30));
+        assert!(!smalls.reached());
+
+        // until the large one signs too
+        let mut all = small.to_vec();
+        all.push(large);
+        assert!(quorum(&all, 100).reached());
+    }
+
+    #[test]
+    fn many_small_operators() {
+        // 50 operators with 1 each can't reach 66% of a total dominated by a 100 stake operator
+        let small: Vec<_> = (0..50).map(|n| signer(n, 1)).collect();
+        assert!(!quorum(&small, 150).reached());
+
+        let mut with_large = small.clone();
+        with_large.push(signer(0xff, 100));
+        assert!(quorum(&with_large, 150).reached());
+    }
+
+    #[test]
+    fn threshold_rounds_up() {
+        let quorum = quorum(&[signer(1, 6)], 10);
+        assert_eq!(quorum.threshold_weight, U256::from(7));
+        assert!(!quorum.reached());
+    }
+
+    #[test]
+    fn signers_count_once() {
+        let repeated = [signer(1, 40), signer(1, 40)];
+        assert_eq!(quorum(&repeated, 100).signer_weight, U256::from(40));
+    }
+
+    #[test]
+    fn unknown_weight() {
+        let unknown = SubmissionProvenance {
+            weight: None,
+            ..signer(2, 0)
+        };
+        assert!(WeightedQuorum::new(&[signer(1, 70), unknown], U256::from(100), 66).is_none());
+    }
+
+    #[test]
+    fn zero_weight_never_reaches() {
+        assert!(!quorum(&[signer(1, 0)], 0).reached());
+    }
+}
@@ -0,0 +1,201 @@
+//! Plain file persistence for the aggregator's quorum queues.
+//!
+//! Each quorum queue lives in its own JSON file under `queues/`, so saving one queue never
+//! rewrites the others. Every write goes to a temporary file that is renamed into place,
+//! so a crash leaves either the old or the new contents, never a truncated file.
+//! The services the aggregator subscribes to aren't kept here, they're resubscribed from the
+//! node's services on startup.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utils::filesystem::{write_atomic, TMP_EXTENSION};
+use wavs_types::{QuorumQueue, QuorumQueueId};
+
+#[derive(Clone)]
+pub struct FileQueueStore {
+    queues_dir: PathBuf,
+}
+
+/// The id is stored alongside the queue since the filename is only a hash of it
+#[derive(Serialize, Deserialize)]
+struct QueueFile {
+    id: QuorumQueueId,
+    queue: QuorumQueue,
+}
+
+impl FileQueueStore {
+    pub const QUEUES_DIR: &str = "queues";
+
+    /// Creates the directory if needed
+    pub fn open(dir: &Path) -> Result<Self> {
+        let queues_dir = dir.join(Self::QUEUES_DIR);
+        std::fs::create_dir_all(&queues_dir)
+            .with_context(|| format!("Failed to create {}", queues_dir.display()))?;
+
+        Ok(Self { queues_dir })
+    }
+
+    /// Scans the queue directory, discarding temporary files left behind by an interrupted write.
+    /// Queue files that can't be parsed are skipped with a warning rather than failing startup
+    pub fn load_queues(&self) -> Result<Vec<(QuorumQueueId, QuorumQueue)>> {
+        let mut queues = Vec::new();
+
+        for entry in std::fs::read_dir(&self.queues_dir)? {
+            let path = entry?.path();
+
+            if path.extension().is_some_and(|ext| ext == TMP_EXTENSION) {
+                tracing::warn!("Removing incomplete quorum queue write {}", path.display());
+                std::fs::remove_file(&path)?;
+                continue;
+            }
+
+            let file = std::fs::read(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|bytes| Ok(serde_json::from_slice::<QueueFile>(&bytes)?));
+
+            match file {
+                Ok(QueueFile { id, queue }) => queues.push((id, queue)),
+                Err(err) => {
+                    tracing::warn!("Skipping quorum queue file {}: {:?}", path.display(), err);
+                }
+            }
+        }
+
+        Ok(queues)
+    }
+
+    pub fn write_queue(&self, id: &QuorumQueueId, queue: &QuorumQueue) -> Result<()> {
+        let bytes = serde_json::to_vec(&QueueFile {
+            id: id.clone(),
+            queue: queue.clone(),
+        })?;
+
+        write_atomic(&self.queue_path(id)?, &bytes)?;
+        Ok(())
+    }
+
+    pub fn delete_queue(&self, id: &QuorumQueueId) -> Result<()> {
+        match std::fs::remove_file(self.queue_path(id)?) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn queue_path(&self, id: &QuorumQueueId) -> Result<PathBuf> {
+        let hash = Sha256::digest(id.to_bytes()?);
+        Ok(self
+            .queues_dir
+            .join(format!("{}.json", const_hex::encode(hash))))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use layer_climb::prelude::EvmAddr;
+    use wavs_types::{EventId, EvmSubmitAction, SubmitAction};
+
+    use super::*;
+
+    fn queue_id(n: u8) -> QuorumQueueId {
+        QuorumQueueId {
+            event_id: EventId::from([n; 20]),
+            action: SubmitAction::Evm(EvmSubmitAction {
+                chain: "evm:anvil".parse().unwrap(),
+                address: EvmAddr::new([n; 20]),
+                gas_price: None,
+                eip1559: None,
+            }),
+        }
+    }
+
+    fn load_sorted(store: &FileQueueStore) -> Vec<(QuorumQueueId, serde_json::Value)> {
+        let mut queues: Vec<_> = store
+            .load_queues()
+            .unwrap()
+            .into_iter()
+            .map(|(id, queue)| (id, serde_json::to_value(queue).unwrap()))
+            .collect();
+        queues.sort_by(|a, b| a.0.event_id.cmp(&b.0.event_id));
+        queues
+    }
+
+    #[test]
+    fn round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+
+        {
+            let store = FileQueueStore::open(dir.path()).unwrap();
+            store
+                .write_queue(&queue_id(1), &QuorumQueue::Active(Vec::new()))
+                .unwrap();
+            store
+                .write_queue(&queue_id(2), &QuorumQueue::Active(Vec::new()))
+                .unwrap();
+            store
+                .write_queue(&queue_id(2), &QuorumQueue::Burned(42))
+                .unwrap();
+            store
+                .write_queue(&queue_id(3), &QuorumQueue::Burned(7))
+                .unwrap();
+            store.delete_queue(&queue_id(3)).unwrap();
+            // deleting a missing queue is not an error
+            store.delete_queue(&queue_id(4)).unwrap();
+        }
+
+        let store = FileQueueStore::open(dir.path()).unwrap();
+        assert_eq!(
+            load_sorted(&store),
+            vec![
+                (
+                    queue_id(1),
+                    serde_json::to_value(QuorumQueue::Active(Vec::new())).unwrap()
+                ),
+                (
+                    queue_id(2),
+                    serde_json::to_value(QuorumQueue::Burned(42)).unwrap()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn recovers_from_interrupted_write() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let store = FileQueueStore::open(dir.path()).unwrap();
+        store
+            .write_queue(&queue_id(1), &QuorumQueue::Burned(1))
+            .unwrap();
+
+        // a crash between writing the temporary file and renaming it leaves a partial file behind
+        let queue_path = store.queue_path(&queue_id(1)).unwrap();
+        std::fs::write(queue_path.with_extension(TMP_EXTENSION), b"{\"id\":").unwrap();
+        // and a queue file can't be parsed if it was corrupted some other way
+        std::fs::write(
+            dir.path().join(FileQueueStore::QUEUES_DIR).join("bad.json"),
+            b"",
+        )
+        .unwrap();
+        drop(store);
+
+        let store = FileQueueStore::open(dir.path()).unwrap();
+        assert_eq!(
+            load_sorted(&store),
+            vec![(
+                queue_id(1),
+                serde_json::to_value(QuorumQueue::Burned(1)).unwrap()
+            )]
+        );
+
+        // the leftover temporary queue file is cleaned up on load
+        let leftovers: Vec<_> = std::fs::read_dir(dir.path().join(FileQueueStore::QUEUES_DIR))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == TMP_EXTENSION))
+            .collect();
+        assert!(leftovers.is_empty());
+    }
+}
@@ -1,8 +1,11 @@
+use alloy_primitives::{Address, Bytes, FixedBytes};
 use alloy_provider::{DynProvider, Provider};
-use alloy_rpc_types_eth::TransactionReceipt;
+use alloy_rpc_types_eth::{TransactionReceipt, TransactionRequest};
+use alloy_sol_types::SolValue;
 use layer_climb::prelude::CosmosAddr;
 use serde::{Deserialize, Serialize};
-use utils::evm_client::EvmSigningClient;
+use utils::evm_client::{gas::GasFees, EvmSigningClient};
+use wavs_engine::bindings::aggregator::world::{wavs::types::chain::AnyTxHash, SubmitReceipt};
 use wavs_types::{
     contracts::cosmwasm::{
         service_handler::{ServiceHandlerExecuteMessages, ServiceHandlerQueryMessages},
@@ -13,7 +16,7 @@ use wavs_types::{
     CosmosSubmitAction, EvmSubmitAction,
     IWavsServiceHandler::IWavsServiceHandlerInstance,
     IWavsServiceManager::IWavsServiceManagerInstance,
-    ServiceManagerError, Submission, WavsSignature, WavsSigner,
+    ServiceManagerError, Submission, SubmitCondition, WavsSignature, WavsSigner,
 };
 
 use crate::subsystems::aggregator::{error::AggregatorError, Aggregator};
@@ -22,15 +25,73 @@ use crate::subsystems::aggregator::{error::AggregatorError, Aggregator};
 #[serde(rename_all = "snake_case")]
 pub enum AnyTransactionReceipt {
     Evm(Box<TransactionReceipt>),
-    // tx hash
-    Cosmos(String),
+    Cosmos {
+        tx_hash: String,
+        /// `None` if the node answered before the transaction was included in a block
+        block_height: Option<u64>,
+        gas_used: Option<u64>,
+    },
+    /// Built and validated, but not broadcast, see [`crate::config::AggregatorConfig::dry_run`]
+    EvmDryRun {
+        to: Address,
+        calldata: Bytes,
+    },
+    /// The execute message that would have been sent, as json
+    CosmosDryRun {
+        contract: String,
+        msg: String,
+    },
 }
 
 impl AnyTransactionReceipt {
+    pub fn is_dry_run(&self) -> bool {
+        matches!(
+            self,
+            AnyTransactionReceipt::EvmDryRun { .. } | AnyTransactionReceipt::CosmosDryRun { .. }
+        )
+    }
+
     pub fn tx_hash(&self) -> String {
         match self {
             AnyTransactionReceipt::Evm(receipt) => format!("{}", receipt.transaction_hash),
-            AnyTransactionReceipt::Cosmos(tx_hash) => tx_hash.clone(),
+            AnyTransactionReceipt::Cosmos { tx_hash, .. } => tx_hash.clone(),
+            AnyTransactionReceipt::EvmDryRun { .. }
+            | AnyTransactionReceipt::CosmosDryRun { .. } => "dry-run".to_string(),
+        }
+    }
+
+    /// What the aggregator component's submit callback is told about the transaction
+    pub fn submit_receipt(&self) -> SubmitReceipt {
+        match self {
+            AnyTransactionReceipt::Evm(receipt) => SubmitReceipt {
+                tx_hash: AnyTxHash::Evm(receipt.transaction_hash.to_vec()),
+                block_number: receipt.block_number,
+                gas_used: Some(receipt.gas_used),
+                effective_gas_price: Some(receipt.effective_gas_price.into()),
+            },
+            AnyTransactionReceipt::Cosmos {
+                tx_hash,
+                block_height,
+                gas_used,
+            } => SubmitReceipt {
+                tx_hash: AnyTxHash::Cosmos(tx_hash.clone()),
+                block_number: *block_height,
+                gas_used: *gas_used,
+                effective_gas_price: None,
+            },
+            // nothing was broadcast, so there's no hash to report
+            AnyTransactionReceipt::EvmDryRun { .. } => SubmitReceipt {
+                tx_hash: AnyTxHash::Evm(vec![0; 32]),
+                block_number: None,
+                gas_used: None,
+                effective_gas_price: None,
+            },
+            AnyTransactionReceipt::CosmosDryRun { .. } => SubmitReceipt {
+                tx_hash: AnyTxHash::Cosmos(String::new()),
+                block_number: None,
+                gas_used: None,
+                effective_gas_price: None,
+            },
         }
     }
 }
@@ -43,14 +104,12 @@ impl Aggregator {
         action: EvmSubmitAction,
     ) -> Result<AnyTransactionReceipt, AggregatorError> {
         tracing::info!("Handling submit for {}", queue.last().unwrap().label());
-        let contract_address = action.address.into();
+        let contract_address = action.address.clone().into();
 
         let service_manager = self
             .evm_get_submission_service_manager(client.provider.clone(), contract_address)
             .await?;
 
-        // TODO - query to see if we should submit at all (e.g. has it already been submitted?)
-
         let block_height_minus_one = service_manager
             .provider()
             .get_block_number()
@@ -125,19 +184,76 @@ impl Aggregator {
             }
         };
 
+        if self.config.aggregator.dry_run {
+            let tx = client
+                .preview_envelope_signatures(
+                    queue.first().unwrap().envelope.clone(),
+                    signature_data,
+                    contract_address,
+                    None,
+                )
+                .await?;
+            let calldata = tx.input.input().cloned().unwrap_or_default();
+            tracing::info!(
+                "Aggregator: Dry run, not broadcasting {} to {} on {} (gas limit {:?}): calldata {}",
+                queue.last().unwrap().label(),
+                contract_address,
+                action.chain,
+                tx.gas,
+                calldata
+            );
+            return Ok(AnyTransactionReceipt::EvmDryRun {
+                to: contract_address,
+                calldata,
+            });
+        }
+
         let tx_receipt = client
             .send_envelope_signatures(
                 queue.first().unwrap().envelope.clone(),
                 signature_data,
                 contract_address,
                 None,
-                action.gas_price,
+                evm_gas_fees(&action),
             )
             .await?;
 
         Ok(AnyTransactionReceipt::Evm(Box::new(tx_receipt)))
     }
 
+    /// Whether the submit condition reports the result as already on-chain
+    pub async fn evm_submit_condition_met(
+        &self,
+        condition: &SubmitCondition,
+        submission: &Submission,
+        action: &EvmSubmitAction,
+    ) -> Result<bool, AggregatorError> {
+        let SubmitCondition::EvmCall { address, selector } = condition;
+
+        let client = match self.get_evm_client(&action.chain).await? {
+            Some(client) => client,
+            None => return Ok(false),
+        };
+
+        let event_id: FixedBytes<20> = submission.event_id.clone().into();
+        let mut input = selector.as_slice().to_vec();
+        input.extend(event_id.abi_encode());
+
+        let tx = TransactionRequest::default()
+            .to(address.unwrap_or_else(|| action.address.clone().into()))
+            .input(input.into());
+
+        let _permit = self.chain_throttle.acquire(&action.chain).await;
+
+        let output = client
+            .provider
+            .call(tx)
+            .await
+            .map_err(|e| AggregatorError::SubmitCondition(e.to_string()))?;
+
+        bool::abi_decode(&output).map_err(|e| AggregatorError::SubmitCondition(e.to_string()))
+    }
+
     pub async fn handle_action_submit_cosmos(
         &self,
         client: layer_climb::prelude::SigningClient,
@@ -208,22 +324,39 @@ impl Aggregator {
 
         // if we reach here, validation passed and we should submit!
 
+        let msg = ServiceHandlerExecuteMessages::WavsHandleSignedEnvelope {
+            envelope: queue.first().unwrap().envelope.clone().into(),
+            signature_data: signature_data.clone().into(),
+        };
+
+        if self.config.aggregator.dry_run {
+            let msg = serde_json::to_string(&msg).unwrap_or_else(|err| format!("{err:?}"));
+            tracing::info!(
+                "Aggregator: Dry run, not broadcasting {} to {} on {}: execute msg {}",
+                queue.last().unwrap().label(),
+                action.address,
+                action.chain,
+                msg
+            );
+            return Ok(AnyTransactionReceipt::CosmosDryRun {
+                contract: action.address.to_string(),
+                msg,
+            });
+        }
+
         let resp = client
-            .contract_execute(
-                &action.address.into(),
-                &ServiceHandlerExecuteMessages::WavsHandleSignedEnvelope {
-                    envelope: queue.first().unwrap().envelope.clone().into(),
-                    signature_data: signature_data.clone().into(),
-                },
-                vec![],
-                None,
-            )
+            .contract_execute(&action.address.into(), &msg, vec![], None)
             .await
             .map_err(AggregatorError::CosmosClient)?;
 
         tracing::info!("Custom submit transaction sent: {:?}", resp.txhash);
 
-        Ok(AnyTransactionReceipt::Cosmos(resp.txhash))
+        Ok(AnyTransactionReceipt::Cosmos {
+            tx_hash: resp.txhash,
+            // zero until the transaction is committed
+            block_height: u64::try_from(resp.height).ok().filter(|height| *height > 0),
+            gas_used: u64::try_from(resp.gas_used).ok().filter(|gas| *gas > 0),
+        })
     }
 
     async fn evm_get_submission_service_manager(
@@ -249,3 +382,85 @@ impl Aggregator {
         ))
     }
 }
+
+/// EIP-1559 fees take precedence, the legacy gas price is only used without them
+fn evm_gas_fees(action: &EvmSubmitAction) -> Option<GasFees> {
+    match (action.eip1559, action.gas_price) {
+        (Some(fees), _) => Some(GasFees::Eip1559 {
+            max_fee_per_gas: fees.max_fee_per_gas,
+            max_priority_fee_per_gas: fees.max_priority_fee_per_gas,
+        }),
+        (None, Some(gas_price)) => Some(GasFees::Legacy { gas_price }),
+        (None, None) => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn submit_receipt_from_evm_receipt() {
+        let receipt: TransactionReceipt = serde_json::from_value(serde_json::json!({
+            "type": "0x2",
+            "status": "0x1",
+            "cumulativeGasUsed": "0x1e8480",
+            "logs": [],
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "transactionHash": format!("0x{}", "ab".repeat(32)),
+            "transactionIndex": "0x0",
+            "blockHash": format!("0x{}", "cd".repeat(32)),
+            "blockNumber": "0x2a",
+            "gasUsed": "0x5208",
+            "effectiveGasPrice": "0x3b9aca00",
+            "from": format!("0x{}", "11".repeat(20)),
+            "to": format!("0x{}", "22".repeat(20)),
+            "contractAddress": null,
+        }))
+        .unwrap();
+
+        let submit_receipt = AnyTransactionReceipt::Evm(Box::new(receipt)).submit_receipt();
+
+        assert!(matches!(
+            &submit_receipt.tx_hash,
+            AnyTxHash::Evm(hash) if *hash == vec![0xab; 32]
+        ));
+        assert_eq!(submit_receipt.block_number, Some(42));
+        assert_eq!(submit_receipt.gas_used, Some(21_000));
+        assert_eq!(
+            submit_receipt.effective_gas_price.map(u128::from),
+            Some(1_000_000_000)
+        );
+    }
+
+    #[test]
+    fn submit_receipt_from_cosmos_and_dry_runs() {
+        let submit_receipt = AnyTransactionReceipt::Cosmos {
+            tx_hash: "ABCD".to_string(),
+            block_height: Some(7),
+            gas_used: Some(120_000),
+        }
+        .submit_receipt();
+        assert!(matches!(&submit_receipt.tx_hash, AnyTxHash::Cosmos(hash) if hash == "ABCD"));
+        assert_eq!(submit_receipt.block_number, Some(7));
+        assert_eq!(submit_receipt.gas_used, Some(120_000));
+        assert!(submit_receipt.effective_gas_price.is_none());
+
+        let uncommitted = AnyTransactionReceipt::Cosmos {
+            tx_hash: "ABCD".to_string(),
+            block_height: None,
+            gas_used: None,
+        }
+        .submit_receipt();
+        assert_eq!(uncommitted.block_number, None);
+        assert_eq!(uncommitted.gas_used, None);
+
+        let dry_run = AnyTransactionReceipt::EvmDryRun {
+            to: Address::ZERO,
+            calldata: Bytes::new(),
+        }
+        .submit_receipt();
+        assert_eq!(dry_run.block_number, None);
+        assert_eq!(dry_run.gas_used, None);
+    }
+}
@@ -1,20 +1,31 @@
 pub mod error;
+pub mod scheduler;
+pub mod secrets;
+pub mod status;
 pub mod wasm_engine;
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Instant;
 
 use error::EngineError;
+use futures::{stream, StreamExt};
+use tokio::sync::Semaphore;
 use tracing::instrument;
-use utils::storage::CAStorage;
-use wavs_engine::bindings::aggregator::world::AnyTxHash;
+use utils::{storage::CAStorage, telemetry::PipelineMetrics};
+use wavs_engine::bindings::aggregator::world::SubmitReceipt;
 use wavs_types::{
-    AggregatorAction, ComponentDigest, Service, Submission, TriggerAction, WorkflowId,
+    AggregatorAction, ComponentDigest, Service, ServiceEventKind, ServiceStatus, Submission,
+    Submit, SubmitAction, TriggerAction, WorkflowId,
 };
 
 use crate::dispatcher::DispatcherCommand;
+use crate::service_events::ServiceEvents;
 use crate::services::Services;
+use crate::subsystems::engine::scheduler::ExecutionScheduler;
+use crate::subsystems::engine::status::WorkflowStatusTracker;
 use crate::subsystems::engine::wasm_engine::WasmEngine;
+use crate::subsystems::in_flight::InFlight;
 use crate::subsystems::submission::data::SubmissionRequest;
 use crate::AppContext;
 
@@ -34,16 +45,24 @@ pub enum EngineCommand {
 }
 
 #[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum AggregatorExecuteKind {
     Standard,
     TimerCallback,
-    SubmitCallback { result: Result<AnyTxHash, String> },
+    SubmitCallback {
+        /// The destination this result is for, one of possibly several per event
+        action: SubmitAction,
+        result: Result<SubmitReceipt, String>,
+    },
 }
 
 #[derive(Debug)]
 #[allow(clippy::large_enum_variant)]
 pub enum EngineResponse {
     Operator(SubmissionRequest),
+    /// Responses from one execution that set an `ordering`, sorted ascending,
+    /// to be submitted one after the other
+    OperatorSequence(Vec<SubmissionRequest>),
     Aggregator {
         submission: Submission,
         actions: Vec<AggregatorAction>,
@@ -55,23 +74,148 @@ pub enum EngineResponse {
 pub struct EngineManager<S: CAStorage> {
     pub engine: Arc<WasmEngine<S>>,
     pub services: Services,
+    pub workflow_status: WorkflowStatusTracker,
+    pub scheduler: ExecutionScheduler,
     pub dispatcher_to_engine_rx: crossbeam::channel::Receiver<EngineCommand>,
     pub subsystem_to_dispatcher_tx: crossbeam::channel::Sender<DispatcherCommand>,
+    pub pipeline_metrics: PipelineMetrics,
+    /// Shared with the submission manager and aggregator, so shutdown can drain them
+    pub in_flight: InFlight,
+    pub service_events: ServiceEvents,
+    /// Room for operator executions waiting on an execution slot. Once it's used up the engine
+    /// stops taking commands, so the backlog stays in the bounded dispatcher channels
+    pub queued: Arc<Semaphore>,
 }
 
 impl<S: CAStorage + Send + Sync + 'static> EngineManager<S> {
     pub fn new(
         engine: WasmEngine<S>,
         services: Services,
+        workflow_status: WorkflowStatusTracker,
+        scheduler: ExecutionScheduler,
         dispatcher_to_engine_rx: crossbeam::channel::Receiver<EngineCommand>,
         subsystem_to_dispatcher_tx: crossbeam::channel::Sender<DispatcherCommand>,
     ) -> Self {
         Self {
             engine: Arc::new(engine),
             services,
+            workflow_status,
+            scheduler,
             dispatcher_to_engine_rx,
             subsystem_to_dispatcher_tx,
+            pipeline_metrics: PipelineMetrics::default(),
+            in_flight: InFlight::default(),
+            service_events: ServiceEvents::default(),
+            queued: Arc::new(Semaphore::new(Semaphore::MAX_PERMITS)),
+        }
+    }
+
+    pub fn with_pipeline_metrics(mut self, pipeline_metrics: PipelineMetrics) -> Self {
+        self.pipeline_metrics = pipeline_metrics;
+        self
+    }
+
+    pub fn with_in_flight(mut self, in_flight: InFlight) -> Self {
+        self.in_flight = in_flight;
+        self
+    }
+
+    pub fn with_service_events(mut self, service_events: ServiceEvents) -> Self {
+        self.service_events = service_events;
+        self
+    }
+
+    /// How many operator executions can wait on an execution slot at once
+    pub fn with_max_queued(mut self, max_queued: usize) -> Self {
+        self.queued = Arc::new(Semaphore::new(max_queued.max(1)));
+        self
+    }
+
+    /// Precompile the operator and aggregator components of all active services,
+    /// compiling at most `parallelism` components at once
+    #[instrument(skip(self, services), fields(subsys = "EngineRunner"))]
+    pub async fn warmup(&self, services: &[Service], parallelism: usize) {
+        let digests: BTreeSet<ComponentDigest> = services
+            .iter()
+            .filter(|service| service.status == ServiceStatus::Active)
+            .flat_map(|service| service.workflows.values())
+            .flat_map(|workflow| {
+                let aggregator = match &workflow.submit {
+                    Submit::Aggregator { component, .. } => Some(component.source.digest().clone()),
+                    Submit::None => None,
+                };
+                std::iter::once(workflow.component.source.digest().clone()).chain(aggregator)
+            })
+            .collect();
+
+        let total = digests.len();
+        if total == 0 {
+            return;
         }
+
+        let lru_size = self.engine.lru_size();
+        if total > lru_size {
+            tracing::warn!(
+                "Warming up {} components but the component cache only holds {}, increase wasm_lru_size to keep them all warm",
+                total,
+                lru_size
+            );
+        }
+
+        tracing::info!(
+            "Warming up {} components (parallelism={})",
+            total,
+            parallelism
+        );
+        let start = Instant::now();
+
+        let results = stream::iter(digests)
+            .map(|digest| {
+                let engine = self.engine.clone();
+                tokio::spawn(async move {
+                    let start = Instant::now();
+                    let result = engine.warmup_component(&digest).await;
+                    (digest, result, start.elapsed())
+                })
+            })
+            .buffer_unordered(parallelism.max(1))
+            .enumerate()
+            .map(|(index, joined)| match joined {
+                Ok((digest, Ok(()), elapsed)) => {
+                    tracing::info!(
+                        "Warmed up component {} ({}/{}) in {:?}",
+                        digest,
+                        index + 1,
+                        total,
+                        elapsed
+                    );
+                    true
+                }
+                Ok((digest, Err(e), _)) => {
+                    tracing::error!(
+                        "Failed to warm up component {} ({}/{}): {:?}",
+                        digest,
+                        index + 1,
+                        total,
+                        e
+                    );
+                    false
+                }
+                Err(e) => {
+                    tracing::error!("Component warmup task failed: {:?}", e);
+                    false
+                }
+            })
+            .collect::<Vec<bool>>()
+            .await;
+
+        let failed = results.iter().filter(|ok| !**ok).count();
+        tracing::info!(
+            "Component warmup finished: compiled={}, failed={}, duration={:?}",
+            total - failed,
+            failed,
+            start.elapsed()
+        );
     }
 
     #[instrument(skip(self, ctx), fields(subsys = "EngineRunner"))]
@@ -79,6 +223,8 @@ impl<S: CAStorage + Send + Sync + 'static> EngineManager<S> {
     where
         S: 'static,
     {
+        ctx.rt.spawn(self.scheduler.clone().run());
+
         while let Ok(command) = self.dispatcher_to_engine_rx.recv() {
             tracing::info!(
                 "Got Engine Command: {}",
@@ -106,28 +252,81 @@ impl<S: CAStorage + Send + Sync + 'static> EngineManager<S> {
                     break;
                 }
                 EngineCommand::ExecuteOperator { action, service } => {
+                    let priority = service
+                        .workflows
+                        .get(&action.config.workflow_id)
+                        .map(|workflow| workflow.priority)
+                        .unwrap_or_default();
+                    let max_concurrent = service
+                        .max_concurrent_executions
+                        .map(|limit| limit as usize);
+                    // the engine thread isn't on the runtime, so it can wait here for room
+                    let Ok(queued) = ctx.rt.block_on(self.queued.clone().acquire_owned()) else {
+                        break;
+                    };
                     let _self = self.clone();
-                    ctx.rt.spawn(async move {
-                        match _self.run_trigger(action, service).await {
-                            Err(e) => {
-                                tracing::error!("Error running operator component: {:?}", e);
-                            }
-                            Ok(messages) => {
-                                for msg in messages {
-                                    if let Err(e) = _self.subsystem_to_dispatcher_tx.send(
-                                        DispatcherCommand::EngineResponse(
-                                            EngineResponse::Operator(msg),
-                                        ),
-                                    ) {
-                                        tracing::error!(
-                                            "Error sending message to dispatcher: {:?}",
-                                            e
-                                        );
+                    let in_flight = self.in_flight.start();
+                    self.scheduler.push_for_service(
+                        action.config.service_id.clone(),
+                        max_concurrent,
+                        priority,
+                        async move {
+                            // it's started, so it no longer counts as queued
+                            drop(queued);
+                            let _in_flight = in_flight;
+                            let service_id = action.config.service_id.clone();
+                            let workflow_id = action.config.workflow_id.clone();
+                            _self.service_events.publish(
+                                &service_id,
+                                &workflow_id,
+                                ServiceEventKind::ExecutionStarted,
+                            );
+                            match _self.run_trigger(action, service).await {
+                                Err(e) => {
+                                    tracing::error!("Error running operator component: {:?}", e);
+                                    _self.service_events.publish(
+                                        &service_id,
+                                        &workflow_id,
+                                        ServiceEventKind::ExecutionResult {
+                                            responses: 0,
+                                            error: Some(e.to_string()),
+                                        },
+                                    );
+                                    _self.workflow_status.record(
+                                        &service_id,
+                                        &workflow_id,
+                                        Err(e.to_string()),
+                                    );
+                                }
+                                Ok(messages) => {
+                                    _self.service_events.publish(
+                                        &service_id,
+                                        &workflow_id,
+                                        ServiceEventKind::ExecutionResult {
+                                            responses: messages.len(),
+                                            error: None,
+                                        },
+                                    );
+                                    _self.workflow_status.record(
+                                        &service_id,
+                                        &workflow_id,
+                                        Ok(messages.len()),
+                                    );
+                                    for response in operator_responses(messages) {
+                                        if let Err(e) = _self
+                                            .subsystem_to_dispatcher_tx
+                                            .send(DispatcherCommand::EngineResponse(response))
+                                        {
+                                            tracing::error!(
+                                                "Error sending message to dispatcher: {:?}",
+                                                e
+                                            );
+                                        }
                                     }
                                 }
                             }
-                        }
-                    });
+                        },
+                    );
                 }
                 EngineCommand::ExecuteAggregator {
                     submission,
@@ -135,7 +334,9 @@ impl<S: CAStorage + Send + Sync + 'static> EngineManager<S> {
                     kind,
                 } => {
                     let _self = self.clone();
+                    let in_flight = self.in_flight.start();
                     ctx.rt.spawn(async move {
+                        let _in_flight = in_flight;
                         match _self
                             .run_aggregator(&submission, service, kind.clone())
                             .await
@@ -173,12 +374,34 @@ impl<S: CAStorage + Send + Sync + 'static> EngineManager<S> {
                 .engine
                 .store_component_from_source(&workflow.component.source)
                 .await?;
+            self.engine
+                .validate_component_imports(&workflow.component)
+                .await?;
             digests.insert(workflow_id.clone(), digest);
         }
 
         Ok(digests)
     }
 
+    /// Run the `init` hook of every workflow component that exports one.
+    /// A component shared by several workflows is only initialized once.
+    #[instrument(skip(self, service), fields(subsys = "EngineRunner", service_id = %service.id()))]
+    pub async fn init_components_for_service(&self, service: &Service) -> Result<(), EngineError> {
+        let mut initialized = HashSet::new();
+
+        for (workflow_id, workflow) in service.workflows.iter() {
+            if !initialized.insert(workflow.component.source.digest().clone()) {
+                continue;
+            }
+
+            // boxed so the deep wasm execution future doesn't end up inlined into every
+            // future that adds a service
+            Box::pin(self.engine.init_operator_component(service, workflow_id)).await?;
+        }
+
+        Ok(())
+    }
+
     async fn run_trigger(
         &self,
         action: TriggerAction,
@@ -212,10 +435,30 @@ impl<S: CAStorage + Send + Sync + 'static> EngineManager<S> {
             workflow.component.source.digest()
         );
 
-        let mut wasm_responses = self
+        let chain = action.data.chain().cloned();
+        let start = Instant::now();
+        let output = self
             .engine
             .execute_operator_component(service.clone(), action.clone())
-            .await?;
+            .await;
+        self.pipeline_metrics.record_execution(
+            start.elapsed().as_secs_f64(),
+            &trigger_config.service_id.to_string(),
+            chain.as_ref(),
+        );
+        let output = output?;
+        let mut wasm_responses = output.responses;
+
+        let response_count = wasm_responses.len();
+        if !workflow
+            .response_mapping
+            .fits(response_count, workflow.submit_targets.len())
+        {
+            return Err(EngineError::ResponseTargetMismatch {
+                responses: response_count,
+                targets: workflow.submit_targets.len(),
+            });
+        }
 
         let mut submission_datas = Vec::new();
         // if there are results, send them down the pipeline to the submit processor
@@ -224,17 +467,22 @@ impl<S: CAStorage + Send + Sync + 'static> EngineManager<S> {
             tracing::info!(
                 service_id = %trigger_config.service_id,
                 service.name = %service.name,
-                service.manager = ?service.manager,
+                service.manager = %service.manager,
                 workflow_id = %trigger_config.workflow_id,
                 "Service {} (workflow {}) component execution produced no result",
                 service.name,
                 trigger_config.workflow_id
             );
         } else {
-            for operator_response in wasm_responses.drain(..) {
+            for (index, operator_response) in wasm_responses.drain(..).enumerate() {
                 let submission_data = SubmissionRequest {
                     trigger_action: action.clone(),
                     operator_response,
+                    submit_targets: workflow.response_mapping.targets_for(
+                        index,
+                        response_count,
+                        &workflow.submit_targets,
+                    ),
                     service: service.clone(),
                     #[cfg(feature = "dev")]
                     debug: Default::default(),
@@ -248,7 +496,7 @@ impl<S: CAStorage + Send + Sync + 'static> EngineManager<S> {
                 tracing::info!(
                     service_id = %trigger_config.service_id,
                     service.name = %service.name,
-                    service.manager = ?service.manager,
+                    service.manager = %service.manager,
                     workflow_id = %trigger_config.workflow_id,
                     payload_size = %payload_size,
                     event_id = %event_id,
@@ -295,12 +543,13 @@ impl<S: CAStorage + Send + Sync + 'static> EngineManager<S> {
                     )
                     .await?
             }
-            AggregatorExecuteKind::SubmitCallback { result } => {
+            AggregatorExecuteKind::SubmitCallback { action, result } => {
                 self.engine
                     .execute_aggregator_component_submit_callback(
                         service.clone(),
                         trigger_action.clone(),
                         operator_response.clone(),
+                        action,
                         result,
                         event_id.clone(),
                     )
@@ -309,7 +558,7 @@ impl<S: CAStorage + Send + Sync + 'static> EngineManager<S> {
                 tracing::info!(
                     service_id = %trigger_action.config.service_id,
                     service.name = %service.name,
-                    service.manager = ?service.manager,
+                    service.manager = %service.manager,
                     workflow_id = %trigger_action.config.workflow_id,
                     event_id = %event_id,
                     "Service {} (workflow {}) aggregator submit callback execution completed",
@@ -325,7 +574,7 @@ impl<S: CAStorage + Send + Sync + 'static> EngineManager<S> {
             tracing::info!(
                 service_id = %trigger_action.config.service_id,
                 service.name = %service.name,
-                service.manager = ?service.manager,
+                service.manager = %service.manager,
                 workflow_id = %trigger_action.config.workflow_id,
                 event_id = %event_id,
                 "Service {} (workflow {}) aggregator execution produced no result",
@@ -336,7 +585,7 @@ impl<S: CAStorage + Send + Sync + 'static> EngineManager<S> {
             tracing::info!(
                 service_id = %trigger_action.config.service_id,
                 service.name = %service.name,
-                service.manager = ?service.manager,
+                service.manager = %service.manager,
                 workflow_id = %trigger_action.config.workflow_id,
                 event_id = %event_id,
                 "Service {} (workflow {}) aggregator execution completed with {} actions",
@@ -349,3 +598,22 @@ impl<S: CAStorage + Send + Sync + 'static> EngineManager<S> {
         Ok(aggregator_actions)
     }
 }
+
+/// Splits one execution's responses by how they're submitted.
+/// Responses without an `ordering` are independent and go out in parallel,
+/// the ones with an `ordering` are kept together, ascending, so each waits on the one before it
+pub fn operator_responses(messages: Vec<SubmissionRequest>) -> Vec<EngineResponse> {
+    let (mut ordered, unordered): (Vec<_>, Vec<_>) = messages
+        .into_iter()
+        .partition(|msg| msg.operator_response.ordering.is_some());
+    ordered.sort_by_key(|msg| msg.operator_response.ordering);
+
+    let mut responses: Vec<_> = unordered
+        .into_iter()
+        .map(EngineResponse::Operator)
+        .collect();
+    if !ordered.is_empty() {
+        responses.push(EngineResponse::OperatorSequence(ordered));
+    }
+    responses
+}
@@ -31,6 +31,12 @@ pub enum EngineError {
     #[error("could not encode EventOrder {0:?}")]
     EncodeEventOrder(bincode::error::EncodeError),
 
+    #[error("Component returned a {size} byte response, over the limit of {limit} bytes")]
+    ResponseTooLarge { size: usize, limit: usize },
+
+    #[error("Component returned {responses} responses for {targets} submit targets")]
+    ResponseTargetMismatch { responses: usize, targets: usize },
+
     #[error("Chain config error: {0}")]
     ChainConfig(String),
 
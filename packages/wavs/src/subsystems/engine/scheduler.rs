@@ -0,0 +1,372 @@
+//! Orders operator executions by workflow priority when execution slots are contended.
+//!
+//! Each priority has its own FIFO queue. Whenever a slot frees up, the next execution is picked
+//! by smooth weighted round robin over the non-empty queues, so higher priorities get a share of
+//! the slots proportional to their weight while lower priorities still make steady progress.
+//!
+//! A service can also be capped in how many of its executions are in flight at once. Executions
+//! over that cap are held back per service and only join the priority queues as earlier ones
+//! finish, so a busy service never ties up execution slots just to wait on itself.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+use tokio::sync::{Notify, Semaphore};
+use utils::telemetry::EngineMetrics;
+use wavs_types::{ServiceId, WorkflowPriority};
+
+type Job = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+#[derive(Clone)]
+pub struct ExecutionScheduler {
+    queues: Arc<Mutex<PriorityQueues>>,
+    slots: Arc<Semaphore>,
+    notify: Arc<Notify>,
+    services: Arc<Mutex<HashMap<ServiceId, ServiceSlots>>>,
+    default_service_limit: Option<usize>,
+    metrics: Option<EngineMetrics>,
+}
+
+impl ExecutionScheduler {
+    /// With no `max_concurrent`, every execution starts as soon as it's pushed
+    pub fn new(max_concurrent: Option<usize>, metrics: Option<EngineMetrics>) -> Self {
+        let slots = max_concurrent
+            .filter(|max| *max > 0)
+            .unwrap_or(Semaphore::MAX_PERMITS);
+
+        Self {
+            queues: Arc::new(Mutex::new(PriorityQueues::default())),
+            slots: Arc::new(Semaphore::new(slots)),
+            notify: Arc::new(Notify::new()),
+            services: Arc::new(Mutex::new(HashMap::new())),
+            default_service_limit: None,
+            metrics,
+        }
+    }
+
+    /// Per-service cap used when a service doesn't set its own
+    pub fn with_default_service_limit(mut self, limit: Option<usize>) -> Self {
+        self.default_service_limit = limit;
+        self
+    }
+
+    /// Like [`Self::push`], but holds the execution back while `service_id` already has
+    /// `limit` (or the default per-service limit) executions in flight
+    pub fn push_for_service(
+        &self,
+        service_id: ServiceId,
+        limit: Option<usize>,
+        priority: WorkflowPriority,
+        job: impl Future<Output = ()> + Send + 'static,
+    ) {
+        let Some(limit) = limit
+            .or(self.default_service_limit)
+            .filter(|limit| *limit > 0)
+        else {
+            self.push(priority, job);
+            return;
+        };
+
+        let mut services = self.services.lock().unwrap();
+        let slots = services.entry(service_id.clone()).or_default();
+        // a service update may have changed the limit
+        slots.limit = limit;
+
+        if slots.running < limit {
+            slots.running += 1;
+            drop(services);
+            self.push(priority, self.release_when_done(service_id, Box::pin(job)));
+        } else {
+            tracing::debug!(
+                "Service {} is at its limit of {} concurrent executions, queueing ({} waiting)",
+                service_id,
+                limit,
+                slots.pending.len() + 1
+            );
+            slots.pending.push_back((priority, Box::pin(job)));
+        }
+    }
+
+    fn release_when_done(&self, service_id: ServiceId, job: Job) -> Job {
+        // a guard rather than a call after the await, so the slot also comes back
+        // if the job panics or is dropped before it finishes
+        let guard = ServiceSlotGuard {
+            scheduler: self.clone(),
+            service_id,
+        };
+        Box::pin(async move {
+            let _guard = guard;
+            job.await;
+        })
+    }
+
+    /// Frees a service slot and hands it to the next held back execution, if any
+    fn release(&self, service_id: ServiceId) {
+        let mut services = self.services.lock().unwrap();
+        let Some(slots) = services.get_mut(&service_id) else {
+            return;
+        };
+        slots.running = slots.running.saturating_sub(1);
+
+        let mut ready = Vec::new();
+        while slots.running < slots.limit {
+            let Some(next) = slots.pending.pop_front() else {
+                break;
+            };
+            slots.running += 1;
+            ready.push(next);
+        }
+
+        if slots.running == 0 && slots.pending.is_empty() {
+            services.remove(&service_id);
+        }
+        drop(services);
+
+        for (priority, job) in ready {
+            self.push(priority, self.release_when_done(service_id.clone(), job));
+        }
+    }
+
+    pub fn push(&self, priority: WorkflowPriority, job: impl Future<Output = ()> + Send + 'static) {
+        self.queues
+            .lock()
+            .unwrap()
+            .jobs
+            .entry(priority)
+            .or_default()
+            .push_back(Box::pin(job));
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_queued_executions(priority.as_str(), 1);
+        }
+
+        self.notify.notify_one();
+    }
+
+    pub fn queue_depth(&self, priority: WorkflowPriority) -> usize {
+        self.queues
+            .lock()
+            .unwrap()
+            .jobs
+            .get(&priority)
+            .map(VecDeque::len)
+            .unwrap_or_default()
+    }
+
+    /// Starts queued executions as slots free up. Runs until the runtime shuts down
+    pub async fn run(self) {
+        loop {
+            // the semaphore is never closed
+            let Ok(permit) = self.slots.clone().acquire_owned().await else {
+                return;
+            };
+
+            let (priority, job) = loop {
+                let notified = self.notify.notified();
+                if let Some(next) = self.queues.lock().unwrap().pop() {
+                    break next;
+                }
+                notified.await;
+            };
+
+            if let Some(metrics) = &self.metrics {
+                metrics.record_queued_executions(priority.as_str(), -1);
+            }
+
+            tokio::spawn(async move {
+                job.await;
+                drop(permit);
+            });
+        }
+    }
+}
+
+/// Releases its service slot when dropped
+struct ServiceSlotGuard {
+    scheduler: ExecutionScheduler,
+    service_id: ServiceId,
+}
+
+impl Drop for ServiceSlotGuard {
+    fn drop(&mut self) {
+        self.scheduler.release(self.service_id.clone());
+    }
+}
+
+#[derive(Default)]
+struct ServiceSlots {
+    limit: usize,
+    running: usize,
+    pending: VecDeque<(WorkflowPriority, Job)>,
+}
+
+#[derive(Default)]
+struct PriorityQueues {
+    jobs: HashMap<WorkflowPriority, VecDeque<Job>>,
+    /// Accumulated weight per priority, the highest goes next
+    credit: HashMap<WorkflowPriority, i64>,
+}
+
+impl PriorityQueues {
+    fn pop(&mut self) -> Option<(WorkflowPriority, Job)> {
+        let waiting: Vec<WorkflowPriority> = WorkflowPriority::ALL
+            .into_iter()
+            .filter(|priority| self.jobs.get(priority).is_some_and(|jobs| !jobs.is_empty()))
+            .collect();
+
+        // credit only accumulates while a priority has work waiting
+        self.credit.retain(|priority, _| waiting.contains(priority));
+
+        let total: i64 = waiting.iter().map(|p| p.weight() as i64).sum();
+        for priority in &waiting {
+            *self.credit.entry(*priority).or_default() += priority.weight() as i64;
+        }
+
+        // ties go to the higher priority
+        let next = waiting
+            .into_iter()
+            .max_by_key(|priority| (self.credit[priority], *priority))?;
+        *self.credit.get_mut(&next)? -= total;
+
+        let job = self.jobs.get_mut(&next)?.pop_front()?;
+        Some((next, job))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use super::*;
+
+    #[test]
+    fn weighted_round_robin() {
+        let mut queues = PriorityQueues::default();
+        for priority in WorkflowPriority::ALL {
+            for _ in 0..100 {
+                queues
+                    .jobs
+                    .entry(priority)
+                    .or_default()
+                    .push_back(Box::pin(async {}));
+            }
+        }
+
+        let total_weight: u32 = WorkflowPriority::ALL.iter().map(|p| p.weight()).sum();
+        let mut counts: HashMap<WorkflowPriority, u32> = HashMap::new();
+        for _ in 0..total_weight {
+            let (priority, _) = queues.pop().unwrap();
+            *counts.entry(priority).or_default() += 1;
+        }
+
+        // one full round hands out slots exactly in proportion to the weights
+        for priority in WorkflowPriority::ALL {
+            assert_eq!(counts[&priority], priority.weight());
+        }
+
+        // with only low priority work left, it gets every slot
+        queues.jobs.remove(&WorkflowPriority::High);
+        queues.jobs.remove(&WorkflowPriority::Normal);
+        for _ in 0..10 {
+            assert_eq!(queues.pop().unwrap().0, WorkflowPriority::Low);
+        }
+    }
+
+    #[tokio::test]
+    async fn limits_concurrent_executions() {
+        let scheduler = ExecutionScheduler::new(Some(1), None);
+        let (started_tx, mut started_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+
+        // occupies the only slot until released
+        let tx = started_tx.clone();
+        scheduler.push(WorkflowPriority::Low, async move {
+            tx.send(WorkflowPriority::Low).unwrap();
+            let _ = release_rx.await;
+        });
+        tokio::spawn(scheduler.clone().run());
+        assert_eq!(started_rx.recv().await, Some(WorkflowPriority::Low));
+
+        for priority in [WorkflowPriority::Low, WorkflowPriority::High] {
+            let tx = started_tx.clone();
+            scheduler.push(priority, async move {
+                tx.send(priority).unwrap();
+            });
+        }
+        assert_eq!(scheduler.queue_depth(WorkflowPriority::Low), 1);
+        assert_eq!(scheduler.queue_depth(WorkflowPriority::High), 1);
+
+        // the high priority execution jumps the queue once the slot frees up
+        release_tx.send(()).unwrap();
+        assert_eq!(started_rx.recv().await, Some(WorkflowPriority::High));
+        assert_eq!(started_rx.recv().await, Some(WorkflowPriority::Low));
+    }
+
+    #[tokio::test]
+    async fn limits_concurrent_executions_per_service() {
+        let scheduler = ExecutionScheduler::new(None, None);
+        tokio::spawn(scheduler.clone().run());
+
+        let service_id = ServiceId::hash(b"service");
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+        let (done_tx, mut done_rx) = tokio::sync::mpsc::unbounded_channel();
+
+        for _ in 0..50 {
+            let running = running.clone();
+            let max_running = max_running.clone();
+            let done_tx = done_tx.clone();
+            scheduler.push_for_service(
+                service_id.clone(),
+                Some(4),
+                WorkflowPriority::Normal,
+                async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    done_tx.send(()).unwrap();
+                },
+            );
+        }
+
+        for _ in 0..50 {
+            done_rx.recv().await.unwrap();
+        }
+
+        assert_eq!(max_running.load(Ordering::SeqCst), 4);
+        // nothing is left behind once the service goes idle
+        assert!(scheduler.services.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn panicking_execution_frees_its_service_slot() {
+        let scheduler = ExecutionScheduler::new(None, None);
+        tokio::spawn(scheduler.clone().run());
+
+        let service_id = ServiceId::hash(b"service");
+        scheduler.push_for_service(
+            service_id.clone(),
+            Some(1),
+            WorkflowPriority::Normal,
+            async { panic!("execution panicked") },
+        );
+
+        let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+        scheduler.push_for_service(service_id, Some(1), WorkflowPriority::Normal, async move {
+            done_tx.send(()).unwrap();
+        });
+
+        tokio::time::timeout(Duration::from_secs(5), done_rx)
+            .await
+            .expect("the held back execution never started")
+            .unwrap();
+    }
+}
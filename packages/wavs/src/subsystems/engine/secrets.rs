@@ -0,0 +1,57 @@
+//! Secret values the operator holds for each service, loaded from the file at `secrets_file`.
+//! The file is JSON, keyed by service id and then by secret name:
+//! `{ "<service id>": { "API_KEY": "..." } }`.
+//! Service definitions only grant secret names to components, so the values never leave the node.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+use serde::Deserialize;
+use wavs_types::ServiceId;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(transparent)]
+pub struct ServiceSecrets(HashMap<ServiceId, BTreeMap<String, String>>);
+
+#[derive(Debug, thiserror::Error)]
+pub enum SecretsError {
+    #[error("Failed to read secrets file {path}: {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Failed to parse secrets file {path}: {source}")]
+    Parse {
+        path: String,
+        source: serde_json::Error,
+    },
+}
+
+impl ServiceSecrets {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SecretsError> {
+        let path = path.as_ref();
+        let contents = std::fs::read(path).map_err(|source| SecretsError::Read {
+            path: path.display().to_string(),
+            source,
+        })?;
+
+        serde_json::from_slice(&contents).map_err(|source| SecretsError::Parse {
+            path: path.display().to_string(),
+            source,
+        })
+    }
+
+    /// Every secret held for the service. The engine narrows these down to what each
+    /// component is granted
+    pub fn for_service(&self, service_id: &ServiceId) -> BTreeMap<String, String> {
+        self.0.get(service_id).cloned().unwrap_or_default()
+    }
+}
+
+impl FromIterator<(ServiceId, BTreeMap<String, String>)> for ServiceSecrets {
+    fn from_iter<T: IntoIterator<Item = (ServiceId, BTreeMap<String, String>)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
@@ -0,0 +1,467 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use wavs_types::{ServiceId, Timestamp, WorkflowId, WorkflowOutcome, WorkflowStatusResponse};
+
+/// Tracks per-workflow execution outcomes so operators can inspect them over the admin API
+#[derive(Clone, Default)]
+pub struct WorkflowStatusTracker {
+    inner: Arc<RwLock<HashMap<(ServiceId, WorkflowId), WorkflowStatusEntry>>>,
+    /// Where outcomes go once they fall out of the in-memory buffer, if enabled
+    spill: Option<Arc<OutcomeSpill>>,
+}
+
+#[derive(Default)]
+struct WorkflowStatusEntry {
+    total_successes: u64,
+    total_failures: u64,
+    recent_outcomes: VecDeque<WorkflowOutcome>,
+}
+
+impl WorkflowStatusTracker {
+    /// Maximum number of outcomes kept per workflow
+    pub const MAX_RECENT_OUTCOMES: usize = 50;
+
+    pub fn new(spill: Option<OutcomeSpill>) -> Self {
+        Self {
+            inner: Arc::default(),
+            spill: spill.map(Arc::new),
+        }
+    }
+
+    pub fn record(
+        &self,
+        service_id: &ServiceId,
+        workflow_id: &WorkflowId,
+        result: Result<usize, String>,
+    ) {
+        let mut lock = self.inner.write().unwrap();
+        let entry = lock
+            .entry((service_id.clone(), workflow_id.clone()))
+            .or_default();
+
+        let outcome = match result {
+            Ok(responses) => {
+                entry.total_successes += 1;
+                WorkflowOutcome {
+                    timestamp: Timestamp::now(),
+                    success: true,
+                    responses,
+                    error: None,
+                }
+            }
+            Err(error) => {
+                entry.total_failures += 1;
+                WorkflowOutcome {
+                    timestamp: Timestamp::now(),
+                    success: false,
+                    responses: 0,
+                    error: Some(error),
+                }
+            }
+        };
+
+        entry.recent_outcomes.push_front(outcome);
+
+        if entry.recent_outcomes.len() > Self::MAX_RECENT_OUTCOMES {
+            let evicted = entry.recent_outcomes.split_off(Self::MAX_RECENT_OUTCOMES);
+
+            if let Some(spill) = &self.spill {
+                // taken before letting go of the outcomes, so concurrent evictions
+                // reach the spill in the order they left memory
+                let mut current = spill.current.lock().unwrap();
+                drop(lock);
+
+                if let Err(err) = spill.append(&mut current, service_id, workflow_id, evicted) {
+                    tracing::warn!("Failed to spill workflow outcomes to disk: {}", err);
+                }
+            }
+        }
+    }
+
+    pub fn get(
+        &self,
+        service_id: &ServiceId,
+        workflow_id: &WorkflowId,
+        limit: Option<usize>,
+    ) -> WorkflowStatusResponse {
+        let lock = self.inner.read().unwrap();
+        let entry = lock.get(&(service_id.clone(), workflow_id.clone()));
+
+        WorkflowStatusResponse {
+            service_id: service_id.clone(),
+            workflow_id: workflow_id.clone(),
+            total_successes: entry.map(|e| e.total_successes).unwrap_or_default(),
+            total_failures: entry.map(|e| e.total_failures).unwrap_or_default(),
+            recent_outcomes: entry
+                .map(|e| {
+                    e.recent_outcomes
+                        .iter()
+                        .take(limit.unwrap_or(Self::MAX_RECENT_OUTCOMES))
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Like [`Self::get`], but once the in-memory outcomes run out the rest of `limit`
+    /// is filled with older outcomes from the disk spill
+    pub fn get_with_spilled(
+        &self,
+        service_id: &ServiceId,
+        workflow_id: &WorkflowId,
+        limit: Option<usize>,
+    ) -> WorkflowStatusResponse {
+        let mut status = self.get(service_id, workflow_id, limit);
+        let limit = limit.unwrap_or(Self::MAX_RECENT_OUTCOMES);
+
+        if let Some(spill) = &self.spill {
+            let remaining = limit.saturating_sub(status.recent_outcomes.len());
+            if remaining > 0 {
+                match spill.read(service_id, workflow_id, remaining) {
+                    Ok(outcomes) => status.recent_outcomes.extend(outcomes),
+                    Err(err) => {
+                        tracing::warn!("Failed to read spilled workflow outcomes: {}", err)
+                    }
+                }
+            }
+        }
+
+        status
+    }
+
+    pub fn remove_service(&self, service_id: &ServiceId) {
+        self.inner
+            .write()
+            .unwrap()
+            .retain(|(id, _), _| id != service_id);
+    }
+}
+
+/// Appends outcomes evicted from memory to rotating JSON-lines files.
+///
+/// Disk usage is bounded by `max_disk_bytes` split across [`OutcomeSpill::MAX_FILES`] files;
+/// the oldest file is removed when a new one is started, and files or entries older than
+/// the retention window are dropped.
+pub struct OutcomeSpill {
+    dir: PathBuf,
+    max_file_bytes: u64,
+    retention: Duration,
+    /// The file currently being appended to and its size
+    current: Mutex<Option<(File, u64)>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SpilledOutcome {
+    service_id: ServiceId,
+    workflow_id: WorkflowId,
+    outcome: WorkflowOutcome,
+}
+
+impl OutcomeSpill {
+    pub const MAX_FILES: u64 = 8;
+
+    const FILE_PREFIX: &'static str = "outcomes-";
+    const FILE_SUFFIX: &'static str = ".jsonl";
+
+    pub fn new(dir: PathBuf, max_disk_bytes: u64, retention: Duration) -> std::io::Result<Self> {
+        std::fs::create_dir_all(&dir)?;
+
+        let spill = Self {
+            dir,
+            max_file_bytes: (max_disk_bytes / Self::MAX_FILES).max(1),
+            retention,
+            current: Mutex::new(None),
+        };
+        spill.prune()?;
+
+        Ok(spill)
+    }
+
+    /// `current` is the locked [`Self::current`]
+    fn append(
+        &self,
+        current: &mut Option<(File, u64)>,
+        service_id: &ServiceId,
+        workflow_id: &WorkflowId,
+        // newest first, as evicted from the in-memory buffer
+        outcomes: VecDeque<WorkflowOutcome>,
+    ) -> std::io::Result<()> {
+        // files are read back newest-last, so write the oldest outcome first
+        for outcome in outcomes.into_iter().rev() {
+            let mut line = serde_json::to_vec(&SpilledOutcome {
+                service_id: service_id.clone(),
+                workflow_id: workflow_id.clone(),
+                outcome,
+            })?;
+            line.push(b'\n');
+
+            let full = match &*current {
+                Some((_, size)) => size + line.len() as u64 > self.max_file_bytes,
+                None => true,
+            };
+
+            if full {
+                let path = self.dir.join(format!(
+                    "{}{:020}{}",
+                    Self::FILE_PREFIX,
+                    Timestamp::now().as_nanos(),
+                    Self::FILE_SUFFIX
+                ));
+                let file = OpenOptions::new().create(true).append(true).open(path)?;
+                *current = Some((file, 0));
+                self.prune()?;
+            }
+
+            if let Some((file, size)) = current.as_mut() {
+                file.write_all(&line)?;
+                *size += line.len() as u64;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Up to `limit` spilled outcomes of the workflow, newest first
+    fn read(
+        &self,
+        service_id: &ServiceId,
+        workflow_id: &WorkflowId,
+        limit: usize,
+    ) -> std::io::Result<Vec<WorkflowOutcome>> {
+        let cutoff = self.cutoff();
+        let mut outcomes = Vec::new();
+
+        for path in self.files()?.into_iter().rev() {
+            let mut file_outcomes = Vec::new();
+            for line in BufReader::new(File::open(path)?).lines() {
+                // a partially written line is skipped rather than failing the whole read
+                let Ok(spilled) = serde_json::from_str::<SpilledOutcome>(&line?) else {
+                    continue;
+                };
+                if spilled.service_id == *service_id
+                    && spilled.workflow_id == *workflow_id
+                    && spilled.outcome.timestamp.as_nanos() >= cutoff
+                {
+                    file_outcomes.push(spilled.outcome);
+                }
+            }
+
+            outcomes.extend(file_outcomes.into_iter().rev());
+            if outcomes.len() >= limit {
+                break;
+            }
+        }
+
+        outcomes.truncate(limit);
+        Ok(outcomes)
+    }
+
+    /// Spill files, oldest first
+    fn files(&self) -> std::io::Result<Vec<PathBuf>> {
+        let mut files = std::fs::read_dir(&self.dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        name.starts_with(Self::FILE_PREFIX) && name.ends_with(Self::FILE_SUFFIX)
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        // the names embed a zero-padded timestamp, so they sort chronologically
+        files.sort();
+        Ok(files)
+    }
+
+    fn prune(&self) -> std::io::Result<()> {
+        let files = self.files()?;
+        let excess = files.len().saturating_sub(Self::MAX_FILES as usize);
+
+        for (i, path) in files.iter().enumerate() {
+            // the newest file might still be written to
+            let is_newest = i + 1 == files.len();
+            let expired = !is_newest
+                && path
+                    .metadata()
+                    .and_then(|meta| meta.modified())
+                    .is_ok_and(|modified| modified.elapsed().is_ok_and(|age| age > self.retention));
+
+            if i < excess || expired {
+                std::fs::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn cutoff(&self) -> u64 {
+        Timestamp::now()
+            .as_nanos()
+            .saturating_sub(self.retention.as_nanos() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_outcomes_newest_first() {
+        let tracker = WorkflowStatusTracker::default();
+        let service_id = ServiceId::hash(b"service");
+        let workflow_id = WorkflowId::default();
+
+        tracker.record(&service_id, &workflow_id, Ok(1));
+        tracker.record(&service_id, &workflow_id, Err("boom".to_string()));
+
+        let status = tracker.get(&service_id, &workflow_id, None);
+        assert_eq!(status.total_successes, 1);
+        assert_eq!(status.total_failures, 1);
+        assert_eq!(status.recent_outcomes.len(), 2);
+        assert_eq!(status.recent_outcomes[0].error.as_deref(), Some("boom"));
+        assert!(status.recent_outcomes[1].success);
+
+        let status = tracker.get(&service_id, &workflow_id, Some(1));
+        assert_eq!(status.recent_outcomes.len(), 1);
+
+        tracker.remove_service(&service_id);
+        let status = tracker.get(&service_id, &workflow_id, None);
+        assert_eq!(status.total_successes, 0);
+        assert!(status.recent_outcomes.is_empty());
+    }
+
+    #[test]
+    fn caps_recent_outcomes() {
+        let tracker = WorkflowStatusTracker::default();
+        let service_id = ServiceId::hash(b"service");
+        let workflow_id = WorkflowId::default();
+
+        for _ in 0..WorkflowStatusTracker::MAX_RECENT_OUTCOMES + 10 {
+            tracker.record(&service_id, &workflow_id, Ok(0));
+        }
+
+        let status = tracker.get(&service_id, &workflow_id, None);
+        assert_eq!(
+            status.total_successes,
+            (WorkflowStatusTracker::MAX_RECENT_OUTCOMES + 10) as u64
+        );
+        assert_eq!(
+            status.recent_outcomes.len(),
+            WorkflowStatusTracker::MAX_RECENT_OUTCOMES
+        );
+    }
+
+    #[test]
+    fn spills_evicted_outcomes_to_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let spill = OutcomeSpill::new(
+            dir.path().to_path_buf(),
+            1024 * 1024,
+            Duration::from_secs(60),
+        )
+        .unwrap();
+        let tracker = WorkflowStatusTracker::new(Some(spill));
+        let service_id = ServiceId::hash(b"service");
+        let workflow_id = WorkflowId::default();
+
+        let total = WorkflowStatusTracker::MAX_RECENT_OUTCOMES + 10;
+        for i in 0..total {
+            tracker.record(&service_id, &workflow_id, Ok(i));
+        }
+
+        // the in-memory view is unchanged
+        let status = tracker.get(&service_id, &workflow_id, Some(total));
+        assert_eq!(
+            status.recent_outcomes.len(),
+            WorkflowStatusTracker::MAX_RECENT_OUTCOMES
+        );
+
+        // the spill continues where memory stops, still newest first
+        let status = tracker.get_with_spilled(&service_id, &workflow_id, Some(total));
+        let responses: Vec<_> = status.recent_outcomes.iter().map(|o| o.responses).collect();
+        assert_eq!(responses, (0..total).rev().collect::<Vec<_>>());
+
+        // other workflows don't see them
+        let status =
+            tracker.get_with_spilled(&service_id, &WorkflowId::new("other").unwrap(), Some(total));
+        assert!(status.recent_outcomes.is_empty());
+    }
+
+    #[test]
+    fn concurrent_evictions_spill_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let spill = OutcomeSpill::new(
+            dir.path().to_path_buf(),
+            1024 * 1024,
+            Duration::from_secs(60),
+        )
+        .unwrap();
+        let tracker = WorkflowStatusTracker::new(Some(spill));
+        let service_id = ServiceId::hash(b"service");
+        let workflow_id = WorkflowId::default();
+
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let tracker = tracker.clone();
+                let service_id = service_id.clone();
+                let workflow_id = workflow_id.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..WorkflowStatusTracker::MAX_RECENT_OUTCOMES {
+                        tracker.record(&service_id, &workflow_id, Ok(0));
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let total = 8 * WorkflowStatusTracker::MAX_RECENT_OUTCOMES;
+        let status = tracker.get_with_spilled(&service_id, &workflow_id, Some(total));
+        assert_eq!(status.recent_outcomes.len(), total);
+        // newest first all the way through, so nothing was spilled out of order
+        assert!(status
+            .recent_outcomes
+            .windows(2)
+            .all(|pair| pair[0].timestamp.as_nanos() >= pair[1].timestamp.as_nanos()));
+    }
+
+    #[test]
+    fn bounds_spill_files() {
+        let dir = tempfile::tempdir().unwrap();
+        // tiny files so every outcome starts a new one
+        let spill =
+            OutcomeSpill::new(dir.path().to_path_buf(), 1, Duration::from_secs(60)).unwrap();
+        let service_id = ServiceId::hash(b"service");
+        let workflow_id = WorkflowId::default();
+
+        for i in 0..20 {
+            let outcome = WorkflowOutcome {
+                timestamp: Timestamp::now(),
+                success: true,
+                responses: i,
+                error: None,
+            };
+            spill
+                .append(
+                    &mut spill.current.lock().unwrap(),
+                    &service_id,
+                    &workflow_id,
+                    VecDeque::from([outcome]),
+                )
+                .unwrap();
+        }
+
+        assert!(spill.files().unwrap().len() as u64 <= OutcomeSpill::MAX_FILES);
+        let outcomes = spill.read(&service_id, &workflow_id, 100).unwrap();
+        assert_eq!(outcomes.first().unwrap().responses, 19);
+    }
+}
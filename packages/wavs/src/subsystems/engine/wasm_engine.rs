@@ -1,27 +1,44 @@
 use std::sync::Arc;
 use std::time::Instant;
-use std::{path::Path, sync::RwLock};
+use std::{
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
 use tracing::{event, instrument, span};
 use utils::storage::db::WavsDb;
 use utils::telemetry::EngineMetrics;
-use wavs_engine::bindings::aggregator::world::wavs::types::chain::AnyTxHash;
+use utils::tls::ChainTls;
+use utils::wkg::oci::OciCredentials;
+use wavs_engine::bindings::aggregator::world::SubmitReceipt;
 use wavs_engine::{
-    backend::wasi_keyvalue::context::KeyValueCtx,
+    backend::{
+        evm_blocks::{EndpointBlockQuery, EvmBlocks},
+        wasi_keyvalue::context::KeyValueCtx,
+    },
     common::base_engine::{BaseEngine, BaseEngineConfig},
-    worlds::instance::{HostComponentLogger, InstanceDepsBuilder},
+    utils::{error::ExecutionOutcome, log::LogFields},
+    worlds::{
+        determinism::Determinism,
+        instance::{HostComponentLogger, InstanceDepsBuilder},
+        operator::execute::OperatorOutput,
+    },
 };
 use wavs_types::{
     AggregatorAction, AggregatorInput, ChainConfigs, ComponentDigest, ComponentSource, EventId,
-    Service, ServiceId, TriggerAction, WasmResponse, WorkflowId,
+    Service, ServiceId, SubmitAction, TriggerAction, TriggerData, WasmResponse, WorkflowId,
 };
 
 use utils::storage::CAStorage;
 
 use super::error::EngineError;
+use super::secrets::ServiceSecrets;
 
 pub struct WasmEngine<S: CAStorage> {
     engine: BaseEngine<S>,
     metrics: EngineMetrics,
+    secrets: ServiceSecrets,
+    capture_guest_output: bool,
+    max_response_bytes: Option<usize>,
 }
 
 impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
@@ -49,7 +66,61 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
 
         let engine = BaseEngine::new(config, db, Arc::new(wasm_storage)).unwrap();
 
-        Self { engine, metrics }
+        Self {
+            engine,
+            metrics,
+            secrets: ServiceSecrets::default(),
+            capture_guest_output: false,
+            max_response_bytes: None,
+        }
+    }
+
+    /// Secrets that components can read through `host::get-secret`, if their service grants them
+    pub fn with_secrets(mut self, secrets: ServiceSecrets) -> Self {
+        self.secrets = secrets;
+        self
+    }
+
+    /// Return what operator components write to stdout and stderr with their output,
+    /// it's only logged at debug otherwise
+    pub fn with_capture_guest_output(mut self, capture_guest_output: bool) -> Self {
+        self.capture_guest_output = capture_guest_output;
+        self
+    }
+
+    /// Where each service's filesystem sandbox lives, as `<root>/<service_id>`.
+    /// Sandboxes left in the app data dir by earlier versions are moved over
+    pub fn with_component_fs_root(mut self, root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        if root != self.engine.app_data_dir {
+            move_service_dirs(&self.engine.app_data_dir, &root);
+        }
+        self.engine.component_fs_root = root;
+        self
+    }
+
+    /// Fail executions that return a response payload over `limit` bytes
+    pub fn with_max_response_bytes(mut self, limit: Option<usize>) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
+    /// Logins for pulling `Oci` components, by registry host
+    pub fn with_oci_credentials(mut self, credentials: OciCredentials) -> Self {
+        self.engine.oci_credentials = credentials;
+        self
+    }
+
+    /// Pin the WASI clocks and randomness components see to the event they run for
+    pub fn with_determinism(mut self, determinism: Determinism) -> Self {
+        self.engine.determinism = determinism;
+        self
+    }
+
+    /// TLS settings for the chains `host::get-evm-block` queries
+    pub fn with_chain_tls(mut self, chain_tls: ChainTls) -> Self {
+        self.engine.evm_blocks = EvmBlocks::new(EndpointBlockQuery::new(chain_tls));
+        self
     }
 
     #[instrument(skip(self), fields(subsys = "Engine"))]
@@ -67,7 +138,9 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
             Ok(digest)
         } else {
             match source {
-                ComponentSource::Download { .. } | ComponentSource::Registry { .. } => {
+                ComponentSource::Download { .. }
+                | ComponentSource::Registry { .. }
+                | ComponentSource::Oci { .. } => {
                     // Fetches component, validates it has the expected digest, and stores it in the lookup
                     self.engine.load_component_from_source(source).await?;
                     Ok(digest)
@@ -80,6 +153,43 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
         }
     }
 
+    /// Make sure the host provides everything the component imports, given its permissions
+    #[instrument(skip(self, component), fields(subsys = "Engine"))]
+    pub async fn validate_component_imports(
+        &self,
+        component: &wavs_types::Component,
+    ) -> Result<(), EngineError> {
+        let wasm = self
+            .engine
+            .load_component(component.source.digest())
+            .await?;
+        wavs_engine::worlds::interfaces::validate_imports(
+            &self.engine.wasm_engine,
+            &wasm,
+            &component.permissions,
+        )?;
+        Ok(())
+    }
+
+    /// Compile the component into the in-memory cache, so the next execution skips compilation
+    #[instrument(skip(self), fields(subsys = "Engine"))]
+    pub async fn warmup_component(&self, digest: &ComponentDigest) -> Result<(), EngineError> {
+        self.engine.load_component(digest).await?;
+        Ok(())
+    }
+
+    /// Drop a component nothing references anymore, both compiled and stored bytes
+    #[instrument(skip(self), fields(subsys = "Engine"))]
+    pub fn remove_component(&self, digest: &ComponentDigest) -> Result<(), EngineError> {
+        self.engine.remove_component(digest)?;
+        Ok(())
+    }
+
+    /// How many compiled components fit in the in-memory cache
+    pub fn lru_size(&self) -> usize {
+        self.engine.memory_cache.lock().unwrap().cap().get()
+    }
+
     // TODO: paginate this
     #[instrument(skip(self), fields(subsys = "Engine"))]
     pub fn list_digests(&self) -> Result<Vec<ComponentDigest>, EngineError> {
@@ -92,13 +202,72 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
         Ok(digests?)
     }
 
+    /// Run the optional `init` export of a workflow's operator component.
+    /// Returns `false` if the component doesn't have one.
+    #[instrument(skip(self, service), fields(subsys = "Engine", service_id = %service.id()))]
+    pub async fn init_operator_component(
+        &self,
+        service: &Service,
+        workflow_id: &WorkflowId,
+    ) -> Result<bool, EngineError> {
+        let workflow = service
+            .workflows
+            .get(workflow_id)
+            .ok_or_else(|| EngineError::UnknownWorkflow(service.id(), workflow_id.clone()))?;
+
+        let digest = workflow.component.source.digest().clone();
+        let chain_configs = self.engine.get_chain_configs()?;
+
+        let component = self.engine.load_component(&digest).await?;
+
+        let service_id = service.id();
+
+        let mut instance_deps = InstanceDepsBuilder {
+            keyvalue_ctx: KeyValueCtx::new(self.engine.db.clone(), service_id.to_string()),
+            evm_blocks: self.engine.evm_blocks.clone(),
+            secrets: self.secrets.for_service(&service_id),
+            determinism: self.engine.determinism,
+            capture_output: self.capture_guest_output,
+            service: service.clone(),
+            workflow_id: workflow_id.clone(),
+            component,
+            // there's no trigger yet, init only gets the service and workflow through the host
+            data: wavs_engine::worlds::instance::InstanceData::new_operator(TriggerData::default()),
+            engine: &self.engine.wasm_engine,
+            data_dir: self.engine.service_fs_dir(&service_id),
+            chain_configs: &chain_configs,
+            log: HostComponentLogger::OperatorHostComponentLogger(log_operator),
+        }
+        .build()?;
+
+        let ran = wavs_engine::worlds::operator::execute::execute_init(
+            &mut instance_deps,
+            service_id.clone(),
+            workflow_id.clone(),
+        )
+        .await?;
+
+        if ran {
+            tracing::info!(
+                service_id = %service_id,
+                workflow_id = %workflow_id,
+                "Component init completed"
+            );
+        }
+
+        Ok(ran)
+    }
+
     /// This will execute a contract that implements the wavs:operator wit interface
-    #[instrument(skip(self), fields(subsys = "Engine"))]
+    #[instrument(
+        skip(self),
+        fields(subsys = "Engine", service_id = %service.id(), workflow_id = %trigger_action.config.workflow_id)
+    )]
     pub async fn execute_operator_component(
         &self,
         service: Service,
         trigger_action: TriggerAction,
-    ) -> Result<Vec<WasmResponse>, EngineError> {
+    ) -> Result<OperatorOutput, EngineError> {
         #[cfg(feature = "dev")]
         if std::env::var("WAVS_FORCE_ENGINE_ERROR_XXX").is_ok() {
             self.metrics.total_errors.add(1, &[]);
@@ -128,6 +297,10 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
 
         let mut instance_deps = InstanceDepsBuilder {
             keyvalue_ctx: KeyValueCtx::new(self.engine.db.clone(), service.id().to_string()),
+            evm_blocks: self.engine.evm_blocks.clone(),
+            secrets: self.secrets.for_service(&service.id()),
+            determinism: self.engine.determinism,
+            capture_output: self.capture_guest_output,
             service,
             workflow_id: trigger_action.config.workflow_id.clone(),
             component,
@@ -137,14 +310,13 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
             engine: &self.engine.wasm_engine,
             data_dir: self
                 .engine
-                .app_data_dir
-                .join(trigger_action.config.service_id.to_string()),
+                .service_fs_dir(&trigger_action.config.service_id),
             chain_configs: &chain_configs,
             log: HostComponentLogger::OperatorHostComponentLogger(log_operator),
         }
         .build()?;
 
-        let initial_fuel = instance_deps.store.get_fuel().unwrap_or(0);
+        let initial_fuel = instance_deps.store.get_fuel().ok();
         let start_time = Instant::now();
 
         #[cfg(feature = "dev")]
@@ -152,38 +324,72 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
             std::thread::sleep(std::time::Duration::from_secs(6));
         }
 
-        let results =
+        let mut results =
             wavs_engine::worlds::operator::execute::execute(&mut instance_deps, trigger_action)
                 .await;
-        let final_fuel = instance_deps.store.get_fuel().unwrap_or(0);
+        if let (true, Ok(output)) = (self.capture_guest_output, &mut results) {
+            output.captured_output = Some(instance_deps.output.captured());
+        }
 
         let duration = start_time.elapsed().as_secs_f64();
-        let fuel_consumed = initial_fuel.saturating_sub(final_fuel);
+        // failed runs have no output but still burned fuel, e.g. running out of it
+        let fuel_consumed = match &results {
+            Ok(output) => output.fuel_consumed,
+            Err(_) => initial_fuel.map(|initial| {
+                initial.saturating_sub(instance_deps.store.get_fuel().unwrap_or_default())
+            }),
+        };
+        let outcome = ExecutionOutcome::from_result(&results);
 
         self.metrics.record_operator_execution(
             duration,
             fuel_consumed,
             &service_id.to_string(),
             workflow_id.as_ref(),
-            results.is_ok(),
+            outcome.is_success(),
+            outcome.as_str(),
         );
 
+        log_execution_outcome(&service_id, &workflow_id, &results);
+
         tracing::info!(
             service_id = %service_id,
             workflow_id = %workflow_id,
             duration_seconds = duration,
-            fuel_consumed = fuel_consumed,
-            success = results.is_ok(),
+            fuel_consumed = ?fuel_consumed,
+            success = outcome.is_success(),
+            outcome = %outcome,
             "WASM operator execution completed"
         );
 
-        results.map_err(|e| e.into())
+        let output = results?;
+        self.check_response_sizes(&output.responses)?;
+
+        Ok(output)
+    }
+
+    /// Each response is checked on its own, before any of them is signed or submitted
+    fn check_response_sizes(&self, responses: &[WasmResponse]) -> Result<(), EngineError> {
+        let Some(limit) = self.max_response_bytes else {
+            return Ok(());
+        };
+
+        match responses
+            .iter()
+            .find(|response| response.payload.len() > limit)
+        {
+            Some(response) => Err(EngineError::ResponseTooLarge {
+                size: response.payload.len(),
+                limit,
+            }),
+            None => Ok(()),
+        }
     }
 
     /// This will execute a contract that implements the wavs:aggregator wit interface
     #[instrument(
         skip(self, service, trigger_action, operator_response),
-        fields(subsys = "Engine")
+        fields(subsys = "Engine", service_id = %service.id(), workflow_id = %trigger_action.config.workflow_id)
     )]
     pub async fn execute_aggregator_component(
         &self,
@@ -199,7 +405,7 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
             mut instance_deps,
             input,
         } = match self
-            .get_aggregator_deps(service, trigger_action, operator_response, event_id)
+            .get_aggregator_deps(service, trigger_action, operator_response, event_id, None)
             .await?
         {
             Some(deps) => deps,
@@ -219,21 +425,26 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
 
         let duration = start_time.elapsed().as_secs_f64();
         let fuel_consumed = initial_fuel.saturating_sub(final_fuel);
+        let outcome = ExecutionOutcome::from_result(&results);
 
         self.metrics.record_aggregator_execution(
             duration,
             fuel_consumed,
             &service_id.to_string(),
             workflow_id.as_ref(),
-            results.is_ok(),
+            outcome.is_success(),
+            outcome.as_str(),
         );
 
+        log_execution_outcome(&service_id, &workflow_id, &results);
+
         tracing::info!(
             service_id = %service_id,
             workflow_id = %workflow_id,
             duration_seconds = duration,
             fuel_consumed = fuel_consumed,
-            success = results.is_ok(),
+            success = outcome.is_success(),
+            outcome = %outcome,
             "WASM aggregator execution completed"
         );
 
@@ -247,7 +458,7 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
 
     #[instrument(
         skip(self, service, trigger_action, operator_response),
-        fields(subsys = "Engine")
+        fields(subsys = "Engine", service_id = %service.id(), workflow_id = %trigger_action.config.workflow_id)
     )]
     pub async fn execute_aggregator_component_timer_callback(
         &self,
@@ -263,7 +474,7 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
             mut instance_deps,
             input,
         } = match self
-            .get_aggregator_deps(service, trigger_action, operator_response, event_id)
+            .get_aggregator_deps(service, trigger_action, operator_response, event_id, None)
             .await?
         {
             Some(deps) => deps,
@@ -284,21 +495,26 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
 
         let duration = start_time.elapsed().as_secs_f64();
         let fuel_consumed = initial_fuel.saturating_sub(final_fuel);
+        let outcome = ExecutionOutcome::from_result(&results);
 
         self.metrics.record_aggregator_execution(
             duration,
             fuel_consumed,
             &service_id.to_string(),
             workflow_id.as_ref(),
-            results.is_ok(),
+            outcome.is_success(),
+            outcome.as_str(),
         );
 
+        log_execution_outcome(&service_id, &workflow_id, &results);
+
         tracing::info!(
             service_id = %service_id,
             workflow_id = %workflow_id,
             duration_seconds = duration,
             fuel_consumed = fuel_consumed,
-            success = results.is_ok(),
+            success = outcome.is_success(),
+            outcome = %outcome,
             "WASM aggregator timer callback execution completed"
         );
 
@@ -312,14 +528,15 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
 
     #[instrument(
         skip(self, service, trigger_action, operator_response),
-        fields(subsys = "Engine")
+        fields(subsys = "Engine", service_id = %service.id(), workflow_id = %trigger_action.config.workflow_id)
     )]
     pub async fn execute_aggregator_component_submit_callback(
         &self,
         service: Service,
         trigger_action: TriggerAction,
         operator_response: WasmResponse,
-        tx_result: Result<AnyTxHash, String>,
+        submit_action: SubmitAction,
+        tx_result: Result<SubmitReceipt, String>,
         event_id: EventId,
     ) -> Result<(), EngineError> {
         let service_id = service.id();
@@ -329,7 +546,13 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
             mut instance_deps,
             input,
         } = match self
-            .get_aggregator_deps(service, trigger_action, operator_response, event_id)
+            .get_aggregator_deps(
+                service,
+                trigger_action,
+                operator_response,
+                event_id,
+                Some(submit_action),
+            )
             .await?
         {
             Some(deps) => deps,
@@ -351,21 +574,26 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
 
         let duration = start_time.elapsed().as_secs_f64();
         let fuel_consumed = initial_fuel.saturating_sub(final_fuel);
+        let outcome = ExecutionOutcome::from_result(&result);
 
         self.metrics.record_aggregator_execution(
             duration,
             fuel_consumed,
             &service_id.to_string(),
             workflow_id.as_ref(),
-            result.is_ok(),
+            outcome.is_success(),
+            outcome.as_str(),
         );
 
+        log_execution_outcome(&service_id, &workflow_id, &result);
+
         tracing::info!(
             service_id = %service_id,
             workflow_id = %workflow_id,
             duration_seconds = duration,
             fuel_consumed = fuel_consumed,
-            success = result.is_ok(),
+            success = outcome.is_success(),
+            outcome = %outcome,
             "WASM aggregator submit callback execution completed"
         );
 
@@ -379,6 +607,7 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
         trigger_action: TriggerAction,
         operator_response: WasmResponse,
         event_id: EventId,
+        submit_action: Option<SubmitAction>,
     ) -> Result<Option<AggregatorDeps>, EngineError> {
         #[cfg(feature = "dev")]
         if std::env::var("WAVS_FORCE_ENGINE_ERROR_XXX").is_ok() {
@@ -411,16 +640,24 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
 
         let component = self.engine.load_component(&digest).await?;
 
+        let mut data = wavs_engine::worlds::instance::InstanceData::new_aggregator(event_id);
+        if let Some(submit_action) = submit_action {
+            data = data.with_submit_action(submit_action);
+        }
+
         let instance_deps = InstanceDepsBuilder {
             keyvalue_ctx: KeyValueCtx::new(self.engine.db.clone(), service.id().to_string()),
+            evm_blocks: self.engine.evm_blocks.clone(),
+            secrets: self.secrets.for_service(&service.id()),
+            determinism: self.engine.determinism,
+            capture_output: self.capture_guest_output,
             workflow_id: trigger_action.config.workflow_id.clone(),
             component,
-            data: wavs_engine::worlds::instance::InstanceData::new_aggregator(event_id),
+            data,
             engine: &self.engine.wasm_engine,
             data_dir: self
                 .engine
-                .app_data_dir
-                .join(trigger_action.config.service_id.to_string()),
+                .service_fs_dir(&trigger_action.config.service_id),
             chain_configs: &chain_configs,
             log: HostComponentLogger::AggregatorHostComponentLogger(log_aggregator),
             service,
@@ -444,7 +681,7 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
 
     #[instrument(skip(self), fields(subsys = "Engine", service_id = %service_id))]
     pub fn remove_storage(&self, service_id: &ServiceId) {
-        let dir_path = self.engine.app_data_dir.join(service_id.to_string());
+        let dir_path = self.engine.service_fs_dir(service_id);
 
         if dir_path.exists() {
             match std::fs::remove_dir_all(&dir_path) {
@@ -461,17 +698,81 @@ impl<S: CAStorage + Send + Sync + 'static> WasmEngine<S> {
     }
 }
 
+/// Moves the per-service directories in `from` to `to`, unless already there
+fn move_service_dirs(from: &Path, to: &Path) {
+    let Ok(entries) = std::fs::read_dir(from) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let is_service_dir = entry.path().is_dir()
+            && name
+                .to_str()
+                .is_some_and(|name| name.parse::<ServiceId>().is_ok());
+        let target = to.join(&name);
+        if !is_service_dir || target.exists() {
+            continue;
+        }
+
+        let moved =
+            std::fs::create_dir_all(to).and_then(|_| std::fs::rename(entry.path(), &target));
+        match moved {
+            Ok(()) => tracing::info!("Moved component storage {:?} to {:?}", entry.path(), target),
+            Err(e) => tracing::error!(
+                "Failed to move component storage {:?} to {:?}: {}",
+                entry.path(),
+                target,
+                e
+            ),
+        }
+    }
+}
+
 struct AggregatorDeps {
     instance_deps: wavs_engine::worlds::instance::InstanceDeps,
     input: AggregatorInput,
 }
 
+/// A clean `Err` returned by the component is expected application behavior,
+/// whereas a trap or host failure indicates a bug that needs attention
+fn log_execution_outcome<T>(
+    service_id: &ServiceId,
+    workflow_id: &WorkflowId,
+    result: &Result<T, wavs_engine::utils::error::EngineError>,
+) {
+    let Err(err) = result else {
+        return;
+    };
+
+    match err.outcome() {
+        ExecutionOutcome::Success => {}
+        ExecutionOutcome::ApplicationError => {
+            tracing::warn!(
+                service_id = %service_id,
+                workflow_id = %workflow_id,
+                outcome = %ExecutionOutcome::ApplicationError,
+                "Component returned an error: {}", err
+            );
+        }
+        outcome => {
+            tracing::error!(
+                service_id = %service_id,
+                workflow_id = %workflow_id,
+                outcome = %outcome,
+                "Component execution failed: {}", err
+            );
+        }
+    }
+}
+
 fn log_operator(
     service_id: &ServiceId,
     workflow_id: &WorkflowId,
     digest: &ComponentDigest,
     level: wavs_engine::bindings::operator::world::host::LogLevel,
     message: String,
+    fields: &[(String, String)],
 ) {
     let span = span!(
         tracing::Level::INFO,
@@ -481,21 +782,24 @@ fn log_operator(
         digest = %digest
     );
 
+    // a component's fields are recorded together, since tracing field names must be known up front
+    let fields = (!fields.is_empty()).then(|| tracing::field::display(LogFields(fields)));
+
     match level {
         wavs_engine::bindings::operator::world::host::LogLevel::Error => {
-            event!(parent: &span, tracing::Level::ERROR, "{}", message)
+            event!(parent: &span, tracing::Level::ERROR, fields, "{}", message)
         }
         wavs_engine::bindings::operator::world::host::LogLevel::Warn => {
-            event!(parent: &span, tracing::Level::WARN, "{}", message)
+            event!(parent: &span, tracing::Level::WARN, fields, "{}", message)
         }
         wavs_engine::bindings::operator::world::host::LogLevel::Info => {
-            event!(parent: &span, tracing::Level::INFO, "{}", message)
+            event!(parent: &span, tracing::Level::INFO, fields, "{}", message)
         }
         wavs_engine::bindings::operator::world::host::LogLevel::Debug => {
-            event!(parent: &span, tracing::Level::DEBUG, "{}", message)
+            event!(parent: &span, tracing::Level::DEBUG, fields, "{}", message)
         }
         wavs_engine::bindings::operator::world::host::LogLevel::Trace => {
-            event!(parent: &span, tracing::Level::TRACE, "{}", message)
+            event!(parent: &span, tracing::Level::TRACE, fields, "{}", message)
         }
     }
 }
@@ -506,6 +810,7 @@ fn log_aggregator(
     digest: &ComponentDigest,
     level: wavs_engine::bindings::aggregator::world::host::LogLevel,
     message: String,
+    fields: &[(String, String)],
 ) {
     let span = span!(
         tracing::Level::INFO,
@@ -515,21 +820,24 @@ fn log_aggregator(
         digest = %digest
     );
 
+    // a component's fields are recorded together, since tracing field names must be known up front
+    let fields = (!fields.is_empty()).then(|| tracing::field::display(LogFields(fields)));
+
     match level {
         wavs_engine::bindings::aggregator::world::host::LogLevel::Error => {
-            event!(parent: &span, tracing::Level::ERROR, "{}", message)
+            event!(parent: &span, tracing::Level::ERROR, fields, "{}", message)
         }
         wavs_engine::bindings::aggregator::world::host::LogLevel::Warn => {
-            event!(parent: &span, tracing::Level::WARN, "{}", message)
+            event!(parent: &span, tracing::Level::WARN, fields, "{}", message)
         }
         wavs_engine::bindings::aggregator::world::host::LogLevel::Info => {
-            event!(parent: &span, tracing::Level::INFO, "{}", message)
+            event!(parent: &span, tracing::Level::INFO, fields, "{}", message)
         }
         wavs_engine::bindings::aggregator::world::host::LogLevel::Debug => {
-            event!(parent: &span, tracing::Level::DEBUG, "{}", message)
+            event!(parent: &span, tracing::Level::DEBUG, fields, "{}", message)
         }
         wavs_engine::bindings::aggregator::world::host::LogLevel::Trace => {
-            event!(parent: &span, tracing::Level::TRACE, "{}", message)
+            event!(parent: &span, tracing::Level::TRACE, fields, "{}", message)
         }
     }
 }
@@ -591,6 +899,161 @@ pub mod tests {
         assert_eq!(digests, expected);
     }
 
+    #[tokio::test]
+    async fn warmup_populates_component_cache() {
+        let storage = MemoryStorage::new();
+        let app_data = tempfile::tempdir().unwrap();
+
+        let engine = WasmEngine::new(
+            storage,
+            &app_data,
+            3,
+            mock_chain_configs(),
+            None,
+            None,
+            metrics(),
+            WavsDb::new().unwrap(),
+            DEFAULT_IPFS_GATEWAY.to_owned(),
+        );
+        assert_eq!(engine.lru_size(), 3);
+
+        let digest = engine
+            .store_component_bytes(COMPONENT_ECHO_DATA_BYTES)
+            .unwrap();
+        // simulate a restart, where the component is only in storage
+        engine.engine.memory_cache.lock().unwrap().clear();
+        assert!(!engine.engine.memory_cache.lock().unwrap().contains(&digest));
+
+        engine.warmup_component(&digest).await.unwrap();
+        assert!(engine.engine.memory_cache.lock().unwrap().contains(&digest));
+
+        // unknown digests are reported, not silently skipped
+        engine
+            .warmup_component(&ComponentDigest::hash(b"missing"))
+            .await
+            .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn compiled_components_are_reused() {
+        let app_data = tempfile::tempdir().unwrap();
+        let new_engine = || {
+            WasmEngine::new(
+                MemoryStorage::new(),
+                &app_data,
+                3,
+                mock_chain_configs(),
+                None,
+                None,
+                metrics(),
+                WavsDb::new().unwrap(),
+                DEFAULT_IPFS_GATEWAY.to_owned(),
+            )
+        };
+
+        let engine = new_engine();
+        let digest = engine
+            .store_component_bytes(COMPONENT_ECHO_DATA_BYTES)
+            .unwrap();
+        assert_eq!(engine.engine.compilations(), 1);
+
+        let service = wavs_types::Service {
+            name: "Cached Service".to_string(),
+            workflows: BTreeMap::from([(
+                WorkflowId::default(),
+                Workflow {
+                    trigger: Trigger::Manual,
+                    component: wavs_types::Component::new(ComponentSource::Digest(digest.clone())),
+                    submit: Submit::None,
+                    submit_targets: Vec::new(),
+                    response_mapping: Default::default(),
+                    submit_condition: None,
+                    priority: Default::default(),
+                    observe_only: false,
+                    min_interval_ms: None,
+                },
+            )]),
+            status: wavs_types::ServiceStatus::Active,
+            manager: wavs_types::ServiceManager::Evm {
+                chain: "evm:anvil".parse().unwrap(),
+                address: Default::default(),
+            },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
+        };
+        let action = TriggerAction {
+            config: TriggerConfig {
+                service_id: service.id(),
+                workflow_id: WorkflowId::default(),
+                trigger: Trigger::Manual,
+            },
+            data: TriggerData::new_raw(br#"{"x":12}"#),
+        };
+
+        // served from memory
+        engine
+            .execute_operator_component(service.clone(), action.clone())
+            .await
+            .unwrap();
+        // evicted from memory, deserialized from disk
+        engine.engine.memory_cache.lock().unwrap().clear();
+        engine
+            .execute_operator_component(service.clone(), action.clone())
+            .await
+            .unwrap();
+        assert_eq!(engine.engine.compilations(), 1);
+
+        // a restarted node finds the compiled component on disk
+        let restarted = new_engine();
+        restarted
+            .store_component_bytes(COMPONENT_ECHO_DATA_BYTES)
+            .unwrap();
+        let results = restarted
+            .execute_operator_component(service, action)
+            .await
+            .unwrap();
+        assert_eq!(&results.responses[0].payload, br#"{"x":12}"#);
+        assert_eq!(restarted.engine.compilations(), 0);
+    }
+
+    #[test]
+    fn stale_precompiled_dirs_are_removed() {
+        let app_data = tempfile::tempdir().unwrap();
+        let precompiled_root = app_data.path().join("precompiled");
+        let stale_dir = precompiled_root.join("0000000000000000");
+        std::fs::create_dir_all(&stale_dir).unwrap();
+        std::fs::write(stale_dir.join("old.cwasm"), b"old").unwrap();
+
+        let new_engine = || {
+            WasmEngine::new(
+                MemoryStorage::new(),
+                &app_data,
+                3,
+                mock_chain_configs(),
+                None,
+                None,
+                metrics(),
+                WavsDb::new().unwrap(),
+                DEFAULT_IPFS_GATEWAY.to_owned(),
+            )
+        };
+        let dirs = || {
+            std::fs::read_dir(&precompiled_root)
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .collect::<Vec<_>>()
+        };
+
+        new_engine();
+        let current = dirs();
+        assert_eq!(current.len(), 1);
+        assert_ne!(current[0], stale_dir);
+
+        // the same wasmtime always lands in the same directory
+        new_engine();
+        assert_eq!(dirs(), current);
+    }
+
     #[test]
     fn reject_invalid_wasm() {
         let storage = MemoryStorage::new();
@@ -649,6 +1112,12 @@ pub mod tests {
             trigger: Trigger::evm_contract_event(rand_address_evm(), "evm:anvil", rand_event_evm()),
             component: wavs_types::Component::new(ComponentSource::Digest(digest.clone())),
             submit: Submit::None,
+            submit_targets: Vec::new(),
+            response_mapping: Default::default(),
+            submit_condition: None,
+            priority: Default::default(),
+            observe_only: false,
+            min_interval_ms: None,
         };
 
         let service = wavs_types::Service {
@@ -659,6 +1128,8 @@ pub mod tests {
                 chain: "evm:anvil".parse().unwrap(),
                 address: Default::default(),
             },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
         };
 
         let service_id = service.id();
@@ -678,7 +1149,239 @@ pub mod tests {
             .await
             .unwrap();
 
-        assert_eq!(&results[0].payload, br#"{"x":12}"#);
+        assert_eq!(&results.responses[0].payload, br#"{"x":12}"#);
+        assert!(results.fuel_consumed.is_some_and(|fuel| fuel > 0));
+    }
+
+    #[tokio::test]
+    async fn execute_response_too_large() {
+        let storage = MemoryStorage::new();
+        let app_data = tempfile::tempdir().unwrap();
+        let engine = WasmEngine::new(
+            storage,
+            &app_data,
+            3,
+            mock_chain_configs(),
+            None,
+            None,
+            metrics(),
+            WavsDb::new().unwrap(),
+            DEFAULT_IPFS_GATEWAY.to_owned(),
+        )
+        .with_max_response_bytes(Some(1024));
+
+        let digest = engine
+            .store_component_bytes(COMPONENT_ECHO_DATA_BYTES)
+            .unwrap();
+        let service = wavs_types::Service::new_simple(
+            Some("Exec Service".to_string()),
+            Trigger::Manual,
+            ComponentSource::Digest(digest),
+            Submit::None,
+            wavs_types::ServiceManager::Evm {
+                chain: "evm:anvil".parse().unwrap(),
+                address: Default::default(),
+            },
+        );
+        let trigger_action = |payload: Vec<u8>| TriggerAction {
+            config: TriggerConfig {
+                service_id: service.id(),
+                workflow_id: WorkflowId::default(),
+                trigger: Trigger::Manual,
+            },
+            data: TriggerData::new_raw(payload),
+        };
+
+        // right at the limit is fine
+        let results = engine
+            .execute_operator_component(service.clone(), trigger_action(vec![b'x'; 1024]))
+            .await
+            .unwrap();
+        assert_eq!(results.responses[0].payload.len(), 1024);
+
+        let err = engine
+            .execute_operator_component(service.clone(), trigger_action(vec![b'x'; 4096]))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            EngineError::ResponseTooLarge {
+                size: 4096,
+                limit: 1024
+            }
+        ));
+    }
+
+    // a minimal component exporting only `init: func() -> result<_, string>`,
+    // `result` is the flattened return value written at offset 0
+    fn init_component_wat(result: &str) -> String {
+        format!(
+            r#"(component
+                (core module $m
+                    (memory (export "memory") 1)
+                    (data (i32.const 0) "{result}")
+                    (func (export "init") (result i32) i32.const 0))
+                (core instance $i (instantiate $m))
+                (func (export "init") (result (result (error string)))
+                    (canon lift (core func $i "init") (memory $i "memory"))))"#
+        )
+    }
+
+    #[tokio::test]
+    async fn init_hook_runs_and_reports_failures() {
+        let storage = MemoryStorage::new();
+        let app_data = tempfile::tempdir().unwrap();
+        let engine = WasmEngine::new(
+            storage,
+            &app_data,
+            3,
+            mock_chain_configs(),
+            None,
+            None,
+            metrics(),
+            WavsDb::new().unwrap(),
+            DEFAULT_IPFS_GATEWAY.to_owned(),
+        );
+
+        let service_for = |digest: ComponentDigest| wavs_types::Service {
+            name: "Init Service".to_string(),
+            workflows: BTreeMap::from([(
+                WorkflowId::default(),
+                Workflow {
+                    trigger: Trigger::Manual,
+                    component: wavs_types::Component::new(ComponentSource::Digest(digest)),
+                    submit: Submit::None,
+                    submit_targets: Vec::new(),
+                    response_mapping: Default::default(),
+                    submit_condition: None,
+                    priority: Default::default(),
+                    observe_only: false,
+                    min_interval_ms: None,
+                },
+            )]),
+            status: wavs_types::ServiceStatus::Active,
+            manager: wavs_types::ServiceManager::Evm {
+                chain: "evm:anvil".parse().unwrap(),
+                address: Default::default(),
+            },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
+        };
+
+        // components without the export are left alone
+        let digest = engine
+            .store_component_bytes(COMPONENT_ECHO_DATA_BYTES)
+            .unwrap();
+        let ran = engine
+            .init_operator_component(&service_for(digest), &WorkflowId::default())
+            .await
+            .unwrap();
+        assert!(!ran);
+
+        // ok: discriminant 0
+        let digest = engine
+            .store_component_bytes(init_component_wat("\\00").as_bytes())
+            .unwrap();
+        let ran = engine
+            .init_operator_component(&service_for(digest), &WorkflowId::default())
+            .await
+            .unwrap();
+        assert!(ran);
+
+        // err: discriminant 1, then a pointer to "boom" and its length
+        let digest = engine
+            .store_component_bytes(
+                init_component_wat(
+                    "\\01\\00\\00\\00\\10\\00\\00\\00\\04\\00\\00\\00\\00\\00\\00\\00boom",
+                )
+                .as_bytes(),
+            )
+            .unwrap();
+        let err = engine
+            .init_operator_component(&service_for(digest), &WorkflowId::default())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("boom"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn memory_limit_exceeded() {
+        let storage = MemoryStorage::new();
+        let app_data = tempfile::tempdir().unwrap();
+        let engine = WasmEngine::new(
+            storage,
+            &app_data,
+            3,
+            mock_chain_configs(),
+            None,
+            None,
+            metrics(),
+            WavsDb::new().unwrap(),
+            DEFAULT_IPFS_GATEWAY.to_owned(),
+        );
+
+        // grows memory one page at a time until growing fails
+        let digest = engine
+            .store_component_bytes(
+                r#"(component
+                    (core module $m
+                        (memory (export "memory") 1)
+                        (func (export "init") (result i32)
+                            (loop $grow
+                                (br_if $grow
+                                    (i32.ne (memory.grow (i32.const 1)) (i32.const -1))))
+                            i32.const 0))
+                    (core instance $i (instantiate $m))
+                    (func (export "init") (result (result (error string)))
+                        (canon lift (core func $i "init") (memory $i "memory"))))"#
+                    .as_bytes(),
+            )
+            .unwrap();
+
+        let limit = 16 * 65536;
+        let mut component = wavs_types::Component::new(ComponentSource::Digest(digest));
+        component.max_memory_bytes = Some(limit);
+
+        let service = wavs_types::Service {
+            name: "Memory Hog".to_string(),
+            workflows: BTreeMap::from([(
+                WorkflowId::default(),
+                Workflow {
+                    trigger: Trigger::Manual,
+                    component,
+                    submit: Submit::None,
+                    submit_targets: Vec::new(),
+                    response_mapping: Default::default(),
+                    submit_condition: None,
+                    priority: Default::default(),
+                    observe_only: false,
+                    min_interval_ms: None,
+                },
+            )]),
+            status: wavs_types::ServiceStatus::Active,
+            manager: wavs_types::ServiceManager::Evm {
+                chain: "evm:anvil".parse().unwrap(),
+                address: Default::default(),
+            },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
+        };
+
+        let err = engine
+            .init_operator_component(&service, &WorkflowId::default())
+            .await
+            .unwrap_err();
+
+        match err {
+            EngineError::Engine(wavs_engine::utils::error::EngineError::MemoryLimitExceeded {
+                service_id,
+                limit: hit,
+            }) => {
+                assert_eq!(service_id, service.id());
+                assert_eq!(hit, limit);
+            }
+            other => panic!("expected a memory limit error, got {other:?}"),
+        }
     }
 
     #[tokio::test]
@@ -707,6 +1410,12 @@ pub mod tests {
             trigger: Trigger::Manual,
             component: wavs_types::Component::new(ComponentSource::Digest(digest.clone())),
             submit: Submit::None,
+            submit_targets: Vec::new(),
+            response_mapping: Default::default(),
+            submit_condition: None,
+            priority: Default::default(),
+            observe_only: false,
+            min_interval_ms: None,
         };
 
         workflow.component.env_keys = ["WAVS_ENV_TEST".to_string()].into_iter().collect();
@@ -720,6 +1429,8 @@ pub mod tests {
                 chain: "evm:anvil".parse().unwrap(),
                 address: Default::default(),
             },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
         };
 
         let service_id = service.id();
@@ -740,7 +1451,7 @@ pub mod tests {
             .await
             .unwrap();
 
-        assert_eq!(&results[0].payload, br#"bar"#);
+        assert_eq!(&results.responses[0].payload, br#"bar"#);
 
         // verify whitelisted host env var is accessible
         let results = engine
@@ -758,7 +1469,7 @@ pub mod tests {
             .await
             .unwrap();
 
-        assert_eq!(&results[0].payload, br#"testing"#);
+        assert_eq!(&results.responses[0].payload, br#"testing"#);
 
         // verify the non-enabled env var is not accessible
         let result = engine
@@ -782,6 +1493,98 @@ pub mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn execute_granted_secrets_only() {
+        let storage = MemoryStorage::new();
+        let app_data = tempfile::tempdir().unwrap();
+        let engine = WasmEngine::new(
+            storage,
+            &app_data,
+            3,
+            mock_chain_configs(),
+            None,
+            None,
+            metrics(),
+            WavsDb::new().unwrap(),
+            DEFAULT_IPFS_GATEWAY.to_owned(),
+        );
+
+        std::env::set_var("WAVS_ENV_SECRETS_TEST", "from-env");
+
+        let digest = engine
+            .store_component_bytes(COMPONENT_ECHO_DATA_BYTES)
+            .unwrap();
+        let mut workflow = Workflow {
+            trigger: Trigger::Manual,
+            component: wavs_types::Component::new(ComponentSource::Digest(digest.clone())),
+            submit: Submit::None,
+            submit_targets: Vec::new(),
+            response_mapping: Default::default(),
+            submit_condition: None,
+            priority: Default::default(),
+            observe_only: false,
+            min_interval_ms: None,
+        };
+
+        workflow.component.secrets = ["API_KEY".to_string()].into_iter().collect();
+        // granting secrets takes away env access, even for allowed keys
+        workflow.component.env_keys = ["WAVS_ENV_SECRETS_TEST".to_string()].into_iter().collect();
+
+        let service = wavs_types::Service {
+            name: "Secrets Service".to_string(),
+            workflows: BTreeMap::from([(WorkflowId::default(), workflow)]),
+            status: wavs_types::ServiceStatus::Active,
+            manager: wavs_types::ServiceManager::Evm {
+                chain: "evm:anvil".parse().unwrap(),
+                address: Default::default(),
+            },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
+        };
+
+        let service_id = service.id();
+
+        let engine = engine.with_secrets(
+            [(
+                service_id.clone(),
+                BTreeMap::from([
+                    ("API_KEY".to_string(), "granted".to_string()),
+                    ("DB_PASSWORD".to_string(), "not-granted".to_string()),
+                ]),
+            )]
+            .into_iter()
+            .collect(),
+        );
+
+        let run = |input: &'static [u8]| {
+            engine.execute_operator_component(
+                service.clone(),
+                TriggerAction {
+                    config: TriggerConfig {
+                        service_id: service_id.clone(),
+                        workflow_id: WorkflowId::default(),
+                        trigger: Trigger::Manual,
+                    },
+                    data: TriggerData::new_raw(input),
+                },
+            )
+        };
+
+        let results = run(b"secret:API_KEY".as_slice()).await.unwrap();
+        assert_eq!(&results.responses[0].payload, b"granted");
+
+        for input in [
+            b"secret:DB_PASSWORD".as_slice(),
+            b"envvar:WAVS_ENV_SECRETS_TEST".as_slice(),
+        ] {
+            let result = run(input).await.unwrap_err();
+            assert!(matches!(
+                result,
+                EngineError::Engine(wavs_engine::utils::error::EngineError::ExecResult(_))
+            ));
+        }
+    }
+
     #[tokio::test]
     async fn execute_custom_event_id() {
         let storage = MemoryStorage::new();
@@ -805,6 +1608,12 @@ pub mod tests {
             trigger: Trigger::Manual,
             component: wavs_types::Component::new(ComponentSource::Digest(digest.clone())),
             submit: Submit::None,
+            submit_targets: Vec::new(),
+            response_mapping: Default::default(),
+            submit_condition: None,
+            priority: Default::default(),
+            observe_only: false,
+            min_interval_ms: None,
         };
 
         workflow.component.config =
@@ -818,6 +1627,8 @@ pub mod tests {
                 chain: "evm:anvil".parse().unwrap(),
                 address: Default::default(),
             },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
         };
 
         let service_id = service.id();
@@ -838,7 +1649,7 @@ pub mod tests {
             .unwrap();
 
         assert_eq!(
-            results[0].event_id_salt.as_ref().unwrap(),
+            results.responses[0].event_id_salt.as_ref().unwrap(),
             "hello world!".as_bytes()
         );
     }
@@ -866,6 +1677,12 @@ pub mod tests {
             trigger: Trigger::Manual,
             component: wavs_types::Component::new(ComponentSource::Digest(digest.clone())),
             submit: Submit::None,
+            submit_targets: Vec::new(),
+            response_mapping: Default::default(),
+            submit_condition: None,
+            priority: Default::default(),
+            observe_only: false,
+            min_interval_ms: None,
         };
 
         workflow.component.config = [
@@ -883,6 +1700,8 @@ pub mod tests {
                 chain: "evm:anvil".parse().unwrap(),
                 address: Default::default(),
             },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
         };
 
         let service_id = service.id();
@@ -903,15 +1722,15 @@ pub mod tests {
             .unwrap();
 
         assert_eq!(
-            results[0].event_id_salt.as_ref().unwrap(),
+            results.responses[0].event_id_salt.as_ref().unwrap(),
             "hello world 1!".as_bytes()
         );
         assert_eq!(
-            results[1].event_id_salt.as_ref().unwrap(),
+            results.responses[1].event_id_salt.as_ref().unwrap(),
             "hello world 2!".as_bytes()
         );
 
-        engine
+        let err = engine
             .execute_operator_component(
                 service.clone(),
                 TriggerAction {
@@ -925,6 +1744,15 @@ pub mod tests {
             )
             .await
             .unwrap_err();
+
+        // mixing salted and unsalted responses is rejected outright, nothing gets submitted
+        assert!(matches!(
+            err,
+            EngineError::Engine(wavs_engine::utils::error::EngineError::MissingEventIdSalt {
+                responses: 2,
+                missing: 1
+            })
+        ));
     }
 
     #[tokio::test]
@@ -952,6 +1780,12 @@ pub mod tests {
             trigger: Trigger::Manual,
             component: wavs_types::Component::new(ComponentSource::Digest(digest.clone())),
             submit: Submit::None,
+            submit_targets: Vec::new(),
+            response_mapping: Default::default(),
+            submit_condition: None,
+            priority: Default::default(),
+            observe_only: false,
+            min_interval_ms: None,
         };
 
         workflow.component.fuel_limit = Some(low_fuel_limit);
@@ -964,6 +1798,8 @@ pub mod tests {
                 chain: "evm:anvil".parse().unwrap(),
                 address: Default::default(),
             },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
         };
 
         let service_id = service.id();
@@ -1042,6 +1878,135 @@ pub mod tests {
         assert!(!nonexistent_dir.exists());
     }
 
+    #[tokio::test]
+    async fn services_have_separate_filesystems() {
+        let app_data = tempfile::tempdir().unwrap();
+        let fs_root = tempfile::tempdir().unwrap();
+        let engine = WasmEngine::new(
+            MemoryStorage::new(),
+            &app_data,
+            3,
+            mock_chain_configs(),
+            None,
+            None,
+            metrics(),
+            WavsDb::new().unwrap(),
+            DEFAULT_IPFS_GATEWAY.to_owned(),
+        )
+        .with_component_fs_root(fs_root.path());
+
+        let digest = engine
+            .store_component_bytes(COMPONENT_PERMISSIONS_BYTES)
+            .unwrap();
+
+        // the component creates `./responses/<timestamp>.txt` before making any request,
+        // which is denied so the test stays offline
+        let service = |name: &str| {
+            let mut component = wavs_types::Component::new(ComponentSource::Digest(digest.clone()));
+            component.permissions.file_system = true;
+            component.permissions.allowed_http_hosts =
+                wavs_types::AllowedHostPermission::Only(vec!["allowed.example".to_string()]);
+            wavs_types::Service {
+                name: name.to_string(),
+                workflows: BTreeMap::from([(
+                    WorkflowId::default(),
+                    Workflow {
+                        trigger: Trigger::Manual,
+                        component,
+                        submit: Submit::None,
+                        submit_targets: Vec::new(),
+                        response_mapping: Default::default(),
+                        submit_condition: None,
+                        priority: Default::default(),
+                        observe_only: false,
+                        min_interval_ms: None,
+                    },
+                )]),
+                status: wavs_types::ServiceStatus::Active,
+                manager: wavs_types::ServiceManager::Evm {
+                    chain: "evm:anvil".parse().unwrap(),
+                    address: Default::default(),
+                },
+                max_concurrent_executions: None,
+                execution_timeout_secs: None,
+            }
+        };
+        let request = example_types::PermissionsRequest {
+            get_url: "https://postman-echo.com/get".to_string(),
+            post_url: "https://postman-echo.com/post".to_string(),
+            post_data: ("hello".to_string(), "world".to_string()),
+            timestamp: 1,
+        };
+        let run = |service: wavs_types::Service| {
+            let action = TriggerAction {
+                config: TriggerConfig {
+                    service_id: service.id(),
+                    workflow_id: WorkflowId::default(),
+                    trigger: Trigger::Manual,
+                },
+                data: TriggerData::Raw(serde_json::to_vec(&request).unwrap()),
+            };
+            engine.execute_operator_component(service, action)
+        };
+
+        let service_a = service("Service A");
+        let service_b = service("Service B");
+        let file_a = fs_root
+            .path()
+            .join(service_a.id().to_string())
+            .join("responses/1.txt");
+        let file_b = fs_root
+            .path()
+            .join(service_b.id().to_string())
+            .join("responses/1.txt");
+
+        run(service_a.clone()).await.unwrap_err();
+        assert!(file_a.exists());
+        assert!(!file_b.exists());
+        std::fs::write(&file_a, "service a").unwrap();
+
+        // the same filename written by another service lands in its own directory
+        run(service_b).await.unwrap_err();
+        assert!(file_b.exists());
+        assert_eq!(std::fs::read_to_string(&file_a).unwrap(), "service a");
+
+        // and nothing is written outside the sandboxes
+        assert!(!fs_root.path().join("responses").exists());
+        assert!(!app_data.path().join(service_a.id().to_string()).exists());
+    }
+
+    #[test]
+    fn legacy_service_dirs_are_moved() {
+        let app_data = tempfile::tempdir().unwrap();
+        let fs_root = tempfile::tempdir().unwrap();
+        let service_id = ServiceId::hash(b"test-service");
+        let legacy_dir = app_data.path().join(service_id.to_string());
+        std::fs::create_dir_all(&legacy_dir).unwrap();
+        std::fs::write(legacy_dir.join("data.txt"), "kept").unwrap();
+
+        let engine = WasmEngine::new(
+            MemoryStorage::new(),
+            &app_data,
+            3,
+            mock_chain_configs(),
+            None,
+            None,
+            metrics(),
+            WavsDb::new().unwrap(),
+            DEFAULT_IPFS_GATEWAY.to_owned(),
+        )
+        .with_component_fs_root(fs_root.path());
+
+        assert!(!legacy_dir.exists());
+        assert_eq!(
+            std::fs::read_to_string(engine.engine.service_fs_dir(&service_id).join("data.txt"))
+                .unwrap(),
+            "kept"
+        );
+        // the compiled component cache stays where it was
+        assert!(app_data.path().join("precompiled").exists());
+    }
+
     #[tokio::test]
     async fn execute_with_low_time_limit() {
         let storage = MemoryStorage::new();
@@ -1065,6 +2030,12 @@ pub mod tests {
             trigger: Trigger::Manual,
             component: wavs_types::Component::new(ComponentSource::Digest(digest.clone())),
             submit: Submit::None,
+            submit_targets: Vec::new(),
+            response_mapping: Default::default(),
+            submit_condition: None,
+            priority: Default::default(),
+            observe_only: false,
+            min_interval_ms: None,
         };
 
         // first, check that it works with enough time and async sleep
@@ -1086,6 +2057,8 @@ pub mod tests {
                 chain: "evm:anvil".parse().unwrap(),
                 address: Default::default(),
             },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
         };
 
         let service_id = service.id();
@@ -1124,6 +2097,8 @@ pub mod tests {
                 chain: "evm:anvil".parse().unwrap(),
                 address: Default::default(),
             },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
         };
 
         let service_id = service.id();
@@ -1162,6 +2137,8 @@ pub mod tests {
                 chain: "evm:anvil".parse().unwrap(),
                 address: Default::default(),
             },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
         };
 
         let service_id = service.id();
@@ -1205,6 +2182,8 @@ pub mod tests {
                 chain: "evm:anvil".parse().unwrap(),
                 address: Default::default(),
             },
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
         };
 
         let service_id = service.id();
@@ -0,0 +1,111 @@
+//! Counts the work the engine, submission manager and aggregator have picked up but not finished,
+//! so shutdown can wait for it before killing them.
+//!
+//! Work hops between subsystems through the dispatcher's channels, and the count can briefly read
+//! zero between one subsystem finishing and the next picking it up. [`InFlight::drain`] therefore
+//! only returns once the count has stayed at zero for a full poll interval.
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tokio::time::Instant;
+
+#[derive(Clone, Default)]
+pub struct InFlight {
+    count: Arc<AtomicUsize>,
+}
+
+/// Marks one piece of work as in flight until dropped
+pub struct InFlightGuard {
+    count: Arc<AtomicUsize>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.count.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl InFlight {
+    const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+    pub fn start(&self) -> InFlightGuard {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        InFlightGuard {
+            count: self.count.clone(),
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::SeqCst)
+    }
+
+    /// Waits for all in-flight work to finish.
+    /// Returns how much was still in flight if `timeout` elapsed first
+    pub async fn drain(&self, timeout: Duration) -> Result<(), usize> {
+        let deadline = Instant::now() + timeout;
+        let mut idle_polls = 0;
+
+        loop {
+            if self.count() == 0 {
+                idle_polls += 1;
+                if idle_polls > 1 {
+                    return Ok(());
+                }
+            } else {
+                idle_polls = 0;
+            }
+
+            if Instant::now() >= deadline {
+                return match self.count() {
+                    0 => Ok(()),
+                    remaining => Err(remaining),
+                };
+            }
+
+            tokio::time::sleep_until(deadline.min(Instant::now() + Self::POLL_INTERVAL)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn drain_waits_for_work_to_finish() {
+        let in_flight = InFlight::default();
+        let guard = in_flight.start();
+
+        let work = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            drop(guard);
+        });
+
+        assert_eq!(in_flight.count(), 1);
+        in_flight.drain(Duration::from_secs(5)).await.unwrap();
+        assert_eq!(in_flight.count(), 0);
+        assert!(work.is_finished());
+    }
+
+    #[tokio::test]
+    async fn drain_times_out_with_work_remaining() {
+        let in_flight = InFlight::default();
+        let _first = in_flight.start();
+        let _second = in_flight.start();
+
+        let start = std::time::Instant::now();
+        let remaining = in_flight
+            .drain(Duration::from_millis(100))
+            .await
+            .unwrap_err();
+
+        assert_eq!(remaining, 2);
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+}
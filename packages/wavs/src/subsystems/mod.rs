@@ -1,4 +1,5 @@
 pub mod aggregator;
 pub mod engine;
+pub mod in_flight;
 pub mod submission;
 pub mod trigger;
@@ -1,5 +1,6 @@
 pub mod data;
 pub mod error;
+pub mod target;
 
 use std::{
     collections::HashMap,
@@ -7,38 +8,57 @@ use std::{
 };
 
 use crate::{
-    config::Config, dispatcher::DispatcherCommand, services::Services,
-    subsystems::submission::data::SubmissionRequest, tracing_service_info, AppContext,
+    config::Config,
+    dispatcher::DispatcherCommand,
+    services::Services,
+    subsystems::{
+        in_flight::InFlight,
+        submission::{data::SubmissionRequest, target::TargetDeliverer},
+    },
+    tracing_service_info, AppContext,
 };
 use alloy_primitives::FixedBytes;
 use alloy_signer_local::PrivateKeySigner;
 use error::SubmissionError;
 use tracing::instrument;
-use utils::{evm_client::signing::make_signer, telemetry::SubmissionMetrics};
+use utils::{
+    evm_client::signing::make_signer,
+    telemetry::{PipelineMetrics, SubmissionMetrics},
+};
 use wavs_types::Submission;
-use wavs_types::{Credential, Envelope, EventOrder, ServiceId, SignerResponse, Submit, WavsSigner};
+use wavs_types::{
+    Credential, Envelope, EventOrder, ResultSignature, ServiceId, SignatureKind, SignedResult,
+    SignerResponse, Submit, WavsSigner, Workflow,
+};
 
 #[derive(Debug)]
 #[allow(clippy::large_enum_variant)]
 pub enum SubmissionCommand {
     Kill,
     Submit(SubmissionRequest),
+    /// Responses from a single execution that carry an `ordering`, sorted ascending.
+    /// Each is only submitted once the one before it has been
+    SubmitSequence(Vec<SubmissionRequest>),
 }
 
 #[derive(Clone)]
 pub struct SubmissionManager {
     // created on-demand from chain_name and hd_index
     pub metrics: SubmissionMetrics,
+    pub pipeline_metrics: PipelineMetrics,
     signers: Arc<RwLock<HashMap<ServiceId, SignerInfo>>>,
     signing_mnemonic: Credential,
     signing_mnemonic_hd_index_count: Arc<AtomicU32>,
     subsystem_to_dispatcher_tx: crossbeam::channel::Sender<DispatcherCommand>,
     dispatcher_to_submission_rx: crossbeam::channel::Receiver<SubmissionCommand>,
+    target_deliverer: TargetDeliverer,
     #[cfg(feature = "dev")]
     pub debug_submissions: Arc<RwLock<Vec<Submission>>>,
     #[cfg(feature = "dev")]
     pub disable_networking: bool,
     pub services: Services,
+    /// Shared with the engine manager and aggregator, so shutdown can drain them
+    pub in_flight: InFlight,
 }
 
 struct SignerInfo {
@@ -64,7 +84,9 @@ impl SubmissionManager {
             signers: Arc::new(RwLock::new(HashMap::new())),
             signing_mnemonic,
             signing_mnemonic_hd_index_count: Arc::new(AtomicU32::new(1)),
+            target_deliverer: TargetDeliverer::new(metrics.clone())?,
             metrics,
+            pipeline_metrics: PipelineMetrics::default(),
             subsystem_to_dispatcher_tx,
             dispatcher_to_submission_rx,
             #[cfg(feature = "dev")]
@@ -72,9 +94,20 @@ impl SubmissionManager {
             #[cfg(feature = "dev")]
             disable_networking: config.disable_submission_networking,
             services,
+            in_flight: InFlight::default(),
         })
     }
 
+    pub fn with_pipeline_metrics(mut self, pipeline_metrics: PipelineMetrics) -> Self {
+        self.pipeline_metrics = pipeline_metrics;
+        self
+    }
+
+    pub fn with_in_flight(mut self, in_flight: InFlight) -> Self {
+        self.in_flight = in_flight;
+        self
+    }
+
     #[instrument(skip(self, ctx), fields(subsys = "Submission"))]
     pub fn start(&self, ctx: AppContext) {
         while let Ok(msg) = self.dispatcher_to_submission_rx.recv() {
@@ -85,57 +118,120 @@ impl SubmissionManager {
                 }
                 SubmissionCommand::Submit(req) => {
                     let _self = self.clone();
+                    let in_flight = self.in_flight.start();
+                    ctx.rt.spawn(async move {
+                        let _in_flight = in_flight;
+                        _self.submit(req).await;
+                    });
+                }
+                SubmissionCommand::SubmitSequence(reqs) => {
+                    let _self = self.clone();
+                    let in_flight = self.in_flight.start();
                     ctx.rt.spawn(async move {
-                        _self
-                            .metrics
-                            .increment_request_count(&req.service, req.workflow_id());
-
-                        // Check if the service is active
-                        if !_self.services.is_active(req.service_id()) {
-                            crate::tracing_service_warn!(
-                                _self.services,
-                                req.service_id(),
-                                "Service is not active, skipping message"
-                            );
-                            return;
-                        }
-
-                        let submission = match _self.sign_request(&req).await {
-                            Ok(s) => {
-                                _self
-                                    .metrics
-                                    .increment_sign_count(&req.service, req.workflow_id());
-                                s
-                            }
-                            Err(e) => {
-                                _self
-                                    .metrics
-                                    .increment_sign_error_count(&req.service, req.workflow_id());
-                                tracing::error!("Error processing message: {:?}", e);
-                                return;
-                            }
-                        };
-
-                        match _self.dispatch(submission, &req).await {
-                            Ok(_) => {
-                                _self
-                                    .metrics
-                                    .increment_dispatch_count(&req.service, req.workflow_id());
-                            }
-                            Err(e) => {
-                                _self.metrics.increment_dispatch_error_count(
-                                    &req.service,
-                                    req.workflow_id(),
-                                );
-                                tracing::error!("Error dispatching submission: {:?}", e);
-                            }
-                        }
+                        let _in_flight = in_flight;
+                        _self.submit_sequence(reqs).await;
                     });
                 }
             }
         }
     }
 
+    /// Signs a request and hands it off to its targets and the aggregator.
+    /// Returns whether it made it all the way through
+    async fn submit(&self, req: SubmissionRequest) -> bool {
+        self.metrics
+            .increment_request_count(&req.service, req.workflow_id());
+
+        // Check if the service is active
+        if !self.services.is_active(req.service_id()) {
+            crate::tracing_service_warn!(
+                self.services,
+                req.service_id(),
+                "Service is not active, skipping message"
+            );
+            return false;
+        }
+
+        let service_id = req.service_id().to_string();
+        let chain = req.trigger_action.data.chain();
+        self.pipeline_metrics
+            .increment_submissions_attempted(&service_id, chain);
+
+        let submission = match self.sign_request(&req).await {
+            Ok(s) => {
+                self.metrics
+                    .increment_sign_count(&req.service, req.workflow_id());
+                s
+            }
+            Err(e) => {
+                self.metrics
+                    .increment_sign_error_count(&req.service, req.workflow_id());
+                self.pipeline_metrics
+                    .increment_submissions_failed(&service_id, chain);
+                tracing::error!("Error processing message: {:?}", e);
+                return false;
+            }
+        };
+
+        let workflow = match self
+            .services
+            .get_workflow(req.service_id(), req.workflow_id())
+        {
+            Ok(workflow) => workflow,
+            Err(e) => {
+                tracing::error!("Error looking up workflow: {:?}", e);
+                return false;
+            }
+        };
+
+        // nothing aggregates this result, so this operator's signature is the final one.
+        // With an aggregator, it delivers the combined result once it's on-chain
+        if matches!(workflow.submit, Submit::None) {
+            self.deliver_to_targets(&submission, &req, &workflow).await;
+            self.pipeline_metrics
+                .increment_submissions_succeeded(&service_id, chain);
+            return true;
+        }
+
+        match self.dispatch(submission, &req).await {
+            Ok(_) => {
+                self.metrics
+                    .increment_dispatch_count(&req.service, req.workflow_id());
+                self.pipeline_metrics
+                    .increment_submissions_succeeded(&service_id, chain);
+                true
+            }
+            Err(e) => {
+                self.metrics
+                    .increment_dispatch_error_count(&req.service, req.workflow_id());
+                self.pipeline_metrics
+                    .increment_submissions_failed(&service_id, chain);
+                tracing::error!("Error dispatching submission: {:?}", e);
+                false
+            }
+        }
+    }
+
+    /// Submits responses one at a time, in the order given, each waiting on the one before.
+    /// If one fails the rest are dropped rather than submitted out of order
+    async fn submit_sequence(&self, reqs: Vec<SubmissionRequest>) {
+        let total = reqs.len();
+        for (index, req) in reqs.into_iter().enumerate() {
+            let service_id = req.service_id().clone();
+            let ordering = req.operator_response.ordering;
+            if !self.submit(req).await {
+                crate::tracing_service_error!(
+                    self.services,
+                    service_id,
+                    "Ordered submission {:?} failed, dropping the {} after it",
+                    ordering,
+                    total - index - 1
+                );
+                return;
+            }
+        }
+    }
+
     #[instrument(skip(self), fields(subsys = "Submission"))]
     pub async fn sign_request(
         &self,
@@ -163,12 +259,11 @@ impl SubmissionManager {
                 .clone()
         };
 
-        let signature_kind = match self
-            .services
-            .get_workflow(service_id, req.workflow_id())?
-            .submit
-        {
+        let workflow = self.services.get_workflow(service_id, req.workflow_id())?;
+        let signature_kind = match workflow.submit {
             Submit::Aggregator { signature_kind, .. } => signature_kind,
+            // off-chain targets still get a signed result, even without an aggregator
+            Submit::None if !workflow.submit_targets.is_empty() => SignatureKind::evm_default(),
             Submit::None => return Err(SubmissionError::InvalidSubmitKind(Submit::None)),
         };
 
@@ -183,6 +278,7 @@ impl SubmissionManager {
             event_id,
             envelope,
             envelope_signature,
+            submit_targets: req.submit_targets.clone(),
         })
     }
 
@@ -233,6 +329,60 @@ impl SubmissionManager {
         Ok(())
     }
 
+    #[instrument(skip(self, submission, req, workflow), fields(subsys = "Submission"))]
+    async fn deliver_to_targets(
+        &self,
+        submission: &Submission,
+        req: &SubmissionRequest,
+        workflow: &Workflow,
+    ) {
+        if submission.submit_targets.is_empty() {
+            return;
+        }
+
+        // shadow testing: an observe-only workflow doesn't send its result anywhere
+        if workflow.observe_only {
+            tracing::info!(
+                "Observe-only workflow, not delivering {} to {} submit target(s)",
+                submission.label(),
+                submission.submit_targets.len()
+            );
+            return;
+        }
+
+        #[cfg(feature = "dev")]
+        if self.disable_networking {
+            tracing::warn!("Networking is disabled, skipping delivery to submit targets");
+            return;
+        }
+
+        let signer = match self.signers.read().unwrap().get(req.service_id()) {
+            Some(info) => info.signer.address(),
+            None => {
+                tracing::error!("Missing signer for service {}", req.service_id());
+                return;
+            }
+        };
+
+        let result = SignedResult::new(
+            submission,
+            vec![ResultSignature {
+                signer,
+                signature: submission.envelope_signature.clone(),
+            }],
+            None,
+        );
+
+        self.target_deliverer
+            .deliver_all(&req.service, &submission.submit_targets, &result)
+            .await;
+    }
+
+    /// Shared with the aggregator, so every delivery ends up in the same record
+    pub fn target_deliverer(&self) -> TargetDeliverer {
+        self.target_deliverer.clone()
+    }
+
     #[instrument(skip(self), fields(subsys = "Submission"))]
     // Adds a service to the submission manager, creating a new signer for it.
     // if no hd_index is provided, it will be automatically assigned.
@@ -1,5 +1,5 @@
 use wavs_types::{
-    EventId, EventIdSalt, Service, ServiceId, TriggerAction, WasmResponse, WorkflowId,
+    EventId, EventIdSalt, Service, ServiceId, SubmitTarget, TriggerAction, WasmResponse, WorkflowId,
 };
 
 /// The data we send from engine to submission, after operator component execution
@@ -7,6 +7,8 @@ use wavs_types::{
 pub struct SubmissionRequest {
     pub trigger_action: TriggerAction,
     pub operator_response: WasmResponse,
+    /// The workflow's submit targets this particular response goes to
+    pub submit_targets: Vec<SubmitTarget>,
     pub service: Service,
     #[cfg(feature = "dev")]
     pub debug: SubmissionRequestDebug,
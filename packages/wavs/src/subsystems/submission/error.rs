@@ -64,6 +64,10 @@ pub enum SubmissionError {
     InvalidSubmitKind(Submit),
     #[error("Submission channel error: {0}")]
     SendCommand(#[from] Box<crossbeam::channel::SendError<DispatcherCommand>>),
+    #[error("submit target {url} responded with status {status}")]
+    SubmitTargetStatus { url: String, status: u16 },
+    #[error("submit target auth: {0}")]
+    SubmitTargetAuth(String),
     #[error("could not encode EventId {0:?}")]
     EncodeEventId(bincode::error::EncodeError),
 }
@@ -0,0 +1,226 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use utils::{telemetry::SubmissionMetrics, time::unix_now};
+use wavs_types::{
+    Service, ServiceId, SignedResult, SubmitTarget, SubmitTargetAuth, TargetDelivery, WorkflowId,
+    WAVS_ENV_PREFIX,
+};
+
+use super::error::SubmissionError;
+
+/// Number of attempts made for each submit target before giving up
+pub const SUBMIT_TARGET_MAX_ATTEMPTS: u32 = 3;
+/// Delay before the first retry, doubled on each subsequent retry
+pub const SUBMIT_TARGET_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Number of recent deliveries kept for `GET /submission/deliveries`
+pub const MAX_TARGET_DELIVERIES: usize = 1000;
+
+/// Comma separated signatures, in the same order as the signers
+pub const SIGNATURES_HEADER: &str = "x-wavs-signatures";
+/// Comma separated signer addresses
+pub const SIGNERS_HEADER: &str = "x-wavs-signers";
+pub const EVENT_ID_HEADER: &str = "x-wavs-event-id";
+
+/// Posts signed results to submit targets and keeps a record of how each delivery went.
+/// Shared by the submission manager, which delivers for workflows without an aggregator,
+/// and the aggregator, which delivers the combined result once it's on-chain
+#[derive(Clone)]
+pub struct TargetDeliverer {
+    http_client: reqwest::Client,
+    metrics: SubmissionMetrics,
+    /// Most recent deliveries, newest last
+    deliveries: Arc<Mutex<VecDeque<TargetDelivery>>>,
+}
+
+impl TargetDeliverer {
+    pub fn new(metrics: SubmissionMetrics) -> Result<Self, SubmissionError> {
+        Ok(Self {
+            http_client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .map_err(SubmissionError::Reqwest)?,
+            metrics,
+            deliveries: Arc::new(Mutex::new(VecDeque::new())),
+        })
+    }
+
+    /// Delivers the result to every target concurrently, recording each outcome
+    pub async fn deliver_all(
+        &self,
+        service: &Service,
+        submit_targets: &[SubmitTarget],
+        result: &SignedResult,
+    ) {
+        let deliveries = submit_targets.iter().map(|submit_target| async move {
+            let label = target_label(submit_target);
+            let error = match deliver(&self.http_client, submit_target, result).await {
+                Ok(()) => {
+                    tracing::info!("Delivered {} to {}", result.label(), label);
+                    self.metrics.increment_target_delivery_count(
+                        service,
+                        &result.workflow_id,
+                        &label,
+                    );
+                    None
+                }
+                Err(e) => {
+                    tracing::error!("Error delivering {} to {}: {:?}", result.label(), label, e);
+                    self.metrics.increment_target_delivery_error_count(
+                        service,
+                        &result.workflow_id,
+                        &label,
+                    );
+                    Some(e.to_string())
+                }
+            };
+
+            self.record(TargetDelivery {
+                service_id: result.service_id.clone(),
+                workflow_id: result.workflow_id.clone(),
+                event_id: result.event_id.clone(),
+                target: label,
+                signatures: result.signatures.len(),
+                error,
+                delivered_at: unix_now(),
+            });
+        });
+
+        futures::future::join_all(deliveries).await;
+    }
+
+    fn record(&self, delivery: TargetDelivery) {
+        let mut deliveries = self.deliveries.lock().unwrap();
+        if deliveries.len() >= MAX_TARGET_DELIVERIES {
+            deliveries.pop_front();
+        }
+        deliveries.push_back(delivery);
+    }
+
+    /// Recorded deliveries, newest first
+    pub fn list(
+        &self,
+        service_id: Option<&ServiceId>,
+        workflow_id: Option<&WorkflowId>,
+    ) -> Vec<TargetDelivery> {
+        self.deliveries
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|delivery| service_id.is_none_or(|id| delivery.service_id == *id))
+            .filter(|delivery| workflow_id.is_none_or(|id| delivery.workflow_id == *id))
+            .cloned()
+            .collect()
+    }
+}
+
+/// A short label for logs and metrics
+pub fn target_label(target: &SubmitTarget) -> String {
+    match target {
+        SubmitTarget::Http { url, .. } => url.clone(),
+    }
+}
+
+/// Deliver a signed result to the target, retrying transient failures with exponential backoff
+pub async fn deliver(
+    client: &reqwest::Client,
+    target: &SubmitTarget,
+    result: &SignedResult,
+) -> Result<(), SubmissionError> {
+    let mut backoff = SUBMIT_TARGET_INITIAL_BACKOFF;
+    let mut attempt = 1;
+
+    loop {
+        match deliver_once(client, target, result).await {
+            Ok(()) => return Ok(()),
+            Err(err) if attempt < SUBMIT_TARGET_MAX_ATTEMPTS && is_retryable(&err) => {
+                tracing::warn!(
+                    "Delivery to {} failed (attempt {}/{}), retrying in {:?}: {}",
+                    target_label(target),
+                    attempt,
+                    SUBMIT_TARGET_MAX_ATTEMPTS,
+                    backoff,
+                    err
+                );
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+async fn deliver_once(
+    client: &reqwest::Client,
+    target: &SubmitTarget,
+    result: &SignedResult,
+) -> Result<(), SubmissionError> {
+    match target {
+        SubmitTarget::Http { url, auth } => {
+            let (signers, signatures): (Vec<_>, Vec<_>) = result
+                .signatures
+                .iter()
+                .map(|signature| {
+                    (
+                        signature.signer.to_string(),
+                        const_hex::encode_prefixed(&signature.signature.data),
+                    )
+                })
+                .unzip();
+
+            let mut request = client
+                .post(url)
+                .header(SIGNATURES_HEADER, signatures.join(","))
+                .header(SIGNERS_HEADER, signers.join(","))
+                .header(EVENT_ID_HEADER, result.event_id.to_string())
+                .json(result);
+
+            match auth {
+                Some(SubmitTargetAuth::Bearer { env_key }) => {
+                    request = request.bearer_auth(read_env(env_key)?);
+                }
+                Some(SubmitTargetAuth::Header { name, env_key }) => {
+                    request = request.header(name.as_str(), read_env(env_key)?);
+                }
+                None => {}
+            }
+
+            let response = request.send().await.map_err(SubmissionError::Reqwest)?;
+
+            if response.status().is_success() {
+                Ok(())
+            } else {
+                Err(SubmissionError::SubmitTargetStatus {
+                    url: url.clone(),
+                    status: response.status().as_u16(),
+                })
+            }
+        }
+    }
+}
+
+fn read_env(env_key: &str) -> Result<String, SubmissionError> {
+    if !env_key.starts_with(WAVS_ENV_PREFIX) {
+        return Err(SubmissionError::SubmitTargetAuth(format!(
+            "env key {env_key} must start with {WAVS_ENV_PREFIX}"
+        )));
+    }
+
+    std::env::var(env_key)
+        .map_err(|_| SubmissionError::SubmitTargetAuth(format!("env key {env_key} is not set")))
+}
+
+// connection problems, rate limiting and server errors are worth another try, anything else won't change
+fn is_retryable(err: &SubmissionError) -> bool {
+    match err {
+        SubmissionError::Reqwest(_) => true,
+        SubmissionError::SubmitTargetStatus { status, .. } => *status == 429 || *status >= 500,
+        _ => false,
+    }
+}
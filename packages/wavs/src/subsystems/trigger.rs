@@ -1,5 +1,7 @@
+pub mod backfill;
 pub mod error;
 pub mod lookup;
+pub mod rate_limit;
 pub mod schedulers;
 pub mod streams;
 
@@ -9,30 +11,44 @@ use crate::{
     services::Services,
     subsystems::trigger::streams::{
         cosmos_stream::StreamTriggerCosmosContractEvent,
-        evm_stream::client::{EvmTriggerStreams, EvmTriggerStreamsController},
+        evm_stream::{
+            block_timestamps::BlockTimestamps,
+            client::{EvmTriggerStreams, EvmTriggerStreamsController},
+            seen_logs::SeenLogs,
+        },
         local_command_stream,
     },
-    tracing_service_info, AppContext,
+    tracing_service_debug, tracing_service_info, AppContext,
 };
 use alloy_sol_types::SolEvent;
 use anyhow::Result;
+use backfill::{fetch_gap_logs, fetch_missed_logs, EvmProgressStore, LogPosition};
 use error::TriggerError;
 use futures::{stream::SelectAll, StreamExt};
 use iri_string::types::UriString;
 use layer_climb::prelude::*;
 use lookup::{LookupId, LookupMaps};
+use rate_limit::TriggerRateLimiter;
+use schedulers::cron_scheduler::CronJitter;
 use std::{
     collections::{HashMap, HashSet},
     num::NonZeroU64,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
 };
 use streams::{cosmos_stream, cron_stream, evm_stream, MultiplexedStream, StreamTriggers};
 use tracing::instrument;
+use utils::config::EvmChainConfigExt;
+use utils::health::check_cosmos_chain_id;
 use utils::telemetry::TriggerMetrics;
+use utils::tls::ChainTls;
 use wavs_types::{
-    contracts::cosmwasm::service_manager::event::WavsServiceUriUpdatedEvent, AnyChainConfig,
-    ByteArray, ChainConfigs, ChainKey, DevHypercoreStreamState, IWavsServiceManager, ServiceId,
-    Trigger, TriggerAction, TriggerConfig, TriggerData,
+    contracts::cosmwasm::service_manager::event::WavsServiceUriUpdatedEvent,
+    extract_evm_log_fields, AnyChainConfig, ByteArray, ChainConfigs, ChainKey,
+    DevHypercoreStreamState, EvmLogFieldError, IWavsServiceManager, ServiceId, Trigger,
+    TriggerAction, TriggerConfig, TriggerData,
 };
 
 #[derive(Debug)]
@@ -61,11 +77,7 @@ impl TriggerCommand {
     pub fn map(trigger_config: &TriggerConfig, chain_configs: &ChainConfigs) -> Vec<Self> {
         match &trigger_config.trigger {
             Trigger::Cron { .. } => vec![Self::StartListeningCron],
-            Trigger::EvmContractEvent {
-                chain,
-                address,
-                event_hash,
-            } => {
+            Trigger::EvmContractEvent { chain, address, .. } => {
                 vec![
                     Self::StartListeningChain {
                         chain: chain.clone(),
@@ -73,7 +85,12 @@ impl TriggerCommand {
                     Self::WatchEvmContractEvents {
                         chain: chain.clone(),
                         addresses: vec![*address],
-                        event_hashes: vec![event_hash.into_inner().into()],
+                        event_hashes: trigger_config
+                            .trigger
+                            .evm_event_hashes()
+                            .into_iter()
+                            .map(|event_hash| event_hash.into_inner().into())
+                            .collect(),
                     },
                 ]
             }
@@ -99,6 +116,12 @@ impl TriggerCommand {
                             chain: chain.clone(),
                         }]
                     }
+                    AnyChainConfig::Solana(_) => {
+                        tracing::warn!(
+                            "Block interval triggers aren't supported on Solana chain {chain}"
+                        );
+                        Vec::new()
+                    }
                 },
                 None => {
                     tracing::warn!("Block interval set for non-existant chain-config: {chain}");
@@ -113,11 +136,14 @@ impl TriggerCommand {
                     feed_key: feed_key.clone(),
                 }]
             }
-            Trigger::Manual => Vec::new(),
+            Trigger::Manual | Trigger::Webhook { .. } => Vec::new(),
         }
     }
 }
 
+/// How often chains given through [`TriggerManager::with_evm_provider`] are checked for new blocks
+const EVM_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum StreamStartState {
     Waiting,
@@ -139,6 +165,17 @@ pub struct TriggerManager {
     pub services: Services,
     pub evm_controllers: Arc<std::sync::RwLock<HashMap<ChainKey, EvmTriggerStreamsController>>>,
     hypercore_stream_states: Arc<std::sync::RwLock<HashMap<String, StreamStartState>>>,
+    /// Logs already turned into triggers per chain, shared by the live stream and the backfill
+    evm_seen_logs: Arc<std::sync::RwLock<HashMap<ChainKey, Arc<Mutex<SeenLogs>>>>>,
+    /// Last log each workflow fired for, `None` unless `evm_backfill` is enabled
+    evm_progress: Option<EvmProgressStore>,
+    /// Drops triggers for workflows with a `min_interval_ms` that arrive too soon
+    rate_limiter: TriggerRateLimiter,
+    /// Whether the last send found the dispatcher channel full, so it's only warned about once
+    dispatcher_backlogged: Arc<AtomicBool>,
+    chain_tls: ChainTls,
+    /// Chains followed by polling these providers instead of the configured websocket endpoints
+    evm_providers: HashMap<ChainKey, alloy_provider::DynProvider>,
     pub config: Config,
 }
 
@@ -153,9 +190,33 @@ impl TriggerManager {
     ) -> Result<Self, TriggerError> {
         let (command_sender, command_receiver) = tokio::sync::mpsc::unbounded_channel();
 
+        let cron_jitter = match (config.cron_jitter_secs, &config.signing_mnemonic) {
+            (0, _) => None,
+            (secs, Some(mnemonic)) => Some(CronJitter::new(
+                mnemonic,
+                std::time::Duration::from_secs(secs),
+            )),
+            (_, None) => {
+                tracing::warn!("cron_jitter_secs is set but there is no signing mnemonic to derive the operator offset from, cron jitter is disabled");
+                None
+            }
+        };
+
+        let evm_progress = match config.evm_backfill {
+            true => Some(
+                EvmProgressStore::open(&config.data.join("trigger"))
+                    .map_err(TriggerError::EvmProgress)?,
+            ),
+            false => None,
+        };
+
         Ok(Self {
             chain_configs: config.chains.clone(),
-            lookup_maps: Arc::new(LookupMaps::new(services.clone(), metrics.clone())),
+            lookup_maps: Arc::new(LookupMaps::new(
+                services.clone(),
+                metrics.clone(),
+                cron_jitter,
+            )),
             subsystem_to_dispatcher_tx,
             command_sender,
             command_receiver: Arc::new(std::sync::Mutex::new(Some(command_receiver))),
@@ -165,10 +226,33 @@ impl TriggerManager {
             services,
             evm_controllers: Arc::new(std::sync::RwLock::new(HashMap::new())),
             hypercore_stream_states: Arc::new(std::sync::RwLock::new(HashMap::new())),
+            evm_seen_logs: Arc::new(std::sync::RwLock::new(HashMap::new())),
+            evm_progress,
+            rate_limiter: TriggerRateLimiter::default(),
+            dispatcher_backlogged: Arc::new(AtomicBool::new(false)),
+            chain_tls: ChainTls::default(),
+            evm_providers: HashMap::new(),
             config: config.clone(),
         })
     }
 
+    /// Custom TLS for the evm websocket streams, loaded once by the dispatcher
+    pub fn with_chain_tls(mut self, chain_tls: ChainTls) -> Self {
+        self.chain_tls = chain_tls;
+        self
+    }
+
+    /// Follows `chain` by polling `provider` rather than through its chain config, and backfills
+    /// through it too, e.g. for an in-memory chain in tests
+    pub fn with_evm_provider(
+        mut self,
+        chain: ChainKey,
+        provider: alloy_provider::DynProvider,
+    ) -> Self {
+        self.evm_providers.insert(chain, provider);
+        self
+    }
+
     pub fn hypercore_streams_info(&self) -> HashMap<String, DevHypercoreStreamState> {
         self.hypercore_stream_states
             .read()
@@ -185,8 +269,10 @@ impl TriggerManager {
             .collect()
     }
 
-    #[instrument(skip(self), fields(subsys = "TriggerManager"))]
+    #[instrument(skip(self, service), fields(subsys = "TriggerManager", service.name = %service.name, service_id = %service.id()))]
     pub fn add_service(&self, service: &wavs_types::Service) -> Result<(), TriggerError> {
+        tracing::debug!("Adding triggers for service: {:?}", service);
+
         // The mechanics of adding a trigger are that we:
 
         // 1. Setup all the records needed to track the trigger in various "lookup" maps.
@@ -197,6 +283,7 @@ impl TriggerManager {
         // will not be fired until the stream that kicks it off is polled (i.e. this definitively happens _after_ the stream is created).
 
         self.lookup_maps.add_service(service)?;
+        self.rate_limiter.add_service(service);
 
         // Ensure the service manager's chain is being listened to for service change events
         // This is needed even if the service has no workflows, so service URI changes can be detected
@@ -239,6 +326,7 @@ impl TriggerManager {
     #[instrument(skip(self), fields(subsys = "TriggerManager"))]
     pub fn remove_service(&self, service_id: ServiceId) -> Result<(), TriggerError> {
         self.lookup_maps.remove_service(service_id.clone())?;
+        self.rate_limiter.remove_service(&service_id);
 
         // TODO - consider sending commands to:
         // 1. stop listening to chains if no triggers remain for them
@@ -255,44 +343,49 @@ impl TriggerManager {
         ctx.rt.block_on(self.start_watcher(kill_receiver)).unwrap();
     }
 
+    /// Blocks while the dispatcher channel is full, so it's only for callers off the runtime
     pub fn send_dispatcher_commands(
         &self,
         commands: impl IntoIterator<Item = DispatcherCommand>,
     ) -> Result<(), TriggerError> {
         for command in commands {
-            match &command {
-                DispatcherCommand::Trigger(action) => {
-                    #[cfg(feature = "dev")]
-                    if std::env::var("WAVS_FORCE_TRIGGER_ERROR_XXX").is_ok() {
-                        self.metrics.increment_total_errors("forced trigger error");
-                        continue;
-                    }
+            if !self.accept_dispatcher_command(&command) {
+                continue;
+            }
 
-                    tracing_service_info!(
-                        &self.services,
-                        action.config.service_id,
-                        "Sending trigger action for workflow {}",
-                        action.config.workflow_id,
-                    );
+            let start = std::time::Instant::now();
+            if let Some(command) = self.try_send_to_dispatcher(command)? {
+                self.subsystem_to_dispatcher_tx
+                    .send(command)
+                    .map_err(Box::new)?;
+            }
 
-                    self.metrics
-                        .record_trigger_fired(action.data.chain(), action.data.trigger_type());
-                }
-                DispatcherCommand::ChangeServiceUri { service_id, uri } => {
-                    tracing_service_info!(
-                        &self.services,
-                        service_id,
-                        "Changing service URI to {}",
-                        uri
-                    );
-                }
-                _ => {}
+            self.metrics
+                .record_trigger_sent_dispatcher_command(start.elapsed().as_secs_f64());
+        }
+
+        Ok(())
+    }
+
+    /// Like [`Self::send_dispatcher_commands`], for the watcher. While the dispatcher channel is
+    /// full it waits on the blocking pool, stalling the watcher without tying up a runtime thread
+    async fn forward_dispatcher_commands(
+        &self,
+        commands: Vec<DispatcherCommand>,
+    ) -> Result<(), TriggerError> {
+        for command in commands {
+            if !self.accept_dispatcher_command(&command) {
+                continue;
             }
 
             let start = std::time::Instant::now();
-            self.subsystem_to_dispatcher_tx
-                .send(command)
-                .map_err(Box::new)?;
+            if let Some(command) = self.try_send_to_dispatcher(command)? {
+                let tx = self.subsystem_to_dispatcher_tx.clone();
+                tokio::task::spawn_blocking(move || tx.send(command))
+                    .await
+                    .map_err(TriggerError::DispatcherSendTask)?
+                    .map_err(Box::new)?;
+            }
 
             self.metrics
                 .record_trigger_sent_dispatcher_command(start.elapsed().as_secs_f64());
@@ -301,6 +394,78 @@ impl TriggerManager {
         Ok(())
     }
 
+    /// Logs the command on its way out, `false` if it's dropped instead
+    fn accept_dispatcher_command(&self, command: &DispatcherCommand) -> bool {
+        match command {
+            DispatcherCommand::Trigger(action) => {
+                #[cfg(feature = "dev")]
+                if std::env::var("WAVS_FORCE_TRIGGER_ERROR_XXX").is_ok() {
+                    self.metrics.increment_total_errors("forced trigger error");
+                    return false;
+                }
+
+                if !self
+                    .rate_limiter
+                    .allow(&action.config, std::time::Instant::now())
+                {
+                    return false;
+                }
+
+                // the dispatcher logs each trigger it receives at info
+                tracing_service_debug!(
+                    &self.services,
+                    action.config.service_id,
+                    "Sending trigger action for workflow {}",
+                    action.config.workflow_id,
+                );
+
+                self.metrics
+                    .record_trigger_fired(action.data.chain(), action.data.trigger_type());
+            }
+            DispatcherCommand::ChangeServiceUri { service_id, uri } => {
+                tracing_service_info!(
+                    &self.services,
+                    service_id,
+                    "Changing service URI to {}",
+                    uri
+                );
+            }
+            _ => {}
+        }
+
+        true
+    }
+
+    /// Sends if there's room in the dispatcher channel, otherwise hands the command back
+    /// for the caller to wait with
+    fn try_send_to_dispatcher(
+        &self,
+        command: DispatcherCommand,
+    ) -> Result<Option<DispatcherCommand>, TriggerError> {
+        let command = match self.subsystem_to_dispatcher_tx.try_send(command) {
+            Ok(()) => {
+                if self.dispatcher_backlogged.swap(false, Ordering::Relaxed) {
+                    tracing::info!("Dispatcher caught up, no longer holding back triggers");
+                }
+                return Ok(None);
+            }
+            Err(crossbeam::channel::TrySendError::Full(command)) => command,
+            Err(crossbeam::channel::TrySendError::Disconnected(command)) => {
+                return Err(Box::new(crossbeam::channel::SendError(command)).into());
+            }
+        };
+
+        self.metrics.increment_dispatcher_channel_full();
+        if !self.dispatcher_backlogged.swap(true, Ordering::Relaxed) {
+            tracing::warn!(
+                "Dispatcher channel is full ({} commands queued), holding back triggers until it catches up",
+                self.subsystem_to_dispatcher_tx.len()
+            );
+        }
+
+        Ok(Some(command))
+    }
+
     pub fn add_trigger(&self, trigger: TriggerAction) -> Result<(), TriggerError> {
         self.command_sender
             .send(TriggerCommand::ManualTrigger(Box::new(trigger)))?;
@@ -434,6 +599,43 @@ impl TriggerManager {
                                 }
                             }
 
+                            if let Some(provider) = self.evm_providers.get(&chain) {
+                                let (block_height_stream, log_stream) =
+                                    evm_stream::start_evm_polling_streams(
+                                        chain.clone(),
+                                        provider.clone(),
+                                        EVM_POLL_INTERVAL,
+                                    );
+
+                                let seen_logs = Arc::new(Mutex::new(SeenLogs::default()));
+                                self.evm_seen_logs
+                                    .write()
+                                    .unwrap()
+                                    .insert(chain.clone(), seen_logs.clone());
+
+                                multiplexed_stream.push(
+                                    evm_stream::start_evm_event_stream(
+                                        chain.clone(),
+                                        log_stream,
+                                        Some(BlockTimestamps::new(provider.clone())),
+                                        seen_logs,
+                                        self.metrics.clone(),
+                                    )
+                                    .await?,
+                                );
+                                multiplexed_stream.push(
+                                    evm_stream::start_evm_block_stream(
+                                        chain.clone(),
+                                        block_height_stream,
+                                        self.metrics.clone(),
+                                    )
+                                    .await?,
+                                );
+
+                                *chain_state = StreamStartState::Connected;
+                                continue;
+                            }
+
                             let chain_config =
                                 match self.chain_configs.read().unwrap().get_chain(&chain) {
                                     Some(config) => config,
@@ -457,6 +659,20 @@ impl TriggerManager {
                                     .await
                                     .map_err(TriggerError::Climb)?;
 
+                                    if let Err(err) =
+                                        check_cosmos_chain_id(&chain, &cosmos_client).await
+                                    {
+                                        tracing::error!(
+                                            "Refusing to stream events from {chain}: {err}"
+                                        );
+                                        if let Some(chain_state) =
+                                            listening_chain_states.get_mut(&chain)
+                                        {
+                                            *chain_state = StreamStartState::Waiting;
+                                        }
+                                        continue;
+                                    }
+
                                     // Start the Cosmos event stream
                                     match cosmos_stream::start_cosmos_stream(
                                         cosmos_client.clone(),
@@ -497,23 +713,41 @@ impl TriggerManager {
                                     }
 
                                     let chain_key: ChainKey = (&chain_config).into();
+                                    let block_timestamps = BlockTimestamps::connect(
+                                        &chain_config,
+                                        self.chain_tls.get(&chain),
+                                    )
+                                    .await;
                                     let EvmTriggerStreams {
                                         controller,
                                         block_height_stream,
                                         log_stream,
                                         // ignoring this for now
                                         new_pending_transaction_stream: _,
+                                        reconnect_stream,
                                     } = EvmTriggerStreams::new(
                                         chain_config.ws_endpoints,
                                         chain_key,
                                         chain_config.ws_priority_endpoint_index,
+                                        self.chain_tls.get(&chain).cloned(),
+                                        std::time::Duration::from_secs(
+                                            self.config.evm_ws_max_backoff_secs,
+                                        ),
                                     );
 
+                                    let seen_logs = Arc::new(Mutex::new(SeenLogs::default()));
+                                    self.evm_seen_logs
+                                        .write()
+                                        .unwrap()
+                                        .insert(chain.clone(), seen_logs.clone());
+
                                     // Start the EVM event stream
                                     // however, the actual subscription for log filters is set via the controller
                                     let evm_event_stream = match evm_stream::start_evm_event_stream(
                                         chain.clone(),
                                         log_stream,
+                                        block_timestamps,
+                                        seen_logs,
                                         self.metrics.clone(),
                                     )
                                     .await
@@ -559,6 +793,12 @@ impl TriggerManager {
 
                                     multiplexed_stream.push(evm_event_stream);
                                     multiplexed_stream.push(evm_block_stream);
+                                    multiplexed_stream.push(
+                                        evm_stream::start_evm_reconnect_stream(
+                                            chain.clone(),
+                                            reconnect_stream,
+                                        ),
+                                    );
 
                                     self.evm_controllers
                                         .write()
@@ -570,30 +810,51 @@ impl TriggerManager {
                                         *chain_state = StreamStartState::Connected;
                                     }
                                 }
+                                AnyChainConfig::Solana(_) => {
+                                    tracing::error!(
+                                        "Listening to Solana chain {chain} is not supported yet"
+                                    );
+                                    if let Some(chain_state) =
+                                        listening_chain_states.get_mut(&chain)
+                                    {
+                                        *chain_state = StreamStartState::Waiting;
+                                    }
+                                }
                             }
                         }
                         TriggerCommand::WatchEvmContractEvents {
                             chain,
                             addresses,
                             event_hashes,
-                        } => match self.evm_controllers.read().unwrap().get(&chain) {
-                            Some(evm_controller) => {
-                                evm_controller
-                                    .subscriptions
-                                    .enable_logs(addresses, event_hashes);
-                            }
-                            None => {
-                                tracing::error!(
+                        } => {
+                            match self.evm_controllers.read().unwrap().get(&chain) {
+                                Some(evm_controller) => {
+                                    evm_controller
+                                        .subscriptions
+                                        .enable_logs(addresses.clone(), event_hashes.clone());
+                                }
+                                // polling already fetches every log
+                                None if self.evm_providers.contains_key(&chain) => {}
+                                None => {
+                                    tracing::error!(
                                         "No EVM controller found for chain {chain}, cannot watch contract event"
                                     );
-                                continue;
+                                    continue;
+                                }
                             }
-                        },
+
+                            // live logs queue up until this returns, so the missed ones go first
+                            dispatcher_commands.extend(
+                                self.backfill_evm_events(&chain, &addresses, &event_hashes)
+                                    .await,
+                            );
+                        }
                         TriggerCommand::WatchEvmBlocks { chain } => {
                             match self.evm_controllers.read().unwrap().get(&chain) {
                                 Some(evm_controller) => {
                                     evm_controller.subscriptions.toggle_block_height(true);
                                 }
+                                None if self.evm_providers.contains_key(&chain) => {}
                                 None => {
                                     tracing::error!(
                                         "No EVM controller found for chain {chain}, cannot watch blocks"
@@ -752,157 +1013,39 @@ impl TriggerManager {
                     tx_index,
                     block_timestamp,
                 } => {
-                    if let Some(event_hash) = log.topic0() {
-                        let contract_address = log.address();
-
-                        if *event_hash == IWavsServiceManager::ServiceURIUpdated::SIGNATURE_HASH {
-                            // 3. Decode the event data
-                            match IWavsServiceManager::ServiceURIUpdated::decode_log_data(
-                                log.data(),
-                            ) {
-                                Ok(decoded_event) => {
-                                    let service_uri =
-                                        UriString::try_from(decoded_event.serviceURI)?;
-                                    // check if this is a service we're interested in
-                                    if let Some(service_id) = self
-                                        .lookup_maps
-                                        .service_manager
-                                        .read()
-                                        .unwrap()
-                                        .get_by_right(&contract_address.into())
-                                    {
-                                        dispatcher_commands.push(
-                                            DispatcherCommand::ChangeServiceUri {
-                                                service_id: service_id.clone(),
-                                                uri: service_uri,
-                                            },
-                                        );
-                                    }
-                                }
-                                Err(e) => {
-                                    tracing::error!(
-                                        "Failed to decode ServiceURIUpdated data: {}",
-                                        e
-                                    );
-                                }
-                            }
-                        }
-
-                        let triggers_by_contract_event_lock = self
-                            .lookup_maps
-                            .triggers_by_evm_contract_event
-                            .read()
-                            .unwrap();
-
-                        if let Some(lookup_ids) = triggers_by_contract_event_lock.get(&(
-                            chain.clone(),
-                            contract_address,
-                            ByteArray::new(**event_hash),
-                        )) {
-                            let trigger_data = TriggerData::EvmContractEvent {
-                                contract_address,
-                                chain,
-                                log_data: log.data().clone(),
-                                tx_hash,
-                                block_number,
-                                log_index,
-                                block_hash,
-                                block_timestamp,
-                                tx_index,
-                            };
-
-                            for trigger_config in self.lookup_maps.get_trigger_configs(lookup_ids) {
-                                dispatcher_commands.push(DispatcherCommand::Trigger(
-                                    TriggerAction {
-                                        data: trigger_data.clone(),
-                                        config: trigger_config.clone(),
-                                    },
-                                ));
-                            }
-                        }
-                    }
+                    dispatcher_commands.extend(self.handle_evm_log(
+                        chain,
+                        *log,
+                        block_number,
+                        tx_hash,
+                        log_index,
+                        block_hash,
+                        tx_index,
+                        block_timestamp,
+                    )?);
+                }
+                StreamTriggers::EvmLogRemoved {
+                    chain,
+                    log,
+                    block_hash,
+                    tx_hash,
+                    log_index,
+                } => {
+                    self.handle_evm_log_removed(chain, *log, block_hash, tx_hash, log_index);
+                }
+                StreamTriggers::EvmReconnected { chain, last_block } => {
+                    dispatcher_commands.extend(self.resume_evm_logs(&chain, last_block).await);
                 }
                 StreamTriggers::Cosmos {
                     contract_events,
                     chain,
                     block_height,
                 } => {
-                    // extra scope in order to properly drop the locks
-                    {
-                        let triggers_by_contract_event_lock = self
-                            .lookup_maps
-                            .triggers_by_cosmos_contract_event
-                            .read()
-                            .unwrap();
-
-                        for StreamTriggerCosmosContractEvent {
-                            contract_address,
-                            event,
-                            event_index,
-                        } in contract_events
-                        {
-                            if layer_climb::events::Event::from(&event)
-                                .is_type(WavsServiceUriUpdatedEvent::EVENT_TYPE)
-                            {
-                                let service_uri = event.attributes.iter().find_map(|attr| {
-                                    if attr.key
-                                        == WavsServiceUriUpdatedEvent::EVENT_ATTR_KEY_SERVICE_URI
-                                    {
-                                        UriString::try_from(attr.value.clone()).ok()
-                                    } else {
-                                        None
-                                    }
-                                });
-
-                                let service_uri = match service_uri {
-                                    Some(uri) => uri,
-                                    None => {
-                                        tracing::error!(
-                                            "ServiceURIUpdated event missing {} attribute",
-                                            WavsServiceUriUpdatedEvent::EVENT_ATTR_KEY_SERVICE_URI
-                                        );
-                                        continue;
-                                    }
-                                };
-
-                                if let Some(service_id) = self
-                                    .lookup_maps
-                                    .service_manager
-                                    .read()
-                                    .unwrap()
-                                    .get_by_right(&contract_address.clone().into())
-                                {
-                                    dispatcher_commands.push(DispatcherCommand::ChangeServiceUri {
-                                        service_id: service_id.clone(),
-                                        uri: service_uri,
-                                    });
-                                }
-                            }
-                            if let Some(lookup_ids) = triggers_by_contract_event_lock.get(&(
-                                chain.clone(),
-                                contract_address.clone(),
-                                event.ty.clone(),
-                            )) {
-                                let trigger_data = TriggerData::CosmosContractEvent {
-                                    contract_address,
-                                    chain: chain.clone(),
-                                    event,
-                                    event_index,
-                                    block_height,
-                                };
-                                for trigger_config in
-                                    self.lookup_maps.get_trigger_configs(lookup_ids)
-                                {
-                                    dispatcher_commands.push(DispatcherCommand::Trigger(
-                                        TriggerAction {
-                                            data: trigger_data.clone(),
-                                            config: trigger_config.clone(),
-                                        },
-                                    ));
-                                }
-                            }
-                        }
-                    }
+                    dispatcher_commands.extend(self.handle_cosmos_events(
+                        &chain,
+                        contract_events,
+                        block_height,
+                    ));
 
                     // process block-based triggers
                     dispatcher_commands.extend(self.process_blocks(chain, block_height));
@@ -924,6 +1067,7 @@ impl TriggerManager {
                                     TriggerAction {
                                         data: TriggerData::Cron {
                                             trigger_time: hit.scheduled_time,
+                                            missed_ticks: Some(hit.missed_ticks),
                                         },
                                         config: trigger_config.clone(),
                                     },
@@ -1056,7 +1200,7 @@ impl TriggerManager {
             }
 
             if !dispatcher_commands.is_empty() {
-                tracing::info!(
+                tracing::debug!(
                     "Sending {} commands to dispatcher",
                     dispatcher_commands.len()
                 );
@@ -1072,7 +1216,8 @@ impl TriggerManager {
                     }
                 }
 
-                self.send_dispatcher_commands(dispatcher_commands)?;
+                self.forward_dispatcher_commands(dispatcher_commands)
+                    .await?;
             }
         }
 
@@ -1096,72 +1241,592 @@ impl TriggerManager {
             }
         };
         // Get the triggers that should fire at this block height
-        let firing_lookup_ids: Vec<LookupId> =
-            match self.lookup_maps.block_schedulers.get_mut(&chain) {
-                Some(mut scheduler) => scheduler
-                    .tick(block_height.into())
-                    .into_iter()
-                    .map(|(id, _)| id)
-                    .collect(),
-                None => Vec::new(),
-            };
+        let hits = match self.lookup_maps.block_schedulers.get_mut(&chain) {
+            Some(mut scheduler) => scheduler.tick_hits(block_height.into()),
+            None => Vec::new(),
+        };
 
-        // Convert lookup_ids to TriggerActions
-        if !firing_lookup_ids.is_empty() {
-            self.lookup_maps
-                .get_trigger_configs(&firing_lookup_ids)
+        // Convert hits to TriggerActions
+        let mut commands = Vec::new();
+        for hit in hits {
+            let Some(trigger_config) = self
+                .lookup_maps
+                .get_trigger_configs(&[hit.lookup_id])
                 .into_iter()
-                .map(|trigger_config| {
-                    DispatcherCommand::Trigger(TriggerAction {
-                        data: TriggerData::BlockInterval {
-                            chain: chain.clone(),
-                            block_height: block_height.get(),
-                        },
-                        config: trigger_config,
-                    })
-                })
-                .collect()
-        } else {
-            Vec::new()
+                .next()
+            else {
+                continue;
+            };
+
+            if hit.is_final {
+                tracing::info!(
+                    service_id = %trigger_config.service_id,
+                    workflow_id = %trigger_config.workflow_id,
+                    "Block interval trigger on {} reached its end block at {}",
+                    chain,
+                    block_height
+                );
+            }
+
+            commands.push(DispatcherCommand::Trigger(TriggerAction {
+                data: TriggerData::BlockInterval {
+                    chain: chain.clone(),
+                    block_height: block_height.get(),
+                    is_final: hit.is_final,
+                },
+                config: trigger_config,
+            }));
         }
+
+        commands
     }
 
-    fn handle_hypercore_event(
+    /// A log can match a trigger through any of the event hashes it lists,
+    /// the trigger data is the same whichever one matched
+    #[allow(clippy::too_many_arguments)]
+    fn handle_evm_log(
         &self,
-        event: streams::hypercore_stream::HypercoreAppendEvent,
-    ) -> Vec<DispatcherCommand> {
-        let mut matched_lookup_ids: HashSet<LookupId> = HashSet::new();
+        chain: ChainKey,
+        log: alloy_rpc_types_eth::Log,
+        block_number: u64,
+        tx_hash: alloy_primitives::TxHash,
+        log_index: u64,
+        block_hash: alloy_primitives::BlockHash,
+        tx_index: u64,
+        block_timestamp: Option<u64>,
+    ) -> Result<Vec<DispatcherCommand>, TriggerError> {
+        let mut dispatcher_commands = Vec::new();
+
+        if let Some(event_hash) = log.topic0() {
+            let contract_address = log.address();
+
+            if *event_hash == IWavsServiceManager::ServiceURIUpdated::SIGNATURE_HASH {
+                // 3. Decode the event data
+                match IWavsServiceManager::ServiceURIUpdated::decode_log_data(log.data()) {
+                    Ok(decoded_event) => {
+                        let service_uri = UriString::try_from(decoded_event.serviceURI)?;
+                        // check if this is a service we're interested in
+                        if let Some(service_id) = self
+                            .lookup_maps
+                            .service_manager
+                            .read()
+                            .unwrap()
+                            .get_by_right(&contract_address.into())
+                        {
+                            dispatcher_commands.push(DispatcherCommand::ChangeServiceUri {
+                                service_id: service_id.clone(),
+                                uri: service_uri,
+                            });
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to decode ServiceURIUpdated data: {}", e);
+                    }
+                }
+            }
 
-        {
-            let triggers_by_hypercore_lock = self
+            let triggers_by_contract_event_lock = self
                 .lookup_maps
-                .triggers_by_hypercore_append
+                .triggers_by_evm_contract_event
                 .read()
                 .unwrap();
 
-            if let Some(lookup_ids) = triggers_by_hypercore_lock.get(&event.feed_key) {
-                matched_lookup_ids.extend(lookup_ids);
+            if let Some(lookup_ids) = triggers_by_contract_event_lock.get(&(
+                chain.clone(),
+                contract_address,
+                ByteArray::new(**event_hash),
+            )) {
+                let trigger_data = TriggerData::EvmContractEvent {
+                    contract_address,
+                    chain,
+                    log_data: log.data().clone(),
+                    tx_hash,
+                    block_number,
+                    log_index,
+                    block_hash,
+                    block_timestamp,
+                    tx_index,
+                };
+
+                for trigger_config in self.lookup_maps.get_trigger_configs(lookup_ids) {
+                    self.record_evm_progress(
+                        &trigger_config,
+                        LogPosition {
+                            block_number,
+                            log_index,
+                        },
+                    );
+                    let data = match transform_evm_trigger_data(&trigger_config, &trigger_data) {
+                        Ok(data) => data,
+                        Err(err) => {
+                            tracing::warn!(
+                                "Skipping log {} in block {} for workflow {} of service {}: {}",
+                                log_index,
+                                block_number,
+                                trigger_config.workflow_id,
+                                trigger_config.service_id,
+                                err
+                            );
+                            continue;
+                        }
+                    };
+                    dispatcher_commands.push(DispatcherCommand::Trigger(TriggerAction {
+                        data,
+                        config: trigger_config.clone(),
+                    }));
+                }
             }
         }
 
-        if matched_lookup_ids.is_empty() {
-            return Vec::new();
-        }
+        Ok(dispatcher_commands)
+    }
 
-        let trigger_data = TriggerData::HypercoreAppend {
-            feed_key: event.feed_key,
-            index: event.index,
-            data: event.data,
-        };
+    /// Turns a block's contract events into trigger actions, and service URI changes for
+    /// the service managers that emitted them
+    fn handle_cosmos_events(
+        &self,
+        chain: &ChainKey,
+        contract_events: Vec<StreamTriggerCosmosContractEvent>,
+        block_height: u64,
+    ) -> Vec<DispatcherCommand> {
+        let mut dispatcher_commands = Vec::new();
 
-        self.lookup_maps
-            .get_trigger_configs(&matched_lookup_ids)
-            .into_iter()
-            .map(|trigger_config| {
-                DispatcherCommand::Trigger(TriggerAction {
-                    data: trigger_data.clone(),
-                    config: trigger_config,
-                })
+        let triggers_by_contract_event_lock = self
+            .lookup_maps
+            .triggers_by_cosmos_contract_event
+            .read()
+            .unwrap();
+
+        for StreamTriggerCosmosContractEvent {
+            contract_address,
+            event,
+            event_index,
+        } in contract_events
+        {
+            if layer_climb::events::Event::from(&event)
+                .is_type(WavsServiceUriUpdatedEvent::EVENT_TYPE)
+            {
+                let service_uri = event.attributes.iter().find_map(|attr| {
+                    if attr.key == WavsServiceUriUpdatedEvent::EVENT_ATTR_KEY_SERVICE_URI {
+                        UriString::try_from(attr.value.clone()).ok()
+                    } else {
+                        None
+                    }
+                });
+
+                let service_uri = match service_uri {
+                    Some(uri) => uri,
+                    None => {
+                        tracing::error!(
+                            "ServiceURIUpdated event missing {} attribute",
+                            WavsServiceUriUpdatedEvent::EVENT_ATTR_KEY_SERVICE_URI
+                        );
+                        continue;
+                    }
+                };
+
+                if let Some(service_id) = self
+                    .lookup_maps
+                    .service_manager
+                    .read()
+                    .unwrap()
+                    .get_by_right(&contract_address.clone().into())
+                {
+                    dispatcher_commands.push(DispatcherCommand::ChangeServiceUri {
+                        service_id: service_id.clone(),
+                        uri: service_uri,
+                    });
+                }
+            }
+            if let Some(lookup_ids) = triggers_by_contract_event_lock.get(&(
+                chain.clone(),
+                contract_address.clone(),
+                event.ty.clone(),
+            )) {
+                // triggers on the same contract and event type can still filter on different attributes
+                let trigger_configs: Vec<_> = self
+                    .lookup_maps
+                    .get_trigger_configs(lookup_ids)
+                    .into_iter()
+                    .filter(|trigger_config| {
+                        trigger_config.trigger.matches_cosmos_attributes(&event)
+                    })
+                    .collect();
+
+                if trigger_configs.is_empty() {
+                    continue;
+                }
+
+                let trigger_data = TriggerData::CosmosContractEvent {
+                    contract_address,
+                    chain: chain.clone(),
+                    event,
+                    event_index,
+                    block_height,
+                };
+                for trigger_config in trigger_configs {
+                    dispatcher_commands.push(DispatcherCommand::Trigger(TriggerAction {
+                        data: trigger_data.clone(),
+                        config: trigger_config,
+                    }));
+                }
+            }
+        }
+
+        dispatcher_commands
+    }
+
+    fn record_evm_progress(&self, trigger_config: &TriggerConfig, position: LogPosition) {
+        if let Some(progress) = &self.evm_progress {
+            if let Err(err) = progress.advance(
+                &trigger_config.service_id,
+                &trigger_config.workflow_id,
+                position,
+            ) {
+                tracing::warn!(
+                    "Failed to record EVM trigger progress for {} / {}: {:?}",
+                    trigger_config.service_id,
+                    trigger_config.workflow_id,
+                    err
+                );
+            }
+        }
+    }
+
+    /// Triggers for the logs each workflow watching these events missed since it last fired,
+    /// see [`backfill`]. Workflows that never fired start from the live stream
+    async fn backfill_evm_events(
+        &self,
+        chain: &ChainKey,
+        addresses: &[alloy_primitives::Address],
+        event_hashes: &[alloy_primitives::B256],
+    ) -> Vec<DispatcherCommand> {
+        let Some(progress) = &self.evm_progress else {
+            return Vec::new();
+        };
+
+        let trigger_configs: Vec<(TriggerConfig, LogPosition)> = {
+            let lookup_ids: HashSet<LookupId> = {
+                let lock = self
+                    .lookup_maps
+                    .triggers_by_evm_contract_event
+                    .read()
+                    .unwrap();
+                addresses
+                    .iter()
+                    .flat_map(|address| {
+                        event_hashes.iter().filter_map(|event_hash| {
+                            lock.get(&(chain.clone(), *address, ByteArray::new(**event_hash)))
+                        })
+                    })
+                    .flatten()
+                    .copied()
+                    .collect()
+            };
+
+            self.lookup_maps
+                .get_trigger_configs(&lookup_ids)
+                .into_iter()
+                .filter_map(|config| {
+                    let position = progress.get(&config.service_id, &config.workflow_id)?;
+                    Some((config, position))
+                })
+                .collect()
+        };
+
+        let Some(from_block) = trigger_configs
+            .iter()
+            .map(|(_, position)| position.block_number)
+            .min()
+        else {
+            return Vec::new();
+        };
+
+        let logs = match self.evm_log_provider(chain).await {
+            Ok(provider) => {
+                fetch_missed_logs(
+                    &provider,
+                    addresses,
+                    event_hashes,
+                    from_block,
+                    self.config.evm_backfill_chunk_blocks,
+                )
+                .await
+            }
+            Err(err) => Err(err),
+        };
+        let logs = match logs {
+            Ok(logs) => logs,
+            Err(err) => {
+                tracing::error!(
+                    "Failed to backfill EVM logs on {chain} from block {from_block}: {:?}",
+                    err
+                );
+                return Vec::new();
+            }
+        };
+
+        let seen_logs = self.evm_seen_logs.read().unwrap().get(chain).cloned();
+        let mut dispatcher_commands = Vec::new();
+
+        for log in logs {
+            let (
+                Some(block_hash),
+                Some(tx_index),
+                Some(block_number),
+                Some(tx_hash),
+                Some(log_index),
+                Some(event_hash),
+            ) = (
+                log.block_hash,
+                log.transaction_index,
+                log.block_number,
+                log.transaction_hash,
+                log.log_index,
+                log.topic0().copied(),
+            )
+            else {
+                continue;
+            };
+
+            // already dispatched from the live stream
+            if let Some(seen_logs) = &seen_logs {
+                if !seen_logs.lock().unwrap().insert(block_hash, log_index) {
+                    continue;
+                }
+            }
+
+            let position = LogPosition {
+                block_number,
+                log_index,
+            };
+            let trigger_data = TriggerData::EvmContractEvent {
+                contract_address: log.address(),
+                chain: chain.clone(),
+                log_data: log.data().clone(),
+                tx_hash,
+                block_number,
+                log_index,
+                block_hash,
+                block_timestamp: log.block_timestamp,
+                tx_index,
+            };
+
+            for (config, last_position) in &trigger_configs {
+                let watches_log = matches!(
+                    &config.trigger,
+                    Trigger::EvmContractEvent { address, .. } if *address == log.address()
+                ) && config
+                    .trigger
+                    .evm_event_hashes()
+                    .contains(&ByteArray::new(*event_hash));
+
+                if watches_log && position > *last_position {
+                    self.record_evm_progress(config, position);
+                    dispatcher_commands.push(DispatcherCommand::Trigger(TriggerAction {
+                        data: trigger_data.clone(),
+                        config: config.clone(),
+                    }));
+                }
+            }
+        }
+
+        tracing::info!(
+            "Backfilled {} EVM trigger(s) on {chain} from block {from_block}",
+            dispatcher_commands.len()
+        );
+
+        dispatcher_commands
+    }
+
+    /// Dispatches the logs a chain's subscription missed while its websocket was down,
+    /// from the last block it delivered up to the current head
+    async fn resume_evm_logs(&self, chain: &ChainKey, last_block: u64) -> Vec<DispatcherCommand> {
+        let log_filter = self
+            .evm_controllers
+            .read()
+            .unwrap()
+            .get(chain)
+            .and_then(|controller| controller.subscriptions.log_filter());
+        let seen_logs = self.evm_seen_logs.read().unwrap().get(chain).cloned();
+        let (Some((addresses, event_hashes)), Some(seen_logs)) = (log_filter, seen_logs) else {
+            tracing::info!("Resubscribed on {chain}, no log filter to backfill");
+            return Vec::new();
+        };
+
+        let gap = match self.evm_log_provider(chain).await {
+            Ok(provider) => {
+                fetch_gap_logs(
+                    &provider,
+                    &addresses,
+                    &event_hashes,
+                    last_block,
+                    self.config.evm_backfill_chunk_blocks,
+                    &seen_logs,
+                )
+                .await
+            }
+            Err(err) => Err(err),
+        };
+        let (head, logs) = match gap {
+            Ok(gap) => gap,
+            Err(err) => {
+                tracing::error!(
+                    "Failed to backfill EVM logs on {chain} after reconnecting, from block {last_block}: {:?}",
+                    err
+                );
+                return Vec::new();
+            }
+        };
+
+        tracing::info!(
+            "Resubscribed on {chain} after a gap of {} blocks since block {last_block}, backfilling {} missed log(s)",
+            head.saturating_sub(last_block),
+            logs.len()
+        );
+
+        let mut dispatcher_commands = Vec::new();
+        for log in logs {
+            let (
+                Some(block_number),
+                Some(tx_hash),
+                Some(log_index),
+                Some(block_hash),
+                Some(tx_index),
+            ) = (
+                log.block_number,
+                log.transaction_hash,
+                log.log_index,
+                log.block_hash,
+                log.transaction_index,
+            )
+            else {
+                continue;
+            };
+            let block_timestamp = log.block_timestamp;
+
+            match self.handle_evm_log(
+                chain.clone(),
+                log,
+                block_number,
+                tx_hash,
+                log_index,
+                block_hash,
+                tx_index,
+                block_timestamp,
+            ) {
+                Ok(commands) => dispatcher_commands.extend(commands),
+                Err(err) => tracing::error!(
+                    "Failed to handle backfilled EVM log on {chain} in block {block_number}: {:?}",
+                    err
+                ),
+            }
+        }
+
+        dispatcher_commands
+    }
+
+    async fn evm_log_provider(&self, chain: &ChainKey) -> Result<alloy_provider::DynProvider> {
+        if let Some(provider) = self.evm_providers.get(chain) {
+            return Ok(provider.clone());
+        }
+
+        let chain_config = self
+            .chain_configs
+            .read()
+            .unwrap()
+            .get_chain(chain)
+            .ok_or_else(|| anyhow::anyhow!("no chain config for {chain}"))?
+            .to_evm_config()?;
+
+        let endpoint = chain_config
+            .query_client_endpoints()?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("no query endpoint for {chain}"))?;
+
+        Ok(endpoint
+            .to_provider_with_tls(self.chain_tls.get(chain))
+            .await?)
+    }
+
+    /// There's no taking back a submission that's already been made, so this only tells
+    /// the operator which workflows may have acted on a log that's no longer on-chain
+    fn handle_evm_log_removed(
+        &self,
+        chain: ChainKey,
+        log: alloy_rpc_types_eth::Log,
+        block_hash: alloy_primitives::BlockHash,
+        tx_hash: alloy_primitives::TxHash,
+        log_index: u64,
+    ) {
+        self.metrics.record_evm_log_removed(&chain);
+
+        let Some(event_hash) = log.topic0() else {
+            return;
+        };
+
+        let triggers_by_contract_event_lock = self
+            .lookup_maps
+            .triggers_by_evm_contract_event
+            .read()
+            .unwrap();
+
+        if let Some(lookup_ids) = triggers_by_contract_event_lock.get(&(
+            chain.clone(),
+            log.address(),
+            ByteArray::new(**event_hash),
+        )) {
+            for trigger_config in self.lookup_maps.get_trigger_configs(lookup_ids) {
+                tracing::warn!(
+                    service_id = %trigger_config.service_id,
+                    workflow_id = %trigger_config.workflow_id,
+                    "Reorg removed a log this workflow triggered on: chain {}, block {}, tx {}, log index {}",
+                    chain,
+                    block_hash,
+                    tx_hash,
+                    log_index
+                );
+            }
+        }
+    }
+
+    fn handle_hypercore_event(
+        &self,
+        event: streams::hypercore_stream::HypercoreAppendEvent,
+    ) -> Vec<DispatcherCommand> {
+        let mut matched_lookup_ids: HashSet<LookupId> = HashSet::new();
+
+        {
+            let triggers_by_hypercore_lock = self
+                .lookup_maps
+                .triggers_by_hypercore_append
+                .read()
+                .unwrap();
+
+            if let Some(lookup_ids) = triggers_by_hypercore_lock.get(&event.feed_key) {
+                matched_lookup_ids.extend(lookup_ids);
+            }
+        }
+
+        if matched_lookup_ids.is_empty() {
+            return Vec::new();
+        }
+
+        let trigger_data = TriggerData::HypercoreAppend {
+            feed_key: event.feed_key,
+            index: event.index,
+            data: event.data,
+        };
+
+        self.lookup_maps
+            .get_trigger_configs(&matched_lookup_ids)
+            .into_iter()
+            .map(|trigger_config| {
+                DispatcherCommand::Trigger(TriggerAction {
+                    data: trigger_data.clone(),
+                    config: trigger_config,
+                })
             })
             .collect()
     }
@@ -1190,6 +1855,33 @@ impl TriggerManager {
     }
 }
 
+/// Swaps the log data for the fields an `EvmContractEvent` trigger's `transform` picks out of it,
+/// anything else is passed through as is
+fn transform_evm_trigger_data(
+    trigger_config: &TriggerConfig,
+    data: &TriggerData,
+) -> Result<TriggerData, EvmLogFieldError> {
+    let mut data = data.clone();
+    if let (
+        Trigger::EvmContractEvent { transform, .. },
+        TriggerData::EvmContractEvent {
+            contract_address,
+            log_data,
+            ..
+        },
+    ) = (&trigger_config.trigger, &mut data)
+    {
+        if !transform.is_empty() {
+            let payload = extract_evm_log_fields(transform, *contract_address, log_data)?;
+            *log_data = alloy_primitives::LogData::new_unchecked(
+                log_data.topics().to_vec(),
+                payload.into(),
+            );
+        }
+    }
+    Ok(data)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1197,7 +1889,9 @@ mod tests {
 
     use crate::{config::Config, services::Services};
     use utils::{
-        storage::db::WavsDb, telemetry::TriggerMetrics, test_utils::address::rand_address_evm,
+        storage::db::WavsDb,
+        telemetry::TriggerMetrics,
+        test_utils::address::{rand_address_cosmos, rand_address_evm},
     };
     use wavs_types::{
         Component, ComponentDigest, ComponentSource, ServiceManager, SignatureKind, Submit,
@@ -1233,11 +1927,20 @@ mod tests {
                             ComponentDigest::hash([0; 32]),
                         ))),
                         signature_kind: SignatureKind::evm_default(),
+                        targets: Vec::new(),
                     },
+                    submit_targets: Vec::new(),
+                    response_mapping: Default::default(),
+                    submit_condition: None,
+                    priority: Default::default(),
+                    observe_only: false,
+                    min_interval_ms: None,
                 },
             )]
             .into_iter()
             .collect(),
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
         };
         services.save(&service).unwrap();
 
@@ -1292,6 +1995,110 @@ mod tests {
         ctx.kill();
     }
 
+    #[test]
+    fn slow_dispatcher_holds_back_triggers() {
+        let config = Config::default();
+        let services = Services::new(WavsDb::new().unwrap());
+        let metrics = TriggerMetrics::new(opentelemetry::global::meter("test"));
+        let (dispatcher_tx, dispatcher_rx) = crossbeam::channel::bounded::<DispatcherCommand>(2);
+
+        let trigger_manager =
+            TriggerManager::new(&config, metrics, services, dispatcher_tx).unwrap();
+
+        let commands: Vec<DispatcherCommand> = (0..10u8)
+            .map(|i| {
+                DispatcherCommand::Trigger(TriggerAction {
+                    config: TriggerConfig {
+                        service_id: ServiceId::hash(b"service"),
+                        workflow_id: WorkflowId::default(),
+                        trigger: Trigger::Manual,
+                    },
+                    data: TriggerData::Raw(vec![i]),
+                })
+            })
+            .collect();
+
+        let producer = std::thread::spawn({
+            let trigger_manager = trigger_manager.clone();
+            move || trigger_manager.send_dispatcher_commands(commands)
+        });
+
+        // nothing is consuming yet, so the producer is stuck with the channel at capacity
+        std::thread::sleep(Duration::from_millis(200));
+        assert!(!producer.is_finished());
+        assert_eq!(dispatcher_rx.len(), 2);
+        assert!(trigger_manager
+            .dispatcher_backlogged
+            .load(Ordering::Relaxed));
+
+        // a slow consumer lets it through a few at a time, never holding more than the capacity
+        let mut received = Vec::new();
+        while received.len() < 10 {
+            assert!(dispatcher_rx.len() <= 2);
+            let command = dispatcher_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+            if let DispatcherCommand::Trigger(action) = command {
+                if let TriggerData::Raw(data) = action.data {
+                    received.push(data[0]);
+                }
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        producer.join().unwrap().unwrap();
+        assert_eq!(received, (0..10).collect::<Vec<u8>>());
+    }
+
+    // a single runtime thread, so a blocking send in the watcher would starve the consumer
+    #[tokio::test(flavor = "current_thread")]
+    async fn watcher_waits_for_dispatcher_off_the_runtime() {
+        let config = Config::default();
+        let services = Services::new(WavsDb::new().unwrap());
+        let metrics = TriggerMetrics::new(opentelemetry::global::meter("test"));
+        let (dispatcher_tx, dispatcher_rx) = crossbeam::channel::bounded::<DispatcherCommand>(1);
+
+        let trigger_manager =
+            TriggerManager::new(&config, metrics, services, dispatcher_tx).unwrap();
+
+        let commands: Vec<DispatcherCommand> = (0..5u8)
+            .map(|i| {
+                DispatcherCommand::Trigger(TriggerAction {
+                    config: TriggerConfig {
+                        service_id: ServiceId::hash(b"service"),
+                        workflow_id: WorkflowId::default(),
+                        trigger: Trigger::Manual,
+                    },
+                    data: TriggerData::Raw(vec![i]),
+                })
+            })
+            .collect();
+
+        let consumer = tokio::spawn(async move {
+            let mut received = Vec::new();
+            while received.len() < 5 {
+                match dispatcher_rx.try_recv() {
+                    Ok(DispatcherCommand::Trigger(action)) => {
+                        if let TriggerData::Raw(data) = action.data {
+                            received.push(data[0]);
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(_) => tokio::time::sleep(Duration::from_millis(10)).await,
+                }
+            }
+            received
+        });
+
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            trigger_manager.forward_dispatcher_commands(commands),
+        )
+        .await
+        .expect("the watcher blocked the runtime")
+        .unwrap();
+
+        assert_eq!(consumer.await.unwrap(), (0..5).collect::<Vec<u8>>());
+    }
+
     #[test]
     fn test_hypercore_append_dispatch() {
         let config = Config::default();
@@ -1326,11 +2133,20 @@ mod tests {
                             ComponentDigest::hash([0; 32]),
                         ))),
                         signature_kind: SignatureKind::evm_default(),
+                        targets: Vec::new(),
                     },
+                    submit_targets: Vec::new(),
+                    response_mapping: Default::default(),
+                    submit_condition: None,
+                    priority: Default::default(),
+                    observe_only: false,
+                    min_interval_ms: None,
                 },
             )]
             .into_iter()
             .collect(),
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
         };
         services.save(&service).unwrap();
 
@@ -1375,4 +2191,340 @@ mod tests {
             other => panic!("unexpected dispatcher command: {:?}", other),
         }
     }
+
+    #[test]
+    fn test_evm_trigger_matches_any_event_hash() {
+        let config = Config::default();
+        let services = Services::new(WavsDb::new().unwrap());
+        let metrics = TriggerMetrics::new(opentelemetry::global::meter("test"));
+        let (dispatcher_tx, _dispatcher_rx) = crossbeam::channel::unbounded::<DispatcherCommand>();
+
+        let trigger_manager =
+            TriggerManager::new(&config, metrics, services, dispatcher_tx).unwrap();
+
+        let chain: ChainKey = "evm:anvil".parse().unwrap();
+        let address = alloy_primitives::Address::repeat_byte(1);
+        let deposit = ByteArray::new([1; 32]);
+        let withdraw = ByteArray::new([2; 32]);
+
+        let trigger_config = TriggerConfig {
+            service_id: ServiceId::hash(b"service"),
+            workflow_id: WorkflowId::new("workflow-1").unwrap(),
+            trigger: Trigger::EvmContractEvent {
+                address,
+                chain: chain.clone(),
+                event_hash: deposit,
+                additional_event_hashes: vec![withdraw],
+                transform: Vec::new(),
+            },
+        };
+        trigger_manager
+            .lookup_maps
+            .add_trigger(trigger_config.clone())
+            .unwrap();
+
+        let log_with_topic = |topic: [u8; 32], data: &[u8]| alloy_rpc_types_eth::Log {
+            inner: alloy_primitives::Log {
+                address,
+                data: alloy_primitives::LogData::new_unchecked(
+                    vec![topic.into()],
+                    data.to_vec().into(),
+                ),
+            },
+            ..Default::default()
+        };
+
+        let fire = |log: alloy_rpc_types_eth::Log| {
+            trigger_manager
+                .handle_evm_log(
+                    chain.clone(),
+                    log,
+                    1,
+                    Default::default(),
+                    0,
+                    Default::default(),
+                    0,
+                    None,
+                )
+                .unwrap()
+        };
+
+        for (topic, data) in [
+            (deposit, b"deposit".as_slice()),
+            (withdraw, b"withdraw".as_slice()),
+        ] {
+            let commands = fire(log_with_topic(topic.into_inner(), data));
+            assert_eq!(commands.len(), 1);
+            match &commands[0] {
+                DispatcherCommand::Trigger(action) => {
+                    assert_eq!(action.config, trigger_config);
+                    match &action.data {
+                        TriggerData::EvmContractEvent { log_data, .. } => {
+                            assert_eq!(log_data.topics()[0].0, topic.into_inner());
+                            assert_eq!(log_data.data.as_ref(), data);
+                        }
+                        other => panic!("unexpected trigger data: {:?}", other),
+                    }
+                }
+                other => panic!("unexpected dispatcher command: {:?}", other),
+            }
+        }
+
+        // events the trigger doesn't list are ignored
+        assert!(fire(log_with_topic([3; 32], b"other")).is_empty());
+    }
+
+    #[test]
+    fn test_evm_trigger_transform_extracts_topic() {
+        let config = Config::default();
+        let services = Services::new(WavsDb::new().unwrap());
+        let metrics = TriggerMetrics::new(opentelemetry::global::meter("test"));
+        let (dispatcher_tx, _dispatcher_rx) = crossbeam::channel::unbounded::<DispatcherCommand>();
+
+        let trigger_manager =
+            TriggerManager::new(&config, metrics, services, dispatcher_tx).unwrap();
+
+        let chain: ChainKey = "evm:anvil".parse().unwrap();
+        let address = alloy_primitives::Address::repeat_byte(1);
+        let transfer = alloy_primitives::B256::repeat_byte(2);
+        let from = alloy_primitives::Address::repeat_byte(3);
+        let amount = alloy_primitives::B256::with_last_byte(42);
+
+        let trigger_config = TriggerConfig {
+            service_id: ServiceId::hash(b"service"),
+            workflow_id: WorkflowId::new("workflow-1").unwrap(),
+            trigger: Trigger::EvmContractEvent {
+                address,
+                chain: chain.clone(),
+                event_hash: ByteArray::new(transfer.0),
+                additional_event_hashes: Vec::new(),
+                transform: vec!["topics[1].address".parse().unwrap()],
+            },
+        };
+        trigger_manager
+            .lookup_maps
+            .add_trigger(trigger_config.clone())
+            .unwrap();
+
+        let fire = |topics: Vec<alloy_primitives::B256>| {
+            trigger_manager
+                .handle_evm_log(
+                    chain.clone(),
+                    alloy_rpc_types_eth::Log {
+                        inner: alloy_primitives::Log {
+                            address,
+                            data: alloy_primitives::LogData::new_unchecked(
+                                topics,
+                                amount.to_vec().into(),
+                            ),
+                        },
+                        ..Default::default()
+                    },
+                    1,
+                    Default::default(),
+                    0,
+                    Default::default(),
+                    0,
+                    None,
+                )
+                .unwrap()
+        };
+
+        let commands = fire(vec![transfer, from.into_word()]);
+        assert_eq!(commands.len(), 1);
+        match &commands[0] {
+            DispatcherCommand::Trigger(action) => match &action.data {
+                TriggerData::EvmContractEvent { log_data, .. } => {
+                    assert_eq!(log_data.data.as_ref(), from.as_slice());
+                    // topics still identify the event
+                    assert_eq!(log_data.topics()[0], transfer);
+                }
+                other => panic!("unexpected trigger data: {:?}", other),
+            },
+            other => panic!("unexpected dispatcher command: {:?}", other),
+        }
+
+        // a log without the indexed param doesn't fire
+        assert!(fire(vec![transfer]).is_empty());
+    }
+
+    #[test]
+    fn evm_logs_from_mock_chain_fire_triggers() {
+        let chain = utils::test_utils::mock_chain::MockEvmChain::new(31337);
+        let address = alloy_primitives::Address::repeat_byte(1);
+        let event_hash = alloy_primitives::B256::repeat_byte(2);
+
+        let service = wavs_types::Service {
+            name: "serv1".to_string(),
+            status: wavs_types::ServiceStatus::Active,
+            manager: ServiceManager::Evm {
+                chain: chain.chain_key(),
+                address: rand_address_evm(),
+            },
+            workflows: vec![(
+                "workflow-1".parse().unwrap(),
+                Workflow {
+                    trigger: Trigger::EvmContractEvent {
+                        address,
+                        chain: chain.chain_key(),
+                        event_hash: ByteArray::new(event_hash.0),
+                        additional_event_hashes: Vec::new(),
+                        transform: Vec::new(),
+                    },
+                    component: Component::new(ComponentSource::Digest(ComponentDigest::hash(
+                        [0; 32],
+                    ))),
+                    submit: Submit::None,
+                    submit_targets: Vec::new(),
+                    response_mapping: Default::default(),
+                    submit_condition: None,
+                    priority: Default::default(),
+                    observe_only: false,
+                    min_interval_ms: None,
+                },
+            )]
+            .into_iter()
+            .collect(),
+            max_concurrent_executions: None,
+            execution_timeout_secs: None,
+        };
+
+        let services = Services::new(WavsDb::new().unwrap());
+        services.save(&service).unwrap();
+        let metrics = TriggerMetrics::new(opentelemetry::global::meter("test"));
+        let (dispatcher_tx, dispatcher_rx) = crossbeam::channel::unbounded::<DispatcherCommand>();
+
+        let trigger_manager =
+            TriggerManager::new(&Config::default(), metrics, services, dispatcher_tx)
+                .unwrap()
+                .with_evm_provider(chain.chain_key(), chain.provider());
+
+        let ctx = utils::context::AppContext::new();
+        std::thread::spawn({
+            let trigger_manager = trigger_manager.clone();
+            let ctx = ctx.clone();
+            move || {
+                trigger_manager.start(ctx);
+            }
+        });
+        trigger_manager.add_service(&service).unwrap();
+
+        // polling starts after the head at the time the chain is first listened to
+        std::thread::sleep(Duration::from_millis(200));
+
+        chain.emit_log(
+            address,
+            vec![alloy_primitives::B256::repeat_byte(3)],
+            b"other".to_vec(),
+        );
+        chain.emit_log(address, vec![event_hash], b"matched".to_vec());
+        let block_number = chain.push_block();
+
+        let action = loop {
+            match dispatcher_rx.recv_timeout(Duration::from_secs(5)) {
+                Ok(DispatcherCommand::Trigger(action)) => break action,
+                Ok(_) => continue,
+                Err(err) => panic!("no trigger was fired: {err}"),
+            }
+        };
+
+        assert_eq!(action.config.service_id, service.id());
+        match action.data {
+            TriggerData::EvmContractEvent {
+                log_data,
+                block_number: log_block,
+                block_timestamp,
+                ..
+            } => {
+                assert_eq!(log_data.data.as_ref(), b"matched");
+                assert_eq!(log_block, block_number);
+                // the mock chain leaves it out of logs, like some nodes do
+                assert!(block_timestamp.is_some());
+            }
+            other => panic!("unexpected trigger data: {:?}", other),
+        }
+
+        ctx.kill();
+    }
+
+    #[test]
+    fn test_cosmos_trigger_filters_on_attributes() {
+        let config = Config::default();
+        let services = Services::new(WavsDb::new().unwrap());
+        let metrics = TriggerMetrics::new(opentelemetry::global::meter("test"));
+        let (dispatcher_tx, _dispatcher_rx) = crossbeam::channel::unbounded::<DispatcherCommand>();
+
+        let trigger_manager =
+            TriggerManager::new(&config, metrics, services, dispatcher_tx).unwrap();
+
+        let chain: ChainKey = "cosmos:wasmd".parse().unwrap();
+        let address = rand_address_cosmos();
+
+        let trigger_config = |workflow_id: &str, attributes: &[(&str, &str)]| TriggerConfig {
+            service_id: ServiceId::hash(b"service"),
+            workflow_id: WorkflowId::new(workflow_id).unwrap(),
+            trigger: Trigger::CosmosContractEvent {
+                address: address.clone(),
+                chain: chain.clone(),
+                event_type: "trade".to_string(),
+                attributes: attributes
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect(),
+            },
+        };
+        let swaps = trigger_config("swaps", &[("action", "swap")]);
+        let big_swaps = trigger_config("big-swaps", &[("action", "swap"), ("size", "big")]);
+        let everything = trigger_config("everything", &[]);
+        for trigger_config in [&swaps, &big_swaps, &everything] {
+            trigger_manager
+                .lookup_maps
+                .add_trigger(trigger_config.clone())
+                .unwrap();
+        }
+
+        let fired = |attributes: &[(&str, &str)]| -> Vec<WorkflowId> {
+            let event = cosmwasm_std::Event::new("trade").add_attributes(attributes.to_vec());
+            let mut workflows: Vec<_> = trigger_manager
+                .handle_cosmos_events(
+                    &chain,
+                    vec![StreamTriggerCosmosContractEvent {
+                        contract_address: address.clone(),
+                        event,
+                        event_index: 0,
+                    }],
+                    1,
+                )
+                .into_iter()
+                .map(|command| match command {
+                    DispatcherCommand::Trigger(action) => action.config.workflow_id,
+                    other => panic!("unexpected dispatcher command: {:?}", other),
+                })
+                .collect();
+            workflows.sort();
+            workflows
+        };
+        let workflow_ids = |configs: &[&TriggerConfig]| -> Vec<WorkflowId> {
+            let mut workflows: Vec<_> = configs
+                .iter()
+                .map(|config| config.workflow_id.clone())
+                .collect();
+            workflows.sort();
+            workflows
+        };
+
+        assert_eq!(
+            fired(&[("action", "swap"), ("size", "big")]),
+            workflow_ids(&[&swaps, &big_swaps, &everything])
+        );
+        assert_eq!(
+            fired(&[("action", "swap"), ("size", "small")]),
+            workflow_ids(&[&swaps, &everything])
+        );
+        assert_eq!(
+            fired(&[("action", "deposit"), ("size", "big")]),
+            workflow_ids(&[&everything])
+        );
+        assert_eq!(fired(&[]), workflow_ids(&[&everything]));
+    }
 }
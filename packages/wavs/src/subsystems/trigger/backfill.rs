@@ -0,0 +1,386 @@
+//! Replays EVM contract events a node missed while it was offline.
+//!
+//! Every EVM trigger that fires records its log's position per workflow, in a JSON file under
+//! the data directory. When a chain's log subscription starts, the logs after each workflow's
+//! last position up to the current head are fetched in fixed-size block ranges (providers cap
+//! how many blocks a single `eth_getLogs` may span) and dispatched before any live log.
+//! Replayed logs go through the chain's [`SeenLogs`](super::streams::evm_stream::seen_logs),
+//! so the same log arriving over the subscription isn't dispatched twice.
+//!
+//! The same goes for a websocket that drops while running: once it's reconnected, the logs from
+//! the last block it delivered up to the head are fetched with [`fetch_gap_logs`].
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use alloy_primitives::{Address, B256};
+use alloy_provider::{DynProvider, Provider};
+use alloy_rpc_types_eth::{Filter, Log};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use utils::filesystem::write_atomic;
+use wavs_types::{ServiceId, WorkflowId};
+
+use crate::subsystems::trigger::streams::evm_stream::seen_logs::SeenLogs;
+
+/// Where a log sits on its chain, logs are processed in this order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct LogPosition {
+    pub block_number: u64,
+    pub log_index: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ProgressEntry {
+    service_id: ServiceId,
+    workflow_id: WorkflowId,
+    position: LogPosition,
+}
+
+/// Position of the last log each workflow was triggered by
+#[derive(Clone)]
+pub struct EvmProgressStore {
+    path: PathBuf,
+    positions: Arc<Mutex<HashMap<(ServiceId, WorkflowId), LogPosition>>>,
+}
+
+impl EvmProgressStore {
+    pub const FILENAME: &str = "evm_progress.json";
+
+    /// Creates the directory if needed and loads the recorded positions
+    pub fn open(dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+
+        let path = dir.join(Self::FILENAME);
+        let entries: Vec<ProgressEntry> = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .with_context(|| format!("Failed to parse {}", path.display()))?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err.into()),
+        };
+
+        let positions = entries
+            .into_iter()
+            .map(|entry| ((entry.service_id, entry.workflow_id), entry.position))
+            .collect();
+
+        Ok(Self {
+            path,
+            positions: Arc::new(Mutex::new(positions)),
+        })
+    }
+
+    pub fn get(&self, service_id: &ServiceId, workflow_id: &WorkflowId) -> Option<LogPosition> {
+        self.positions
+            .lock()
+            .unwrap()
+            .get(&(service_id.clone(), workflow_id.clone()))
+            .copied()
+    }
+
+    /// Records the position if it's past the one already recorded.
+    /// Logs re-delivered out of order after a reorg never move a workflow backwards
+    pub fn advance(
+        &self,
+        service_id: &ServiceId,
+        workflow_id: &WorkflowId,
+        position: LogPosition,
+    ) -> Result<()> {
+        let key = (service_id.clone(), workflow_id.clone());
+        let mut positions = self.positions.lock().unwrap();
+        if positions
+            .get(&key)
+            .is_some_and(|current| *current >= position)
+        {
+            return Ok(());
+        }
+        positions.insert(key, position);
+
+        let entries: Vec<_> = positions
+            .iter()
+            .map(|((service_id, workflow_id), position)| ProgressEntry {
+                service_id: service_id.clone(),
+                workflow_id: workflow_id.clone(),
+                position: *position,
+            })
+            .collect();
+        write_atomic(&self.path, &serde_json::to_vec(&entries)?)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+pub trait EvmLogQuery: Send + Sync {
+    async fn head(&self) -> Result<u64>;
+
+    /// Logs in the inclusive block range emitted by any of `addresses` with any of `event_hashes`
+    async fn logs(
+        &self,
+        addresses: &[Address],
+        event_hashes: &[B256],
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<Log>>;
+}
+
+#[async_trait]
+impl EvmLogQuery for DynProvider {
+    async fn head(&self) -> Result<u64> {
+        Ok(self.get_block_number().await?)
+    }
+
+    async fn logs(
+        &self,
+        addresses: &[Address],
+        event_hashes: &[B256],
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<Log>> {
+        let filter = Filter::new()
+            .address(addresses.to_vec())
+            .event_signature(event_hashes.to_vec())
+            .from_block(from_block)
+            .to_block(to_block);
+
+        Ok(self.get_logs(&filter).await?)
+    }
+}
+
+/// Logs from `from_block` up to the current head, queried `chunk_blocks` blocks at a time,
+/// in chain order
+pub async fn fetch_missed_logs(
+    query: &dyn EvmLogQuery,
+    addresses: &[Address],
+    event_hashes: &[B256],
+    from_block: u64,
+    chunk_blocks: u64,
+) -> Result<Vec<Log>> {
+    let head = query.head().await?;
+    fetch_logs_in_range(
+        query,
+        addresses,
+        event_hashes,
+        from_block,
+        head,
+        chunk_blocks,
+    )
+    .await
+}
+
+/// Logs from `last_block` up to the current head that aren't in `seen_logs` yet, which are
+/// marked seen. Returned with the head they were fetched up to
+pub async fn fetch_gap_logs(
+    query: &dyn EvmLogQuery,
+    addresses: &[Address],
+    event_hashes: &[B256],
+    last_block: u64,
+    chunk_blocks: u64,
+    seen_logs: &Mutex<SeenLogs>,
+) -> Result<(u64, Vec<Log>)> {
+    let head = query.head().await?;
+    // the last block may have had logs after the ones delivered before the drop
+    let logs = fetch_logs_in_range(
+        query,
+        addresses,
+        event_hashes,
+        last_block,
+        head,
+        chunk_blocks,
+    )
+    .await?;
+
+    let mut seen_logs = seen_logs.lock().unwrap();
+    let logs = logs
+        .into_iter()
+        .filter(|log| match (log.block_hash, log.log_index) {
+            (Some(block_hash), Some(log_index)) => seen_logs.insert(block_hash, log_index),
+            _ => false,
+        })
+        .collect();
+
+    Ok((head, logs))
+}
+
+async fn fetch_logs_in_range(
+    query: &dyn EvmLogQuery,
+    addresses: &[Address],
+    event_hashes: &[B256],
+    from_block: u64,
+    head: u64,
+    chunk_blocks: u64,
+) -> Result<Vec<Log>> {
+    let chunk_blocks = chunk_blocks.max(1);
+
+    let mut logs = Vec::new();
+    let mut start = from_block;
+    while start <= head {
+        let end = start.saturating_add(chunk_blocks - 1).min(head);
+        logs.extend(query.logs(addresses, event_hashes, start, end).await?);
+        start = end + 1;
+    }
+
+    logs.sort_by_key(|log| (log.block_number, log.log_index));
+    Ok(logs)
+}
+
+#[cfg(test)]
+mod test {
+    use alloy_primitives::LogData;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    /// A chain with one matching log every few blocks, remembering the ranges it was asked for
+    struct MockChain {
+        head: u64,
+        logs: Vec<Log>,
+        ranges: Mutex<Vec<(u64, u64)>>,
+    }
+
+    impl MockChain {
+        fn new(head: u64, log_blocks: &[u64]) -> Self {
+            Self::with_logs(head, log_blocks.iter().map(|block| (*block, 0)))
+        }
+
+        fn with_logs(head: u64, log_positions: impl IntoIterator<Item = (u64, u64)>) -> Self {
+            let logs = log_positions
+                .into_iter()
+                .map(|(block_number, log_index)| Log {
+                    inner: alloy_primitives::Log {
+                        address: Address::repeat_byte(1),
+                        data: LogData::new_unchecked(
+                            vec![B256::repeat_byte(2)],
+                            Default::default(),
+                        ),
+                    },
+                    block_hash: Some(B256::with_last_byte(block_number as u8)),
+                    block_number: Some(block_number),
+                    block_timestamp: None,
+                    transaction_hash: Some(B256::repeat_byte(9)),
+                    transaction_index: Some(0),
+                    log_index: Some(log_index),
+                    removed: false,
+                })
+                .collect();
+
+            Self {
+                head,
+                logs,
+                ranges: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl EvmLogQuery for MockChain {
+        async fn head(&self) -> Result<u64> {
+            Ok(self.head)
+        }
+
+        async fn logs(
+            &self,
+            _addresses: &[Address],
+            _event_hashes: &[B256],
+            from_block: u64,
+            to_block: u64,
+        ) -> Result<Vec<Log>> {
+            self.ranges.lock().unwrap().push((from_block, to_block));
+            Ok(self
+                .logs
+                .iter()
+                .filter(|log| (from_block..=to_block).contains(&log.block_number.unwrap()))
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn fetches_historical_logs_in_chunks() {
+        let chain = MockChain::new(125, &[95, 101, 110, 120, 125]);
+
+        let logs = fetch_missed_logs(&chain, &[], &[], 100, 10).await.unwrap();
+
+        let blocks: Vec<u64> = logs.iter().map(|log| log.block_number.unwrap()).collect();
+        assert_eq!(blocks, vec![101, 110, 120, 125]);
+        assert_eq!(
+            *chain.ranges.lock().unwrap(),
+            vec![(100, 109), (110, 119), (120, 125)]
+        );
+    }
+
+    #[tokio::test]
+    async fn nothing_to_fetch_past_head() {
+        let chain = MockChain::new(100, &[100]);
+
+        let logs = fetch_missed_logs(&chain, &[], &[], 101, 10).await.unwrap();
+
+        assert!(logs.is_empty());
+        assert!(chain.ranges.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reconnect_gap_is_filled_exactly_once() {
+        // two logs per block, the subscription delivered everything up to block 10's first log
+        // and then dropped, while the chain moved on to block 15
+        let chain = MockChain::with_logs(15, (8..=15).flat_map(|block| [(block, 0), (block, 1)]));
+        let seen_logs = Mutex::new(SeenLogs::default());
+        for log in chain.logs.iter().take(5) {
+            assert!(seen_logs
+                .lock()
+                .unwrap()
+                .insert(log.block_hash.unwrap(), log.log_index.unwrap()));
+        }
+
+        let (head, logs) = fetch_gap_logs(&chain, &[], &[], 10, 2, &seen_logs)
+            .await
+            .unwrap();
+
+        assert_eq!(head, 15);
+        let positions: Vec<(u64, u64)> = logs
+            .iter()
+            .map(|log| (log.block_number.unwrap(), log.log_index.unwrap()))
+            .collect();
+        let mut expected = vec![(10, 1)];
+        expected.extend((11..=15).flat_map(|block| [(block, 0), (block, 1)]));
+        assert_eq!(positions, expected);
+
+        // and nothing is dispatched twice
+        let (_, logs) = fetch_gap_logs(&chain, &[], &[], 10, 2, &seen_logs)
+            .await
+            .unwrap();
+        assert!(logs.is_empty());
+    }
+
+    #[test]
+    fn progress_persists_and_only_advances() {
+        let dir = tempdir().unwrap();
+        let service_id = ServiceId::hash(b"service");
+        let workflow_id = WorkflowId::new("workflow").unwrap();
+        let position = |block_number, log_index| LogPosition {
+            block_number,
+            log_index,
+        };
+
+        let store = EvmProgressStore::open(dir.path()).unwrap();
+        assert_eq!(store.get(&service_id, &workflow_id), None);
+
+        store
+            .advance(&service_id, &workflow_id, position(10, 2))
+            .unwrap();
+        store
+            .advance(&service_id, &workflow_id, position(10, 1))
+            .unwrap();
+        assert_eq!(store.get(&service_id, &workflow_id), Some(position(10, 2)));
+
+        let reopened = EvmProgressStore::open(dir.path()).unwrap();
+        assert_eq!(
+            reopened.get(&service_id, &workflow_id),
+            Some(position(10, 2))
+        );
+    }
+}
@@ -45,8 +45,12 @@ pub enum TriggerError {
     IntervalStartAfterEnd,
     #[error("Config error: {0}")]
     Config(String),
+    #[error("EVM trigger progress: {0}")]
+    EvmProgress(anyhow::Error),
     #[error("Dispatcher command send error: {0}")]
     DispatcherCommandSendError(#[from] Box<crossbeam::channel::SendError<DispatcherCommand>>),
+    #[error("Dispatcher command send task failed: {0}")]
+    DispatcherSendTask(tokio::task::JoinError),
     #[error("Local command send error: {0}")]
     LocalCommandError(#[from] tokio::sync::mpsc::error::SendError<TriggerCommand>),
     #[error("Unable to convert event index: {0}")]
@@ -17,7 +17,10 @@ use crate::{
     },
 };
 
-use super::schedulers::{block_scheduler::BlockSchedulers, cron_scheduler::CronScheduler};
+use super::schedulers::{
+    block_scheduler::BlockSchedulers,
+    cron_scheduler::{CronJitter, CronScheduler},
+};
 
 #[allow(clippy::type_complexity)]
 pub struct LookupMaps {
@@ -52,10 +55,16 @@ pub struct LookupMaps {
     pub lookup_id: Arc<AtomicUsize>,
     /// cron scheduler
     pub cron_scheduler: CronScheduler,
+    /// per-operator offset applied to cron triggers, if enabled
+    pub cron_jitter: Option<CronJitter>,
 }
 
 impl LookupMaps {
-    pub fn new(services: Services, metrics: TriggerMetrics) -> Self {
+    pub fn new(
+        services: Services,
+        metrics: TriggerMetrics,
+        cron_jitter: Option<CronJitter>,
+    ) -> Self {
         Self {
             trigger_configs: Arc::new(RwLock::new(BTreeMap::new())),
             lookup_id: Arc::new(AtomicUsize::new(0)),
@@ -68,6 +77,7 @@ impl LookupMaps {
             triggers_by_service_workflow: Arc::new(RwLock::new(BTreeMap::new())),
             service_manager: Arc::new(RwLock::new(BiMap::new())),
             cron_scheduler: CronScheduler::default(),
+            cron_jitter,
             services,
             metrics,
         }
@@ -138,23 +148,20 @@ impl LookupMaps {
             .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
 
         match config.trigger.clone() {
-            Trigger::EvmContractEvent {
-                address,
-                chain,
-                event_hash,
-            } => {
-                let key = (chain.clone(), address, event_hash);
-                self.triggers_by_evm_contract_event
-                    .write()
-                    .unwrap()
-                    .entry(key)
-                    .or_default()
-                    .insert(lookup_id);
+            Trigger::EvmContractEvent { address, chain, .. } => {
+                let mut lock = self.triggers_by_evm_contract_event.write().unwrap();
+                // one entry per event, so a log fires the trigger if it matches any of them
+                for event_hash in config.trigger.evm_event_hashes() {
+                    lock.entry((chain.clone(), address, event_hash))
+                        .or_default()
+                        .insert(lookup_id);
+                }
             }
             Trigger::CosmosContractEvent {
                 address,
                 chain,
                 event_type,
+                ..
             } => {
                 let key = (chain.clone(), address.clone(), event_type.clone());
                 self.triggers_by_cosmos_contract_event
@@ -185,13 +192,14 @@ impl LookupMaps {
                 start_time,
                 end_time,
             } => {
+                let mut state = CronIntervalState::new(lookup_id, &schedule, start_time, end_time)?;
+                if let Some(jitter) = &self.cron_jitter {
+                    state =
+                        state.with_jitter(jitter.offset(&config.service_id, &config.workflow_id));
+                }
+
                 // Add directly to the cron scheduler
-                self.cron_scheduler
-                    .lock()
-                    .unwrap()
-                    .add_trigger(CronIntervalState::new(
-                        lookup_id, &schedule, start_time, end_time,
-                    )?)?;
+                self.cron_scheduler.lock().unwrap().add_trigger(state)?;
             }
             Trigger::AtProtoEvent {
                 collection,
@@ -224,7 +232,7 @@ impl LookupMaps {
                     .or_default()
                     .insert(lookup_id);
             }
-            Trigger::Manual => {}
+            Trigger::Manual | Trigger::Webhook { .. } => {}
         }
 
         // adding it to our lookups is the same, regardless of type
@@ -271,15 +279,18 @@ impl LookupMaps {
         if let Some(trigger) = trigger_type {
             match trigger {
                 Trigger::EvmContractEvent {
-                    address,
-                    chain,
-                    event_hash,
+                    ref address,
+                    ref chain,
+                    ..
                 } => {
                     let mut lock = self.triggers_by_evm_contract_event.write().unwrap();
-                    if let Some(set) = lock.get_mut(&(chain.clone(), address, event_hash)) {
-                        set.remove(&lookup_id);
-                        if set.is_empty() {
-                            lock.remove(&(chain, address, event_hash));
+                    for event_hash in trigger.evm_event_hashes() {
+                        let key = (chain.clone(), *address, event_hash);
+                        if let Some(set) = lock.get_mut(&key) {
+                            set.remove(&lookup_id);
+                            if set.is_empty() {
+                                lock.remove(&key);
+                            }
                         }
                     }
                 }
@@ -287,6 +298,7 @@ impl LookupMaps {
                     address,
                     chain,
                     event_type,
+                    ..
                 } => {
                     let mut lock = self.triggers_by_cosmos_contract_event.write().unwrap();
                     if let Some(set) =
@@ -311,7 +323,7 @@ impl LookupMaps {
                         .unwrap()
                         .remove_trigger(lookup_id);
                 }
-                Trigger::Manual => {}
+                Trigger::Manual | Trigger::Webhook { .. } => {}
                 Trigger::AtProtoEvent {
                     collection,
                     repo_did,
@@ -382,23 +394,14 @@ impl LookupMaps {
             for lookup_id in &lookup_ids {
                 if let Some(config) = trigger_configs.get(lookup_id) {
                     match &config.trigger {
-                        Trigger::EvmContractEvent {
-                            address,
-                            chain,
-                            event_hash,
-                        } => {
-                            if let Some(set) = triggers_by_evm_contract_event.get_mut(&(
-                                chain.clone(),
-                                *address,
-                                *event_hash,
-                            )) {
-                                set.remove(lookup_id);
-                                if set.is_empty() {
-                                    triggers_by_evm_contract_event.remove(&(
-                                        chain.clone(),
-                                        *address,
-                                        *event_hash,
-                                    ));
+                        Trigger::EvmContractEvent { address, chain, .. } => {
+                            for event_hash in config.trigger.evm_event_hashes() {
+                                let key = (chain.clone(), *address, event_hash);
+                                if let Some(set) = triggers_by_evm_contract_event.get_mut(&key) {
+                                    set.remove(lookup_id);
+                                    if set.is_empty() {
+                                        triggers_by_evm_contract_event.remove(&key);
+                                    }
                                 }
                             }
                         }
@@ -406,6 +409,7 @@ impl LookupMaps {
                             address,
                             chain,
                             event_type,
+                            ..
                         } => {
                             if let Some(set) = triggers_by_cosmos_contract_event.get_mut(&(
                                 chain.clone(),
@@ -434,7 +438,7 @@ impl LookupMaps {
                                 .unwrap()
                                 .remove_trigger(*lookup_id);
                         }
-                        Trigger::Manual => {}
+                        Trigger::Manual | Trigger::Webhook { .. } => {}
                         Trigger::AtProtoEvent {
                             collection,
                             repo_did,
@@ -0,0 +1,155 @@
+//! Enforces a workflow's `min_interval_ms`, dropping triggers that arrive sooner than that after
+//! the last one it let through.
+//!
+//! Drops aren't logged one by one, a burst of them is reported in a single debug line once the
+//! next trigger for the workflow gets through.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use wavs_types::{Service, ServiceId, TriggerConfig, WorkflowId};
+
+struct WorkflowLimit {
+    min_interval: Duration,
+    last_fired: Option<Instant>,
+    dropped: u64,
+}
+
+#[derive(Clone, Default)]
+pub struct TriggerRateLimiter {
+    limits: Arc<Mutex<HashMap<(ServiceId, WorkflowId), WorkflowLimit>>>,
+}
+
+impl TriggerRateLimiter {
+    pub fn add_service(&self, service: &Service) {
+        let mut limits = self.limits.lock().unwrap();
+        let service_id = service.id();
+        limits.retain(|(id, _), _| *id != service_id);
+
+        for (workflow_id, workflow) in &service.workflows {
+            if let Some(ms) = workflow.min_interval_ms.filter(|ms| *ms > 0) {
+                limits.insert(
+                    (service_id.clone(), workflow_id.clone()),
+                    WorkflowLimit {
+                        min_interval: Duration::from_millis(ms),
+                        last_fired: None,
+                        dropped: 0,
+                    },
+                );
+            }
+        }
+    }
+
+    pub fn remove_service(&self, service_id: &ServiceId) {
+        self.limits
+            .lock()
+            .unwrap()
+            .retain(|(id, _), _| id != service_id);
+    }
+
+    /// Whether a trigger for this workflow arriving at `now` should be dispatched
+    pub fn allow(&self, config: &TriggerConfig, now: Instant) -> bool {
+        let mut limits = self.limits.lock().unwrap();
+        let Some(limit) = limits.get_mut(&(config.service_id.clone(), config.workflow_id.clone()))
+        else {
+            return true;
+        };
+
+        if let Some(last_fired) = limit.last_fired {
+            if now.saturating_duration_since(last_fired) < limit.min_interval {
+                limit.dropped += 1;
+                return false;
+            }
+        }
+
+        if limit.dropped > 0 {
+            tracing::debug!(
+                "Dropped {} triggers for service {} workflow {} arriving within {}ms of the last one",
+                limit.dropped,
+                config.service_id,
+                config.workflow_id,
+                limit.min_interval.as_millis(),
+            );
+            limit.dropped = 0;
+        }
+        limit.last_fired = Some(now);
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use wavs_types::{ComponentDigest, ComponentSource, ServiceManager, Submit, Trigger};
+
+    use super::*;
+
+    fn service(min_interval_ms: Option<u64>) -> Service {
+        let mut service = Service::new_simple(
+            None,
+            Trigger::Manual,
+            ComponentSource::Digest(ComponentDigest::hash(b"component")),
+            Submit::None,
+            ServiceManager::Evm {
+                chain: "evm:anvil".parse().unwrap(),
+                address: Default::default(),
+            },
+        );
+        for workflow in service.workflows.values_mut() {
+            workflow.min_interval_ms = min_interval_ms;
+        }
+        service
+    }
+
+    fn trigger_config(service: &Service) -> TriggerConfig {
+        TriggerConfig {
+            service_id: service.id(),
+            workflow_id: service.workflows.keys().next().unwrap().clone(),
+            trigger: Trigger::Manual,
+        }
+    }
+
+    #[test]
+    fn at_most_one_trigger_per_interval() {
+        let service = service(Some(100));
+        let config = trigger_config(&service);
+        let limiter = TriggerRateLimiter::default();
+        limiter.add_service(&service);
+
+        // a tight loop of triggers, one every millisecond for a second
+        let start = Instant::now();
+        let fired: Vec<Duration> = (0..1000)
+            .map(Duration::from_millis)
+            .filter(|offset| limiter.allow(&config, start + *offset))
+            .collect();
+
+        assert_eq!(fired.len(), 10);
+        for pair in fired.windows(2) {
+            assert!(pair[1] - pair[0] >= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn unlimited_without_min_interval() {
+        let limited = service(Some(100));
+        let limiter = TriggerRateLimiter::default();
+        limiter.add_service(&limited);
+
+        let unlimited = service(None);
+        let mut config = trigger_config(&unlimited);
+        config.service_id = ServiceId::hash(b"other");
+
+        let now = Instant::now();
+        assert!((0..10).all(|_| limiter.allow(&config, now)));
+
+        // removing the service lifts its limit
+        let config = trigger_config(&limited);
+        assert!(limiter.allow(&config, now));
+        assert!(!limiter.allow(&config, now));
+        limiter.remove_service(&limited.id());
+        assert!(limiter.allow(&config, now));
+    }
+}
@@ -100,6 +100,10 @@ impl IntervalState for BlockIntervalState {
         }
     }
 
+    fn reports_final(&self) -> bool {
+        true
+    }
+
     fn start_time(&self) -> Option<Self::Time> {
         self._start_time
     }
@@ -1,7 +1,8 @@
 use std::{str::FromStr, sync::Arc};
 
+use alloy_primitives::keccak256;
 use chrono::Utc;
-use wavs_types::Timestamp;
+use wavs_types::{Credential, ServiceId, Timestamp, WorkflowId};
 
 use crate::subsystems::trigger::{error::TriggerError, lookup::LookupId};
 
@@ -11,10 +12,52 @@ impl IntervalTime for Timestamp {}
 
 pub type CronScheduler = Arc<std::sync::Mutex<IntervalScheduler<Timestamp, CronIntervalState>>>;
 
+/// Spreads cron executions of the same workflow across operators.
+///
+/// Each operator derives a stable offset within `window` from its own signing mnemonic,
+/// so operators running the same schedule don't all hit shared upstreams on the exact tick.
+/// Only the firing is delayed, the scheduled time reported to components is unchanged.
+#[derive(Clone)]
+pub struct CronJitter {
+    seed: [u8; 32],
+    window: std::time::Duration,
+}
+
+impl CronJitter {
+    pub fn new(operator_mnemonic: &Credential, window: std::time::Duration) -> Self {
+        let mut preimage = b"wavs-cron-jitter:".to_vec();
+        preimage.extend_from_slice(operator_mnemonic.as_str().as_bytes());
+
+        Self {
+            seed: keccak256(preimage).0,
+            window,
+        }
+    }
+
+    /// Deterministic offset for this operator and workflow, in `[0, window)`
+    pub fn offset(&self, service_id: &ServiceId, workflow_id: &WorkflowId) -> std::time::Duration {
+        let window_nanos = self.window.as_nanos() as u64;
+        if window_nanos == 0 {
+            return std::time::Duration::ZERO;
+        }
+
+        let mut preimage = self.seed.to_vec();
+        preimage.extend_from_slice(service_id.as_ref());
+        preimage.extend_from_slice(workflow_id.as_bytes());
+        let hash = keccak256(preimage);
+        let value = u64::from_be_bytes(hash.0[..8].try_into().unwrap());
+
+        std::time::Duration::from_nanos(value % window_nanos)
+    }
+}
+
 pub struct CronIntervalState {
     pub schedule: cron::Schedule,
     iterator: Option<cron::OwnedScheduleIterator<Utc>>,
     next_trigger_time: Option<Timestamp>,
+    /// The scheduled time of the last hit, and how many scheduled times it skipped over
+    last_hit: Option<(Timestamp, u32)>,
+    jitter_nanos: u64,
     _lookup_id: LookupId,
     _start_time: Option<Timestamp>,
     _end_time: Option<Timestamp>,
@@ -36,12 +79,25 @@ impl CronIntervalState {
             schedule,
             iterator: None,
             next_trigger_time: None,
+            last_hit: None,
+            jitter_nanos: 0,
             _lookup_id: lookup_id,
             _start_time: start_time,
             _end_time: end_time,
         })
     }
 
+    /// Delay each firing by `jitter` past its scheduled time
+    pub fn with_jitter(mut self, jitter: std::time::Duration) -> Self {
+        self.jitter_nanos = jitter.as_nanos() as u64;
+        self
+    }
+
+    fn fire_time(&self) -> Option<Timestamp> {
+        self.next_trigger_time
+            .map(|time| Timestamp::from_nanos(time.as_nanos().saturating_add(self.jitter_nanos)))
+    }
+
     pub fn set_next_trigger_time(&mut self) {
         if let Some(iterator) = self.iterator.as_mut() {
             // Try to get the next time from the iterator
@@ -95,19 +151,46 @@ impl IntervalState for CronIntervalState {
         // Set up iterator for upcoming events
         self.iterator = Some(self.schedule.after_owned(effective_now));
         self.set_next_trigger_time();
-        self.next_trigger_time
+        self.fire_time()
     }
 
     fn interval_hit(&mut self, now: Self::Time) -> Option<Option<Self::Time>> {
-        if let Some(next_trigger_time) = self.next_trigger_time {
-            if now >= next_trigger_time {
-                // We've hit this trigger time, calculate the next one
-                self.set_next_trigger_time();
-                return Some(self.next_trigger_time);
+        let fire_time = self.fire_time()?;
+        if now < fire_time {
+            return None;
+        }
+
+        // We've hit this trigger time, calculate the next one
+        let mut scheduled = self.next_trigger_time?;
+        let mut missed_ticks = 0;
+        self.set_next_trigger_time();
+
+        // If we fell behind, fire once for the latest tick that's due rather than
+        // catching up on every one in a burst, and report how many were skipped
+        while let Some(next) = self.next_trigger_time {
+            if self.fire_time().is_some_and(|fire_time| fire_time > now) {
+                break;
             }
+            scheduled = next;
+            missed_ticks += 1;
+            self.set_next_trigger_time();
+        }
+
+        self.last_hit = Some((scheduled, missed_ticks));
+        Some(self.fire_time())
+    }
+
+    fn scheduled_time(&self, fire_time: Self::Time) -> Self::Time {
+        match self.last_hit {
+            Some((scheduled, _)) => scheduled,
+            None => Timestamp::from_nanos(fire_time.as_nanos().saturating_sub(self.jitter_nanos)),
         }
+    }
 
-        None
+    fn missed_ticks(&self) -> u32 {
+        self.last_hit
+            .map(|(_, missed_ticks)| missed_ticks)
+            .unwrap_or_default()
     }
 
     fn start_time(&self) -> Option<Self::Time> {
@@ -16,6 +16,15 @@ pub struct IntervalScheduler<T: IntervalTime, S: IntervalState<Time = T>> {
 
 pub trait IntervalTime: Ord + Copy {}
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntervalHit<T> {
+    pub lookup_id: LookupId,
+    pub scheduled_time: T,
+    pub missed_ticks: u32,
+    /// The trigger reached its end time and has been removed, this is the last hit for it
+    pub is_final: bool,
+}
+
 pub trait IntervalState {
     /// The unit of time this scheduler works in
     type Time: IntervalTime;
@@ -33,6 +42,23 @@ pub trait IntervalState {
     // allow the possibility that the window was missed
     fn initialize(&mut self, now: Self::Time) -> Option<Self::Time>;
 
+    // the time reported for a hit that fired at `fire_time`
+    // these only differ when the state delays firing past the real schedule (e.g. cron jitter)
+    fn scheduled_time(&self, fire_time: Self::Time) -> Self::Time {
+        fire_time
+    }
+
+    // how many scheduled times the last hit skipped over because the scheduler fell behind
+    fn missed_ticks(&self) -> u32 {
+        0
+    }
+
+    // whether reaching the end time should always produce a final hit,
+    // even if the end time isn't on the interval
+    fn reports_final(&self) -> bool {
+        false
+    }
+
     fn start_time(&self) -> Option<Self::Time>;
 
     fn end_time(&self) -> Option<Self::Time>;
@@ -81,6 +107,14 @@ impl<T: IntervalTime, S: IntervalState<Time = T>> IntervalScheduler<T, S> {
 
     /// Call this on each "tick"
     pub fn tick(&mut self, now: T) -> Vec<(LookupId, T)> {
+        self.tick_hits(now)
+            .into_iter()
+            .map(|hit| (hit.lookup_id, hit.scheduled_time))
+            .collect()
+    }
+
+    /// Like [`Self::tick`], with the details of each hit
+    pub fn tick_hits(&mut self, now: T) -> Vec<IntervalHit<T>> {
         let mut results = Vec::new();
         // Add all the unadded triggers to the scheduler
         for mut state in self.unadded_triggers.drain(..) {
@@ -89,6 +123,7 @@ impl<T: IntervalTime, S: IntervalState<Time = T>> IntervalScheduler<T, S> {
             // it's up to the specific scheduler to manage its
             // exact interval timing
             if let Some(next_time) = state.initialize(now) {
+                let next_time = Self::due_time(&state, next_time);
                 self.triggers.entry(next_time).or_default().push(state);
             }
         }
@@ -109,9 +144,16 @@ impl<T: IntervalTime, S: IntervalState<Time = T>> IntervalScheduler<T, S> {
             // gives us "clear empty keys" in the BTreeMap as well
             // which is likely a bigger performance win overall
             for mut state in states.drain(..) {
+                let mut hit_index = None;
                 let mut re_insert_time = match state.interval_hit(now) {
                     Some(new_next_time) => {
-                        results.push((state.lookup_id(), next_time));
+                        hit_index = Some(results.len());
+                        results.push(IntervalHit {
+                            lookup_id: state.lookup_id(),
+                            scheduled_time: state.scheduled_time(next_time),
+                            missed_ticks: state.missed_ticks(),
+                            is_final: false,
+                        });
                         // this is the new next time as determined by the scheduler
                         // and it may be None if the trigger has ended
                         new_next_time
@@ -129,12 +171,24 @@ impl<T: IntervalTime, S: IntervalState<Time = T>> IntervalScheduler<T, S> {
                 if let Some(end_time) = state.end_time() {
                     if now >= end_time {
                         re_insert_time = None;
+
+                        if state.reports_final() {
+                            match hit_index {
+                                Some(index) => results[index].is_final = true,
+                                None => results.push(IntervalHit {
+                                    lookup_id: state.lookup_id(),
+                                    scheduled_time: now,
+                                    missed_ticks: 0,
+                                    is_final: true,
+                                }),
+                            }
+                        }
                     }
                 }
 
                 if let Some(next_time) = re_insert_time {
                     // if the trigger has any next time, re-insert it
-                    re_add.push((next_time, state));
+                    re_add.push((Self::due_time(&state, next_time), state));
                 }
             }
         }
@@ -146,6 +200,15 @@ impl<T: IntervalTime, S: IntervalState<Time = T>> IntervalScheduler<T, S> {
         results
     }
 
+    // states that report a final hit are woken up at their end time
+    // even when the interval would step right over it
+    fn due_time(state: &S, next_time: T) -> T {
+        match state.end_time() {
+            Some(end_time) if state.reports_final() => next_time.min(end_time),
+            _ => next_time,
+        }
+    }
+
     /// Totally remove a trigger (called from the TriggerManager, as opposed to local expirey)
     pub fn remove_trigger(&mut self, id: LookupId) -> bool {
         let existed = self.trigger_ids.remove(&id);
@@ -25,6 +25,8 @@ pub struct CronHit {
     pub lookup_ids: Vec<LookupId>,
     /// The scheduled time when these triggers should fire
     pub scheduled_time: Timestamp,
+    /// Scheduled times these triggers skipped over to get here
+    pub missed_ticks: u32,
 }
 
 pub type MultiplexedStream = SelectAll<
@@ -52,6 +54,19 @@ pub enum StreamTriggers {
         block_timestamp: Option<u64>,
         log_index: u64,
     },
+    /// A log we may already have triggered on was removed by a reorg
+    EvmLogRemoved {
+        chain: ChainKey,
+        log: Box<alloy_rpc_types_eth::Log>,
+        block_hash: alloy_primitives::BlockHash,
+        tx_hash: alloy_primitives::TxHash,
+        log_index: u64,
+    },
+    /// The websocket came back and resubscribed, logs after `last_block` may have been missed
+    EvmReconnected {
+        chain: ChainKey,
+        last_block: u64,
+    },
     // We need a separate stream for EVM block interval triggers
     EvmBlock {
         chain: ChainKey,
@@ -25,24 +25,25 @@ pub async fn start_cron_stream(
     // Process cron triggers on each interval tick
     let cron_stream = Box::pin(interval_stream.map(move |_| {
         let current_time = Timestamp::now();
-        let trigger_results = cron_scheduler.lock().unwrap().tick(current_time);
+        let trigger_results = cron_scheduler.lock().unwrap().tick_hits(current_time);
 
-        // Group triggers by their scheduled timestamp
-        let mut time_to_lookup_ids: std::collections::BTreeMap<Timestamp, Vec<_>> =
+        // Group triggers by their scheduled timestamp and missed tick count
+        let mut time_to_lookup_ids: std::collections::BTreeMap<(Timestamp, u32), Vec<_>> =
             std::collections::BTreeMap::new();
-        for (lookup_id, scheduled_time) in trigger_results {
+        for hit in trigger_results {
             time_to_lookup_ids
-                .entry(scheduled_time)
+                .entry((hit.scheduled_time, hit.missed_ticks))
                 .or_default()
-                .push(lookup_id);
+                .push(hit.lookup_id);
         }
 
         // Convert the grouped results into CronHit structs
         let hits: Vec<CronHit> = time_to_lookup_ids
             .into_iter()
-            .map(|(scheduled_time, lookup_ids)| CronHit {
+            .map(|((scheduled_time, missed_ticks), lookup_ids)| CronHit {
                 lookup_ids,
                 scheduled_time,
+                missed_ticks,
             })
             .collect();
 
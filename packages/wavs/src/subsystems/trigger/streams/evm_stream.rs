@@ -1,10 +1,17 @@
+pub mod block_timestamps;
 pub mod client;
 #[cfg(test)]
 mod client_tests;
+pub mod seen_logs;
 
-use alloy_rpc_types_eth::Log;
+use alloy_provider::{DynProvider, Provider};
+use alloy_rpc_types_eth::{Filter, Log};
 use futures::{Stream, StreamExt};
-use std::pin::Pin;
+use std::{
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use utils::telemetry::TriggerMetrics;
 use wavs_types::ChainKey;
@@ -12,10 +19,16 @@ use wavs_types::ChainKey;
 use crate::subsystems::trigger::error::TriggerError;
 
 use super::StreamTriggers;
+use block_timestamps::BlockTimestamps;
+use seen_logs::SeenLogs;
 
 pub async fn start_evm_event_stream(
     chain: ChainKey,
     log_stream: UnboundedReceiverStream<Log>,
+    block_timestamps: Option<BlockTimestamps>,
+    // kept across reconnects, since it lives as long as the stream does,
+    // and shared with the backfill so replayed logs aren't dispatched again
+    seen_logs: Arc<Mutex<SeenLogs>>,
     _metrics: TriggerMetrics,
 ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamTriggers, TriggerError>> + Send>>, TriggerError>
 {
@@ -23,13 +36,47 @@ pub async fn start_evm_event_stream(
 
     let event_stream = Box::pin(log_stream.filter_map(move |log| {
         let chain = chain.clone();
+        let block_timestamps = block_timestamps.clone();
+        let seen_logs = seen_logs.clone();
         async move {
             if log.removed {
                 tracing::warn!("Reorg removed log: {:?}", log);
-                return None;
+                return match (log.block_hash, log.transaction_hash, log.log_index) {
+                    (Some(block_hash), Some(tx_hash), Some(log_index)) => {
+                        seen_logs.lock().unwrap().remove(block_hash, log_index);
+                        Some(Ok(StreamTriggers::EvmLogRemoved {
+                            chain,
+                            log: Box::new(log),
+                            block_hash,
+                            tx_hash,
+                            log_index,
+                        }))
+                    }
+                    _ => None,
+                };
             }
 
-            let block_timestamp = log.block_timestamp;
+            if let (Some(block_hash), Some(log_index)) = (log.block_hash, log.log_index) {
+                if !seen_logs.lock().unwrap().insert(block_hash, log_index) {
+                    tracing::debug!(
+                        "Dropping re-delivered log {} in block {} on {}",
+                        log_index,
+                        block_hash,
+                        chain
+                    );
+                    return None;
+                }
+            }
+
+            let block_timestamp = match (log.block_timestamp, &block_timestamps) {
+                (None, Some(block_timestamps)) => match (log.block_number, log.block_hash) {
+                    (Some(block_number), Some(block_hash)) => {
+                        block_timestamps.resolve(block_number, block_hash).await
+                    }
+                    _ => None,
+                },
+                (block_timestamp, _) => block_timestamp,
+            };
 
             match (
                 log.block_hash,
@@ -80,3 +127,179 @@ pub async fn start_evm_block_stream(
 
     Ok(block_stream)
 }
+
+/// Follows the chain by polling `provider` for new blocks instead of subscribing over a
+/// websocket, for providers that can't push, like an in-memory chain in tests.
+/// Returns the block heights and all of their logs, starting after the current head.
+/// Nothing is filtered here, the lookup maps drop the logs no trigger is watching
+pub fn start_evm_polling_streams(
+    chain: ChainKey,
+    provider: DynProvider,
+    interval: Duration,
+) -> (UnboundedReceiverStream<u64>, UnboundedReceiverStream<Log>) {
+    let (block_height_tx, block_height_rx) = tokio::sync::mpsc::unbounded_channel();
+    let (log_tx, log_rx) = tokio::sync::mpsc::unbounded_channel();
+
+    tokio::spawn(async move {
+        let mut last_block = None;
+        loop {
+            match provider.get_block_number().await {
+                Ok(head) => {
+                    let from_block = match last_block {
+                        Some(last_block) => last_block + 1,
+                        None => head + 1,
+                    };
+                    for block_number in from_block..=head {
+                        let filter = Filter::new()
+                            .from_block(block_number)
+                            .to_block(block_number);
+                        match provider.get_logs(&filter).await {
+                            Ok(logs) => {
+                                for log in logs {
+                                    if log_tx.send(log).is_err() {
+                                        return;
+                                    }
+                                }
+                            }
+                            Err(err) => {
+                                tracing::error!(
+                                    "Failed to poll logs of block {block_number} on {chain}: {:?}",
+                                    err
+                                );
+                                break;
+                            }
+                        }
+                        if block_height_tx.send(block_number).is_err() {
+                            return;
+                        }
+                        last_block = Some(block_number);
+                    }
+                    last_block.get_or_insert(head);
+                }
+                Err(err) => {
+                    tracing::error!("Failed to poll block number on {chain}: {:?}", err);
+                }
+            }
+
+            tokio::time::sleep(interval).await;
+        }
+    });
+
+    (
+        UnboundedReceiverStream::new(block_height_rx),
+        UnboundedReceiverStream::new(log_rx),
+    )
+}
+
+pub fn start_evm_reconnect_stream(
+    chain: ChainKey,
+    reconnect_stream: UnboundedReceiverStream<u64>,
+) -> Pin<Box<dyn Stream<Item = Result<StreamTriggers, TriggerError>> + Send>> {
+    Box::pin(reconnect_stream.map(move |last_block| {
+        Ok(StreamTriggers::EvmReconnected {
+            chain: chain.clone(),
+            last_block,
+        })
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use alloy_primitives::{Address, LogData, B256};
+
+    use super::*;
+
+    fn log(block_hash: B256, log_index: u64, removed: bool) -> Log {
+        Log {
+            inner: alloy_primitives::Log {
+                address: Address::ZERO,
+                data: LogData::new_unchecked(vec![B256::ZERO], Default::default()),
+            },
+            block_hash: Some(block_hash),
+            block_number: Some(1),
+            block_timestamp: Some(1_000),
+            transaction_hash: Some(B256::repeat_byte(9)),
+            transaction_index: Some(0),
+            log_index: Some(log_index),
+            removed,
+        }
+    }
+
+    async fn collect(logs: Vec<Log>) -> Vec<StreamTriggers> {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let stream = start_evm_event_stream(
+            ChainKey::new("evm:test").unwrap(),
+            UnboundedReceiverStream::new(rx),
+            None,
+            Default::default(),
+            TriggerMetrics::new(opentelemetry::global::meter("test")),
+        )
+        .await
+        .unwrap();
+
+        for log in logs {
+            tx.send(log).unwrap();
+        }
+        drop(tx);
+
+        stream.map(|trigger| trigger.unwrap()).collect().await
+    }
+
+    #[tokio::test]
+    async fn drops_redelivered_logs() {
+        let block = B256::repeat_byte(1);
+
+        // the subscription reconnected and replayed the first two logs
+        let triggers = collect(vec![
+            log(block, 0, false),
+            log(block, 1, false),
+            log(block, 0, false),
+            log(block, 1, false),
+            log(block, 2, false),
+        ])
+        .await;
+
+        let log_indexes: Vec<u64> = triggers
+            .iter()
+            .map(|trigger| match trigger {
+                StreamTriggers::Evm { log_index, .. } => *log_index,
+                other => panic!("unexpected trigger: {other:?}"),
+            })
+            .collect();
+        assert_eq!(log_indexes, vec![0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn retracts_removed_logs() {
+        let block = B256::repeat_byte(1);
+
+        let triggers = collect(vec![
+            log(block, 0, false),
+            log(block, 0, true),
+            // the block came back after the reorg, so the log is new again
+            log(block, 0, false),
+        ])
+        .await;
+
+        assert_eq!(triggers.len(), 3);
+        assert!(matches!(
+            triggers[0],
+            StreamTriggers::Evm { log_index: 0, .. }
+        ));
+        match &triggers[1] {
+            StreamTriggers::EvmLogRemoved {
+                block_hash,
+                log_index,
+                ..
+            } => {
+                assert_eq!(*block_hash, block);
+                assert_eq!(*log_index, 0);
+            }
+            other => panic!("expected a retraction, got {other:?}"),
+        }
+        assert!(matches!(
+            triggers[2],
+            StreamTriggers::Evm { log_index: 0, .. }
+        ));
+    }
+}
@@ -0,0 +1,220 @@
+//! Some nodes don't include `blockTimestamp` in the logs they push over the subscription.
+//! Rather than forwarding those triggers without a timestamp, we look the block up and keep
+//! the last few timestamps around, since a burst of logs usually comes from the same block.
+//! They're kept by block hash, after a reorg the same number can point to another block.
+
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use alloy_primitives::BlockHash;
+use alloy_provider::{DynProvider, Provider};
+use alloy_rpc_types_eth::{BlockId, BlockNumberOrTag};
+use async_trait::async_trait;
+use lru::LruCache;
+use utils::{config::EvmChainConfigExt, tls::RpcTls};
+use wavs_types::EvmChainConfig;
+
+const CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(16).unwrap();
+
+#[async_trait]
+pub trait BlockTimestampQuery: Send + Sync {
+    /// `None` if the node doesn't know about the block
+    async fn block_timestamp(&self, block: BlockId) -> anyhow::Result<Option<u64>>;
+}
+
+#[async_trait]
+impl BlockTimestampQuery for DynProvider {
+    async fn block_timestamp(&self, block: BlockId) -> anyhow::Result<Option<u64>> {
+        Ok(self
+            .get_block(block)
+            .await?
+            .map(|block| block.header.timestamp))
+    }
+}
+
+#[derive(Clone)]
+pub struct BlockTimestamps {
+    query: Arc<dyn BlockTimestampQuery>,
+    cache: Arc<Mutex<LruCache<BlockHash, u64>>>,
+}
+
+impl BlockTimestamps {
+    pub fn new(query: impl BlockTimestampQuery + 'static) -> Self {
+        Self {
+            query: Arc::new(query),
+            cache: Arc::new(Mutex::new(LruCache::new(CACHE_SIZE))),
+        }
+    }
+
+    /// Queries through the chain's first query endpoint. If no provider can be made,
+    /// logs are forwarded with whatever timestamp the node gave us
+    pub async fn connect(chain_config: &EvmChainConfig, tls: Option<&RpcTls>) -> Option<Self> {
+        let endpoint = match chain_config.query_client_endpoints() {
+            Ok(endpoints) => endpoints.into_iter().next()?,
+            Err(err) => {
+                tracing::warn!("No endpoint for backfilling block timestamps: {:?}", err);
+                return None;
+            }
+        };
+
+        match endpoint.to_provider_with_tls(tls).await {
+            Ok(provider) => Some(Self::new(provider)),
+            Err(err) => {
+                tracing::warn!(
+                    "Failed to connect to {} for backfilling block timestamps: {:?}",
+                    endpoint,
+                    err
+                );
+                None
+            }
+        }
+    }
+
+    /// Falls back to the parent block's timestamp if the block itself can't be queried
+    pub async fn resolve(&self, block_number: u64, block_hash: BlockHash) -> Option<u64> {
+        if let Some(timestamp) = self.cache.lock().unwrap().get(&block_hash) {
+            return Some(*timestamp);
+        }
+
+        match self.query.block_timestamp(BlockId::hash(block_hash)).await {
+            Ok(Some(timestamp)) => {
+                self.cache.lock().unwrap().put(block_hash, timestamp);
+                return Some(timestamp);
+            }
+            Ok(None) => {
+                tracing::warn!(
+                    "Block {} ({}) not found while backfilling log timestamp",
+                    block_number,
+                    block_hash
+                );
+            }
+            Err(err) => {
+                tracing::warn!(
+                    "Failed to query block {} ({}) for log timestamp: {:?}",
+                    block_number,
+                    block_hash,
+                    err
+                );
+            }
+        }
+
+        // only known by number, so not cached
+        let parent = block_number.checked_sub(1)?;
+        match self
+            .query
+            .block_timestamp(BlockNumberOrTag::Number(parent).into())
+            .await
+        {
+            Ok(Some(timestamp)) => Some(timestamp),
+            _ => {
+                tracing::warn!(
+                    "No timestamp available for block {} or its parent",
+                    block_number
+                );
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, sync::atomic::AtomicUsize};
+
+    use alloy_primitives::{Address, LogData, B256};
+    use alloy_rpc_types_eth::Log;
+    use futures::StreamExt;
+    use tokio_stream::wrappers::UnboundedReceiverStream;
+    use utils::telemetry::TriggerMetrics;
+    use wavs_types::ChainKey;
+
+    use super::*;
+    use crate::subsystems::trigger::streams::{evm_stream::start_evm_event_stream, StreamTriggers};
+
+    /// Knows the timestamps of some blocks, by number and hash, and fails for the rest
+    #[derive(Default)]
+    struct MockBlocks {
+        by_number: HashMap<u64, u64>,
+        by_hash: HashMap<BlockHash, u64>,
+        queries: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl BlockTimestampQuery for MockBlocks {
+        async fn block_timestamp(&self, block: BlockId) -> anyhow::Result<Option<u64>> {
+            self.queries
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let timestamp = match block {
+                BlockId::Hash(hash) => self.by_hash.get(&hash.block_hash),
+                BlockId::Number(BlockNumberOrTag::Number(number)) => self.by_number.get(&number),
+                _ => None,
+            };
+            timestamp
+                .copied()
+                .map(Some)
+                .ok_or_else(|| anyhow::anyhow!("unknown block {block:?}"))
+        }
+    }
+
+    fn log_without_timestamp(block_number: u64, block_hash: BlockHash, log_index: u64) -> Log {
+        Log {
+            inner: alloy_primitives::Log {
+                address: Address::ZERO,
+                data: LogData::new_unchecked(vec![B256::ZERO], Default::default()),
+            },
+            block_hash: Some(block_hash),
+            block_number: Some(block_number),
+            block_timestamp: None,
+            transaction_hash: Some(B256::ZERO),
+            transaction_index: Some(0),
+            log_index: Some(log_index),
+            removed: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn backfills_missing_timestamps() {
+        let known_hash = B256::repeat_byte(1);
+        let unknown_hash = B256::repeat_byte(2);
+        let queries = Arc::new(AtomicUsize::new(0));
+
+        let blocks = MockBlocks {
+            by_hash: HashMap::from([(known_hash, 1_000)]),
+            by_number: HashMap::from([(9, 990)]),
+            queries: queries.clone(),
+        };
+
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut stream = start_evm_event_stream(
+            ChainKey::new("evm:test").unwrap(),
+            UnboundedReceiverStream::new(rx),
+            Some(BlockTimestamps::new(blocks)),
+            Default::default(),
+            TriggerMetrics::new(opentelemetry::global::meter("test")),
+        )
+        .await
+        .unwrap();
+
+        // the first log queries the block, the second is served from the cache
+        tx.send(log_without_timestamp(11, known_hash, 0)).unwrap();
+        tx.send(log_without_timestamp(11, known_hash, 1)).unwrap();
+        // the block can't be queried, so its parent's timestamp is used instead
+        tx.send(log_without_timestamp(10, unknown_hash, 0)).unwrap();
+        drop(tx);
+
+        let mut timestamps = Vec::new();
+        while let Some(trigger) = stream.next().await {
+            match trigger.unwrap() {
+                StreamTriggers::Evm {
+                    block_timestamp, ..
+                } => timestamps.push(block_timestamp),
+                other => panic!("unexpected trigger: {other:?}"),
+            }
+        }
+
+        assert_eq!(timestamps, vec![Some(1_000), Some(1_000), Some(990)]);
+        assert_eq!(queries.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+}
@@ -3,6 +3,8 @@ mod connection;
 mod rpc_types;
 mod subscription;
 
+use std::time::Duration;
+
 use alloy_primitives::B256;
 use alloy_rpc_types_eth::Log;
 use connection::Connection;
@@ -12,6 +14,7 @@ use subscription::Subscriptions;
 pub use subscription::SubscriptionKind;
 
 use tokio_stream::wrappers::UnboundedReceiverStream;
+use utils::tls::RpcTls;
 
 use channels::Channels;
 
@@ -22,6 +25,8 @@ pub struct EvmTriggerStreams {
     pub block_height_stream: UnboundedReceiverStream<u64>,
     pub log_stream: UnboundedReceiverStream<Log>,
     pub new_pending_transaction_stream: UnboundedReceiverStream<B256>,
+    /// The last block seen before each dropped connection, sent once it's back and resubscribed
+    pub reconnect_stream: UnboundedReceiverStream<u64>,
 }
 
 pub struct EvmTriggerStreamsController {
@@ -30,16 +35,21 @@ pub struct EvmTriggerStreamsController {
 }
 
 impl EvmTriggerStreams {
+    pub const DEFAULT_MAX_BACKOFF: Duration = Connection::BACKOFF_CAP;
+
     pub fn new(
         ws_endpoints: Vec<String>,
         chain_key: wavs_types::ChainKey,
         ws_priority_endpoint_index: Option<usize>,
+        tls: Option<RpcTls>,
+        max_backoff: Duration,
     ) -> Self {
         let channels = Channels::new();
 
         let rpc_ids = RpcIds::new();
 
-        let subscriptions = Subscriptions::new(rpc_ids.clone(), channels.subscription);
+        let subscriptions =
+            Subscriptions::new(rpc_ids.clone(), channels.subscription, chain_key.clone());
 
         let connection = Connection::new(
             rpc_ids,
@@ -47,6 +57,8 @@ impl EvmTriggerStreams {
             channels.connection,
             chain_key,
             ws_priority_endpoint_index,
+            tls,
+            max_backoff,
         );
 
         Self {
@@ -61,6 +73,9 @@ impl EvmTriggerStreams {
             new_pending_transaction_stream: UnboundedReceiverStream::new(
                 channels.client.subscription_new_pending_transaction_rx,
             ),
+            reconnect_stream: UnboundedReceiverStream::new(
+                channels.client.subscription_reconnect_rx,
+            ),
         }
     }
 }
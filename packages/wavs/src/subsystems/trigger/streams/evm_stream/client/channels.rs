@@ -22,6 +22,7 @@ pub struct SubscriptionChannels {
     pub subscription_block_height_tx: UnboundedSender<u64>,
     pub subscription_log_tx: UnboundedSender<Log>,
     pub subscription_new_pending_transaction_tx: UnboundedSender<B256>,
+    pub subscription_reconnect_tx: UnboundedSender<u64>,
     pub connection_send_rpc_tx: UnboundedSender<RpcRequest>,
     pub connection_state_rx: UnboundedReceiver<ConnectionState>,
     pub connection_data_rx: UnboundedReceiver<ConnectionData>,
@@ -31,6 +32,7 @@ pub struct ClientChannels {
     pub subscription_block_height_rx: UnboundedReceiver<u64>,
     pub subscription_log_rx: UnboundedReceiver<Log>,
     pub subscription_new_pending_transaction_rx: UnboundedReceiver<B256>,
+    pub subscription_reconnect_rx: UnboundedReceiver<u64>,
 }
 
 impl Channels {
@@ -44,6 +46,8 @@ impl Channels {
         let (subscription_log_tx, subscription_log_rx) = tokio::sync::mpsc::unbounded_channel();
         let (subscription_new_pending_transaction_tx, subscription_new_pending_transaction_rx) =
             tokio::sync::mpsc::unbounded_channel();
+        let (subscription_reconnect_tx, subscription_reconnect_rx) =
+            tokio::sync::mpsc::unbounded_channel();
 
         Self {
             connection: ConnectionChannels {
@@ -55,6 +59,7 @@ impl Channels {
                 subscription_block_height_tx,
                 subscription_log_tx,
                 subscription_new_pending_transaction_tx,
+                subscription_reconnect_tx,
                 connection_send_rpc_tx,
                 connection_state_rx,
                 connection_data_rx,
@@ -63,6 +68,7 @@ impl Channels {
                 subscription_block_height_rx,
                 subscription_log_rx,
                 subscription_new_pending_transaction_rx,
+                subscription_reconnect_rx,
             },
         }
     }
@@ -12,14 +12,18 @@ use tokio::{
     net::TcpStream,
     sync::{mpsc::UnboundedSender, oneshot, Notify},
 };
-use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+use tokio_tungstenite::{
+    connect_async_tls_with_config, tungstenite::Message, Connector, MaybeTlsStream, WebSocketStream,
+};
 
 use super::{
     channels::ConnectionChannels,
     rpc_types::{id::RpcIds, outbound::RpcRequest},
 };
-use utils::evm_client::EvmEndpoint;
+use utils::error::EvmClientError;
+use utils::evm_client::{check_chain_id, EvmEndpoint};
 use utils::health::check_evm_chain_endpoint_health_query;
+use utils::tls::RpcTls;
 use wavs_types::{ChainKey, ChainKeyNamespace};
 
 // A handle for managing WebSocket connections with intelligent retry logic
@@ -68,8 +72,10 @@ pub enum ConnectionState {
 
 impl Connection {
     pub const BACKOFF_BASE: Duration = Duration::from_secs(1);
+    /// Default for the longest wait between reconnect attempts
     pub const BACKOFF_CAP: Duration = Duration::from_secs(30);
     pub const PRIORITY_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+    pub const CHAIN_ID_TIMEOUT: Duration = Duration::from_secs(10);
 
     #[tracing::instrument(skip_all, fields(namespace = %ChainKeyNamespace::EVM, chain_key = %chain_key))]
     pub fn new(
@@ -78,6 +84,8 @@ impl Connection {
         channels: ConnectionChannels,
         chain_key: ChainKey,
         priority_endpoint_index: Option<usize>,
+        tls: Option<RpcTls>,
+        max_backoff: Duration,
     ) -> Self {
         let ConnectionChannels {
             connection_send_rpc_rx,
@@ -191,6 +199,8 @@ impl Connection {
             force_switch_flag,
             force_switch_notify,
             is_using_priority,
+            tls,
+            max_backoff,
         ));
 
         let message_handle = tokio::spawn(message_loop(
@@ -297,6 +307,8 @@ async fn connection_loop(
     force_switch_flag: Arc<AtomicBool>,
     force_switch_notify: Arc<Notify>,
     is_using_priority: Arc<std::sync::RwLock<bool>>,
+    tls: Option<RpcTls>,
+    max_backoff: Duration,
 ) {
     let mut endpoint_idx = 0;
     let mut current_backoff = Connection::BACKOFF_BASE;
@@ -327,11 +339,18 @@ async fn connection_loop(
 
                 let endpoint = endpoints[endpoint_idx].clone();
                 tracing::info!("connecting to {endpoint}");
-                let result = connect_async(&endpoint).await;
+                // without custom tls this is the same as `connect_async`
+                let connector = tls.as_ref().map(|tls| Connector::Rustls(tls.client_config()));
+                let result = match connect_async_tls_with_config(&endpoint, None, false, connector).await {
+                    Ok((mut ws, _)) => verify_chain_id(&mut ws, &chain_key, &endpoint)
+                        .await
+                        .map(|_| ws),
+                    Err(err) => Err(err.into()),
+                };
                 (result, endpoint)
             } => {
                 match result {
-                    Ok((ws, _)) => {
+                    Ok(ws) => {
                         tracing::info!("connected {endpoint}");
                         current_backoff = Connection::BACKOFF_BASE; // reset backoff on success
                         failures_in_cycle = 0; // reset failure count
@@ -457,7 +476,7 @@ async fn connection_loop(
                         if let Err(e) = connection_state_tx.send(ConnectionState::Disconnected) {
                             tracing::error!("Failed to send disconnected state: {}", e);
                         }
-                        tracing::error!("connect error to {endpoint}: {err:?}");
+                        tracing::error!("connect error to {endpoint}: {err}");
 
                         // Clear force_switch_flag if we failed to connect to priority endpoint
                         if let Some(priority_index) = priority_endpoint_index {
@@ -479,7 +498,7 @@ async fn connection_loop(
                         // Check if we've tried all endpoints in this cycle
                         if failures_in_cycle >= endpoints.len() {
                             // Completed a full cycle without success, increase backoff
-                            current_backoff = (current_backoff * 2).min(Connection::BACKOFF_CAP);
+                            current_backoff = (current_backoff * 2).min(max_backoff);
                             failures_in_cycle = 0; // reset for new cycle
                         }
                     }
@@ -545,10 +564,69 @@ async fn message_loop(
     }
 }
 
+/// Asks a freshly connected node for its chain id before anything else is sent on the socket,
+/// so an endpoint serving the wrong chain is skipped just like one that failed to connect
+async fn verify_chain_id(
+    ws: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    chain_key: &ChainKey,
+    endpoint: &str,
+) -> Result<(), ConnectionError> {
+    const REQUEST_ID: &str = "wavs-chain-id";
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": REQUEST_ID,
+        "method": "eth_chainId",
+        "params": [],
+    });
+    ws.send(Message::Text(request.to_string().into())).await?;
+
+    let response = tokio::time::timeout(Connection::CHAIN_ID_TIMEOUT, async {
+        while let Some(message) = ws.next().await {
+            if let Message::Text(text) = message? {
+                let response: serde_json::Value = serde_json::from_str(&text)?;
+                if response["id"] == REQUEST_ID {
+                    return Ok(response);
+                }
+            }
+        }
+        Err(ConnectionError::ClosedBeforeChainId)
+    })
+    .await
+    .map_err(|_| ConnectionError::ChainIdTimeout)??;
+
+    let actual = response["result"]
+        .as_str()
+        .and_then(|hex| u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+        .ok_or_else(|| ConnectionError::InvalidChainId(response.to_string()))?;
+
+    check_chain_id(endpoint, &chain_key.id, actual)?;
+
+    Ok(())
+}
+
 #[derive(Debug, Error)]
 pub enum ConnectionError {
     #[error("No active connection")]
     NoActiveConnection,
+
+    #[error("{0}")]
+    WebSocket(#[from] tokio_tungstenite::tungstenite::Error),
+
+    #[error("Invalid JSON-RPC response: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Timed out waiting for eth_chainId")]
+    ChainIdTimeout,
+
+    #[error("Connection closed before eth_chainId responded")]
+    ClosedBeforeChainId,
+
+    #[error("Unexpected eth_chainId response: {0}")]
+    InvalidChainId(String),
+
+    #[error("{0}")]
+    ChainIdMismatch(#[from] EvmClientError),
 }
 
 #[cfg(test)]
@@ -559,7 +637,10 @@ mod test {
     use alloy_node_bindings::Anvil;
 
     use tokio::time::{timeout, Duration};
-    use utils::{init_tracing_tests, test_utils::anvil::safe_spawn_anvil};
+    use utils::{
+        init_tracing_tests,
+        test_utils::anvil::{safe_spawn_anvil, safe_spawn_anvil_extra},
+    };
 
     #[tokio::test]
     async fn connection_works() {
@@ -580,6 +661,8 @@ mod test {
             channels.connection,
             wavs_types::ChainKey::new("evm:31337").expect("Invalid chain key format"),
             None,
+            None,
+            Connection::BACKOFF_CAP,
         );
 
         let message_count = std::sync::Arc::new(std::sync::Mutex::new(0u32));
@@ -646,6 +729,8 @@ mod test {
             channels.connection,
             wavs_types::ChainKey::new("evm:31337").expect("Invalid chain key format"),
             None,
+            None,
+            Connection::BACKOFF_CAP,
         );
 
         // Wait for connection to be established and current_endpoint to be set
@@ -671,6 +756,45 @@ mod test {
         assert_eq!(anvil.ws_endpoint(), connected_endpoint);
     }
 
+    #[tokio::test]
+    async fn connection_skip_wrong_chain() {
+        init_tracing_tests();
+
+        let wrong_chain = safe_spawn_anvil_extra(|anvil| anvil.chain_id(1));
+        let anvil = safe_spawn_anvil();
+
+        let endpoints = vec![
+            wrong_chain.ws_endpoint(), // Will connect, but is serving chain 1
+            anvil.ws_endpoint(),       // Will succeed
+        ];
+
+        let channels = Channels::new();
+        let rpc_ids = RpcIds::new();
+        let connection = Connection::new(
+            rpc_ids,
+            endpoints,
+            channels.connection,
+            wavs_types::ChainKey::new("evm:31337").expect("Invalid chain key format"),
+            None,
+            None,
+            Connection::BACKOFF_CAP,
+        );
+
+        let result = timeout(Duration::from_secs(10), async {
+            loop {
+                if let Some(endpoint) = connection.current_endpoint() {
+                    return endpoint;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await;
+
+        let connected_endpoint = result.expect("Timeout waiting for current_endpoint to be set");
+
+        assert_eq!(anvil.ws_endpoint(), connected_endpoint);
+    }
+
     #[tokio::test]
     async fn connection_cycles() {
         init_tracing_tests();
@@ -698,6 +822,8 @@ mod test {
             channels.connection,
             wavs_types::ChainKey::new("evm:31337").expect("Invalid chain key format"),
             None,
+            None,
+            Connection::BACKOFF_CAP,
         );
 
         // Step 1: Wait for initial connection to anvil_1
@@ -809,6 +935,8 @@ mod test {
             channels.connection,
             wavs_types::ChainKey::new("evm:31337").expect("Invalid chain key format"),
             Some(1),
+            None,
+            Connection::BACKOFF_CAP,
         );
 
         // Step 1: Should connect to priority endpoint (anvil_2) first
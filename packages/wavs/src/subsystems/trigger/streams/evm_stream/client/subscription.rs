@@ -1,6 +1,9 @@
 use std::{
     collections::{HashMap, HashSet},
-    sync::{atomic::AtomicBool, Arc},
+    sync::{
+        atomic::{AtomicBool, AtomicU64},
+        Arc,
+    },
     time::Duration,
 };
 
@@ -8,7 +11,7 @@ use alloy_primitives::{Address, B256};
 use alloy_rpc_types_eth::Log;
 use slotmap::Key;
 use tokio::{sync::oneshot, task::JoinHandle};
-use wavs_types::ChainKeyNamespace;
+use wavs_types::{ChainKey, ChainKeyNamespace};
 
 use crate::subsystems::trigger::streams::evm_stream::client::rpc_types::outbound::SubscribeParams;
 
@@ -31,12 +34,13 @@ pub struct Subscriptions {
 }
 
 impl Subscriptions {
-    #[tracing::instrument(skip_all, fields(namespace = %ChainKeyNamespace::EVM))]
-    pub fn new(rpc_ids: RpcIds, channels: SubscriptionChannels) -> Self {
+    #[tracing::instrument(skip_all, fields(namespace = %ChainKeyNamespace::EVM, chain_key = %chain_key))]
+    pub fn new(rpc_ids: RpcIds, channels: SubscriptionChannels, chain_key: ChainKey) -> Self {
         let SubscriptionChannels {
             mut subscription_block_height_tx,
             mut subscription_log_tx,
             mut subscription_new_pending_transaction_tx,
+            subscription_reconnect_tx,
             connection_send_rpc_tx,
             mut connection_state_rx,
             mut connection_data_rx,
@@ -104,8 +108,16 @@ impl Subscriptions {
                         }
                         Some(state) = connection_state_rx.recv() => {
                             match state {
-                                ConnectionState::Connected(_endpoint) => {
+                                ConnectionState::Connected(endpoint) => {
                                     inner.set_is_connected(true);
+                                    // the resubscribe went out with set_is_connected,
+                                    // whoever is listening fills in the blocks we missed
+                                    if let Some(last_block) = inner.take_resume_block() {
+                                        tracing::info!("reconnected to {endpoint} and resubscribed, last block seen before the disconnect was {last_block}");
+                                        if let Err(e) = subscription_reconnect_tx.send(last_block) {
+                                            tracing::error!("failed to send reconnect: {}", e);
+                                        }
+                                    }
                                 },
                                 ConnectionState::Disconnected => {
                                     inner.set_is_connected(false);
@@ -157,6 +169,21 @@ impl Subscriptions {
     pub fn active_subscriptions(&self) -> HashMap<String, SubscriptionKind> {
         self.inner.ids._lookup.read().unwrap().clone()
     }
+
+    /// The addresses and topics of the current log filter, if logs are enabled
+    pub fn log_filter(&self) -> Option<(Vec<Address>, Vec<B256>)> {
+        self.inner
+            ._logs
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|LogFilter { addresses, topics }| {
+                (
+                    addresses.iter().copied().collect(),
+                    topics.iter().copied().collect(),
+                )
+            })
+    }
 }
 
 impl Drop for Subscriptions {
@@ -187,6 +214,10 @@ struct SubscriptionsInner {
     _pending_transactions: AtomicBool,
     // not really a subscription, but used to track connection state
     _is_connected: AtomicBool,
+    // highest block a forwarded event came from, 0 until there is one
+    _last_block: AtomicU64,
+    // set when the connection drops, taken once it's back
+    _resume_block: std::sync::Mutex<Option<u64>>,
     ids: SubscriptionIds,
     rpc_ids: RpcIds,
     rpc_ids_in_flight: RpcIdsInFlight,
@@ -203,6 +234,8 @@ impl SubscriptionsInner {
             _logs: std::sync::RwLock::new(None),
             _pending_transactions: AtomicBool::new(false),
             _is_connected: AtomicBool::new(false),
+            _last_block: AtomicU64::new(0),
+            _resume_block: std::sync::Mutex::new(None),
             ids: SubscriptionIds::default(),
             rpc_ids,
             rpc_ids_in_flight: RpcIdsInFlight::default(),
@@ -288,11 +321,25 @@ impl SubscriptionsInner {
         }
     }
 
+    #[tracing::instrument(skip_all, fields(namespace = %ChainKeyNamespace::EVM))]
     pub fn set_is_connected(&self, value: bool) {
-        self._is_connected
-            .store(value, std::sync::atomic::Ordering::SeqCst);
+        let was_connected = self
+            ._is_connected
+            .swap(value, std::sync::atomic::Ordering::SeqCst);
 
         if !value {
+            if was_connected {
+                let last_block = self.last_block();
+                match last_block {
+                    Some(last_block) => tracing::warn!(
+                        "connection lost, last block seen was {last_block}, will resume from there once reconnected"
+                    ),
+                    None => tracing::warn!(
+                        "connection lost before any block was seen, nothing to resume from once reconnected"
+                    ),
+                }
+                *self._resume_block.lock().unwrap() = last_block;
+            }
             self.ids.clear();
             self.rpc_ids_in_flight.clear();
         } else {
@@ -300,6 +347,23 @@ impl SubscriptionsInner {
         }
     }
 
+    pub fn last_block(&self) -> Option<u64> {
+        match self._last_block.load(std::sync::atomic::Ordering::SeqCst) {
+            0 => None,
+            block => Some(block),
+        }
+    }
+
+    fn observe_block(&self, block: u64) {
+        self._last_block
+            .fetch_max(block, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    /// The last block seen before the connection dropped, once per disconnect
+    pub fn take_resume_block(&self) -> Option<u64> {
+        self._resume_block.lock().unwrap().take()
+    }
+
     // all requests must go through here so we can track in-flight requests
     #[tracing::instrument(skip_all, fields(namespace = %ChainKeyNamespace::EVM))]
     fn send_rpc(
@@ -540,6 +604,7 @@ impl SubscriptionsInner {
                     .ids
                     .is_most_recent(&subscription_id, SubscriptionCategory::NewHeads)
                 {
+                    self.observe_block(header.number);
                     if let Err(e) = subscription_block_height_tx.send(header.number) {
                         tracing::error!("failed to send new block height: {}", e);
                     }
@@ -569,6 +634,9 @@ impl SubscriptionsInner {
                     .ids
                     .is_most_recent(&subscription_id, SubscriptionCategory::AllLogs)
                 {
+                    if let Some(block_number) = log.block_number {
+                        self.observe_block(block_number);
+                    }
                     if let Err(e) = subscription_log_tx.send(log) {
                         tracing::error!("failed to send log: {}", e);
                     }
@@ -1358,4 +1426,60 @@ mod tests {
         // The subscription id should never be registered because it was queued for removal.
         assert!(!inner.ids.exists("sub-head-ephemeral"));
     }
+
+    #[tokio::test]
+    async fn dropped_connection_resubscribes_and_resumes_from_last_block() {
+        let (inner, mut connection_rx) = new_inner();
+        let (mut block_tx, _block_rx) = unbounded_channel();
+        let (mut log_tx, mut log_rx) = unbounded_channel();
+        let (mut pending_tx, _pending_rx) = unbounded_channel();
+        let address = Address::repeat_byte(1);
+        let topic = B256::repeat_byte(2);
+
+        inner.enable_logs(vec![address], vec![topic]);
+        inner.set_is_connected(true);
+        let Some(RpcRequest::Subscribe { id, .. }) = connection_rx.recv().await else {
+            panic!("expected a logs subscription request");
+        };
+        inner.on_received_rpc_response(
+            id,
+            RpcResponse::NewSubscription {
+                subscription_id: "sub-1".into(),
+            },
+        );
+
+        let mut log = sample_log(address, topic);
+        log.block_number = Some(10);
+        inner.on_received_subscription_event(
+            &mut block_tx,
+            &mut log_tx,
+            &mut pending_tx,
+            "sub-1".into(),
+            RpcSubscriptionEvent::Logs(log),
+        );
+        assert_eq!(log_rx.recv().await.unwrap().block_number, Some(10));
+        assert_eq!(inner.take_resume_block(), None);
+
+        // the connection drops and comes back
+        inner.set_is_connected(false);
+        assert!(inner.ids._lookup.read().unwrap().is_empty());
+        inner.set_is_connected(true);
+
+        // the same filter is subscribed to again
+        match connection_rx.recv().await {
+            Some(RpcRequest::Subscribe {
+                params: SubscribeParams::Logs { addresses, topics },
+                ..
+            }) => {
+                assert_eq!(addresses, HashSet::from([address]));
+                assert_eq!(topics, HashSet::from([topic]));
+            }
+            other => panic!("expected a logs subscription request, got {other:?}"),
+        }
+        assert!(connection_rx.try_recv().is_err());
+
+        // and the gap is filled from the last block delivered, once
+        assert_eq!(inner.take_resume_block(), Some(10));
+        assert_eq!(inner.take_resume_block(), None);
+    }
 }
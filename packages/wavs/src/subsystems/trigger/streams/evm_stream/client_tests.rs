@@ -36,6 +36,8 @@ async fn block_height_stream() {
         vec![anvil.ws_endpoint()],
         wavs_types::ChainKey::new("evm:31337").expect("Invalid chain key format"),
         None,
+        None,
+        EvmTriggerStreams::DEFAULT_MAX_BACKOFF,
     );
 
     controller.subscriptions.toggle_block_height(true);
@@ -80,6 +82,8 @@ async fn simple_log_stream() {
         vec![anvil.ws_endpoint()],
         wavs_types::ChainKey::new("evm:31337").expect("Invalid chain key format"),
         None,
+        None,
+        EvmTriggerStreams::DEFAULT_MAX_BACKOFF,
     );
 
     let contract = EventEmitterClient::new(&anvil, 0).deploy().await;
@@ -182,6 +186,8 @@ async fn multi_log_stream(add_kind: AddKind) {
         vec![anvil.ws_endpoint()],
         wavs_types::ChainKey::new("evm:31337").expect("Invalid chain key format"),
         None,
+        None,
+        EvmTriggerStreams::DEFAULT_MAX_BACKOFF,
     );
 
     let contract = EventEmitterClient::new(&anvil, 0).deploy().await;
@@ -325,6 +331,8 @@ async fn multi_contract_log_stream() {
         vec![anvil.ws_endpoint()],
         wavs_types::ChainKey::new("evm:31337").expect("Invalid chain key format"),
         None,
+        None,
+        EvmTriggerStreams::DEFAULT_MAX_BACKOFF,
     );
 
     let contract_1 = EventEmitterClient::new(&anvil, 0).deploy().await;
@@ -458,6 +466,8 @@ async fn fallback_chain_log_stream() {
         vec![anvil_1.ws_endpoint(), anvil_2.ws_endpoint()],
         wavs_types::ChainKey::new("evm:31337").expect("Invalid chain key format"),
         None,
+        None,
+        EvmTriggerStreams::DEFAULT_MAX_BACKOFF,
     );
 
     let contract_1 = EventEmitterClient::new(&anvil_1, 0).deploy().await;
@@ -580,6 +590,8 @@ async fn unsubscribe_log_stream(wait_for_subscriptions: bool) {
         vec![anvil.ws_endpoint()],
         wavs_types::ChainKey::new("evm:31337").expect("Invalid chain key format"),
         None,
+        None,
+        EvmTriggerStreams::DEFAULT_MAX_BACKOFF,
     );
 
     let contract = EventEmitterClient::new(&anvil, 0).deploy().await;
@@ -700,6 +712,8 @@ async fn controller_drop() {
         vec![anvil.ws_endpoint()],
         wavs_types::ChainKey::new("evm:31337").expect("Invalid chain key format"),
         None,
+        None,
+        EvmTriggerStreams::DEFAULT_MAX_BACKOFF,
     );
 
     controller.subscriptions.toggle_block_height(true);
@@ -753,6 +767,8 @@ async fn all_log_stream() {
         vec![anvil.ws_endpoint()],
         wavs_types::ChainKey::new("evm:31337").expect("Invalid chain key format"),
         None,
+        None,
+        EvmTriggerStreams::DEFAULT_MAX_BACKOFF,
     );
 
     let contract = EventEmitterClient::new(&anvil, 0).deploy().await;
@@ -846,6 +862,8 @@ async fn unsubscribe_all_log_stream(explicit: bool) {
         vec![anvil.ws_endpoint()],
         wavs_types::ChainKey::new("evm:31337").expect("Invalid chain key format"),
         None,
+        None,
+        EvmTriggerStreams::DEFAULT_MAX_BACKOFF,
     );
 
     let contract = EventEmitterClient::new(&anvil, 0).deploy().await;
@@ -924,6 +942,8 @@ async fn multiple_clients() {
         vec![anvil_1.ws_endpoint()],
         wavs_types::ChainKey::new("evm:31337").expect("Invalid chain key format"),
         None,
+        None,
+        EvmTriggerStreams::DEFAULT_MAX_BACKOFF,
     );
 
     let EvmTriggerStreams {
@@ -934,6 +954,8 @@ async fn multiple_clients() {
         vec![anvil_2.ws_endpoint()],
         wavs_types::ChainKey::new("evm:31337").expect("Invalid chain key format"),
         None,
+        None,
+        EvmTriggerStreams::DEFAULT_MAX_BACKOFF,
     );
 
     let contract_1 = EventEmitterClient::new(&anvil_1, 0).deploy().await;
@@ -1082,6 +1104,8 @@ async fn multiple_clients_drop_one() {
         vec![anvil_1.ws_endpoint()],
         wavs_types::ChainKey::new("evm:31337").expect("Invalid chain key format"),
         None,
+        None,
+        EvmTriggerStreams::DEFAULT_MAX_BACKOFF,
     );
 
     let EvmTriggerStreams {
@@ -1092,6 +1116,8 @@ async fn multiple_clients_drop_one() {
         vec![anvil_2.ws_endpoint()],
         wavs_types::ChainKey::new("evm:31337").expect("Invalid chain key format"),
         None,
+        None,
+        EvmTriggerStreams::DEFAULT_MAX_BACKOFF,
     );
 
     let contract_1 = EventEmitterClient::new(&anvil_1, 0).deploy().await;
@@ -0,0 +1,39 @@
+//! A websocket subscription that reconnects can re-deliver logs we've already turned into
+//! trigger actions. Logs are identified by their block hash and index within the block,
+//! and the most recent ones are remembered so a replay after a reconnect is dropped.
+//! Anything older than the window is assumed to be gone from the node's replay range.
+
+use std::num::NonZeroUsize;
+
+use alloy_primitives::BlockHash;
+use lru::LruCache;
+
+pub const DEFAULT_WINDOW: NonZeroUsize = NonZeroUsize::new(4096).unwrap();
+
+pub struct SeenLogs {
+    seen: LruCache<(BlockHash, u64), ()>,
+}
+
+impl Default for SeenLogs {
+    fn default() -> Self {
+        Self::new(DEFAULT_WINDOW)
+    }
+}
+
+impl SeenLogs {
+    pub fn new(window: NonZeroUsize) -> Self {
+        Self {
+            seen: LruCache::new(window),
+        }
+    }
+
+    /// `true` the first time a log is seen within the window
+    pub fn insert(&mut self, block_hash: BlockHash, log_index: u64) -> bool {
+        self.seen.put((block_hash, log_index), ()).is_none()
+    }
+
+    /// Forget a log that a reorg removed, so it's treated as new if its block comes back
+    pub fn remove(&mut self, block_hash: BlockHash, log_index: u64) {
+        self.seen.pop(&(block_hash, log_index));
+    }
+}
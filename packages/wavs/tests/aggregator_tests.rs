@@ -2,13 +2,35 @@
 
 mod wavs_systems;
 
-use utils::{context::AppContext, init_tracing_tests, telemetry::Metrics};
-use wavs::subsystems::aggregator::AggregatorCommand;
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use alloy_provider::Provider;
+use axum::{http::StatusCode, routing::post, Json, Router};
+
+use layer_climb::prelude::EvmAddr;
+use utils::{
+    context::AppContext, init_tracing_tests, telemetry::Metrics,
+    test_utils::test_contracts::TestContractDeps,
+};
+use wavs::{
+    config::{OnTimeout, QuorumTimeoutConfig},
+    dispatcher::DispatcherCommand,
+    subsystems::{aggregator::AggregatorCommand, engine::AggregatorExecuteKind},
+};
+use wavs_types::{
+    ByteArray, ChainKey, EvmChainConfigBuilder, EvmSubmitAction, QueuedSubmission, QuorumQueueId,
+    SignedResult, Submission, SubmissionProvenance, Submit, SubmitAction, SubmitCondition,
+    SubmitTarget, TargetDelivery,
+};
 
 use crate::wavs_systems::{
     channels::TestChannels,
     mock_aggregator::{
-        mock_aggregator, wait_for_aggregator_broadcasts, wait_for_aggregator_receives,
+        mock_aggregator, mock_aggregator_with_deliverer, wait_for_aggregator_broadcasts,
+        wait_for_aggregator_receives,
     },
     mock_config::mock_config,
     mock_service::{mock_service, mock_services},
@@ -61,3 +83,484 @@ fn send_to_self() {
     wait_for_aggregator_receives(&aggregator, 3, None).unwrap();
     assert_eq!(aggregator.metrics.get_receive_count(), 3);
 }
+
+#[test]
+fn quorum_timeout_drops_queue() {
+    init_tracing_tests();
+
+    let ctx = AppContext::new();
+    let channels = TestChannels::new();
+    let services = mock_services();
+    let service = mock_service();
+    let metrics = Metrics::new(opentelemetry::global::meter("wavs_metrics"));
+    let mut config = mock_config();
+    config.aggregator.quorum_timeouts.insert(
+        service.id(),
+        QuorumTimeoutConfig {
+            quorum_timeout_secs: 60,
+            on_timeout: OnTimeout::Drop,
+        },
+    );
+
+    services.save(&service).unwrap();
+
+    let submission_manager =
+        mock_submission_manager(ctx.clone(), &metrics, &config, &channels, services.clone());
+
+    let aggregator = mock_aggregator(ctx.clone(), &metrics, &config, &channels, services);
+
+    ctx.rt.block_on(async {
+        submission_manager
+            .add_service_key(service.id(), None)
+            .unwrap();
+
+        let req = mock_submission_request(&service, "message");
+        let submission = submission_manager.sign_request(&req).await.unwrap();
+        let queue_id = QuorumQueueId {
+            event_id: submission.event_id.clone(),
+            action: SubmitAction::Evm(EvmSubmitAction {
+                chain: "evm:anvil".parse().unwrap(),
+                address: EvmAddr::new([0u8; 20]),
+                gas_price: None,
+                eip1559: None,
+            }),
+        };
+
+        let queued = |received_at| QueuedSubmission {
+            submission: submission.clone(),
+            provenance: SubmissionProvenance {
+                signer: alloy_primitives::Address::ZERO,
+                peer: None,
+                received_at,
+                weight: None,
+            },
+        };
+
+        // still within the timeout
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        aggregator
+            .save_quorum_queue(queue_id.clone(), vec![queued(now)])
+            .await
+            .unwrap();
+        assert_eq!(aggregator.expire_quorum_queues().await.unwrap(), 0);
+
+        // the first signature arrived long ago
+        aggregator
+            .save_quorum_queue(queue_id.clone(), vec![queued(now - 120)])
+            .await
+            .unwrap();
+        assert_eq!(aggregator.expire_quorum_queues().await.unwrap(), 1);
+
+        let queues = aggregator
+            .list_quorum_queues(Some(submission.event_id.clone()), None)
+            .await
+            .unwrap();
+        assert_eq!(queues.len(), 1);
+        assert!(queues[0].burned_at.is_some());
+
+        // burned queues aren't expired again
+        assert_eq!(aggregator.expire_quorum_queues().await.unwrap(), 0);
+    });
+}
+
+#[test]
+fn fan_out_reports_each_target() {
+    init_tracing_tests();
+
+    let ctx = AppContext::new();
+    let channels = TestChannels::new();
+    let services = mock_services();
+    let metrics = Metrics::new(opentelemetry::global::meter("wavs_metrics"));
+    let mut config = mock_config();
+
+    let (test_deps, handler_address) = ctx.rt.block_on(async {
+        let test_deps = TestContractDeps::new().await;
+        let handler_address = test_deps.deploy_test_service_handler().await;
+        (test_deps, handler_address)
+    });
+
+    let chain: ChainKey = "evm:31337".parse().unwrap();
+    config.chains.write().unwrap().evm.insert(
+        chain.id.clone(),
+        EvmChainConfigBuilder {
+            ws_endpoints: vec![test_deps._anvil.ws_endpoint()],
+            http_endpoint: Some(test_deps._anvil.endpoint()),
+            http_endpoints: Vec::new(),
+            faucet_endpoint: None,
+            ws_priority_endpoint_index: None,
+        },
+    );
+    config.aggregator_evm_credential = config.signing_mnemonic.clone();
+
+    // one target on the local chain, the other on a chain the node has no config for
+    let reachable = SubmitAction::Evm(EvmSubmitAction {
+        chain: chain.clone(),
+        address: EvmAddr::new(handler_address.into_array()),
+        gas_price: None,
+        eip1559: None,
+    });
+    let unreachable = SubmitAction::Evm(EvmSubmitAction {
+        chain: "evm:1".parse().unwrap(),
+        address: EvmAddr::new([1u8; 20]),
+        gas_price: None,
+        eip1559: None,
+    });
+
+    let mut service = mock_service();
+    for workflow in service.workflows.values_mut() {
+        if let Submit::Aggregator { targets, .. } = &mut workflow.submit {
+            *targets = vec![unreachable.clone(), reachable.clone()];
+        }
+    }
+    services.save(&service).unwrap();
+
+    let submission_manager =
+        mock_submission_manager(ctx.clone(), &metrics, &config, &channels, services.clone());
+
+    let _aggregator = mock_aggregator(ctx.clone(), &metrics, &config, &channels, services);
+
+    let submission = ctx.rt.block_on(async {
+        submission_manager
+            .add_service_key(service.id(), None)
+            .unwrap();
+
+        let req = mock_submission_request(&service, "message");
+        submission_manager.sign_request(&req).await.unwrap()
+    });
+
+    // the component returned nothing itself, the fixed targets are still submitted to
+    channels
+        .dispatcher_to_aggregator_tx
+        .send(AggregatorCommand::Actions {
+            submission,
+            actions: Vec::new(),
+            kind: AggregatorExecuteKind::Standard,
+        })
+        .unwrap();
+
+    let mut results = Vec::new();
+    while results.len() < 2 {
+        let command = channels
+            .subsystem_to_dispatcher_rx
+            .recv_timeout(Duration::from_secs(30))
+            .unwrap();
+        if let DispatcherCommand::AggregatorExecute {
+            kind: AggregatorExecuteKind::SubmitCallback { action, result },
+            ..
+        } = command
+        {
+            results.push((action, result));
+        }
+    }
+
+    let result_for = |target: &SubmitAction| {
+        results
+            .iter()
+            .find(|(action, _)| action == target)
+            .map(|(_, result)| result)
+            .unwrap()
+    };
+
+    assert!(result_for(&unreachable).is_err());
+    let receipt = result_for(&reachable).as_ref().unwrap();
+    assert!(receipt.block_number.is_some());
+}
+
+#[test]
+fn delivers_aggregated_result_to_submit_targets() {
+    let (submission, received, deliveries) = run_with_http_target(false);
+
+    // posted once it's on-chain, with the signatures that took it there
+    let result = received.unwrap();
+    assert_eq!(result.event_id, submission.event_id);
+    assert_eq!(result.envelope, submission.envelope);
+    assert_eq!(result.signatures.len(), 1);
+    assert_eq!(
+        result.signatures[0].signature,
+        submission.envelope_signature
+    );
+    assert!(result.tx_hash.is_some());
+
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].error, None);
+}
+
+#[test]
+fn dry_run_skips_submit_targets() {
+    let (_, received, deliveries) = run_with_http_target(true);
+
+    assert!(received.is_none());
+    assert!(deliveries.is_empty());
+}
+
+/// Aggregates one submission for a workflow with an http submit target, returning what the
+/// target received and the deliveries recorded once the submit callback is sent
+fn run_with_http_target(dry_run: bool) -> (Submission, Option<SignedResult>, Vec<TargetDelivery>) {
+    init_tracing_tests();
+
+    let ctx = AppContext::new();
+    let channels = TestChannels::new();
+    let services = mock_services();
+    let metrics = Metrics::new(opentelemetry::global::meter("wavs_metrics"));
+    let mut config = mock_config();
+    config.aggregator.dry_run = dry_run;
+
+    let (test_deps, handler_address) = ctx.rt.block_on(async {
+        let test_deps = TestContractDeps::new().await;
+        let handler_address = test_deps.deploy_test_service_handler().await;
+        (test_deps, handler_address)
+    });
+
+    let chain: ChainKey = "evm:31337".parse().unwrap();
+    config.chains.write().unwrap().evm.insert(
+        chain.id.clone(),
+        EvmChainConfigBuilder {
+            ws_endpoints: vec![test_deps._anvil.ws_endpoint()],
+            http_endpoint: Some(test_deps._anvil.endpoint()),
+            http_endpoints: Vec::new(),
+            faucet_endpoint: None,
+            ws_priority_endpoint_index: None,
+        },
+    );
+    config.aggregator_evm_credential = config.signing_mnemonic.clone();
+
+    let received = Arc::new(Mutex::new(None::<SignedResult>));
+    let listener = ctx
+        .rt
+        .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+        .unwrap();
+    let addr = listener.local_addr().unwrap();
+    let router = Router::new().route(
+        "/sink",
+        post({
+            let received = received.clone();
+            move |Json(result): Json<SignedResult>| {
+                let received = received.clone();
+                async move {
+                    *received.lock().unwrap() = Some(result);
+                    StatusCode::OK
+                }
+            }
+        }),
+    );
+    ctx.rt.spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+
+    let mut service = mock_service();
+    for workflow in service.workflows.values_mut() {
+        if let Submit::Aggregator { targets, .. } = &mut workflow.submit {
+            *targets = vec![SubmitAction::Evm(EvmSubmitAction {
+                chain: chain.clone(),
+                address: EvmAddr::new(handler_address.into_array()),
+                gas_price: None,
+                eip1559: None,
+            })];
+        }
+        workflow.submit_targets = vec![SubmitTarget::Http {
+            url: format!("http://{addr}/sink"),
+            auth: None,
+        }];
+    }
+    services.save(&service).unwrap();
+
+    let submission_manager =
+        mock_submission_manager(ctx.clone(), &metrics, &config, &channels, services.clone());
+
+    let _aggregator = mock_aggregator_with_deliverer(
+        ctx.clone(),
+        &metrics,
+        &config,
+        &channels,
+        services,
+        submission_manager.target_deliverer(),
+    );
+
+    let submission = ctx.rt.block_on(async {
+        submission_manager
+            .add_service_key(service.id(), None)
+            .unwrap();
+
+        let req = mock_submission_request(&service, "message");
+        submission_manager.sign_request(&req).await.unwrap()
+    });
+
+    channels
+        .dispatcher_to_aggregator_tx
+        .send(AggregatorCommand::Actions {
+            submission: submission.clone(),
+            actions: Vec::new(),
+            kind: AggregatorExecuteKind::Standard,
+        })
+        .unwrap();
+
+    // targets are delivered to before the submit callback goes out
+    loop {
+        let command = channels
+            .subsystem_to_dispatcher_rx
+            .recv_timeout(Duration::from_secs(30))
+            .unwrap();
+        if let DispatcherCommand::AggregatorExecute {
+            kind: AggregatorExecuteKind::SubmitCallback { result, .. },
+            ..
+        } = command
+        {
+            assert!(result.is_ok(), "{result:?}");
+            break;
+        }
+    }
+
+    let received = received.lock().unwrap().clone();
+    let deliveries = submission_manager
+        .target_deliverer()
+        .list(Some(&service.id()), None);
+    (submission, received, deliveries)
+}
+
+// Runtime code for the submit condition contract: `return true`, `return false` and `revert`
+const CONDITION_TRUE: &str = "0x600160005260206000f3";
+const CONDITION_FALSE: &str = "0x600060005260206000f3";
+const CONDITION_REVERT: &str = "0x60006000fd";
+
+#[test]
+fn submit_condition_met_skips_and_burns_queue() {
+    let outcome = run_with_submit_condition(CONDITION_TRUE);
+
+    assert_eq!(outcome.skipped, 1);
+    assert!(outcome.submitted.is_none());
+    assert!(outcome.burned);
+}
+
+#[test]
+fn submit_condition_not_met_submits() {
+    let outcome = run_with_submit_condition(CONDITION_FALSE);
+
+    assert_eq!(outcome.skipped, 0);
+    assert_eq!(outcome.submitted, Some(true));
+    assert!(outcome.burned);
+}
+
+#[test]
+fn submit_condition_error_falls_back_to_submitting() {
+    let outcome = run_with_submit_condition(CONDITION_REVERT);
+
+    assert_eq!(outcome.skipped, 0);
+    assert_eq!(outcome.submitted, Some(true));
+    assert!(outcome.burned);
+}
+
+struct SubmitConditionOutcome {
+    skipped: u64,
+    /// Whether the submit callback reported success, `None` if nothing was submitted
+    submitted: Option<bool>,
+    burned: bool,
+}
+
+fn run_with_submit_condition(condition_code: &str) -> SubmitConditionOutcome {
+    init_tracing_tests();
+
+    let ctx = AppContext::new();
+    let channels = TestChannels::new();
+    let services = mock_services();
+    let metrics = Metrics::new(opentelemetry::global::meter("wavs_metrics"));
+    let mut config = mock_config();
+
+    let condition_address = alloy_primitives::Address::repeat_byte(0x42);
+    let (test_deps, handler_address) = ctx.rt.block_on(async {
+        let test_deps = TestContractDeps::new().await;
+        let handler_address = test_deps.deploy_test_service_handler().await;
+        test_deps
+            .client
+            .provider
+            .raw_request::<_, serde_json::Value>(
+                "anvil_setCode".into(),
+                (condition_address, condition_code),
+            )
+            .await
+            .unwrap();
+        (test_deps, handler_address)
+    });
+
+    let chain: ChainKey = "evm:31337".parse().unwrap();
+    config.chains.write().unwrap().evm.insert(
+        chain.id.clone(),
+        EvmChainConfigBuilder {
+            ws_endpoints: vec![test_deps._anvil.ws_endpoint()],
+            http_endpoint: Some(test_deps._anvil.endpoint()),
+            http_endpoints: Vec::new(),
+            faucet_endpoint: None,
+            ws_priority_endpoint_index: None,
+        },
+    );
+    config.aggregator_evm_credential = config.signing_mnemonic.clone();
+
+    let mut service = mock_service();
+    for workflow in service.workflows.values_mut() {
+        if let Submit::Aggregator { targets, .. } = &mut workflow.submit {
+            *targets = vec![SubmitAction::Evm(EvmSubmitAction {
+                chain: chain.clone(),
+                address: EvmAddr::new(handler_address.into_array()),
+                gas_price: None,
+                eip1559: None,
+            })];
+        }
+        workflow.submit_condition = Some(SubmitCondition::EvmCall {
+            address: Some(condition_address),
+            selector: ByteArray::new([0x12, 0x34, 0x56, 0x78]),
+        });
+    }
+    services.save(&service).unwrap();
+
+    let submission_manager =
+        mock_submission_manager(ctx.clone(), &metrics, &config, &channels, services.clone());
+
+    let aggregator = mock_aggregator(ctx.clone(), &metrics, &config, &channels, services);
+
+    let submission = ctx.rt.block_on(async {
+        submission_manager
+            .add_service_key(service.id(), None)
+            .unwrap();
+
+        let req = mock_submission_request(&service, "message");
+        submission_manager.sign_request(&req).await.unwrap()
+    });
+
+    channels
+        .dispatcher_to_aggregator_tx
+        .send(AggregatorCommand::Actions {
+            submission: submission.clone(),
+            actions: Vec::new(),
+            kind: AggregatorExecuteKind::Standard,
+        })
+        .unwrap();
+
+    // a skip is only visible in the metrics, a submission reports back through its callback
+    let mut submitted = None;
+    let end = Instant::now() + Duration::from_secs(30);
+    while submitted.is_none() && aggregator.metrics.get_skipped_count() == 0 {
+        assert!(Instant::now() < end, "timed out waiting for the aggregator");
+        if let Ok(DispatcherCommand::AggregatorExecute {
+            kind: AggregatorExecuteKind::SubmitCallback { result, .. },
+            ..
+        }) = channels
+            .subsystem_to_dispatcher_rx
+            .recv_timeout(Duration::from_millis(50))
+        {
+            submitted = Some(result.is_ok());
+        }
+    }
+
+    let queues = ctx
+        .rt
+        .block_on(aggregator.list_quorum_queues(Some(submission.event_id.clone()), None))
+        .unwrap();
+
+    SubmitConditionOutcome {
+        skipped: aggregator.metrics.get_skipped_count(),
+        submitted,
+        burned: queues.iter().all(|queue| queue.burned_at.is_some()) && !queues.is_empty(),
+    }
+}
@@ -3,7 +3,7 @@ use std::num::{NonZeroU32, NonZeroU64};
 use wavs::subsystems::trigger::{
     lookup::LookupId,
     schedulers::{
-        block_scheduler::{BlockHeight, BlockIntervalState},
+        block_scheduler::{BlockHeight, BlockIntervalState, BlockScheduler},
         interval_scheduler::IntervalState,
     },
 };
@@ -183,3 +183,48 @@ fn test_custom_cases() {
     let next = state.initialize(make_block_height(101));
     assert_eq!(next, Some(make_block_height(105)));
 }
+
+#[test]
+fn test_final_hit_when_interval_skips_end_block() {
+    let mut scheduler = BlockScheduler::new();
+    scheduler
+        .add_trigger(make_state(1, 5, Some(10), Some(12)))
+        .unwrap();
+
+    let hits = scheduler.tick_hits(make_block_height(10));
+    assert_eq!(hits.len(), 1);
+    assert!(!hits[0].is_final);
+
+    assert!(scheduler.tick_hits(make_block_height(11)).is_empty());
+
+    // the next interval would be 15, but the trigger still reports reaching its end at 12
+    let hits = scheduler.tick_hits(make_block_height(12));
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].lookup_id, 1);
+    assert_eq!(hits[0].scheduled_time, make_block_height(12));
+    assert!(hits[0].is_final);
+
+    // and only once
+    for height in 13..=20 {
+        assert!(scheduler.tick_hits(make_block_height(height)).is_empty());
+    }
+    assert!(scheduler.is_empty());
+}
+
+#[test]
+fn test_final_hit_on_end_block() {
+    let mut scheduler = BlockScheduler::new();
+    scheduler
+        .add_trigger(make_state(1, 5, Some(10), Some(15)))
+        .unwrap();
+
+    assert!(!scheduler.tick_hits(make_block_height(10))[0].is_final);
+
+    // the last interval hit is also the final one, not reported twice
+    let hits = scheduler.tick_hits(make_block_height(15));
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].scheduled_time, make_block_height(15));
+    assert!(hits[0].is_final);
+
+    assert!(scheduler.tick_hits(make_block_height(20)).is_empty());
+}
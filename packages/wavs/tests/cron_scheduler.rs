@@ -283,3 +283,97 @@ fn test_scheduler_returns_scheduled_time_not_current_time() {
         "Returned time should be before current tick time"
     );
 }
+
+#[test]
+fn test_jitter_delays_firing_but_keeps_scheduled_time() {
+    let jitter = std::time::Duration::from_millis(500);
+    let mut scheduler = IntervalScheduler::<Timestamp, CronIntervalState>::new();
+    let state = make_state(1, "0 0 12 * * *", None, None)
+        .unwrap()
+        .with_jitter(jitter);
+    scheduler.add_trigger(state).unwrap();
+
+    // Initialize at 11:59:59 so the next tick is exactly noon
+    let base_time = make_timestamp(
+        Utc::now()
+            .with_hour(11)
+            .unwrap()
+            .with_minute(59)
+            .unwrap()
+            .with_second(59)
+            .unwrap()
+            .with_nanosecond(0)
+            .unwrap(),
+    );
+    assert!(scheduler.tick(base_time).is_empty());
+
+    let noon = Timestamp::from_nanos(
+        base_time.as_nanos() + std::time::Duration::from_secs(1).as_nanos() as u64,
+    );
+
+    // Nothing fires on the tick itself
+    assert!(scheduler.tick(noon).is_empty());
+
+    // Fires once the jitter has elapsed, but reports the original tick
+    let after_jitter = Timestamp::from_nanos(noon.as_nanos() + jitter.as_nanos() as u64);
+    let results = scheduler.tick(after_jitter);
+    assert_eq!(results, vec![(1, noon)]);
+}
+
+#[test]
+fn test_missed_ticks_fire_once_for_latest_tick() {
+    let mut scheduler = IntervalScheduler::<Timestamp, CronIntervalState>::new();
+    scheduler
+        .add_trigger(make_state(1, "* * * * * *", None, None).unwrap())
+        .unwrap();
+
+    let base_time = make_timestamp(Utc::now().with_nanosecond(0).unwrap());
+    assert!(scheduler.tick(base_time).is_empty());
+
+    // The scheduler falls five seconds behind
+    let late = Timestamp::from_nanos(
+        base_time.as_nanos() + std::time::Duration::from_secs(5).as_nanos() as u64,
+    );
+    let hits = scheduler.tick_hits(late);
+    assert_eq!(hits.len(), 1, "Missed ticks should not fire in a burst");
+    assert_eq!(hits[0].lookup_id, 1);
+    assert_eq!(hits[0].scheduled_time, late);
+    assert_eq!(hits[0].missed_ticks, 4);
+
+    // Back on schedule, nothing was missed
+    let on_time = Timestamp::from_nanos(
+        late.as_nanos() + std::time::Duration::from_secs(1).as_nanos() as u64,
+    );
+    let hits = scheduler.tick_hits(on_time);
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].scheduled_time, on_time);
+    assert_eq!(hits[0].missed_ticks, 0);
+}
+
+#[test]
+fn test_jitter_offset_is_deterministic_and_bounded() {
+    use wavs::subsystems::trigger::schedulers::cron_scheduler::CronJitter;
+    use wavs_types::{Credential, ServiceId, WorkflowId};
+
+    let window = std::time::Duration::from_secs(10);
+    let service_id = ServiceId::hash(b"service");
+    let workflow_id = WorkflowId::default();
+
+    let operator_1 = CronJitter::new(&Credential::new("operator one".to_string()), window);
+    let operator_2 = CronJitter::new(&Credential::new("operator two".to_string()), window);
+
+    let offset_1 = operator_1.offset(&service_id, &workflow_id);
+    assert_eq!(offset_1, operator_1.offset(&service_id, &workflow_id));
+    assert!(offset_1 < window);
+    assert!(operator_2.offset(&service_id, &workflow_id) < window);
+    assert_ne!(offset_1, operator_2.offset(&service_id, &workflow_id));
+
+    let disabled = CronJitter::new(
+        &Credential::new("operator one".to_string()),
+        std::time::Duration::ZERO,
+    );
+    assert_eq!(
+        disabled.offset(&service_id, &workflow_id),
+        std::time::Duration::ZERO
+    );
+}
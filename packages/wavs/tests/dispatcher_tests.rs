@@ -1,5 +1,7 @@
 #![cfg(feature = "dev")]
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use alloy_sol_types::SolValue;
 use example_types::{SquareRequest, SquareResponse};
@@ -7,6 +9,7 @@ use utils::init_tracing_tests;
 use utils::test_utils::test_contracts::ISimpleSubmit::DataWithId;
 use utils::{
     context::AppContext,
+    storage::s3::S3Config,
     test_utils::{
         address::{rand_address_cosmos, rand_address_evm},
         mock_engine::COMPONENT_SQUARE_BYTES,
@@ -14,8 +17,8 @@ use utils::{
 };
 use wavs::dispatcher::DispatcherCommand;
 use wavs_types::{
-    Component, ComponentSource, Service, ServiceManager, ServiceStatus, SignatureKind, Submit,
-    Workflow, WorkflowId,
+    Component, ComponentSource, Credential, Service, ServiceManager, ServiceStatus, SignatureKind,
+    Submit, Workflow, WorkflowId,
 };
 mod wavs_systems;
 use wavs_systems::{
@@ -55,7 +58,14 @@ fn dispatcher_pipeline() {
                 submit: Submit::Aggregator {
                     component: Box::new(Component::new(ComponentSource::Digest(digest))),
                     signature_kind: SignatureKind::evm_default(),
+                    targets: Vec::new(),
                 },
+                submit_targets: Vec::new(),
+                response_mapping: Default::default(),
+                submit_condition: None,
+                priority: Default::default(),
+                observe_only: false,
+                min_interval_ms: None,
             },
         )]
         .into(),
@@ -64,6 +74,8 @@ fn dispatcher_pipeline() {
             chain: "evm:anvil".parse().unwrap(),
             address: rand_address_evm(),
         },
+        max_concurrent_executions: None,
+        execution_timeout_secs: None,
     };
 
     let contract_address = rand_address_cosmos();
@@ -123,3 +135,181 @@ fn dispatcher_pipeline() {
         assert_eq!(data_2, SquareResponse::new(9));
     }
 }
+
+/// Services deploying the same component bytes share one stored copy,
+/// which is only dropped when the last of them is removed
+#[test]
+fn shared_component_outlives_one_service() {
+    init_tracing_tests();
+
+    let data_dir = tempfile::tempdir().unwrap();
+    let ctx = AppContext::new();
+    let dispatcher = MockE2ETestRunner::create_dispatcher(ctx.clone(), &data_dir);
+
+    let digest = dispatcher
+        .engine_manager
+        .engine
+        .store_component_bytes(COMPONENT_SQUARE_BYTES)
+        .unwrap();
+
+    let service_for = |name: &str| Service {
+        name: name.to_string(),
+        workflows: [(
+            WorkflowId::default(),
+            Workflow {
+                component: Component::new(ComponentSource::Digest(digest.clone())),
+                trigger: mock_cosmos_event_trigger(),
+                submit: Submit::None,
+                submit_targets: Vec::new(),
+                response_mapping: Default::default(),
+                submit_condition: None,
+                priority: Default::default(),
+                observe_only: false,
+                min_interval_ms: None,
+            },
+        )]
+        .into(),
+        status: ServiceStatus::Active,
+        manager: ServiceManager::Evm {
+            chain: "evm:anvil".parse().unwrap(),
+            address: rand_address_evm(),
+        },
+        max_concurrent_executions: None,
+        execution_timeout_secs: None,
+    };
+    let service_1 = service_for("Square 1");
+    let service_2 = service_for("Square 2");
+
+    ctx.rt.block_on(async {
+        dispatcher
+            .add_service_direct(service_1.clone())
+            .await
+            .unwrap();
+        dispatcher
+            .add_service_direct(service_2.clone())
+            .await
+            .unwrap();
+    });
+
+    // stored once for both services
+    let digests = dispatcher.engine_manager.engine.list_digests().unwrap();
+    assert_eq!(digests, vec![digest.clone()]);
+
+    // still in use by the second service
+    dispatcher.remove_service(service_1.id()).unwrap();
+    let digests = dispatcher.engine_manager.engine.list_digests().unwrap();
+    assert_eq!(digests, vec![digest]);
+
+    dispatcher.remove_service(service_2.id()).unwrap();
+    assert!(dispatcher
+        .engine_manager
+        .engine
+        .list_digests()
+        .unwrap()
+        .is_empty());
+}
+
+/// Shutdown waits for executions already under way before stopping the subsystems
+#[test]
+fn dispatcher_drains_in_flight_work_on_shutdown() {
+    init_tracing_tests();
+
+    let data_dir = tempfile::tempdir().unwrap();
+    let ctx = AppContext::new();
+    let dispatcher = Arc::new(MockE2ETestRunner::create_dispatcher(ctx.clone(), &data_dir));
+
+    let finished = Arc::new(AtomicBool::new(false));
+
+    // stands in for a long-running execution
+    let execution = std::thread::spawn({
+        let guard = dispatcher.in_flight.start();
+        let finished = finished.clone();
+        move || {
+            std::thread::sleep(Duration::from_millis(1500));
+            finished.store(true, Ordering::SeqCst);
+            drop(guard);
+        }
+    });
+
+    let (stopped_tx, stopped_rx) = std::sync::mpsc::channel();
+    std::thread::spawn({
+        let dispatcher = dispatcher.clone();
+        let ctx = ctx.clone();
+        move || {
+            dispatcher.start(ctx).unwrap();
+            stopped_tx.send(()).unwrap();
+        }
+    });
+
+    std::thread::sleep(Duration::from_millis(500));
+    ctx.kill();
+
+    stopped_rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert!(finished.load(Ordering::SeqCst));
+    assert_eq!(dispatcher.in_flight.count(), 0);
+    execution.join().unwrap();
+}
+
+/// Work that outlasts the shutdown timeout doesn't hold up shutdown
+#[test]
+fn dispatcher_shutdown_times_out() {
+    init_tracing_tests();
+
+    let data_dir = tempfile::tempdir().unwrap();
+    let ctx = AppContext::new();
+    let mut dispatcher = MockE2ETestRunner::create_dispatcher(ctx.clone(), &data_dir);
+    dispatcher.shutdown_timeout = Duration::from_millis(500);
+    let dispatcher = Arc::new(dispatcher);
+
+    // never finishes
+    let _stuck = dispatcher.in_flight.start();
+
+    let (stopped_tx, stopped_rx) = std::sync::mpsc::channel();
+    std::thread::spawn({
+        let dispatcher = dispatcher.clone();
+        let ctx = ctx.clone();
+        move || {
+            dispatcher.start(ctx).unwrap();
+            stopped_tx.send(()).unwrap();
+        }
+    });
+
+    std::thread::sleep(Duration::from_millis(500));
+    ctx.kill();
+
+    stopped_rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(dispatcher.in_flight.count(), 1);
+}
+
+/// With `ca_s3` set, components go to the bucket instead of `<data>/ca`
+#[test]
+fn dispatcher_uses_configured_s3_storage() {
+    init_tracing_tests();
+
+    let data_dir = tempfile::tempdir().unwrap();
+    let config = wavs::config::Config {
+        signing_mnemonic: Some(Credential::new(
+            "test test test test test test test test test test test junk".to_string(),
+        )),
+        data: data_dir.path().to_path_buf(),
+        ca_s3: Some(S3Config {
+            // nothing listens here, and nothing needs to until a component is stored
+            endpoint: "http://127.0.0.1:1".to_string(),
+            bucket: "components".to_string(),
+            region: "us-east-1".to_string(),
+            access_key_id: Credential::new("test".to_string()),
+            secret_access_key: Credential::new("test".to_string()),
+            prefix: String::new(),
+        }),
+        ..wavs::config::Config::default()
+    };
+    let dispatcher = MockE2ETestRunner::create_dispatcher_with_config(&config);
+
+    // object storage can't list what it holds, unlike the local store
+    let err = dispatcher.engine_manager.engine.list_digests().unwrap_err();
+    assert!(
+        err.to_string().contains("not supported on object storage"),
+        "{err}"
+    );
+    assert!(!data_dir.path().join("ca").exists());
+}
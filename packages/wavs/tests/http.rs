@@ -1,20 +1,48 @@
 #![cfg(feature = "dev")]
+use alloy_sol_types::SolValue;
 use axum::{
     body::Body,
     http::{Method, Request},
 };
+use example_types::{SquareRequest, SquareResponse};
+use http_body_util::BodyExt;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use layer_climb::prelude::EvmAddr;
 use tower::Service;
-use utils::test_utils::{address::rand_address_evm, mock_engine::COMPONENT_SQUARE_BYTES};
+use utils::{
+    context::AppContext,
+    test_utils::{
+        address::rand_address_evm, mock_engine::COMPONENT_SQUARE_BYTES,
+        test_contracts::ISimpleSubmit::DataWithId,
+    },
+};
 use wavs::config::Config;
 mod wavs_systems;
-use wavs::health::HealthStatus;
+use wavs::{
+    dispatcher::DispatcherCommand,
+    health::HealthStatus,
+    subsystems::{
+        aggregator::{peer::Peer, AggregatorCommand},
+        engine::AggregatorExecuteKind,
+    },
+};
 use wavs_systems::{
     http::{map_response, TestHttpApp},
+    mock_app::MockE2ETestRunner,
+    mock_service::mock_service,
+    mock_submissions::{mock_submission_request, wait_for_submission_messages},
     mock_trigger_manager::mock_evm_event_trigger,
 };
 use wavs_types::{
-    AnyChainConfig, ChainKey, Component, ComponentDigest, ComponentSource, CosmosChainConfig,
-    EvmChainConfig, SignatureKind, UploadComponentResponse,
+    AggregatorAction, AnyChainConfig, ChainKey, Component, ComponentDigest, ComponentSource,
+    CosmosChainConfig, Credential, ErrorResponse, EvmChainConfig, EvmChainConfigBuilder,
+    EvmSubmitAction, ListServicesResponse, QueuedSubmission, QuorumQueueId, QuorumQueueStatus,
+    ServiceEvent, ServiceEventKind, ServiceId, ServiceSummary, SignatureKind, SubmissionProvenance,
+    SubmitAction, Trigger, UploadComponentResponse,
 };
 
 #[test]
@@ -33,6 +61,71 @@ fn http_not_found() {
     });
 
     assert_eq!(response.status(), 404);
+    let body: ErrorResponse = app.ctx.rt.block_on(map_response(response));
+    assert_eq!(body.code, "not_found");
+}
+
+#[test]
+fn http_error_bodies() {
+    let app = TestHttpApp::new();
+    let get = |uri: String| {
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.clone().ctx.rt.block_on({
+            let mut app = app.clone();
+            async move { app.http_router().await.call(req).await.unwrap() }
+        });
+        let status = response.status();
+        assert_eq!(
+            response.headers()["content-type"].to_str().unwrap(),
+            "application/json"
+        );
+        let body: ErrorResponse = app.ctx.rt.block_on(map_response(response));
+        (status, body)
+    };
+
+    let service = mock_service();
+
+    // the service isn't saved
+    let (status, body) = get(format!("/dev/services/{}", service.hash().unwrap()));
+    assert_eq!(status, 404);
+    assert_eq!(body.code, "not_found");
+
+    let (status, body) = get(format!(
+        "/services/{}/workflows/default/status",
+        service.id()
+    ));
+    assert_eq!(status, 404);
+    assert_eq!(body.code, "not_found");
+    assert!(body.error.contains(&service.id().to_string()));
+
+    let (status, body) = get("/services/not-an-id/workflows/default/status".to_string());
+    assert_eq!(status, 400);
+    assert_eq!(body.code, "bad_request");
+
+    let (status, body) = get("/services/not-an-id/events".to_string());
+    assert_eq!(status, 400);
+    assert_eq!(body.code, "bad_request");
+
+    let (status, body) = get(format!(
+        "/services/{}/workflows/NOT_VALID/status",
+        service.id()
+    ));
+    assert_eq!(status, 400);
+    assert_eq!(body.code, "bad_request");
+
+    let (status, body) = get("/dev/services/not-a-hash".to_string());
+    assert_eq!(status, 400);
+    assert_eq!(body.code, "bad_request");
+
+    let (status, body) = get("/aggregator/queues/not-hex".to_string());
+    assert_eq!(status, 400);
+    assert_eq!(body.code, "bad_request");
+    assert_eq!(body.error, "Invalid queue id: not-hex");
 }
 
 #[test]
@@ -120,6 +213,7 @@ fn http_save_service() {
                 ComponentDigest::hash([1, 2, 3]),
             ))),
             signature_kind: SignatureKind::evm_default(),
+            targets: Vec::new(),
         },
         wavs_types::ServiceManager::Evm {
             chain: "evm:anvil".try_into().unwrap(),
@@ -187,6 +281,7 @@ fn create_test_evm_chain_config() -> AnyChainConfig {
         chain_id: "1337".parse().unwrap(),
         ws_endpoints: vec!["wss://localhost:8546".to_string()],
         http_endpoint: Some("http://localhost:8545".to_string()),
+        http_endpoints: Vec::new(),
         faucet_endpoint: None,
         ws_priority_endpoint_index: None,
     })
@@ -363,6 +458,8 @@ fn test_add_chain_prevents_duplicates() {
         500,
         "Duplicate chain addition should fail with 500"
     );
+    let body: ErrorResponse = app.ctx.rt.block_on(map_response(response2));
+    assert_eq!(body.code, "internal_error");
 }
 
 #[test]
@@ -407,3 +504,623 @@ fn body_size_limit() {
         "16MB body should be rejected with 413 Payload Too Large"
     );
 }
+
+#[test]
+fn http_quorum_queues() {
+    let ctx = AppContext::new();
+    let temp_data_dir = tempfile::tempdir().unwrap();
+    let dispatcher = Arc::new(MockE2ETestRunner::create_dispatcher(
+        ctx.clone(),
+        &temp_data_dir,
+    ));
+
+    let service = mock_service();
+    dispatcher.services.save(&service).unwrap();
+    dispatcher
+        .submission_manager
+        .add_service_key(service.id(), None)
+        .unwrap();
+
+    // the same partial queue, waiting on two different contracts
+    let queue_ids: Vec<QuorumQueueId> = ctx.rt.block_on(async {
+        let req = mock_submission_request(&service, "message");
+        let submission = dispatcher
+            .submission_manager
+            .sign_request(&req)
+            .await
+            .unwrap();
+
+        let mut queue_ids = Vec::new();
+        for address in [EvmAddr::new([1u8; 20]), EvmAddr::new([2u8; 20])] {
+            let queue_id = QuorumQueueId {
+                event_id: submission.event_id.clone(),
+                action: SubmitAction::Evm(EvmSubmitAction {
+                    chain: "evm:anvil".parse().unwrap(),
+                    address,
+                    gas_price: None,
+                    eip1559: None,
+                }),
+            };
+            dispatcher
+                .aggregator
+                .save_quorum_queue(
+                    queue_id.clone(),
+                    vec![QueuedSubmission {
+                        submission: submission.clone(),
+                        provenance: SubmissionProvenance {
+                            signer: alloy_primitives::Address::ZERO,
+                            peer: Some("peer-1".to_string()),
+                            received_at: 0,
+                            weight: None,
+                        },
+                    }],
+                )
+                .await
+                .unwrap();
+            queue_ids.push(queue_id);
+        }
+        queue_ids
+    });
+
+    let app = TestHttpApp::new_with_dispatcher(ctx, dispatcher, Some(temp_data_dir));
+    let get = |uri: String| {
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+
+        app.clone().ctx.rt.block_on({
+            let mut app = app.clone();
+            async move { app.http_router().await.call(req).await.unwrap() }
+        })
+    };
+
+    let response = get(format!("/aggregator/queues?service_id={}", service.id()));
+    assert!(response.status().is_success());
+    let queues: Vec<QuorumQueueStatus> = app.ctx.rt.block_on(map_response(response));
+    assert_eq!(queues.len(), 2);
+    for queue in &queues {
+        assert_eq!(queue.service_id, Some(service.id()));
+        assert_eq!(queue.collected, 1);
+        assert!(queue.age_secs.unwrap() > 0);
+        // never checked against the service manager
+        assert_eq!(queue.quorum, None);
+        assert_eq!(queue.entries[0].peer.as_deref(), Some("peer-1"));
+    }
+
+    // paging through one at a time
+    let response = get("/aggregator/queues?offset=1&limit=1".to_string());
+    let page: Vec<QuorumQueueStatus> = app.ctx.rt.block_on(map_response(response));
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].id, queues[1].id);
+
+    let response = get("/aggregator/queues?offset=2".to_string());
+    let page: Vec<QuorumQueueStatus> = app.ctx.rt.block_on(map_response(response));
+    assert!(page.is_empty());
+
+    // a single queue, by the id the list reported
+    let response = get(format!("/aggregator/queues/{}", queues[0].id));
+    assert!(response.status().is_success());
+    let queue: QuorumQueueStatus = app.ctx.rt.block_on(map_response(response));
+    assert_eq!(queue.id, queues[0].id);
+    assert_eq!(queue.collected, 1);
+    assert!(queue_ids
+        .iter()
+        .any(|queue_id| queue_id.action == queue.action));
+
+    let unknown = QuorumQueueId {
+        event_id: queue_ids[0].event_id.clone(),
+        action: SubmitAction::Evm(EvmSubmitAction {
+            chain: "evm:anvil".parse().unwrap(),
+            address: EvmAddr::new([3u8; 20]),
+            gas_price: None,
+            eip1559: None,
+        }),
+    };
+    let response = get(format!(
+        "/aggregator/queues/{}",
+        const_hex::encode(unknown.to_bytes().unwrap())
+    ));
+    assert_eq!(response.status(), 404);
+
+    let response = get("/aggregator/queues/not-hex".to_string());
+    assert_eq!(response.status(), 400);
+}
+
+#[test]
+fn http_quorum_queue_provenance() {
+    let ctx = AppContext::new();
+    let temp_data_dir = tempfile::tempdir().unwrap();
+
+    let mnemonic =
+        Credential::new("test test test test test test test test test test test junk".to_string());
+    let mut config = Config {
+        signing_mnemonic: Some(mnemonic.clone()),
+        aggregator_evm_credential: Some(mnemonic),
+        data: temp_data_dir.path().to_path_buf(),
+        ..Config::default()
+    };
+    // nothing listens there, the submission fails and the queue is kept
+    let chain: ChainKey = "evm:31337".parse().unwrap();
+    config.chains.write().unwrap().evm.insert(
+        chain.id.clone(),
+        EvmChainConfigBuilder {
+            ws_endpoints: Vec::new(),
+            http_endpoint: Some("http://127.0.0.1:1".to_string()),
+            http_endpoints: Vec::new(),
+            faucet_endpoint: None,
+            ws_priority_endpoint_index: None,
+        },
+    );
+    config.aggregator.submit_max_attempts = Some(1);
+
+    let dispatcher = Arc::new(MockE2ETestRunner::create_dispatcher_with_config(&config));
+    std::thread::spawn({
+        let dispatcher = dispatcher.clone();
+        let ctx = ctx.clone();
+        move || dispatcher.aggregator.start(ctx)
+    });
+
+    let service = mock_service();
+    dispatcher.services.save(&service).unwrap();
+    dispatcher
+        .submission_manager
+        .add_service_key(service.id(), None)
+        .unwrap();
+
+    let (submission, other_submission) = ctx.rt.block_on(async {
+        let req = mock_submission_request(&service, "message");
+        let submission = dispatcher
+            .submission_manager
+            .sign_request(&req)
+            .await
+            .unwrap();
+
+        // salted, so it's a different event
+        let mut req = mock_submission_request(&service, "message");
+        req.operator_response.event_id_salt = Some(b"other".to_vec());
+        let other_submission = dispatcher
+            .submission_manager
+            .sign_request(&req)
+            .await
+            .unwrap();
+
+        (submission, other_submission)
+    });
+    let signer = submission
+        .envelope_signature
+        .evm_signer_address(&submission.envelope)
+        .unwrap();
+
+    // an unrelated queue, left out when filtering by event id
+    ctx.rt.block_on(async {
+        dispatcher
+            .aggregator
+            .save_quorum_queue(
+                QuorumQueueId {
+                    event_id: other_submission.event_id.clone(),
+                    action: SubmitAction::Evm(EvmSubmitAction {
+                        chain: chain.clone(),
+                        address: EvmAddr::new([2u8; 20]),
+                        gas_price: None,
+                        eip1559: None,
+                    }),
+                },
+                vec![QueuedSubmission {
+                    submission: other_submission.clone(),
+                    provenance: SubmissionProvenance {
+                        signer,
+                        peer: None,
+                        received_at: 0,
+                        weight: None,
+                    },
+                }],
+            )
+            .await
+            .unwrap();
+    });
+
+    // received from a peer, the provenance is recorded before the aggregator component runs
+    dispatcher
+        .dispatcher_to_aggregator_tx
+        .send(AggregatorCommand::Receive {
+            submission: submission.clone(),
+            peer: Peer::Other("peer-x".to_string()),
+        })
+        .unwrap();
+    let command = dispatcher
+        .subsystem_to_dispatcher_rx
+        .recv_timeout(Duration::from_secs(10))
+        .unwrap();
+    assert!(matches!(
+        command,
+        DispatcherCommand::AggregatorExecute {
+            kind: AggregatorExecuteKind::Standard,
+            ..
+        }
+    ));
+
+    let action = SubmitAction::Evm(EvmSubmitAction {
+        chain: chain.clone(),
+        address: EvmAddr::new([1u8; 20]),
+        gas_price: None,
+        eip1559: None,
+    });
+    dispatcher
+        .dispatcher_to_aggregator_tx
+        .send(AggregatorCommand::Actions {
+            submission: submission.clone(),
+            actions: vec![AggregatorAction::Submit(action.clone())],
+            kind: AggregatorExecuteKind::Standard,
+        })
+        .unwrap();
+
+    let app = TestHttpApp::new_with_dispatcher(ctx, dispatcher.clone(), Some(temp_data_dir));
+    let get = |uri: String| {
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+
+        app.clone().ctx.rt.block_on({
+            let mut app = app.clone();
+            async move { app.http_router().await.call(req).await.unwrap() }
+        })
+    };
+
+    let uri = format!("/aggregator/queues?event_id={}", submission.event_id);
+    let end = Instant::now() + Duration::from_secs(30);
+    let queues = loop {
+        let response = get(uri.clone());
+        assert!(response.status().is_success());
+        let queues: Vec<QuorumQueueStatus> = app.ctx.rt.block_on(map_response(response));
+        if !queues.is_empty() {
+            break queues;
+        }
+        assert!(Instant::now() < end, "timed out waiting for the queue");
+        std::thread::sleep(Duration::from_millis(50));
+    };
+
+    assert_eq!(queues.len(), 1);
+    let queue = &queues[0];
+    assert_eq!(queue.event_id, submission.event_id);
+    assert_eq!(queue.action, action);
+    assert_eq!(queue.burned_at, None);
+    assert_eq!(queue.collected, 1);
+    assert_eq!(queue.entries.len(), 1);
+    assert_eq!(queue.entries[0].signer, signer);
+    assert_eq!(queue.entries[0].peer.as_deref(), Some("peer-x"));
+    assert!(queue.entries[0].received_at > 0);
+    // operators aren't verified, so the weight was never looked up
+    assert_eq!(queue.entries[0].weight, None);
+
+    // both queues without the filter
+    let response = get(format!("/aggregator/queues?service_id={}", service.id()));
+    let queues: Vec<QuorumQueueStatus> = app.ctx.rt.block_on(map_response(response));
+    assert_eq!(queues.len(), 2);
+
+    dispatcher
+        .dispatcher_to_aggregator_tx
+        .send(AggregatorCommand::Kill)
+        .unwrap();
+}
+
+#[test]
+fn http_list_and_get_services() {
+    let ctx = AppContext::new();
+    let temp_data_dir = tempfile::tempdir().unwrap();
+    let dispatcher = Arc::new(MockE2ETestRunner::create_dispatcher(
+        ctx.clone(),
+        &temp_data_dir,
+    ));
+
+    let app = TestHttpApp::new_with_dispatcher(ctx, dispatcher.clone(), Some(temp_data_dir));
+    let get = |uri: String, if_none_match: Option<&str>| {
+        let mut req = Request::builder().method(Method::GET).uri(uri);
+        if let Some(etag) = if_none_match {
+            req = req.header("If-None-Match", etag);
+        }
+        let req = req.body(Body::empty()).unwrap();
+
+        app.clone().ctx.rt.block_on({
+            let mut app = app.clone();
+            async move { app.http_router().await.call(req).await.unwrap() }
+        })
+    };
+    let etag = |response: &axum::http::Response<Body>| {
+        response.headers()["etag"].to_str().unwrap().to_string()
+    };
+
+    // empty registry
+    let response = get("/services".to_string(), None);
+    assert!(response.status().is_success());
+    let empty_etag = etag(&response);
+    let list: ListServicesResponse = app.ctx.rt.block_on(map_response(response));
+    assert_eq!(list.total, 0);
+    assert!(list.services.is_empty());
+    assert!(list.summaries.is_empty());
+
+    // polling with the etag is cheap while nothing changed
+    let response = get("/services".to_string(), Some(&empty_etag));
+    assert_eq!(response.status(), 304);
+
+    // a single service
+    let service = mock_service();
+    dispatcher.services.save(&service).unwrap();
+
+    let response = get("/services".to_string(), Some(&empty_etag));
+    assert!(response.status().is_success());
+    assert_ne!(etag(&response), empty_etag);
+    let list: ListServicesResponse = app.ctx.rt.block_on(map_response(response));
+    assert_eq!(list.total, 1);
+    assert_eq!(list.service_ids, vec![service.id()]);
+    assert_eq!(
+        list.summaries,
+        vec![ServiceSummary {
+            service_id: service.id(),
+            name: service.name.clone(),
+            workflows: 1,
+            trigger_kinds: vec!["manual".to_string()],
+        }]
+    );
+
+    let response = get("/services?offset=1".to_string(), None);
+    let list: ListServicesResponse = app.ctx.rt.block_on(map_response(response));
+    assert_eq!(list.total, 1);
+    assert!(list.summaries.is_empty());
+
+    let response = get(format!("/services/{}", service.id()), None);
+    assert!(response.status().is_success());
+    let service_etag = etag(&response);
+    let fetched: wavs_types::Service = app.ctx.rt.block_on(map_response(response));
+    assert_eq!(fetched, service);
+
+    let response = get(format!("/services/{}", service.id()), Some(&service_etag));
+    assert_eq!(response.status(), 304);
+
+    // not found
+    let response = get(format!("/services/{}", ServiceId::hash(b"missing")), None);
+    assert_eq!(response.status(), 404);
+    let body: ErrorResponse = app.ctx.rt.block_on(map_response(response));
+    assert_eq!(body.code, "not_found");
+
+    let response = get("/services/not-an-id".to_string(), None);
+    assert_eq!(response.status(), 400);
+}
+
+#[test]
+fn http_webhook_trigger() {
+    let ctx = AppContext::new();
+    let runner = MockE2ETestRunner::new(ctx.clone());
+
+    let digest = runner
+        .dispatcher
+        .engine_manager
+        .engine
+        .store_component_bytes(COMPONENT_SQUARE_BYTES)
+        .unwrap();
+
+    let service = wavs_types::Service::new_simple(
+        Some("Webhook square".to_string()),
+        Trigger::Webhook {
+            signature_secret: None,
+        },
+        ComponentSource::Digest(digest.clone()),
+        wavs_types::Submit::Aggregator {
+            component: Box::new(Component::new(ComponentSource::Digest(digest))),
+            signature_kind: SignatureKind::evm_default(),
+            targets: Vec::new(),
+        },
+        wavs_types::ServiceManager::Evm {
+            chain: "evm:anvil".try_into().unwrap(),
+            address: rand_address_evm(),
+        },
+    );
+    let workflow_id = service.workflows.keys().next().unwrap().clone();
+    ctx.rt.block_on(async {
+        runner
+            .dispatcher
+            .add_service_direct(service.clone())
+            .await
+            .unwrap();
+    });
+
+    let post = |uri: String, body: Vec<u8>| {
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(uri)
+            .body(Body::from(body))
+            .unwrap();
+
+        ctx.rt.block_on({
+            let mut app = runner.http_app.clone();
+            async move { app.http_router().await.call(req).await.unwrap() }
+        })
+    };
+
+    // the body reaches the component as webhook trigger data
+    let response = post(
+        format!("/trigger/{}/{}", service.id(), workflow_id),
+        serde_json::to_vec(&SquareRequest::new(3)).unwrap(),
+    );
+    assert_eq!(response.status(), 202);
+
+    wait_for_submission_messages(&runner.dispatcher.submission_manager, 1, None).unwrap();
+    let processed = runner.dispatcher.submission_manager.get_debug_submissions();
+    let payload = DataWithId::abi_decode(&processed[0].envelope.payload).unwrap();
+    let response: SquareResponse = serde_json::from_slice(&payload.data).unwrap();
+    assert_eq!(response, SquareResponse::new(9));
+
+    // unknown workflows
+    let response = post(format!("/trigger/{}/missing", service.id()), b"{}".to_vec());
+    assert_eq!(response.status(), 404);
+
+    // malformed ids
+    let response = post("/trigger/not-an-id/default".to_string(), b"{}".to_vec());
+    assert_eq!(response.status(), 400);
+    let response = post(
+        format!("/trigger/{}/NOT_VALID", service.id()),
+        b"{}".to_vec(),
+    );
+    assert_eq!(response.status(), 400);
+}
+
+#[test]
+fn http_webhook_trigger_requires_webhook_workflow() {
+    let ctx = AppContext::new();
+    let temp_data_dir = tempfile::tempdir().unwrap();
+    let dispatcher = Arc::new(MockE2ETestRunner::create_dispatcher(
+        ctx.clone(),
+        &temp_data_dir,
+    ));
+
+    // triggered manually, not by webhooks
+    let service = mock_service();
+    dispatcher.services.save(&service).unwrap();
+    let workflow_id = service.workflows.keys().next().unwrap().clone();
+
+    let app = TestHttpApp::new_with_dispatcher(ctx, dispatcher, Some(temp_data_dir));
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri(format!("/trigger/{}/{}", service.id(), workflow_id))
+        .body(Body::from("{}"))
+        .unwrap();
+
+    let response = app.clone().ctx.rt.block_on({
+        let mut app = app.clone();
+        async move { app.http_router().await.call(req).await.unwrap() }
+    });
+
+    assert_eq!(response.status(), 400);
+    let body: ErrorResponse = app.ctx.rt.block_on(map_response(response));
+    assert_eq!(body.code, "bad_request");
+}
+
+#[test]
+fn http_service_events() {
+    let ctx = AppContext::new();
+    let runner = MockE2ETestRunner::new(ctx.clone());
+
+    let digest = runner
+        .dispatcher
+        .engine_manager
+        .engine
+        .store_component_bytes(COMPONENT_SQUARE_BYTES)
+        .unwrap();
+
+    let service = wavs_types::Service::new_simple(
+        Some("Evented square".to_string()),
+        Trigger::Webhook {
+            signature_secret: None,
+        },
+        ComponentSource::Digest(digest),
+        wavs_types::Submit::None,
+        wavs_types::ServiceManager::Evm {
+            chain: "evm:anvil".try_into().unwrap(),
+            address: rand_address_evm(),
+        },
+    );
+    let workflow_id = service.workflows.keys().next().unwrap().clone();
+    ctx.rt.block_on(async {
+        runner
+            .dispatcher
+            .add_service_direct(service.clone())
+            .await
+            .unwrap();
+    });
+
+    let call = |req: Request<Body>| {
+        ctx.rt.block_on({
+            let mut app = runner.http_app.clone();
+            async move { app.http_router().await.call(req).await.unwrap() }
+        })
+    };
+
+    // subscribe before anything happens
+    let response = call(
+        Request::builder()
+            .method(Method::GET)
+            .uri(format!("/services/{}/events", service.id()))
+            .body(Body::empty())
+            .unwrap(),
+    );
+    assert!(response.status().is_success());
+    assert_eq!(
+        response.headers()["content-type"].to_str().unwrap(),
+        "text/event-stream"
+    );
+    let mut body = response.into_body();
+
+    let response = call(
+        Request::builder()
+            .method(Method::POST)
+            .uri(format!("/trigger/{}/{}", service.id(), workflow_id))
+            .body(Body::from(
+                serde_json::to_vec(&SquareRequest::new(3)).unwrap(),
+            ))
+            .unwrap(),
+    );
+    assert_eq!(response.status(), 202);
+
+    let events: Vec<ServiceEvent> = ctx.rt.block_on(async {
+        let mut events = Vec::new();
+        while !events
+            .iter()
+            .any(|event: &ServiceEvent| event.kind.name() == "execution_result")
+        {
+            let frame = tokio::time::timeout(std::time::Duration::from_secs(10), body.frame())
+                .await
+                .expect("timed out waiting for events")
+                .unwrap()
+                .unwrap();
+            let Ok(data) = frame.into_data() else {
+                continue;
+            };
+            for line in String::from_utf8(data.to_vec()).unwrap().lines() {
+                if let Some(json) = line.strip_prefix("data: ") {
+                    events.push(serde_json::from_str(json).unwrap());
+                }
+            }
+        }
+        events
+    });
+
+    let kinds: Vec<&str> = events.iter().map(|event| event.kind.name()).collect();
+    assert_eq!(
+        kinds,
+        vec!["trigger_received", "execution_started", "execution_result"]
+    );
+    assert!(events.iter().all(|event| event.service_id == service.id()));
+    assert_eq!(
+        events[0].kind,
+        ServiceEventKind::TriggerReceived {
+            trigger_type: "webhook".to_string()
+        }
+    );
+    assert!(matches!(
+        events[2].kind,
+        ServiceEventKind::ExecutionResult { error: None, .. }
+    ));
+
+    // reconnecting after the first event replays the rest
+    let response = call(
+        Request::builder()
+            .method(Method::GET)
+            .uri(format!("/services/{}/events", service.id()))
+            .header("Last-Event-ID", events[0].id.to_string())
+            .body(Body::empty())
+            .unwrap(),
+    );
+    let mut body = response.into_body();
+    let replayed = ctx.rt.block_on(async {
+        let frame = tokio::time::timeout(std::time::Duration::from_secs(10), body.frame())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        String::from_utf8(frame.into_data().unwrap().to_vec()).unwrap()
+    });
+    assert!(replayed.contains("event: execution_started"));
+}
@@ -223,6 +223,8 @@ fn mock_e2e_same_tx_different_block_hash() {
         address: contract_address,
         chain: chain.clone(),
         event_hash,
+        additional_event_hashes: Vec::new(),
+        transform: Vec::new(),
     };
 
     let service = Service {
@@ -235,7 +237,14 @@ fn mock_e2e_same_tx_different_block_hash() {
                 submit: Submit::Aggregator {
                     component: Box::new(Component::new(ComponentSource::Digest(digest.clone()))),
                     signature_kind: SignatureKind::evm_default(),
+                    targets: Vec::new(),
                 },
+                submit_targets: Vec::new(),
+                response_mapping: Default::default(),
+                submit_condition: None,
+                priority: Default::default(),
+                observe_only: false,
+                min_interval_ms: None,
             },
         )]
         .into(),
@@ -244,6 +253,8 @@ fn mock_e2e_same_tx_different_block_hash() {
             chain: chain.clone(),
             address: rand_address_evm(),
         },
+        max_concurrent_executions: None,
+        execution_timeout_secs: None,
     };
 
     runner.ctx.rt.block_on({
@@ -62,6 +62,12 @@ fn db_service_store() {
                     b"digest-1",
                 ))),
                 submit: Submit::None,
+                submit_targets: Vec::new(),
+                response_mapping: Default::default(),
+                submit_condition: None,
+                priority: Default::default(),
+                observe_only: false,
+                min_interval_ms: None,
             },
         ),
         (
@@ -72,6 +78,12 @@ fn db_service_store() {
                     b"digest-2",
                 ))),
                 submit: Submit::None,
+                submit_targets: Vec::new(),
+                response_mapping: Default::default(),
+                submit_condition: None,
+                priority: Default::default(),
+                observe_only: false,
+                min_interval_ms: None,
             },
         ),
     ]
@@ -85,6 +97,8 @@ fn db_service_store() {
             chain: "evm:anvil".parse().unwrap(),
             address: rand_address_evm(),
         },
+        max_concurrent_executions: None,
+        execution_timeout_secs: None,
     };
 
     storage
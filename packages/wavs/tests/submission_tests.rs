@@ -1,7 +1,18 @@
 #![cfg(feature = "dev")]
-use std::time::Duration;
+use std::{
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
 
-use wavs::subsystems::submission::SubmissionCommand;
+use axum::{http::HeaderMap, http::StatusCode, routing::post, Router};
+use wavs::subsystems::{
+    engine::{operator_responses, EngineResponse},
+    submission::{data::SubmissionRequest, target, SubmissionCommand},
+};
+use wavs_types::{Submit, SubmitTarget, WorkflowId};
 
 use utils::{context::AppContext, telemetry::Metrics};
 
@@ -80,3 +91,310 @@ fn collect_messages_with_wait() {
     wait_for_submission_messages(&submission_manager, 4, Some(Duration::from_millis(300)))
         .unwrap_err();
 }
+
+fn ordered_request(
+    service: &wavs_types::Service,
+    payload: &str,
+    ordering: u64,
+) -> SubmissionRequest {
+    let mut req = mock_submission_request(service, payload);
+    req.operator_response.ordering = Some(ordering);
+    req.operator_response.event_id_salt = Some(payload.as_bytes().to_vec());
+    req
+}
+
+fn submitted_payloads(
+    submission_manager: &wavs::subsystems::submission::SubmissionManager,
+) -> Vec<String> {
+    submission_manager
+        .get_debug_submissions()
+        .into_iter()
+        .map(|x| String::from_utf8(x.operator_response.payload).unwrap())
+        .collect()
+}
+
+#[test]
+fn ordered_responses_are_grouped_and_sorted() {
+    let service = mock_service();
+
+    let mut unordered = mock_submission_request(&service, "unordered");
+    unordered.operator_response.event_id_salt = Some(b"unordered".to_vec());
+
+    // the component can return them in any order, interleaved with unordered ones
+    let responses = operator_responses(vec![
+        ordered_request(&service, "third", 30),
+        unordered,
+        ordered_request(&service, "first", 1),
+        ordered_request(&service, "second", 2),
+    ]);
+
+    assert_eq!(responses.len(), 2);
+    match &responses[0] {
+        EngineResponse::Operator(req) => {
+            assert_eq!(req.operator_response.payload, b"unordered".to_vec())
+        }
+        other => panic!("expected an unordered response, got {other:?}"),
+    }
+    match &responses[1] {
+        EngineResponse::OperatorSequence(reqs) => assert_eq!(
+            reqs.iter()
+                .map(|req| req.operator_response.ordering.unwrap())
+                .collect::<Vec<_>>(),
+            vec![1, 2, 30]
+        ),
+        other => panic!("expected an ordered sequence, got {other:?}"),
+    }
+}
+
+#[test]
+fn submit_sequence_in_order() {
+    let ctx = AppContext::new();
+    let channels = TestChannels::new();
+    let services = mock_services();
+    let service = mock_service();
+    let metrics = Metrics::new(opentelemetry::global::meter("wavs_metrics"));
+
+    services.save(&service).unwrap();
+
+    let submission_manager =
+        mock_submission_manager(ctx.clone(), &metrics, &mock_config(), &channels, services);
+
+    submission_manager
+        .add_service_key(service.id(), None)
+        .unwrap();
+
+    channels
+        .dispatcher_to_submission_tx
+        .send(SubmissionCommand::SubmitSequence(vec![
+            ordered_request(&service, "first", 1),
+            ordered_request(&service, "second", 2),
+            ordered_request(&service, "third", 3),
+        ]))
+        .unwrap();
+    wait_for_submission_messages(&submission_manager, 3, None).unwrap();
+
+    let end = Instant::now() + Duration::from_secs(5);
+    while submission_manager.metrics.get_dispatch_count() < 3 && Instant::now() < end {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    assert_eq!(
+        submitted_payloads(&submission_manager),
+        vec!["first", "second", "third"]
+    );
+}
+
+#[test]
+fn submit_sequence_stops_at_failure() {
+    let ctx = AppContext::new();
+    let channels = TestChannels::new();
+    let services = mock_services();
+    let service = mock_service();
+    let metrics = Metrics::new(opentelemetry::global::meter("wavs_metrics"));
+
+    services.save(&service).unwrap();
+
+    let submission_manager =
+        mock_submission_manager(ctx.clone(), &metrics, &mock_config(), &channels, services);
+
+    submission_manager
+        .add_service_key(service.id(), None)
+        .unwrap();
+
+    // can't be signed, since the service has no such workflow
+    let mut broken = ordered_request(&service, "second", 2);
+    broken.trigger_action.config.workflow_id = WorkflowId::new("missing").unwrap();
+
+    channels
+        .dispatcher_to_submission_tx
+        .send(SubmissionCommand::SubmitSequence(vec![
+            ordered_request(&service, "first", 1),
+            broken,
+            ordered_request(&service, "third", 3),
+        ]))
+        .unwrap();
+    wait_for_submission_messages(&submission_manager, 2, None).unwrap();
+
+    // the third is held back for good, rather than going out ahead of the second
+    wait_for_submission_messages(&submission_manager, 3, Some(Duration::from_millis(300)))
+        .unwrap_err();
+    assert_eq!(submitted_payloads(&submission_manager), vec!["first"]);
+    assert_eq!(submission_manager.metrics.get_dispatch_count(), 1);
+}
+
+#[test]
+fn delivers_to_http_submit_target_with_retries() {
+    let ctx = AppContext::new();
+    let channels = TestChannels::new();
+    let services = mock_services();
+    let metrics = Metrics::new(opentelemetry::global::meter("wavs_metrics"));
+
+    // the first attempt fails, so the delivery is only recorded after a retry
+    let attempts = Arc::new(AtomicU32::new(0));
+    let received_headers = Arc::new(Mutex::new(None::<HeaderMap>));
+
+    let listener = ctx
+        .rt
+        .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+        .unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let router = Router::new().route(
+        "/sink",
+        post({
+            let attempts = attempts.clone();
+            let received_headers = received_headers.clone();
+            move |headers: HeaderMap| {
+                let attempts = attempts.clone();
+                let received_headers = received_headers.clone();
+                async move {
+                    if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                        return StatusCode::SERVICE_UNAVAILABLE;
+                    }
+                    *received_headers.lock().unwrap() = Some(headers);
+                    StatusCode::OK
+                }
+            }
+        }),
+    );
+    ctx.rt.spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+
+    let mut service = mock_service();
+    for workflow in service.workflows.values_mut() {
+        workflow.submit = Submit::None;
+        workflow.submit_targets = vec![SubmitTarget::Http {
+            url: format!("http://{addr}/sink"),
+            auth: None,
+        }];
+    }
+    services.save(&service).unwrap();
+
+    let submission_manager =
+        mock_submission_manager(ctx.clone(), &metrics, &mock_config(), &channels, services);
+
+    submission_manager
+        .add_service_key(service.id(), None)
+        .unwrap();
+
+    channels
+        .dispatcher_to_submission_tx
+        .send(SubmissionCommand::Submit(mock_submission_request(
+            &service, "foo",
+        )))
+        .unwrap();
+
+    let end = Instant::now() + Duration::from_secs(5);
+    while submission_manager.metrics.get_target_delivery_count() == 0 && Instant::now() < end {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    assert_eq!(submission_manager.metrics.get_target_delivery_count(), 1);
+    assert_eq!(
+        submission_manager.metrics.get_target_delivery_error_count(),
+        0
+    );
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    // Submit::None never reaches the aggregator
+    assert_eq!(submission_manager.metrics.get_dispatch_count(), 0);
+
+    let headers = received_headers.lock().unwrap().clone().unwrap();
+    let signer = submission_manager.get_service_signer(service.id()).unwrap();
+    let wavs_types::SignerResponse::Secp256k1 { evm_address, .. } = signer;
+    // without an aggregator the result carries just this operator's signature
+    assert_eq!(
+        headers
+            .get(target::SIGNERS_HEADER)
+            .unwrap()
+            .to_str()
+            .unwrap(),
+        evm_address
+    );
+    assert!(headers
+        .get(target::SIGNATURES_HEADER)
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .starts_with("0x"));
+
+    let deliveries = submission_manager
+        .target_deliverer()
+        .list(Some(&service.id()), None);
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].target, format!("http://{addr}/sink"));
+    assert_eq!(deliveries[0].signatures, 1);
+    assert_eq!(deliveries[0].error, None);
+}
+
+#[test]
+fn observe_only_skips_http_submit_targets() {
+    let ctx = AppContext::new();
+    let channels = TestChannels::new();
+    let services = mock_services();
+    let metrics = Metrics::new(opentelemetry::global::meter("wavs_metrics"));
+
+    let hits = Arc::new(AtomicU32::new(0));
+
+    let listener = ctx
+        .rt
+        .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+        .unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let router = Router::new().route(
+        "/sink",
+        post({
+            let hits = hits.clone();
+            move || {
+                let hits = hits.clone();
+                async move {
+                    hits.fetch_add(1, Ordering::SeqCst);
+                    StatusCode::OK
+                }
+            }
+        }),
+    );
+    ctx.rt.spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+
+    let mut service = mock_service();
+    for workflow in service.workflows.values_mut() {
+        workflow.submit = Submit::None;
+        workflow.observe_only = true;
+        workflow.submit_targets = vec![SubmitTarget::Http {
+            url: format!("http://{addr}/sink"),
+            auth: None,
+        }];
+    }
+    services.save(&service).unwrap();
+
+    let submission_manager =
+        mock_submission_manager(ctx.clone(), &metrics, &mock_config(), &channels, services);
+
+    submission_manager
+        .add_service_key(service.id(), None)
+        .unwrap();
+
+    channels
+        .dispatcher_to_submission_tx
+        .send(SubmissionCommand::Submit(mock_submission_request(
+            &service, "foo",
+        )))
+        .unwrap();
+
+    let end = Instant::now() + Duration::from_secs(5);
+    while submission_manager.metrics.get_sign_count() == 0 && Instant::now() < end {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    assert_eq!(submission_manager.metrics.get_sign_count(), 1);
+
+    // the result is signed as usual, but never posted
+    std::thread::sleep(Duration::from_millis(500));
+    assert_eq!(hits.load(Ordering::SeqCst), 0);
+    assert_eq!(submission_manager.metrics.get_target_delivery_count(), 0);
+    assert!(submission_manager
+        .target_deliverer()
+        .list(Some(&service.id()), None)
+        .is_empty());
+}
@@ -185,7 +185,14 @@ async fn block_interval_trigger_is_removed_when_config_is_gone() {
                         ComponentDigest::hash([1, 2, 3]),
                     ))),
                     signature_kind: SignatureKind::evm_default(),
+                    targets: Vec::new(),
                 },
+                submit_targets: Vec::new(),
+                response_mapping: Default::default(),
+                submit_condition: None,
+                priority: Default::default(),
+                observe_only: false,
+                min_interval_ms: None,
             },
         )]
         .into(),
@@ -194,6 +201,8 @@ async fn block_interval_trigger_is_removed_when_config_is_gone() {
             chain: chain.clone(),
             address: rand_address_evm(),
         },
+        max_concurrent_executions: None,
+        execution_timeout_secs: None,
     };
     services.save(&service).unwrap();
 
@@ -59,6 +59,7 @@ fn zeroed_cli_args(
         dotenv: Some(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("non-existant-file")),
         port: None,
         log_level: Vec::new(),
+        log_format: None,
         host: None,
         cors_allowed_origins: Vec::new(),
         wasm_lru_size: None,
@@ -71,6 +72,7 @@ fn zeroed_cli_args(
         ipfs_gateway: None,
         submission_poll_interval_ms: None,
         bearer_token: None,
+        health_signing_key: None,
         max_body_size_mb: None,
         dev_endpoints_enabled: None,
         #[cfg(feature = "dev")]
@@ -83,5 +85,9 @@ fn zeroed_cli_args(
         jetstream_endpoint: None,
         jetstream_max_message_size: None,
         hyperswarm_bootstrap: None,
+        cron_jitter_secs: None,
+        warmup_components: None,
+        warmup_parallelism: None,
+        evm_backfill: None,
     }
 }
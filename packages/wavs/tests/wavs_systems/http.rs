@@ -3,7 +3,7 @@ use std::sync::Arc;
 use axum::body::Body;
 use http_body_util::BodyExt;
 use serde::de::DeserializeOwned;
-use utils::{context::AppContext, storage::fs::FileStorage, telemetry::HttpMetrics};
+use utils::{context::AppContext, storage::any::AnyStorage, telemetry::HttpMetrics};
 
 use wavs::dispatcher::Dispatcher;
 
@@ -40,7 +40,7 @@ impl TestHttpApp {
 
     pub fn new_with_dispatcher(
         ctx: AppContext,
-        dispatcher: Arc<Dispatcher<FileStorage>>,
+        dispatcher: Arc<Dispatcher<AnyStorage>>,
         temp_data_dir: Option<tempfile::TempDir>,
     ) -> Self {
         let inner = TestApp::new();
@@ -4,8 +4,14 @@ use std::{
 };
 
 use thiserror::Error;
-use utils::{context::AppContext, telemetry::Metrics};
-use wavs::{config::Config, services::Services, subsystems::aggregator::Aggregator};
+use utils::{
+    chain_throttle::ChainThrottle, context::AppContext, telemetry::Metrics, tls::ChainTls,
+};
+use wavs::{
+    config::Config,
+    services::Services,
+    subsystems::{aggregator::Aggregator, submission::target::TargetDeliverer},
+};
 
 use crate::wavs_systems::channels::TestChannels;
 
@@ -16,16 +22,48 @@ pub fn mock_aggregator(
     channels: &TestChannels,
     services: Services,
 ) -> Aggregator {
-    let aggregator = Aggregator::new(
+    start_mock_aggregator(
+        ctx,
+        new_mock_aggregator(metrics, config, channels, services),
+    )
+}
+
+/// Like [`mock_aggregator`], posting on-chain results to submit targets through `target_deliverer`
+pub fn mock_aggregator_with_deliverer(
+    ctx: AppContext,
+    metrics: &Metrics,
+    config: &Config,
+    channels: &TestChannels,
+    services: Services,
+    target_deliverer: TargetDeliverer,
+) -> Aggregator {
+    start_mock_aggregator(
+        ctx,
+        new_mock_aggregator(metrics, config, channels, services)
+            .with_target_deliverer(target_deliverer),
+    )
+}
+
+fn new_mock_aggregator(
+    metrics: &Metrics,
+    config: &Config,
+    channels: &TestChannels,
+    services: Services,
+) -> Aggregator {
+    Aggregator::new(
         config,
         metrics.wavs.aggregator.clone(),
+        ChainThrottle::unlimited(),
+        ChainTls::default(),
         services,
         channels.dispatcher_to_aggregator_rx.clone(),
         channels.dispatcher_to_aggregator_tx.clone(),
         channels.subsystem_to_dispatcher_tx.clone(),
     )
-    .unwrap();
+    .unwrap()
+}
 
+fn start_mock_aggregator(ctx: AppContext, aggregator: Aggregator) -> Aggregator {
     assert_eq!(aggregator.metrics.get_broadcast_count(), 0);
     assert_eq!(aggregator.metrics.get_receive_count(), 0);
     assert_eq!(aggregator.metrics.get_submit_count(), 0);
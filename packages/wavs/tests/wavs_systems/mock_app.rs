@@ -11,7 +11,7 @@ use tracing::instrument;
 use utils::{
     context::AppContext,
     service::DEFAULT_IPFS_GATEWAY,
-    storage::{fs::FileStorage, memory::MemoryStorage},
+    storage::{any::AnyStorage, memory::MemoryStorage},
     telemetry::{EngineMetrics, Metrics},
 };
 use utils::{storage::db::WavsDb, test_utils::address::rand_address_evm};
@@ -29,7 +29,7 @@ use super::mock_trigger_manager::{mock_evm_event_trigger, mock_real_trigger_acti
 
 pub struct MockE2ETestRunner {
     pub ctx: AppContext,
-    pub dispatcher: Arc<Dispatcher<FileStorage>>,
+    pub dispatcher: Arc<Dispatcher<AnyStorage>>,
     pub temp_data_dir: tempfile::TempDir,
     pub http_app: TestHttpApp,
 }
@@ -62,7 +62,7 @@ impl MockE2ETestRunner {
     pub fn create_dispatcher(
         _ctx: AppContext,
         data_dir: impl AsRef<std::path::Path>,
-    ) -> Dispatcher<FileStorage> {
+    ) -> Dispatcher<AnyStorage> {
         let config = wavs::config::Config {
             signing_mnemonic: Some(Credential::new(
                 "test test test test test test test test test test test junk".to_string(),
@@ -70,10 +70,14 @@ impl MockE2ETestRunner {
             data: data_dir.as_ref().to_path_buf(),
             ..wavs::config::Config::default()
         };
+        Self::create_dispatcher_with_config(&config)
+    }
+
+    pub fn create_dispatcher_with_config(config: &wavs::config::Config) -> Dispatcher<AnyStorage> {
         let meter = opentelemetry::global::meter("wavs_metrics");
         let metrics = Metrics::new(meter);
 
-        let mut dispatcher = Dispatcher::new(&config, metrics.wavs).unwrap();
+        let mut dispatcher = Dispatcher::new(config, metrics.wavs).unwrap();
         dispatcher.trigger_manager.disable_networking = true;
         dispatcher.submission_manager.disable_networking = true;
         dispatcher
@@ -158,6 +162,7 @@ impl MockE2ETestRunner {
             // just use the same component for submit for simplicity
             component: Box::new(Component::new(component_source.clone())),
             signature_kind: SignatureKind::evm_default(),
+            targets: Vec::new(),
         };
 
         let service = Service::new_simple(
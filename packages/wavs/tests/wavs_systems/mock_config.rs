@@ -6,6 +6,7 @@ pub fn mock_config() -> Config {
         signing_mnemonic: Some(Credential::new(
             "test test test test test test test test test test test junk".to_string(),
         )),
+        data: tempfile::tempdir().unwrap().keep(),
         ..wavs::config::Config::default()
     }
 }
@@ -27,10 +27,19 @@ pub fn mock_service() -> Service {
                         ComponentDigest::hash([0; 32]),
                     ))),
                     signature_kind: SignatureKind::evm_default(),
+                    targets: Vec::new(),
                 },
+                submit_targets: Vec::new(),
+                response_mapping: Default::default(),
+                submit_condition: None,
+                priority: Default::default(),
+                observe_only: false,
+                min_interval_ms: None,
             },
         )]
         .into_iter()
         .collect(),
+        max_concurrent_executions: None,
+        execution_timeout_secs: None,
     }
 }
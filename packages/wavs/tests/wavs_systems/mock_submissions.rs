@@ -67,6 +67,13 @@ pub fn mock_submission_request(service: &Service, payload: &str) -> SubmissionRe
             event_id_salt: None,
             ordering: None,
         },
+        submit_targets: service
+            .workflows
+            .values()
+            .next()
+            .unwrap()
+            .submit_targets
+            .clone(),
         service: service.clone(),
         debug: SubmissionRequestDebug {
             do_not_submit_aggregator: true,